@@ -10,9 +10,16 @@ export interface Diagnostic {
   start?: number;
   length?: number;
 }
+/** The time spent in a phase of the compilation. */
+export interface PhaseTiming {
+  phase: string;
+  durationMs: number;
+}
 export interface CompileResult {
   code: string;
   diagnostics: Array<Diagnostic>;
+  /** Time spent in each phase, when timings are collected. */
+  timings?: Array<PhaseTiming>;
 }
 export interface FileEntry {
   filename: string;
@@ -25,6 +32,18 @@ export interface BatchEntryResult {
 }
 export declare class Compiler {
   constructor();
+  /**
+   * Collect the time spent in each phase, reported in the `timings` of compile results and by
+   * `link_timings`.
+   */
+  setCollectTimings(enabled: boolean): void;
+  /** Time spent linking libraries so far, when timings are collected. */
+  linkTimings(): Array<PhaseTiming> | null;
+  /**
+   * Set the minimum level of the compiler logs written to the console: `"debug"`, `"info"`,
+   * `"warn"`, `"error"`, or `"silent"` to disable them.
+   */
+  setLogLevel(level: string): void;
   compile(filename: string, content: string): CompileResult;
   linkFile(filename: string, sourceCode: string): string;
   compileBatch(files: Array<FileEntry>): Array<BatchEntryResult>;
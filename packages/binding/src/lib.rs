@@ -7,14 +7,17 @@ use angular_compiler_cli::ngtsc::file_system::src::types::{
 };
 use angular_compiler_cli::ngtsc::file_system::FileSystem;
 use angular_compiler_cli::ngtsc::file_system::ReadonlyFileSystem;
+use angular_compiler_cli::ngtsc::logging::{ConsoleLogger, LogLevel, Logger, SilentLogger};
+use angular_compiler_cli::ngtsc::perf::{PerfPhase, PerfRecorder};
 use angular_compiler_cli::ngtsc::program::NgtscProgram;
+use angular_compiler_cli::ngtsc::resource::FileResourceLoader;
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use xxhash_rust::xxh3::xxh3_64;
 
 // ============ Cache Configuration ============
@@ -112,17 +115,25 @@ struct CachedDiagnostic {
     length: Option<u32>,
 }
 
+/// Loads component templates and stylesheets from disk, reading each resource once per
+/// compilation.
+fn resource_loader() -> Arc<FileResourceLoader<NodeJSFileSystem>> {
+    Arc::new(FileResourceLoader::new(Arc::new(NodeJSFileSystem::new())))
+}
+
 /// A FileSystem that reads from disk (NodeJS) but captures writes in memory.
 struct CapturingFileSystem {
     delegate: NodeJSFileSystem,
     captured_files: Mutex<HashMap<AbsoluteFsPath, Vec<u8>>>,
+    logger: Arc<dyn Logger + Send + Sync>,
 }
 
 impl CapturingFileSystem {
-    fn new() -> Self {
+    fn new(logger: Arc<dyn Logger + Send + Sync>) -> Self {
         CapturingFileSystem {
             delegate: NodeJSFileSystem::new(),
             captured_files: Mutex::new(HashMap::new()),
+            logger,
         }
     }
 }
@@ -215,7 +226,8 @@ impl FileSystem for CapturingFileSystem {
         _exclusive: Option<bool>,
     ) -> io::Result<()> {
         let mut captured = self.captured_files.lock().unwrap();
-        eprintln!("[Rust Binding] Writing to memory: {}", path);
+        self.logger
+            .debug(&format!("[Rust Binding] Writing to memory: {}", path));
         captured.insert(path.clone(), data.to_vec());
         Ok(())
     }
@@ -252,16 +264,46 @@ pub struct Diagnostic {
     pub length: Option<u32>,
 }
 
+/// The time spent in a phase of the compilation.
+#[napi(object)]
+pub struct PhaseTiming {
+    pub phase: String,
+    pub duration_ms: f64,
+}
+
+impl PhaseTiming {
+    fn from_perf(timings: Vec<angular_compiler_cli::ngtsc::perf::PhaseTiming>) -> Vec<Self> {
+        timings
+            .into_iter()
+            .map(|timing| PhaseTiming {
+                phase: timing.phase.name().to_string(),
+                duration_ms: timing.duration.as_secs_f64() * 1000.0,
+            })
+            .collect()
+    }
+}
+
 #[napi(object)]
 pub struct CompileResult {
     pub code: String,
     pub diagnostics: Vec<Diagnostic>,
+    /// Time spent in each phase, when timings are collected.
+    pub timings: Option<Vec<PhaseTiming>>,
 }
 
 #[napi]
 pub struct Compiler {
     compiler_cache_dir: PathBuf,
     linker_cache_dir: PathBuf,
+    collect_timings: bool,
+    /// Whether compiled files end with their source map, see `set_inline_source_map`.
+    inline_source_map: bool,
+    /// Whether templates are compiled for development tooling, see `set_debug_locations`.
+    debug_locations: bool,
+    /// Time spent linking by `link_file`, when timings are collected.
+    link_perf: PerfRecorder,
+    /// Where compiler logs are reported, the Node console by default.
+    logger: Arc<dyn Logger + Send + Sync>,
 }
 
 #[napi]
@@ -271,17 +313,80 @@ impl Compiler {
         let compiler_cache_dir = get_cache_dir(COMPILER_CACHE_SUBDIR);
         let linker_cache_dir = get_cache_dir(LINKER_CACHE_SUBDIR);
 
-        eprintln!(
+        let logger: Arc<dyn Logger + Send + Sync> = Arc::new(ConsoleLogger::new(LogLevel::Info));
+        logger.info(&format!(
             "[Rust NGC] Cache dir: {}",
             compiler_cache_dir.parent().unwrap().display()
-        );
+        ));
 
         Compiler {
             compiler_cache_dir,
             linker_cache_dir,
+            collect_timings: false,
+            inline_source_map: false,
+            debug_locations: false,
+            link_perf: PerfRecorder::noop(),
+            logger,
         }
     }
 
+    /// Set the minimum level of the compiler logs written to the console: `"debug"`, `"info"`,
+    /// `"warn"`, `"error"`, or `"silent"` to disable them.
+    #[napi]
+    pub fn set_log_level(&mut self, level: String) -> napi::Result<()> {
+        let level = match level.as_str() {
+            "debug" => LogLevel::Debug,
+            "info" => LogLevel::Info,
+            "warn" => LogLevel::Warn,
+            "error" => LogLevel::Error,
+            "silent" => {
+                self.logger = Arc::new(SilentLogger::new());
+                return Ok(());
+            }
+            _ => {
+                return Err(napi::Error::from_reason(format!(
+                    "Unknown log level: {}",
+                    level
+                )))
+            }
+        };
+        self.logger = Arc::new(ConsoleLogger::new(level));
+        Ok(())
+    }
+
+    /// Collect the time spent in each phase, reported in the `timings` of compile results and by
+    /// `link_timings`.
+    #[napi]
+    pub fn set_collect_timings(&mut self, enabled: bool) {
+        self.collect_timings = enabled;
+        self.link_perf = if enabled {
+            PerfRecorder::new()
+        } else {
+            PerfRecorder::noop()
+        };
+    }
+
+    /// End the code of compile results with its source map, in an inline `sourceMappingURL`
+    /// comment, so that each result is a self-contained file.
+    #[napi]
+    pub fn set_inline_source_map(&mut self, enabled: bool) {
+        self.inline_source_map = enabled;
+    }
+
+    /// Attach the template location of each element to the DOM, so that development tooling
+    /// can map rendered nodes back to their definition.
+    #[napi]
+    pub fn set_debug_locations(&mut self, enabled: bool) {
+        self.debug_locations = enabled;
+    }
+
+    /// Time spent linking libraries so far, when timings are collected.
+    #[napi]
+    pub fn link_timings(&self) -> Option<Vec<PhaseTiming>> {
+        self.collect_timings
+            .then(|| PhaseTiming::from_perf(self.link_perf.finalize()))
+    }
+
     /// Read cached compile result from disk
     fn read_compiler_cache(&self, _hash: &str) -> Option<CompileResult> {
         // CACHE DISABLED TEMPORARILY
@@ -316,7 +421,7 @@ impl Compiler {
         }
 
         // 3. Setup Capturing FileSystem
-        let fs = CapturingFileSystem::new();
+        let fs = CapturingFileSystem::new(self.logger.clone());
         let abs_filename_str = fs.resolve(&[&filename]).to_string();
         let abs_filename = AbsoluteFsPath::from(Path::new(&abs_filename_str));
         fs.write_file(&abs_filename, content.as_bytes(), None).ok();
@@ -325,10 +430,15 @@ impl Compiler {
         let mut options = NgCompilerOptions::default();
         options.project = abs_filename_str.clone();
         options.out_dir = Some(fs.dirname(&abs_filename_str));
+        options.trace_performance = self.collect_timings;
+        options.inline_source_map = self.inline_source_map;
+        options.enable_debug_locations = self.debug_locations;
 
         // 5. Create Program
         let root_names = vec![abs_filename_str.clone()];
-        let mut program = NgtscProgram::new(root_names, options, &fs);
+        let mut program = NgtscProgram::new(root_names, options, &fs)
+            .with_logger(self.logger.clone())
+            .with_resource_loader(resource_loader());
 
         // 6. Load NG Structure
         let mut diagnostics = Vec::new();
@@ -336,6 +446,7 @@ impl Compiler {
             return CompileResult {
                 code: format!("/* Error loading: {} */", e),
                 diagnostics: vec![],
+                timings: None,
             };
         }
 
@@ -356,6 +467,7 @@ impl Compiler {
                 return CompileResult {
                     code: format!("/* Error emitting: {} */", e),
                     diagnostics: vec![],
+                    timings: None,
                 };
             }
         }
@@ -371,7 +483,14 @@ impl Compiler {
             }
         };
 
-        let result = CompileResult { code, diagnostics };
+        let timings = self
+            .collect_timings
+            .then(|| PhaseTiming::from_perf(program.get_perf_timings()));
+        let result = CompileResult {
+            code,
+            diagnostics,
+            timings,
+        };
 
         // 9. Write to cache
         self.write_compiler_cache(&hash, &result);
@@ -392,7 +511,10 @@ impl Compiler {
         // 3. Link
         use angular_compiler_cli::linker::napi::link_file;
 
-        let result = match link_file(source_code, filename) {
+        let linked = self
+            .link_perf
+            .in_phase(PerfPhase::Link, || link_file(source_code, filename));
+        let result = match linked {
             Ok(code) => code,
             Err(e) => format!("/* Linker Error: {} */", e),
         };
@@ -412,7 +534,7 @@ impl Compiler {
         }
 
         // 1. Setup Capturing FileSystem & Root Names
-        let fs = CapturingFileSystem::new();
+        let fs = CapturingFileSystem::new(self.logger.clone());
         let mut root_names = Vec::new();
         let mut file_map = HashMap::new();
 
@@ -429,7 +551,11 @@ impl Compiler {
         }
 
         // 2. Setup Compiler Options
-        let mut options = NgCompilerOptions::default();
+        let mut options = NgCompilerOptions {
+            inline_source_map: self.inline_source_map,
+            enable_debug_locations: self.debug_locations,
+            ..NgCompilerOptions::default()
+        };
         if !root_names.is_empty() {
             // Find common root
             let mut common_root = fs.dirname(&root_names[0]);
@@ -442,7 +568,8 @@ impl Compiler {
                     common_root = parent;
                 }
             }
-            eprintln!("[Rust Binding] Common root: {}", common_root);
+            self.logger
+                .debug(&format!("[Rust Binding] Common root: {}", common_root));
             options.out_dir = Some(common_root.clone());
             options.root_dir = Some(common_root.clone());
             options.project = root_names[0].clone();
@@ -450,7 +577,9 @@ impl Compiler {
         // Ensure we compile all inputs - default behavior is sufficient
 
         // 3. Create Program (ONCE)
-        let mut program = NgtscProgram::new(root_names.clone(), options, &fs);
+        let mut program = NgtscProgram::new(root_names.clone(), options, &fs)
+            .with_logger(self.logger.clone())
+            .with_resource_loader(resource_loader());
 
         // 4. Load NG Structure
         let mut global_diagnostics = Vec::new();
@@ -518,7 +647,10 @@ impl Compiler {
             let code = match fs.read_file(&output_path) {
                 Ok(c) => Some(c),
                 Err(_) => {
-                    eprintln!("[Rust Binding] Failed to read from memory: {}", output_path);
+                    self.logger.warn(&format!(
+                        "[Rust Binding] Failed to read from memory: {}",
+                        output_path
+                    ));
                     None
                 }
             };
@@ -545,7 +677,8 @@ impl Compiler {
                 .map(|p| p.to_string_lossy().to_string())
                 .collect(),
             Err(e) => {
-                eprintln!("Failed to resolve dependencies: {}", e);
+                self.logger
+                    .error(&format!("Failed to resolve dependencies: {}", e));
                 vec![]
             }
         }
@@ -592,7 +725,7 @@ impl Compiler {
                 files: res.files,
             },
             Err(e) => {
-                eprintln!("Bundle Error: {}", e);
+                self.logger.error(&format!("Bundle Error: {}", e));
                 NapiBundleResult {
                     bundle_js: format!("/* Bundle Error: {} */", e),
                     styles_css: None,
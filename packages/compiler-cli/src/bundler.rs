@@ -2,8 +2,12 @@ use crate::compile::parallel::parallel_compile;
 use crate::config::angular::{AngularConfig, BuildOptions};
 use anyhow::Result;
 use glob::glob;
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{ExportDefaultDeclarationKind, ImportDeclarationSpecifier, Statement};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 
 pub struct BundleResult {
     pub bundle_js: String,
@@ -195,3 +199,275 @@ pub fn bundle_project(project_path: &Path) -> Result<BundleResult> {
         files: files_map,
     })
 }
+
+/// Concatenate in-memory ES modules into a single module, starting from `entry`.
+///
+/// Modules reached through relative imports are inlined before their importers and lose their
+/// `export`s; only the exports of `entry` remain. Imports of other packages are hoisted to the
+/// top and deduplicated. Calls to Angular's `ɵɵdefine*` functions are annotated
+/// `/*@__PURE__*/` so that minifiers can drop the definitions of unused classes.
+///
+/// Modules share one scope, so top-level names must not collide across modules.
+pub fn bundle_modules(modules: &HashMap<String, String>, entry: &str) -> Result<String> {
+    let mut order = Vec::new();
+    collect_modules(modules, entry, &mut HashSet::new(), &mut order)?;
+
+    let mut external_imports: Vec<String> = Vec::new();
+    let mut default_names: HashMap<&str, String> = HashMap::new();
+    let mut body = String::new();
+    for path in &order {
+        let source = &modules[path.as_str()];
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+        if !ret.errors.is_empty() {
+            anyhow::bail!("Failed to parse {}: {}", path, ret.errors[0]);
+        }
+        let text = |span: Span| &source[span.start as usize..span.end as usize];
+        let is_entry = path == entry;
+
+        body.push_str(&format!("// {}\n", path));
+        for stmt in &ret.program.body {
+            match stmt {
+                Statement::ImportDeclaration(import) => {
+                    let specifier = import.source.value.as_str();
+                    let Some(resolved) = resolve_import(modules, path, specifier) else {
+                        let import = text(import.span).to_string();
+                        if !external_imports.contains(&import) {
+                            external_imports.push(import);
+                        }
+                        continue;
+                    };
+                    // The imported bindings are in scope already; only renames need a binding.
+                    for spec in import.specifiers.iter().flatten() {
+                        let (imported, local) = match spec {
+                            ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                                (spec.imported.name().to_string(), &spec.local.name)
+                            }
+                            ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
+                                let Some(name) = default_names.get(resolved.as_str()) else {
+                                    anyhow::bail!("{} has no default export", resolved);
+                                };
+                                (name.clone(), &spec.local.name)
+                            }
+                            ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => {
+                                anyhow::bail!(
+                                    "Namespace imports of bundled modules are not supported: {}",
+                                    text(import.span)
+                                );
+                            }
+                        };
+                        if imported != local.as_str() {
+                            body.push_str(&format!("const {} = {};\n", local, imported));
+                        }
+                    }
+                }
+                Statement::ExportAllDeclaration(export) => {
+                    let specifier = export.source.value.as_str();
+                    if is_entry && resolve_import(modules, path, specifier).is_none() {
+                        body.push_str(text(export.span));
+                        body.push('\n');
+                    }
+                }
+                Statement::ExportNamedDeclaration(export) if !is_entry => {
+                    if let Some(decl) = &export.declaration {
+                        body.push_str(text(decl.span()));
+                        body.push('\n');
+                    }
+                }
+                Statement::ExportNamedDeclaration(export)
+                    if export.source.as_ref().is_some_and(|source| {
+                        resolve_import(modules, path, source.value.as_str()).is_some()
+                    }) =>
+                {
+                    // Re-exported bindings are inlined; export them by name.
+                    let names: Vec<&str> = export
+                        .specifiers
+                        .iter()
+                        .map(|spec| text(spec.span))
+                        .collect();
+                    body.push_str(&format!("export {{ {} }};\n", names.join(", ")));
+                }
+                Statement::ExportDefaultDeclaration(export) if !is_entry => {
+                    let name = match &export.declaration {
+                        ExportDefaultDeclarationKind::ClassDeclaration(class)
+                            if class.id.is_some() =>
+                        {
+                            body.push_str(text(class.span));
+                            class.id.as_ref().unwrap().name.to_string()
+                        }
+                        ExportDefaultDeclarationKind::FunctionDeclaration(func)
+                            if func.id.is_some() =>
+                        {
+                            body.push_str(text(func.span));
+                            func.id.as_ref().unwrap().name.to_string()
+                        }
+                        decl => {
+                            let name = format!("{}_default", module_identifier(path));
+                            body.push_str(&format!("const {} = {};", name, text(decl.span())));
+                            name
+                        }
+                    };
+                    body.push('\n');
+                    default_names.insert(path.as_str(), name);
+                }
+                _ => {
+                    body.push_str(text(stmt.span()));
+                    body.push('\n');
+                }
+            }
+        }
+    }
+
+    let mut bundle = String::new();
+    for import in &external_imports {
+        bundle.push_str(import);
+        bundle.push('\n');
+    }
+    bundle.push_str(&annotate_pure_definitions(&body));
+    Ok(bundle)
+}
+
+/// Add the modules `path` depends on, and then `path` itself, to `order`.
+fn collect_modules(
+    modules: &HashMap<String, String>,
+    path: &str,
+    visited: &mut HashSet<String>,
+    order: &mut Vec<String>,
+) -> Result<()> {
+    if !visited.insert(path.to_string()) {
+        return Ok(());
+    }
+    let Some(source) = modules.get(path) else {
+        anyhow::bail!("Module {} is not part of the bundle", path);
+    };
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source, SourceType::mjs()).parse();
+    for stmt in &ret.program.body {
+        let specifier = match stmt {
+            Statement::ImportDeclaration(import) => Some(&import.source),
+            Statement::ExportAllDeclaration(export) => Some(&export.source),
+            Statement::ExportNamedDeclaration(export) => export.source.as_ref(),
+            _ => None,
+        };
+        if let Some(resolved) =
+            specifier.and_then(|specifier| resolve_import(modules, path, &specifier.value))
+        {
+            collect_modules(modules, &resolved, visited, order)?;
+        }
+    }
+    order.push(path.to_string());
+    Ok(())
+}
+
+/// Resolve a relative import of `importer` to one of `modules`, trying the `.js` and
+/// `/index.js` suffixes Node resolution would.
+fn resolve_import(
+    modules: &HashMap<String, String>,
+    importer: &str,
+    specifier: &str,
+) -> Option<String> {
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return None;
+    }
+    let mut resolved = PathBuf::new();
+    for component in Path::new(importer)
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(specifier)
+        .components()
+    {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+    let resolved = resolved.to_string_lossy().replace('\\', "/");
+    [
+        resolved.clone(),
+        format!("{}.js", resolved),
+        format!("{}.mjs", resolved),
+        format!("{}/index.js", resolved),
+    ]
+    .into_iter()
+    .find(|candidate| modules.contains_key(candidate))
+}
+
+/// An identifier derived from a module path, e.g. `app_component` for `app/app.component.js`.
+fn module_identifier(path: &str) -> String {
+    let stem = Path::new(path).file_stem().map_or_else(
+        || path.to_string(),
+        |stem| stem.to_string_lossy().to_string(),
+    );
+    stem.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Annotate calls to Angular's definition functions, e.g. `i0.ɵɵdefineComponent(...)`, as
+/// side-effect free unless they already are.
+fn annotate_pure_definitions(code: &str) -> String {
+    const PURE: &str = "/*@__PURE__*/";
+    let definition_call = regex::Regex::new(r"(?:[\w$]+\.)?ɵɵdefine[A-Za-z]+\(").unwrap();
+    let mut output = String::with_capacity(code.len());
+    let mut last = 0;
+    for call in definition_call.find_iter(code) {
+        let before = code[..call.start()].trim_end();
+        let annotated = before.ends_with(PURE) || before.ends_with("/*#__PURE__*/");
+        output.push_str(&code[last..call.start()]);
+        if !annotated {
+            output.push_str(PURE);
+            output.push(' ');
+        }
+        last = call.start();
+    }
+    output.push_str(&code[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_modules_keeps_definitions_pure() {
+        let modules = HashMap::from([
+            (
+                "main.js".to_string(),
+                "import { AppComponent } from './app/app.component';\n\
+                 import { bootstrapApplication } from '@angular/platform-browser';\n\
+                 bootstrapApplication(AppComponent);\n"
+                    .to_string(),
+            ),
+            (
+                "app/app.component.js".to_string(),
+                "import * as i0 from '@angular/core';\n\
+                 export class AppComponent {}\n\
+                 AppComponent.ɵcmp = /*@__PURE__*/ i0.ɵɵdefineComponent({ type: AppComponent });\n\
+                 export class UnusedPipe {}\n\
+                 UnusedPipe.ɵpipe = i0.ɵɵdefinePipe({ name: 'unused', type: UnusedPipe });\n"
+                    .to_string(),
+            ),
+        ]);
+
+        let bundle = bundle_modules(&modules, "main.js").unwrap();
+
+        assert!(bundle.starts_with(
+            "import * as i0 from '@angular/core';\n\
+             import { bootstrapApplication } from '@angular/platform-browser';\n"
+        ));
+        assert!(!bundle.contains("./app/app.component"));
+        assert!(!bundle.contains("export class"));
+        assert!(bundle.contains("/*@__PURE__*/ i0.ɵɵdefineComponent({ type: AppComponent })"));
+        assert!(bundle.contains(
+            "UnusedPipe.ɵpipe = /*@__PURE__*/ i0.ɵɵdefinePipe({ name: 'unused', type: UnusedPipe })"
+        ));
+        assert!(!bundle.contains("/*@__PURE__*/ /*@__PURE__*/"));
+        assert!(
+            bundle.find("class AppComponent").unwrap()
+                < bundle.find("bootstrapApplication(AppComponent)").unwrap()
+        );
+    }
+}
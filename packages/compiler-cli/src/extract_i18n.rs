@@ -3,8 +3,18 @@
 //! Corresponds to packages/compiler-cli/src/extract_i18n.ts
 //! Extracts i18n messages from Angular templates.
 
-use crate::ngtsc::xi18n::MessageExtractor;
-use crate::transformers::api::I18nFormat;
+use crate::ngtsc::core::{Diagnostic, NgCompilerOptions};
+use crate::ngtsc::diagnostics::ErrorCode;
+use crate::ngtsc::file_system::{AbsoluteFsPath, FileSystem, NodeJSFileSystem};
+use crate::ngtsc::metadata::DecoratorMetadata;
+use crate::ngtsc::program::NgtscProgram;
+use crate::transformers::api::{DiagnosticCategory, I18nFormat};
+use angular_compiler::i18n::message_bundle::MessageBundle;
+use angular_compiler::i18n::{Arb, Json, Serializer, Xliff, Xliff2, Xmb};
+use angular_compiler::ml_parser::html_parser::HtmlParser;
+use angular_compiler::parse_util::ParseError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Options for i18n extraction.
 #[derive(Debug, Clone, Default)]
@@ -33,32 +43,160 @@ pub struct ExtractI18nResult {
 }
 
 /// Extract i18n messages from source files.
+///
+/// The messages are written to `out_file`, `messages.<format extension>` by default, like the
+/// `xi18n` command of Angular.
 pub fn extract_i18n(options: ExtractI18nOptions) -> ExtractI18nResult {
-    let extractor = MessageExtractor::new();
-    let mut message_count = 0;
+    extract_i18n_with(options, &NodeJSFileSystem::new())
+}
+
+fn extract_i18n_with<T: FileSystem>(options: ExtractI18nOptions, fs: &T) -> ExtractI18nResult {
+    let format = options.format.unwrap_or(I18nFormat::Xlf);
+    let out_file = options
+        .out_file
+        .unwrap_or_else(|| format!("messages.{}", format_extension(format)));
+    let out_path = fs.resolve(&[&out_file]);
+    let root_names = options
+        .source_files
+        .iter()
+        .map(|file| fs.resolve(&[file]).into_string())
+        .collect();
+
+    let mut program = NgtscProgram::new(root_names, NgCompilerOptions::default(), fs);
+    let extracted = program
+        .load_ng_structure(Path::new("/"))
+        .and_then(|_| write_messages(&program, format, &out_path, options.locale));
+    match extracted {
+        Ok((message_count, diagnostics)) => ExtractI18nResult {
+            success: true,
+            output: fs.read_file(&out_path).ok(),
+            diagnostics: diagnostics.into_iter().map(|d| d.message).collect(),
+            message_count,
+        },
+        Err(e) => ExtractI18nResult {
+            success: false,
+            output: None,
+            diagnostics: vec![e],
+            message_count: 0,
+        },
+    }
+}
+
+/// The extension of files in the given format.
+fn format_extension(format: I18nFormat) -> &'static str {
+    match format {
+        I18nFormat::Xlf | I18nFormat::Xlf2 => "xlf",
+        I18nFormat::Xmb => "xmb",
+        I18nFormat::Json => "json",
+        I18nFormat::Arb => "arb",
+    }
+}
+
+/// Extract the i18n messages of the components of an analyzed program and write them to
+/// `out_path` in the given format.
+///
+/// `load_ng_structure` must have been called on the program. Messages are written in the order
+/// their components were analyzed, with their legacy ids when
+/// `enableI18nLegacyMessageIdFormat` is set. Problems with individual templates are returned as
+/// diagnostics and don't prevent the file from being written.
+pub fn extract_messages_to<T: FileSystem>(
+    program: &NgtscProgram<'_, T>,
+    format: I18nFormat,
+    out_path: &AbsoluteFsPath,
+) -> Result<Vec<Diagnostic>, String> {
+    write_messages(program, format, out_path, None).map(|(_, diagnostics)| diagnostics)
+}
 
-    // In a real implementation, we would:
-    // 1. Parse each source file
-    // 2. Find i18n-marked content
-    // 3. Extract messages
+/// `extract_messages_to`, for messages in the given locale. Returns the number of messages
+/// written along with the diagnostics.
+fn write_messages<T: FileSystem>(
+    program: &NgtscProgram<'_, T>,
+    format: I18nFormat,
+    out_path: &AbsoluteFsPath,
+    locale: Option<String>,
+) -> Result<(usize, Vec<Diagnostic>), String> {
+    let result = program.get_compilation_result().ok_or_else(|| {
+        "Compilation result not available. Did you call load_ng_structure?".to_string()
+    })?;
+    let fs = program.get_file_system();
 
-    for _file in &options.source_files {
-        // Would parse and extract here
-        message_count += 0; // Placeholder
+    let mut bundle = MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), locale, false);
+    let mut diagnostics = Vec::new();
+
+    for directive in &result.directives {
+        let dir = match directive {
+            DecoratorMetadata::Directive(dir) if dir.t2.is_component => dir,
+            _ => continue,
+        };
+        let component = match &dir.component {
+            Some(component) => component,
+            None => continue,
+        };
+        let source_file = dir
+            .source_file
+            .as_ref()
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let (template, url) = if let Some(template) = &component.template {
+            (template.clone(), source_file)
+        } else if let Some(template_url) = &component.template_url {
+            let template_path = fs.resolve(&[&fs.dirname(&source_file), template_url]);
+            match fs.read_file(&template_path) {
+                Ok(content) => (content, template_path.into_string()),
+                Err(e) => {
+                    diagnostics.push(Diagnostic {
+                        category: DiagnosticCategory::Error,
+                        file: Some(PathBuf::from(&source_file)),
+                        message: format!("Could not read template '{}': {}", template_url, e),
+                        code: ErrorCode::ComponentResourceNotFound as usize,
+                        start: None,
+                        length: None,
+                        related_information: Vec::new(),
+                    });
+                    continue;
+                }
+            }
+        } else {
+            continue;
+        };
+
+        let errors = bundle.update_from_template(&template, &url);
+        diagnostics.extend(
+            errors
+                .iter()
+                .map(|error| parse_error_to_diagnostic(error, &url)),
+        );
     }
 
-    // Generate output based on format
-    let output = match options.format.unwrap_or(I18nFormat::Xlf) {
-        I18nFormat::Xlf | I18nFormat::Xlf2 => Some(extractor.to_xliff()),
-        I18nFormat::Xmb => Some(extractor.to_xmb()),
-        I18nFormat::Json => Some("{}".to_string()),
+    if program.get_options().i18n_legacy_message_ids() {
+        bundle.compute_legacy_ids();
+    }
+    let serializer: Box<dyn Serializer> = match format {
+        I18nFormat::Xlf => Box::new(Xliff::new()),
+        I18nFormat::Xlf2 => Box::new(Xliff2::new()),
+        I18nFormat::Xmb => Box::new(Xmb::new()),
+        I18nFormat::Json => Box::new(Json::new()),
+        I18nFormat::Arb => Box::new(Arb::new()),
     };
+    let output = bundle.write(serializer.as_ref(), None);
+
+    fs.write_file(out_path, output.as_bytes(), None)
+        .map_err(|e| format!("Failed to write {}: {}", out_path, e))?;
 
-    ExtractI18nResult {
-        success: true,
-        output,
-        diagnostics: Vec::new(),
-        message_count,
+    Ok((bundle.get_messages().len(), diagnostics))
+}
+
+fn parse_error_to_diagnostic(error: &ParseError, url: &str) -> Diagnostic {
+    let start = error.span.start.offset;
+    Diagnostic {
+        category: DiagnosticCategory::Error,
+        file: Some(PathBuf::from(url)),
+        message: error.msg.clone(),
+        code: ErrorCode::TemplateParseError as usize,
+        start: Some(start),
+        length: Some(error.span.end.offset.saturating_sub(start)),
+        related_information: Vec::new(),
     }
 }
 
@@ -77,6 +215,7 @@ pub fn main_xi18n(args: &[String]) -> i32 {
                         "xlf2" | "xliff2" => Some(I18nFormat::Xlf2),
                         "xmb" => Some(I18nFormat::Xmb),
                         "json" => Some(I18nFormat::Json),
+                        "arb" => Some(I18nFormat::Arb),
                         _ => None,
                     };
                     i += 1;
@@ -116,3 +255,147 @@ pub fn main_xi18n(args: &[String]) -> i32 {
         1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ngtsc::file_system::testing::MockFileSystem;
+    use crate::ngtsc::file_system::ReadonlyFileSystem;
+
+    #[test]
+    fn should_extract_messages_of_all_components_to_xliff2() {
+        let fs = MockFileSystem::new_posix();
+        fs.init_with_files(vec![
+            (
+                "/header.component.ts",
+                r#"
+                    import { Component } from '@angular/core';
+
+                    @Component({
+                        selector: 'app-header',
+                        template: '<h1 i18n="site header">Welcome</h1>',
+                        standalone: true
+                    })
+                    export class HeaderComponent {}
+                "#,
+            ),
+            (
+                "/footer.component.ts",
+                r#"
+                    import { Component } from '@angular/core';
+
+                    @Component({
+                        selector: 'app-footer',
+                        templateUrl: './footer.component.html',
+                        standalone: true
+                    })
+                    export class FooterComponent {}
+                "#,
+            ),
+            ("/footer.component.html", "<p i18n>All rights reserved</p>"),
+        ]);
+
+        let mut program = NgtscProgram::new(
+            vec![
+                "/header.component.ts".to_string(),
+                "/footer.component.ts".to_string(),
+            ],
+            NgCompilerOptions::default(),
+            &fs,
+        );
+        program.load_ng_structure(Path::new("/")).unwrap();
+
+        let out_path = AbsoluteFsPath::from("/messages.xlf");
+        let diagnostics = extract_messages_to(&program, I18nFormat::Xlf2, &out_path).unwrap();
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+        let output = fs.read_file(&out_path).unwrap();
+        assert!(output.contains("version=\"2.0\""), "{}", output);
+        let header = output
+            .find("<source>Welcome</source>")
+            .expect("header message");
+        let footer = output
+            .find("<source>All rights reserved</source>")
+            .expect("footer message");
+        assert!(header < footer);
+
+        // Extracting again produces the same file.
+        extract_messages_to(&program, I18nFormat::Xlf2, &out_path).unwrap();
+        assert_eq!(fs.read_file(&out_path).unwrap(), output);
+    }
+
+    #[test]
+    fn should_write_legacy_ids_only_when_enabled() {
+        let fs = MockFileSystem::new_posix();
+        fs.init_with_files(vec![(
+            "/app.component.ts",
+            r#"
+                import { Component } from '@angular/core';
+
+                @Component({ selector: 'app-root', template: '<p i18n>Hello</p>', standalone: true })
+                export class AppComponent {}
+            "#,
+        )]);
+        let extract = |enable_i18n_legacy_message_id_format| {
+            let options = NgCompilerOptions {
+                enable_i18n_legacy_message_id_format,
+                ..NgCompilerOptions::default()
+            };
+            let mut program =
+                NgtscProgram::new(vec!["/app.component.ts".to_string()], options, &fs);
+            program.load_ng_structure(Path::new("/")).unwrap();
+            let out_path = AbsoluteFsPath::from("/messages.xlf");
+            extract_messages_to(&program, I18nFormat::Xlf, &out_path).unwrap();
+            fs.read_file(&out_path).unwrap()
+        };
+
+        // Legacy ids are written unless they are disabled
+        let output = extract(None);
+        assert!(output.contains("x-legacy-id"), "{}", output);
+        let output = extract(Some(true));
+        assert!(output.contains("x-legacy-id"), "{}", output);
+        let output = extract(Some(false));
+        assert!(output.contains("<source>Hello</source>"), "{}", output);
+        assert!(!output.contains("x-legacy-id"), "{}", output);
+    }
+
+    #[test]
+    fn should_write_the_messages_of_the_source_files_to_the_default_out_file() {
+        let fs = MockFileSystem::new_posix();
+        fs.init_with_files(vec![(
+            "/app.component.ts",
+            r#"
+                import { Component } from '@angular/core';
+
+                @Component({ selector: 'app-root', template: '<p i18n="@@greeting">Hello</p>', standalone: true })
+                export class AppComponent {}
+            "#,
+        )]);
+
+        let result = extract_i18n_with(
+            ExtractI18nOptions {
+                format: Some(I18nFormat::Json),
+                source_files: vec!["/app.component.ts".to_string()],
+                ..ExtractI18nOptions::default()
+            },
+            &fs,
+        );
+        assert!(result.success, "{:?}", result.diagnostics);
+        assert_eq!(result.message_count, 1);
+        let output = result.output.unwrap();
+        assert!(output.contains(r#""greeting": "Hello""#), "{}", output);
+        assert_eq!(
+            fs.read_file(&AbsoluteFsPath::from("/messages.json"))
+                .unwrap(),
+            output
+        );
+    }
+
+    #[test]
+    fn should_require_an_analyzed_program() {
+        let fs = MockFileSystem::new_posix();
+        let program = NgtscProgram::new(vec![], NgCompilerOptions::default(), &fs);
+        let out_path = AbsoluteFsPath::from("/messages.json");
+        assert!(extract_messages_to(&program, I18nFormat::Json, &out_path).is_err());
+    }
+}
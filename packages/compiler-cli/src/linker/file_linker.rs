@@ -24,7 +24,9 @@ impl<'a, A: AstFactory> LinkerEnvironment<'a, A> {
 }
 
 use crate::linker::ast::AstNode;
-use crate::linker::partial_linkers::partial_linker_selector::PartialLinkerSelector;
+use crate::linker::partial_linkers::partial_linker_selector::{
+    declaration_versions, PartialLinkerSelector,
+};
 
 pub struct FileLinker<'a, A: AstFactory>
 where
@@ -73,13 +75,16 @@ where
             .get_object()
             .map_err(|_| "Metadata is not an object".to_string())?;
 
-        // We need a proper version handling. For now, empty string or stub.
-        let linker = self.linker_selector.get_linker(name, "0.0.0", "0.0.0");
+        let (min_version, version) = declaration_versions(&obj)?;
+        let linker = self
+            .linker_selector
+            .get_linker(name, &min_version, &version)
+            .map_err(|error| error.to_string())?;
 
         let mut constant_pool = ConstantPool::new(false); // Mock Constant Pool (needs implementation or passing in)
 
         let _definition =
-            linker.link_partial_declaration(&mut constant_pool, &obj, source_url, "0.0.0", None);
+            linker.link_partial_declaration(&mut constant_pool, &obj, source_url, &version, None);
 
         // Translate definition (output AST) to native AST using environment.translator?
         // Since we don't have translator instance yet, we stub.
@@ -3,9 +3,12 @@
 use std::any::Any;
 use std::collections::HashMap;
 
-use crate::linker::ast_value::AstValue;
+use crate::linker::ast_value::{AstObject, AstValue};
 use crate::linker::oxc_ast_host::{OxcAstHost, OxcNode};
-use crate::linker::partial_linkers::partial_linker_selector::PartialLinkerSelector;
+use crate::linker::partial_linker::PartialLinker;
+use crate::linker::partial_linkers::partial_linker_selector::{
+    declaration_versions, PartialLinkerSelector,
+};
 use angular_compiler::constant_pool::ConstantPool;
 use angular_compiler::output::abstract_emitter::EmitterVisitorContext;
 use angular_compiler::output::abstract_js_emitter::AbstractJsEmitterVisitor;
@@ -77,6 +80,21 @@ pub fn link_file(source_code: String, filename: String) -> Result<String> {
             }
         }
 
+        /// Selects the linker for a declaration from the versions recorded in its metadata,
+        /// returning it along with the declaration's version.
+        fn select_linker(
+            &self,
+            name: &str,
+            obj: &AstObject<'_, OxcNode<'a>>,
+        ) -> std::result::Result<(&dyn PartialLinker<OxcNode<'a>>, String), String> {
+            let (min_version, version) = declaration_versions(obj)?;
+            let linker = self
+                .selector
+                .get_linker(name, &min_version, &version)
+                .map_err(|e| e.to_string())?;
+            Ok((linker, version))
+        }
+
         fn emit_expression(&self, expr: &o::Expression) -> String {
             let expr = self.transform_expression(expr.clone());
             let mut visitor = AbstractJsEmitterVisitor::new();
@@ -598,10 +616,15 @@ pub fn link_file(source_code: String, filename: String) -> Result<String> {
 
                                                         match value.get_object() {
                                                             Ok(obj) => {
-                                                                let linker =
-                                                                    self.selector.get_linker(
-                                                                        d_name, "0.0.0", "0.0.0",
-                                                                    );
+                                                                let (linker, version) = match self
+                                                                    .select_linker(d_name, &obj)
+                                                                {
+                                                                    Ok(selected) => selected,
+                                                                    Err(e) => {
+                                                                        self.errors.push(e);
+                                                                        continue;
+                                                                    }
+                                                                };
                                                                 let mut constant_pool =
                                                                     ConstantPool::new(false);
 
@@ -611,7 +634,7 @@ pub fn link_file(source_code: String, filename: String) -> Result<String> {
                                                                         &mut constant_pool,
                                                                         &obj,
                                                                         self.source_url,
-                                                                        "0.0.0",
+                                                                        &version,
                                                                         Some(target_name),
                                                                     );
 
@@ -715,9 +738,14 @@ pub fn link_file(source_code: String, filename: String) -> Result<String> {
                                 unsafe { std::mem::transmute(arg_expr) };
                             let oxc_node = OxcNode::Expression(arg_expr_a);
                             let value = AstValue::new(oxc_node, &self.host);
-                            match value.get_object() {
-                                Ok(obj) => {
-                                    let linker = self.selector.get_linker(n, "0.0.0", "0.0.0");
+                            match value
+                                .get_object()
+                                .map_err(|e| format!("Failed to parse metadata object: {}", e))
+                                .and_then(|obj| {
+                                    let (linker, version) = self.select_linker(n, &obj)?;
+                                    Ok((obj, linker, version))
+                                }) {
+                                Ok((obj, linker, version)) => {
                                     let mut constant_pool = ConstantPool::new(false);
 
                                     // Link partial declaration
@@ -725,7 +753,7 @@ pub fn link_file(source_code: String, filename: String) -> Result<String> {
                                         &mut constant_pool,
                                         &obj,
                                         self.source_url,
-                                        "0.0.0",
+                                        &version,
                                         None,
                                     );
 
@@ -747,8 +775,7 @@ pub fn link_file(source_code: String, filename: String) -> Result<String> {
                                     self.replacements.push((span.start, span.end, js_code));
                                 }
                                 Err(e) => {
-                                    self.errors
-                                        .push(format!("Failed to parse metadata object: {}", e));
+                                    self.errors.push(e);
                                 }
                             }
                         }
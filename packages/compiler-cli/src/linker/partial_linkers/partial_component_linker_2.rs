@@ -189,8 +189,9 @@ impl PartialComponentLinker2 {
         );
 
         // Parse template
+        let preserve_whitespaces = meta_obj.get_bool("preserveWhitespaces").unwrap_or(false);
         let template_opts = angular_compiler::render3::view::template::ParseTemplateOptions {
-            preserve_whitespaces: Some(false), // TODO: get from metadata
+            preserve_whitespaces: Some(preserve_whitespaces),
             ..Default::default()
         };
         let parsed_template = angular_compiler::render3::view::template::parse_template(
@@ -856,7 +857,7 @@ impl PartialComponentLinker2 {
             template: R3ComponentTemplate {
                 nodes: parsed_template.nodes,
                 ng_content_selectors: parsed_template.ng_content_selectors,
-                preserve_whitespaces: false,
+                preserve_whitespaces,
             },
             declarations,
             defer: R3ComponentDeferMetadata::PerComponent {
@@ -872,6 +873,8 @@ impl PartialComponentLinker2 {
             i18n_use_external_ids: false,
             change_detection,
             relative_template_path: None,
+            enable_debug_locations: false,
+            template_start: None,
             has_directive_dependencies: false,
             raw_imports: None,
         })
@@ -1,7 +1,32 @@
 use crate::linker::ast::AstNode;
+use crate::linker::ast_value::AstObject;
+use crate::linker::error::FatalLinkerError;
 use crate::linker::partial_linker::PartialLinker;
+use crate::version::Version;
 use std::collections::HashMap;
 
+/// The `minVersion` range of the partial declarations the linkers can process. Partial
+/// declarations were introduced in Angular 12.
+const SUPPORTED_DECLARATION_RANGE: &str = ">=12.0.0";
+
+/// The version in declarations emitted by a development build of Angular.
+const PLACEHOLDER_VERSION: &str = "0.0.0";
+
+/// Reads the `minVersion` and `version` of a partial declaration's metadata. Metadata that
+/// doesn't record them (e.g. decorator metadata) is treated as a development build.
+pub fn declaration_versions<TExpression: AstNode>(
+    meta_obj: &AstObject<TExpression>,
+) -> Result<(String, String), String> {
+    let read = |key: &str| {
+        if meta_obj.has(key) {
+            meta_obj.get_string(key)
+        } else {
+            Ok(PLACEHOLDER_VERSION.to_string())
+        }
+    };
+    Ok((read("minVersion")?, read("version")?))
+}
+
 pub struct PartialLinkerSelector<'a, TExpression: AstNode> {
     linkers: HashMap<String, Box<dyn PartialLinker<TExpression> + 'a>>,
 }
@@ -84,19 +109,98 @@ impl<'a, TExpression: AstNode + 'a> PartialLinkerSelector<'a, TExpression> {
     pub fn get_linker(
         &self,
         name: &str,
-        _min_version: &str,
-        _version: &str,
-    ) -> &dyn PartialLinker<TExpression> {
-        if let Some(linker) = self.linkers.get(name) {
-            return linker.as_ref();
+        min_version: &str,
+        version: &str,
+    ) -> Result<&dyn PartialLinker<TExpression>, FatalLinkerError> {
+        let is_placeholder = version.starts_with(PLACEHOLDER_VERSION);
+        if !is_placeholder
+            && !Version::parse(min_version)
+                .is_some_and(|min| min.satisfies(SUPPORTED_DECLARATION_RANGE))
+        {
+            return Err(FatalLinkerError::new(
+                format!(
+                    "Unsupported partial declaration version {} for {}.\nValid version ranges are:\n - {}",
+                    version, name, SUPPORTED_DECLARATION_RANGE
+                ),
+                name,
+            ));
         }
 
-        if let Some(suffix) = name.split('.').last() {
-            if let Some(linker) = self.linkers.get(suffix) {
-                return linker.as_ref();
-            }
-        }
+        let suffix = name.split('.').next_back().unwrap_or(name);
+        self.linkers
+            .get(name)
+            .or_else(|| self.linkers.get(suffix))
+            .map(|linker| linker.as_ref())
+            .ok_or_else(|| FatalLinkerError::new(format!("Linker for {} not found", name), name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linker::ast_value::AstValue;
+    use crate::linker::oxc_ast_host::{OxcAstHost, OxcNode};
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::{Expression, Statement};
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    /// Selects the linker for `ɵɵngDeclareDirective` the way `FileLinker` does, from the
+    /// versions recorded in the metadata literal `meta`.
+    fn select_directive_linker(meta: &str) -> Result<(), String> {
+        let source = format!("({});", meta);
+        let allocator = Allocator::default();
+        let program = Parser::new(&allocator, &source, SourceType::mjs())
+            .parse()
+            .program;
+        let Some(Statement::ExpressionStatement(statement)) = program.body.first() else {
+            panic!("expected an expression statement");
+        };
+        let Expression::ParenthesizedExpression(meta_expr) = &statement.expression else {
+            panic!("expected a parenthesized expression");
+        };
+        let host = OxcAstHost::new(&source);
+        let meta_obj =
+            AstValue::new(OxcNode::Expression(&meta_expr.expression), &host).get_object()?;
+
+        let selector = PartialLinkerSelector::<OxcNode>::new();
+        let (min_version, version) = declaration_versions(&meta_obj)?;
+        selector
+            .get_linker("ɵɵngDeclareDirective", &min_version, &version)
+            .map(|_| ())
+            .map_err(|error| error.message)
+    }
+
+    #[test]
+    fn test_select_linker_for_supported_version() {
+        assert!(select_directive_linker(r#"{ minVersion: "14.0.0", version: "17.1.0" }"#).is_ok());
+    }
+
+    #[test]
+    fn test_select_linker_for_development_build() {
+        assert!(select_directive_linker(
+            r#"{ minVersion: "11.0.0", version: "0.0.0-PLACEHOLDER" }"#
+        )
+        .is_ok());
+        assert!(select_directive_linker("{}").is_ok());
+    }
+
+    #[test]
+    fn test_reject_unsupported_min_version() {
+        let error =
+            select_directive_linker(r#"{ minVersion: "11.0.0", version: "11.2.0" }"#).unwrap_err();
+        assert!(error.starts_with(
+            "Unsupported partial declaration version 11.2.0 for ɵɵngDeclareDirective."
+        ));
+    }
 
-        panic!("Linker for {} not found", name)
+    #[test]
+    fn test_reject_unknown_declaration() {
+        let selector = PartialLinkerSelector::<OxcNode>::new();
+        let error = selector
+            .get_linker("ɵɵngDeclareUnknown", "14.0.0", "17.0.0")
+            .err()
+            .unwrap();
+        assert_eq!(error.message, "Linker for ɵɵngDeclareUnknown not found");
     }
 }
@@ -3,6 +3,10 @@
 // Generates additional fields for classes with transformed inputs.
 
 use super::factory::CompileResult;
+use crate::ngtsc::metadata::InputOrOutput;
+use angular_compiler::output::output_ast::{
+    Expression, ReadPropExpr, ReadVarExpr, WrappedNodeExpr,
+};
 
 /// Input mapping with optional transform.
 #[derive(Debug, Clone)]
@@ -17,9 +21,25 @@ pub struct InputMapping {
     pub transform: Option<InputTransform>,
 }
 
+impl From<&InputOrOutput> for InputMapping {
+    fn from(input: &InputOrOutput) -> Self {
+        Self {
+            class_property_name: input.class_property_name.clone(),
+            binding_property_name: input.binding_property_name.clone(),
+            required: input.required,
+            transform: input.transform.as_ref().map(|transform| InputTransform {
+                node: transform.node.clone(),
+                type_expr: transform.type_ref.clone(),
+            }),
+        }
+    }
+}
+
 /// Metadata about an input transform function.
 #[derive(Debug, Clone)]
 pub struct InputTransform {
+    /// The transform function, e.g. `booleanAttribute`.
+    pub node: String,
     /// The type expression for the transform's input type.
     pub type_expr: String,
 }
@@ -44,3 +64,39 @@ pub fn compile_input_transform_fields(inputs: &[InputMapping]) -> Vec<CompileRes
 
     extra_fields
 }
+
+/// Build the expression referring to an input's transform function in the emitted definition.
+///
+/// References such as `booleanAttribute` or `Transforms.trim` become variable and property
+/// reads; inline functions are emitted verbatim.
+pub fn input_transform_expr(node: &str) -> Expression {
+    let is_reference = node.split('.').all(|part| {
+        part.chars().next().is_some_and(|c| !c.is_ascii_digit())
+            && part
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    });
+    if !is_reference {
+        return Expression::WrappedNode(WrappedNodeExpr {
+            node: Box::new(node.to_string()),
+            type_: None,
+            source_span: None,
+        });
+    }
+
+    let mut parts = node.split('.');
+    let mut expr = Expression::ReadVar(ReadVarExpr {
+        name: parts.next().unwrap_or_default().to_string(),
+        type_: None,
+        source_span: None,
+    });
+    for name in parts {
+        expr = Expression::ReadProp(ReadPropExpr {
+            receiver: Box::new(expr),
+            name: name.to_string(),
+            type_: None,
+            source_span: None,
+        });
+    }
+    expr
+}
@@ -15,7 +15,9 @@ pub mod schema;
 pub mod util;
 
 // Re-exports
-pub use api::{NoopResourceLoader, ResourceLoader, ResourceLoaderContext, ResourceType};
+pub use api::{
+    NoopResourceLoader, PreloadFuture, ResourceLoader, ResourceLoaderContext, ResourceType,
+};
 pub use debug_info::{extract_class_debug_info, R3ClassDebugInfo};
 pub use di::{
     get_constructor_dependencies, get_valid_constructor_dependencies,
@@ -36,7 +38,9 @@ pub use factory::{
     R3FactoryMetadata,
 };
 pub use injectable_registry::{InjectableClassRegistry, InjectableMeta};
-pub use input_transforms::{compile_input_transform_fields, InputMapping, InputTransform};
+pub use input_transforms::{
+    compile_input_transform_fields, input_transform_expr, InputMapping, InputTransform,
+};
 pub use jit_declaration_registry::JitDeclarationRegistry;
 pub use metadata::{
     ctor_parameter_to_metadata, decorator_to_metadata, extract_class_metadata,
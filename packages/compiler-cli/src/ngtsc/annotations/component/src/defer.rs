@@ -0,0 +1,125 @@
+// Component Defer Dependencies
+//
+// Works out which template dependencies are only used inside `@defer` blocks, so that they are
+// loaded through dynamic imports instead of static ones.
+
+use crate::ngtsc::imports::DeferredSymbolTracker;
+use angular_compiler::output::output_ast::Expression;
+use angular_compiler::render3::r3_ast as t;
+use angular_compiler::render3::view::api::{
+    R3DeferPerComponentDependency, R3TemplateDependencyMetadata,
+};
+use angular_compiler::render3::view::t2_binder::find_matching_directives_and_pipes_in_nodes;
+use indexmap::IndexMap;
+use std::collections::HashSet;
+
+/// Split the declarations of a component into the ones that stay eager and the dependencies of
+/// its `@defer` blocks.
+///
+/// `deferrable_symbols` maps the imported classes that could be loaded lazily to the specifier
+/// they are imported from. A symbol is only deferred when it is not used outside of `@defer`
+/// blocks, and when every other symbol from the same import can be deferred too.
+pub fn resolve_deferred_dependencies(
+    component_name: &str,
+    nodes: &[t::R3Node],
+    declarations: Vec<R3TemplateDependencyMetadata>,
+    deferrable_symbols: &IndexMap<String, String>,
+) -> (
+    Vec<R3TemplateDependencyMetadata>,
+    Vec<R3DeferPerComponentDependency>,
+) {
+    if deferrable_symbols.is_empty() {
+        return (declarations, Vec::new());
+    }
+
+    let selectors: Vec<String> = declarations
+        .iter()
+        .filter_map(|decl| match decl {
+            R3TemplateDependencyMetadata::Directive(d) if !d.selector.is_empty() => {
+                Some(d.selector.clone())
+            }
+            _ => None,
+        })
+        .collect();
+    let matching = find_matching_directives_and_pipes_in_nodes(nodes.to_vec(), &selectors);
+
+    let mut candidates = HashSet::new();
+    let mut eager = HashSet::new();
+    for decl in &declarations {
+        let Some(name) = declaration_name(decl) else {
+            continue;
+        };
+        let is_candidate = match decl {
+            R3TemplateDependencyMetadata::Directive(d) => {
+                matching.directives.defer_candidates.contains(&d.selector)
+            }
+            R3TemplateDependencyMetadata::Pipe(p) => {
+                matching.pipes.defer_candidates.contains(&p.name)
+            }
+            R3TemplateDependencyMetadata::NgModule(_) => false,
+        };
+        if is_candidate {
+            candidates.insert(name.to_string());
+        } else {
+            eager.insert(name.to_string());
+        }
+    }
+
+    let mut tracker = DeferredSymbolTracker::new(false);
+    let mut symbols_by_import: IndexMap<&str, Vec<&str>> = IndexMap::new();
+    for (symbol, import_path) in deferrable_symbols {
+        symbols_by_import
+            .entry(import_path.as_str())
+            .or_default()
+            .push(symbol.as_str());
+    }
+    for (import_path, symbols) in &symbols_by_import {
+        tracker.register_import(import_path, symbols);
+    }
+    for (symbol, import_path) in deferrable_symbols {
+        if candidates.contains(symbol) && !eager.contains(symbol) {
+            tracker.mark_as_deferrable_candidate(symbol, import_path, component_name, false);
+        }
+    }
+
+    let mut eager_declarations = Vec::new();
+    let mut dependencies = Vec::new();
+    for decl in declarations {
+        let deferred_import = declaration_name(&decl).and_then(|name| {
+            deferrable_symbols
+                .get(name)
+                .filter(|import_path| tracker.can_defer(import_path))
+                .map(|import_path| (name.to_string(), import_path.clone()))
+        });
+        match deferred_import {
+            Some((symbol_name, import_path)) => {
+                if !dependencies
+                    .iter()
+                    .any(|dep: &R3DeferPerComponentDependency| dep.symbol_name == symbol_name)
+                {
+                    dependencies.push(R3DeferPerComponentDependency {
+                        symbol_name,
+                        import_path,
+                        is_default_import: false,
+                    });
+                }
+            }
+            None => eager_declarations.push(decl),
+        }
+    }
+
+    (eager_declarations, dependencies)
+}
+
+/// The local name of the class a declaration refers to.
+fn declaration_name(decl: &R3TemplateDependencyMetadata) -> Option<&str> {
+    let type_ = match decl {
+        R3TemplateDependencyMetadata::Directive(d) => &d.type_,
+        R3TemplateDependencyMetadata::Pipe(p) => &p.type_,
+        R3TemplateDependencyMetadata::NgModule(m) => &m.type_,
+    };
+    match type_ {
+        Expression::ReadVar(read) => Some(read.name.as_str()),
+        _ => None,
+    }
+}
@@ -1,25 +1,43 @@
+use super::defer::resolve_deferred_dependencies;
+use crate::ngtsc::annotations::common::input_transform_expr;
+use crate::ngtsc::annotations::directive::src::to_r3_query_metadata;
+use crate::ngtsc::diagnostics::ErrorCode;
+use crate::ngtsc::file_system::{AbsoluteFsPath, NodeJSFileSystem, ReadonlyFileSystem};
+use crate::ngtsc::hmr::extract_hmr_metadata;
+use crate::ngtsc::imports::ModuleResolver;
 use crate::ngtsc::metadata::{
-    extract_directive_metadata, DecoratorMetadata, DirectiveMetadata, ModuleMetadataReader,
+    extract_directive_metadata, ComponentMetadata, DecoratorMetadata, DirectiveMeta,
+    DirectiveMetadata, ModuleMetadataReader,
 };
 use crate::ngtsc::reflection::{ClassDeclaration, ReflectionHost, TypeScriptReflectionHost};
 use crate::ngtsc::transform::src::api::{
     AnalysisOutput, CompileResult, ConstantPool, DecoratorHandler, DetectResult, HandlerPrecedence,
 };
+use crate::ngtsc::translator::src::type_translator::translate_type;
+use angular_compiler::compiler::CompilerConfig;
+use angular_compiler::constant_pool::ConstantPool as CompilerConstantPool;
 use angular_compiler::core::ViewEncapsulation;
 use angular_compiler::ml_parser::html_whitespaces::{
     visit_all_with_siblings_nodes, WhitespaceVisitor,
 };
 use angular_compiler::output::abstract_emitter::EmitterVisitorContext;
 use angular_compiler::output::abstract_js_emitter::AbstractJsEmitterVisitor;
-use angular_compiler::output::output_ast::{Expression, ExpressionTrait, ReadVarExpr};
-use angular_compiler::parse_util::{ParseLocation, ParseSourceFile, ParseSourceSpan};
+use angular_compiler::output::output_ast::{Expression, ExpressionTrait, ReadVarExpr, Statement};
+use angular_compiler::parse_util::{
+    ParseError, ParseErrorLevel, ParseLocation, ParseSourceFile, ParseSourceSpan,
+};
+use angular_compiler::render3::r3_hmr_compiler::{
+    compile_hmr_initializer, compile_hmr_update_callback, HmrDefinition,
+};
 use angular_compiler::render3::r3_template_transform::{
     html_ast_to_render3_ast, Render3ParseOptions,
 };
 use angular_compiler::render3::view::api::{
     DeclarationListEmitMode, R3ComponentDeferMetadata, R3ComponentMetadata, R3ComponentTemplate,
-    R3DirectiveMetadata, R3HostMetadata, R3LifecycleMetadata, R3TemplateDependencyMetadata,
+    R3DeferResolverFunctionMetadata, R3DirectiveMetadata, R3HostMetadata, R3LifecycleMetadata,
+    R3TemplateDependencyMetadata,
 };
+use angular_compiler::render3::view::compiler::compile_defer_resolver_function;
 // use angular_compiler::render3::view::template::{parse_template, ParseTemplateOptions};
 // use std::collections::HashMap;
 use angular_compiler::template::pipeline::src::compilation::TemplateCompilationMode;
@@ -27,19 +45,48 @@ use angular_compiler::template::pipeline::src::emit::emit_component;
 use angular_compiler::template::pipeline::src::ingest::{ingest_host_binding, HostBindingInput};
 use angular_compiler::template::pipeline::src::phases;
 use std::any::Any;
+use std::collections::HashSet;
 // use std::time::Instant;
-// use angular_compiler::constant_pool::ConstantPool as CompilerConstantPool; // Distinct from ngtsc ConstantPool if needed
 
-pub struct ComponentDecoratorHandler;
+pub struct ComponentDecoratorHandler<'fs> {
+    config: CompilerConfig,
+    enable_hmr: bool,
+    /// Where the project files that components import from are read, the disk by default.
+    fs: Option<&'fs dyn ReadonlyFileSystem>,
+}
 
-impl ComponentDecoratorHandler {
+impl<'fs> ComponentDecoratorHandler<'fs> {
     pub fn new() -> Self {
-        Self
+        Self {
+            config: CompilerConfig::default(),
+            enable_hmr: false,
+            fs: None,
+        }
+    }
+
+    /// Read the project files that components import directives and pipes from with `fs`.
+    pub fn with_file_system(mut self, fs: &'fs dyn ReadonlyFileSystem) -> Self {
+        self.fs = Some(fs);
+        self
+    }
+
+    /// Compile components with the given options. In `debug` mode the location of each template
+    /// element is attached to the DOM for development tooling.
+    pub fn with_config(mut self, config: CompilerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Compile components for hot module replacement: each component loads its latest
+    /// definition from the dev server, see `compile_hmr_update_module`.
+    pub fn with_hmr(mut self, enable_hmr: bool) -> Self {
+        self.enable_hmr = enable_hmr;
+        self
     }
 }
 
 impl DecoratorHandler<DirectiveMetadata<'static>, DirectiveMetadata<'static>, (), ()>
-    for ComponentDecoratorHandler
+    for ComponentDecoratorHandler<'_>
 {
     fn name(&self) -> &str {
         "ComponentDecoratorHandler"
@@ -120,19 +167,57 @@ impl DecoratorHandler<DirectiveMetadata<'static>, DirectiveMetadata<'static>, ()
         _node: &ClassDeclaration,
         analysis: &DirectiveMetadata<'static>,
         _resolution: Option<&()>,
-        _constant_pool: &mut ConstantPool,
+        constant_pool: &mut ConstantPool,
     ) -> Vec<CompileResult> {
-        self.compile_ivy(analysis)
+        self.compile_ivy_with_pool(analysis, &mut constant_pool.pool)
     }
 }
 
-impl ComponentDecoratorHandler {
+impl ComponentDecoratorHandler<'_> {
     pub fn compile_ivy(&self, analysis: &DirectiveMetadata<'static>) -> Vec<CompileResult> {
+        let mut constant_pool = CompilerConstantPool::new(false);
+        self.compile_ivy_with_pool(analysis, &mut constant_pool)
+    }
+
+    /// Compile the component into `constant_pool`, which may be shared with the other classes
+    /// of the same file. Only the pooled statements added by this component are returned.
+    pub fn compile_ivy_with_pool(
+        &self,
+        analysis: &DirectiveMetadata<'static>,
+        constant_pool: &mut CompilerConstantPool,
+    ) -> Vec<CompileResult> {
+        self.compile_component(analysis, constant_pool)
+            .map(|(result, _)| result)
+            .into_iter()
+            .collect()
+    }
+
+    /// The module the dev server serves to replace the definition of a component with HMR
+    /// enabled: an `export default function Cmp_UpdateMetadata(Cmp, ɵɵnamespaces)` applying the
+    /// recompiled definition to the class.
+    pub fn compile_hmr_update_module(
+        &self,
+        analysis: &DirectiveMetadata<'static>,
+    ) -> Option<String> {
+        if !self.enable_hmr {
+            return None;
+        }
+        let mut constant_pool = CompilerConstantPool::new(false);
+        self.compile_component(analysis, &mut constant_pool)
+            .and_then(|(_, update_module)| update_module)
+    }
+
+    /// Compile a component, returning its definition and, with HMR enabled, its update module.
+    fn compile_component(
+        &self,
+        analysis: &DirectiveMetadata<'static>,
+        constant_pool: &mut CompilerConstantPool,
+    ) -> Option<(CompileResult, Option<String>)> {
         // Extract DirectiveMeta from DecoratorMetadata enum (must be a component)
         let dir = match analysis {
             DecoratorMetadata::Directive(d) if d.t2.is_component => d,
             _ => {
-                return vec![];
+                return None;
             }
         };
 
@@ -185,54 +270,68 @@ impl ComponentDecoratorHandler {
         };
         // eprintln!("DEBUG: [handler] Project root for ModuleMetadataReader: {}", project_root.display());
         let metadata_reader = ModuleMetadataReader::new(&project_root);
+        let node_fs = NodeJSFileSystem::new();
+        let fs = self.fs.unwrap_or(&node_fs);
+        // The directives and pipes declared in a project file.
+        let read_ts_metadata = |file: &std::path::Path| {
+            let content = fs.read_file(&AbsoluteFsPath::from(file)).ok()?;
+            metadata_reader.extract_ts_metadata_from_source(file, &content)
+        };
 
-        let (nodes, ng_content_selectors, preserve_whitespaces, styles) = if let Some(ast) =
-            comp_meta.template_ast.as_ref()
-        {
-            let options = Render3ParseOptions {
-                collect_comment_nodes: false,
-                ..Default::default()
-            };
+        let (nodes, ng_content_selectors, preserve_whitespaces, styles, template_errors) =
+            if let Some(ast) = comp_meta.template_ast.as_ref() {
+                let preserve_whitespaces = dir.t2.preserve_whitespaces;
+                let options = Render3ParseOptions {
+                    collect_comment_nodes: false,
+                    preserve_whitespaces,
+                };
 
-            // Apply whitespace visitor
-            let mut visitor = WhitespaceVisitor::new(true, None, false);
-            let processed_nodes = visit_all_with_siblings_nodes(&mut visitor, ast);
-
-            let result = html_ast_to_render3_ast(&processed_nodes, &mut binding_parser, &options);
-            // Combine inline styles from template with any style URLs
-            let mut combined_styles = result.styles;
-            combined_styles.extend(result.style_urls);
-            (
-                result.nodes,
-                result.ng_content_selectors,
-                true, // TODO: Get from options
-                combined_styles,
-            )
-        } else {
-            let parsed_template = angular_compiler::render3::view::template::parse_template(
-                &template_str,
-                &template_url,
-                angular_compiler::render3::view::template::ParseTemplateOptions {
-                    preserve_whitespaces: Some(false),
-                    ..Default::default()
-                },
-            );
-            // Combine inline styles from template with any style URLs
-            let mut combined_styles = parsed_template.styles;
-            combined_styles.extend(parsed_template.style_urls);
-            (
-                parsed_template.nodes,
-                parsed_template.ng_content_selectors,
-                parsed_template.preserve_whitespaces.unwrap_or(false),
-                combined_styles,
-            )
-        };
+                // Apply whitespace visitor unless the component opted into `preserveWhitespaces`
+                let processed_nodes = if preserve_whitespaces {
+                    ast.clone()
+                } else {
+                    let mut visitor = WhitespaceVisitor::new(true, None, false);
+                    visit_all_with_siblings_nodes(&mut visitor, ast)
+                };
 
-        // TODO: Handle parsing errors?
-        // if let Some(errors) = parsed_template.errors { ... }
+                let result =
+                    html_ast_to_render3_ast(&processed_nodes, &mut binding_parser, &options);
+                // Combine inline styles from template with any style URLs
+                let mut combined_styles = result.styles;
+                combined_styles.extend(result.style_urls);
+                (
+                    result.nodes,
+                    result.ng_content_selectors,
+                    preserve_whitespaces,
+                    combined_styles,
+                    result.errors,
+                )
+            } else {
+                let parsed_template = angular_compiler::render3::view::template::parse_template(
+                    &template_str,
+                    &template_url,
+                    angular_compiler::render3::view::template::ParseTemplateOptions {
+                        preserve_whitespaces: Some(dir.t2.preserve_whitespaces),
+                        ..Default::default()
+                    },
+                );
+                // Combine inline styles from template with any style URLs
+                let mut combined_styles = parsed_template.styles;
+                combined_styles.extend(parsed_template.style_urls);
+                (
+                    parsed_template.nodes,
+                    parsed_template.ng_content_selectors,
+                    parsed_template.preserve_whitespaces.unwrap_or(false),
+                    combined_styles,
+                    parsed_template.errors.unwrap_or_default(),
+                )
+            };
 
         // Detect dependencies (directives, pipes, modules) from imports
         let mut declarations_map = indexmap::IndexMap::new();
+        // Directives and pipes imported directly from a project file, by import specifier. These
+        // can be loaded lazily when they are only used in `@defer` blocks.
+        let mut deferrable_symbols = indexmap::IndexMap::new();
 
         if let Some(imports) = &dir.imports {
             // eprintln!("DEBUG: [handler] Processing imports for component: {}, total imports: {}", dir.t2.name, imports.len());
@@ -275,6 +374,42 @@ impl ComponentDecoratorHandler {
                 // 2. If dynamic fails, fall back to hardcoded for compatibility
                 // 3. If no module_path (local), use ReadVar expression directly
 
+                if let (Some(path), Some(source_file)) = (&module_path, &dir.source_file) {
+                    if path.starts_with('.') {
+                        let resolved = ModuleResolver::new(fs, AbsoluteFsPath::from(&project_root))
+                            .resolve_module(path, &source_file.to_string_lossy());
+                        let local_meta = resolved
+                            .and_then(|file| read_ts_metadata(file.as_path()))
+                            .and_then(|metas| {
+                                metas.into_iter().find(|meta| match meta {
+                                    R3TemplateDependencyMetadata::Directive(d) => {
+                                        matches!(&d.type_, Expression::ReadVar(rv) if rv.name == import_name)
+                                    }
+                                    R3TemplateDependencyMetadata::Pipe(p) => {
+                                        matches!(&p.type_, Expression::ReadVar(rv) if rv.name == import_name)
+                                    }
+                                    R3TemplateDependencyMetadata::NgModule(_) => false,
+                                })
+                            });
+                        if let Some(mut meta) = local_meta {
+                            let key = match &mut meta {
+                                R3TemplateDependencyMetadata::Directive(d) => {
+                                    d.source_span = source_span.clone();
+                                    format!("dir:{}", d.selector)
+                                }
+                                R3TemplateDependencyMetadata::Pipe(p) => {
+                                    p.source_span = source_span.clone();
+                                    format!("pipe:{}", p.name)
+                                }
+                                R3TemplateDependencyMetadata::NgModule(_) => unreachable!(),
+                            };
+                            declarations_map.insert(key, meta);
+                            deferrable_symbols.insert(import_name.clone(), path.clone());
+                            continue;
+                        }
+                    }
+                }
+
                 if let Some(path) = &module_path {
                     // External module - try dynamic loading first
                     if let Some(dynamic_deps) = metadata_reader.read_metadata(path) {
@@ -397,9 +532,7 @@ impl ComponentDecoratorHandler {
                     let mut found = false;
                     // Dynamic resolution for local components
                     if let Some(source_file) = &dir.source_file {
-                        if let Some(local_details) =
-                            metadata_reader.extract_ts_metadata(source_file)
-                        {
+                        if let Some(local_details) = read_ts_metadata(source_file) {
                             for meta in local_details {
                                 let matches = match &meta {
                                      R3TemplateDependencyMetadata::Directive(d) => {
@@ -467,6 +600,26 @@ impl ComponentDecoratorHandler {
         // eprintln!("DEBUG: [handler] Final declarations_map size: {}", declarations_map.len());
         // eprintln!("DEBUG: [handler] Final declarations_map keys: {:?}", declarations_map.keys().collect::<Vec<_>>());
 
+        let (declarations, defer_dependencies) = resolve_deferred_dependencies(
+            &dir.t2.name,
+            &nodes,
+            declarations_map.into_iter().map(|(_, v)| v).collect(),
+            &deferrable_symbols,
+        );
+        let deferrable_imports: HashSet<String> = defer_dependencies
+            .iter()
+            .map(|dep| dep.symbol_name.clone())
+            .collect();
+        let dependencies_fn = if defer_dependencies.is_empty() {
+            None
+        } else {
+            Some(compile_defer_resolver_function(
+                &R3DeferResolverFunctionMetadata::PerComponent {
+                    dependencies: defer_dependencies,
+                },
+            ))
+        };
+
         let mut r3_metadata = R3ComponentMetadata {
             directive: R3DirectiveMetadata {
                 name: dir.t2.name.clone(),
@@ -495,40 +648,8 @@ impl ComponentDecoratorHandler {
                     ),
                 ),
                 selector: dir.t2.selector.clone(),
-                queries: dir
-                    .queries
-                    .iter()
-                    .map(|q| angular_compiler::render3::view::api::R3QueryMetadata {
-                        property_name: q.property_name.clone(),
-                        first: q.first,
-                        predicate:
-                            angular_compiler::render3::view::api::R3QueryPredicate::Selectors(vec![
-                                q.selector.clone(),
-                            ]),
-                        descendants: q.descendants,
-                        emit_distinct_changes_only: true,
-                        read: None,
-                        static_: q.is_static,
-                        is_signal: q.is_signal,
-                    })
-                    .collect(),
-                view_queries: dir
-                    .view_queries
-                    .iter()
-                    .map(|vq| angular_compiler::render3::view::api::R3QueryMetadata {
-                        property_name: vq.property_name.clone(),
-                        first: vq.first,
-                        predicate:
-                            angular_compiler::render3::view::api::R3QueryPredicate::Selectors(vec![
-                                vq.selector.clone(),
-                            ]),
-                        descendants: vq.descendants,
-                        emit_distinct_changes_only: true,
-                        read: None,
-                        static_: vq.is_static,
-                        is_signal: vq.is_signal,
-                    })
-                    .collect(),
+                queries: dir.queries.iter().map(to_r3_query_metadata).collect(),
+                view_queries: dir.view_queries.iter().map(to_r3_query_metadata).collect(),
                 host: dir.host.clone(),
                 inputs: dir
                     .t2
@@ -542,15 +663,10 @@ impl ComponentDecoratorHandler {
                                 binding_property_name: v.binding_property_name.clone(),
                                 is_signal: v.is_signal,
                                 required: v.required,
-                                transform_function: v.transform.as_ref().map(|t| {
-                                    angular_compiler::output::output_ast::Expression::ReadVar(
-                                        angular_compiler::output::output_ast::ReadVarExpr {
-                                            name: t.node.clone(),
-                                            type_: None,
-                                            source_span: None,
-                                        },
-                                    )
-                                }),
+                                transform_function: v
+                                    .transform
+                                    .as_ref()
+                                    .map(|t| input_transform_expr(&t.node)),
                             },
                         )
                     })
@@ -575,14 +691,16 @@ impl ComponentDecoratorHandler {
                 nodes: nodes.clone(), // Clone for pipeline ingestion
                 preserve_whitespaces: preserve_whitespaces,
             },
-            declarations: declarations_map.into_iter().map(|(_, v)| v).collect(),
+            declarations,
             declaration_list_emit_mode: DeclarationListEmitMode::Direct,
             styles: {
                 let mut combined = comp_meta.styles.clone().unwrap_or_default();
                 combined.extend(styles);
                 combined
             },
-            encapsulation: ViewEncapsulation::Emulated,
+            encapsulation: comp_meta
+                .encapsulation
+                .unwrap_or(ViewEncapsulation::Emulated),
             change_detection: comp_meta.change_detection.map(|s| {
                 angular_compiler::render3::view::api::ChangeDetectionOrExpression::Strategy(s)
             }),
@@ -592,21 +710,32 @@ impl ComponentDecoratorHandler {
             i18n_use_external_ids: false,
             raw_imports: None,
             external_styles: None,
-            defer: R3ComponentDeferMetadata::PerComponent {
-                dependencies_fn: None,
+            defer: R3ComponentDeferMetadata::PerComponent { dependencies_fn },
+            relative_template_path: template_file(dir, comp_meta).map(|file| {
+                let path = std::path::Path::new(&file);
+                path.strip_prefix(&project_root)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .to_string()
+            }),
+            enable_debug_locations: self.config.debug,
+            template_start: if self.config.debug {
+                inline_template_start(dir, comp_meta, fs)
+            } else {
+                None
             },
-            relative_template_path: None,
             has_directive_dependencies: false,
         };
 
-        let mut real_constant_pool = angular_compiler::constant_pool::ConstantPool::new(false);
+        let pool_start = constant_pool.statements.len();
 
         // 4. Emit component definition using centralized compiler
-        let compiled = angular_compiler::render3::view::compiler::compile_component_from_metadata(
-            &r3_metadata,
-            &mut real_constant_pool,
-            &mut binding_parser,
-        );
+        let (compiled, pipeline_diagnostics) =
+            angular_compiler::render3::view::compiler::compile_component_with_diagnostics(
+                &r3_metadata,
+                constant_pool,
+                &mut binding_parser,
+            );
 
         // Detect required imports based on metadata
         let mut import_manager = crate::ngtsc::translator::src::import_manager::import_manager::EmitterImportManager::new();
@@ -636,7 +765,7 @@ impl ComponentDecoratorHandler {
             .collect();
 
         // Emit AST to String
-        let mut emitter = AbstractJsEmitterVisitor::with_imports(imports_map);
+        let mut emitter = AbstractJsEmitterVisitor::with_imports(imports_map.clone());
         let mut ctx = EmitterVisitorContext::create_root();
         let context: &mut dyn Any = &mut ctx;
 
@@ -645,33 +774,181 @@ impl ComponentDecoratorHandler {
         let initializer = ctx.to_source();
 
         // Emit statements (hoisted statements)
-        let mut emitted_statements = vec![];
-        for stmt in &compiled.statements {
+        let mut emit_statement = |stmt: &Statement| {
             let mut stmt_ctx = EmitterVisitorContext::create_root();
             let stmt_context: &mut dyn Any = &mut stmt_ctx;
             stmt.visit_statement(&mut emitter, stmt_context);
-            emitted_statements.push(stmt_ctx.to_source());
-        }
+            stmt_ctx.to_source()
+        };
+        let mut emitted_statements: Vec<String> =
+            new_component_statements(&compiled.statements, constant_pool, pool_start)
+                .into_iter()
+                .map(&mut emit_statement)
+                .collect();
+
+        // With HMR, the component loads its update module whenever the dev server reports a
+        // change. The class is only read once the module has loaded, so the initializer can be
+        // hoisted along with the other statements.
+        let hmr_update_module = if self.enable_hmr {
+            let file_path = dir
+                .source_file
+                .as_ref()
+                .map(|file| {
+                    file.strip_prefix(&project_root)
+                        .unwrap_or(file)
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let hmr_meta = extract_hmr_metadata(&dir.t2.name, &file_path, &imports_map);
+            emitted_statements.push(emit_statement(
+                &compile_hmr_initializer(&hmr_meta).to_stmt(),
+            ));
+
+            // The update module is loaded on its own, so it declares every constant of the file
+            // rather than only the ones added by this component.
+            let constant_statements: Vec<Statement> =
+                new_component_statements(&compiled.statements, constant_pool, 0)
+                    .into_iter()
+                    .cloned()
+                    .collect();
+            let definitions = [HmrDefinition {
+                name: "ɵcmp".to_string(),
+                initializer: Some(compiled.expression.clone()),
+                statements: vec![],
+            }];
+            let update_callback =
+                compile_hmr_update_callback(&definitions, &constant_statements, &hmr_meta);
+            Some(format!(
+                "export default {}",
+                emit_statement(&Statement::DeclareFn(update_callback))
+            ))
+        } else {
+            None
+        };
 
-        // 4. Convert diagnostics (not easily available from compiled result yet, need to improve return type of compile_component_from_metadata if we want them back)
-        // For now, returning empty diagnostics as the centralized compiler doesn't return them directly in the struct yet
-        let ts_diagnostics: Vec<ts::Diagnostic> = vec![];
+        // 4. Convert diagnostics: template parse errors and errors found by the template
+        // pipeline. Pipeline warnings (unused imports) are not reported yet.
+        let ts_diagnostics: Vec<ts::Diagnostic> = template_errors
+            .iter()
+            .chain(
+                pipeline_diagnostics
+                    .iter()
+                    .filter(|error| error.level == ParseErrorLevel::Error),
+            )
+            .map(|error| template_error_diagnostic(error, dir, comp_meta))
+            .collect();
 
-        vec![CompileResult {
+        let result = CompileResult {
             name: "ɵcmp".to_string(),
             initializer: Some(initializer),
             statements: emitted_statements,
-            type_desc: "ComponentDef".to_string(),
-            deferrable_imports: None,
+            type_desc: translate_type(&compiled.type_),
+            deferrable_imports: (!deferrable_imports.is_empty()).then_some(deferrable_imports),
             diagnostics: ts_diagnostics,
             additional_imports,
-        }]
+        };
+        Some((result, hmr_update_module))
+    }
+}
+
+/// The file a component's template is read from: its `templateUrl`, resolved next to the component,
+/// or the component file itself for inline templates.
+fn template_file(dir: &DirectiveMeta, comp_meta: &ComponentMetadata) -> Option<String> {
+    let component_file = dir
+        .source_file
+        .as_ref()
+        .map(|path| path.to_string_lossy().to_string());
+    match &comp_meta.template_url {
+        Some(url) => component_file.map(|file| {
+            let dir = std::path::Path::new(&file)
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_default();
+            dir.join(url).to_string_lossy().to_string()
+        }),
+        None => component_file,
+    }
+}
+
+/// Where the inline template of a component starts in its source file, from which the
+/// locations of its elements are shifted like the offsets of `template_error_diagnostic`.
+fn inline_template_start(
+    dir: &DirectiveMeta,
+    comp_meta: &ComponentMetadata,
+    fs: &dyn ReadonlyFileSystem,
+) -> Option<ParseLocation> {
+    if comp_meta.template_url.is_some() {
+        return None;
+    }
+    let offset = comp_meta.template_offset?;
+    let file = dir.source_file.as_ref()?;
+    let content = fs.read_file(&AbsoluteFsPath::from(file.as_path())).ok()?;
+    let start = ParseLocation::from_source(content, file.to_string_lossy().to_string(), 0, 0, 0);
+    Some(start.move_by(offset as i32))
+}
+
+/// Report a template error against the file the template came from. Offsets of inline
+/// templates are shifted to the position of the `template` literal in the component source.
+fn template_error_diagnostic(
+    error: &ParseError,
+    dir: &DirectiveMeta,
+    comp_meta: &ComponentMetadata,
+) -> ts::Diagnostic {
+    let file = template_file(dir, comp_meta);
+    let offset = match &comp_meta.template_url {
+        Some(_) => 0,
+        None => comp_meta.template_offset.unwrap_or(0),
+    };
+    let start = error.span.start.offset;
+    let category = match error.level {
+        ParseErrorLevel::Error => ts::DiagnosticCategory::Error,
+        ParseErrorLevel::Warning => ts::DiagnosticCategory::Warning,
+    };
+    ts::Diagnostic {
+        category,
+        code: ErrorCode::TemplateParseError as i32,
+        file,
+        start: offset + start,
+        length: error.span.end.offset.saturating_sub(start),
+        message_text: ts::DiagnosticMessageChain::String(error.msg.clone()),
+        related_information: None,
+    }
+}
+
+/// Select the statements to hoist for a component compiled into a (possibly shared) pool.
+///
+/// `compiled` starts with every statement of the template pool, including the constants that
+/// earlier components already hoisted; those are skipped. Constants added by the host bindings
+/// are only present in `pool`, so all pooled statements are taken from there, followed by the
+/// remaining (template function) statements.
+fn new_component_statements<'s>(
+    compiled: &'s [Statement],
+    pool: &'s CompilerConstantPool,
+    pool_start: usize,
+) -> Vec<&'s Statement> {
+    let pooled = &pool.statements[pool_start..];
+    let pooled_names: HashSet<&str> = pooled.iter().filter_map(declared_name).collect();
+    let rest = compiled
+        .iter()
+        .skip(pool_start)
+        .filter(|stmt| declared_name(stmt).is_none_or(|name| !pooled_names.contains(name)));
+    pooled.iter().chain(rest).collect()
+}
+
+fn declared_name(stmt: &Statement) -> Option<&str> {
+    match stmt {
+        Statement::DeclareVar(decl) => Some(&decl.name),
+        Statement::DeclareFn(decl) => Some(&decl.name),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ngtsc::file_system::MemoryFileSystem;
+    use crate::ngtsc::imports::{OwningModule, Reference};
     use crate::ngtsc::metadata::{
         ClassPropertyMapping, ComponentMetadata, DirectiveMeta, T2DirectiveMetadata,
     };
@@ -719,4 +996,279 @@ mod tests {
         assert!(initializer.contains("decls: 2"));
         assert!(initializer.contains("vars: 0"));
     }
+
+    fn compile_with_component_meta(component: ComponentMetadata) -> String {
+        let metadata = DecoratorMetadata::Directive(DirectiveMeta {
+            t2: T2DirectiveMetadata {
+                name: "TestComponent".to_string(),
+                selector: Some("test-comp".to_string()),
+                is_component: true,
+                ..Default::default()
+            },
+            component: Some(ComponentMetadata {
+                template: Some("<div>Hello World</div>".to_string()),
+                ..component
+            }),
+            is_standalone: true,
+            ..Default::default()
+        });
+
+        let handler = ComponentDecoratorHandler::new();
+        let results = handler.compile_ivy(&metadata);
+        results[0].initializer.clone().unwrap()
+    }
+
+    fn compile_with_change_detection(
+        change_detection: Option<angular_compiler::core::ChangeDetectionStrategy>,
+    ) -> String {
+        compile_with_component_meta(ComponentMetadata {
+            change_detection,
+            ..Default::default()
+        })
+    }
+
+    fn compile_with_encapsulation(encapsulation: ViewEncapsulation) -> String {
+        compile_with_component_meta(ComponentMetadata {
+            styles: Some(vec!["div { color: red; }".to_string()]),
+            encapsulation: Some(encapsulation),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_compile_change_detection_default_vs_on_push() {
+        let implicit = compile_with_change_detection(None);
+        let default = compile_with_change_detection(Some(
+            angular_compiler::core::ChangeDetectionStrategy::Default,
+        ));
+        let on_push = compile_with_change_detection(Some(
+            angular_compiler::core::ChangeDetectionStrategy::OnPush,
+        ));
+
+        // `Default` is the runtime default and is omitted from the definition.
+        assert!(!implicit.contains("changeDetection"));
+        assert_eq!(implicit, default);
+
+        assert!(on_push.contains("changeDetection: 0"));
+        assert_eq!(on_push.replace(", changeDetection: 0", ""), default);
+    }
+
+    #[test]
+    fn test_compile_emulated_encapsulation_scopes_styles() {
+        let initializer = compile_with_encapsulation(ViewEncapsulation::Emulated);
+        assert!(initializer.contains("_ngcontent-%COMP%"));
+        assert!(initializer.contains("encapsulation: 0"));
+    }
+
+    #[test]
+    fn test_compile_shadow_dom_encapsulation_skips_scoping() {
+        let initializer = compile_with_encapsulation(ViewEncapsulation::ShadowDom);
+        assert!(!initializer.contains("_ngcontent-%COMP%"));
+        assert!(!initializer.contains("_nghost-%COMP%"));
+        assert!(initializer.contains("div { color: red; }"));
+        assert!(initializer.contains("encapsulation: 3"));
+    }
+
+    fn component_with_template(name: &str, template: &str) -> DirectiveMetadata<'static> {
+        DecoratorMetadata::Directive(DirectiveMeta {
+            t2: T2DirectiveMetadata {
+                name: name.to_string(),
+                selector: Some(name.to_lowercase()),
+                is_component: true,
+                ..Default::default()
+            },
+            component: Some(ComponentMetadata {
+                template: Some(template.to_string()),
+                ..Default::default()
+            }),
+            is_standalone: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_share_constant_pool_across_components() {
+        let template = r#"<div [title]="['a', 'b']" [id]="['a', 'b']"></div>"#;
+        let first = component_with_template("FirstCmp", template);
+        let second = component_with_template("SecondCmp", template);
+        let handler = ComponentDecoratorHandler::new();
+
+        let compile = |order: [&DirectiveMetadata<'static>; 2]| {
+            let mut pool = CompilerConstantPool::new(false);
+            order.map(|cmp| handler.compile_ivy_with_pool(cmp, &mut pool).remove(0))
+        };
+
+        for (a, b) in [(&first, &second), (&second, &first)] {
+            let [a_result, b_result] = compile([a, b]);
+
+            // The array literal is hoisted once, by whichever component is compiled first.
+            assert_eq!(
+                a_result.statements,
+                vec!["var _c0 = () => [\"a\", \"b\"];\n"]
+            );
+            assert!(b_result.statements.is_empty());
+
+            for result in [a_result, b_result] {
+                let initializer = result.initializer.unwrap();
+                assert!(initializer.contains(
+                    r#"ɵɵproperty("title", i0.ɵɵpureFunction0(2, _c0))("id", i0.ɵɵpureFunction0(3, _c0))"#
+                ));
+            }
+        }
+    }
+
+    /// Compile a component that imports `LazyCmp` from `./lazy.component`, in an in-memory
+    /// project.
+    fn compile_with_lazy_import(template: &str) -> CompileResult {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/app/lazy.component.ts",
+            "import { Component } from '@angular/core';\n\
+             @Component({ selector: 'lazy-cmp', template: '', standalone: true })\n\
+             export class LazyCmp {}\n",
+        )])
+        .unwrap();
+        let source_file = std::path::PathBuf::from("/app/app.component.ts");
+
+        let mut lazy_ref = Reference::from_name("LazyCmp", Some(source_file.clone()));
+        lazy_ref.best_guess_owning_module = Some(OwningModule::new(
+            "./lazy.component",
+            source_file.to_string_lossy(),
+        ));
+        let metadata = DecoratorMetadata::Directive(DirectiveMeta {
+            t2: T2DirectiveMetadata {
+                name: "AppCmp".to_string(),
+                selector: Some("app-cmp".to_string()),
+                is_component: true,
+                ..Default::default()
+            },
+            component: Some(ComponentMetadata {
+                template: Some(template.to_string()),
+                ..Default::default()
+            }),
+            is_standalone: true,
+            imports: Some(vec![lazy_ref]),
+            source_file: Some(source_file),
+            ..Default::default()
+        });
+
+        ComponentDecoratorHandler::new()
+            .with_file_system(&fs)
+            .compile_ivy(&metadata)
+            .remove(0)
+    }
+
+    #[test]
+    fn test_defer_only_dependency_is_loaded_lazily() {
+        let result = compile_with_lazy_import("@defer { <lazy-cmp></lazy-cmp> }");
+        let initializer = result.initializer.unwrap();
+
+        assert!(result
+            .statements
+            .iter()
+            .any(|stmt| stmt.contains("AppCmp_DeferFn")
+                && stmt.contains("import('./lazy.component').then((m) => m.LazyCmp)")));
+        assert!(initializer.contains("i0.ɵɵdefer(1, 0, AppCmp_DeferFn)"));
+        assert!(!initializer.contains("dependencies: [LazyCmp]"));
+        assert_eq!(
+            result.deferrable_imports,
+            Some(HashSet::from(["LazyCmp".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_dependency_used_eagerly_stays_eager() {
+        let result =
+            compile_with_lazy_import("<lazy-cmp></lazy-cmp> @defer { <lazy-cmp></lazy-cmp> }");
+        let initializer = result.initializer.unwrap();
+
+        assert!(initializer.contains("dependencies: [LazyCmp]"));
+        assert!(!result
+            .statements
+            .iter()
+            .any(|stmt| stmt.contains("import(")));
+        assert!(result.deferrable_imports.is_none());
+    }
+
+    #[test]
+    fn test_report_duplicate_switch_case() {
+        let template = r#"@switch (mode) { @case ('a') { A } @case ('a') { again } }"#;
+        let cmp = component_with_template("SwitchCmp", template);
+        let result = ComponentDecoratorHandler::new().compile_ivy(&cmp).remove(0);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(
+            diagnostic.message_text.to_string(),
+            r#"@switch block has more than one @case with the value "'a'""#
+        );
+        assert_eq!(diagnostic.category, ts::DiagnosticCategory::Warning);
+        assert_eq!(diagnostic.code, ErrorCode::TemplateParseError as i32);
+        assert_eq!(diagnostic.start, template.rfind("@case (").unwrap());
+        assert_eq!(diagnostic.length, "@case ('a') {".len());
+        // The first matching case still wins.
+        assert!(result
+            .initializer
+            .unwrap()
+            .contains(r#"=== "a" ? 0 : (tmp_0_0 === "a" ? 1 : -1)"#));
+    }
+
+    #[test]
+    fn test_report_let_read_before_declaration() {
+        let template = r#"<span>{{name}}</span> @let name = 'x';"#;
+        let cmp = component_with_template("LetCmp", template);
+        let result = ComponentDecoratorHandler::new().compile_ivy(&cmp).remove(0);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(
+            diagnostic.message_text.to_string(),
+            "Cannot read @let declaration 'name' before it has been defined."
+        );
+        assert_eq!(diagnostic.code, ErrorCode::TemplateParseError as i32);
+        assert_eq!(diagnostic.start, template.find("{{name}}").unwrap());
+    }
+
+    #[test]
+    fn test_report_dollar_event_outside_listener() {
+        let template = r#"<div [title]="$event"></div>"#;
+        let cmp = component_with_template("EventCmp", template);
+        let result = ComponentDecoratorHandler::new().compile_ivy(&cmp).remove(0);
+
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(
+            diagnostic.message_text.to_string(),
+            "The '$event' variable is only available in event bindings."
+        );
+        assert_eq!(diagnostic.code, ErrorCode::TemplateParseError as i32);
+        assert_eq!(diagnostic.start, template.find("$event").unwrap());
+    }
+
+    #[test]
+    fn test_compile_source_locations_in_debug_mode() {
+        let mut cmp = component_with_template("LocCmp", "<div>\n  <span>{{ x }}</span>\n</div>");
+        if let DecoratorMetadata::Directive(dir) = &mut cmp {
+            dir.source_file = Some(std::path::PathBuf::from("src/app/loc.component.ts"));
+        }
+
+        let debug = CompilerConfig {
+            debug: true,
+            ..CompilerConfig::default()
+        };
+        let result = ComponentDecoratorHandler::new()
+            .with_config(debug)
+            .compile_ivy(&cmp)
+            .remove(0);
+        // Each element is located by its slot, offset, line and column.
+        assert!(result.initializer.unwrap().contains(
+            r#"i0.ɵɵattachSourceLocations("src/app/loc.component.ts", [[0, 0, 0, 0], [1, 8, 1, 2]]);"#
+        ));
+
+        let release = ComponentDecoratorHandler::new().compile_ivy(&cmp).remove(0);
+        assert!(!release
+            .initializer
+            .unwrap()
+            .contains("ɵɵattachSourceLocations"));
+    }
 }
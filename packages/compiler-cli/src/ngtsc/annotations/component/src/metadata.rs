@@ -132,11 +132,14 @@ pub enum ViewEncapsulation {
 }
 
 /// Change detection strategy.
+///
+/// Discriminants match the runtime `ChangeDetectionStrategy` enum, which is what ends up
+/// in the emitted `changeDetection` field.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ChangeDetectionStrategy {
+    OnPush = 0,
     #[default]
-    Default = 0,
-    OnPush = 1,
+    Default = 1,
 }
 
 /// Deferred block metadata.
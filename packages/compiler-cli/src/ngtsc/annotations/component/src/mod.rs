@@ -1,5 +1,6 @@
 // Annotations Component Source Module
 
+pub mod defer;
 pub mod handler;
 pub mod metadata;
 pub mod resources;
@@ -12,7 +13,8 @@ pub use metadata::{
     ComponentTemplateInfo, DeferTrigger, DeferredBlock, R3ComponentMetadata, ViewEncapsulation,
 };
 pub use resources::{
-    extract_template, parse_template_declaration, ExtractTemplateOptions, ParsedComponentTemplate,
+    extract_template, load_component_template, parse_template_declaration,
+    preload_component_resources, ExtractTemplateOptions, ParsedComponentTemplate,
     ParsedTemplateWithSource, ResourceTypeForDiagnostics, SourceMapping, StyleUrlMeta,
     TemplateDeclaration,
 };
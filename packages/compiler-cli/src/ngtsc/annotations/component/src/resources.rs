@@ -2,6 +2,8 @@
 //
 // Utilities for extracting and parsing component templates and styles.
 
+use super::metadata::ComponentTemplateInfo;
+use crate::ngtsc::annotations::common::{PreloadFuture, ResourceLoader, ResourceLoaderContext};
 use angular_compiler::parse_util::ParseSourceFile;
 
 /// Style URL metadata from decorator.
@@ -87,6 +89,8 @@ pub struct ParsedTemplateWithSource {
 pub enum SourceMapping {
     Direct {
         node: String,
+        /// Offset of the template content within the source file of `node`.
+        offset: usize,
     },
     Indirect {
         component_class: String,
@@ -98,6 +102,16 @@ pub enum SourceMapping {
     },
 }
 
+impl SourceMapping {
+    /// Map an offset in the template content to an offset in the file the template came from.
+    pub fn to_source_offset(&self, template_offset: usize) -> usize {
+        match self {
+            SourceMapping::Direct { offset, .. } => offset + template_offset,
+            SourceMapping::Indirect { .. } | SourceMapping::External { .. } => template_offset,
+        }
+    }
+}
+
 /// Template declaration info.
 #[derive(Debug, Clone)]
 pub struct TemplateDeclaration {
@@ -109,6 +123,8 @@ pub struct TemplateDeclaration {
     pub template_url: String,
     /// Resolved template URL.
     pub resolved_template_url: String,
+    /// Offset of an inline template's content within the component's source file.
+    pub source_offset: usize,
 }
 
 impl TemplateDeclaration {
@@ -118,6 +134,7 @@ impl TemplateDeclaration {
             preserve_whitespaces: false,
             template_url: String::new(),
             resolved_template_url: String::new(),
+            source_offset: 0,
         }
     }
 
@@ -127,8 +144,14 @@ impl TemplateDeclaration {
             preserve_whitespaces: false,
             template_url: template_url.into(),
             resolved_template_url: resolved.into(),
+            source_offset: 0,
         }
     }
+
+    pub fn with_source_offset(mut self, offset: usize) -> Self {
+        self.source_offset = offset;
+        self
+    }
 }
 
 /// Template extraction options.
@@ -157,6 +180,7 @@ pub fn extract_template(
         source_mapping: if declaration.is_inline {
             SourceMapping::Direct {
                 node: class_name.to_string(),
+                offset: declaration.source_offset,
             }
         } else {
             SourceMapping::External {
@@ -181,3 +205,143 @@ pub fn parse_template_declaration(
         TemplateDeclaration::inline(template.unwrap_or(""))
     }
 }
+
+/// Start loading the external template and stylesheets of a component.
+///
+/// This is the resource part of Angular's `preanalyze`: the returned futures should be awaited
+/// before analysis, which then loads the resources synchronously from the loader's cache.
+/// Returns no futures when the loader can't preload.
+pub fn preload_component_resources(
+    loader: &dyn ResourceLoader,
+    containing_file: &str,
+    class_name: &str,
+    template_url: Option<&str>,
+    style_urls: &[String],
+) -> Result<Vec<PreloadFuture>, String> {
+    if !loader.can_preload() {
+        return Ok(Vec::new());
+    }
+
+    let mut futures = Vec::new();
+    if let Some(url) = template_url {
+        let resolved = loader.resolve(url, containing_file)?;
+        let context = ResourceLoaderContext::new_template(containing_file, class_name);
+        futures.extend(loader.preload(&resolved, &context));
+    }
+    for (order, url) in style_urls.iter().enumerate() {
+        let resolved = loader.resolve(url, containing_file)?;
+        let context = ResourceLoaderContext::new_style(containing_file, class_name, order as u32);
+        futures.extend(loader.preload(&resolved, &context));
+    }
+    Ok(futures)
+}
+
+/// Inline the external template of a component: load `template.url`, resolved relative to
+/// `containing_file`, and store it as the template content.
+///
+/// Does nothing for inline templates.
+pub fn load_component_template(
+    loader: &dyn ResourceLoader,
+    template: &mut ComponentTemplateInfo,
+    containing_file: &str,
+) -> Result<(), String> {
+    if template.content.is_some() {
+        return Ok(());
+    }
+    if let Some(url) = &template.url {
+        let resolved = loader.resolve(url, containing_file)?;
+        template.content = Some(loader.load(&resolved)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ngtsc::file_system::testing::MockFileSystem;
+    use crate::ngtsc::file_system::{AbsoluteFsPath, FileSystem};
+    use crate::ngtsc::resource::FileResourceLoader;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Waker};
+
+    fn run(mut future: PreloadFuture) -> Result<(), String> {
+        let mut cx = Context::from_waker(Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => panic!("preload did not complete"),
+        }
+    }
+
+    fn loader() -> FileResourceLoader<MockFileSystem> {
+        let fs = MockFileSystem::new_posix();
+        fs.ensure_dir(&AbsoluteFsPath::new("/app".to_string()))
+            .unwrap();
+        fs.init_with_files(vec![
+            ("/app/app.component.ts", "export class AppComponent {}"),
+            ("/app/app.component.html", "<h1>{{ title }}</h1>"),
+            ("/app/app.component.css", "h1 { color: red; }"),
+        ]);
+        FileResourceLoader::new(Arc::new(fs))
+    }
+
+    #[test]
+    fn should_load_an_external_template_into_the_template_info() {
+        let loader = loader();
+        let mut template = ComponentTemplateInfo {
+            url: Some("./app.component.html".to_string()),
+            ..Default::default()
+        };
+
+        load_component_template(&loader, &mut template, "/app/app.component.ts").unwrap();
+        assert_eq!(template.content.as_deref(), Some("<h1>{{ title }}</h1>"));
+    }
+
+    #[test]
+    fn should_preload_resources_before_analysis() {
+        let loader = loader();
+        let futures = preload_component_resources(
+            &loader,
+            "/app/app.component.ts",
+            "AppComponent",
+            Some("./app.component.html"),
+            &["app.component.css".to_string()],
+        )
+        .unwrap();
+        assert_eq!(futures.len(), 2);
+        assert!(!loader.is_cached("/app/app.component.html"));
+
+        for future in futures {
+            run(future).unwrap();
+        }
+        assert!(loader.is_cached("/app/app.component.html"));
+        assert!(loader.is_cached("/app/app.component.css"));
+
+        // Cached resources are not preloaded again
+        let futures = preload_component_resources(
+            &loader,
+            "/app/app.component.ts",
+            "AppComponent",
+            Some("./app.component.html"),
+            &[],
+        )
+        .unwrap();
+        assert!(futures.is_empty());
+    }
+
+    #[test]
+    fn should_report_missing_resources() {
+        let loader = loader();
+        let mut template = ComponentTemplateInfo {
+            url: Some("./missing.html".to_string()),
+            ..Default::default()
+        };
+
+        let error =
+            load_component_template(&loader, &mut template, "/app/app.component.ts").unwrap_err();
+        assert_eq!(
+            error,
+            "Could not find resource ./missing.html relative to /app/app.component.ts"
+        );
+        assert!(template.content.is_none());
+    }
+}
@@ -3,11 +3,15 @@
 // Handles @Directive decorator processing.
 
 use super::symbol::DirectiveSymbol;
-use crate::ngtsc::metadata::{extract_directive_metadata, DecoratorMetadata, DirectiveMetadata};
+use crate::ngtsc::annotations::common::input_transform_expr;
+use crate::ngtsc::metadata::{
+    extract_directive_metadata, DecoratorMetadata, DirectiveMetadata, QueryMetadata,
+};
 use crate::ngtsc::reflection::{ClassDeclaration, ReflectionHost, TypeScriptReflectionHost};
 use crate::ngtsc::transform::src::api::{
     AnalysisOutput, CompileResult, DecoratorHandler, DetectResult, HandlerPrecedence,
 };
+use crate::ngtsc::translator::src::type_translator::translate_type;
 use angular_compiler::output::abstract_emitter::EmitterVisitorContext;
 use angular_compiler::output::abstract_js_emitter::AbstractJsEmitterVisitor;
 use angular_compiler::output::output_ast::{
@@ -18,7 +22,7 @@ use angular_compiler::render3::r3_factory::{
     compile_factory_function, DepsOrInvalid, FactoryTarget, R3ConstructorFactoryMetadata,
     R3DependencyMetadata, R3FactoryMetadata,
 };
-use angular_compiler::render3::util::R3Reference;
+use angular_compiler::render3::util::{ForwardRefHandling, MaybeForwardRefExpression, R3Reference};
 use angular_compiler::render3::view::api::{
     R3DirectiveMetadata, R3InputMetadata, R3QueryMetadata, R3QueryPredicate,
 };
@@ -26,6 +30,29 @@ use angular_compiler::render3::view::compiler::compile_directive_from_metadata;
 use angular_compiler::template_parser::binding_parser::BindingParser;
 use std::any::Any;
 
+/// Convert extracted query metadata into the `R3QueryMetadata` used by the compiler.
+pub fn to_r3_query_metadata(q: &QueryMetadata) -> R3QueryMetadata {
+    let predicate = if q.is_type_predicate {
+        R3QueryPredicate::Expression(MaybeForwardRefExpression::new(
+            input_transform_expr(&q.selector),
+            ForwardRefHandling::None,
+        ))
+    } else {
+        R3QueryPredicate::Selectors(vec![q.selector.clone()])
+    };
+
+    R3QueryMetadata {
+        property_name: q.property_name.clone(),
+        first: q.first,
+        predicate,
+        descendants: q.descendants,
+        emit_distinct_changes_only: true,
+        read: q.read.as_ref().map(|r| input_transform_expr(r)),
+        static_: q.is_static,
+        is_signal: q.is_signal,
+    }
+}
+
 pub struct DirectiveDecoratorHandler {
     #[allow(dead_code)]
     is_core: bool,
@@ -140,12 +167,23 @@ impl DecoratorHandler<DirectiveHandlerData, DirectiveHandlerData, DirectiveSymbo
         _resolution: Option<&()>,
         constant_pool: &mut crate::ngtsc::transform::src::api::ConstantPool,
     ) -> Vec<CompileResult> {
-        self.compile_ivy(analysis)
+        self.compile_ivy_with_pool(analysis, &mut constant_pool.pool)
     }
 }
 
 impl DirectiveDecoratorHandler {
     pub fn compile_ivy(&self, analysis: &DirectiveMetadata) -> Vec<CompileResult> {
+        let mut constant_pool = angular_compiler::constant_pool::ConstantPool::new(false);
+        self.compile_ivy_with_pool(analysis, &mut constant_pool)
+    }
+
+    /// Compile the directive into `constant_pool`, which may be shared with the other classes
+    /// of the same file. Only the pooled statements added by this directive are returned.
+    pub fn compile_ivy_with_pool(
+        &self,
+        analysis: &DirectiveMetadata,
+        constant_pool: &mut angular_compiler::constant_pool::ConstantPool,
+    ) -> Vec<CompileResult> {
         // Extract DirectiveMeta from DecoratorMetadata enum
         let dir = match analysis {
             DecoratorMetadata::Directive(d) => d,
@@ -175,13 +213,10 @@ impl DirectiveDecoratorHandler {
                         class_property_name: value.class_property_name.clone(),
                         binding_property_name: value.binding_property_name.clone(),
                         required: value.required,
-                        transform_function: value.transform.as_ref().map(|t| {
-                            Expression::ReadVar(ReadVarExpr {
-                                name: t.node.clone(),
-                                type_: None,
-                                source_span: None,
-                            })
-                        }),
+                        transform_function: value
+                            .transform
+                            .as_ref()
+                            .map(|t| input_transform_expr(&t.node)),
                         is_signal: value.is_signal,
                     },
                 )
@@ -195,30 +230,9 @@ impl DirectiveDecoratorHandler {
             .map(|(key, value)| (key.clone(), value.binding_property_name.clone()))
             .collect();
 
-        // Helper to convert QueryMetadata to R3QueryMetadata
-        let convert_query = |q: &crate::ngtsc::metadata::QueryMetadata| -> R3QueryMetadata {
-            R3QueryMetadata {
-                property_name: q.property_name.clone(),
-                first: q.first,
-                predicate: R3QueryPredicate::Selectors(vec![q.selector.clone()]), // Simplified: assume selector string
-                descendants: q.descendants,
-                emit_distinct_changes_only: true,
-                read: q.read.as_ref().map(|r| {
-                    // Start of rudimentary ReadToken parsing
-                    Expression::ReadVar(ReadVarExpr {
-                        name: r.clone(),
-                        type_: None,
-                        source_span: None,
-                    })
-                }),
-                static_: q.is_static,
-                is_signal: q.is_signal,
-            }
-        };
-
         let view_queries: Vec<R3QueryMetadata> =
-            dir.view_queries.iter().map(convert_query).collect();
-        let queries: Vec<R3QueryMetadata> = dir.queries.iter().map(convert_query).collect();
+            dir.view_queries.iter().map(to_r3_query_metadata).collect();
+        let queries: Vec<R3QueryMetadata> = dir.queries.iter().map(to_r3_query_metadata).collect();
 
         // Map host directives
         let host_directives = dir.host_directives.as_ref().map(|directives| {
@@ -302,7 +316,7 @@ impl DirectiveDecoratorHandler {
             deps: None,
         };
 
-        let mut constant_pool = angular_compiler::constant_pool::ConstantPool::new(false);
+        let pool_start = constant_pool.statements.len();
         let binding_parser_expr_parser = angular_compiler::expression_parser::parser::Parser::new();
         let binding_parser_schema_registry =
             angular_compiler::schema::dom_element_schema_registry::DomElementSchemaRegistry::new();
@@ -315,7 +329,7 @@ impl DirectiveDecoratorHandler {
         // 2. Compile Directive Definition (ɵdir)
         // 2. Compile Directive Definition (ɵdir)
         let compiled_dir =
-            compile_directive_from_metadata(&r3_meta, &mut constant_pool, &mut binding_parser);
+            compile_directive_from_metadata(&r3_meta, constant_pool, &mut binding_parser);
 
         // 3. Compile Factory (ɵfac)
         let deps: Option<angular_compiler::render3::r3_factory::DepsOrInvalid> =
@@ -440,12 +454,21 @@ impl DirectiveDecoratorHandler {
         }
         let dir_initializer = ctx.to_source();
 
+        // Emit the constants hoisted while compiling this directive (e.g. query predicates)
+        let mut emitted_statements = vec![];
+        for stmt in &constant_pool.statements[pool_start..] {
+            let mut stmt_ctx = EmitterVisitorContext::create_root();
+            let stmt_context: &mut dyn Any = &mut stmt_ctx;
+            stmt.visit_statement(&mut emitter, stmt_context);
+            emitted_statements.push(stmt_ctx.to_source());
+        }
+
         vec![
             CompileResult {
                 name: "ɵfac".to_string(),
                 initializer: Some(fac_initializer),
                 statements: vec![],
-                type_desc: "FactoryDef".to_string(),
+                type_desc: translate_type(&compiled_fac.type_),
                 deferrable_imports: None,
                 diagnostics: vec![],
                 additional_imports: additional_imports.clone(),
@@ -453,8 +476,8 @@ impl DirectiveDecoratorHandler {
             CompileResult {
                 name: "ɵdir".to_string(),
                 initializer: Some(dir_initializer),
-                statements: vec![],
-                type_desc: "DirectiveDef".to_string(),
+                statements: emitted_statements,
+                type_desc: translate_type(&compiled_dir.type_),
                 deferrable_imports: None,
                 diagnostics: vec![],
                 additional_imports,
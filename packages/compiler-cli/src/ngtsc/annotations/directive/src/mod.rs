@@ -11,7 +11,7 @@ pub mod query_functions;
 pub mod symbol;
 
 // Re-exports
-pub use handler::{DirectiveDecoratorHandler, DirectiveHandlerData};
+pub use handler::{to_r3_query_metadata, DirectiveDecoratorHandler, DirectiveHandlerData};
 pub use initializer_function_access::{
     validate_access_of_initializer_api_member, AccessLevel, AccessLevelError, InitializerApiConfig,
 };
@@ -26,7 +26,9 @@ pub use input_output_parse_options::{
     parse_and_validate_input_and_output_options, parse_options_from_pairs, InputOutputOptions,
     OptionsParseError,
 };
-pub use model_function::{try_parse_model_function, ModelFunctionMetadata};
+pub use model_function::{
+    model_initializer_config, try_parse_model_function, ModelFunctionMetadata,
+};
 pub use output_function::{
     output_from_observable_config, output_initializer_config, output_initializer_configs,
     try_parse_initializer_based_output, OutputMapping,
@@ -2,8 +2,14 @@
 //
 // Handles parsing of model() initializer for two-way binding.
 
+use super::initializer_function_access::{AccessLevel, InitializerApiConfig};
 use super::input_output_parse_options::InputOutputOptions;
 
+/// Configuration for the model() initializer function.
+pub fn model_initializer_config() -> InitializerApiConfig {
+    InitializerApiConfig::new("model", vec![AccessLevel::Public, AccessLevel::Protected])
+}
+
 /// Model metadata.
 #[derive(Debug, Clone)]
 pub struct ModelFunctionMetadata {
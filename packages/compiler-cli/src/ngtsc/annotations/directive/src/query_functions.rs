@@ -54,7 +54,7 @@ impl QueryFunctionMetadata {
             selector: selector.into(),
             is_required: false,
             first: true,
-            descendants: false,
+            descendants: true,
             read: None,
             is_signal: true,
         }
@@ -66,7 +66,7 @@ impl QueryFunctionMetadata {
             selector: selector.into(),
             is_required: false,
             first: false,
-            descendants: true,
+            descendants: false,
             read: None,
             is_signal: true,
         }
@@ -81,6 +81,11 @@ impl QueryFunctionMetadata {
         self.read = Some(read.into());
         self
     }
+
+    pub fn with_descendants(mut self, descendants: bool) -> Self {
+        self.descendants = descendants;
+        self
+    }
 }
 
 /// All query initializer APIs.
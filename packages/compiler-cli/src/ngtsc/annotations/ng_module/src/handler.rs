@@ -47,6 +47,30 @@ pub struct NgModuleAnalysis {
     pub remote_scopes_may_require_cycle_protection: bool,
 }
 
+impl NgModuleAnalysis {
+    pub fn new(name: impl Into<String>) -> Self {
+        let n = name.into();
+        Self {
+            module_meta: R3NgModuleMetadata::new(n.clone()),
+            injector_meta: R3InjectorMetadata::new(n.clone()),
+            factory_meta: R3FactoryMetadata::new(n.clone()),
+            class_metadata: None,
+            declarations: Vec::new(),
+            raw_declarations: None,
+            declarations_have_forward_refs: false,
+            imports: Vec::new(),
+            raw_imports: None,
+            exports: Vec::new(),
+            raw_exports: None,
+            id: None,
+            factory_symbol_name: n,
+            providers_requiring_factory: Vec::new(),
+            providers: None,
+            remote_scopes_may_require_cycle_protection: false,
+        }
+    }
+}
+
 /// R3 NgModule metadata for compilation.
 #[derive(Debug, Clone)]
 pub struct R3NgModuleMetadata {
@@ -154,6 +178,100 @@ impl NgModuleDecoratorHandler {
         Self { is_core }
     }
 
+    /// Compile the definitions of a module: its factory, `ɵmod` and `ɵinj`.
+    pub fn compile_ng_module(
+        analysis: &NgModuleAnalysis,
+        resolution: Option<&NgModuleResolution>,
+    ) -> Vec<CompileResult> {
+        let meta = &analysis.module_meta;
+        let define_ng_module_name = Identifiers::define_ng_module().name.unwrap_or_default();
+
+        let mut mod_definition = vec![format!("type: {}", meta.type_ref)];
+        if !meta.bootstrap.is_empty() {
+            mod_definition.push(format!("bootstrap: [{}]", meta.bootstrap.join(", ")));
+        }
+        let mod_def = format!(
+            "i0.{}({{ {} }})",
+            define_ng_module_name,
+            mod_definition.join(", ")
+        );
+
+        let fac_def = format!(
+            "function {}_Factory(__ngFactoryType__) {{ return new (__ngFactoryType__ || {})(); }}",
+            analysis.factory_meta.name, analysis.factory_meta.type_ref
+        );
+
+        vec![
+            CompileResult {
+                name: "ɵfac".to_string(),
+                initializer: Some(fac_def),
+                statements: vec![],
+                type_desc: format!("i0.ɵɵFactoryDeclaration<{}, never>", meta.type_ref),
+                deferrable_imports: None,
+                diagnostics: Vec::new(),
+                additional_imports: Vec::new(),
+            },
+            CompileResult {
+                name: "ɵmod".to_string(),
+                initializer: Some(mod_def),
+                statements: vec![],
+                type_desc: format!(
+                    "i0.ɵɵNgModuleDeclaration<{}, never, never, never>",
+                    meta.type_ref
+                ),
+                deferrable_imports: None,
+                diagnostics: Vec::new(),
+                additional_imports: Vec::new(),
+            },
+            Self::compile_injector(analysis, resolution),
+        ]
+    }
+
+    /// Compile the injector definition of a module.
+    ///
+    /// The providers and imports are kept as written, so a `ModuleWithProviders` such as
+    /// `FeatureModule.forRoot(config)` is called when the injector is created.
+    /// Generates: ɵɵdefineInjector({ providers: [...], imports: [...] })
+    pub fn compile_injector(
+        analysis: &NgModuleAnalysis,
+        resolution: Option<&NgModuleResolution>,
+    ) -> CompileResult {
+        let define_injector_name = Identifiers::define_injector().name.unwrap_or_default();
+        let imports = resolution
+            .map(|r| &r.injector_imports)
+            .unwrap_or(&analysis.imports);
+
+        let mut definition = Vec::new();
+        if let Some(providers) = &analysis.providers {
+            definition.push(format!("providers: {}", providers));
+        }
+        if !imports.is_empty() {
+            definition.push(format!("imports: [{}]", imports.join(", ")));
+        }
+        let inj_def = if definition.is_empty() {
+            format!("i0.{}({{}})", define_injector_name)
+        } else {
+            format!(
+                "i0.{}({{ {} }})",
+                define_injector_name,
+                definition.join(", ")
+            )
+        };
+
+        CompileResult {
+            name: "ɵinj".to_string(),
+            initializer: Some(inj_def),
+            statements: vec![],
+            type_desc: format!(
+                "i0.ɵɵInjectorDeclaration<{}>",
+                analysis.module_meta.type_ref
+            ),
+            deferrable_imports: None,
+            diagnostics: Vec::new(),
+            additional_imports: Vec::new(),
+        }
+    }
+
     /// Extract declarations from analysis.
     #[allow(dead_code)]
     fn resolve_type_list(
@@ -219,58 +337,43 @@ impl DecoratorHandler<NgModuleAnalysis, NgModuleAnalysis, NgModuleSymbol, NgModu
         &self,
         _node: &ClassDeclaration,
         analysis: &NgModuleAnalysis,
-        _resolution: Option<&NgModuleResolution>,
+        resolution: Option<&NgModuleResolution>,
         _constant_pool: &mut crate::ngtsc::transform::src::api::ConstantPool,
     ) -> Vec<CompileResult> {
-        let meta = &analysis.module_meta;
+        Self::compile_ng_module(analysis, resolution)
+    }
+}
 
-        // Use R3Identifiers
-        let define_ng_module_name = Identifiers::define_ng_module().name.unwrap_or_default();
-        let define_injector_name = Identifiers::define_injector().name.unwrap_or_default();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Generate ɵmod definition
-        let mod_def = format!(
-            "static ɵmod = {}({{ type: {} }});",
-            define_ng_module_name, meta.type_ref
+    #[test]
+    fn test_compile_injector_without_providers() {
+        let analysis = NgModuleAnalysis::new("AppModule");
+
+        let result = NgModuleDecoratorHandler::compile_injector(&analysis, None);
+        assert_eq!(
+            result.initializer.as_deref(),
+            Some("i0.ɵɵdefineInjector({})")
         );
+    }
 
-        // Generate ɵinj definition
-        let inj_def = format!("static ɵinj = {}({{}});", define_injector_name);
+    #[test]
+    fn test_compile_injector_keeps_module_with_providers_imports() {
+        let mut analysis = NgModuleAnalysis::new("AppModule");
+        analysis.imports = vec![
+            "CommonModule".to_string(),
+            "FeatureModule.forRoot(config)".to_string(),
+        ];
+        analysis.providers = Some("[AppService, [LoggerService]]".to_string());
 
-        // Generate factory
-        let fac_def = format!(
-            "static ɵfac = function {}Factory(t) {{ return new (t || {})(); }};",
-            analysis.factory_meta.name, analysis.factory_meta.name
+        let result = NgModuleDecoratorHandler::compile_injector(&analysis, None);
+        assert_eq!(
+            result.initializer.as_deref(),
+            Some(
+                "i0.ɵɵdefineInjector({ providers: [AppService, [LoggerService]], imports: [CommonModule, FeatureModule.forRoot(config)] })"
+            )
         );
-
-        vec![
-            CompileResult {
-                name: "ɵmod".to_string(),
-                initializer: Some(mod_def),
-                statements: vec![],
-                type_desc: "NgModuleDef".to_string(),
-                deferrable_imports: None,
-                diagnostics: Vec::new(),
-                additional_imports: Vec::new(),
-            },
-            CompileResult {
-                name: "ɵinj".to_string(),
-                initializer: Some(inj_def),
-                statements: vec![],
-                type_desc: "InjectorDef".to_string(),
-                deferrable_imports: None,
-                diagnostics: Vec::new(),
-                additional_imports: Vec::new(),
-            },
-            CompileResult {
-                name: "ɵfac".to_string(),
-                initializer: Some(fac_def),
-                statements: vec![],
-                type_desc: "Factory".to_string(),
-                deferrable_imports: None,
-                diagnostics: Vec::new(),
-                additional_imports: Vec::new(),
-            },
-        ]
     }
 }
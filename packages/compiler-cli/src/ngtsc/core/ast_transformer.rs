@@ -8,29 +8,29 @@
 use oxc_allocator::{Allocator, CloneIn};
 use oxc_ast::ast::*;
 use oxc_ast::AstBuilder;
+use oxc_ast_visit::VisitMut;
 use oxc_span::SPAN;
+use std::collections::HashSet;
 
-/// Represents a static property that failed to be parsed and needs to be injected via string manipulation
-#[derive(Debug, Clone)]
-pub struct FailedProperty {
-    pub class_name: String,
-    pub prop_name: String,
-    pub placeholder: String,
-    pub expr: String,
+/// Resets the spans of nodes parsed from generated code. They point into that code rather than
+/// into the source file, which breaks source maps and the placement of comments.
+struct ClearSpans;
+
+impl<'a> VisitMut<'a> for ClearSpans {
+    fn visit_span(&mut self, span: &mut Span) {
+        *span = SPAN;
+    }
 }
 
 /// Transform a parsed program to add Ivy compilation results
-/// Returns Vec of properties that couldn't be parsed and need to be injected into class bodies
 pub fn transform_component_ast<'a>(
     allocator: &'a Allocator,
     program: &mut Program<'a>,
     component_name: &str,
     hoisted_statements: &'a str,
-    fac_expr_str: &'a str,
-    def_expr_str: &'a str,
-    def_name: &str,
+    definitions: &[(&str, &'a str)],
     additional_imports: &[(String, String)],
-) -> Vec<FailedProperty> {
+) {
     // 1. Remove Angular decorators from the class
     remove_angular_decorators(program, component_name);
 
@@ -46,17 +46,8 @@ pub fn transform_component_ast<'a>(
     // 3. Add hoisted statements to program body (after all imports)
     add_hoisted_statements(allocator, program, hoisted_statements);
 
-    // 4. Add ɵfac and ɵcmp/ɵdir as static properties to the class
-    let raw_suffix = add_static_properties_to_class(
-        allocator,
-        program,
-        component_name,
-        fac_expr_str,
-        def_expr_str,
-        def_name,
-    );
-
-    raw_suffix
+    // 4. Add ɵfac and ɵcmp/ɵdir (or ɵmod and ɵinj) as static properties to the class
+    add_static_properties_to_class(allocator, program, component_name, definitions);
 }
 
 /// Remove Angular decorators (@Component, @Directive, etc.) from class declarations
@@ -193,77 +184,44 @@ fn add_hoisted_statements<'a>(
             .unwrap_or(0);
 
         // Add statements after the last import (or at the beginning if no imports)
-        for (idx, stmt) in parse_result.program.body.into_iter().enumerate() {
+        for (idx, mut stmt) in parse_result.program.body.into_iter().enumerate() {
+            ClearSpans.visit_statement(&mut stmt);
             program.body.insert(last_import_index + idx, stmt);
         }
     }
 }
 
-/// Adds static properties to the class. Returns Vec<FailedProperty> with properties
-/// that couldn't be parsed and need to be injected into class bodies via string manipulation.
+/// Adds static properties to the class. Properties that can't be parsed as class members are
+/// assigned to the class in a statement after it instead.
 fn add_static_properties_to_class<'a>(
     allocator: &'a Allocator,
     program: &mut Program<'a>,
     component_name: &str,
-    fac_expr_str: &'a str,
-    def_expr_str: &'a str,
-    def_name: &str,
-) -> Vec<FailedProperty> {
-    // Track which properties failed to be added as class members
-    let mut failed_fac = false;
-    let mut failed_def = false;
+    definitions: &[(&str, &'a str)],
+) {
+    // Definitions that failed to be added as class members
+    let mut failed = Vec::new();
     let mut class_found = false;
     let mut class_stmt_idx = None;
 
     // Find the class declaration and add static properties
     for (idx, stmt) in program.body.iter_mut().enumerate() {
-        match stmt {
-            Statement::ExportNamedDeclaration(export_decl) => {
-                if let Some(Declaration::ClassDeclaration(class)) = &mut export_decl.declaration {
-                    if let Some(class_id) = &class.id {
-                        let c_name = class_id.name.as_str();
-                        if c_name == component_name {
-                            let (f_ok, d_ok) = add_properties_to_class_body(
-                                allocator,
-                                class,
-                                fac_expr_str,
-                                def_expr_str,
-                                def_name,
-                            );
-                            failed_fac = !f_ok;
-                            failed_def = !d_ok;
-                            class_found = true;
-                            class_stmt_idx = Some(idx);
-                            break;
-                        }
-                    }
-                }
-            }
-
-            Statement::ClassDeclaration(class) => {
-                if let Some(class_id) = &class.id {
-                    if class_id.name.as_str() == component_name {
-                        let (f_ok, d_ok) = add_properties_to_class_body(
-                            allocator,
-                            class,
-                            fac_expr_str,
-                            def_expr_str,
-                            def_name,
-                        );
-                        failed_fac = !f_ok;
-                        failed_def = !d_ok;
-                        class_found = true;
-                        class_stmt_idx = Some(idx);
-                        break;
-                    }
-                }
-            }
-            _ => {}
+        let class = match stmt {
+            Statement::ExportNamedDeclaration(export_decl) => match &mut export_decl.declaration {
+                Some(Declaration::ClassDeclaration(class)) => class,
+                _ => continue,
+            },
+            Statement::ClassDeclaration(class) => class,
+            _ => continue,
+        };
+        if class.id.as_ref().map(|id| id.name.as_str()) == Some(component_name) {
+            failed = add_properties_to_class_body(allocator, class, definitions);
+            class_found = true;
+            class_stmt_idx = Some(idx);
+            break;
         }
     }
 
-    let mut failed_properties = Vec::new();
-
     if class_found {
         use oxc_parser::Parser;
         use oxc_span::SourceType;
@@ -279,47 +237,17 @@ fn add_static_properties_to_class<'a>(
             }
         }
 
-        // Handle failed ɵfac
-        if failed_fac && !fac_expr_str.is_empty() {
-            let fac_stripped = strip_pure(fac_expr_str);
-            let fac_stmt_str = format!("{}.ɵfac = {};", component_name, fac_stripped);
-            let fac_stmt_ptr = allocator.alloc_str(&fac_stmt_str);
-            let parser = Parser::new(allocator, fac_stmt_ptr, source_type);
-            let result = parser.parse();
-            if result.errors.is_empty() {
-                for stmt in result.program.body {
-                    stmts_to_insert.push(stmt);
-                }
-                add_placeholder_to_class(
-                    allocator,
-                    program,
-                    component_name,
-                    "ɵfac",
-                    fac_expr_str,
-                    &mut failed_properties,
-                );
-            }
-        }
-
-        // Handle failed definition (ɵcmp/ɵdir)
-        if failed_def && !def_expr_str.is_empty() {
-            let def_stripped = strip_pure(def_expr_str);
-            let def_stmt_str = format!("{}.{} = {};", component_name, def_name, def_stripped);
-            let def_stmt_ptr = allocator.alloc_str(&def_stmt_str);
-            let parser = Parser::new(allocator, def_stmt_ptr, source_type);
+        // Assign the failed definitions after the class instead
+        for (name, expr_str) in failed {
+            let stmt_str = format!("{}.{} = {};", component_name, name, strip_pure(expr_str));
+            let stmt_ptr = allocator.alloc_str(&stmt_str);
+            let parser = Parser::new(allocator, stmt_ptr, source_type);
             let result = parser.parse();
             if result.errors.is_empty() {
-                for stmt in result.program.body {
+                for mut stmt in result.program.body {
+                    ClearSpans.visit_statement(&mut stmt);
                     stmts_to_insert.push(stmt);
                 }
-                add_placeholder_to_class(
-                    allocator,
-                    program,
-                    component_name,
-                    def_name,
-                    def_expr_str,
-                    &mut failed_properties,
-                );
             }
         }
 
@@ -330,82 +258,16 @@ fn add_static_properties_to_class<'a>(
             }
         }
     }
-
-    failed_properties
 }
 
-/// Helper to add a placeholder static property to a class in the AST
-fn add_placeholder_to_class<'a>(
-    allocator: &'a Allocator,
-    program: &mut Program<'a>,
-    class_name: &str,
-    prop_name: &str,
-    expr: &str,
-    failed_properties: &mut Vec<FailedProperty>,
-) {
-    let ast = AstBuilder::new(allocator);
-    let placeholder = format!("__NG_RAW_{}_{}__", class_name, prop_name);
-    let placeholder_expr = format!("'{}'", placeholder);
-
-    // Find class again and push PropertyDefinition
-    for stmt in program.body.iter_mut() {
-        let class = match stmt {
-            Statement::ExportNamedDeclaration(export_decl) => {
-                if let Some(Declaration::ClassDeclaration(class)) = &mut export_decl.declaration {
-                    if class.id.as_ref().map(|id| id.name.as_str()) == Some(class_name) {
-                        Some(class)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            Statement::ClassDeclaration(class) => {
-                if class.id.as_ref().map(|id| id.name.as_str()) == Some(class_name) {
-                    Some(class)
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        };
-
-        if let Some(class) = class {
-            if let Some(prop_def) = create_static_property(
-                allocator,
-                &ast,
-                prop_name,
-                allocator.alloc_str(&placeholder_expr),
-            ) {
-                class
-                    .body
-                    .body
-                    .push(ClassElement::PropertyDefinition(prop_def));
-
-                failed_properties.push(FailedProperty {
-                    class_name: class_name.to_string(),
-                    prop_name: prop_name.to_string(),
-                    placeholder,
-                    expr: expr.to_string(),
-                });
-            }
-            return;
-        }
-    }
-}
-
-/// Returns (fac_success, def_success) indicating which properties were successfully added
-fn add_properties_to_class_body<'a>(
+/// Returns the definitions that could not be added as static properties
+fn add_properties_to_class_body<'a, 'd>(
     allocator: &'a Allocator,
     class: &mut Class<'a>,
-    fac_expr_str: &'a str,
-    def_expr_str: &'a str,
-    prop_name: &str,
-) -> (bool, bool) {
+    definitions: &[(&'d str, &'a str)],
+) -> Vec<(&'d str, &'a str)> {
     let ast = AstBuilder::new(allocator);
-    let mut f_ok = false;
-    let mut d_ok = false;
+    let mut failed = Vec::new();
 
     // Find the last non-constructor method to insert static properties after it
     // This ensures ɵfac and ɵdir are at the very end, after all methods
@@ -432,37 +294,24 @@ fn add_properties_to_class_body<'a>(
         }
     }
 
-    if !fac_expr_str.is_empty() {
-        if let Some(mut prop_def) =
-            create_static_property(allocator, &ast, "ɵfac", allocator.alloc_str(fac_expr_str))
-        {
-            prop_def.span = oxc_ast::ast::Span::default();
-            class
-                .body
-                .body
-                .insert(insert_position, ClassElement::PropertyDefinition(prop_def));
-            f_ok = true;
-            insert_position += 1; // Update for next insertion
+    for &(name, expr_str) in definitions {
+        if expr_str.is_empty() {
+            continue;
         }
-    }
-
-    if !def_expr_str.is_empty() {
-        if let Some(mut prop_def) = create_static_property(
-            allocator,
-            &ast,
-            prop_name,
-            allocator.alloc_str(def_expr_str),
-        ) {
-            prop_def.span = oxc_ast::ast::Span::default();
-            class
-                .body
-                .body
-                .insert(insert_position, ClassElement::PropertyDefinition(prop_def));
-            d_ok = true;
+        match create_static_property(allocator, &ast, name, allocator.alloc_str(expr_str)) {
+            Some(mut prop_def) => {
+                ClearSpans.visit_property_definition(&mut prop_def);
+                class
+                    .body
+                    .body
+                    .insert(insert_position, ClassElement::PropertyDefinition(prop_def));
+                insert_position += 1; // Update for next insertion
+            }
+            None => failed.push((name, expr_str)),
         }
     }
 
-    (f_ok, d_ok)
+    failed
 }
 
 /// Create a PropertyDefinition by parsing the expression standalone, then building the property
@@ -688,6 +537,51 @@ fn ensure_imports<'a>(
     }
 }
 
+/// Local names of the imports that are referenced exactly once in the original source, i.e. only
+/// from the decorator that lists them.
+pub fn single_use_imports(
+    program: &Program<'_>,
+    scoping: &oxc_semantic::Scoping,
+) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for stmt in &program.body {
+        let Statement::ImportDeclaration(import) = stmt else {
+            continue;
+        };
+        for spec in import.specifiers.iter().flatten() {
+            let name = spec.local().name.as_str();
+            let single_use = scoping
+                .get_root_binding(name)
+                .is_some_and(|id| scoping.get_resolved_reference_ids(id).len() == 1);
+            if single_use {
+                names.insert(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Remove the import specifiers whose symbols are now loaded through a dynamic `import()` in a
+/// defer dependency function. Import declarations left without specifiers are dropped.
+pub fn remove_deferred_imports(program: &mut Program<'_>, names: &HashSet<String>) {
+    if names.is_empty() {
+        return;
+    }
+    program.body.retain_mut(|stmt| {
+        let Statement::ImportDeclaration(import) = stmt else {
+            return true;
+        };
+        let Some(specifiers) = &mut import.specifiers else {
+            return true;
+        };
+        if specifiers.is_empty() {
+            return true;
+        }
+        specifiers.retain(|spec| !names.contains(spec.local().name.as_str()));
+        !specifiers.is_empty()
+    });
+}
+
 /// Ensure import * as i0 from '@angular/core' exists
 fn ensure_angular_core_import<'a>(allocator: &'a Allocator, program: &mut Program<'a>) {
     ensure_imports(
@@ -700,8 +594,44 @@ fn ensure_angular_core_import<'a>(allocator: &'a Allocator, program: &mut Progra
 #[cfg(test)]
 mod tests {
 
+    use super::*;
+    use oxc_span::SourceType;
+
     #[test]
     fn test_decorator_name_extraction() {
         // Test would go here
     }
+
+    #[test]
+    fn test_remove_deferred_imports() {
+        let source = "import { Component } from '@angular/core';\n\
+                      import { LazyCmp } from './lazy.component';\n\
+                      import { Shared, Util } from './shared';\n\
+                      @Component({ imports: [LazyCmp, Shared] })\n\
+                      export class AppCmp { run() { return Shared; } }\n";
+        let allocator = Allocator::default();
+        let mut program = oxc_parser::Parser::new(&allocator, source, SourceType::ts())
+            .parse()
+            .program;
+
+        let semantic = oxc_semantic::SemanticBuilder::new().build(&program);
+        let single_use = single_use_imports(&program, semantic.semantic.scoping());
+        assert_eq!(
+            single_use,
+            HashSet::from(["Component".to_string(), "LazyCmp".to_string()])
+        );
+
+        // `Shared` is also used outside the decorator and must stay.
+        let deferred: HashSet<String> = ["LazyCmp", "Shared"]
+            .iter()
+            .map(|name| name.to_string())
+            .filter(|name| single_use.contains(name))
+            .collect();
+        remove_deferred_imports(&mut program, &deferred);
+
+        let code = oxc_codegen::Codegen::new().build(&program).code;
+        assert!(!code.contains("LazyCmp } from"));
+        assert!(!code.contains("./lazy.component"));
+        assert!(code.contains("import { Shared, Util } from \"./shared\""));
+    }
 }
@@ -1,24 +1,78 @@
+use crate::ngtsc::annotations::common::{PreloadFuture, ResourceLoader};
 use crate::ngtsc::annotations::component::src::handler::ComponentDecoratorHandler;
+use crate::ngtsc::annotations::component::src::{
+    extract_template, parse_template_declaration, preload_component_resources,
+    ExtractTemplateOptions,
+};
 use crate::ngtsc::annotations::directive::src::handler::DirectiveDecoratorHandler;
+use crate::ngtsc::annotations::ng_module::src::{NgModuleAnalysis, NgModuleDecoratorHandler};
+use crate::ngtsc::core::unused_imports::unused_import_diagnostics;
 use crate::ngtsc::core::NgCompilerOptions;
+use crate::ngtsc::diagnostics::ErrorCode;
+use crate::ngtsc::entry_point::{find_flat_index_entry_point, get_reexports, FlatIndexGenerator};
 use crate::ngtsc::file_system::{AbsoluteFsPath, FileSystem};
+use crate::ngtsc::imports::src::resolver::ModuleResolver;
+use crate::ngtsc::logging::{Logger, SilentLogger};
 use crate::ngtsc::metadata::{
-    DecoratorMetadata, DirectiveMetadata, MetadataReader, OxcMetadataReader,
+    validate_host_directive_bindings, DecoratorMetadata, DirectiveMetadata, InjectableMeta,
+    MetadataReader, OxcMetadataReader,
+};
+use crate::ngtsc::perf::{PerfPhase, PerfRecorder};
+use crate::ngtsc::shims::{get_exported_classes, is_shim, ShimGenerator};
+use crate::ngtsc::transform::src::api::CompileResult;
+use crate::ngtsc::transform::src::{
+    emit_declaration_file, IvyDeclarationDtsTransform, IvyDeclarationField,
 };
+use crate::ngtsc::validation::check_duplicate_references;
+use angular_compiler::compiler::{compile_injectable, CompilerConfig, R3InjectableMetadata};
 use angular_compiler::ml_parser::tags::TagDefinition;
 use angular_compiler::ml_parser::{
     html_tags::get_html_tag_definition, parser::Parser as HtmlParser,
 };
+use angular_compiler::output::abstract_emitter::EmitterVisitorContext;
+use angular_compiler::output::abstract_js_emitter::AbstractJsEmitterVisitor;
+use angular_compiler::output::output_ast::{self as o, ExpressionTrait};
+use angular_compiler::render3::r3_factory::R3DependencyMetadata;
+use angular_compiler::render3::r3_template_transform::{
+    html_ast_to_render3_ast, Render3ParseOptions,
+};
+use angular_compiler::render3::util::{ForwardRefHandling, MaybeForwardRefExpression, R3Reference};
+use angular_compiler::render3::view::template::make_binding_parser;
 use oxc_allocator::Allocator;
 use oxc_parser::Parser;
 use oxc_span::SourceType;
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::any::Any;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
 
 fn get_html_tag_definition_wrapper(name: &str) -> &'static dyn TagDefinition {
     get_html_tag_definition(name)
 }
 
+/// Wakes the thread blocked in `block_on`.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a resource preload to completion on the current thread.
+fn block_on(mut future: PreloadFuture) -> Result<(), String> {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => return result,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
 pub enum CompilationTicketKind {
     Fresh,
     Incremental,
@@ -34,6 +88,12 @@ pub struct NgCompiler<'a, T: FileSystem> {
     pub options: NgCompilerOptions,
     pub fs: &'a T,
     pub is_core: bool,
+    /// Time spent in each phase, tracked when `tracePerformance` is enabled.
+    pub perf_recorder: PerfRecorder,
+    /// Where progress and non-fatal problems of the compilation are reported.
+    pub logger: Arc<dyn Logger + Send + Sync>,
+    /// Loads external templates and stylesheets. Without one they are read from `fs`.
+    pub resource_loader: Option<Arc<dyn ResourceLoader>>,
 }
 
 #[derive(Default)]
@@ -45,17 +105,116 @@ pub struct CompilationResult {
 
 impl<'a, T: FileSystem> NgCompiler<'a, T> {
     pub fn new(ticket: CompilationTicket<'a, T>) -> Self {
+        let perf_recorder = if ticket.options.trace_performance {
+            PerfRecorder::new()
+        } else {
+            PerfRecorder::noop()
+        };
         NgCompiler {
             options: ticket.options,
             fs: ticket.fs,
             is_core: false,
+            perf_recorder,
+            logger: Arc::new(SilentLogger::new()),
+            resource_loader: None,
+        }
+    }
+
+    pub fn with_logger(mut self, logger: Arc<dyn Logger + Send + Sync>) -> Self {
+        self.logger = logger;
+        self
+    }
+
+    pub fn with_resource_loader(mut self, resource_loader: Arc<dyn ResourceLoader>) -> Self {
+        self.resource_loader = Some(resource_loader);
+        self
+    }
+
+    /// How templates are compiled for these options.
+    fn compiler_config(&self) -> CompilerConfig {
+        CompilerConfig {
+            debug: self.options.enable_debug_locations,
+            ..CompilerConfig::default()
+        }
+    }
+
+    /// Preload the external template and stylesheets of a component before they are loaded
+    /// during analysis, like the `preanalyze` step of Angular's `analyzeAsync`.
+    fn preload_resources(
+        &self,
+        containing_file: &str,
+        class_name: &str,
+        template_url: Option<&str>,
+        style_urls: &[String],
+    ) {
+        let Some(loader) = &self.resource_loader else {
+            return;
+        };
+        let preloads = preload_component_resources(
+            loader.as_ref(),
+            containing_file,
+            class_name,
+            template_url,
+            style_urls,
+        )
+        .and_then(|futures| futures.into_iter().try_for_each(block_on));
+        if let Err(e) = preloads {
+            self.logger.warn(&e);
+        }
+    }
+
+    /// Resolve `url` relative to `containing_file` and read the resource it points to.
+    fn load_resource(
+        &self,
+        url: &str,
+        containing_file: &str,
+    ) -> Result<(AbsoluteFsPath, String), String> {
+        match &self.resource_loader {
+            Some(loader) => {
+                let resolved = loader.resolve(url, containing_file)?;
+                let content = loader.load(&resolved)?;
+                Ok((AbsoluteFsPath::new(resolved), content))
+            }
+            None => {
+                let path = self.fs.resolve(&[&self.fs.dirname(containing_file), url]);
+                let content = self.fs.read_file(&path).map_err(|e| e.to_string())?;
+                Ok((path, content))
+            }
         }
     }
 
     pub fn analyze_async(&mut self, root_names: &[String]) -> Result<CompilationResult, String> {
+        let (mut result, templates) = self
+            .perf_recorder
+            .in_phase(PerfPhase::Analysis, || self.analyze_files(root_names))?;
+        self.perf_recorder.in_phase(PerfPhase::TypeCheck, || {
+            result
+                .diagnostics
+                .extend(validate_host_directive_bindings(&result.directives));
+            if self.options.report_unused_imports {
+                result
+                    .diagnostics
+                    .extend(unused_import_diagnostics(&result.directives, &templates));
+            }
+        });
+        self.logger.info(&format!(
+            "Analyzed {} files, found {} decorated classes",
+            result.files.len(),
+            result.directives.len()
+        ));
+        Ok(result)
+    }
+
+    /// Extract the metadata of the root files, along with the template sources by component name.
+    fn analyze_files(
+        &self,
+        root_names: &[String],
+    ) -> Result<(CompilationResult, HashMap<String, String>), String> {
         // eprintln!("DEBUG: NgCompiler::analyze_async called with {} root files", root_names.len());
         let mut result = CompilationResult::default();
         let metadata_reader = OxcMetadataReader;
+        // Template sources by component name, for the unused imports report.
+        let mut templates: HashMap<String, String> = HashMap::new();
 
         for file in root_names {
             let path = PathBuf::from(file);
@@ -95,22 +254,37 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
                         if !dir.t2.is_component {
                             continue;
                         }
+                        if let Some(comp) = &dir.component {
+                            self.preload_resources(
+                                abs_path.as_str(),
+                                &dir.t2.name,
+                                comp.template_url.as_deref(),
+                                comp.style_urls.as_deref().unwrap_or_default(),
+                            );
+                        }
 
                         let template_str = if let Some(comp) = &dir.component {
                             if let Some(template) = &comp.template {
-                                Some(template.clone())
+                                let declaration = parse_template_declaration(
+                                    Some(template),
+                                    None,
+                                    dir.t2.preserve_whitespaces,
+                                )
+                                .with_source_offset(comp.template_offset.unwrap_or(0));
+                                Some((template.clone(), declaration, abs_path.clone()))
                             } else if let Some(template_url) = &comp.template_url {
-                                let component_dir = self.fs.dirname(abs_path.as_str());
-                                let template_path =
-                                    self.fs.resolve(&[&component_dir, template_url]);
-                                // eprintln!("DEBUG: Resolving template URL '{}' relative to '{}' -> '{}'", template_url, component_dir, template_path);
-                                match self.fs.read_file(&template_path) {
-                                    Ok(content) => {
+                                match self.load_resource(template_url, abs_path.as_str()) {
+                                    Ok((template_path, content)) => {
                                         // eprintln!("DEBUG: Successfully read template file: {}", template_path);
-                                        Some(content)
+                                        let declaration = parse_template_declaration(
+                                            None,
+                                            Some(template_url),
+                                            dir.t2.preserve_whitespaces,
+                                        );
+                                        Some((content, declaration, template_path))
                                     }
                                     Err(e) => {
-                                        // eprintln!("DEBUG: Failed to read template file: {} (Error: {})", template_path, e);
+                                        // eprintln!("DEBUG: Failed to read template file: {}", e);
                                         None
                                     }
                                 }
@@ -121,12 +295,90 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
                             None
                         };
 
-                        if let Some(template) = template_str {
+                        if let Some((template, declaration, template_file)) = template_str {
+                            templates.insert(dir.t2.name.clone(), template.clone());
                             let parser = HtmlParser::new(get_html_tag_definition_wrapper);
-                            let parse_result = parser.parse(&template, "template.html", None);
-
-                            if !parse_result.errors.is_empty() {
-                            } else {
+                            let parse_result =
+                                parser.parse(&template, template_file.as_str(), None);
+
+                            // Report errors against the file the template came from, at the
+                            // position inside the `template` literal for inline templates.
+                            let template_diagnostics: Vec<crate::ngtsc::core::Diagnostic> =
+                                if !parse_result.errors.is_empty() {
+                                    parse_result
+                                        .errors
+                                        .iter()
+                                        .map(|error| crate::ngtsc::core::Diagnostic {
+                                            category:
+                                                crate::transformers::api::DiagnosticCategory::Error,
+                                            file: Some(PathBuf::from(template_file.as_str())),
+                                            message: error.msg.clone(),
+                                            code: ErrorCode::TemplateParseError as usize,
+                                            start: Some(error.span.start.offset),
+                                            length: Some(
+                                                error
+                                                    .span
+                                                    .end
+                                                    .offset
+                                                    .saturating_sub(error.span.start.offset),
+                                            ),
+                                            related_information: Vec::new(),
+                                        })
+                                        .collect()
+                                } else {
+                                    let nodes = html_ast_to_render3_ast(
+                                        &parse_result.root_nodes,
+                                        &mut make_binding_parser(false),
+                                        &Render3ParseOptions::default(),
+                                    )
+                                    .nodes;
+                                    check_duplicate_references(template_file.as_str(), &nodes)
+                                        .into_iter()
+                                        .map(|error| crate::ngtsc::core::Diagnostic {
+                                            category:
+                                                crate::transformers::api::DiagnosticCategory::Error,
+                                            file: Some(PathBuf::from(template_file.as_str())),
+                                            message: error.message,
+                                            code: ErrorCode::DuplicateVariableDeclaration as usize,
+                                            start: error.start,
+                                            length: error.length,
+                                            related_information: error
+                                                .related_information
+                                                .into_iter()
+                                                .flatten()
+                                                .map(|info| {
+                                                    crate::ngtsc::core::DiagnosticRelatedInformation {
+                                                        file: info.file.map(PathBuf::from),
+                                                        message: info.message_text,
+                                                        start: info.start,
+                                                        length: info.length,
+                                                    }
+                                                })
+                                                .collect(),
+                                        })
+                                        .collect()
+                                };
+                            if !template_diagnostics.is_empty() {
+                                let source_mapping = extract_template(
+                                    &dir.t2.name,
+                                    &declaration,
+                                    &template,
+                                    &ExtractTemplateOptions::default(),
+                                )
+                                .source_mapping;
+                                for mut diagnostic in template_diagnostics {
+                                    diagnostic.start = diagnostic
+                                        .start
+                                        .map(|start| source_mapping.to_source_offset(start));
+                                    for info in &mut diagnostic.related_information {
+                                        info.start = info
+                                            .start
+                                            .map(|start| source_mapping.to_source_offset(start));
+                                    }
+                                    result.diagnostics.push(diagnostic);
+                                }
+                            }
+                            if parse_result.errors.is_empty() {
                                 if let Some(comp) = &mut dir.component {
                                     comp.template_ast = Some(parse_result.root_nodes);
                                 }
@@ -135,7 +387,6 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
 
                         let style_urls = dir.component.as_ref().and_then(|c| c.style_urls.clone());
                         if let Some(style_urls) = style_urls {
-                            let component_dir = self.fs.dirname(abs_path.as_str());
                             let mut resolved_styles = if let Some(comp) = &mut dir.component {
                                 comp.styles.take().unwrap_or_default()
                             } else {
@@ -143,10 +394,10 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
                             };
 
                             for url in style_urls {
-                                let style_path = self.fs.resolve(&[&component_dir, &url]);
-                                match self.fs.read_file(&style_path) {
-                                    Ok(content) => resolved_styles.push(content),
-                                    Err(_) => {}
+                                if let Ok((_, content)) =
+                                    self.load_resource(&url, abs_path.as_str())
+                                {
+                                    resolved_styles.push(content);
                                 }
                             }
                             if let Some(comp) = &mut dir.component {
@@ -156,23 +407,70 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
                     }
                 }
 
+                for directive in &directives {
+                    if let DecoratorMetadata::Directive(dir) = directive {
+                        result.diagnostics.extend(dir.diagnostics.iter().cloned());
+                    }
+                }
                 result.directives.extend(directives);
                 result.files.push(path);
             }
         }
-        Ok(result)
+        Ok((result, templates))
     }
 
     pub fn emit(
         &self,
         compilation_result: &CompilationResult,
+    ) -> Result<Vec<crate::ngtsc::core::Diagnostic>, String> {
+        self.perf_recorder.in_phase(PerfPhase::Emit, || {
+            self.emit_files(compilation_result, None)
+        })
+    }
+
+    /// Emit like [`NgCompiler::emit`], but write the JavaScript of each source file to the path
+    /// returned by `output_path` instead of deriving it from `outDir`.
+    ///
+    /// Declaration files and shims are written next to that path.
+    pub fn emit_with_output_paths(
+        &self,
+        compilation_result: &CompilationResult,
+        output_path: &(dyn Fn(&std::path::Path) -> PathBuf + Sync),
+    ) -> Result<Vec<crate::ngtsc::core::Diagnostic>, String> {
+        self.perf_recorder.in_phase(PerfPhase::Emit, || {
+            self.emit_files(compilation_result, Some(output_path))
+        })
+    }
+
+    fn emit_files(
+        &self,
+        compilation_result: &CompilationResult,
+        output_path: Option<&(dyn Fn(&std::path::Path) -> PathBuf + Sync)>,
     ) -> Result<Vec<crate::ngtsc::core::Diagnostic>, String> {
         use oxc_ast::ast::*;
+        let js_output_path = |file: &std::path::Path| match output_path {
+            Some(output_path) => output_path(file),
+            None => self.output_path(file).with_extension("js"),
+        };
+        if output_path.is_none() {
+            let diagnostics = self.root_dir_diagnostics(&compilation_result.files);
+            if !diagnostics.is_empty() {
+                return Ok(diagnostics);
+            }
+        }
+        if self.options.emit_declaration_only {
+            return Ok(self.emit_declarations(compilation_result, &js_output_path));
+        }
+
         let mut result_diagnostics: Vec<crate::ngtsc::core::Diagnostic> = Vec::new();
         let fs = self.fs;
+        let logger = &self.logger;
 
         let component_handler =
-            crate::ngtsc::annotations::component::src::handler::ComponentDecoratorHandler::new();
+            crate::ngtsc::annotations::component::src::handler::ComponentDecoratorHandler::new()
+                .with_config(self.compiler_config())
+                .with_hmr(self.options.hmr)
+                .with_file_system(fs);
         let directive_handler =
             crate::ngtsc::annotations::directive::src::handler::DirectiveDecoratorHandler::new(
                 false,
@@ -216,38 +514,11 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
             .collect::<Vec<_>>() // Collect into Vec to allow par_iter
             .into_par_iter()
             .map(|(src_file, directives_wrapper)| {
-                let directives: Vec<&DecoratorMetadata> = directives_wrapper.into_iter().map(|w| w.0).collect();
+                let directives: Vec<&DecoratorMetadata> =
+                    directives_wrapper.into_iter().map(|w| w.0).collect();
 
                 // Setup output path
-                let mut out_path = if let Some(out_dir) = &self.options.out_dir {
-                    let absolute_project_root = if let Some(root_dir) = &self.options.root_dir {
-                        let p = PathBuf::from(root_dir);
-                        std::fs::canonicalize(&p).unwrap_or(p)
-                    } else {
-                        let project_path = std::path::Path::new(&self.options.project);
-                        let project_root = project_path.parent().unwrap_or(std::path::Path::new("."));
-                        std::fs::canonicalize(project_root).unwrap_or(project_root.to_path_buf())
-                    };
-
-                    let absolute_src_file = std::fs::canonicalize(&src_file)
-                        .unwrap_or(src_file.clone());
-
-                    let relative_path = absolute_src_file
-                        .strip_prefix(&absolute_project_root)
-                        .unwrap_or_else(|_| {
-                            std::path::Path::new(src_file.file_name().unwrap())
-                        });
-
-                    let mut p = PathBuf::from(out_dir);
-                    p.push(relative_path);
-
-                    let out_file_path = p.with_extension("js");
-                    out_file_path
-                } else {
-                    let mut p = PathBuf::from(&src_file);
-                    let out_file_path = p.with_extension("js");
-                    out_file_path
-                };
+                let out_path = js_output_path(&src_file);
 
                 // Ensure parent dir exists
                 if let Some(parent) = out_path.parent() {
@@ -272,6 +543,10 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
                             let semantic = oxc_semantic::SemanticBuilder::new()
                                 .with_excess_capacity(0.0)
                                 .build(&parse_result.program);
+                            let single_use_imports = super::ast_transformer::single_use_imports(
+                                &parse_result.program,
+                                semantic.semantic.scoping(),
+                            );
 
                             // Step 2: Apply TypeScript transformer to strip types
                             let transform_options = oxc_transformer::TransformOptions::default();
@@ -286,75 +561,34 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
                             );
 
                             // Step 3: AST-based Ivy transformation for ALL directives in this file
-                            // Track failed properties that couldn't be parsed (accumulated across all directives)
-                            let mut failed_properties: Vec<super::ast_transformer::FailedProperty> = Vec::new();
-                            let mut last_def_name = "ɵcmp".to_string(); // Default, will be updated for each directive
+                            // One pool per file: identical constants of different classes are hoisted once,
+                            // and names only depend on the source order of the classes.
+                            let mut constant_pool =
+                                angular_compiler::constant_pool::ConstantPool::new(false);
+                            // Imports that are only referenced by a decorator and are now loaded lazily.
+                            let mut deferred_imports = std::collections::HashSet::new();
 
                             for directive in directives {
-                                let (compiled_results, directive_name) = match directive {
-                                    DecoratorMetadata::Directive(dir) => {
-                                        let results = if dir.t2.is_component {
-                                            component_handler.compile_ivy(&directive)
-                                        } else {
-                                            directive_handler.compile_ivy(&directive)
-                                        };
-                                        (results, dir.t2.name.clone())
-                                    }
-                                    DecoratorMetadata::Pipe(pipe) => {
-                                        let initializer = format!(
-                                            "/*@__PURE__*/ i0.ɵɵdefinePipe({{ name: '{}', type: {}, pure: {}{} }})",
-                                            pipe.pipe_name, pipe.name, pipe.is_pure,
-                                            if pipe.is_standalone { ", standalone: true" } else { "" }
-                                        );
-                                        let results = vec![crate::ngtsc::transform::src::api::CompileResult {
-                                            name: "ɵpipe".to_string(),
-                                            initializer: Some(initializer),
-                                            statements: vec![],
-                                            type_desc: format!("i0.ɵɵPipeDeclaration<{}, '{}', {}>", pipe.name, pipe.pipe_name, pipe.is_standalone),
-                                            deferrable_imports: None,
-                                            diagnostics: Vec::new(),
-                                            additional_imports: Vec::new(),
-                                        }];
-                                        (results, pipe.name.clone())
-                                    }
-                                    DecoratorMetadata::Injectable(inj) => {
-                                        let provided_in = inj.provided_in.as_deref().unwrap_or("null");
-                                        let provided_in_value = if provided_in == "null" { "null".to_string() } else { format!("'{}'", provided_in) };
-                                        let fac_initializer = format!("function {}_Factory(__ngFactoryType__) {{ return new (__ngFactoryType__ || {})(); }}", inj.name, inj.name);
-                                        let prov_initializer = format!("/*@__PURE__*/ i0.ɵɵdefineInjectable({{ token: {}, factory: {}.ɵfac, providedIn: {} }})", inj.name, inj.name, provided_in_value);
-                                        let results = vec![
-                                            crate::ngtsc::transform::src::api::CompileResult {
-                                                name: "ɵfac".to_string(),
-                                                initializer: Some(fac_initializer),
-                                                statements: vec![],
-                                                type_desc: format!("i0.ɵɵFactoryDeclaration<{}, never>", inj.name),
-                                                deferrable_imports: None,
-                                                diagnostics: Vec::new(),
-                                                additional_imports: Vec::new(),
-                                            },
-                                            crate::ngtsc::transform::src::api::CompileResult {
-                                                name: "ɵprov".to_string(),
-                                                initializer: Some(prov_initializer),
-                                                statements: vec![],
-                                                type_desc: format!("i0.ɵɵInjectableDeclaration<{}>", inj.name),
-                                                deferrable_imports: None,
-                                                diagnostics: Vec::new(),
-                                                additional_imports: Vec::new(),
-                                            }
-                                        ];
-                                        (results, inj.name.clone())
-                                    }
-                                    DecoratorMetadata::NgModule(ngm) => (vec![], ngm.name.clone())
-                                };
+                                let (compiled_results, directive_name) = compile_decorator(
+                                    &component_handler,
+                                    &directive_handler,
+                                    directive,
+                                    &mut constant_pool,
+                                );
 
                                 // Collect diagnostics
                                 for r in &compiled_results {
-                                    diagnostics.extend(r.diagnostics.iter().map(|d| crate::ngtsc::core::Diagnostic {
-                                        file: d.file.clone().map(PathBuf::from),
-                                        message: d.message_text.to_string(),
-                                        code: d.code as usize,
-                                        start: Some(d.start),
-                                        length: Some(d.length),
+                                    diagnostics.extend(r.diagnostics.iter().map(|d| {
+                                        crate::ngtsc::core::Diagnostic {
+                                            category:
+                                                crate::transformers::api::DiagnosticCategory::Error,
+                                            file: d.file.clone().map(PathBuf::from),
+                                            message: d.message_text.to_string(),
+                                            code: d.code as usize,
+                                            start: Some(d.start),
+                                            length: Some(d.length),
+                                            related_information: Vec::new(),
+                                        }
                                     }));
                                 }
 
@@ -375,82 +609,112 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
                                 }
 
                                 // Prepare expressions for transform_component_ast
-                                let fac_expr_str_default = format!("function {}_Factory(t) {{ return new (t || {})(); }}", directive_name, directive_name);
-
-                                // Finding the main initializer (cmp, pipe, prov)
-                                let main_result = compiled_results.iter().find(|r| r.name == "ɵcmp" || r.name == "ɵpipe" || r.name == "ɵprov" || r.name == "ɵdir");
-                                let main_initializer = main_result.and_then(|r| r.initializer.as_deref()).unwrap_or("null");
-                                let def_name = main_result.map(|r| r.name.clone()).unwrap_or_else(|| "ɵcmp".to_string());
-                                last_def_name = def_name.clone(); // Store for post-processing
-
-                                let cmp_expr_str = format!("/*@__PURE__*/ {}", main_initializer);
-
-                                let fac_initializer = compiled_results.iter().find(|r| r.name == "ɵfac").and_then(|r| r.initializer.as_deref()).unwrap_or(&fac_expr_str_default);
-
-                                let hoisted_statements_arena: &str = allocator.alloc_str(&hoisted_statements);
-                                let fac_expr_arena: &str = allocator.alloc_str(fac_initializer); // Use correct fac logic
-                                let cmp_expr_arena: &str = allocator.alloc_str(&cmp_expr_str);
+                                let fac_expr_str_default = format!(
+                                    "function {}_Factory(t) {{ return new (t || {})(); }}",
+                                    directive_name, directive_name
+                                );
+
+                                // Finding the main initializer (cmp, pipe, prov, mod)
+                                let main_result = compiled_results.iter().find(|r| {
+                                    r.name == "ɵcmp"
+                                        || r.name == "ɵpipe"
+                                        || r.name == "ɵprov"
+                                        || r.name == "ɵdir"
+                                        || r.name == "ɵmod"
+                                });
+                                let main_initializer = main_result
+                                    .and_then(|r| r.initializer.as_deref())
+                                    .unwrap_or("null");
+
+                                let fac_initializer = compiled_results
+                                    .iter()
+                                    .find(|r| r.name == "ɵfac")
+                                    .and_then(|r| r.initializer.as_deref())
+                                    .unwrap_or(&fac_expr_str_default);
+
+                                let hoisted_statements_arena: &str =
+                                    allocator.alloc_str(&hoisted_statements);
+                                // ɵfac first, then the definitions in the order they were compiled
+                                // (e.g. ɵmod before ɵinj)
+                                let mut definitions: Vec<(&str, &str)> =
+                                    vec![("ɵfac", allocator.alloc_str(fac_initializer))];
+                                for res in compiled_results.iter().filter(|r| r.name != "ɵfac") {
+                                    if let Some(initializer) = res.initializer.as_deref() {
+                                        let expr = format!("/*@__PURE__*/ {}", initializer);
+                                        definitions.push((&res.name, allocator.alloc_str(&expr)));
+                                    }
+                                }
 
                                 // Only transform if we have something valid
                                 if main_initializer != "null" {
                                     // Use additional_imports from main result, or fallback to first result
-                                    let additional_imports = main_result.map(|r| r.additional_imports.as_slice()).unwrap_or_else(|| compiled_results[0].additional_imports.as_slice());
+                                    let additional_imports = main_result
+                                        .map(|r| r.additional_imports.as_slice())
+                                        .unwrap_or_else(|| {
+                                            compiled_results[0].additional_imports.as_slice()
+                                        });
 
-                                    let failed = super::ast_transformer::transform_component_ast(
+                                    super::ast_transformer::transform_component_ast(
                                         &allocator,
                                         &mut parse_result.program,
                                         &directive_name,
                                         hoisted_statements_arena,
-                                        fac_expr_arena, // fac
-                                        cmp_expr_arena, // cmp/pipe/prov
-                                        &def_name, // Use correct field name (ɵcmp, ɵdir, ɵpipe, or ɵprov)
+                                        &definitions,
                                         additional_imports,
                                     );
-
-                                    failed_properties.extend(failed);
                                 }
-                            }
 
-                            // Step 4: Codegen final JavaScript
-                            let codegen = oxc_codegen::Codegen::new().with_options(oxc_codegen::CodegenOptions {
-                                single_quote: true,
-                                ..oxc_codegen::CodegenOptions::default()
-                            });
-                            let mut code = codegen.build(&parse_result.program).code;
-
-                            // Replace placeholders with real expressions
-                            for prop in &failed_properties {
-                                // Codegen for string literals with single_quote: true will be 'placeholder'
-                                let placeholder_pattern = format!("'{}'", prop.placeholder);
-                                if code.contains(&placeholder_pattern) {
-                                    code = code.replace(&placeholder_pattern, &prop.expr);
+                                if let Some(deferrable) =
+                                    main_result.and_then(|r| r.deferrable_imports.as_ref())
+                                {
+                                    deferred_imports.extend(
+                                        deferrable.intersection(&single_use_imports).cloned(),
+                                    );
                                 }
                             }
+                            super::ast_transformer::remove_deferred_imports(
+                                &mut parse_result.program,
+                                &deferred_imports,
+                            );
 
-                            // Fix property names (ɵUNIQUE_FAC -> ɵfac, ɵUNIQUE_DIR -> last_def_name)
-                            code = code.replace("ɵUNIQUE_FAC", "ɵfac");
-                            code = code.replace("ɵUNIQUE_DIR", &last_def_name);
+                            // Step 4: Codegen final JavaScript
+                            let codegen = oxc_codegen::Codegen::new().with_options(
+                                oxc_codegen::CodegenOptions {
+                                    single_quote: true,
+                                    source_map_path: self
+                                        .options
+                                        .inline_source_map
+                                        .then(|| src_file.clone()),
+                                    ..oxc_codegen::CodegenOptions::default()
+                                },
+                            );
+                            // The source map describes the generated code as is, so the code
+                            // must not be edited afterwards.
+                            let output = codegen.build(&parse_result.program);
+                            let mut code = output.code;
 
+                            if let Some(map) = output.map {
+                                append_inline_source_map(&mut code, &map.to_json_string());
+                            }
                             Some(code)
                         } else {
                             // Parse error
                             None
                         }
-                    },
-                    Err(_) => None
+                    }
+                    Err(_) => None,
                 };
 
                 if let Some(content) = output_content {
                     let out_path_abs = AbsoluteFsPath::from(out_path.as_path());
-                    match fs.write_file(&out_path_abs, content.as_bytes(), None) {
-                        Ok(_) => (),
-                        Err(_) => (),
+                    if let Err(e) = fs.write_file(&out_path_abs, content.as_bytes(), None) {
+                        logger.warn(&format!("Failed to write {}: {}", out_path_abs, e));
                     }
                 }
 
                 FileResult {
                     path: src_file,
-                    diagnostics
+                    diagnostics,
                 }
             })
             .collect();
@@ -488,27 +752,9 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
                 return;
             }
 
-            if let Some(out_dir) = &self.options.out_dir {
-                // Calculate output path preserving directory structure
-                let absolute_project_root = if let Some(root_dir) = &self.options.root_dir {
-                    let p = PathBuf::from(root_dir);
-                    std::fs::canonicalize(&p).unwrap_or(p)
-                } else {
-                    let project_path = std::path::Path::new(&self.options.project);
-                    let project_root = project_path.parent().unwrap_or(std::path::Path::new("."));
-                    std::fs::canonicalize(project_root).unwrap_or(project_root.to_path_buf())
-                };
-
-                let absolute_src_file =
-                    std::fs::canonicalize(file.as_path()).unwrap_or(file.as_path().to_path_buf());
-
-                let relative_path = absolute_src_file
-                    .strip_prefix(&absolute_project_root)
-                    .unwrap_or_else(|_| std::path::Path::new(file.file_name().unwrap()));
-
-                let mut out_path = PathBuf::from(out_dir);
-                out_path.push(relative_path);
-                out_path.set_extension("js");
+            // Without an `outDir` or a custom output path, plain files aren't emitted
+            if self.options.out_dir.is_some() || output_path.is_some() {
+                let out_path = js_output_path(file);
 
                 // Ensure parent dir exists
                 if let Some(parent) = out_path.parent() {
@@ -520,56 +766,25 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
 
                 match fs.read_file(&source_path) {
                     Ok(source_content) => {
-                        // Transpilation: parse TypeScript, transform to strip types, then codegen
-                        let allocator = Allocator::default();
-                        let source_type = SourceType::ts();
                         let file_path = file.to_string_lossy().to_string();
-
-                        let parser = Parser::new(&allocator, &source_content, source_type);
-                        let mut parse_result = parser.parse();
-
-                        if !parse_result.errors.is_empty() {
+                        let Some(output) = transpile(&file_path, &source_content, self.options.inline_source_map) else {
                             return;
-                        }
-
-                        // Run semantic analysis to get scoping information
-
-                        let semantic = oxc_semantic::SemanticBuilder::new()
-                            .with_excess_capacity(0.0)
-                            .build(&parse_result.program);
-
-                        // Apply TypeScript transformer to strip types
-
-                        let transform_options = oxc_transformer::TransformOptions::default();
-                        let transformer = oxc_transformer::Transformer::new(
-                            &allocator,
-                            std::path::Path::new(&file_path),
-                            &transform_options,
-                        );
-                        let _ = transformer.build_with_scoping(
-                            semantic.semantic.into_scoping(),
-                            &mut parse_result.program,
-                        );
-
-                        // Use OXC codegen to emit JavaScript without types
-
-                        let codegen =
-                            oxc_codegen::Codegen::new().with_options(oxc_codegen::CodegenOptions {
-                                single_quote: true,
-                                ..oxc_codegen::CodegenOptions::default()
-                            });
-                        let mut js_output = codegen.build(&parse_result.program).code;
+                        };
+                        let mut js_output = output.code;
 
                         // Add signature line for main.ts
                         if file_path.ends_with("main.ts") {
                             js_output.push_str("\nconsole.log('%cAngular Rust compiler powered by Truonglv4', 'color: #00ff00; font-weight: bold;');\n");
                         }
 
+                        if let Some(map) = output.map {
+                            append_inline_source_map(&mut js_output, &map.to_json_string());
+                        }
+
                         let out_path_abs = AbsoluteFsPath::from(out_path.as_path());
 
-                        match fs.write_file(&out_path_abs, js_output.as_bytes(), None) {
-                            Ok(_) => (),
-                            Err(_) => (),
+                        if let Err(e) = fs.write_file(&out_path_abs, js_output.as_bytes(), None) {
+                            logger.warn(&format!("Failed to write {}: {}", out_path_abs, e));
                         }
                     }
                     Err(_) => {}
@@ -577,9 +792,389 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
             }
         });
 
+        if let Some(flat_module_out_file) = &self.options.flat_module_out_file {
+            result_diagnostics
+                .extend(self.emit_flat_index(flat_module_out_file, &compilation_result.files));
+        }
+
+        if self.options.generate_ng_factory_shims || self.options.generate_ng_summary_shims {
+            self.emit_shims(&compilation_result.files, &js_output_path);
+        }
+
         Ok(result_diagnostics)
     }
 
+    /// The HMR update module of the component `class_name` declared in `file`, which the dev
+    /// server serves when the component changes. `None` unless HMR is enabled.
+    pub fn emit_hmr_update_module(
+        &self,
+        compilation_result: &CompilationResult,
+        file: &std::path::Path,
+        class_name: &str,
+    ) -> Option<String> {
+        let component_handler = ComponentDecoratorHandler::new()
+            .with_config(self.compiler_config())
+            .with_hmr(self.options.hmr)
+            .with_file_system(self.fs);
+        compilation_result
+            .directives
+            .iter()
+            .find(|directive| match directive {
+                DecoratorMetadata::Directive(dir) => {
+                    &*dir.t2.name == class_name && dir.source_file.as_deref() == Some(file)
+                }
+                _ => false,
+            })
+            .and_then(|directive| component_handler.compile_hmr_update_module(directive))
+    }
+
+    /// Write only the `.d.ts` of each source file, with the Ivy fields of the classes it
+    /// declares, for `emitDeclarationOnly`.
+    fn emit_declarations(
+        &self,
+        compilation_result: &CompilationResult,
+        js_output_path: &dyn Fn(&std::path::Path) -> PathBuf,
+    ) -> Vec<crate::ngtsc::core::Diagnostic> {
+        let component_handler = ComponentDecoratorHandler::new().with_file_system(self.fs);
+        let directive_handler = DirectiveDecoratorHandler::new(self.is_core);
+        let mut diagnostics = Vec::new();
+
+        for file in &compilation_result.files {
+            let src_path = file.to_string_lossy();
+            if src_path.contains("node_modules")
+                || src_path.ends_with(".spec.ts")
+                || src_path.ends_with(".d.ts")
+            {
+                continue;
+            }
+            let Ok(source) = self.fs.read_file(&AbsoluteFsPath::from(file.as_path())) else {
+                continue;
+            };
+            let Some(declarations) = emit_declaration_file(&source) else {
+                continue;
+            };
+
+            let mut transform = IvyDeclarationDtsTransform::new();
+            let mut constant_pool = angular_compiler::constant_pool::ConstantPool::new(false);
+            for directive in compilation_result
+                .directives
+                .iter()
+                .filter(|directive| directive.source_file() == Some(file))
+            {
+                let (results, class_name) = compile_decorator(
+                    &component_handler,
+                    &directive_handler,
+                    directive,
+                    &mut constant_pool,
+                );
+                diagnostics.extend(results.iter().flat_map(|r| &r.diagnostics).map(|d| {
+                    crate::ngtsc::core::Diagnostic {
+                        category: crate::transformers::api::DiagnosticCategory::Error,
+                        file: d.file.clone().map(PathBuf::from),
+                        message: d.message_text.to_string(),
+                        code: d.code as usize,
+                        start: Some(d.start),
+                        length: Some(d.length),
+                        related_information: Vec::new(),
+                    }
+                }));
+                transform.add_fields(
+                    &class_name,
+                    results.iter().map(IvyDeclarationField::from).collect(),
+                );
+            }
+
+            let out_path = js_output_path(file).with_extension("d.ts");
+            if let Some(parent) = out_path.parent() {
+                let parent = AbsoluteFsPath::from(parent);
+                if let Err(e) = self.fs.ensure_dir(&parent) {
+                    self.logger
+                        .warn(&format!("Failed to create directory {}: {}", parent, e));
+                }
+            }
+            let out_path = AbsoluteFsPath::from(out_path.as_path());
+            let contents = transform.transform_declaration_file(&declarations);
+            if let Err(e) = self.fs.write_file(&out_path, contents.as_bytes(), None) {
+                self.logger
+                    .warn(&format!("Failed to write {}: {}", out_path, e));
+            }
+        }
+        diagnostics
+    }
+
+    /// The directory whose tree is mirrored under `outDir`: `rootDir`, resolved against the
+    /// project directory, or the project directory itself.
+    fn root_dir(&self) -> PathBuf {
+        let project_dir = std::path::Path::new(&self.options.project)
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(std::path::Path::new("."));
+        match &self.options.root_dir {
+            Some(root_dir) => project_dir.join(root_dir),
+            None => project_dir.to_path_buf(),
+        }
+    }
+
+    /// The path of `file` relative to the root directory, or `None` if it isn't under it.
+    fn path_below_root_dir(&self, file: &std::path::Path) -> Option<PathBuf> {
+        let root_dir = self.root_dir();
+        // Only compare canonical paths when both exist on disk, the file system may be virtual
+        file.strip_prefix(&root_dir)
+            .ok()
+            .map(std::path::Path::to_path_buf)
+            .or_else(|| {
+                let root_dir = std::fs::canonicalize(&root_dir).ok()?;
+                let file = std::fs::canonicalize(file).ok()?;
+                file.strip_prefix(root_dir)
+                    .ok()
+                    .map(std::path::Path::to_path_buf)
+            })
+    }
+
+    /// Report the emitted source files that aren't under the root directory, whose place under
+    /// `outDir` is undefined, like TypeScript's TS6059.
+    fn root_dir_diagnostics(&self, files: &[PathBuf]) -> Vec<crate::ngtsc::core::Diagnostic> {
+        if self.options.out_dir.is_none() {
+            return Vec::new();
+        }
+        let root_dir = self.root_dir();
+        files
+            .iter()
+            .filter(|file| {
+                let path = file.to_string_lossy();
+                !path.contains("node_modules") && !path.ends_with(".d.ts")
+            })
+            .filter(|file| self.path_below_root_dir(file).is_none())
+            .map(|file| crate::ngtsc::core::Diagnostic {
+                category: crate::transformers::api::DiagnosticCategory::Error,
+                file: None,
+                message: format!(
+                    "File '{}' is not under 'rootDir' '{}'. 'rootDir' is expected to contain all source files.",
+                    file.display(),
+                    root_dir.display()
+                ),
+                code: 6059,
+                start: None,
+                length: None,
+                related_information: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Where the outputs of a source file go: the same path under `outDir`, relative to
+    /// `rootDir` (or the project directory), or next to the source without an `outDir`.
+    ///
+    /// Source files outside of the root are reported by `root_dir_diagnostics` before anything
+    /// is emitted.
+    fn output_path(&self, file: &std::path::Path) -> PathBuf {
+        let Some(out_dir) = &self.options.out_dir else {
+            return file.to_path_buf();
+        };
+        let relative_path = self
+            .path_below_root_dir(file)
+            .or_else(|| file.file_name().map(PathBuf::from))
+            .unwrap_or_else(|| file.to_path_buf());
+        PathBuf::from(out_dir).join(relative_path)
+    }
+
+    /// Write the `.ngfactory.js` and `.ngsummary.js` shims requested by `generateNgFactoryShims`
+    /// and `generateNgSummaryShims` next to the emitted source files.
+    fn emit_shims(&self, files: &[PathBuf], js_output_path: &dyn Fn(&std::path::Path) -> PathBuf) {
+        let generator = ShimGenerator::new();
+        for file in files {
+            let src_path = file.to_string_lossy();
+            if src_path.contains("node_modules")
+                || src_path.ends_with(".spec.ts")
+                || src_path.ends_with(".d.ts")
+                || is_shim(&src_path)
+            {
+                continue;
+            }
+            let Ok(source) = self.fs.read_file(&AbsoluteFsPath::from(file.as_path())) else {
+                continue;
+            };
+            let classes = get_exported_classes(&source);
+
+            let mut shims = Vec::new();
+            if self.options.generate_ng_factory_shims {
+                let ng_modules: Vec<String> = classes
+                    .iter()
+                    .filter(|class| class.is_ng_module())
+                    .map(|class| class.name.clone())
+                    .collect();
+                shims.push(generator.generate_factory(&src_path, &ng_modules));
+            }
+            if self.options.generate_ng_summary_shims {
+                let names: Vec<String> = classes.iter().map(|class| class.name.clone()).collect();
+                shims.push(generator.generate_summary(&src_path, &names));
+            }
+
+            let out_path = js_output_path(file);
+            for shim in shims {
+                let Some(code) =
+                    transpile(&shim.file_name, &shim.content, false).map(|output| output.code)
+                else {
+                    continue;
+                };
+                let shim_name = std::path::Path::new(&shim.file_name)
+                    .file_name()
+                    .unwrap_or_default();
+                let shim_out_path = out_path.with_file_name(shim_name).with_extension("js");
+                if let Some(parent) = shim_out_path.parent() {
+                    let _ = self.fs.ensure_dir(&AbsoluteFsPath::from(parent));
+                }
+                let shim_out_path = AbsoluteFsPath::from(shim_out_path.as_path());
+                if let Err(e) = self.fs.write_file(&shim_out_path, code.as_bytes(), None) {
+                    self.logger
+                        .warn(&format!("Failed to write {}: {}", shim_out_path, e));
+                }
+            }
+        }
+    }
+
+    /// Write the flat module index for `flatModuleOutFile`, with its `.d.ts` and
+    /// `.metadata.json`, next to the emitted entry point.
+    fn emit_flat_index(
+        &self,
+        flat_module_out_file: &str,
+        root_files: &[PathBuf],
+    ) -> Option<crate::ngtsc::core::Diagnostic> {
+        let root_names: Vec<String> = root_files
+            .iter()
+            .map(|file| file.to_string_lossy().to_string())
+            .collect();
+        let Some(entry_point) = find_flat_index_entry_point(&root_names) else {
+            return Some(crate::ngtsc::core::Diagnostic {
+                category: crate::transformers::api::DiagnosticCategory::Error,
+                file: None,
+                message: "Angular compiler option \"flatModuleOutFile\" requires one and only one .ts file in the \"files\" field.".to_string(),
+                code: ErrorCode::ConfigFlatModuleNoIndex as usize,
+                start: None,
+                length: None,
+                related_information: Vec::new(),
+            });
+        };
+
+        let no_index_name = Path::new(flat_module_out_file)
+            .file_stem()
+            .is_none_or(|stem| stem.is_empty());
+        if no_index_name {
+            return Some(crate::ngtsc::core::Diagnostic {
+                category: crate::transformers::api::DiagnosticCategory::Error,
+                file: None,
+                message: format!(
+                    "Angular compiler option \"flatModuleOutFile\" must name a file, got \"{}\".",
+                    flat_module_out_file
+                ),
+                code: ErrorCode::ConfigFlatModuleNoIndex as usize,
+                start: None,
+                length: None,
+                related_information: Vec::new(),
+            });
+        }
+
+        let public_classes = self.public_api_classes(&entry_point);
+        let generator = FlatIndexGenerator::new(
+            entry_point,
+            flat_module_out_file,
+            self.options.flat_module_id.clone(),
+        );
+        let out_path = self.output_path(Path::new(&generator.flat_index_path));
+
+        if let Some(parent) = out_path.parent() {
+            let parent = AbsoluteFsPath::from(parent);
+            if let Err(e) = self.fs.ensure_dir(&parent) {
+                self.logger
+                    .warn(&format!("Failed to create directory {}: {}", parent, e));
+            }
+        }
+        let shim = generator.make_top_level_shim();
+        let outputs = [
+            (out_path.with_extension("js"), shim.clone()),
+            (out_path.with_extension("d.ts"), shim),
+            (
+                out_path.with_extension("metadata.json"),
+                generator.make_metadata(&public_classes),
+            ),
+        ];
+        for (path, contents) in outputs {
+            let path = AbsoluteFsPath::from(path.as_path());
+            if let Err(e) = self.fs.write_file(&path, contents.as_bytes(), None) {
+                self.logger
+                    .warn(&format!("Failed to write {}: {}", path, e));
+            }
+        }
+        None
+    }
+
+    /// The names of the classes exported by the flat module `entry_point`, either declared in it
+    /// or re-exported from the relative modules it re-exports.
+    fn public_api_classes(&self, entry_point: &str) -> Vec<String> {
+        let resolver =
+            ModuleResolver::new(self.fs, AbsoluteFsPath::new(self.fs.dirname(entry_point)));
+        let mut classes = BTreeSet::new();
+        self.collect_exported_classes(
+            &resolver,
+            entry_point,
+            None,
+            &mut HashSet::new(),
+            &mut classes,
+        );
+        classes.into_iter().collect()
+    }
+
+    /// Add the classes `file` exports to `classes`. `names` maps the names of the file that are
+    /// re-exported to their public name, or is `None` when all of them are.
+    fn collect_exported_classes(
+        &self,
+        resolver: &ModuleResolver,
+        file: &str,
+        names: Option<&HashMap<String, String>>,
+        visiting: &mut HashSet<String>,
+        classes: &mut BTreeSet<String>,
+    ) {
+        if !visiting.insert(file.to_string()) {
+            return;
+        }
+        let public_name = |name: &str| match names {
+            Some(names) => names.get(name).cloned(),
+            None => Some(name.to_string()),
+        };
+        if let Ok(source) = self.fs.read_file(&AbsoluteFsPath::new(file.to_string())) {
+            classes.extend(
+                get_exported_classes(&source)
+                    .iter()
+                    .filter_map(|class| public_name(&class.name)),
+            );
+            for reexport in get_reexports(&source) {
+                if !reexport.specifier.starts_with('.') {
+                    continue;
+                }
+                let Some(module) = resolver.resolve_module(&reexport.specifier, file) else {
+                    continue;
+                };
+                let module_names = reexport.names.map(|reexported| {
+                    reexported
+                        .into_iter()
+                        .filter_map(|(name, exported)| Some((name, public_name(&exported)?)))
+                        .collect::<HashMap<_, _>>()
+                });
+                let module_names = match (names, module_names) {
+                    (Some(names), None) => Some(names.clone()),
+                    (_, module_names) => module_names,
+                };
+                self.collect_exported_classes(
+                    resolver,
+                    module.as_str(),
+                    module_names.as_ref(),
+                    visiting,
+                    classes,
+                );
+            }
+        }
+        visiting.remove(file);
+    }
+
     fn process_directive_fallback(
         &self,
         directive: &DecoratorMetadata<'static>,
@@ -588,7 +1183,9 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
         result_diagnostics: &mut Vec<crate::ngtsc::core::Diagnostic>,
         compilation_files: &[PathBuf],
     ) {
-        let component_handler = ComponentDecoratorHandler::new();
+        let component_handler = ComponentDecoratorHandler::new()
+            .with_config(self.compiler_config())
+            .with_file_system(fs);
         let directive_handler = DirectiveDecoratorHandler::new(self.is_core);
 
         let (compiled_results, directive_name, source_file) = match directive {
@@ -607,11 +1204,13 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
         for r in &compiled_results {
             result_diagnostics.extend(r.diagnostics.iter().map(|d| {
                 crate::ngtsc::core::Diagnostic {
+                    category: crate::transformers::api::DiagnosticCategory::Error,
                     file: d.file.clone().map(PathBuf::from),
                     message: d.message_text.to_string(),
                     code: d.code as usize,
                     start: Some(d.start),
                     length: Some(d.length),
+                    related_information: Vec::new(),
                 }
             }));
         }
@@ -704,6 +1303,189 @@ impl<'a, T: FileSystem> NgCompiler<'a, T> {
         }
     }
 }
+/// Compile the Ivy definitions of a decorated class, returning them with the class name.
+fn compile_decorator(
+    component_handler: &ComponentDecoratorHandler,
+    directive_handler: &DirectiveDecoratorHandler,
+    directive: &DecoratorMetadata<'static>,
+    constant_pool: &mut angular_compiler::constant_pool::ConstantPool,
+) -> (Vec<CompileResult>, String) {
+    match directive {
+        DecoratorMetadata::Directive(dir) => {
+            let results = if dir.t2.is_component {
+                component_handler.compile_ivy_with_pool(directive, constant_pool)
+            } else {
+                directive_handler.compile_ivy_with_pool(directive, constant_pool)
+            };
+            (results, dir.t2.name.clone())
+        }
+        DecoratorMetadata::Pipe(pipe) => {
+            let initializer = format!(
+                "/*@__PURE__*/ i0.ɵɵdefinePipe({{ name: '{}', type: {}, pure: {}{} }})",
+                pipe.pipe_name,
+                pipe.name,
+                pipe.is_pure,
+                if pipe.is_standalone {
+                    ", standalone: true"
+                } else {
+                    ""
+                }
+            );
+            let results = vec![CompileResult {
+                name: "ɵpipe".to_string(),
+                initializer: Some(initializer),
+                statements: vec![],
+                type_desc: format!(
+                    "i0.ɵɵPipeDeclaration<{}, '{}', {}>",
+                    pipe.name, pipe.pipe_name, pipe.is_standalone
+                ),
+                deferrable_imports: None,
+                diagnostics: Vec::new(),
+                additional_imports: Vec::new(),
+            }];
+            (results, pipe.name.clone())
+        }
+        DecoratorMetadata::Injectable(inj) => {
+            let fac_initializer = format!(
+                "function {}_Factory(__ngFactoryType__) {{ return new (__ngFactoryType__ || {})(); }}",
+                inj.name, inj.name
+            );
+            let compiled = compile_injectable(&injectable_metadata(inj), false);
+            let mut emitter = AbstractJsEmitterVisitor::new();
+            let mut ctx = EmitterVisitorContext::create_root();
+            {
+                let context: &mut dyn Any = &mut ctx;
+                compiled.expression.visit_expression(&mut emitter, context);
+            }
+            let prov_initializer = ctx.to_source();
+            let results = vec![
+                CompileResult {
+                    name: "ɵfac".to_string(),
+                    initializer: Some(fac_initializer),
+                    statements: vec![],
+                    type_desc: format!("i0.ɵɵFactoryDeclaration<{}, never>", inj.name),
+                    deferrable_imports: None,
+                    diagnostics: Vec::new(),
+                    additional_imports: Vec::new(),
+                },
+                CompileResult {
+                    name: "ɵprov".to_string(),
+                    initializer: Some(prov_initializer),
+                    statements: vec![],
+                    type_desc: format!("i0.ɵɵInjectableDeclaration<{}>", inj.name),
+                    deferrable_imports: None,
+                    diagnostics: Vec::new(),
+                    additional_imports: Vec::new(),
+                },
+            ];
+            (results, inj.name.clone())
+        }
+        DecoratorMetadata::NgModule(ngm) => {
+            let mut analysis = NgModuleAnalysis::new(ngm.name.clone());
+            analysis.module_meta.bootstrap = ngm.bootstrap.clone();
+            analysis.declarations = ngm.declarations.clone();
+            analysis.raw_declarations = ngm.raw_declarations.clone();
+            analysis.imports = ngm.imports.clone();
+            analysis.raw_imports = ngm.raw_imports.clone();
+            analysis.exports = ngm.exports.clone();
+            analysis.raw_exports = ngm.raw_exports.clone();
+            analysis.providers = ngm.providers.clone();
+            let results = NgModuleDecoratorHandler::compile_ng_module(&analysis, None);
+            (results, ngm.name.clone())
+        }
+    }
+}
+
+/// Builds the metadata `compile_injectable` compiles the `ɵprov` of an injectable from. The
+/// provider expressions are emitted as they were written.
+fn injectable_metadata(inj: &InjectableMeta) -> R3InjectableMetadata {
+    let written = |text: &str| {
+        o::Expression::WrappedNode(o::WrappedNodeExpr {
+            node: Box::new(text.to_string()),
+            type_: None,
+            source_span: None,
+        })
+    };
+    let reference =
+        |text: &str| MaybeForwardRefExpression::new(written(text), ForwardRefHandling::None);
+    let provided_in = inj
+        .provided_in
+        .as_deref()
+        .map_or_else(|| *o::literal(o::LiteralValue::Null), written);
+    R3InjectableMetadata {
+        name: inj.name.clone(),
+        type_: R3Reference::new(written(&inj.name), *o::variable(inj.name.clone())),
+        type_argument_count: 0,
+        provided_in: MaybeForwardRefExpression::new(provided_in, ForwardRefHandling::None),
+        use_class: inj.use_class.as_deref().map(reference),
+        use_factory: inj.use_factory.as_deref().map(written),
+        use_existing: inj.use_existing.as_deref().map(reference),
+        use_value: inj.use_value.as_deref().map(reference),
+        deps: inj.deps.as_ref().map(|deps| {
+            deps.iter()
+                .map(|token| R3DependencyMetadata {
+                    token: Some(written(token)),
+                    attribute_name_type: None,
+                    host: false,
+                    optional: false,
+                    self_: false,
+                    skip_self: false,
+                })
+                .collect()
+        }),
+    }
+}
+
+/// Transpile the TypeScript `source` of `file_path` to JavaScript, stripping its types. `None`
+/// if it can't be parsed.
+/// Strip the types of a TypeScript file, generating a source map of the result when
+/// `source_map` is set.
+fn transpile(
+    file_path: &str,
+    source: &str,
+    source_map: bool,
+) -> Option<oxc_codegen::CodegenReturn> {
+    let allocator = Allocator::default();
+    let mut parse_result = Parser::new(&allocator, source, SourceType::ts()).parse();
+    if !parse_result.errors.is_empty() {
+        return None;
+    }
+
+    // Run semantic analysis to get scoping information
+    let semantic = oxc_semantic::SemanticBuilder::new()
+        .with_excess_capacity(0.0)
+        .build(&parse_result.program);
+
+    // Apply TypeScript transformer to strip types
+    let transform_options = oxc_transformer::TransformOptions::default();
+    let transformer = oxc_transformer::Transformer::new(
+        &allocator,
+        std::path::Path::new(file_path),
+        &transform_options,
+    );
+    let _ =
+        transformer.build_with_scoping(semantic.semantic.into_scoping(), &mut parse_result.program);
+
+    // Use OXC codegen to emit JavaScript without types
+    let codegen = oxc_codegen::Codegen::new().with_options(oxc_codegen::CodegenOptions {
+        single_quote: true,
+        source_map_path: source_map.then(|| PathBuf::from(file_path)),
+        ..oxc_codegen::CodegenOptions::default()
+    });
+    Some(codegen.build(&parse_result.program))
+}
+
+/// End emitted code with its source map as a `data:` URL, as tsc does for `inlineSourceMap`.
+fn append_inline_source_map(code: &mut String, source_map: &str) {
+    use base64::Engine;
+
+    if !code.ends_with('\n') {
+        code.push('\n');
+    }
+    code.push_str("//# sourceMappingURL=data:application/json;base64,");
+    code.push_str(&base64::engine::general_purpose::STANDARD.encode(source_map));
+}
+
 /// Strip Angular decorators (@Component, @Directive, @Injectable, etc.) from transpiled code
 fn strip_angular_decorator(code: &str) -> String {
     // Pattern to match: export @Decorator({...}) class ClassName
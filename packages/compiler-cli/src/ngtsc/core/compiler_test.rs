@@ -3,11 +3,19 @@ mod tests {
     use crate::ngtsc::core::{
         CompilationTicket, CompilationTicketKind, NgCompiler, NgCompilerOptions,
     };
+    use crate::ngtsc::diagnostics::ErrorCode;
     use crate::ngtsc::file_system::testing::MockFileSystem;
-    use crate::ngtsc::file_system::FileSystem;
+    use crate::ngtsc::file_system::{
+        AbsoluteFsPath, FileSystem, MemoryFileSystem, ReadonlyFileSystem,
+    };
+    use crate::ngtsc::logging::{LogLevel, Logger};
     use crate::ngtsc::metadata::DecoratorMetadata;
+    use crate::ngtsc::perf::PerfPhase;
+    use crate::ngtsc::resource::FileResourceLoader;
+    use crate::transformers::api::DiagnosticCategory;
     use angular_compiler::ml_parser::ast::Node;
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     #[test]
     fn test_analyze_async_parses_template() {
@@ -35,6 +43,7 @@ mod tests {
             flat_module_out_file: None,
             out_dir: None,
             root_dir: None,
+            ..NgCompilerOptions::default()
         };
 
         let ticket = CompilationTicket {
@@ -108,6 +117,7 @@ mod tests {
             flat_module_out_file: None,
             out_dir: None,
             root_dir: None,
+            ..NgCompilerOptions::default()
         };
 
         let ticket = CompilationTicket {
@@ -173,6 +183,7 @@ mod tests {
             flat_module_out_file: None,
             out_dir: None,
             root_dir: None,
+            ..NgCompilerOptions::default()
         };
 
         let ticket = CompilationTicket {
@@ -200,4 +211,1105 @@ mod tests {
             panic!("Expected Directive metadata");
         }
     }
+
+    #[test]
+    fn test_analyze_async_reports_inline_template_errors_in_source_file() {
+        let source = r#"
+                import { Component } from '@angular/core';
+
+                @Component({
+                    selector: 'app-root',
+                    template: `<div><span>Hello</div>`,
+                    standalone: true
+                })
+                export class AppComponent {}
+            "#;
+        let fs = MockFileSystem::new_posix();
+        fs.init_with_files(vec![("/app.component.ts", source)]);
+        let fs_arc = Arc::new(fs);
+
+        let options = NgCompilerOptions {
+            project: ".".to_string(),
+            strict_injection_parameters: true,
+            strict_templates: true,
+            skip_template_codegen: false,
+            flat_module_out_file: None,
+            out_dir: None,
+            root_dir: None,
+            ..NgCompilerOptions::default()
+        };
+
+        let ticket = CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &*fs_arc,
+        };
+
+        let mut compiler = NgCompiler::new(ticket);
+        let res = compiler
+            .analyze_async(&["/app.component.ts".to_string()])
+            .expect("Analysis failed");
+
+        assert!(
+            !res.diagnostics.is_empty(),
+            "Expected a template parse error"
+        );
+        let literal_start = source.find('`').unwrap();
+        let literal_end = source.rfind('`').unwrap();
+        let unclosed_span = source.find("<span>").unwrap();
+        for diagnostic in &res.diagnostics {
+            assert_eq!(
+                diagnostic.file.as_deref(),
+                Some(std::path::Path::new("/app.component.ts"))
+            );
+            let start = diagnostic.start.expect("Diagnostic should have a position");
+            assert!(start > literal_start && start < literal_end);
+        }
+        assert_eq!(res.diagnostics[0].message, "Unclosed element \"span\"");
+        assert_eq!(res.diagnostics[0].start, Some(unclosed_span));
+    }
+
+    #[test]
+    fn test_emit_flat_module_index() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[
+            ("/lib/public_api.ts", "export * from './button';\n"),
+            ("/lib/button.ts", "export class Button {}\n"),
+        ])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/lib/tsconfig.json".to_string(),
+            flat_module_out_file: Some("my-lib.js".to_string()),
+            flat_module_id: Some("my-lib".to_string()),
+            out_dir: Some("/dist".to_string()),
+            root_dir: Some("/lib".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+
+        let result = compiler
+            .analyze_async(&["/lib/public_api.ts".to_string()])
+            .unwrap();
+        let diagnostics = compiler.emit(&result).unwrap();
+        assert!(diagnostics.is_empty());
+
+        let read = |path: &str| {
+            fs.read_file(&AbsoluteFsPath::new(path.to_string()))
+                .unwrap()
+        };
+        let index = read("/dist/my-lib.js");
+        assert!(index.contains("export * from './public_api';"));
+        assert_eq!(read("/dist/my-lib.d.ts"), index);
+        let metadata = read("/dist/my-lib.metadata.json");
+        assert!(metadata.contains(r#""importAs":"my-lib""#));
+        assert!(metadata.contains(r#""metadata":{"Button":{"__symbolic":"class"}}"#));
+    }
+
+    #[test]
+    fn test_emit_flat_module_index_without_file_name() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[("/lib/public_api.ts", "export class Button {}\n")])
+            .unwrap();
+        let options = NgCompilerOptions {
+            project: "/lib/tsconfig.json".to_string(),
+            flat_module_out_file: Some("..".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+
+        let result = compiler
+            .analyze_async(&["/lib/public_api.ts".to_string()])
+            .unwrap();
+        let diagnostics = compiler.emit(&result).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            ErrorCode::ConfigFlatModuleNoIndex as usize
+        );
+    }
+
+    #[test]
+    fn test_emit_declaration_only() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/lib/dir.ts",
+            r#"
+                import { Directive, Input } from '@angular/core';
+
+                @Directive({ selector: '[dir]', standalone: true })
+                export class Dir {
+                    @Input() value: string = '';
+                    toggle(force?: boolean): void {}
+                }
+            "#,
+        )])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/lib/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            root_dir: Some("/lib".to_string()),
+            emit_declaration_only: true,
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+
+        let result = compiler
+            .analyze_async(&["/lib/dir.ts".to_string()])
+            .unwrap();
+        let diagnostics = compiler.emit(&result).unwrap();
+        assert!(diagnostics.is_empty());
+
+        assert!(!fs.exists(&AbsoluteFsPath::new("/dist/dir.js".to_string())));
+        let dts = fs
+            .read_file(&AbsoluteFsPath::new("/dist/dir.d.ts".to_string()))
+            .unwrap();
+        assert!(dts.contains("export declare class Dir {"));
+        assert!(dts.contains("    value: string;\n"));
+        assert!(dts.contains("    toggle(force?: boolean): void;\n"));
+        assert!(dts.contains("    static ɵdir: i0.ɵɵDirectiveDeclaration<Dir, \"[dir]\", never"));
+        assert!(!dts.contains("@Input"));
+    }
+
+    #[test]
+    fn test_emit_injectable_provided_in_and_provider_forms() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/app/services.ts",
+            r#"
+                import { Injectable } from '@angular/core';
+
+                export class SomeModule {}
+
+                @Injectable({ providedIn: 'root' })
+                export class RootService {}
+
+                @Injectable({ providedIn: 'platform' })
+                export class PlatformService {}
+
+                @Injectable({ providedIn: 'any' })
+                export class AnyService {}
+
+                @Injectable({ providedIn: SomeModule })
+                export class ModuleService {}
+
+                @Injectable()
+                export class UnscopedService {}
+
+                @Injectable({ providedIn: 'root', useClass: RootService })
+                export class ClassService {}
+
+                @Injectable({ providedIn: 'root', useClass: RootService, deps: [AnyService] })
+                export class ClassDepsService {}
+
+                @Injectable({ providedIn: 'root', useFactory: () => new RootService() })
+                export class FactoryService {}
+
+                @Injectable({ providedIn: 'root', useFactory: createService, deps: [AnyService] })
+                export class FactoryDepsService {}
+
+                @Injectable({ providedIn: 'root', useExisting: RootService })
+                export class ExistingService {}
+
+                @Injectable({ providedIn: 'root', useValue: 42 })
+                export class ValueService {}
+
+                export function createService(any: AnyService) {
+                    return new RootService();
+                }
+            "#,
+        )])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            root_dir: Some("/app".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+
+        let result = compiler
+            .analyze_async(&["/app/services.ts".to_string()])
+            .unwrap();
+        compiler.emit(&result).unwrap();
+        let js = fs
+            .read_file(&AbsoluteFsPath::new("/dist/services.js".to_string()))
+            .unwrap();
+        let prov = |class_name: &str| {
+            let start = js
+                .find(&format!("token: {},", class_name))
+                .unwrap_or_else(|| panic!("missing ɵprov of {}", class_name));
+            js[start..start + js[start..].find("});").unwrap()].to_string()
+        };
+
+        assert!(prov("RootService").contains("providedIn: 'root'"));
+        assert!(prov("PlatformService").contains("providedIn: 'platform'"));
+        assert!(prov("AnyService").contains("providedIn: 'any'"));
+        assert!(prov("ModuleService").contains("providedIn: SomeModule"));
+        assert!(!prov("UnscopedService").contains("providedIn"));
+        assert!(prov("UnscopedService").contains("factory: UnscopedService.ɵfac"));
+
+        assert!(prov("ClassService")
+            .contains("factory: (__ngFactoryType__) => RootService.ɵfac(__ngFactoryType__)"));
+        assert!(prov("ClassDepsService")
+            .contains("__ngConditionalFactory__ = new RootService(i0.ɵɵinject(AnyService));"));
+        assert!(prov("FactoryService").contains("factory: () => (() => new RootService())()"));
+        assert!(prov("FactoryDepsService")
+            .contains("__ngConditionalFactory__ = createService(i0.ɵɵinject(AnyService));"));
+        assert!(prov("ExistingService")
+            .contains("__ngConditionalFactory__ = i0.ɵɵinject(RootService);"));
+        assert!(prov("ValueService").contains("__ngConditionalFactory__ = 42;"));
+    }
+
+    #[test]
+    fn test_trace_performance_records_phase_timings() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/app/cmp.ts",
+            r#"
+                import { Component } from '@angular/core';
+
+                @Component({ selector: 'app-cmp', standalone: true, template: '<p>{{ name }}</p>' })
+                export class Cmp {
+                    name = 'app';
+                }
+            "#,
+        )])
+        .unwrap();
+        let compile = |trace_performance| {
+            let options = NgCompilerOptions {
+                project: "/app/tsconfig.json".to_string(),
+                out_dir: Some("/dist".to_string()),
+                root_dir: Some("/app".to_string()),
+                trace_performance,
+                ..NgCompilerOptions::default()
+            };
+            let mut compiler = NgCompiler::new(CompilationTicket {
+                kind: CompilationTicketKind::Fresh,
+                options,
+                fs: &fs,
+            });
+            let result = compiler
+                .analyze_async(&["/app/cmp.ts".to_string()])
+                .unwrap();
+            compiler.emit(&result).unwrap();
+            compiler.perf_recorder.finalize()
+        };
+
+        let timings = compile(true);
+        let phases: Vec<PerfPhase> = timings.iter().map(|timing| timing.phase).collect();
+        assert_eq!(
+            phases,
+            vec![PerfPhase::Analysis, PerfPhase::TypeCheck, PerfPhase::Emit]
+        );
+        assert!(timings
+            .iter()
+            .all(|timing| timing.duration > Duration::ZERO));
+
+        assert!(compile(false).is_empty());
+    }
+
+    #[test]
+    fn test_emit_ng_factory_shims() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/app/app.module.ts",
+            r#"
+                import { NgModule } from '@angular/core';
+
+                @NgModule({})
+                export class AppModule {}
+            "#,
+        )])
+        .unwrap();
+        let compile = |generate_ng_factory_shims| {
+            let options = NgCompilerOptions {
+                project: "/app/tsconfig.json".to_string(),
+                out_dir: Some("/dist".to_string()),
+                root_dir: Some("/app".to_string()),
+                generate_ng_factory_shims,
+                ..NgCompilerOptions::default()
+            };
+            let mut compiler = NgCompiler::new(CompilationTicket {
+                kind: CompilationTicketKind::Fresh,
+                options,
+                fs: &fs,
+            });
+            let result = compiler
+                .analyze_async(&["/app/app.module.ts".to_string()])
+                .unwrap();
+            compiler.emit(&result).unwrap();
+        };
+        let factory_path = AbsoluteFsPath::new("/dist/app.module.ngfactory.js".to_string());
+
+        compile(false);
+        assert!(!fs.exists(&factory_path));
+
+        compile(true);
+        let factory = fs.read_file(&factory_path).unwrap();
+        assert!(factory.contains("import { AppModule } from './app.module';"));
+        assert!(factory
+            .contains("export const AppModuleNgFactory = new i0.ɵNgModuleFactory(AppModule);"));
+        assert!(factory.contains("export const ɵNonEmptyModule = true;"));
+        assert!(!factory.contains("ɵNgModuleFactory<any>"));
+    }
+
+    #[test]
+    fn test_emit_mirrors_the_source_tree_below_root_dir() {
+        let fs = MemoryFileSystem::new();
+        let component = |selector: &str| {
+            format!(
+                r#"
+                    import {{ Component }} from '@angular/core';
+
+                    @Component({{ selector: '{}', template: 'hi', standalone: true }})
+                    export class Cmp {{}}
+                "#,
+                selector
+            )
+        };
+        let files = [
+            (
+                "/app/src/app/util.ts",
+                "export const a: number = 1;\n".to_string(),
+            ),
+            (
+                "/app/src/app/nested/util.ts",
+                "export const b: number = 2;\n".to_string(),
+            ),
+            ("/app/src/app/cmp.ts", component("app-cmp")),
+            ("/app/src/app/nested/cmp.ts", component("nested-cmp")),
+        ];
+        let file_refs: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(path, content)| (*path, content.as_str()))
+            .collect();
+        fs.init_with_files(&file_refs).unwrap();
+        let root_names: Vec<String> = files.iter().map(|(path, _)| path.to_string()).collect();
+
+        // `rootDir` may be absolute or relative to the project
+        for root_dir in ["/app/src", "src"] {
+            let options = NgCompilerOptions {
+                project: "/app/tsconfig.json".to_string(),
+                out_dir: Some("/dist".to_string()),
+                root_dir: Some(root_dir.to_string()),
+                ..NgCompilerOptions::default()
+            };
+            let mut compiler = NgCompiler::new(CompilationTicket {
+                kind: CompilationTicketKind::Fresh,
+                options,
+                fs: &fs,
+            });
+            let result = compiler.analyze_async(&root_names).unwrap();
+            compiler.emit(&result).unwrap();
+
+            let read = |path: &str| {
+                fs.read_file(&AbsoluteFsPath::new(path.to_string()))
+                    .unwrap_or_else(|_| panic!("{} wasn't emitted (rootDir {})", path, root_dir))
+            };
+            assert!(read("/dist/app/util.js").contains("a = 1"));
+            assert!(read("/dist/app/nested/util.js").contains("b = 2"));
+            assert!(read("/dist/app/cmp.js").contains("app-cmp"));
+            assert!(read("/dist/app/nested/cmp.js").contains("nested-cmp"));
+            assert!(!fs.exists(&AbsoluteFsPath::new("/dist/util.js".to_string())));
+        }
+    }
+
+    #[test]
+    fn test_emit_reports_files_outside_of_root_dir() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[
+            ("/app/src/a.ts", "export const a: number = 1;\n"),
+            ("/other/b.ts", "export const b: number = 2;\n"),
+        ])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            root_dir: Some("src".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+        let result = compiler
+            .analyze_async(&["/app/src/a.ts".to_string(), "/other/b.ts".to_string()])
+            .unwrap();
+        let diagnostics = compiler.emit(&result).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, 6059);
+        assert_eq!(
+            diagnostics[0].message,
+            "File '/other/b.ts' is not under 'rootDir' '/app/src'. 'rootDir' is expected to contain all source files."
+        );
+        assert!(!fs.exists(&AbsoluteFsPath::new("/dist/a.js".to_string())));
+        assert!(!fs.exists(&AbsoluteFsPath::new("/dist/other/b.js".to_string())));
+        assert!(!fs.exists(&AbsoluteFsPath::new("/dist/b.js".to_string())));
+    }
+
+    #[test]
+    fn test_emit_defer_only_dependency_loads_lazily() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[
+            (
+                "/app/lazy.component.ts",
+                r#"
+                    import { Component } from '@angular/core';
+
+                    @Component({ selector: 'lazy-cmp', template: 'lazy', standalone: true })
+                    export class LazyCmp {}
+                "#,
+            ),
+            (
+                "/app/app.component.ts",
+                r#"
+                    import { Component } from '@angular/core';
+                    import { LazyCmp } from './lazy.component';
+
+                    @Component({
+                        selector: 'app-cmp',
+                        template: '@defer { <lazy-cmp></lazy-cmp> } @placeholder { <p>wait</p> }',
+                        standalone: true,
+                        imports: [LazyCmp],
+                    })
+                    export class AppCmp {}
+                "#,
+            ),
+        ])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+        let result = compiler
+            .analyze_async(&[
+                "/app/lazy.component.ts".to_string(),
+                "/app/app.component.ts".to_string(),
+            ])
+            .unwrap();
+        compiler.emit(&result).unwrap();
+
+        let js = fs
+            .read_file(&AbsoluteFsPath::new("/dist/app.component.js".to_string()))
+            .unwrap();
+        // The dependency is only loaded by the defer block, which references the loader function.
+        assert!(
+            js.contains("import('./lazy.component').then((m) => m.LazyCmp)"),
+            "{}",
+            js
+        );
+        assert!(js.contains("AppCmp_DeferFn"));
+        assert!(
+            js.contains("i0.ɵɵdefer(2, 0, AppCmp_DeferFn, null, 1)"),
+            "{}",
+            js
+        );
+        assert!(js.contains("i0.ɵɵdeferOnIdle()"));
+        assert!(!js.contains("from \"./lazy.component\""), "{}", js);
+        assert!(!js.contains("dependencies: [LazyCmp]"));
+    }
+
+    #[test]
+    fn test_emit_ng_module_keeps_module_with_providers_imports() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/app/app.module.ts",
+            r#"
+                import { NgModule } from '@angular/core';
+                import { FeatureModule } from './feature.module';
+                import { AppService } from './app.service';
+
+                const config = { debug: true };
+
+                @NgModule({
+                    imports: [FeatureModule.forRoot(config)],
+                    providers: [AppService],
+                })
+                export class AppModule {}
+            "#,
+        )])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+        let result = compiler
+            .analyze_async(&["/app/app.module.ts".to_string()])
+            .unwrap();
+        compiler.emit(&result).unwrap();
+
+        let js = fs
+            .read_file(&AbsoluteFsPath::new("/dist/app.module.js".to_string()))
+            .unwrap();
+        assert!(!js.contains("@NgModule"), "{}", js);
+        assert!(
+            js.contains("static ɵmod = /* @__PURE__ */ i0.ɵɵdefineNgModule({ type: AppModule });"),
+            "{}",
+            js
+        );
+        // The providers of the ModuleWithProviders are only known once forRoot() is called.
+        assert!(
+            js.contains(
+                "static ɵinj = /* @__PURE__ */ i0.ɵɵdefineInjector({\n\t\tproviders: [AppService],\n\t\timports: [FeatureModule.forRoot(config)]\n\t});"
+            ),
+            "{}",
+            js
+        );
+    }
+
+    #[test]
+    fn test_analyze_async_loads_resources_through_the_resource_loader() {
+        let app_dir = AbsoluteFsPath::new("/app".to_string());
+        let fs = MockFileSystem::new_posix();
+        fs.ensure_dir(&app_dir).unwrap();
+        fs.init_with_files(vec![(
+            "/app/app.component.ts",
+            r#"
+                import { Component } from '@angular/core';
+
+                @Component({
+                    selector: 'app-root',
+                    templateUrl: './app.component.html',
+                    styleUrls: ['./app.component.css'],
+                    standalone: true
+                })
+                export class AppComponent {}
+            "#,
+        )]);
+        // The resources only exist where the loader reads them from.
+        let resource_fs = MockFileSystem::new_posix();
+        resource_fs.ensure_dir(&app_dir).unwrap();
+        resource_fs.init_with_files(vec![
+            ("/app/app.component.html", "<h1>Loaded</h1>"),
+            ("/app/app.component.css", "h1 { color: red; }"),
+        ]);
+        let loader = Arc::new(FileResourceLoader::new(Arc::new(resource_fs)));
+
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options: NgCompilerOptions::default(),
+            fs: &fs,
+        })
+        .with_resource_loader(loader.clone());
+        let result = compiler
+            .analyze_async(&["/app/app.component.ts".to_string()])
+            .unwrap();
+
+        assert!(loader.is_cached("/app/app.component.html"));
+        assert!(loader.is_cached("/app/app.component.css"));
+        let DecoratorMetadata::Directive(dir) = &result.directives[0] else {
+            panic!("expected a component");
+        };
+        let comp = dir.component.as_ref().unwrap();
+        match comp.template_ast.as_deref() {
+            Some([Node::Element(el)]) => assert_eq!(&*el.name, "h1"),
+            other => panic!("unexpected template: {:?}", other.map(|nodes| nodes.len())),
+        }
+        assert_eq!(
+            comp.styles.as_deref(),
+            Some(&["h1 { color: red; }".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn test_emit_debug_locations_of_inline_templates() {
+        let source = "import { Component } from '@angular/core';\n\n@Component({\n  selector: 'loc-cmp',\n  template: `<div>\n  <span>{{ x }}</span>\n</div>`,\n  standalone: true,\n})\nexport class LocCmp {}\n";
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[("/app/loc.ts", source)]).unwrap();
+        let emit = |enable_debug_locations: bool| {
+            let options = NgCompilerOptions {
+                project: "/app/tsconfig.json".to_string(),
+                out_dir: Some("/dist".to_string()),
+                enable_debug_locations,
+                ..NgCompilerOptions::default()
+            };
+            let mut compiler = NgCompiler::new(CompilationTicket {
+                kind: CompilationTicketKind::Fresh,
+                options,
+                fs: &fs,
+            });
+            let result = compiler
+                .analyze_async(&["/app/loc.ts".to_string()])
+                .unwrap();
+            compiler.emit(&result).unwrap();
+            fs.read_file(&AbsoluteFsPath::new("/dist/loc.js".to_string()))
+                .unwrap()
+        };
+
+        // Elements of inline templates are located in the component source.
+        let div = source.find("<div>").unwrap();
+        let span = source.find("<span>").unwrap();
+        let js = emit(true);
+        let compact: String = js.split_whitespace().collect();
+        assert!(
+            compact.contains(&format!(
+                "i0.ɵɵattachSourceLocations('/app/loc.ts',[[0,{},4,13],[1,{},5,2]]);",
+                div, span
+            )),
+            "{}",
+            js
+        );
+        assert!(!emit(false).contains("ɵɵattachSourceLocations"));
+    }
+
+    #[test]
+    fn test_emit_preserve_whitespaces() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/app/cmp.ts",
+            r#"
+                import { Component } from '@angular/core';
+
+                @Component({ selector: 'kept-cmp', template: '<span>  a  b  </span>', preserveWhitespaces: true, standalone: true })
+                export class KeptCmp {}
+
+                @Component({ selector: 'collapsed-cmp', template: '<span>  a  b  </span>', standalone: true })
+                export class CollapsedCmp {}
+            "#,
+        )])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+
+        let result = compiler
+            .analyze_async(&["/app/cmp.ts".to_string()])
+            .unwrap();
+        compiler.emit(&result).unwrap();
+        let js = fs
+            .read_file(&AbsoluteFsPath::new("/dist/cmp.js".to_string()))
+            .unwrap();
+        let template = |class_name: &str| {
+            let start = js
+                .find(&format!("function {}_Template", class_name))
+                .unwrap_or_else(|| panic!("missing template of {}: {}", class_name, js));
+            js[start..start + js[start..].find("\n}").unwrap_or(js.len() - start)].to_string()
+        };
+
+        assert!(
+            template("KeptCmp").contains("i0.ɵɵtext(1, '  a  b  ')"),
+            "{}",
+            js
+        );
+        assert!(
+            template("CollapsedCmp").contains("i0.ɵɵtext(1, ' a b ')"),
+            "{}",
+            js
+        );
+    }
+
+    #[test]
+    fn test_emit_inline_source_map() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[
+            (
+                "/app/cmp.ts",
+                r#"
+                    import { Component } from '@angular/core';
+
+                    @Component({ selector: 'app-cmp', template: 'hi', standalone: true })
+                    export class Cmp {}
+                "#,
+            ),
+            ("/app/util.ts", "export const a: number = 1;\n"),
+        ])
+        .unwrap();
+        let emit = |inline_source_map| {
+            let options = NgCompilerOptions {
+                project: "/app/tsconfig.json".to_string(),
+                out_dir: Some("/dist".to_string()),
+                inline_source_map,
+                ..NgCompilerOptions::default()
+            };
+            let mut compiler = NgCompiler::new(CompilationTicket {
+                kind: CompilationTicketKind::Fresh,
+                options,
+                fs: &fs,
+            });
+            let result = compiler
+                .analyze_async(&["/app/cmp.ts".to_string(), "/app/util.ts".to_string()])
+                .unwrap();
+            compiler.emit(&result).unwrap();
+            ["/dist/cmp.js", "/dist/util.js"].map(|path| {
+                fs.read_file(&AbsoluteFsPath::new(path.to_string()))
+                    .unwrap()
+            })
+        };
+
+        for output in emit(true) {
+            let last_line = output.lines().last().unwrap();
+            assert!(
+                last_line.starts_with("//# sourceMappingURL=data:application/json;base64,"),
+                "unexpected output: {}",
+                output
+            );
+            assert_eq!(output.matches("sourceMappingURL").count(), 1);
+
+            use base64::Engine;
+            let encoded = last_line.rsplit(',').next().unwrap();
+            let map = base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .unwrap();
+            let map: serde_json::Value = serde_json::from_slice(&map).unwrap();
+            assert_eq!(map["version"], 3);
+            assert!(!map["mappings"].as_str().unwrap().is_empty());
+        }
+        for output in emit(false) {
+            assert!(!output.contains("sourceMappingURL"), "{}", output);
+        }
+    }
+
+    #[test]
+    fn test_inline_source_map_maps_emitted_code() {
+        let source = r#"
+import { Component } from '@angular/core';
+
+@Component({ selector: 'app-cmp', template: '<p>{{ title }}</p>', standalone: true })
+export class Cmp {
+    title = 'app';
+}
+
+export function greet() {
+    return 'hello';
+}
+"#;
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[("/app/cmp.ts", source)]).unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            inline_source_map: true,
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+        let result = compiler
+            .analyze_async(&["/app/cmp.ts".to_string()])
+            .unwrap();
+        compiler.emit(&result).unwrap();
+        let output = fs
+            .read_file(&AbsoluteFsPath::new("/dist/cmp.js".to_string()))
+            .unwrap();
+
+        use base64::Engine;
+        let encoded = output.lines().last().unwrap().rsplit(',').next().unwrap();
+        let map = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap();
+        let map =
+            oxc_sourcemap::SourceMap::from_json_string(std::str::from_utf8(&map).unwrap()).unwrap();
+        let lookup_table = map.generate_lookup_table();
+
+        // Line and column of `text`, which is preceded by ASCII only on its line.
+        let position = |code: &str, text: &str| {
+            let offset = code.find(text).unwrap();
+            let line_start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+            (
+                code[..offset].matches('\n').count() as u32,
+                (offset - line_start) as u32,
+            )
+        };
+        let (line, col) = position(&output, "'hello'");
+        let token = map.lookup_token(&lookup_table, line, col).unwrap();
+        assert_eq!(
+            (token.get_src_line(), token.get_src_col()),
+            position(source, "'hello'")
+        );
+    }
+
+    #[test]
+    fn test_analyze_reports_duplicate_template_references() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/app/cmp.ts",
+            r#"
+                import { Component } from '@angular/core';
+
+                @Component({ selector: 'app-cmp', standalone: true, template: '<input #ref><input #ref>' })
+                export class Cmp {}
+            "#,
+        )])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            root_dir: Some("/app".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+        let result = compiler
+            .analyze_async(&["/app/cmp.ts".to_string()])
+            .unwrap();
+
+        let diagnostics: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.code == ErrorCode::DuplicateVariableDeclaration as usize)
+            .collect();
+        assert_eq!(diagnostics.len(), 1);
+        let source = fs
+            .read_file(&AbsoluteFsPath::new("/app/cmp.ts".to_string()))
+            .unwrap();
+        let start = diagnostics[0].start.unwrap();
+        assert_eq!(&source[start..start + 4], "#ref");
+        assert_eq!(source[..start].matches("#ref").count(), 1);
+
+        let related = &diagnostics[0].related_information;
+        assert_eq!(related.len(), 1);
+        assert_eq!(
+            related[0].file.as_deref(),
+            Some(std::path::Path::new("/app/cmp.ts"))
+        );
+        let first_start = related[0].start.unwrap();
+        assert_eq!(&source[first_start..first_start + 4], "#ref");
+        assert_eq!(source[..first_start].matches("#ref").count(), 0);
+    }
+
+    #[derive(Default)]
+    struct CapturingLogger {
+        messages: Mutex<Vec<(LogLevel, String)>>,
+    }
+
+    impl CapturingLogger {
+        fn log(&self, level: LogLevel, msg: &str) {
+            self.messages.lock().unwrap().push((level, msg.to_string()));
+        }
+    }
+
+    impl Logger for CapturingLogger {
+        fn level(&self) -> LogLevel {
+            LogLevel::Debug
+        }
+        fn debug(&self, msg: &str) {
+            self.log(LogLevel::Debug, msg);
+        }
+        fn info(&self, msg: &str) {
+            self.log(LogLevel::Info, msg);
+        }
+        fn warn(&self, msg: &str) {
+            self.log(LogLevel::Warn, msg);
+        }
+        fn error(&self, msg: &str) {
+            self.log(LogLevel::Error, msg);
+        }
+    }
+
+    #[test]
+    fn test_compile_reports_progress_to_logger() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[(
+            "/app/cmp.ts",
+            r#"
+                import { Component } from '@angular/core';
+
+                @Component({ selector: 'app-cmp', standalone: true, template: '<p>hi</p>' })
+                export class Cmp {}
+            "#,
+        )])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            root_dir: Some("/app".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let logger = Arc::new(CapturingLogger::default());
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        })
+        .with_logger(logger.clone());
+        let result = compiler
+            .analyze_async(&["/app/cmp.ts".to_string()])
+            .unwrap();
+        compiler.emit(&result).unwrap();
+
+        let messages = logger.messages.lock().unwrap();
+        assert!(messages.iter().any(|(level, _)| *level == LogLevel::Info));
+    }
+
+    #[test]
+    fn test_emit_declaration_only_reports_write_failures() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[
+            ("/lib/dir.ts", "export class Dir {}\n"),
+            // A file where the output directory should be.
+            ("/dist", ""),
+        ])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/lib/tsconfig.json".to_string(),
+            out_dir: Some("/dist".to_string()),
+            root_dir: Some("/lib".to_string()),
+            emit_declaration_only: true,
+            ..NgCompilerOptions::default()
+        };
+        let logger = Arc::new(CapturingLogger::default());
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        })
+        .with_logger(logger.clone());
+        let result = compiler
+            .analyze_async(&["/lib/dir.ts".to_string()])
+            .unwrap();
+        compiler.emit(&result).unwrap();
+
+        let messages = logger.messages.lock().unwrap();
+        assert!(messages.iter().any(|(level, msg)| {
+            *level == LogLevel::Warn && msg.starts_with("Failed to write /dist/dir.d.ts")
+        }));
+    }
+
+    #[test]
+    fn test_emit_hmr_initializer_and_update_module() {
+        let emit = |hmr: bool| {
+            let fs = MemoryFileSystem::new();
+            fs.init_with_files(&[(
+                "/app/app.ts",
+                r#"
+                    import { Component } from '@angular/core';
+
+                    @Component({ selector: 'app-root', standalone: true, template: '<p>{{ title }}</p>' })
+                    export class AppComponent {
+                        title = 'app';
+                    }
+                "#,
+            )])
+            .unwrap();
+            let options = NgCompilerOptions {
+                project: "/app/tsconfig.json".to_string(),
+                out_dir: Some("/dist".to_string()),
+                root_dir: Some("/app".to_string()),
+                hmr,
+                ..NgCompilerOptions::default()
+            };
+            let mut compiler = NgCompiler::new(CompilationTicket {
+                kind: CompilationTicketKind::Fresh,
+                options,
+                fs: &fs,
+            });
+            let result = compiler
+                .analyze_async(&["/app/app.ts".to_string()])
+                .unwrap();
+            assert!(compiler.emit(&result).unwrap().is_empty());
+            let output = fs
+                .read_file(&AbsoluteFsPath::new("/dist/app.js".to_string()))
+                .unwrap();
+            let update_module = compiler.emit_hmr_update_module(
+                &result,
+                std::path::Path::new("/app/app.ts"),
+                "AppComponent",
+            );
+            (output, update_module)
+        };
+
+        let (output, update_module) = emit(true);
+        assert!(output
+            .contains("i0.ɵɵreplaceMetadata(AppComponent, m.default, [i0], [], import.meta, id)"));
+        assert!(output.contains("(typeof ngDevMode === 'undefined' || ngDevMode) && import.meta.hot && import.meta.hot.on('angular:component-update'"));
+        let update_module = update_module.expect("HMR update module should be emitted");
+        assert!(update_module.starts_with(
+            "export default function AppComponent_UpdateMetadata(AppComponent, ɵɵnamespaces) {\n  const i0 = ɵɵnamespaces[0];\n  AppComponent.ɵcmp = i0.ɵɵdefineComponent({"
+        ));
+        assert!(update_module.contains("i0.ɵɵtextInterpolate(ctx.title);"));
+
+        let (output, update_module) = emit(false);
+        assert!(!output.contains("ɵɵreplaceMetadata"));
+        assert!(!output.contains("import.meta.hot"));
+        assert!(update_module.is_none());
+    }
+
+    #[test]
+    fn test_report_unused_standalone_imports() {
+        let source = r#"
+            import { Component, Directive } from '@angular/core';
+
+            @Directive({ selector: '[highlight]', standalone: true })
+            export class Highlight {}
+
+            @Directive({ selector: '[tooltip]', standalone: true })
+            export class Tooltip {}
+
+            @Component({
+                selector: 'app-root',
+                standalone: true,
+                imports: [Highlight, Tooltip],
+                template: '<p highlight>Hello</p>',
+            })
+            export class AppComponent {}
+        "#;
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[("/app/app.ts", source)]).unwrap();
+        let options = NgCompilerOptions {
+            report_unused_imports: true,
+            ..NgCompilerOptions::default()
+        };
+        let mut compiler = NgCompiler::new(CompilationTicket {
+            kind: CompilationTicketKind::Fresh,
+            options,
+            fs: &fs,
+        });
+
+        let result = compiler
+            .analyze_async(&["/app/app.ts".to_string()])
+            .unwrap();
+
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.category, DiagnosticCategory::Suggestion);
+        assert_eq!(
+            diagnostic.message,
+            "Tooltip is not used within the template of AppComponent"
+        );
+        let start = source.find("Tooltip]").unwrap();
+        assert_eq!(diagnostic.start, Some(start));
+        assert_eq!(diagnostic.length, Some("Tooltip".len()));
+    }
 }
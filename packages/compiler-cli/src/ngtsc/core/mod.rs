@@ -2,6 +2,8 @@
 //!
 //! Corresponds to packages/compiler-cli/src/ngtsc/core
 
+use crate::ngtsc::typecheck::TypeCheckingConfig;
+use crate::transformers::api::DiagnosticCategory;
 use std::path::PathBuf;
 
 pub mod ast_transformer;
@@ -9,6 +11,7 @@ pub mod ast_transformer;
 pub mod compiler;
 #[cfg(test)]
 mod compiler_test;
+mod unused_imports;
 
 pub use compiler::{CompilationResult, CompilationTicket, CompilationTicketKind, NgCompiler};
 
@@ -20,16 +23,92 @@ pub struct NgCompilerOptions {
     pub strict_injection_parameters: bool,
     pub skip_template_codegen: bool,
     pub flat_module_out_file: Option<String>,
+    /// `flatModuleId`: the module name consumers import the flat module index as.
+    pub flat_module_id: Option<String>,
     pub out_dir: Option<String>,
+    /// `emitDeclarationOnly`: only write `.d.ts` files, with their Ivy declaration fields.
+    pub emit_declaration_only: bool,
     pub root_dir: Option<String>,
+    /// `fullTemplateTypeCheck`: check embedded templates without the `strictTemplates` checks.
+    pub full_template_type_check: bool,
+    /// `strictInputTypes`: overrides whether input binding types are checked.
+    pub strict_input_types: Option<bool>,
+    /// `strictNullInputTypes`: overrides whether `null`/`undefined` inputs are reported.
+    pub strict_null_input_types: Option<bool>,
+    /// `strictOutputEventTypes`: overrides whether `$event` is typed in output handlers.
+    pub strict_output_event_types: Option<bool>,
+    /// `strictDomLocalRefTypes`: overrides whether references to DOM elements are typed.
+    pub strict_dom_local_ref_types: Option<bool>,
+    /// `reportUnusedImports`: suggest removing directives and pipes that are imported or
+    /// declared but never used in a template.
+    pub report_unused_imports: bool,
+    /// `_enableHmr`: make components replaceable at runtime by the dev server, which serves the
+    /// modules returned by `NgCompiler::emit_hmr_update_module`.
+    pub hmr: bool,
+    /// `tracePerformance`: record the time spent in each phase of the compilation, see
+    /// `NgCompiler::perf_recorder`.
+    pub trace_performance: bool,
+    /// `generateNgFactoryShims`: emit a `.ngfactory.js` next to each file, with an
+    /// `NgModuleFactory` for each of its NgModules.
+    pub generate_ng_factory_shims: bool,
+    /// `generateNgSummaryShims`: emit a `.ngsummary.js` next to each file.
+    pub generate_ng_summary_shims: bool,
+    /// `inlineSourceMap`: end each emitted JavaScript file with its source map, base64-encoded in
+    /// a `sourceMappingURL` comment.
+    pub inline_source_map: bool,
+    /// `enableI18nLegacyMessageIdFormat`: write the legacy ids of extracted i18n messages next to
+    /// their primary id, so translations keyed by the legacy ids keep matching. Enabled unless
+    /// set to `false`, see `i18n_legacy_message_ids`.
+    pub enable_i18n_legacy_message_id_format: Option<bool>,
+    /// Attach the template location of each element to the DOM for development tooling, see
+    /// `CompilerConfig::debug`.
+    pub enable_debug_locations: bool,
+}
+
+impl NgCompilerOptions {
+    /// Whether extracted i18n messages are written with their legacy ids.
+    pub fn i18n_legacy_message_ids(&self) -> bool {
+        self.enable_i18n_legacy_message_id_format != Some(false)
+    }
+
+    /// The template type-checking configuration for these options.
+    ///
+    /// `strictTemplates` enables every check and `fullTemplateTypeCheck` only checks template
+    /// bodies and pipes. The `strict*Types` flags then override individual checks.
+    pub fn type_checking_config(&self) -> TypeCheckingConfig {
+        let full = self.strict_templates || self.full_template_type_check;
+        let strict = self.strict_templates;
+        TypeCheckingConfig {
+            apply_full_template_type_check_mode: full,
+            check_template_bodies: full,
+            check_type_of_input_bindings: self.strict_input_types.unwrap_or(strict),
+            strict_null_input_types: self.strict_null_input_types.unwrap_or(strict),
+            check_type_of_output_events: self.strict_output_event_types.unwrap_or(strict),
+            check_type_of_dom_references: self.strict_dom_local_ref_types.unwrap_or(strict),
+            check_type_of_pipes: full,
+            ..TypeCheckingConfig::default()
+        }
+    }
 }
 
 /// Compilation diagnostics
 #[derive(Debug, Clone)]
 pub struct Diagnostic {
+    pub category: DiagnosticCategory,
     pub file: Option<PathBuf>,
     pub message: String,
     pub code: usize,
     pub start: Option<usize>,
     pub length: Option<usize>,
+    /// Other locations relevant to the diagnostic, e.g. the first of duplicate declarations.
+    pub related_information: Vec<DiagnosticRelatedInformation>,
+}
+
+/// A location related to a [`Diagnostic`].
+#[derive(Debug, Clone)]
+pub struct DiagnosticRelatedInformation {
+    pub file: Option<PathBuf>,
+    pub message: String,
+    pub start: Option<usize>,
+    pub length: Option<usize>,
 }
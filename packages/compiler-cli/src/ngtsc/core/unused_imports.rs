@@ -0,0 +1,157 @@
+//! Unused Imports
+//!
+//! Reports directives and pipes that are in scope of a template but never used by one, so that
+//! they can be removed from `imports` and `declarations` arrays.
+
+use super::Diagnostic;
+use crate::ngtsc::diagnostics::ErrorCode;
+use crate::ngtsc::metadata::{DecoratorMetadata, T2DirectiveMetadata};
+use crate::ngtsc::typecheck::src::bind_template;
+use crate::transformers::api::DiagnosticCategory;
+use angular_compiler::render3::view::template::parse_template;
+use std::collections::{HashMap, HashSet};
+
+/// The directives and pipes a template uses: directive class names and pipe names.
+#[derive(Default)]
+struct TemplateUsage {
+    directives: HashSet<String>,
+    pipes: HashSet<String>,
+}
+
+impl TemplateUsage {
+    fn of(template: &str, directives: &[T2DirectiveMetadata]) -> Self {
+        let parsed = parse_template(template, "", Default::default());
+        let bound = bind_template(parsed.nodes, directives);
+        Self {
+            directives: bound
+                .get_used_directives()
+                .into_iter()
+                .map(|dir| dir.name)
+                .collect(),
+            pipes: bound.get_used_pipes().into_iter().collect(),
+        }
+    }
+
+    fn extend(&mut self, other: TemplateUsage) {
+        self.directives.extend(other.directives);
+        self.pipes.extend(other.pipes);
+    }
+}
+
+/// Suggest removing the directives and pipes that are imported by a standalone component but
+/// not used in its template, and those declared by an NgModule, but neither exported nor used
+/// by any template of the compilation.
+///
+/// `templates` maps component class names to their template source. Imports that are not
+/// directives or pipes of the compilation, e.g. NgModules from libraries, are not reported.
+pub fn unused_import_diagnostics(
+    metadata: &[DecoratorMetadata<'_>],
+    templates: &HashMap<String, String>,
+) -> Vec<Diagnostic> {
+    let directives: HashMap<&str, &T2DirectiveMetadata> = metadata
+        .iter()
+        .filter_map(|meta| match meta {
+            DecoratorMetadata::Directive(dir) => Some((dir.t2.name.as_str(), &dir.t2)),
+            _ => None,
+        })
+        .collect();
+    let pipes: HashMap<&str, &str> = metadata
+        .iter()
+        .filter_map(|meta| match meta {
+            DecoratorMetadata::Pipe(pipe) => Some((pipe.name.as_str(), pipe.pipe_name.as_str())),
+            _ => None,
+        })
+        .collect();
+    let is_used = |name: &str, usage: &TemplateUsage| match pipes.get(name) {
+        Some(pipe_name) => usage.pipes.contains(*pipe_name),
+        None => usage.directives.contains(name),
+    };
+
+    let all_directives: Vec<T2DirectiveMetadata> =
+        directives.values().map(|dir| (*dir).clone()).collect();
+
+    let mut diagnostics = Vec::new();
+    let mut usage_in_compilation = TemplateUsage::default();
+    for meta in metadata {
+        let DecoratorMetadata::Directive(component) = meta else {
+            continue;
+        };
+        let Some(template) = templates.get(&component.t2.name) else {
+            continue;
+        };
+        usage_in_compilation.extend(TemplateUsage::of(template, &all_directives));
+
+        let Some(imports) = component
+            .imports
+            .as_ref()
+            .filter(|_| component.is_standalone)
+        else {
+            continue;
+        };
+        let imported: Vec<_> = imports
+            .iter()
+            .filter(|import| {
+                directives.contains_key(import.debug_name())
+                    || pipes.contains_key(import.debug_name())
+            })
+            .collect();
+        let scope: Vec<T2DirectiveMetadata> = imported
+            .iter()
+            .filter_map(|import| directives.get(import.debug_name()))
+            .map(|dir| (*dir).clone())
+            .collect();
+        let usage = TemplateUsage::of(template, &scope);
+        for import in imported {
+            if is_used(import.debug_name(), &usage) {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                category: DiagnosticCategory::Suggestion,
+                file: component.source_file.clone(),
+                message: format!(
+                    "{} is not used within the template of {}",
+                    import.debug_name(),
+                    component.t2.name
+                ),
+                code: ErrorCode::UnusedStandaloneImports as usize,
+                start: import.span.map(|span| span.start as usize),
+                length: import.span.map(|span| span.size() as usize),
+                related_information: Vec::new(),
+            });
+        }
+    }
+
+    for meta in metadata {
+        let DecoratorMetadata::NgModule(module) = meta else {
+            continue;
+        };
+        for declaration in &module.declarations {
+            let is_component = directives
+                .get(declaration.as_str())
+                .is_some_and(|dir| dir.is_component);
+            let is_known = directives.contains_key(declaration.as_str())
+                || pipes.contains_key(declaration.as_str());
+            // Components are used by routes and bootstrapping as well as templates.
+            if !is_known
+                || is_component
+                || module.exports.contains(declaration)
+                || is_used(declaration, &usage_in_compilation)
+            {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                category: DiagnosticCategory::Suggestion,
+                file: module.source_file.clone(),
+                message: format!(
+                    "{} is declared in {} but not used within any template, nor exported",
+                    declaration, module.name
+                ),
+                code: ErrorCode::UnusedStandaloneImports as usize,
+                start: None,
+                length: None,
+                related_information: Vec::new(),
+            });
+        }
+    }
+    diagnostics
+}
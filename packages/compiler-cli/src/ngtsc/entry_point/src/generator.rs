@@ -2,6 +2,10 @@
 //
 // Generates flat module entry points.
 
+use crate::ngtsc::file_system::src::util::{clean_path, strip_extension};
+use crate::ngtsc::imports::src::emitter::calculate_relative_path;
+use std::path::Path;
+
 /// Generator for flat module entry points.
 pub struct FlatModuleEntryPointGenerator {
     /// Output file name.
@@ -53,3 +57,74 @@ pub struct FlatModuleExport {
     /// Source module.
     pub from: String,
 }
+
+/// Generates the flat module index for `flatModuleOutFile`: a file next to the entry point that
+/// re-exports it, so a library can be bundled and imported as a single module.
+#[derive(Debug, Clone)]
+pub struct FlatIndexGenerator {
+    /// The entry point the index re-exports.
+    pub entry_point: String,
+    /// Path of the generated index, with a `.ts` extension.
+    pub flat_index_path: String,
+    /// The `flatModuleId`, recorded as `importAs` in the metadata.
+    pub module_name: Option<String>,
+}
+
+impl FlatIndexGenerator {
+    /// `relative_flat_index_path` is the `flatModuleOutFile` option, resolved against the
+    /// directory of the entry point.
+    pub fn new(
+        entry_point: impl Into<String>,
+        relative_flat_index_path: &str,
+        module_name: Option<String>,
+    ) -> Self {
+        let entry_point = entry_point.into();
+        let entry_dir = Path::new(&entry_point)
+            .parent()
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let index_path = clean_path(&format!("{}/{}", entry_dir, relative_flat_index_path));
+        let flat_index_path = format!(
+            "{}.ts",
+            index_path.strip_suffix(".js").unwrap_or(&index_path)
+        );
+        Self {
+            entry_point,
+            flat_index_path,
+            module_name,
+        }
+    }
+
+    /// The module specifier of the entry point, relative to the index.
+    fn relative_entry_point(&self) -> String {
+        calculate_relative_path(&self.flat_index_path, &strip_extension(&self.entry_point))
+    }
+
+    /// The contents of the index. The same shim serves as the `.ts` source, the emitted `.js`
+    /// and the `.d.ts`.
+    pub fn make_top_level_shim(&self) -> String {
+        format!(
+            "/**\n * Generated bundle index. Do not edit.\n */\n\nexport * from '{}';\n",
+            self.relative_entry_point()
+        )
+    }
+
+    /// The `.metadata.json` of the index, which points bundlers at the entry point and records
+    /// the classes of its public API.
+    pub fn make_metadata(&self, classes: &[String]) -> String {
+        let symbols: serde_json::Map<String, serde_json::Value> = classes
+            .iter()
+            .map(|name| (name.clone(), serde_json::json!({ "__symbolic": "class" })))
+            .collect();
+        let mut metadata = serde_json::json!({
+            "__symbolic": "module",
+            "version": 4,
+            "metadata": symbols,
+            "exports": [{ "from": self.relative_entry_point() }],
+        });
+        if let Some(module_name) = &self.module_name {
+            metadata["importAs"] = serde_json::Value::from(module_name.as_str());
+        }
+        metadata.to_string()
+    }
+}
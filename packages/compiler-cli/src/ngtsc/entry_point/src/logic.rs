@@ -2,7 +2,12 @@
 //
 // Entry point analysis logic.
 
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Statement;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
 use std::collections::HashSet;
+use std::path::Path;
 
 /// Entry point for compilation.
 #[derive(Debug, Clone)]
@@ -64,3 +69,76 @@ pub fn analyze_entry_point(_path: &str) -> EntryPointAnalysis {
         dependencies: Vec::new(),
     }
 }
+
+/// Find the entry point for the flat module index among the root files.
+///
+/// A single non-declaration `.ts` root file is the entry point. Otherwise the `index.ts` with the
+/// shortest path is used, if there is one.
+pub fn find_flat_index_entry_point(root_files: &[String]) -> Option<String> {
+    let ts_files: Vec<&String> = root_files
+        .iter()
+        .filter(|file| file.ends_with(".ts") && !file.ends_with(".d.ts"))
+        .collect();
+    if let [entry_point] = ts_files.as_slice() {
+        return Some(entry_point.to_string());
+    }
+
+    ts_files
+        .into_iter()
+        .filter(|file| {
+            Path::new(file)
+                .file_name()
+                .is_some_and(|name| name == "index.ts")
+        })
+        .min_by_key(|file| file.len())
+        .cloned()
+}
+
+/// A module re-exported by a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReExport {
+    /// The module specifier, e.g. `./button`.
+    pub specifier: String,
+    /// The re-exported bindings as `(name in the module, exported name)`, or `None` for
+    /// `export * from`.
+    pub names: Option<Vec<(String, String)>>,
+}
+
+/// The modules re-exported by the TypeScript `source`, in order.
+pub fn get_reexports(source: &str) -> Vec<ReExport> {
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, source, SourceType::ts()).parse();
+
+    parsed
+        .program
+        .body
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::ExportAllDeclaration(export) if export.exported.is_none() => {
+                Some(ReExport {
+                    specifier: export.source.value.to_string(),
+                    names: None,
+                })
+            }
+            Statement::ExportNamedDeclaration(export) => {
+                let source = export.source.as_ref()?;
+                Some(ReExport {
+                    specifier: source.value.to_string(),
+                    names: Some(
+                        export
+                            .specifiers
+                            .iter()
+                            .map(|spec| {
+                                (
+                                    spec.local.name().to_string(),
+                                    spec.exported.name().to_string(),
+                                )
+                            })
+                            .collect(),
+                    ),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -59,4 +59,79 @@ mod tests {
             assert!(output.contains("MyComponent"));
         }
     }
+
+    mod flat_index_tests {
+        use super::*;
+
+        #[test]
+        fn should_find_single_ts_root_file() {
+            let roots = vec![
+                "/lib/public_api.ts".to_string(),
+                "/lib/types.d.ts".to_string(),
+            ];
+            assert_eq!(
+                find_flat_index_entry_point(&roots),
+                Some("/lib/public_api.ts".to_string())
+            );
+        }
+
+        #[test]
+        fn should_prefer_shortest_index_among_many_root_files() {
+            let roots = vec![
+                "/lib/src/index.ts".to_string(),
+                "/lib/index.ts".to_string(),
+                "/lib/other.ts".to_string(),
+            ];
+            assert_eq!(
+                find_flat_index_entry_point(&roots),
+                Some("/lib/index.ts".to_string())
+            );
+
+            let no_index = vec!["/lib/a.ts".to_string(), "/lib/b.ts".to_string()];
+            assert_eq!(find_flat_index_entry_point(&no_index), None);
+        }
+
+        #[test]
+        fn should_find_reexported_modules() {
+            let reexports = get_reexports(
+                "export * from './button';\n\
+                 export { Menu, MenuItem as Item } from './menu';\n\
+                 export * as icons from './icons';\n\
+                 export class Local {}\n",
+            );
+            assert_eq!(
+                reexports,
+                vec![
+                    ReExport {
+                        specifier: "./button".to_string(),
+                        names: None,
+                    },
+                    ReExport {
+                        specifier: "./menu".to_string(),
+                        names: Some(vec![
+                            ("Menu".to_string(), "Menu".to_string()),
+                            ("MenuItem".to_string(), "Item".to_string()),
+                        ]),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn should_generate_index_next_to_entry_point() {
+            let gen = FlatIndexGenerator::new(
+                "/lib/src/public_api.ts",
+                "../my-lib.js",
+                Some("@org/my-lib".to_string()),
+            );
+            assert_eq!(gen.flat_index_path, "/lib/my-lib.ts");
+            assert!(gen
+                .make_top_level_shim()
+                .contains("export * from './src/public_api';"));
+            assert_eq!(
+                gen.make_metadata(&["Button".to_string()]),
+                r#"{"__symbolic":"module","version":4,"metadata":{"Button":{"__symbolic":"class"}},"exports":[{"from":"./src/public_api"}],"importAs":"@org/my-lib"}"#
+            );
+        }
+    }
 }
@@ -0,0 +1,490 @@
+use crate::ngtsc::file_system::src::types::{
+    AbsoluteFsPath, FileStats, FileSystem, PathManipulation, PathSegment, ReadonlyFileSystem,
+};
+use crate::ngtsc::file_system::src::util::clean_path;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, Error, ErrorKind};
+use std::sync::Mutex;
+
+/// The maximum number of symbolic links followed while resolving a single path.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// A `FileSystem` that keeps everything in memory, using POSIX paths.
+///
+/// Useful for tests and for embedding the compiler where there is no real disk. The root `/`
+/// always exists; other directories are created with `ensure_dir` (or `init_with_files`).
+pub struct MemoryFileSystem {
+    case_sensitive: bool,
+    state: Mutex<MemoryFileSystemState>,
+}
+
+#[derive(Default)]
+struct MemoryFileSystemState {
+    cwd: String,
+    /// Every entry except the root, keyed by the spelling it was created with.
+    entries: HashMap<String, Entry>,
+    /// The lowercased path of every entry, for case-insensitive lookups.
+    folded: HashMap<String, String>,
+    /// The names of the entries in each directory.
+    children: HashMap<String, BTreeSet<String>>,
+}
+
+impl MemoryFileSystemState {
+    fn insert(&mut self, path: String, entry: Entry) {
+        self.children
+            .entry(dirname(&path))
+            .or_default()
+            .insert(basename(&path).to_string());
+        self.folded.insert(path.to_lowercase(), path.clone());
+        self.entries.insert(path, entry);
+    }
+
+    fn remove(&mut self, path: &str) {
+        if let Some(names) = self.children.get_mut(&dirname(path)) {
+            names.remove(basename(path));
+        }
+        self.children.remove(path);
+        self.folded.remove(&path.to_lowercase());
+        self.entries.remove(path);
+    }
+}
+
+enum Entry {
+    File(Vec<u8>),
+    Directory,
+    SymLink(AbsoluteFsPath),
+}
+
+enum EntryKind {
+    File,
+    Directory,
+    SymLink,
+}
+
+impl MemoryFileSystem {
+    /// Create an empty, case-sensitive file system whose working directory is `/`.
+    pub fn new() -> Self {
+        MemoryFileSystem {
+            case_sensitive: true,
+            state: Mutex::new(MemoryFileSystemState {
+                cwd: "/".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Set whether paths are compared case-sensitively. Names keep the case they were created
+    /// with either way.
+    pub fn with_case_sensitivity(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Write the given files, creating their parent directories as needed.
+    pub fn init_with_files(&self, files: &[(&str, &str)]) -> io::Result<()> {
+        for (path, content) in files {
+            let path = self.resolve(&[path]);
+            self.ensure_dir(&AbsoluteFsPath::new(self.dirname(path.as_str())))?;
+            self.write_file(&path, content.as_bytes(), None)?;
+        }
+        Ok(())
+    }
+
+    fn same_path(&self, a: &str, b: &str) -> bool {
+        if self.case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase() == b.to_lowercase()
+        }
+    }
+
+    /// Find the stored spelling of `path`, along with what kind of entry it is.
+    fn find_entry(
+        &self,
+        state: &MemoryFileSystemState,
+        path: &str,
+    ) -> Option<(AbsoluteFsPath, EntryKind)> {
+        if path == "/" {
+            return Some((AbsoluteFsPath::new("/".to_string()), EntryKind::Directory));
+        }
+        let (key, entry) = if self.case_sensitive {
+            state.entries.get_key_value(path)?
+        } else {
+            state
+                .entries
+                .get_key_value(state.folded.get(&path.to_lowercase())?)?
+        };
+        let kind = match entry {
+            Entry::File(_) => EntryKind::File,
+            Entry::Directory => EntryKind::Directory,
+            Entry::SymLink(_) => EntryKind::SymLink,
+        };
+        Some((AbsoluteFsPath::new(key.clone()), kind))
+    }
+
+    /// Resolve the symbolic links in `path`. The last segment is only followed when
+    /// `follow_last` is set, matching `stat` vs `lstat`.
+    fn resolve_links(
+        &self,
+        state: &MemoryFileSystemState,
+        path: &AbsoluteFsPath,
+        follow_last: bool,
+    ) -> io::Result<String> {
+        let mut pending: Vec<String> = split_path(path.as_str()).into_iter().rev().collect();
+        let mut resolved = String::from("/");
+        let mut hops = 0;
+
+        while let Some(segment) = pending.pop() {
+            let candidate = join_segment(&resolved, &segment);
+            let is_last = pending.is_empty();
+            match self.find_entry(state, &candidate) {
+                Some((key, EntryKind::SymLink)) if follow_last || !is_last => {
+                    hops += 1;
+                    if hops > MAX_SYMLINK_HOPS {
+                        return Err(Error::other(format!(
+                            "Too many symbolic links while resolving {}",
+                            path
+                        )));
+                    }
+                    let Some(Entry::SymLink(target)) = state.entries.get(key.as_str()) else {
+                        unreachable!("{} was found as a symbolic link", key);
+                    };
+                    pending.extend(split_path(target.as_str()).into_iter().rev());
+                    resolved = String::from("/");
+                }
+                Some((key, _)) => resolved = key.into_string(),
+                None => resolved = candidate,
+            }
+        }
+        Ok(resolved)
+    }
+
+    fn stat_entry(&self, path: &AbsoluteFsPath, follow_last: bool) -> io::Result<FileStats> {
+        let state = self.state.lock().unwrap();
+        let resolved = self.resolve_links(&state, path, follow_last)?;
+        let (is_file, is_directory, is_symbolic_link) = match self.find_entry(&state, &resolved) {
+            Some((_, EntryKind::File)) => (true, false, false),
+            Some((_, EntryKind::Directory)) => (false, true, false),
+            Some((_, EntryKind::SymLink)) => (false, false, true),
+            None => return Err(not_found(path)),
+        };
+        Ok(FileStats {
+            is_file,
+            is_directory,
+            is_symbolic_link,
+        })
+    }
+
+    /// Resolve the parent of `path` to an existing directory and return the stored spelling of
+    /// `path` within it.
+    fn entry_path_in_parent(
+        &self,
+        state: &MemoryFileSystemState,
+        path: &AbsoluteFsPath,
+    ) -> io::Result<AbsoluteFsPath> {
+        let path = self.resolve_links(state, path, false)?;
+        let parent = dirname(&path);
+        match self.find_entry(state, &parent) {
+            Some((_, EntryKind::Directory)) => {}
+            Some(_) => return Err(not_a_directory(&parent)),
+            None => return Err(not_found(&parent)),
+        }
+        Ok(match self.find_entry(state, &path) {
+            Some((key, _)) => key,
+            None => AbsoluteFsPath::new(path),
+        })
+    }
+}
+
+impl Default for MemoryFileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathManipulation for MemoryFileSystem {
+    fn extname(&self, path: &str) -> String {
+        let name = self.basename(path, None).as_str().to_string();
+        match name.rfind('.') {
+            Some(idx) if idx > 0 => name[idx..].to_string(),
+            _ => String::new(),
+        }
+    }
+    fn is_root(&self, path: &AbsoluteFsPath) -> bool {
+        clean_path(path.as_str()) == "/"
+    }
+    fn is_rooted(&self, path: &str) -> bool {
+        path.starts_with('/')
+    }
+    fn dirname(&self, file: &str) -> String {
+        let cleaned = clean_path(file);
+        if !self.is_rooted(&cleaned) && !cleaned.contains('/') {
+            return ".".to_string();
+        }
+        dirname(&cleaned)
+    }
+    fn join(&self, base_path: &str, paths: &[&str]) -> String {
+        let mut full_path = base_path.to_string();
+        for p in paths {
+            full_path.push('/');
+            full_path.push_str(p);
+        }
+        clean_path(&full_path)
+    }
+    fn relative(&self, from: &str, to: &str) -> String {
+        let from_segments = split_path(&clean_path(from));
+        let to_segments = split_path(&clean_path(to));
+        let common = from_segments
+            .iter()
+            .zip(to_segments.iter())
+            .take_while(|(a, b)| self.same_path(a, b))
+            .count();
+        let mut result = vec![".."; from_segments.len() - common];
+        result.extend(to_segments[common..].iter().map(String::as_str));
+        result.join("/")
+    }
+    fn basename(&self, file_path: &str, extension: Option<&str>) -> PathSegment {
+        let cleaned = clean_path(file_path);
+        let mut name = cleaned.rsplit('/').next().unwrap_or("").to_string();
+        if let Some(ext) = extension {
+            if name.ends_with(ext) {
+                name.truncate(name.len() - ext.len());
+            }
+        }
+        PathSegment::new(name)
+    }
+    fn normalize(&self, path: &str) -> String {
+        path.replace('\\', "/")
+    }
+    fn resolve(&self, paths: &[&str]) -> AbsoluteFsPath {
+        let mut resolved = self.pwd().into_string();
+        for p in paths {
+            let p = self.normalize(p);
+            if self.is_rooted(&p) {
+                resolved = p;
+            } else {
+                resolved = format!("{}/{}", resolved, p);
+            }
+        }
+        AbsoluteFsPath::new(clean_path(&resolved))
+    }
+    fn pwd(&self) -> AbsoluteFsPath {
+        AbsoluteFsPath::new(self.state.lock().unwrap().cwd.clone())
+    }
+    fn chdir(&self, path: &AbsoluteFsPath) {
+        let path = self.resolve(&[path.as_str()]);
+        self.state.lock().unwrap().cwd = path.into_string();
+    }
+}
+
+impl ReadonlyFileSystem for MemoryFileSystem {
+    fn is_case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    fn exists(&self, path: &AbsoluteFsPath) -> bool {
+        self.stat(path).is_ok()
+    }
+
+    fn read_file(&self, path: &AbsoluteFsPath) -> io::Result<String> {
+        let buffer = self.read_file_buffer(path)?;
+        String::from_utf8(buffer).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    fn read_file_buffer(&self, path: &AbsoluteFsPath) -> io::Result<Vec<u8>> {
+        let state = self.state.lock().unwrap();
+        let resolved = self.resolve_links(&state, path, true)?;
+        match self.find_entry(&state, &resolved) {
+            Some((key, EntryKind::File)) => match &state.entries[key.as_str()] {
+                Entry::File(data) => Ok(data.clone()),
+                _ => unreachable!("{} was found as a file", key),
+            },
+            Some(_) => Err(Error::other(format!(
+                "Unable to read file {}: it is a directory",
+                path
+            ))),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn readdir(&self, path: &AbsoluteFsPath) -> io::Result<Vec<PathSegment>> {
+        let state = self.state.lock().unwrap();
+        let resolved = self.resolve_links(&state, path, true)?;
+        let dir = match self.find_entry(&state, &resolved) {
+            Some((key, EntryKind::Directory)) => key.into_string(),
+            Some(_) => return Err(not_a_directory(path)),
+            None => return Err(not_found(path)),
+        };
+
+        Ok(state
+            .children
+            .get(&dir)
+            .into_iter()
+            .flatten()
+            .map(|name| PathSegment::new(name.clone()))
+            .collect())
+    }
+
+    fn lstat(&self, path: &AbsoluteFsPath) -> io::Result<FileStats> {
+        self.stat_entry(path, false)
+    }
+
+    fn stat(&self, path: &AbsoluteFsPath) -> io::Result<FileStats> {
+        self.stat_entry(path, true)
+    }
+
+    fn realpath(&self, file_path: &AbsoluteFsPath) -> io::Result<AbsoluteFsPath> {
+        let state = self.state.lock().unwrap();
+        let resolved = self.resolve_links(&state, file_path, true)?;
+        if self.find_entry(&state, &resolved).is_none() {
+            return Err(not_found(file_path));
+        }
+        Ok(AbsoluteFsPath::new(resolved))
+    }
+
+    fn get_default_lib_location(&self) -> AbsoluteFsPath {
+        AbsoluteFsPath::new("/node_modules/typescript/lib".to_string())
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn write_file(
+        &self,
+        path: &AbsoluteFsPath,
+        data: &[u8],
+        exclusive: Option<bool>,
+    ) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        // Writing through a symbolic link writes its target.
+        let resolved = AbsoluteFsPath::new(self.resolve_links(&state, path, true)?);
+        let key = self.entry_path_in_parent(&state, &resolved)?;
+        match self.find_entry(&state, key.as_str()) {
+            Some((_, EntryKind::File)) if exclusive.unwrap_or(false) => {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("File {} already exists", path),
+                ))
+            }
+            Some((_, EntryKind::Directory)) => {
+                return Err(Error::other(format!(
+                    "Unable to write file {}: it is a directory",
+                    path
+                )))
+            }
+            _ => {}
+        }
+        state.insert(key.into_string(), Entry::File(data.to_vec()));
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &AbsoluteFsPath) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let key = self.entry_path_in_parent(&state, path)?;
+        match self.find_entry(&state, key.as_str()) {
+            Some((key, EntryKind::File | EntryKind::SymLink)) => state.remove(key.as_str()),
+            Some((_, EntryKind::Directory)) => {
+                return Err(Error::other(format!(
+                    "Unable to remove file {}: it is a directory",
+                    path
+                )))
+            }
+            None => return Err(not_found(path)),
+        }
+        Ok(())
+    }
+
+    fn symlink(&self, target: &AbsoluteFsPath, path: &AbsoluteFsPath) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let key = self.entry_path_in_parent(&state, path)?;
+        if self.find_entry(&state, key.as_str()).is_some() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} already exists", path),
+            ));
+        }
+        state.insert(
+            key.into_string(),
+            Entry::SymLink(AbsoluteFsPath::new(clean_path(target.as_str()))),
+        );
+        Ok(())
+    }
+
+    fn copy_file(&self, from: &AbsoluteFsPath, to: &AbsoluteFsPath) -> io::Result<()> {
+        let data = self.read_file_buffer(from)?;
+        self.write_file(to, &data, None)
+    }
+
+    fn move_file(&self, from: &AbsoluteFsPath, to: &AbsoluteFsPath) -> io::Result<()> {
+        self.copy_file(from, to)?;
+        self.remove_file(from)
+    }
+
+    fn ensure_dir(&self, path: &AbsoluteFsPath) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let resolved = self.resolve_links(&state, path, true)?;
+        let mut current = String::from("/");
+        for segment in split_path(&resolved) {
+            let candidate = join_segment(&current, &segment);
+            current = match self.find_entry(&state, &candidate) {
+                Some((key, EntryKind::Directory)) => key.into_string(),
+                Some(_) => return Err(not_a_directory(&candidate)),
+                None => {
+                    state.insert(candidate.clone(), Entry::Directory);
+                    candidate
+                }
+            };
+        }
+        Ok(())
+    }
+
+    fn remove_deep(&self, path: &AbsoluteFsPath) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        let key = self.entry_path_in_parent(&state, path)?;
+        let mut pending = vec![key.into_string()];
+        while let Some(path) = pending.pop() {
+            if let Some(names) = state.children.get(&path) {
+                pending.extend(names.iter().map(|name| join_segment(&path, name)));
+            }
+            state.remove(&path);
+        }
+        Ok(())
+    }
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_string())
+        .collect()
+}
+
+fn join_segment(dir: &str, segment: &str) -> String {
+    match segment {
+        "." => dir.to_string(),
+        ".." => dirname(dir),
+        _ if dir == "/" => format!("/{}", segment),
+        _ => format!("{}/{}", dir, segment),
+    }
+}
+
+fn dirname(path: &str) -> String {
+    match path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(idx) => path[..idx].to_string(),
+    }
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or("")
+}
+
+fn not_found(path: impl std::fmt::Display) -> Error {
+    Error::new(
+        ErrorKind::NotFound,
+        format!("No such file or directory: {}", path),
+    )
+}
+
+fn not_a_directory(path: impl std::fmt::Display) -> Error {
+    Error::other(format!("Not a directory: {}", path))
+}
@@ -2,6 +2,7 @@ pub mod compiler_host;
 pub mod helpers;
 pub mod invalid_file_system;
 pub mod logical;
+pub mod memory_file_system;
 pub mod node_js_file_system;
 pub mod types;
 pub mod util;
@@ -10,6 +11,7 @@ pub use compiler_host::*;
 pub use helpers::*;
 pub use invalid_file_system::*;
 pub use logical::*;
+pub use memory_file_system::*;
 pub use node_js_file_system::*;
 pub use types::*;
 pub use util::*;
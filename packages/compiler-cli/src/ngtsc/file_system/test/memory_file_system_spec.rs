@@ -0,0 +1,138 @@
+use crate::ngtsc::file_system::src::memory_file_system::MemoryFileSystem;
+use crate::ngtsc::file_system::src::types::{
+    AbsoluteFsPath, FileSystem, PathManipulation, ReadonlyFileSystem,
+};
+
+fn abs(path: &str) -> AbsoluteFsPath {
+    AbsoluteFsPath::new(path.to_string())
+}
+
+fn names(fs: &MemoryFileSystem, dir: &str) -> Vec<String> {
+    fs.readdir(&abs(dir))
+        .unwrap()
+        .into_iter()
+        .map(|segment| segment.as_str().to_string())
+        .collect()
+}
+
+#[test]
+fn test_write_and_read_file() {
+    let fs = MemoryFileSystem::new();
+    fs.ensure_dir(&abs("/src/app")).unwrap();
+    fs.write_file(&abs("/src/app/main.ts"), b"export {};", None)
+        .unwrap();
+
+    assert!(fs.exists(&abs("/src/app/main.ts")));
+    assert_eq!(
+        fs.read_file(&abs("/src/app/main.ts")).unwrap(),
+        "export {};"
+    );
+    assert!(fs.stat(&abs("/src/app")).unwrap().is_directory());
+    assert!(fs.read_file(&abs("/src/app/missing.ts")).is_err());
+    assert!(fs.read_file(&abs("/src/app")).is_err());
+}
+
+#[test]
+fn test_write_requires_existing_parent() {
+    let fs = MemoryFileSystem::new();
+    assert!(fs.write_file(&abs("/missing/a.ts"), b"", None).is_err());
+
+    fs.write_file(&abs("/a.ts"), b"1", None).unwrap();
+    assert!(fs.write_file(&abs("/a.ts"), b"2", Some(true)).is_err());
+    assert_eq!(fs.read_file(&abs("/a.ts")).unwrap(), "1");
+}
+
+#[test]
+fn test_readdir_lists_direct_children() {
+    let fs = MemoryFileSystem::new();
+    fs.init_with_files(&[("/src/b.ts", ""), ("/src/a.ts", ""), ("/src/lib/c.ts", "")])
+        .unwrap();
+
+    assert_eq!(names(&fs, "/src"), vec!["a.ts", "b.ts", "lib"]);
+    assert_eq!(names(&fs, "/"), vec!["src"]);
+    assert!(fs.readdir(&abs("/src/a.ts")).is_err());
+    assert!(fs.readdir(&abs("/missing")).is_err());
+}
+
+#[test]
+fn test_realpath_follows_symlinks() {
+    let fs = MemoryFileSystem::new();
+    fs.init_with_files(&[("/node_modules/.pnpm/lib/index.d.ts", "x")])
+        .unwrap();
+    fs.symlink(&abs("/node_modules/.pnpm/lib"), &abs("/node_modules/lib"))
+        .unwrap();
+
+    assert_eq!(
+        fs.realpath(&abs("/node_modules/lib/index.d.ts")).unwrap(),
+        abs("/node_modules/.pnpm/lib/index.d.ts")
+    );
+    assert_eq!(
+        fs.read_file(&abs("/node_modules/lib/index.d.ts")).unwrap(),
+        "x"
+    );
+    assert!(fs
+        .lstat(&abs("/node_modules/lib"))
+        .unwrap()
+        .is_symbolic_link());
+    assert!(fs.stat(&abs("/node_modules/lib")).unwrap().is_directory());
+}
+
+#[test]
+fn test_case_sensitivity() {
+    let sensitive = MemoryFileSystem::new();
+    sensitive.init_with_files(&[("/App.ts", "")]).unwrap();
+    assert!(!sensitive.exists(&abs("/app.ts")));
+
+    let insensitive = MemoryFileSystem::new().with_case_sensitivity(false);
+    insensitive
+        .init_with_files(&[("/Src/App.ts", "a")])
+        .unwrap();
+    assert!(!insensitive.is_case_sensitive());
+    assert_eq!(insensitive.read_file(&abs("/src/app.ts")).unwrap(), "a");
+    assert_eq!(
+        insensitive.realpath(&abs("/src/app.ts")).unwrap(),
+        abs("/Src/App.ts")
+    );
+
+    // Writing through a different case updates the existing entry.
+    insensitive
+        .write_file(&abs("/SRC/APP.TS"), b"b", None)
+        .unwrap();
+    assert_eq!(names(&insensitive, "/src"), vec!["App.ts"]);
+    assert_eq!(insensitive.read_file(&abs("/Src/App.ts")).unwrap(), "b");
+}
+
+#[test]
+fn test_move_and_remove() {
+    let fs = MemoryFileSystem::new();
+    fs.init_with_files(&[("/out/a.js", "a"), ("/out/nested/b.js", "b")])
+        .unwrap();
+
+    fs.move_file(&abs("/out/a.js"), &abs("/out/c.js")).unwrap();
+    assert!(!fs.exists(&abs("/out/a.js")));
+    assert_eq!(fs.read_file(&abs("/out/c.js")).unwrap(), "a");
+
+    fs.remove_deep(&abs("/out")).unwrap();
+    assert!(!fs.exists(&abs("/out/nested/b.js")));
+    assert!(names(&fs, "/").is_empty());
+}
+
+#[test]
+fn test_path_manipulation() {
+    let fs = MemoryFileSystem::new();
+    fs.chdir(&abs("/project"));
+
+    assert_eq!(fs.pwd(), abs("/project"));
+    assert_eq!(fs.resolve(&["src", "../main.ts"]), abs("/project/main.ts"));
+    assert_eq!(
+        fs.relative("/project/src", "/project/lib/a.ts"),
+        "../lib/a.ts"
+    );
+    assert_eq!(fs.dirname("/project/main.ts"), "/project");
+    assert_eq!(
+        fs.basename("/project/main.ts", Some(".ts")).as_str(),
+        "main"
+    );
+    assert_eq!(fs.extname("/project/main.d.ts"), ".ts");
+    assert!(fs.is_root(&abs("/")));
+}
@@ -1,4 +1,5 @@
 pub mod compiler_host_spec;
 pub mod helpers_spec;
 pub mod logical_spec;
+pub mod memory_file_system_spec;
 pub mod node_js_file_system_spec;
@@ -2,6 +2,10 @@
 //
 // Metadata for hot module replacement.
 
+use angular_compiler::output::output_ast::{Expression, ReadVarExpr};
+use angular_compiler::render3::r3_hmr_compiler::{R3HmrMetadata, R3HmrNamespaceDependency};
+use std::collections::HashMap;
+
 /// HMR metadata.
 #[derive(Debug, Clone)]
 pub struct HmrMetadata {
@@ -33,3 +37,37 @@ pub fn generate_hmr_update_code(metadata: &HmrMetadata) -> String {
         metadata.component_name, metadata.component_id
     )
 }
+
+/// Extract the metadata needed to compile the HMR code of a component.
+///
+/// `namespaces` maps the modules imported by the compiled definition to their namespace alias.
+/// The namespaces are passed to the update callback in the order of their aliases, so `i0`
+/// (`@angular/core`) always comes first.
+pub fn extract_hmr_metadata(
+    class_name: &str,
+    file_path: &str,
+    namespaces: &HashMap<String, String>,
+) -> R3HmrMetadata {
+    let mut namespace_dependencies: Vec<R3HmrNamespaceDependency> = namespaces
+        .iter()
+        .map(|(module_name, alias)| R3HmrNamespaceDependency {
+            module_name: module_name.clone(),
+            assigned_name: alias.clone(),
+        })
+        .collect();
+    namespace_dependencies.sort_by(|a, b| {
+        (a.assigned_name.len(), &a.assigned_name).cmp(&(b.assigned_name.len(), &b.assigned_name))
+    });
+
+    R3HmrMetadata {
+        type_: Expression::ReadVar(ReadVarExpr {
+            name: class_name.to_string(),
+            type_: None,
+            source_span: None,
+        }),
+        class_name: class_name.to_string(),
+        file_path: file_path.to_string(),
+        namespace_dependencies,
+        local_dependencies: Vec::new(),
+    }
+}
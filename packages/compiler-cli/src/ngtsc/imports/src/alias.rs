@@ -4,6 +4,8 @@
 // exported/imported through alternative paths to avoid issues with
 // transitive dependencies.
 
+use super::find_export::{find_exported_name_of_node, ExportMap};
+
 /// Escape characters that aren't alphanumeric, '/' or '_'.
 fn escape_for_alias(s: &str) -> String {
     s.chars()
@@ -123,26 +125,26 @@ impl AliasingHost for UnifiedModulesAliasingHost {
     }
 }
 
+/// Looks up the exports of a file, or returns `None` if they are unknown.
+type ExportsGetter = Box<dyn Fn(&str) -> Option<ExportMap> + Send + Sync>;
+
 /// Aliasing host for private exports.
 ///
-/// Exports directives from any file containing an NgModule under a private symbol name.
-#[derive(Debug, Default)]
+/// Exports directives from any file containing an NgModule under a private symbol name, unless
+/// that file already exports them. This lets consumers of a library import every declaration an
+/// NgModule exports, even when the entry point doesn't export it publicly.
 pub struct PrivateExportAliasingHost {
-    /// Counter for generating unique private names.
-    counter: std::sync::atomic::AtomicU32,
+    exports_getter: ExportsGetter,
 }
 
 impl PrivateExportAliasingHost {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Generate a private alias name for a symbol.
-    fn generate_private_name(&self, symbol_name: &str, ng_module_name: &str) -> String {
-        let id = self
-            .counter
-            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        format!("ɵngExportɵ{}ɵ{}${}", ng_module_name, symbol_name, id)
+    pub fn new<F>(exports_getter: F) -> Self
+    where
+        F: Fn(&str) -> Option<ExportMap> + Send + Sync + 'static,
+    {
+        Self {
+            exports_getter: Box::new(exports_getter),
+        }
     }
 }
 
@@ -160,7 +162,13 @@ impl AliasingHost for PrivateExportAliasingHost {
             return None;
         }
 
-        Some(self.generate_private_name(decl_name, ng_module_name))
+        // A declaration the file already exports can be imported under its own name.
+        let exports = (self.exports_getter)(context_file).unwrap_or_default();
+        if find_exported_name_of_node(decl_name, &exports).is_some() {
+            return None;
+        }
+
+        Some(format!("ɵngExportɵ{}ɵ{}", ng_module_name, decl_name))
     }
 
     fn get_alias_in(
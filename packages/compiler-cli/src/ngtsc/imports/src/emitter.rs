@@ -279,6 +279,52 @@ impl ReferenceEmitStrategy for AbsoluteModuleStrategy {
     }
 }
 
+/// Strategy: Import references that live under `node_modules` from their package's public entry,
+/// e.g. `@scope/pkg`, rather than through a deep relative path.
+#[derive(Debug, Default)]
+pub struct PackageImportStrategy;
+
+impl PackageImportStrategy {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ReferenceEmitStrategy for PackageImportStrategy {
+    fn emit(
+        &self,
+        reference: &Reference,
+        _context_file: &str,
+        _import_flags: ImportFlags,
+    ) -> Option<ReferenceEmitResult> {
+        let source_file = reference.source_file.as_ref()?;
+        let source_file_str = source_file.to_string_lossy().replace('\\', "/");
+        let package = package_name_of(&source_file_str)?;
+
+        let import_expr = format!("import('{}').{}", package, reference.debug_name());
+        Some(ReferenceEmitResult::Success(EmittedReference::new(
+            import_expr,
+            ImportedFile::Known(source_file_str),
+        )))
+    }
+}
+
+/// The name of the package containing `file`, taken from its innermost `node_modules` directory.
+fn package_name_of(file: &str) -> Option<String> {
+    let (_, in_package) = file.rsplit_once("/node_modules/")?;
+    let mut segments = in_package.split('/');
+    let first = segments.next()?;
+    if first.starts_with('@') {
+        let name = segments.next()?;
+        // `name` must be a directory, not the file itself.
+        segments.next()?;
+        Some(format!("{}/{}", first, name))
+    } else {
+        segments.next()?;
+        Some(first.to_string())
+    }
+}
+
 /// Strategy: Use logical project paths.
 #[derive(Debug)]
 pub struct LogicalProjectStrategy {
@@ -319,7 +365,7 @@ impl ReferenceEmitStrategy for LogicalProjectStrategy {
 }
 
 /// Calculate relative path between two file paths.
-fn calculate_relative_path(from: &str, to: &str) -> String {
+pub(crate) fn calculate_relative_path(from: &str, to: &str) -> String {
     use std::path::Path;
 
     let from_path = Path::new(from);
@@ -25,8 +25,8 @@ pub use default::{
 pub use deferred_symbol_tracker::{DeferredSymbolTracker, SymbolState};
 pub use emitter::{
     AbsoluteModuleStrategy, EmittedReference, FailedEmitResult, ImportFlags, ImportedFile,
-    LocalIdentifierStrategy, LogicalProjectStrategy, ReferenceEmitKind, ReferenceEmitResult,
-    ReferenceEmitStrategy, ReferenceEmitter, RelativePathStrategy,
+    LocalIdentifierStrategy, LogicalProjectStrategy, PackageImportStrategy, ReferenceEmitKind,
+    ReferenceEmitResult, ReferenceEmitStrategy, ReferenceEmitter, RelativePathStrategy,
 };
 pub use find_export::{find_exported_name_of_node, ExportInfo, ExportMap};
 pub use imported_symbols_tracker::ImportedSymbolsTracker;
@@ -3,58 +3,131 @@
 // Used by RouterEntryPointManager and NgModuleRouteAnalyzer for resolving
 // module source-files in lazy-loaded routes.
 
-use std::path::PathBuf;
+use crate::ngtsc::file_system::{AbsoluteFsPath, ReadonlyFileSystem};
 
 /// Used for resolving module source-files references in lazy-loaded routes.
-#[derive(Debug)]
-pub struct ModuleResolver {
+pub struct ModuleResolver<'a> {
+    fs: &'a dyn ReadonlyFileSystem,
     /// Base path for resolving modules.
-    base_path: PathBuf,
+    base_path: AbsoluteFsPath,
+    /// The tsconfig `baseUrl`, which non-relative specifiers and `paths` substitutions are
+    /// resolved against.
+    base_url: Option<AbsoluteFsPath>,
+    /// The tsconfig `paths`, as `(pattern, substitutions)` in declaration order.
+    paths: Vec<(String, Vec<String>)>,
 }
 
-impl ModuleResolver {
-    pub fn new(base_path: impl Into<PathBuf>) -> Self {
+impl<'a> ModuleResolver<'a> {
+    pub fn new(fs: &'a dyn ReadonlyFileSystem, base_path: AbsoluteFsPath) -> Self {
         Self {
-            base_path: base_path.into(),
+            fs,
+            base_path,
+            base_url: None,
+            paths: Vec::new(),
         }
     }
 
+    /// Configure the tsconfig `baseUrl` and `paths` mappings, e.g.
+    /// `("@lib/*", vec!["libs/*/src/index.ts"])`.
+    pub fn with_path_mappings(
+        mut self,
+        base_url: AbsoluteFsPath,
+        paths: Vec<(String, Vec<String>)>,
+    ) -> Self {
+        self.base_url = Some(base_url);
+        self.paths = paths;
+        self
+    }
+
     /// Resolve a module by name relative to a containing file.
     ///
     /// # Arguments
     /// * `module_name` - The module specifier to resolve
     /// * `containing_file` - The file from which the module is being referenced
-    pub fn resolve_module(&self, module_name: &str, containing_file: &str) -> Option<PathBuf> {
+    pub fn resolve_module(
+        &self,
+        module_name: &str,
+        containing_file: &str,
+    ) -> Option<AbsoluteFsPath> {
         // Handle relative module paths
         if module_name.starts_with("./") || module_name.starts_with("../") {
-            let containing_dir = std::path::Path::new(containing_file)
-                .parent()
-                .unwrap_or(std::path::Path::new(""));
-
-            let resolved = containing_dir.join(module_name);
-
-            // Try with .ts extension
-            let with_ts = resolved.with_extension("ts");
-            if with_ts.exists() {
-                return Some(with_ts);
-            }
-
-            // Try with /index.ts
-            let with_index = resolved.join("index.ts");
-            if with_index.exists() {
-                return Some(with_index);
-            }
+            let containing_dir = self.fs.dirname(containing_file);
+            let resolved = self.fs.resolve(&[&containing_dir, module_name]);
+            return Some(self.resolve_source_file(&resolved).unwrap_or(resolved));
+        }
 
+        // Path mappings come first, then `baseUrl`, then node resolution.
+        if let Some(resolved) = self.resolve_path_mapping(module_name) {
             return Some(resolved);
         }
+        if let Some(base_url) = &self.base_url {
+            let candidate = self.fs.resolve(&[base_url.as_str(), module_name]);
+            if let Some(resolved) = self.resolve_source_file(&candidate) {
+                return Some(resolved);
+            }
+        }
 
         // Handle absolute/package paths
         // In a real implementation, this would use TypeScript's module resolution
-        let resolved = self.base_path.join("node_modules").join(module_name);
-        if resolved.exists() {
+        let resolved = self
+            .fs
+            .resolve(&[self.base_path.as_str(), "node_modules", module_name]);
+        if self.fs.exists(&resolved) {
             return Some(resolved);
         }
 
         None
     }
+
+    /// Resolve `module_name` through the `paths` mappings. As in TypeScript, an exact pattern wins
+    /// over wildcards, and among wildcard patterns the one with the longest prefix is used; its
+    /// substitutions are then tried in order.
+    fn resolve_path_mapping(&self, module_name: &str) -> Option<AbsoluteFsPath> {
+        let base_url = self.base_url.as_ref()?;
+
+        let mut best: Option<(&[String], &str, usize)> = None;
+        for (pattern, substitutions) in &self.paths {
+            match pattern.split_once('*') {
+                None if pattern == module_name => {
+                    best = Some((substitutions, "", usize::MAX));
+                    break;
+                }
+                None => {}
+                Some((prefix, suffix)) => {
+                    let matches = module_name.len() >= prefix.len() + suffix.len()
+                        && module_name.starts_with(prefix)
+                        && module_name.ends_with(suffix);
+                    if matches && best.is_none_or(|(_, _, len)| prefix.len() > len) {
+                        let captured = &module_name[prefix.len()..module_name.len() - suffix.len()];
+                        best = Some((substitutions, captured, prefix.len()));
+                    }
+                }
+            }
+        }
+
+        let (substitutions, captured, _) = best?;
+        substitutions.iter().find_map(|substitution| {
+            let candidate = self
+                .fs
+                .resolve(&[base_url.as_str(), &substitution.replacen('*', captured, 1)]);
+            self.resolve_source_file(&candidate)
+        })
+    }
+
+    /// Find the source file for a module path: the path itself, with a `.ts` or `.d.ts`
+    /// extension, or the `index.ts` inside it.
+    fn resolve_source_file(&self, path: &AbsoluteFsPath) -> Option<AbsoluteFsPath> {
+        let is_file =
+            |candidate: &AbsoluteFsPath| self.fs.stat(candidate).is_ok_and(|stats| stats.is_file());
+        if is_file(path) {
+            return Some(path.clone());
+        }
+        ["ts", "d.ts"]
+            .iter()
+            .map(|ext| AbsoluteFsPath::new(format!("{}.{}", path, ext)))
+            .chain(std::iter::once(
+                self.fs.resolve(&[path.as_str(), "index.ts"]),
+            ))
+            .find(is_file)
+    }
 }
@@ -0,0 +1,73 @@
+// Reference Emitter Tests
+
+use super::super::src::emitter::*;
+use super::super::src::references::Reference;
+use std::path::PathBuf;
+
+fn reference(name: &str, file: &str) -> Reference<'static> {
+    Reference::from_name(name, Some(PathBuf::from(file)))
+}
+
+#[test]
+fn test_package_import_strategy_uses_package_entry() {
+    let strategy = PackageImportStrategy::new();
+    let result = strategy
+        .emit(
+            &reference("X", "/app/node_modules/@foo/bar/index.d.ts"),
+            "/app/src/main.ts",
+            ImportFlags::NONE,
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.expression, "import('@foo/bar').X");
+    assert!(matches!(
+        result.imported_file,
+        ImportedFile::Known(ref file) if file == "/app/node_modules/@foo/bar/index.d.ts"
+    ));
+}
+
+#[test]
+fn test_package_import_strategy_ignores_deep_paths_and_nesting() {
+    let strategy = PackageImportStrategy::new();
+    let result = strategy
+        .emit(
+            &reference(
+                "Y",
+                "/app/node_modules/a/node_modules/lodash/src/internal/y.d.ts",
+            ),
+            "/app/src/main.ts",
+            ImportFlags::NONE,
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.expression, "import('lodash').Y");
+}
+
+#[test]
+fn test_package_import_strategy_skips_project_files() {
+    let strategy = PackageImportStrategy::new();
+    assert!(strategy
+        .emit(
+            &reference("Z", "/app/src/z.ts"),
+            "/app/src/main.ts",
+            ImportFlags::NONE,
+        )
+        .is_none());
+}
+
+#[test]
+fn test_reference_emitter_prefers_package_import_over_relative_path() {
+    let emitter = ReferenceEmitter::new(vec![
+        Box::new(LocalIdentifierStrategy::new()),
+        Box::new(PackageImportStrategy::new()),
+        Box::new(RelativePathStrategy::new()),
+    ]);
+    let result = emitter
+        .emit(
+            &reference("X", "/app/node_modules/@foo/bar/index.d.ts"),
+            "/app/src/main.ts",
+            ImportFlags::NONE,
+        )
+        .unwrap();
+    assert_eq!(result.expression, "import('@foo/bar').X");
+}
@@ -1,5 +1,7 @@
 // Imports Module Tests
 
 mod core_spec;
+mod emitter_spec;
 mod imported_symbols_tracker_spec;
 mod references_spec;
+mod resolver_spec;
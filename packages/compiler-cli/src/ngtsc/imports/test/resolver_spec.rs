@@ -0,0 +1,91 @@
+// Module Resolver Tests
+
+use super::super::src::resolver::*;
+use crate::ngtsc::file_system::{AbsoluteFsPath, FileSystem, MemoryFileSystem};
+
+fn abs(path: &str) -> AbsoluteFsPath {
+    AbsoluteFsPath::new(path.to_string())
+}
+
+fn project_fs(files: &[&str]) -> MemoryFileSystem {
+    let fs = MemoryFileSystem::new();
+    let files: Vec<(&str, &str)> = files.iter().map(|file| (*file, "export {};")).collect();
+    fs.init_with_files(&files).unwrap();
+    fs
+}
+
+const CONTAINING_FILE: &str = "/project/src/main.ts";
+
+#[test]
+fn test_resolve_relative_module() {
+    let fs = project_fs(&["/project/src/app/foo.ts", "/project/src/lazy/index.ts"]);
+    let resolver = ModuleResolver::new(&fs, abs("/project"));
+
+    assert_eq!(
+        resolver.resolve_module("./app/foo", CONTAINING_FILE),
+        Some(abs("/project/src/app/foo.ts"))
+    );
+    assert_eq!(
+        resolver.resolve_module("../src/lazy", CONTAINING_FILE),
+        Some(abs("/project/src/lazy/index.ts"))
+    );
+}
+
+#[test]
+fn test_resolve_exact_path_alias() {
+    let fs = project_fs(&["/project/src/app/foo.ts"]);
+    let resolver = ModuleResolver::new(&fs, abs("/project")).with_path_mappings(
+        abs("/project"),
+        vec![("@app/foo".to_string(), vec!["src/app/foo".to_string()])],
+    );
+
+    assert_eq!(
+        resolver.resolve_module("@app/foo", CONTAINING_FILE),
+        Some(abs("/project/src/app/foo.ts"))
+    );
+}
+
+#[test]
+fn test_resolve_wildcard_path_mapping() {
+    let fs = project_fs(&["/project/libs/ui/index.ts", "/project/libs/ui/button.ts"]);
+    let resolver = ModuleResolver::new(&fs, abs("/project")).with_path_mappings(
+        abs("/project"),
+        vec![
+            ("*".to_string(), vec!["types/*".to_string()]),
+            (
+                "@lib/*".to_string(),
+                vec!["missing/*".to_string(), "libs/*".to_string()],
+            ),
+        ],
+    );
+
+    // The longest matching prefix wins, and its substitutions are tried in order.
+    assert_eq!(
+        resolver.resolve_module("@lib/ui", CONTAINING_FILE),
+        Some(abs("/project/libs/ui/index.ts"))
+    );
+    assert_eq!(
+        resolver.resolve_module("@lib/ui/button", CONTAINING_FILE),
+        Some(abs("/project/libs/ui/button.ts"))
+    );
+}
+
+#[test]
+fn test_fall_back_to_node_resolution() {
+    let fs = project_fs(&[]);
+    fs.ensure_dir(&abs("/project/node_modules/@angular/core"))
+        .unwrap();
+    let resolver = ModuleResolver::new(&fs, abs("/project")).with_path_mappings(
+        abs("/project"),
+        vec![("@lib/*".to_string(), vec!["libs/*".to_string()])],
+    );
+
+    assert_eq!(
+        resolver.resolve_module("@angular/core", CONTAINING_FILE),
+        Some(abs("/project/node_modules/@angular/core"))
+    );
+    assert_eq!(
+        resolver.resolve_module("@lib/missing", CONTAINING_FILE),
+        None
+    );
+}
@@ -7,3 +7,4 @@ pub mod transform;
 
 pub use api::*;
 pub use context::*;
+pub use template::{get_template_identifiers, IdentifierKind, TemplateIdentifier};
@@ -2,6 +2,18 @@
 //
 // Indexes template elements and bindings.
 
+use crate::ngtsc::metadata::T2DirectiveMetadata;
+use crate::ngtsc::typecheck::src::{
+    bind_template, for_each_template_expression, for_each_template_node,
+};
+use angular_compiler::expression_parser::ast::AST;
+use angular_compiler::parse_util::ParseSourceSpan;
+use angular_compiler::render3::r3_ast as t;
+use angular_compiler::render3::view::t2_api::{DirectiveOwner, ReferenceTarget};
+use angular_compiler::render3::view::template::parse_template;
+use serde::Serialize;
+use std::collections::HashMap;
+
 /// Template element index.
 #[derive(Debug, Clone)]
 pub struct TemplateIndex {
@@ -57,3 +69,153 @@ pub fn index_template(_template: &str) -> TemplateIndex {
         references: Vec::new(),
     }
 }
+
+/// The kind of an identifier found in a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IdentifierKind {
+    /// A directive matched on an element or template.
+    Directive,
+    /// A pipe used in an expression.
+    Pipe,
+    /// A template reference variable, e.g. `#form`.
+    Reference,
+    /// A property, attribute, class or style binding.
+    Property,
+    /// An event binding.
+    Event,
+}
+
+/// An identifier in a template and the declaration it resolves to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateIdentifier {
+    /// The name as it is written in the template, or the directive class name.
+    pub name: String,
+    pub kind: IdentifierKind,
+    /// Start offset of the identifier in the template.
+    pub start: usize,
+    /// End offset of the identifier in the template.
+    pub end: usize,
+    /// The declaration the identifier resolves to, e.g. `DatePipe` or `NgModel.model`. `None`
+    /// when it doesn't resolve to a declaration, such as a DOM property.
+    pub target: Option<String>,
+}
+
+/// Index the identifiers in a component template: the directives matched on its nodes, the
+/// pipes, references and bindings it uses, in document order.
+///
+/// `pipes` maps the pipe names in scope of the component to their classes.
+pub fn get_template_identifiers(
+    template: &str,
+    directives: &[T2DirectiveMetadata],
+    pipes: &HashMap<String, String>,
+) -> Vec<TemplateIdentifier> {
+    let parsed = parse_template(template, "", Default::default());
+    let bound = bind_template(parsed.nodes, directives);
+    let Some(nodes) = bound.target().template.as_ref() else {
+        return Vec::new();
+    };
+
+    let mut identifiers = Vec::new();
+    for_each_template_node(nodes, &mut |node| {
+        let (owner, span, inputs, outputs, references) = match node {
+            t::R3Node::Element(el) => (
+                DirectiveOwner::Element((**el).clone()),
+                &el.start_source_span,
+                &el.inputs,
+                &el.outputs,
+                &el.references,
+            ),
+            t::R3Node::Template(tmpl) => (
+                DirectiveOwner::Template((**tmpl).clone()),
+                &tmpl.start_source_span,
+                &tmpl.inputs,
+                &tmpl.outputs,
+                &tmpl.references,
+            ),
+            _ => return,
+        };
+        let matched = bound.get_directives_of_node(&owner).unwrap_or_default();
+        for directive in &matched {
+            identifiers.push(identifier(
+                &directive.name,
+                IdentifierKind::Directive,
+                span,
+                Some(directive.name.clone()),
+            ));
+        }
+        for input in inputs {
+            let target = matched.iter().find_map(|dir| {
+                dir.inputs
+                    .iter()
+                    .find(|(_, entry)| entry.binding_property_name == *input.name)
+                    .map(|(_, entry)| format!("{}.{}", dir.name, entry.class_property_name))
+            });
+            identifiers.push(identifier(
+                &input.name,
+                IdentifierKind::Property,
+                &input.key_span,
+                target,
+            ));
+        }
+        for output in outputs {
+            let target = matched.iter().find_map(|dir| {
+                dir.outputs
+                    .iter()
+                    .find(|(_, entry)| entry.binding_property_name == *output.name)
+                    .map(|(_, entry)| format!("{}.{}", dir.name, entry.class_property_name))
+            });
+            identifiers.push(identifier(
+                &output.name,
+                IdentifierKind::Event,
+                &output.key_span,
+                target,
+            ));
+        }
+        for reference in references {
+            let target = bound
+                .get_reference_target(reference)
+                .map(|target| match target {
+                    ReferenceTarget::DirectiveOnNode { directive, .. } => directive.name,
+                    ReferenceTarget::Element(el) => el.name.to_string(),
+                    ReferenceTarget::Template(_) => "ng-template".to_string(),
+                });
+            identifiers.push(identifier(
+                &reference.name,
+                IdentifierKind::Reference,
+                &reference.key_span,
+                target,
+            ));
+        }
+    });
+
+    for_each_template_expression(nodes, &mut |ast| {
+        if let AST::BindingPipe(pipe) = ast {
+            identifiers.push(TemplateIdentifier {
+                name: pipe.name.clone(),
+                kind: IdentifierKind::Pipe,
+                start: pipe.name_span.start,
+                end: pipe.name_span.end,
+                target: pipes.get(&pipe.name).cloned(),
+            });
+        }
+    });
+
+    identifiers.sort_by_key(|identifier| identifier.start);
+    identifiers
+}
+
+fn identifier(
+    name: &str,
+    kind: IdentifierKind,
+    span: &ParseSourceSpan,
+    target: Option<String>,
+) -> TemplateIdentifier {
+    TemplateIdentifier {
+        name: name.to_string(),
+        kind,
+        start: span.start.offset,
+        end: span.end.offset,
+        target,
+    }
+}
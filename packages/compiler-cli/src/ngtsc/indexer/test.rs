@@ -97,4 +97,65 @@ mod tests {
             assert!(indexer.context().get_components("test.ts").is_some());
         }
     }
+
+    mod template_identifier_tests {
+        use super::*;
+        use crate::ngtsc::metadata::{ClassPropertyMapping, InputOrOutput, T2DirectiveMetadata};
+        use std::collections::HashMap;
+
+        #[test]
+        fn should_index_pipes_and_directives_with_targets() {
+            let mut inputs = ClassPropertyMapping::new();
+            inputs.insert(InputOrOutput {
+                class_property_name: "tooltipText".to_string(),
+                binding_property_name: "tooltip".to_string(),
+                is_signal: false,
+                required: false,
+                transform: None,
+            });
+            let tooltip = T2DirectiveMetadata {
+                name: "TooltipDirective".to_string(),
+                selector: Some("[tooltip]".to_string()),
+                inputs,
+                ..Default::default()
+            };
+            let pipes = HashMap::from([("date".to_string(), "DatePipe".to_string())]);
+
+            let template = "<span [tooltip]=\"hint\">{{ today | date }}</span>";
+            let identifiers = get_template_identifiers(template, &[tooltip], &pipes);
+
+            let directive = identifiers
+                .iter()
+                .find(|id| id.kind == IdentifierKind::Directive)
+                .unwrap();
+            assert_eq!(directive.target.as_deref(), Some("TooltipDirective"));
+
+            let property = identifiers
+                .iter()
+                .find(|id| id.kind == IdentifierKind::Property)
+                .unwrap();
+            assert_eq!(property.name, "tooltip");
+            assert_eq!(
+                property.target.as_deref(),
+                Some("TooltipDirective.tooltipText")
+            );
+
+            let pipe = identifiers
+                .iter()
+                .find(|id| id.kind == IdentifierKind::Pipe)
+                .unwrap();
+            assert_eq!(pipe.name, "date");
+            assert_eq!(pipe.target.as_deref(), Some("DatePipe"));
+            assert_eq!(&template[pipe.start..pipe.end], "date");
+
+            let json = serde_json::to_string(pipe).unwrap();
+            assert_eq!(
+                json,
+                format!(
+                    r#"{{"name":"date","kind":"pipe","start":{},"end":{},"target":"DatePipe"}}"#,
+                    pipe.start, pipe.end
+                )
+            );
+        }
+    }
 }
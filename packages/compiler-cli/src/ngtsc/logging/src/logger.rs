@@ -26,6 +26,9 @@ pub trait Logger {
 /// Null logger (logs nothing).
 pub struct NullLogger;
 
+/// Logger that discards every message, e.g. for tools that report diagnostics themselves.
+pub type SilentLogger = NullLogger;
+
 impl NullLogger {
     pub fn new() -> Self {
         Self
@@ -36,8 +36,9 @@ pub use src::api::{
 pub use src::property_mapping::{ClassPropertyMapping, ClassPropertyName, InputOrOutput};
 pub use src::registry::{MetadataReader, OxcMetadataReader};
 pub use src::util::{
-    extract_directive_metadata, extract_injectable_metadata, extract_pipe_metadata,
-    get_all_metadata,
+    extract_directive_metadata, extract_directive_metadata_with_evaluator,
+    extract_injectable_metadata, extract_ng_module_metadata, extract_pipe_metadata,
+    get_all_metadata, validate_host_directive_bindings,
 };
 pub use src::ModuleMetadataReader;
 
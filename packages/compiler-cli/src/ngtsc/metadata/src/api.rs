@@ -100,6 +100,10 @@ pub struct HostDirectiveMeta<'a> {
     pub inputs: Option<HashMap<String, String>>,
     /// Outputs from the host directive that have been exposed.
     pub outputs: Option<HashMap<String, String>>,
+    /// Source spans of the exposed inputs, keyed by their public name on the host directive.
+    pub input_spans: HashMap<String, oxc_span::Span>,
+    /// Source spans of the exposed outputs, keyed by their public name on the host directive.
+    pub output_spans: HashMap<String, oxc_span::Span>,
 }
 
 impl<'a> Clone for HostDirectiveMeta<'a> {
@@ -109,6 +113,8 @@ impl<'a> Clone for HostDirectiveMeta<'a> {
             is_forward_reference: self.is_forward_reference,
             inputs: self.inputs.clone(),
             outputs: self.outputs.clone(),
+            input_spans: self.input_spans.clone(),
+            output_spans: self.output_spans.clone(),
         }
     }
 }
@@ -143,11 +149,18 @@ pub struct T2DirectiveMetadata {
 #[derive(Debug, Clone, Default)]
 pub struct ComponentMetadata {
     pub template: Option<String>,
+    /// Offset of the inline template's content in the source file, i.e. just past the
+    /// opening quote of the `template` literal.
+    pub template_offset: Option<usize>,
     pub template_url: Option<String>,
     pub template_ast: Option<Vec<HtmlNode>>,
     pub styles: Option<Vec<String>>,
     pub style_urls: Option<Vec<String>>,
     pub change_detection: Option<angular_compiler::core::ChangeDetectionStrategy>,
+    pub encapsulation: Option<angular_compiler::core::ViewEncapsulation>,
+    /// The declared types of the class members, e.g. `title` -> `string | null`, which the
+    /// extended template checks use to reason about expressions.
+    pub member_types: HashMap<String, String>,
 }
 
 /// Metadata collected for a directive within an NgModule's scope.
@@ -218,6 +231,8 @@ pub struct DirectiveMeta<'a> {
     pub view_queries: Vec<QueryMetadata>,
     /// Lifecycle hooks detected on the class.
     pub lifecycle: angular_compiler::render3::view::api::R3LifecycleMetadata,
+    /// Errors found while extracting the metadata, e.g. an invalid input transform.
+    pub diagnostics: Vec<crate::ngtsc::core::Diagnostic>,
 }
 
 /// Constructor parameter metadata.
@@ -244,6 +259,8 @@ pub struct QueryMetadata {
     pub property_name: String,
     /// The template reference variable or component/directive type selector.
     pub selector: String,
+    /// Whether `selector` refers to a type (e.g. `MyComponent`) rather than reference names.
+    pub is_type_predicate: bool,
     /// Whether to return only the first match (ViewChild) or all matches (ViewChildren).
     pub first: bool,
     /// Whether to include descendants.
@@ -256,45 +273,87 @@ pub struct QueryMetadata {
     pub is_signal: bool,
 }
 
+impl T2DirectiveMeta for T2DirectiveMetadata {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn selector(&self) -> Option<&str> {
+        self.selector.as_deref()
+    }
+
+    fn is_component(&self) -> bool {
+        self.is_component
+    }
+
+    fn inputs(&self) -> &dyn angular_compiler::render3::view::t2_api::InputOutputPropertySet {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &dyn angular_compiler::render3::view::t2_api::InputOutputPropertySet {
+        &self.outputs
+    }
+
+    fn export_as(&self) -> Option<&[String]> {
+        self.export_as.as_deref()
+    }
+
+    fn is_structural(&self) -> bool {
+        self.is_structural
+    }
+
+    fn ng_content_selectors(&self) -> Option<&[String]> {
+        self.ng_content_selectors.as_deref()
+    }
+
+    fn preserve_whitespaces(&self) -> bool {
+        self.preserve_whitespaces
+    }
+
+    fn animation_trigger_names(&self) -> Option<&LegacyAnimationTriggerNames> {
+        self.animation_trigger_names.as_ref()
+    }
+}
+
 impl<'a> T2DirectiveMeta for DirectiveMeta<'a> {
     fn name(&self) -> &str {
-        &self.t2.name
+        self.t2.name()
     }
 
     fn selector(&self) -> Option<&str> {
-        self.t2.selector.as_deref()
+        self.t2.selector()
     }
 
     fn is_component(&self) -> bool {
-        self.t2.is_component
+        self.t2.is_component()
     }
 
     fn inputs(&self) -> &dyn angular_compiler::render3::view::t2_api::InputOutputPropertySet {
-        &self.t2.inputs
+        self.t2.inputs()
     }
 
     fn outputs(&self) -> &dyn angular_compiler::render3::view::t2_api::InputOutputPropertySet {
-        &self.t2.outputs
+        self.t2.outputs()
     }
 
     fn export_as(&self) -> Option<&[String]> {
-        self.t2.export_as.as_deref()
+        self.t2.export_as()
     }
 
     fn is_structural(&self) -> bool {
-        self.t2.is_structural
+        self.t2.is_structural()
     }
 
     fn ng_content_selectors(&self) -> Option<&[String]> {
-        self.t2.ng_content_selectors.as_deref()
+        self.t2.ng_content_selectors()
     }
 
     fn preserve_whitespaces(&self) -> bool {
-        self.t2.preserve_whitespaces
+        self.t2.preserve_whitespaces()
     }
 
     fn animation_trigger_names(&self) -> Option<&LegacyAnimationTriggerNames> {
-        self.t2.animation_trigger_names.as_ref()
+        self.t2.animation_trigger_names()
     }
 }
 
@@ -327,6 +386,7 @@ impl<'a> Default for DirectiveMeta<'a> {
             constructor_params: Vec::new(),
             view_queries: Vec::new(),
             lifecycle: angular_compiler::render3::view::api::R3LifecycleMetadata::default(),
+            diagnostics: Vec::new(),
         }
     }
 }
@@ -363,6 +423,7 @@ impl<'a> Clone for DirectiveMeta<'a> {
             constructor_params: self.constructor_params.clone(),
             view_queries: self.view_queries.clone(),
             lifecycle: self.lifecycle.clone(),
+            diagnostics: self.diagnostics.clone(),
         }
     }
 }
@@ -402,7 +463,17 @@ impl Default for PipeMeta {
 #[derive(Debug, Clone)]
 pub struct InjectableMeta {
     pub name: String,
+    /// `providedIn` as a JavaScript expression: a quoted scope such as `'root'`, or a reference
+    /// to the module providing the injectable.
     pub provided_in: Option<String>,
+    /// The provider the injectable is created from, as JavaScript expressions. `use_factory` is
+    /// parenthesized unless it can be called as written.
+    pub use_class: Option<String>,
+    pub use_factory: Option<String>,
+    pub use_existing: Option<String>,
+    pub use_value: Option<String>,
+    /// Tokens injected into `useClass`/`useFactory`, when `deps` is given.
+    pub deps: Option<Vec<String>>,
     pub source_file: Option<PathBuf>,
 }
 
@@ -420,6 +491,10 @@ pub struct NgModuleMeta {
     pub raw_declarations: Option<String>,
     pub raw_imports: Option<String>,
     pub raw_exports: Option<String>,
+    /// Components bootstrapped by the module.
+    pub bootstrap: Vec<String>,
+    /// The `providers` of the module, as a JavaScript expression.
+    pub providers: Option<String>,
     pub decorator: Option<String>,
     pub may_declare_providers: bool,
     pub source_file: Option<PathBuf>,
@@ -438,6 +513,8 @@ impl Default for NgModuleMeta {
             raw_declarations: None,
             raw_imports: None,
             raw_exports: None,
+            bootstrap: Vec::new(),
+            providers: None,
             decorator: None,
             may_declare_providers: false,
             source_file: None,
@@ -12,6 +12,10 @@ pub mod util;
 pub use api::*;
 pub use property_mapping::{ClassPropertyMapping, ClassPropertyName, InputOrOutput};
 pub use registry::{MetadataReader, OxcMetadataReader};
-pub use util::{extract_directive_metadata, extract_injectable_metadata, extract_pipe_metadata};
+pub use util::{
+    extract_directive_metadata, extract_directive_metadata_with_evaluator,
+    extract_injectable_metadata, extract_ng_module_metadata, extract_pipe_metadata,
+    validate_host_directive_bindings,
+};
 pub mod reader;
 pub use reader::ModuleMetadataReader;
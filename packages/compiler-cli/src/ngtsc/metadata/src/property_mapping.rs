@@ -50,6 +50,13 @@ impl ClassPropertyMapping {
         self.get(class_property_name)
     }
 
+    /// Whether any entry is bound under the given public name.
+    pub fn has_binding_property_name(&self, binding_property_name: &str) -> bool {
+        self.entries
+            .values()
+            .any(|entry| entry.binding_property_name == binding_property_name)
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&String, &InputOrOutput)> {
         self.entries.iter()
     }
@@ -19,6 +19,30 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// A directive, component or pipe definition read from a compiled library.
+struct LibraryDefinition {
+    /// The selector of a directive, or the name of a pipe.
+    selector_or_name: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    export_as: Option<Vec<String>>,
+    is_component: bool,
+    kind: R3TemplateDependencyKind,
+}
+
+impl LibraryDefinition {
+    fn pipe(name: String) -> Self {
+        LibraryDefinition {
+            selector_or_name: name,
+            inputs: vec![],
+            outputs: vec![],
+            export_as: None,
+            is_component: false,
+            kind: R3TemplateDependencyKind::Pipe,
+        }
+    }
+}
+
 pub struct ModuleMetadataReader {
     node_modules_path: PathBuf,
     project_root: PathBuf,
@@ -150,7 +174,7 @@ impl ModuleMetadataReader {
         visited.insert(entry_path.clone());
 
         // Map class name to metadata
-        let mut all_definitions = HashMap::new();
+        let mut all_definitions: HashMap<String, LibraryDefinition> = HashMap::new();
         // Names of classes exported by NgModules
         let mut all_exported_classes = Vec::new();
 
@@ -287,7 +311,11 @@ impl ModuleMetadataReader {
                                                                                     == "ɵcmp",
                                                                             )
                                                                         {
-                                                                            all_definitions.insert(class_name.to_string(), (meta.0, meta.1, meta.2, meta.3, R3TemplateDependencyKind::Directive));
+                                                                            all_definitions.insert(
+                                                                                class_name
+                                                                                    .to_string(),
+                                                                                meta,
+                                                                            );
                                                                         }
                                                                     }
                                                                 }
@@ -307,7 +335,12 @@ impl ModuleMetadataReader {
                                                                     if let Some(name) =
                                                                         self.parse_pipe_meta(expr)
                                                                     {
-                                                                        all_definitions.insert(class_name.to_string(), (name, vec![], vec![], false, R3TemplateDependencyKind::Pipe));
+                                                                        all_definitions.insert(
+                                                                            class_name.to_string(),
+                                                                            LibraryDefinition::pipe(
+                                                                                name,
+                                                                            ),
+                                                                        );
                                                                     }
                                                                 }
                                                             }
@@ -335,9 +368,7 @@ impl ModuleMetadataReader {
         };
 
         for export_name in classes_to_export {
-            if let Some((selector_or_name, inputs, outputs, is_component, kind)) =
-                all_definitions.get(&export_name)
-            {
+            if let Some(definition) = all_definitions.get(&export_name) {
                 let expression = Expression::External(ExternalExpr {
                     value: ExternalReference {
                         module_name: Some(module_name.to_string()),
@@ -348,22 +379,22 @@ impl ModuleMetadataReader {
                     source_span: None,
                 });
 
-                let meta = match kind {
+                let meta = match definition.kind {
                     R3TemplateDependencyKind::Directive => {
                         R3TemplateDependencyMetadata::Directive(R3DirectiveDependencyMetadata {
-                            selector: selector_or_name.clone(),
+                            selector: definition.selector_or_name.clone(),
                             type_: expression,
-                            inputs: inputs.clone(),
-                            outputs: outputs.clone(),
-                            export_as: vec![].into(),
+                            inputs: definition.inputs.clone(),
+                            outputs: definition.outputs.clone(),
+                            export_as: definition.export_as.clone(),
                             kind: R3TemplateDependencyKind::Directive,
-                            is_component: *is_component,
+                            is_component: definition.is_component,
                             source_span: None,
                         })
                     }
                     R3TemplateDependencyKind::Pipe => R3TemplateDependencyMetadata::Pipe(
                         angular_compiler::render3::view::api::R3PipeDependencyMetadata {
-                            name: selector_or_name.clone(),
+                            name: definition.selector_or_name.clone(),
                             type_: expression,
                             kind: R3TemplateDependencyKind::Pipe,
                             source_span: None,
@@ -405,12 +436,12 @@ impl ModuleMetadataReader {
         &self,
         expr: &OxcExpression,
         is_cmp: bool,
-    ) -> Option<(String, Vec<String>, Vec<String>, bool)> {
-        // Returns (selector, inputs, outputs, is_component)
+    ) -> Option<LibraryDefinition> {
         if let OxcExpression::ObjectExpression(obj) = expr {
             let mut selector = String::new();
             let mut inputs = Vec::new();
             let mut outputs = Vec::new();
+            let mut export_as = None;
 
             for prop in &obj.properties {
                 if let oxc_ast::ast::ObjectPropertyKind::ObjectProperty(p) = prop {
@@ -487,12 +518,35 @@ impl ModuleMetadataReader {
                                     }
                                 }
                             }
+                            "exportAs" => {
+                                // exportAs: ["foo", "bar"]
+                                if let OxcExpression::ArrayExpression(arr) = &p.value {
+                                    export_as = Some(
+                                        arr.elements
+                                            .iter()
+                                            .filter_map(|el| match el.as_expression() {
+                                                Some(OxcExpression::StringLiteral(s)) => {
+                                                    Some(s.value.to_string())
+                                                }
+                                                _ => None,
+                                            })
+                                            .collect(),
+                                    );
+                                }
+                            }
                             _ => {}
                         }
                     }
                 }
             }
-            return Some((selector, inputs, outputs, is_cmp));
+            return Some(LibraryDefinition {
+                selector_or_name: selector,
+                inputs,
+                outputs,
+                export_as,
+                is_component: is_cmp,
+                kind: R3TemplateDependencyKind::Directive,
+            });
         }
         None
     }
@@ -522,10 +576,18 @@ impl ModuleMetadataReader {
                 return None;
             }
         };
+        self.extract_ts_metadata_from_source(path, &content)
+    }
 
+    /// The directives and pipes declared in the TypeScript source `content` of `path`.
+    pub fn extract_ts_metadata_from_source(
+        &self,
+        path: &Path,
+        content: &str,
+    ) -> Option<Vec<R3TemplateDependencyMetadata>> {
         let allocator = Allocator::default();
         let source_type = SourceType::from_path(path).unwrap_or_default();
-        let ret = Parser::new(&allocator, &content, source_type).parse();
+        let ret = Parser::new(&allocator, content, source_type).parse();
 
         if !ret.errors.is_empty() {
             // eprintln!("DEBUG: [ts_reader] Parse errors for {}: {:?}", path.display(), ret.errors);
@@ -603,7 +665,7 @@ impl ModuleMetadataReader {
                                 name: name.to_string(),
                                 identifier: None, // We don't have resolved identifier yet
                                 import: None,
-                                node: dec,
+                                node: Some(dec),
                                 args,
                             };
 
@@ -6,16 +6,29 @@
 use crate::ngtsc::imports::OwningModule;
 use oxc_ast::ast::Program;
 use oxc_ast::ast::{Declaration, Expression, ModuleDeclaration, ObjectPropertyKind, PropertyKey};
+use oxc_codegen::Gen;
+use oxc_span::GetSpan;
 use std::collections::HashMap;
 
 use super::api::{
     ComponentMetadata, DecoratorMetadata, DirectiveMeta, DirectiveTypeCheckMeta, InjectableMeta,
-    MatchSource, MetaKind, PipeMeta, Reference, T2DirectiveMetadata,
+    MatchSource, MetaKind, NgModuleMeta, PipeMeta, Reference, T2DirectiveMetadata,
 };
 use super::property_mapping::{DecoratorInputTransform, InputOrOutput};
+use crate::ngtsc::annotations::directive::src::{
+    input_initializer_config, model_initializer_config, output_from_observable_config,
+    output_initializer_config, parse_options_from_pairs, query_initializer_apis,
+    try_parse_initializer_based_output, try_parse_model_function, try_parse_signal_input_mapping,
+    try_parse_signal_query, validate_access_of_initializer_api_member, AccessLevel,
+    InitializerApiConfig, InitializerFunctionName, InputOutputOptions, OptionsParseError,
+};
+use crate::ngtsc::core::Diagnostic;
+use crate::ngtsc::diagnostics::ErrorCode;
+use crate::ngtsc::partial_evaluator::{PartialEvaluator, ResolvedValue};
 use crate::ngtsc::reflection::{
     ClassDeclaration, Decorator, ReflectionHost, TypeScriptReflectionHost,
 };
+use crate::transformers::api::DiagnosticCategory;
 
 /// Extract directive metadata from a class declaration and its decorator.
 /// The lifetime `'a` is tied to the OXC AST allocator.
@@ -25,6 +38,26 @@ pub fn extract_directive_metadata<'a>(
     is_component: bool,
     source_file: &std::path::Path,
     imports_map: &HashMap<String, String>,
+) -> Option<DecoratorMetadata<'a>> {
+    extract_directive_metadata_with_evaluator(
+        class_decl,
+        decorator,
+        is_component,
+        source_file,
+        imports_map,
+        &PartialEvaluator::new(),
+    )
+}
+
+/// Like [`extract_directive_metadata`], but uses `evaluator` to resolve decorator fields that are
+/// derived from constants, e.g. `` selector: `app-${NAME}` `` or `{...BASE_METADATA}`.
+pub fn extract_directive_metadata_with_evaluator<'a>(
+    class_decl: &'a ClassDeclaration<'a>,
+    decorator: &Decorator<'a>,
+    is_component: bool,
+    source_file: &std::path::Path,
+    imports_map: &HashMap<String, String>,
+    evaluator: &PartialEvaluator,
 ) -> Option<DecoratorMetadata<'a>> {
     let name = class_decl
         .id
@@ -41,7 +74,10 @@ pub fn extract_directive_metadata<'a>(
             ..Default::default()
         },
         component: if is_component {
-            Some(ComponentMetadata::default())
+            Some(ComponentMetadata {
+                member_types: extract_member_types(class_decl),
+                ..Default::default()
+            })
         } else {
             None
         },
@@ -49,7 +85,7 @@ pub fn extract_directive_metadata<'a>(
         source_file: Some(source_file.to_path_buf()),
         type_check: DirectiveTypeCheckMeta::default(),
         // Store the OXC decorator reference directly
-        decorator: Some(decorator.node),
+        decorator: decorator.node,
         ..Default::default()
     };
 
@@ -155,6 +191,7 @@ pub fn extract_directive_metadata<'a>(
                 let mut is_input = false;
                 let mut binding_name = prop_name.to_string();
                 let mut transform_info: Option<DecoratorInputTransform> = None;
+                let mut required = false;
 
                 for dec in &prop.decorators {
                     if let Expression::CallExpression(call) = &dec.expression {
@@ -190,15 +227,24 @@ pub fn extract_directive_metadata<'a>(
                                                                         binding_name = s.value.to_string();
                                                                     }
                                                                 }
+                                                                "required" => {
+                                                                    if let Expression::BooleanLiteral(b) = &prop.value {
+                                                                        required = b.value;
+                                                                    }
+                                                                }
                                                                 "transform" => {
-                                                                   let node_str = match &prop.value {
-                                                                       Expression::Identifier(id) => id.name.to_string(),
-                                                                       _ => "TRANSFORM_EXPR".to_string(),
-                                                                   };
-                                                                   transform_info = Some(DecoratorInputTransform {
-                                                                       node: node_str.clone(),
-                                                                       type_ref: node_str,
-                                                                   });
+                                                                    match extract_decorator_input_transform(
+                                                                        class_decl,
+                                                                        prop_name,
+                                                                        &prop.value,
+                                                                        source_file,
+                                                                    ) {
+                                                                        Ok(transform) => transform_info = Some(transform),
+                                                                        Err(diagnostic) => {
+                                                                            meta.diagnostics.push(diagnostic);
+                                                                            meta.is_poisoned = true;
+                                                                        }
+                                                                    }
                                                                 }
                                                                 _ => {}
                                                             }
@@ -225,91 +271,70 @@ pub fn extract_directive_metadata<'a>(
                         class_property_name: prop_name.to_string(),
                         binding_property_name: binding_name.clone(),
                         is_signal: false,
-                        required: false,
+                        required,
                         transform: transform_info,
                     });
                 }
 
                 // 2. Check for signal input/model: input(), input.required(), model(), model.required()
-                if let Some(value) = &prop.value {
-                    if let Expression::CallExpression(call) = value {
-                        let mut is_signal = false;
-                        let mut is_model = false;
-                        let mut is_required = false;
-                        let mut alias = prop_name.to_string();
-
-                        match &call.callee {
-                            Expression::Identifier(ident) => {
-                                if ident.name == "input" {
-                                    is_signal = true;
-                                } else if ident.name == "model" {
-                                    is_signal = true;
-                                    is_model = true;
-                                }
-                            }
-                            Expression::StaticMemberExpression(member) => {
-                                if let Expression::Identifier(obj) = &member.object {
-                                    if (obj.name == "input" || obj.name == "model")
-                                        && member.property.name == "required"
-                                    {
-                                        is_signal = true;
-                                        is_required = true;
-                                        if obj.name == "model" {
-                                            is_model = true;
-                                        }
-                                    }
-                                }
-                            }
-                            _ => {}
-                        }
-
-                        if is_signal {
-                            // Extract options from arguments
-                            // input(initialValue, options) or input.required(options)
-                            // model(initialValue, options) or model.required(options)
-
-                            let options_arg = if is_required {
-                                call.arguments.first()
-                            } else {
-                                call.arguments.get(1)
-                            };
-
-                            if let Some(arg) = options_arg {
-                                if let Some(Expression::ObjectExpression(obj)) = arg.as_expression()
-                                {
-                                    for p in &obj.properties {
-                                        if let ObjectPropertyKind::ObjectProperty(op) = p {
-                                            if let PropertyKey::StaticIdentifier(k) = &op.key {
-                                                if k.name == "alias" {
-                                                    if let Some(val) =
-                                                        extract_string_value(&op.value)
-                                                    {
-                                                        alias = val;
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-
-                            meta.t2.inputs.insert(InputOrOutput {
-                                class_property_name: prop_name.to_string(),
-                                binding_property_name: alias.clone(),
-                                is_signal: true,
-                                required: is_required,
-                                transform: None, // Parsing signal inputs with transform is a separate task
+                if let Some(Expression::CallExpression(call)) = &prop.value {
+                    if let Some((function_name @ ("input" | "model"), is_required)) =
+                        initializer_function_name(call)
+                    {
+                        let config = if function_name == "model" {
+                            model_initializer_config()
+                        } else {
+                            input_initializer_config()
+                        };
+                        // input(initialValue, options) or input.required(options)
+                        // model(initialValue, options) or model.required(options)
+                        let options_index = if is_required { 0 } else { 1 };
+                        let parsed = validate_initializer_api_member(&config, prop, source_file)
+                            .and_then(|_| {
+                                parse_initializer_options(call, options_index, source_file)
                             });
 
-                            if is_model {
+                        match parsed {
+                            Ok(options) if function_name == "model" => {
+                                let model = try_parse_model_function(
+                                    prop_name,
+                                    Some(&options),
+                                    is_required,
+                                );
+                                meta.t2.inputs.insert(InputOrOutput {
+                                    class_property_name: model.property_name.clone(),
+                                    binding_property_name: model.input_name,
+                                    is_signal: true,
+                                    required: model.required,
+                                    transform: None,
+                                });
+                                // Model outputs are regular outputs event-wise
                                 meta.t2.outputs.insert(InputOrOutput {
-                                    class_property_name: prop_name.to_string(),
-                                    binding_property_name: format!("{}Change", alias),
-                                    is_signal: false, // Model outputs are regular outputs event-wise
+                                    class_property_name: model.property_name,
+                                    binding_property_name: model.output_name,
+                                    is_signal: false,
                                     required: false,
                                     transform: None,
                                 });
                             }
+                            Ok(options) => {
+                                let input = try_parse_signal_input_mapping(
+                                    prop_name,
+                                    Some(&options),
+                                    is_required,
+                                );
+                                meta.t2.inputs.insert(InputOrOutput {
+                                    class_property_name: input.class_property_name,
+                                    binding_property_name: input.binding_property_name,
+                                    is_signal: true,
+                                    required: input.required,
+                                    transform: None, // Parsing signal inputs with transform is a separate task
+                                });
+                            }
+                            Err(diagnostic) => {
+                                meta.diagnostics.push(diagnostic);
+                                meta.is_poisoned = true;
+                            }
                         }
                     }
                 }
@@ -349,112 +374,124 @@ pub fn extract_directive_metadata<'a>(
                     });
                 }
 
-                // 4. Check for output() signal-like function
-                if let Some(value) = &prop.value {
-                    if let Expression::CallExpression(call) = value {
-                        let mut is_output_fn = false;
-                        let mut output_alias = prop_name.to_string();
-
-                        if let Expression::Identifier(ident) = &call.callee {
-                            if ident.name == "output" {
-                                is_output_fn = true;
-                            }
+                // 4. Check for initializer outputs: output(), outputFromObservable()
+                if let Some(Expression::CallExpression(call)) = &prop.value {
+                    let config = match &call.callee {
+                        Expression::Identifier(ident) if ident.name == "output" => {
+                            Some(output_initializer_config())
                         }
-
-                        if is_output_fn {
-                            if let Some(arg) = call.arguments.first() {
-                                if let Some(Expression::ObjectExpression(obj)) = arg.as_expression()
-                                {
-                                    for p in &obj.properties {
-                                        if let ObjectPropertyKind::ObjectProperty(op) = p {
-                                            if let PropertyKey::StaticIdentifier(k) = &op.key {
-                                                if k.name == "alias" {
-                                                    if let Expression::StringLiteral(s) = &op.value
-                                                    {
-                                                        output_alias = s.value.to_string();
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-
-                            meta.t2.outputs.insert(InputOrOutput {
-                                class_property_name: prop_name.to_string(),
-                                binding_property_name: output_alias,
-                                is_signal: true,
-                                required: false,
-                                transform: None,
-                            });
+                        Expression::Identifier(ident) if ident.name == "outputFromObservable" => {
+                            Some(output_from_observable_config())
                         }
-                    }
-                }
+                        _ => None,
+                    };
 
-                // 5. Check for signal queries (viewChild, contentChild, etc.)
-                if let Some(value) = &prop.value {
-                    if let Expression::CallExpression(call) = value {
-                        let mut query_name = None;
-                        let mut is_required = false;
+                    if let Some(config) = config {
+                        // output(options) or outputFromObservable(observable, options)
+                        let options_index = if config.function_name == "output" {
+                            0
+                        } else {
+                            1
+                        };
+                        let parsed = validate_initializer_api_member(&config, prop, source_file)
+                            .and_then(|_| {
+                                parse_initializer_options(call, options_index, source_file)
+                            });
 
-                        match &call.callee {
-                            Expression::Identifier(ident) => {
-                                query_name = Some(ident.name.as_str());
+                        match parsed {
+                            Ok(options) => {
+                                let output =
+                                    try_parse_initializer_based_output(prop_name, Some(&options));
+                                meta.t2.outputs.insert(InputOrOutput {
+                                    class_property_name: output.class_property_name,
+                                    binding_property_name: output.binding_property_name,
+                                    is_signal: output.is_signal,
+                                    required: false,
+                                    transform: None,
+                                });
                             }
-                            Expression::StaticMemberExpression(member) => {
-                                if let Expression::Identifier(obj) = &member.object {
-                                    if member.property.name == "required" {
-                                        query_name = Some(obj.name.as_str());
-                                        is_required = true;
-                                    }
-                                }
+                            Err(diagnostic) => {
+                                meta.diagnostics.push(diagnostic);
+                                meta.is_poisoned = true;
                             }
-                            _ => {}
                         }
+                    }
+                }
 
-                        if let Some(name) = query_name {
-                            let (is_query, is_view, first) = match name {
-                                "viewChild" => (true, true, true),
-                                "viewChildren" => (true, true, false), // .required() for viewChildren? maybe
-                                "contentChild" => (true, false, true),
-                                "contentChildren" => (true, false, false),
-                                _ => (false, false, false),
-                            };
+                // 5. Check for signal queries: viewChild(), viewChildren(), contentChild(), contentChildren()
+                if let Some(Expression::CallExpression(call)) = &prop.value {
+                    let function_name =
+                        initializer_function_name(call).and_then(|(name, is_required)| {
+                            InitializerFunctionName::from_str(name)
+                                .filter(|name| {
+                                    query_initializer_apis()
+                                        .iter()
+                                        .any(|api| api.function_name == *name)
+                                })
+                                .map(|name| (name, is_required))
+                        });
 
-                            if is_query {
-                                // Extract selector (first argument, required)
-                                let mut selector: Option<String> = None;
+                    // viewChild(locator, options) or viewChild.required(locator, options)
+                    let predicate = call
+                        .arguments
+                        .first()
+                        .and_then(|arg| arg.as_expression())
+                        .and_then(extract_query_predicate);
 
-                                if let Some(arg) = call.arguments.first() {
-                                    match arg.as_expression() {
-                                        Some(Expression::StringLiteral(s)) => {
-                                            selector = Some(s.value.to_string());
+                    if let (Some((function_name, is_required)), Some((selector, is_type))) =
+                        (function_name, predicate)
+                    {
+                        let mut read = None;
+                        let mut descendants = None;
+                        if let Some(Expression::ObjectExpression(obj)) =
+                            call.arguments.get(1).and_then(|arg| arg.as_expression())
+                        {
+                            for p in &obj.properties {
+                                if let ObjectPropertyKind::ObjectProperty(op) = p {
+                                    match (op.key.static_name().as_deref(), &op.value) {
+                                        (Some("read"), value) => {
+                                            read = extract_query_predicate(value)
+                                                .filter(|(_, is_type)| *is_type)
+                                                .map(|(read, _)| read);
+                                        }
+                                        (Some("descendants"), Expression::BooleanLiteral(b)) => {
+                                            descendants = Some(b.value);
                                         }
-                                        // Handle options object as first arg?
-                                        // viewChild(options) is possible if options has 'read', but selector is usually mandatory/implicit.
-                                        // For now support string selector.
                                         _ => {}
                                     }
                                 }
+                            }
+                        }
 
-                                if let Some(sel) = selector {
-                                    eprintln!("DEBUG: Found signal query: prop={}, selector={}, is_signal=true, is_required={}", prop_name, sel, is_required);
-                                    let query_meta = super::api::QueryMetadata {
-                                        property_name: prop_name.to_string(),
-                                        selector: sel,
-                                        first,
-                                        descendants: true, // Default for signals?
-                                        is_static: false,  // Signals are dynamic
-                                        read: None,
-                                        is_signal: true,
-                                    };
+                        if let Some(mut query) = try_parse_signal_query(
+                            prop_name,
+                            function_name,
+                            &selector,
+                            is_required,
+                            read.as_deref(),
+                        ) {
+                            if let Some(descendants) = descendants {
+                                query = query.with_descendants(descendants);
+                            }
+                            let query_meta = super::api::QueryMetadata {
+                                property_name: query.property_name,
+                                selector: query.selector,
+                                is_type_predicate: is_type,
+                                first: query.first,
+                                descendants: query.descendants,
+                                is_static: false, // Signal queries are never static
+                                read: query.read,
+                                is_signal: true,
+                            };
 
-                                    if is_view {
-                                        meta.view_queries.push(query_meta);
-                                    } else {
-                                        meta.queries.push(query_meta);
-                                    }
-                                }
+                            if matches!(
+                                function_name,
+                                InitializerFunctionName::ViewChild
+                                    | InitializerFunctionName::ViewChildren
+                            ) {
+                                meta.view_queries.push(query_meta);
+                            } else {
+                                meta.queries.push(query_meta);
                             }
                         }
                     }
@@ -528,6 +565,7 @@ pub fn extract_directive_metadata<'a>(
                                     let query_meta = super::api::QueryMetadata {
                                         property_name: prop_name.to_string(),
                                         selector: sel,
+                                        is_type_predicate: false,
                                         first,
                                         descendants,
                                         is_static: false, // TODO: Parse static option
@@ -625,12 +663,29 @@ pub fn extract_directive_metadata<'a>(
     if let Some(arg) = decorator.args.as_ref().and_then(|args| args.first()) {
         if let Expression::ObjectExpression(obj_expr) = arg {
             for prop in &obj_expr.properties {
+                // Metadata shared through a spread constant, e.g. `{...BASE_METADATA, ...}`
+                if let ObjectPropertyKind::SpreadProperty(spread) = prop {
+                    if let ResolvedValue::Object(shared) =
+                        evaluator.evaluate_expression(&spread.argument)
+                    {
+                        if let Some(selector) = shared.get("selector").and_then(|v| v.as_string()) {
+                            meta.t2.selector = Some(selector.to_string());
+                        }
+                        if let Some(export_as) = shared.get("exportAs").and_then(|v| v.as_string())
+                        {
+                            meta.t2.export_as =
+                                Some(export_as.split(',').map(|s| s.trim().to_string()).collect());
+                        }
+                    }
+                }
                 if let ObjectPropertyKind::ObjectProperty(prop) = prop {
                     if let PropertyKey::StaticIdentifier(key) = &prop.key {
                         match key.name.as_str() {
                             "selector" => {
-                                if let Some(val) = extract_string_value(&prop.value) {
-                                    meta.t2.selector = Some(val);
+                                if let Some(val) =
+                                    evaluator.evaluate_expression(&prop.value).as_string()
+                                {
+                                    meta.t2.selector = Some(val.to_string());
                                 }
                             }
                             "inputs" => match &prop.value {
@@ -744,7 +799,9 @@ pub fn extract_directive_metadata<'a>(
                                 _ => {}
                             },
                             "exportAs" => {
-                                if let Some(val) = extract_string_value(&prop.value) {
+                                if let Some(val) =
+                                    evaluator.evaluate_expression(&prop.value).as_string()
+                                {
                                     meta.t2.export_as = Some(
                                         val.split(',').map(|s| s.trim().to_string()).collect(),
                                     );
@@ -761,6 +818,8 @@ pub fn extract_directive_metadata<'a>(
                                 if let Some(val) = extract_string_value(&prop.value) {
                                     if let Some(comp) = meta.component.as_mut() {
                                         comp.template = Some(val);
+                                        comp.template_offset =
+                                            Some(prop.value.span().start as usize + 1);
                                     }
                                 }
                             }
@@ -815,41 +874,16 @@ pub fn extract_directive_metadata<'a>(
                                     let collected: Vec<Reference> = arr
                                         .elements
                                         .iter()
-                                        .filter_map(|e| {
-                                            if let Some(expr) = e.as_expression() {
-                                                if let Expression::Identifier(ident) = expr {
-                                                    let ident_name = ident.name.as_str();
-                                                    let owning_module = imports_map
-                                                        .get(ident_name)
-                                                        .map(|specifier| {
-                                                            OwningModule::new(
-                                                                specifier.clone(),
-                                                                source_file.to_string_lossy(),
-                                                            )
-                                                        });
-
-                                                    if let Some(owning_module) = owning_module {
-                                                        // We can't use with_owning_module directly because it doesn't take span/source_file manually
-                                                        let mut r = Reference::from_name_with_span(
-                                                            ident_name.to_string(),
-                                                            Some(source_file.to_path_buf()),
-                                                            ident.span,
-                                                        );
-                                                        r.best_guess_owning_module =
-                                                            Some(owning_module);
-                                                        return Some(r);
-                                                    } else {
-                                                        return Some(
-                                                            Reference::from_name_with_span(
-                                                                ident_name.to_string(),
-                                                                Some(source_file.to_path_buf()),
-                                                                ident.span,
-                                                            ),
-                                                        );
-                                                    }
-                                                }
+                                        .filter_map(|e| e.as_expression())
+                                        .filter_map(|expr| match unwrap_forward_ref(expr).0 {
+                                            Expression::Identifier(ident) => {
+                                                Some(reference_for_identifier(
+                                                    ident,
+                                                    source_file,
+                                                    imports_map,
+                                                ))
                                             }
-                                            None
+                                            _ => None,
                                         })
                                         .collect();
                                     meta.imports = Some(collected);
@@ -860,6 +894,11 @@ pub fn extract_directive_metadata<'a>(
                                     meta.is_standalone = b.value;
                                 }
                             }
+                            "preserveWhitespaces" => {
+                                if let Expression::BooleanLiteral(b) = &prop.value {
+                                    meta.t2.preserve_whitespaces = b.value;
+                                }
+                            }
                             "changeDetection" => {
                                 if let Some(comp) = meta.component.as_mut() {
                                     if let Expression::StaticMemberExpression(member) = &prop.value
@@ -878,6 +917,31 @@ pub fn extract_directive_metadata<'a>(
                                     }
                                 }
                             }
+                            "encapsulation" => {
+                                if let Some(comp) = meta.component.as_mut() {
+                                    use angular_compiler::core::ViewEncapsulation;
+                                    if let Expression::StaticMemberExpression(member) = &prop.value
+                                    {
+                                        comp.encapsulation = match member.property.name.as_str() {
+                                            "Emulated" => Some(ViewEncapsulation::Emulated),
+                                            "None" => Some(ViewEncapsulation::None),
+                                            "ShadowDom" => Some(ViewEncapsulation::ShadowDom),
+                                            "IsolatedShadowDom" => {
+                                                Some(ViewEncapsulation::IsolatedShadowDom)
+                                            }
+                                            _ => None,
+                                        };
+                                    } else if let Expression::NumericLiteral(num) = &prop.value {
+                                        comp.encapsulation = match num.value as i32 {
+                                            0 => Some(ViewEncapsulation::Emulated),
+                                            2 => Some(ViewEncapsulation::None),
+                                            3 => Some(ViewEncapsulation::ShadowDom),
+                                            4 => Some(ViewEncapsulation::IsolatedShadowDom),
+                                            _ => None,
+                                        };
+                                    }
+                                }
+                            }
                             "queries" => {
                                 if let Expression::ArrayExpression(arr) = &prop.value {
                                     // Legacy queries array parsing - disabled for now as we moved to Vec<QueryMetadata>
@@ -956,17 +1020,21 @@ pub fn extract_directive_metadata<'a>(
                                         if let Some(expr) = elem.as_expression() {
                                             // Can be Identifier (just the directive) or Object ({directive: ..., inputs: ..., outputs: ...})
                                             let mut directive_ref = None;
+                                            let mut is_forward_reference = false;
                                             let mut inputs = None;
                                             let mut outputs = None;
+                                            let mut input_spans = HashMap::new();
+                                            let mut output_spans = HashMap::new();
 
+                                            let (expr, is_forward) = unwrap_forward_ref(expr);
                                             match expr {
                                                 Expression::Identifier(ident) => {
-                                                    directive_ref =
-                                                        Some(Reference::from_name_with_span(
-                                                            ident.name.to_string(),
-                                                            Some(source_file.to_path_buf()),
-                                                            ident.span,
-                                                        ));
+                                                    directive_ref = Some(reference_for_identifier(
+                                                        ident,
+                                                        source_file,
+                                                        imports_map,
+                                                    ));
+                                                    is_forward_reference = is_forward;
                                                 }
                                                 Expression::ObjectExpression(obj) => {
                                                     for p in &obj.properties {
@@ -982,48 +1050,28 @@ pub fn extract_directive_metadata<'a>(
                                                             };
                                                             match key {
                                                                  Some("directive") => {
-                                                                     if let Expression::Identifier(ident) = &prop.value {
-                                                                         directive_ref = Some(Reference::from_name_with_span(
-                                                                             ident.name.to_string(),
-                                                                             Some(source_file.to_path_buf()),
-                                                                             ident.span,
+                                                                     let (value, is_forward) = unwrap_forward_ref(&prop.value);
+                                                                     if let Expression::Identifier(ident) = value {
+                                                                         directive_ref = Some(reference_for_identifier(
+                                                                             ident,
+                                                                             source_file,
+                                                                             imports_map,
                                                                          ));
+                                                                         is_forward_reference = is_forward;
                                                                      }
                                                                  }
                                                                  Some("inputs") => {
                                                                      if let Expression::ArrayExpression(arr) = &prop.value {
-                                                                         let mut map = std::collections::HashMap::new();
-                                                                         for elem in &arr.elements {
-                                                                             if let Some(expr) = elem.as_expression() {
-                                                                                 if let Expression::StringLiteral(s) = expr {
-                                                                                     let val = s.value.as_str();
-                                                                                     if let Some((left, right)) = val.split_once(':') {
-                                                                                         map.insert(left.trim().to_string(), right.trim().to_string());
-                                                                                     } else {
-                                                                                         map.insert(val.to_string(), val.to_string());
-                                                                                     }
-                                                                                 }
-                                                                             }
-                                                                         }
+                                                                         let (map, spans) = parse_host_directive_bindings(arr);
                                                                          inputs = Some(map);
+                                                                         input_spans = spans;
                                                                      }
                                                                  }
                                                                  Some("outputs") => {
                                                                      if let Expression::ArrayExpression(arr) = &prop.value {
-                                                                         let mut map = std::collections::HashMap::new();
-                                                                         for elem in &arr.elements {
-                                                                             if let Some(expr) = elem.as_expression() {
-                                                                                 if let Expression::StringLiteral(s) = expr {
-                                                                                     let val = s.value.as_str();
-                                                                                     if let Some((left, right)) = val.split_once(':') {
-                                                                                         map.insert(left.trim().to_string(), right.trim().to_string());
-                                                                                     } else {
-                                                                                         map.insert(val.to_string(), val.to_string());
-                                                                                     }
-                                                                                 }
-                                                                             }
-                                                                         }
+                                                                         let (map, spans) = parse_host_directive_bindings(arr);
                                                                          outputs = Some(map);
+                                                                         output_spans = spans;
                                                                      }
                                                                  }
                                                                  _ => {}
@@ -1036,9 +1084,11 @@ pub fn extract_directive_metadata<'a>(
 
                                             directives.push(super::api::HostDirectiveMeta {
                                                 directive: directive_ref,
-                                                is_forward_reference: false,
+                                                is_forward_reference,
                                                 inputs,
                                                 outputs,
+                                                input_spans,
+                                                output_spans,
                                             });
                                         }
                                     }
@@ -1126,35 +1176,133 @@ pub fn extract_injectable_metadata<'a>(
         .map(|id| id.name.to_string())
         .unwrap_or_default();
 
-    let mut provided_in: Option<String> = None;
-
-    // Extract @Injectable({ providedIn: '...' })
-    if let Some(args) = &decorator.args {
-        if let Some(first_arg) = args.first() {
-            if let Expression::ObjectExpression(obj) = first_arg {
-                for prop in &obj.properties {
-                    if let ObjectPropertyKind::ObjectProperty(obj_prop) = prop {
-                        let key = match &obj_prop.key {
-                            PropertyKey::StaticIdentifier(id) => Some(id.name.as_str()),
-                            _ => None,
-                        };
+    let mut meta = InjectableMeta {
+        name,
+        provided_in: None,
+        use_class: None,
+        use_factory: None,
+        use_existing: None,
+        use_value: None,
+        deps: None,
+        source_file: Some(source_file.to_path_buf()),
+    };
 
-                        if key == Some("providedIn") {
-                            if let Expression::StringLiteral(s) = &obj_prop.value {
-                                provided_in = Some(s.value.to_string());
-                            }
-                        }
+    // Extract @Injectable({ providedIn: ..., useClass: ..., deps: [...] })
+    if let Some(Expression::ObjectExpression(obj)) = decorator.args.as_ref().and_then(|a| a.first())
+    {
+        for prop in &obj.properties {
+            let ObjectPropertyKind::ObjectProperty(obj_prop) = prop else {
+                continue;
+            };
+            let key = match &obj_prop.key {
+                PropertyKey::StaticIdentifier(id) => id.name.as_str(),
+                _ => continue,
+            };
+            let value = &obj_prop.value;
+
+            match key {
+                "providedIn" => {
+                    meta.provided_in = match value {
+                        Expression::StringLiteral(s) => Some(format!("'{}'", s.value)),
+                        Expression::NullLiteral(_) => None,
+                        _ => Some(print_without_types(value)),
+                    };
+                }
+                "useClass" => meta.use_class = Some(print_without_types(value)),
+                "useFactory" => {
+                    // The factory is emitted as a callee, where functions have to be grouped
+                    let factory = print_without_types(value);
+                    meta.use_factory = Some(match value.without_parentheses() {
+                        Expression::Identifier(_) | Expression::CallExpression(_) => factory,
+                        callee if callee.is_member_expression() => factory,
+                        _ => format!("({})", factory),
+                    });
+                }
+                "useExisting" => meta.use_existing = Some(print_without_types(value)),
+                "useValue" => meta.use_value = Some(print_without_types(value)),
+                "deps" => {
+                    if let Expression::ArrayExpression(arr) = value {
+                        meta.deps = Some(
+                            arr.elements
+                                .iter()
+                                .filter_map(|el| el.as_expression())
+                                .map(print_without_types)
+                                .collect(),
+                        );
                     }
                 }
+                _ => {}
             }
         }
     }
 
-    Some(DecoratorMetadata::Injectable(InjectableMeta {
+    Some(DecoratorMetadata::Injectable(meta))
+}
+
+/// Extract NgModule metadata from a class declaration and its @NgModule decorator.
+pub fn extract_ng_module_metadata<'a>(
+    class_decl: &'a ClassDeclaration<'a>,
+    decorator: &Decorator<'a>,
+    source_file: &std::path::Path,
+) -> Option<DecoratorMetadata<'a>> {
+    let name = class_decl
+        .id
+        .as_ref()
+        .map(|id| id.name.to_string())
+        .unwrap_or_default();
+
+    let mut meta = NgModuleMeta {
         name,
-        provided_in,
         source_file: Some(source_file.to_path_buf()),
-    }))
+        ..Default::default()
+    };
+
+    // Extract @NgModule({ declarations: [...], imports: [...], providers: ..., ... })
+    if let Some(Expression::ObjectExpression(obj)) = decorator.args.as_ref().and_then(|a| a.first())
+    {
+        for prop in &obj.properties {
+            let ObjectPropertyKind::ObjectProperty(obj_prop) = prop else {
+                continue;
+            };
+            let key = match &obj_prop.key {
+                PropertyKey::StaticIdentifier(id) => id.name.as_str(),
+                _ => continue,
+            };
+            let value = &obj_prop.value;
+            // The elements of an array, e.g. `FeatureModule.forRoot(config)` for an import
+            let elements = || match value {
+                Expression::ArrayExpression(arr) => arr
+                    .elements
+                    .iter()
+                    .filter_map(|el| el.as_expression())
+                    .map(print_without_types)
+                    .collect(),
+                _ => Vec::new(),
+            };
+
+            match key {
+                "declarations" => {
+                    meta.declarations = elements();
+                    meta.raw_declarations = Some(print_without_types(value));
+                }
+                "imports" => {
+                    meta.imports = elements();
+                    meta.raw_imports = Some(print_without_types(value));
+                }
+                "exports" => {
+                    meta.exports = elements();
+                    meta.raw_exports = Some(print_without_types(value));
+                }
+                "schemas" => meta.schemas = elements(),
+                "bootstrap" => meta.bootstrap = elements(),
+                "providers" => meta.providers = Some(print_without_types(value)),
+                _ => {}
+            }
+        }
+    }
+    meta.may_declare_providers = meta.providers.is_some() || !meta.imports.is_empty();
+
+    Some(DecoratorMetadata::NgModule(meta))
 }
 
 /// Get all Angular decorator metadata from a program.
@@ -1166,6 +1314,10 @@ pub fn get_all_metadata<'a>(
     let mut directives = Vec::new();
     let host = TypeScriptReflectionHost::new();
 
+    // Constants of the file, used to resolve metadata derived from them
+    let mut evaluator = PartialEvaluator::new();
+    evaluator.register_constants(program);
+
     // 1. Build imports map
     let mut imports_map = HashMap::new();
     for stmt in &program.body {
@@ -1211,12 +1363,13 @@ pub fn get_all_metadata<'a>(
 
                 for decorator in decorators {
                     if decorator.name == "Component" || decorator.name == "Directive" {
-                        if let Some(metadata) = extract_directive_metadata(
+                        if let Some(metadata) = extract_directive_metadata_with_evaluator(
                             class_decl,
                             &decorator,
                             decorator.name == "Component",
                             path,
                             &imports_map,
+                            &evaluator,
                         ) {
                             directives.push(metadata);
                         }
@@ -1231,6 +1384,12 @@ pub fn get_all_metadata<'a>(
                         {
                             directives.push(metadata);
                         }
+                    } else if decorator.name == "NgModule" {
+                        if let Some(metadata) =
+                            extract_ng_module_metadata(class_decl, &decorator, path)
+                        {
+                            directives.push(metadata);
+                        }
                     }
                 }
             }
@@ -1240,6 +1399,396 @@ pub fn get_all_metadata<'a>(
     directives
 }
 
+/// Parse the exposed bindings of a host directive, e.g. `['value', 'checked: isChecked']`, into
+/// a map from the host directive's public name to its alias, along with their source spans.
+fn parse_host_directive_bindings(
+    arr: &oxc_ast::ast::ArrayExpression<'_>,
+) -> (HashMap<String, String>, HashMap<String, oxc_span::Span>) {
+    let mut bindings = HashMap::new();
+    let mut spans = HashMap::new();
+    for elem in &arr.elements {
+        if let Some(Expression::StringLiteral(s)) = elem.as_expression() {
+            let (name, alias) = match s.value.split_once(':') {
+                Some((name, alias)) => (name.trim(), alias.trim()),
+                None => (s.value.as_str(), s.value.as_str()),
+            };
+            bindings.insert(name.to_string(), alias.to_string());
+            spans.insert(name.to_string(), s.span);
+        }
+    }
+    (bindings, spans)
+}
+
+/// Check that the inputs and outputs exposed through `hostDirectives` exist on the host
+/// directives. Host directives that aren't part of `directives` can't be checked and are skipped.
+pub fn validate_host_directive_bindings(directives: &[DecoratorMetadata<'_>]) -> Vec<Diagnostic> {
+    let known: HashMap<&str, &DirectiveMeta<'_>> = directives
+        .iter()
+        .filter_map(|directive| match directive {
+            DecoratorMetadata::Directive(dir) => Some((dir.t2.name.as_str(), dir)),
+            _ => None,
+        })
+        .collect();
+
+    let mut diagnostics = Vec::new();
+    for directive in directives {
+        let DecoratorMetadata::Directive(origin) = directive else {
+            continue;
+        };
+        for host_directive in origin.host_directives.iter().flatten() {
+            let Some(host) = host_directive
+                .directive
+                .as_ref()
+                .and_then(|r| known.get(r.debug_name()))
+            else {
+                continue;
+            };
+
+            for (kind, bindings, spans, available) in [
+                (
+                    "input",
+                    &host_directive.inputs,
+                    &host_directive.input_spans,
+                    &host.t2.inputs,
+                ),
+                (
+                    "output",
+                    &host_directive.outputs,
+                    &host_directive.output_spans,
+                    &host.t2.outputs,
+                ),
+            ] {
+                let mut names: Vec<&String> = bindings.iter().flatten().map(|(k, _)| k).collect();
+                names.sort_by_key(|name| spans.get(*name).map(|span| span.start));
+                for name in names {
+                    if available.has_binding_property_name(name) {
+                        continue;
+                    }
+                    let span = spans.get(name);
+                    diagnostics.push(Diagnostic {
+                        category: DiagnosticCategory::Error,
+                        file: origin.source_file.clone(),
+                        message: format!(
+                            "Directive {} does not have an {} with a public name of {}.",
+                            host.t2.name, kind, name
+                        ),
+                        code: ErrorCode::HostDirectiveUndefinedBinding as usize,
+                        start: span.map(|span| span.start as usize),
+                        length: span.map(|span| span.size() as usize),
+                        related_information: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Unwrap `forwardRef(() => X)` to `X`, the AST counterpart of
+/// [`expand_forward_ref`](crate::ngtsc::annotations::common::expand_forward_ref). Also returns
+/// whether the expression was wrapped.
+fn unwrap_forward_ref<'b, 'a>(expr: &'b Expression<'a>) -> (&'b Expression<'a>, bool) {
+    if let Expression::CallExpression(call) = expr.without_parentheses() {
+        let is_forward_ref =
+            matches!(&call.callee, Expression::Identifier(ident) if ident.name == "forwardRef");
+        if let (true, Some(Expression::ArrowFunctionExpression(arrow))) = (
+            is_forward_ref,
+            call.arguments.first().and_then(|arg| arg.as_expression()),
+        ) {
+            let inner = match arrow.body.statements.first() {
+                Some(oxc_ast::ast::Statement::ExpressionStatement(stmt)) if arrow.expression => {
+                    Some(&stmt.expression)
+                }
+                Some(oxc_ast::ast::Statement::ReturnStatement(ret)) => ret.argument.as_ref(),
+                _ => None,
+            };
+            if let Some(inner) = inner {
+                return (inner.without_parentheses(), true);
+            }
+        }
+    }
+    (expr, false)
+}
+
+/// Create a reference to an identifier, guessing its owning module from the file's imports.
+fn reference_for_identifier(
+    ident: &oxc_ast::ast::IdentifierReference<'_>,
+    source_file: &std::path::Path,
+    imports_map: &HashMap<String, String>,
+) -> Reference<'static> {
+    let mut reference = Reference::from_name_with_span(
+        ident.name.to_string(),
+        Some(source_file.to_path_buf()),
+        ident.span,
+    );
+    reference.best_guess_owning_module = imports_map
+        .get(ident.name.as_str())
+        .map(|specifier| OwningModule::new(specifier.clone(), source_file.to_string_lossy()));
+    reference
+}
+
+/// Get the name of an initializer API call like `input()` or `input.required()`, together with
+/// whether the `.required` variant is used.
+fn initializer_function_name<'b>(
+    call: &'b oxc_ast::ast::CallExpression<'_>,
+) -> Option<(&'b str, bool)> {
+    match &call.callee {
+        Expression::Identifier(ident) => Some((ident.name.as_str(), false)),
+        Expression::StaticMemberExpression(member) if member.property.name == "required" => {
+            match &member.object {
+                Expression::Identifier(obj) => Some((obj.name.as_str(), true)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Extract the locator of a query. Returns the selector and whether it refers to a type.
+fn extract_query_predicate(expr: &Expression<'_>) -> Option<(String, bool)> {
+    match expr.without_parentheses() {
+        Expression::StringLiteral(s) => Some((s.value.to_string(), false)),
+        expr @ (Expression::Identifier(_) | Expression::StaticMemberExpression(_)) => {
+            Some((print_without_types(expr), true))
+        }
+        _ => None,
+    }
+}
+
+/// Create a diagnostic pointing at `span` in the source file being analyzed.
+fn metadata_diagnostic(
+    source_file: &std::path::Path,
+    span: oxc_span::Span,
+    code: ErrorCode,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        category: DiagnosticCategory::Error,
+        file: Some(source_file.to_path_buf()),
+        message,
+        code: code as usize,
+        start: Some(span.start as usize),
+        length: Some(span.size() as usize),
+        related_information: Vec::new(),
+    }
+}
+
+/// Check that an initializer API like `input()` is used on a member with an allowed visibility.
+fn validate_initializer_api_member(
+    config: &InitializerApiConfig,
+    prop: &oxc_ast::ast::PropertyDefinition<'_>,
+    source_file: &std::path::Path,
+) -> Result<(), Diagnostic> {
+    let access = match prop.accessibility {
+        Some(oxc_ast::ast::TSAccessibility::Private) => AccessLevel::Private,
+        Some(oxc_ast::ast::TSAccessibility::Protected) => AccessLevel::Protected,
+        _ => AccessLevel::Public,
+    };
+    validate_access_of_initializer_api_member(config, access).map_err(|error| {
+        metadata_diagnostic(
+            source_file,
+            prop.span,
+            ErrorCode::InitializerApiDisallowedMemberVisibility,
+            error.message(),
+        )
+    })
+}
+
+/// Parse the options object passed to an initializer API like `input()` or `model()`.
+///
+/// The options are optional, but when given they must be an object literal.
+fn parse_initializer_options(
+    call: &oxc_ast::ast::CallExpression<'_>,
+    index: usize,
+    source_file: &std::path::Path,
+) -> Result<InputOutputOptions, Diagnostic> {
+    let options = match call
+        .arguments
+        .get(index)
+        .and_then(|arg| arg.as_expression())
+    {
+        Some(options) => options,
+        None => return Ok(InputOutputOptions::default()),
+    };
+    let obj = match options.without_parentheses() {
+        Expression::ObjectExpression(obj) => obj,
+        _ => {
+            return Err(metadata_diagnostic(
+                source_file,
+                options.span(),
+                ErrorCode::ValueHasWrongType,
+                OptionsParseError::wrong_type("an object literal").message,
+            ))
+        }
+    };
+
+    let mut pairs = Vec::new();
+    for p in &obj.properties {
+        if let ObjectPropertyKind::ObjectProperty(op) = p {
+            if let (Some(key), Some(value)) =
+                (op.key.static_name(), extract_string_value(&op.value))
+            {
+                pairs.push((key.to_string(), value));
+            }
+        }
+    }
+    parse_options_from_pairs(&pairs).map_err(|error| {
+        metadata_diagnostic(
+            source_file,
+            options.span(),
+            ErrorCode::ValueHasWrongType,
+            error.message,
+        )
+    })
+}
+
+/// Parse the `transform` option of an `@Input()` decorator.
+///
+/// Mirrors the checks of Angular's `parseDecoratorInputTransformFunction`. The input type of
+/// an inline function is read from its first parameter; functions referenced by name (such as
+/// `booleanAttribute`) can't be inspected without type information, so their input type is
+/// `unknown`.
+fn extract_decorator_input_transform(
+    class_decl: &ClassDeclaration<'_>,
+    class_property_name: &str,
+    value: &Expression<'_>,
+    source_file: &std::path::Path,
+) -> Result<DecoratorInputTransform, Diagnostic> {
+    let error = |code: ErrorCode, message: String| {
+        metadata_diagnostic(source_file, value.span(), code, message)
+    };
+
+    let conflicting_name = format!("ngAcceptInputType_{}", class_property_name);
+    let has_conflicting_member = class_decl.body.body.iter().any(|element| {
+        element.r#static()
+            && element
+                .property_key()
+                .and_then(|key| key.static_name())
+                .is_some_and(|name| name == conflicting_name)
+    });
+    if has_conflicting_member {
+        return Err(error(
+            ErrorCode::ConflictingInputTransform,
+            format!(
+                "Class cannot have both a transform function on Input {} and a static member called {}",
+                class_property_name, conflicting_name
+            ),
+        ));
+    }
+
+    let params = match value.without_parentheses() {
+        Expression::Identifier(_) | Expression::StaticMemberExpression(_) => None,
+        Expression::ArrowFunctionExpression(arrow) => Some((&arrow.type_parameters, &arrow.params)),
+        Expression::FunctionExpression(func) => Some((&func.type_parameters, &func.params)),
+        _ => {
+            return Err(error(
+                ErrorCode::ValueHasWrongType,
+                "Input transform must be a function".to_string(),
+            ))
+        }
+    };
+
+    let mut type_ref = "unknown".to_string();
+    if let Some((type_parameters, params)) = params {
+        if type_parameters.is_some() {
+            return Err(error(
+                ErrorCode::ValueHasWrongType,
+                "Input transform function cannot be generic".to_string(),
+            ));
+        }
+        // Functions without parameters accept `unknown`, as returning a value is still valid.
+        if let Some(first) = params.items.first() {
+            let annotation = first.pattern.type_annotation.as_ref().ok_or_else(|| {
+                error(
+                    ErrorCode::ValueHasWrongType,
+                    "Input transform function first parameter must have a type".to_string(),
+                )
+            })?;
+            let mut codegen = oxc_codegen::Codegen::new();
+            annotation
+                .type_annotation
+                .print(&mut codegen, oxc_codegen::Context::default());
+            type_ref = codegen.into_source_text();
+        } else if params.rest.is_some() {
+            return Err(error(
+                ErrorCode::ValueHasWrongType,
+                "Input transform function first parameter cannot be a spread parameter".to_string(),
+            ));
+        }
+    }
+
+    Ok(DecoratorInputTransform {
+        node: print_without_types(value),
+        type_ref,
+    })
+}
+
+/// Collect the declared types of a class's instance properties, as written in their type
+/// annotations or, without one, as widened from a literal initializer.
+fn extract_member_types(class_decl: &ClassDeclaration<'_>) -> HashMap<String, String> {
+    let mut member_types = HashMap::new();
+    for element in &class_decl.body.body {
+        let oxc_ast::ast::ClassElement::PropertyDefinition(prop) = element else {
+            continue;
+        };
+        let PropertyKey::StaticIdentifier(key) = &prop.key else {
+            continue;
+        };
+        if prop.r#static {
+            continue;
+        }
+        let declared = if let Some(annotation) = &prop.type_annotation {
+            let mut codegen = oxc_codegen::Codegen::new();
+            annotation
+                .type_annotation
+                .print(&mut codegen, oxc_codegen::Context::default());
+            codegen.into_source_text()
+        } else {
+            match prop.value.as_ref().map(|value| value.without_parentheses()) {
+                Some(Expression::StringLiteral(_)) | Some(Expression::TemplateLiteral(_)) => {
+                    "string".to_string()
+                }
+                Some(Expression::NumericLiteral(_)) => "number".to_string(),
+                Some(Expression::BooleanLiteral(_)) => "boolean".to_string(),
+                _ => continue,
+            }
+        };
+        let declared = if prop.optional {
+            format!("{} | undefined", declared)
+        } else {
+            declared
+        };
+        member_types.insert(key.name.to_string(), declared);
+    }
+    member_types
+}
+
+/// Print an expression as JavaScript, stripping the type annotations of inline functions.
+fn print_without_types(expression: &Expression<'_>) -> String {
+    let mut codegen = oxc_codegen::Codegen::new();
+    codegen.print_expression(expression);
+    let source = format!("({});", codegen.into_source_text());
+
+    let allocator = oxc_allocator::Allocator::default();
+    let mut parsed =
+        oxc_parser::Parser::new(&allocator, &source, oxc_span::SourceType::ts()).parse();
+    let semantic = oxc_semantic::SemanticBuilder::new().build(&parsed.program);
+    let _ = oxc_transformer::Transformer::new(
+        &allocator,
+        std::path::Path::new("transform.ts"),
+        &oxc_transformer::TransformOptions::default(),
+    )
+    .build_with_scoping(semantic.semantic.into_scoping(), &mut parsed.program);
+
+    let mut codegen = oxc_codegen::Codegen::new();
+    if let Some(oxc_ast::ast::Statement::ExpressionStatement(statement)) =
+        parsed.program.body.first()
+    {
+        codegen.print_expression(statement.expression.without_parentheses());
+    }
+    codegen.into_source_text()
+}
+
 /// Helper to extract string value from Expression (StringLiteral or TemplateLiteral)
 fn extract_string_value(expr: &oxc_ast::ast::Expression) -> Option<String> {
     use oxc_ast::ast::Expression;
@@ -1323,6 +1872,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_extract_member_types_for_extended_checks() {
+        let source = r#"
+            import {Component} from '@angular/core';
+
+            @Component({
+                selector: 'test-comp',
+                template: '{{ title ?? "x" }} {{ subtitle ?? "y" }}'
+            })
+            export class TestComponent {
+                title: string = 'Hello';
+                subtitle: string | null = null;
+                count = 0;
+                label?: string;
+                static instances = 0;
+            }
+        "#;
+
+        let allocator = Allocator::default();
+        let program = TestProgram::new(&allocator, source);
+        let class_decl = program
+            .find_class("TestComponent")
+            .expect("Class not found");
+        let host = TypeScriptReflectionHost::new();
+        let decl = program
+            .find_declaration("TestComponent")
+            .expect("Declaration not found");
+        let decorators = host.get_decorators_of_declaration(decl);
+        let decorator = decorators
+            .iter()
+            .find(|d| d.name == "Component")
+            .expect("Component decorator not found");
+        let path = std::path::Path::new("test.ts");
+        let metadata =
+            extract_directive_metadata(class_decl, decorator, true, path, &HashMap::new())
+                .expect("Metadata extraction failed");
+        let DecoratorMetadata::Directive(dir) = metadata else {
+            panic!("Expected directive metadata");
+        };
+
+        let member_types = &dir.component.as_ref().unwrap().member_types;
+        assert_eq!(member_types["title"], "string");
+        assert_eq!(member_types["subtitle"], "string | null");
+        assert_eq!(member_types["count"], "number");
+        assert_eq!(member_types["label"], "string | undefined");
+        assert!(!member_types.contains_key("instances"));
+
+        let mut checker = crate::ngtsc::typecheck::TemplateTypeCheckerImpl::new(
+            crate::ngtsc::typecheck::TypeCheckingConfig::default(),
+        );
+        let result = checker.type_check_directive(&dir, &[]);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].code, "NG8102");
+        assert_eq!(
+            result.diagnostics[0].category,
+            ts::DiagnosticCategory::Warning
+        );
+        assert!(result.success);
+    }
+
     #[test]
     fn test_extract_model_inputs() {
         let source = r#"
@@ -1597,4 +2206,509 @@ mod tests {
             panic!("Expected Directive metadata");
         }
     }
+
+    #[test]
+    fn test_extract_encapsulation() {
+        let source = r#"
+            import {Component, ViewEncapsulation} from '@angular/core';
+
+            @Component({
+                selector: 'test-comp',
+                template: '',
+                encapsulation: ViewEncapsulation.ShadowDom
+            })
+            export class TestComponent {}
+        "#;
+
+        let allocator = Allocator::default();
+        let program = TestProgram::new(&allocator, source);
+        let class_decl = program
+            .find_class("TestComponent")
+            .expect("Class not found");
+
+        let host = TypeScriptReflectionHost::new();
+        let decl = program
+            .find_declaration("TestComponent")
+            .expect("Declaration not found");
+        let decorators = host.get_decorators_of_declaration(decl);
+        let decorator = decorators
+            .iter()
+            .find(|d| d.name == "Component")
+            .expect("Component decorator not found");
+
+        let path = std::path::Path::new("test.ts");
+        let imports = HashMap::new();
+
+        let metadata = extract_directive_metadata(class_decl, decorator, true, path, &imports)
+            .expect("Metadata extraction failed");
+
+        if let DecoratorMetadata::Directive(dir) = metadata {
+            let comp = dir.component.expect("component metadata not found");
+            assert_eq!(
+                comp.encapsulation,
+                Some(angular_compiler::core::ViewEncapsulation::ShadowDom)
+            );
+        } else {
+            panic!("Expected Directive metadata");
+        }
+    }
+
+    fn with_directive_metadata(source: &str, check: impl FnOnce(&DirectiveMeta)) {
+        let allocator = Allocator::default();
+        let program = TestProgram::new(&allocator, source);
+        let class_decl = program.find_class("TestDir").expect("Class not found");
+
+        let host = TypeScriptReflectionHost::new();
+        let decl = program
+            .find_declaration("TestDir")
+            .expect("Declaration not found");
+        let decorators = host.get_decorators_of_declaration(decl);
+        let decorator = decorators
+            .iter()
+            .find(|d| d.name == "Directive")
+            .expect("Directive decorator not found");
+
+        let path = std::path::Path::new("test.ts");
+        let imports = HashMap::new();
+
+        let metadata = extract_directive_metadata(class_decl, decorator, false, path, &imports)
+            .expect("Metadata extraction failed");
+
+        if let DecoratorMetadata::Directive(dir) = &metadata {
+            check(dir);
+        } else {
+            panic!("Expected Directive metadata");
+        }
+    }
+
+    #[test]
+    fn test_extract_decorator_input_transforms() {
+        let source = r#"
+            import {Directive, Input, numberAttribute} from '@angular/core';
+
+            @Directive({selector: '[test-dir]'})
+            export class TestDir {
+                @Input({transform: numberAttribute, required: true}) size: number;
+                @Input({alias: 'label', transform: (value: string | undefined) => value ?? ''}) text: string;
+                @Input({transform: () => true}) flag: boolean;
+            }
+        "#;
+
+        with_directive_metadata(source, |dir| {
+            assert!(dir.diagnostics.is_empty());
+
+            let size = crate::ngtsc::annotations::common::InputMapping::from(
+                dir.t2.inputs.get("size").expect("size input not found"),
+            );
+            assert!(size.required);
+            let transform = size.transform.expect("transform not found");
+            assert_eq!(transform.node, "numberAttribute");
+            assert_eq!(transform.type_expr, "unknown");
+
+            let text = dir.t2.inputs.get("text").expect("text input not found");
+            assert_eq!(text.binding_property_name, "label");
+            let transform = text.transform.as_ref().expect("transform not found");
+            assert_eq!(transform.type_ref, "string | undefined");
+
+            let flag = dir.t2.inputs.get("flag").expect("flag input not found");
+            assert_eq!(flag.transform.as_ref().unwrap().type_ref, "unknown");
+
+            let handler =
+                crate::ngtsc::annotations::directive::src::handler::DirectiveDecoratorHandler::new(
+                    false,
+                );
+            let results = handler.compile_ivy(&DecoratorMetadata::Directive(dir.clone()));
+            let dir_def = results
+                .iter()
+                .find(|r| r.name == "ɵdir")
+                .expect("ɵdir not found");
+            let initializer = dir_def.initializer.as_ref().unwrap();
+            assert!(initializer.contains(r#"size: [2, "size", "size", numberAttribute]"#));
+            assert!(initializer.contains(r#"text: [2, "label", "text", ((value) => value ?? "")]"#));
+        });
+    }
+
+    #[test]
+    fn test_reject_invalid_decorator_input_transforms() {
+        let source = r#"
+            import {Directive, Input} from '@angular/core';
+
+            @Directive({selector: '[test-dir]'})
+            export class TestDir {
+                static ngAcceptInputType_a: string;
+                @Input({transform: (value: string) => value}) a: string;
+                @Input({transform: 'trim'}) b: string;
+                @Input({transform: <T>(value: T) => value}) c: string;
+                @Input({transform: (value) => value}) d: string;
+            }
+        "#;
+
+        with_directive_metadata(source, |dir| {
+            assert!(dir.is_poisoned);
+            let messages: Vec<&str> = dir.diagnostics.iter().map(|d| d.message.as_str()).collect();
+            assert_eq!(
+                messages,
+                vec![
+                    "Class cannot have both a transform function on Input a and a static member called ngAcceptInputType_a",
+                    "Input transform must be a function",
+                    "Input transform function cannot be generic",
+                    "Input transform function first parameter must have a type",
+                ]
+            );
+            assert_eq!(
+                dir.diagnostics[1].code,
+                ErrorCode::ValueHasWrongType as usize
+            );
+            assert!(dir.t2.inputs.get("b").unwrap().transform.is_none());
+        });
+    }
+
+    #[test]
+    fn test_extract_model_inputs_and_outputs() {
+        let source = r#"
+            import {Directive, model} from '@angular/core';
+
+            @Directive({selector: '[test-dir]'})
+            export class TestDir {
+                value = model(0);
+                checked = model.required<boolean>({alias: 'isChecked'});
+            }
+        "#;
+
+        with_directive_metadata(source, |dir| {
+            assert!(dir.diagnostics.is_empty());
+
+            let checked = dir
+                .t2
+                .inputs
+                .get("checked")
+                .expect("checked input not found");
+            assert!(checked.is_signal);
+            assert!(checked.required);
+            assert_eq!(checked.binding_property_name, "isChecked");
+            let checked_change = dir
+                .t2
+                .outputs
+                .get("checked")
+                .expect("checked output not found");
+            assert_eq!(checked_change.binding_property_name, "isCheckedChange");
+
+            let handler =
+                crate::ngtsc::annotations::directive::src::handler::DirectiveDecoratorHandler::new(
+                    false,
+                );
+            let results = handler.compile_ivy(&DecoratorMetadata::Directive(dir.clone()));
+            let dir_def = results
+                .iter()
+                .find(|r| r.name == "ɵdir")
+                .expect("ɵdir not found");
+            let initializer = dir_def.initializer.as_ref().unwrap();
+            assert!(initializer.contains(r#"value: [1, "value"]"#));
+            assert!(initializer.contains(r#"value: "valueChange""#));
+            assert!(initializer.contains(r#"checked: [1, "isChecked", "checked"]"#));
+            assert!(initializer.contains(r#"checked: "isCheckedChange""#));
+        });
+
+        // A host template binding `[(value)]` listens to the model's change event.
+        let handler =
+            crate::ngtsc::annotations::component::src::handler::ComponentDecoratorHandler::new();
+        let host = DecoratorMetadata::Directive(DirectiveMeta {
+            t2: T2DirectiveMetadata {
+                name: "HostCmp".into(),
+                selector: Some("host-cmp".into()),
+                is_component: true,
+                ..Default::default()
+            },
+            component: Some(ComponentMetadata {
+                template: Some(r#"<div test-dir [(value)]="count"></div>"#.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let results = handler.compile_ivy(&host);
+        let cmp_def = results
+            .iter()
+            .find(|r| r.name == "ɵcmp")
+            .expect("ɵcmp not found");
+        let initializer = cmp_def.initializer.as_ref().unwrap();
+        assert!(initializer.contains(r#"ɵɵtwoWayProperty("value", ctx.count)"#));
+        assert!(initializer.contains(r#"ɵɵtwoWayListener("valueChange""#));
+    }
+
+    #[test]
+    fn test_reject_invalid_model_declarations() {
+        let source = r#"
+            import {Directive, model} from '@angular/core';
+
+            @Directive({selector: '[test-dir]'})
+            export class TestDir {
+                private hidden = model(0);
+                options = {alias: 'x'};
+                dynamic = model.required(this.options);
+            }
+        "#;
+
+        with_directive_metadata(source, |dir| {
+            assert!(dir.is_poisoned);
+            let codes: Vec<usize> = dir.diagnostics.iter().map(|d| d.code).collect();
+            assert_eq!(
+                codes,
+                vec![
+                    ErrorCode::InitializerApiDisallowedMemberVisibility as usize,
+                    ErrorCode::ValueHasWrongType as usize,
+                ]
+            );
+            assert!(dir.t2.inputs.get("hidden").is_none());
+            assert!(dir.t2.outputs.get("dynamic").is_none());
+        });
+    }
+
+    #[test]
+    fn test_extract_initializer_outputs() {
+        let source = r#"
+            import {Directive, output} from '@angular/core';
+            import {outputFromObservable} from '@angular/core/rxjs-interop';
+            import {Subject} from 'rxjs';
+
+            @Directive({selector: '[test-dir]'})
+            export class TestDir {
+                clicked = output<void>();
+                protected renamed = output<string>({alias: 'nameChange'});
+                values$ = new Subject<number>();
+                values = outputFromObservable(this.values$, {alias: 'valueStream'});
+                ticks = outputFromObservable(this.values$);
+            }
+        "#;
+
+        with_directive_metadata(source, |dir| {
+            assert!(dir.diagnostics.is_empty());
+            assert_eq!(
+                dir.t2.outputs.get("renamed").unwrap().binding_property_name,
+                "nameChange"
+            );
+            assert_eq!(
+                dir.t2.outputs.get("values").unwrap().binding_property_name,
+                "valueStream"
+            );
+
+            let handler =
+                crate::ngtsc::annotations::directive::src::handler::DirectiveDecoratorHandler::new(
+                    false,
+                );
+            let results = handler.compile_ivy(&DecoratorMetadata::Directive(dir.clone()));
+            let dir_def = results
+                .iter()
+                .find(|r| r.name == "ɵdir")
+                .expect("ɵdir not found");
+            let initializer = dir_def.initializer.as_ref().unwrap();
+            assert!(initializer.contains(r#"clicked: "clicked""#));
+            assert!(initializer.contains(r#"renamed: "nameChange""#));
+            assert!(initializer.contains(r#"values: "valueStream""#));
+            assert!(initializer.contains(r#"ticks: "ticks""#));
+        });
+    }
+
+    #[test]
+    fn test_reject_invalid_initializer_outputs() {
+        let source = r#"
+            import {Directive, output} from '@angular/core';
+
+            @Directive({selector: '[test-dir]'})
+            export class TestDir {
+                private hidden = output();
+                opts = {alias: 'x'};
+                dynamic = output(this.opts);
+            }
+        "#;
+
+        with_directive_metadata(source, |dir| {
+            assert!(dir.is_poisoned);
+            let codes: Vec<usize> = dir.diagnostics.iter().map(|d| d.code).collect();
+            assert_eq!(
+                codes,
+                vec![
+                    ErrorCode::InitializerApiDisallowedMemberVisibility as usize,
+                    ErrorCode::ValueHasWrongType as usize,
+                ]
+            );
+            assert!(dir.t2.outputs.get("hidden").is_none());
+        });
+    }
+
+    #[test]
+    fn test_compile_signal_queries() {
+        let source = r#"
+            import {Directive, ElementRef, viewChild, viewChildren, contentChild, contentChildren} from '@angular/core';
+            import {Cmp, Dir} from './other';
+
+            @Directive({selector: '[test-dir]'})
+            export class TestDir {
+                ref = viewChild('ref');
+                cmp = viewChild.required(Cmp);
+                elements = viewChildren(Cmp, {read: ElementRef});
+                label = contentChild('label');
+                dirs = contentChildren(Dir, {descendants: true});
+                items = contentChildren('item');
+            }
+        "#;
+
+        with_directive_metadata(source, |dir| {
+            let cmp = dir
+                .view_queries
+                .iter()
+                .find(|q| q.property_name == "cmp")
+                .unwrap();
+            assert!(cmp.is_type_predicate);
+            assert!(cmp.first);
+
+            let handler =
+                crate::ngtsc::annotations::directive::src::handler::DirectiveDecoratorHandler::new(
+                    false,
+                );
+            let results = handler.compile_ivy(&DecoratorMetadata::Directive(dir.clone()));
+            let dir_def = results
+                .iter()
+                .find(|r| r.name == "ɵdir")
+                .expect("ɵdir not found");
+            let initializer = dir_def.initializer.as_ref().unwrap();
+            assert!(initializer.contains(r#"ɵɵviewQuerySignal(ctx.ref, ["ref"], 5)"#));
+            assert!(initializer.contains("ɵɵviewQuerySignal(ctx.cmp, Cmp, 5)"));
+            assert!(initializer.contains("ɵɵviewQuerySignal(ctx.elements, Cmp, 5, ElementRef)"));
+            assert!(
+                initializer.contains(r#"ɵɵcontentQuerySignal(dirIndex, ctx.label, ["label"], 5)"#)
+            );
+            assert!(initializer.contains("ɵɵcontentQuerySignal(dirIndex, ctx.dirs, Dir, 5)"));
+            assert!(
+                initializer.contains(r#"ɵɵcontentQuerySignal(dirIndex, ctx.items, ["item"], 4)"#)
+            );
+            assert_eq!(initializer.matches("ɵɵqueryAdvance()").count(), 6);
+        });
+    }
+
+    #[test]
+    fn test_resolve_selector_from_constants() {
+        let source = r#"
+            import {Component, Directive} from '@angular/core';
+
+            const NAME = 'root';
+            const SHARED = {selector: '[shared]', exportAs: 'shared'};
+
+            @Component({selector: `app-${NAME}`, template: ''})
+            export class AppComponent {}
+
+            @Directive({...SHARED})
+            export class SharedDir {}
+        "#;
+        let allocator = Allocator::default();
+        let program = TestProgram::new(&allocator, source);
+
+        let metadata = get_all_metadata(&program.program, std::path::Path::new("test.ts"));
+        let selectors: Vec<Option<&str>> = metadata
+            .iter()
+            .map(|m| match m {
+                DecoratorMetadata::Directive(dir) => dir.t2.selector.as_deref(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(selectors, vec![Some("app-root"), Some("[shared]")]);
+    }
+
+    #[test]
+    fn test_resolve_forward_ref_host_directives() {
+        let source = r#"
+            import {Directive, forwardRef} from '@angular/core';
+            import {BarDir} from './bar';
+
+            @Directive({
+                selector: '[test-dir]',
+                hostDirectives: [
+                    forwardRef(() => FooDir),
+                    {directive: forwardRef(() => BarDir), inputs: ['value']},
+                ],
+                imports: [forwardRef(() => FooDir)],
+            })
+            export class TestDir {}
+
+            @Directive({selector: '[foo]'})
+            export class FooDir {}
+        "#;
+
+        with_directive_metadata(source, |dir| {
+            let host_directives = dir
+                .host_directives
+                .as_ref()
+                .expect("hostDirectives not found");
+            assert_eq!(host_directives.len(), 2);
+            let foo = &host_directives[0];
+            assert!(foo.is_forward_reference);
+            assert_eq!(foo.directive.as_ref().unwrap().debug_name(), "FooDir");
+            let bar = &host_directives[1];
+            assert!(bar.is_forward_reference);
+            assert_eq!(bar.directive.as_ref().unwrap().debug_name(), "BarDir");
+            assert_eq!(dir.imports.as_ref().unwrap()[0].debug_name(), "FooDir");
+
+            let handler =
+                crate::ngtsc::annotations::directive::src::handler::DirectiveDecoratorHandler::new(
+                    false,
+                );
+            let results = handler.compile_ivy(&DecoratorMetadata::Directive(dir.clone()));
+            let dir_def = results
+                .iter()
+                .find(|r| r.name == "ɵdir")
+                .expect("ɵdir not found");
+            let initializer = dir_def.initializer.as_ref().unwrap();
+            assert!(initializer.contains("ɵɵHostDirectivesFeature(function() {"));
+            assert!(initializer.contains("return [FooDir, {directive: BarDir"));
+        });
+    }
+
+    #[test]
+    fn test_validate_host_directive_bindings() {
+        let source = r#"
+            import {Directive, input, output} from '@angular/core';
+
+            @Directive({selector: '[host-dir]'})
+            export class HostDir {
+                value = input(0);
+                changed = output<number>();
+            }
+
+            @Directive({
+                selector: '[valid]',
+                hostDirectives: [{directive: HostDir, inputs: ['value: hostValue'], outputs: ['changed']}],
+            })
+            export class ValidDir {}
+
+            @Directive({
+                selector: '[invalid]',
+                hostDirectives: [{directive: HostDir, inputs: ['valeu: hostValue'], outputs: ['change']}],
+            })
+            export class InvalidDir {}
+        "#;
+        let allocator = Allocator::default();
+        let program = TestProgram::new(&allocator, source);
+
+        let metadata = get_all_metadata(&program.program, std::path::Path::new("test.ts"));
+        let diagnostics = validate_host_directive_bindings(&metadata);
+
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Directive HostDir does not have an input with a public name of valeu.",
+                "Directive HostDir does not have an output with a public name of change.",
+            ]
+        );
+        let diagnostic = &diagnostics[0];
+        assert_eq!(
+            diagnostic.code,
+            ErrorCode::HostDirectiveUndefinedBinding as usize
+        );
+        assert_eq!(diagnostic.start, source.find("'valeu: hostValue'"));
+        assert_eq!(diagnostic.length, Some("'valeu: hostValue'".len()));
+        assert_eq!(
+            diagnostic.file.as_deref(),
+            Some(std::path::Path::new("test.ts"))
+        );
+    }
 }
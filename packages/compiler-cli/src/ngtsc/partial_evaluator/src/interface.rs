@@ -2,7 +2,14 @@
 //
 // Public interface for partial evaluator.
 
+use super::interpreter::Interpreter;
 use super::result::ResolvedValue;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    BindingPatternKind, Declaration, Expression, Program, Statement, VariableDeclarationKind,
+};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
 use std::collections::HashMap;
 
 /// Partial evaluator interface.
@@ -25,8 +32,46 @@ impl PartialEvaluator {
         self.known_values.get(name)
     }
 
-    pub fn evaluate(&self, _expression: &str) -> ResolvedValue {
-        ResolvedValue::Unknown
+    /// Record the values of the top-level `const` declarations of a program, so expressions
+    /// referencing them can be evaluated.
+    pub fn register_constants(&mut self, program: &Program<'_>) {
+        for stmt in &program.body {
+            let declaration = match stmt {
+                Statement::ExportNamedDeclaration(export) => export.declaration.as_ref(),
+                stmt => stmt.as_declaration(),
+            };
+            let Some(Declaration::VariableDeclaration(variables)) = declaration else {
+                continue;
+            };
+            if variables.kind != VariableDeclarationKind::Const {
+                continue;
+            }
+            for declarator in &variables.declarations {
+                if let (BindingPatternKind::BindingIdentifier(id), Some(init)) =
+                    (&declarator.id.kind, &declarator.init)
+                {
+                    let value = self.evaluate_expression(init);
+                    if value.is_known() {
+                        self.set_known(&id.name, value);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Evaluate an expression given as source text.
+    pub fn evaluate(&self, expression: &str) -> ResolvedValue {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_typescript(true);
+        match Parser::new(&allocator, expression, source_type).parse_expression() {
+            Ok(expr) => self.evaluate_expression(&expr),
+            Err(_) => ResolvedValue::Unknown,
+        }
+    }
+
+    /// Evaluate an expression of an already parsed program.
+    pub fn evaluate_expression(&self, expression: &Expression<'_>) -> ResolvedValue {
+        Interpreter::new().evaluate_expression(expression, &self.known_values)
     }
 }
 
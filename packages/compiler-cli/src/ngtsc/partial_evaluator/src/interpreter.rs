@@ -3,6 +3,8 @@
 // Interprets expressions for static evaluation.
 
 use super::result::ResolvedValue;
+use oxc_ast::ast::{ArrayExpressionElement, Expression, ObjectPropertyKind, TemplateLiteral};
+use std::collections::HashMap;
 
 /// Expression interpreter.
 pub struct Interpreter;
@@ -61,9 +63,137 @@ impl Interpreter {
             (ResolvedValue::String(l), "+", ResolvedValue::String(r)) => {
                 ResolvedValue::String(format!("{}{}", l, r))
             }
+            (ResolvedValue::String(l), "+", r) => match to_template_string(r) {
+                Some(r) => ResolvedValue::String(format!("{}{}", l, r)),
+                None => ResolvedValue::Unknown,
+            },
+            (l, "+", ResolvedValue::String(r)) => match to_template_string(l) {
+                Some(l) => ResolvedValue::String(format!("{}{}", l, r)),
+                None => ResolvedValue::Unknown,
+            },
             _ => ResolvedValue::Unknown,
         }
     }
+
+    /// Evaluate an expression, resolving identifiers through `known`.
+    pub fn evaluate_expression(
+        &self,
+        expr: &Expression<'_>,
+        known: &HashMap<String, ResolvedValue>,
+    ) -> ResolvedValue {
+        match expr {
+            Expression::StringLiteral(s) => ResolvedValue::String(s.value.to_string()),
+            Expression::NumericLiteral(n) => ResolvedValue::Number(n.value),
+            Expression::BooleanLiteral(b) => ResolvedValue::Boolean(b.value),
+            Expression::NullLiteral(_) => ResolvedValue::Null,
+            Expression::Identifier(ident) if ident.name == "undefined" => ResolvedValue::Undefined,
+            Expression::Identifier(ident) => known
+                .get(ident.name.as_str())
+                .cloned()
+                .unwrap_or(ResolvedValue::Unknown),
+            Expression::TemplateLiteral(template) => self.evaluate_template(template, known),
+            Expression::ArrayExpression(array) => {
+                let mut values = Vec::new();
+                for element in &array.elements {
+                    match element {
+                        ArrayExpressionElement::SpreadElement(spread) => {
+                            match self.evaluate_expression(&spread.argument, known) {
+                                ResolvedValue::Array(spread) => values.extend(spread),
+                                _ => return ResolvedValue::Unknown,
+                            }
+                        }
+                        ArrayExpressionElement::Elision(_) => values.push(ResolvedValue::Undefined),
+                        element => match element.as_expression() {
+                            Some(element) => values.push(self.evaluate_expression(element, known)),
+                            None => return ResolvedValue::Unknown,
+                        },
+                    }
+                }
+                ResolvedValue::Array(values)
+            }
+            Expression::ObjectExpression(object) => {
+                let mut entries = HashMap::new();
+                for property in &object.properties {
+                    match property {
+                        ObjectPropertyKind::ObjectProperty(property) => {
+                            let key = match property.key.static_name() {
+                                Some(key) => key.to_string(),
+                                None => return ResolvedValue::Unknown,
+                            };
+                            entries.insert(key, self.evaluate_expression(&property.value, known));
+                        }
+                        ObjectPropertyKind::SpreadProperty(spread) => {
+                            match self.evaluate_expression(&spread.argument, known) {
+                                ResolvedValue::Object(spread) => entries.extend(spread),
+                                // Spreading these into an object adds no properties.
+                                ResolvedValue::Null | ResolvedValue::Undefined => {}
+                                _ => return ResolvedValue::Unknown,
+                            }
+                        }
+                    }
+                }
+                ResolvedValue::Object(entries)
+            }
+            Expression::StaticMemberExpression(member) => {
+                match self.evaluate_expression(&member.object, known) {
+                    ResolvedValue::Object(object) => object
+                        .get(member.property.name.as_str())
+                        .cloned()
+                        .unwrap_or(ResolvedValue::Undefined),
+                    ResolvedValue::Array(array) if member.property.name == "length" => {
+                        ResolvedValue::Number(array.len() as f64)
+                    }
+                    _ => ResolvedValue::Unknown,
+                }
+            }
+            Expression::BinaryExpression(binary) => {
+                let left = self.evaluate_expression(&binary.left, known);
+                let right = self.evaluate_expression(&binary.right, known);
+                self.evaluate_binary(&left, binary.operator.as_str(), &right)
+            }
+            Expression::ParenthesizedExpression(paren) => {
+                self.evaluate_expression(&paren.expression, known)
+            }
+            Expression::TSAsExpression(e) => self.evaluate_expression(&e.expression, known),
+            Expression::TSSatisfiesExpression(e) => self.evaluate_expression(&e.expression, known),
+            Expression::TSNonNullExpression(e) => self.evaluate_expression(&e.expression, known),
+            _ => ResolvedValue::Unknown,
+        }
+    }
+
+    /// Evaluate a template literal like `` `app-${NAME}` `` by concatenating its parts.
+    fn evaluate_template(
+        &self,
+        template: &TemplateLiteral<'_>,
+        known: &HashMap<String, ResolvedValue>,
+    ) -> ResolvedValue {
+        let mut result = String::new();
+        for (i, quasi) in template.quasis.iter().enumerate() {
+            match &quasi.value.cooked {
+                Some(cooked) => result.push_str(cooked),
+                None => return ResolvedValue::Unknown,
+            }
+            if let Some(expr) = template.expressions.get(i) {
+                match to_template_string(&self.evaluate_expression(expr, known)) {
+                    Some(value) => result.push_str(&value),
+                    None => return ResolvedValue::Unknown,
+                }
+            }
+        }
+        ResolvedValue::String(result)
+    }
+}
+
+/// Convert a primitive value to the string it produces in a template literal.
+fn to_template_string(value: &ResolvedValue) -> Option<String> {
+    match value {
+        ResolvedValue::String(s) => Some(s.clone()),
+        ResolvedValue::Number(n) => Some(n.to_string()),
+        ResolvedValue::Boolean(b) => Some(b.to_string()),
+        ResolvedValue::Null => Some("null".to_string()),
+        ResolvedValue::Undefined => Some("undefined".to_string()),
+        _ => None,
+    }
 }
 
 impl Default for Interpreter {
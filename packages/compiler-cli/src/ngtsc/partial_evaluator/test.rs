@@ -95,4 +95,75 @@ mod tests {
             assert!(class_ref.module.is_none());
         }
     }
+
+    mod expression_evaluation_tests {
+        use super::*;
+        use oxc_allocator::Allocator;
+        use oxc_parser::Parser;
+        use oxc_span::SourceType;
+
+        #[test]
+        fn should_evaluate_template_literal_selector() {
+            let mut evaluator = PartialEvaluator::new();
+            evaluator.set_known("NAME", ResolvedValue::String("root".to_string()));
+            evaluator.set_known("VERSION", ResolvedValue::Number(2.0));
+
+            let value = evaluator.evaluate("`app-${NAME}-v${VERSION}`");
+            assert_eq!(value.as_string(), Some("app-root-v2"));
+
+            assert!(!evaluator.evaluate("`app-${UNKNOWN}`").is_known());
+        }
+
+        #[test]
+        fn should_evaluate_spread_merged_metadata() {
+            let mut evaluator = PartialEvaluator::new();
+            evaluator.set_known(
+                "BASE",
+                evaluator.evaluate("{selector: 'app-base', standalone: true, imports: ['A']}"),
+            );
+
+            let value = evaluator
+                .evaluate("{...BASE, selector: 'app-child', imports: [...BASE.imports, 'B']}");
+            assert_eq!(
+                value.get_property("selector").and_then(|v| v.as_string()),
+                Some("app-child")
+            );
+            assert_eq!(
+                value.get_property("standalone").and_then(|v| v.as_bool()),
+                Some(true)
+            );
+            let imports: Vec<&str> = value
+                .get_property("imports")
+                .and_then(|v| v.as_array())
+                .unwrap()
+                .iter()
+                .filter_map(|v| v.as_string())
+                .collect();
+            assert_eq!(imports, vec!["A", "B"]);
+
+            assert!(!evaluator.evaluate("{...unknownObject}").is_known());
+        }
+
+        #[test]
+        fn should_register_program_constants() {
+            let source = r#"
+                const PREFIX = 'app';
+                export const NAME = `${PREFIX}-root`;
+                let mutable = 'x';
+            "#;
+            let allocator = Allocator::default();
+            let program = Parser::new(&allocator, source, SourceType::ts())
+                .parse()
+                .program;
+
+            let mut evaluator = PartialEvaluator::new();
+            evaluator.register_constants(&program);
+
+            assert_eq!(
+                evaluator.get_known("NAME").and_then(|v| v.as_string()),
+                Some("app-root")
+            );
+            assert!(evaluator.get_known("mutable").is_none());
+        }
+    }
 }
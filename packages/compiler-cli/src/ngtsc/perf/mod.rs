@@ -1,4 +1,7 @@
-// TODO: Performance tracking
-pub struct PerfRecorder {
-    // ...
-}
+//! Performance Tracking Module
+//!
+//! Corresponds to packages/compiler-cli/src/ngtsc/perf
+
+pub mod src;
+
+pub use src::*;
@@ -0,0 +1,27 @@
+// Performance Tracking API
+//
+// The phases of a compilation whose time is tracked.
+
+/// A phase of the compilation. Time is attributed to one phase at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PerfPhase {
+    /// Parsing source files and extracting the metadata of their decorators.
+    Analysis,
+    /// Checking templates and producing their diagnostics.
+    TypeCheck,
+    /// Compiling the Ivy definitions and writing the output files.
+    Emit,
+    /// Linking partial declarations of libraries.
+    Link,
+}
+
+impl PerfPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PerfPhase::Analysis => "Analysis",
+            PerfPhase::TypeCheck => "TypeCheck",
+            PerfPhase::Emit => "Emit",
+            PerfPhase::Link => "Link",
+        }
+    }
+}
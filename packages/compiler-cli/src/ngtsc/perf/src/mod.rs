@@ -0,0 +1,7 @@
+//! Performance Tracking Source
+
+pub mod api;
+pub mod recorder;
+
+pub use api::*;
+pub use recorder::*;
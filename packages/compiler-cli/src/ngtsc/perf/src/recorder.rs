@@ -0,0 +1,98 @@
+// Performance Recorder
+//
+// Accumulates the time spent in each phase of a compilation.
+
+use super::api::PerfPhase;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The time spent in a phase.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    pub phase: PerfPhase,
+    pub duration: Duration,
+}
+
+/// Records the time spent in each phase, when enabled.
+pub struct PerfRecorder {
+    enabled: bool,
+    /// Accumulated time per phase, in the order phases were first entered.
+    phases: Mutex<Vec<PhaseTiming>>,
+}
+
+impl PerfRecorder {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            phases: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// A recorder which doesn't track anything.
+    pub fn noop() -> Self {
+        Self {
+            enabled: false,
+            phases: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Run `f`, attributing the time it takes to `phase`.
+    pub fn in_phase<T>(&self, phase: PerfPhase, f: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return f();
+        }
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    fn record(&self, phase: PerfPhase, duration: Duration) {
+        let mut phases = self.phases.lock().unwrap();
+        match phases.iter_mut().find(|timing| timing.phase == phase) {
+            Some(timing) => timing.duration += duration,
+            None => phases.push(PhaseTiming { phase, duration }),
+        }
+    }
+
+    /// The time spent in each phase so far.
+    pub fn finalize(&self) -> Vec<PhaseTiming> {
+        self.phases.lock().unwrap().clone()
+    }
+}
+
+impl Default for PerfRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulates_time_per_phase() {
+        let recorder = PerfRecorder::new();
+        let sleep = || std::thread::sleep(Duration::from_millis(1));
+        recorder.in_phase(PerfPhase::Analysis, sleep);
+        recorder.in_phase(PerfPhase::Emit, sleep);
+        recorder.in_phase(PerfPhase::Analysis, sleep);
+
+        let timings = recorder.finalize();
+        let phases: Vec<PerfPhase> = timings.iter().map(|t| t.phase).collect();
+        assert_eq!(phases, vec![PerfPhase::Analysis, PerfPhase::Emit]);
+        assert!(timings[0].duration >= Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_noop_recorder() {
+        let recorder = PerfRecorder::noop();
+        assert_eq!(recorder.in_phase(PerfPhase::Link, || 42), 42);
+        assert!(recorder.finalize().is_empty());
+    }
+}
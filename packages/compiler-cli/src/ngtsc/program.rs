@@ -1,5 +1,6 @@
+use crate::ngtsc::annotations::common::ResourceLoader;
 use crate::ngtsc::core::NgCompilerOptions;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 // use crate::compiler::CompilationResult; // Removed to resolve conflict with ngtsc::core::CompilationResult
 // Let's use the one from ngtsc::core if exported, or fully qualify.
 // Actually, let's remove this import and use the one NgCompiler uses.
@@ -10,7 +11,11 @@ use std::path::Path;
 // Or check where NgCompiler comes from.
 // Import:
 use crate::ngtsc::core::{CompilationResult, CompilationTicket, CompilationTicketKind, NgCompiler};
-use crate::ngtsc::file_system::FileSystem;
+use crate::ngtsc::file_system::{AbsoluteFsPath, FileSystem};
+use crate::ngtsc::logging::Logger;
+use crate::ngtsc::perf::PhaseTiming;
+use glob::{MatchOptions, Pattern};
+use std::sync::Arc;
 
 pub struct NgtscProgram<'a, T: FileSystem> {
     root_names: Vec<String>,
@@ -36,6 +41,35 @@ impl<'a, T: FileSystem> NgtscProgram<'a, T> {
         }
     }
 
+    /// Report the progress of the compilation to `logger` instead of discarding it.
+    pub fn with_logger(mut self, logger: Arc<dyn Logger + Send + Sync>) -> Self {
+        self.compiler = self.compiler.with_logger(logger);
+        self
+    }
+
+    /// Load external templates and stylesheets with `resource_loader` instead of the program's
+    /// file system.
+    pub fn with_resource_loader(mut self, resource_loader: Arc<dyn ResourceLoader>) -> Self {
+        self.compiler = self.compiler.with_resource_loader(resource_loader);
+        self
+    }
+
+    /// Create a program whose root files are discovered from tsconfig-style `files`, `include`
+    /// and `exclude` settings, relative to `base_dir`.
+    pub fn from_root_file_spec(
+        base_dir: &AbsoluteFsPath,
+        spec: &RootFileSpec,
+        options: NgCompilerOptions,
+        fs: &'a T,
+    ) -> Self {
+        let root_names = discover_root_files(fs, base_dir, spec);
+        Self::new(root_names, options, fs)
+    }
+
+    pub fn root_names(&self) -> &[String] {
+        &self.root_names
+    }
+
     pub fn load_ng_structure(&mut self, _path: &Path) -> Result<(), String> {
         // eprintln!("DEBUG: NgtscProgram::load_ng_structure called with {} root files", self.root_names.len());
         for name in &self.root_names {
@@ -55,6 +89,23 @@ impl<'a, T: FileSystem> NgtscProgram<'a, T> {
         }
     }
 
+    pub fn get_compilation_result(&self) -> Option<&CompilationResult> {
+        self.result.as_ref()
+    }
+
+    /// The time spent in each phase so far, empty unless `tracePerformance` is enabled.
+    pub fn get_perf_timings(&self) -> Vec<PhaseTiming> {
+        self.compiler.perf_recorder.finalize()
+    }
+
+    pub fn get_options(&self) -> &NgCompilerOptions {
+        &self.options
+    }
+
+    pub fn get_file_system(&self) -> &'a T {
+        self.compiler.fs
+    }
+
     pub fn emit(&self) -> Result<Vec<crate::ngtsc::core::Diagnostic>, String> {
         // Ensure analysis happens if not already done (simplified)
         // In reality, load_ng_structure is called before emit.
@@ -64,4 +115,256 @@ impl<'a, T: FileSystem> NgtscProgram<'a, T> {
             Err("Compilation result not available. Did you call load_ng_structure?".to_string())
         }
     }
+
+    /// Emit, writing the JavaScript of each source file to the path returned by `output_path`,
+    /// e.g. to flatten the output into a single directory.
+    pub fn emit_with_output_paths(
+        &self,
+        output_path: impl Fn(&Path) -> PathBuf + Sync,
+    ) -> Result<Vec<crate::ngtsc::core::Diagnostic>, String> {
+        if let Some(result) = &self.result {
+            self.compiler.emit_with_output_paths(result, &output_path)
+        } else {
+            Err("Compilation result not available. Did you call load_ng_structure?".to_string())
+        }
+    }
+}
+
+/// The `files`, `include` and `exclude` settings of a tsconfig.
+#[derive(Debug, Clone, Default)]
+pub struct RootFileSpec {
+    pub files: Vec<String>,
+    /// Defaults to `["**/*"]`, or to nothing when `files` is given.
+    pub include: Option<Vec<String>>,
+    /// Defaults to `["node_modules", "bower_components", "jspm_packages"]`.
+    pub exclude: Option<Vec<String>>,
+}
+
+const DEFAULT_EXCLUDE: &[&str] = &["node_modules", "bower_components", "jspm_packages"];
+
+/// Expand a `RootFileSpec` to the absolute paths of the `.ts`/`.tsx` files it selects, following
+/// tsc: explicit `files` are always kept, wildcards never match dot-files, and a pattern whose
+/// last segment has neither a wildcard nor an extension names a directory.
+pub fn discover_root_files<T: FileSystem>(
+    fs: &T,
+    base_dir: &AbsoluteFsPath,
+    spec: &RootFileSpec,
+) -> Vec<String> {
+    let match_options = MatchOptions {
+        case_sensitive: fs.is_case_sensitive(),
+        require_literal_separator: true,
+        require_literal_leading_dot: true,
+    };
+    let default_include = if spec.files.is_empty() {
+        vec!["**/*".to_string()]
+    } else {
+        Vec::new()
+    };
+    let include = compile_patterns(spec.include.as_ref().unwrap_or(&default_include));
+    let exclude = match &spec.exclude {
+        Some(exclude) => compile_patterns(exclude),
+        None => compile_patterns(
+            &DEFAULT_EXCLUDE
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>(),
+        ),
+    };
+
+    let mut root_names = Vec::new();
+    for file in &spec.files {
+        let path = fs.resolve(&[base_dir.as_str(), file]);
+        if fs.stat(&path).map(|s| s.is_file()).unwrap_or(false) {
+            root_names.push(path.into_string());
+        }
+    }
+
+    if !include.is_empty() {
+        let mut discovered = Vec::new();
+        let mut pending = vec![String::new()];
+        while let Some(dir) = pending.pop() {
+            let dir_path = AbsoluteFsPath::new(fs.join(base_dir.as_str(), &[&dir]));
+            let Ok(entries) = fs.readdir(&dir_path) else {
+                continue;
+            };
+            for entry in entries {
+                let relative = if dir.is_empty() {
+                    entry.as_str().to_string()
+                } else {
+                    format!("{}/{}", dir, entry.as_str())
+                };
+                let path = AbsoluteFsPath::new(fs.join(base_dir.as_str(), &[&relative]));
+                let Ok(stats) = fs.stat(&path) else {
+                    continue;
+                };
+                if stats.is_directory() {
+                    // Don't walk directories that are excluded as a whole, e.g. `node_modules`.
+                    let placeholder = format!("{}/_", relative);
+                    if !exclude
+                        .iter()
+                        .any(|p| p.matches_with(&placeholder, match_options))
+                    {
+                        pending.push(relative);
+                    }
+                } else if (relative.ends_with(".ts") || relative.ends_with(".tsx"))
+                    && include
+                        .iter()
+                        .any(|p| p.matches_with(&relative, match_options))
+                    && !exclude
+                        .iter()
+                        .any(|p| p.matches_with(&relative, match_options))
+                {
+                    discovered.push(path.into_string());
+                }
+            }
+        }
+        discovered.sort();
+        for path in discovered {
+            if !root_names.contains(&path) {
+                root_names.push(path);
+            }
+        }
+    }
+
+    root_names
+}
+
+fn compile_patterns(specs: &[String]) -> Vec<Pattern> {
+    specs
+        .iter()
+        .filter_map(|spec| {
+            let spec = spec.trim_start_matches("./").trim_end_matches('/');
+            let last = spec.rsplit('/').next().unwrap_or(spec);
+            let pattern = if last.contains(['*', '?', '.']) {
+                spec.to_string()
+            } else {
+                format!("{}/**/*", spec)
+            };
+            Pattern::new(&pattern).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ngtsc::file_system::{MemoryFileSystem, ReadonlyFileSystem};
+
+    fn fs() -> MemoryFileSystem {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[
+            ("/app/src/main.ts", ""),
+            ("/app/src/app/app.component.ts", ""),
+            ("/app/src/app/app.component.spec.ts", ""),
+            ("/app/src/app/app.component.html", ""),
+            ("/app/src/.hidden/skip.ts", ""),
+            ("/app/tools/build.ts", ""),
+            ("/app/node_modules/lib/index.d.ts", ""),
+        ])
+        .unwrap();
+        fs
+    }
+
+    fn base() -> AbsoluteFsPath {
+        AbsoluteFsPath::new("/app".to_string())
+    }
+
+    #[test]
+    fn should_discover_root_files_from_include_and_exclude() {
+        let fs = fs();
+        let spec = RootFileSpec {
+            include: Some(vec!["src/**/*.ts".to_string()]),
+            exclude: Some(vec!["**/*.spec.ts".to_string()]),
+            ..Default::default()
+        };
+        let program =
+            NgtscProgram::from_root_file_spec(&base(), &spec, NgCompilerOptions::default(), &fs);
+        assert_eq!(
+            program.root_names(),
+            &[
+                "/app/src/app/app.component.ts".to_string(),
+                "/app/src/main.ts".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_default_to_all_files_except_node_modules() {
+        let fs = fs();
+        let root_names = discover_root_files(&fs, &base(), &RootFileSpec::default());
+        assert_eq!(
+            root_names,
+            vec![
+                "/app/src/app/app.component.spec.ts".to_string(),
+                "/app/src/app/app.component.ts".to_string(),
+                "/app/src/main.ts".to_string(),
+                "/app/tools/build.ts".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_explicit_files_and_expand_directory_includes() {
+        let fs = fs();
+        let spec = RootFileSpec {
+            files: vec!["tools/build.ts".to_string(), "missing.ts".to_string()],
+            include: Some(vec!["src/app".to_string()]),
+            exclude: Some(vec!["src/app/*.spec.ts".to_string()]),
+        };
+        assert_eq!(
+            discover_root_files(&fs, &base(), &spec),
+            vec![
+                "/app/tools/build.ts".to_string(),
+                "/app/src/app/app.component.ts".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_emit_to_the_remapped_output_paths() {
+        let fs = MemoryFileSystem::new();
+        fs.init_with_files(&[
+            (
+                "/app/src/app/util.ts",
+                "export const answer: number = 42;\n",
+            ),
+            (
+                "/app/src/app/app.component.ts",
+                r#"
+                    import { Component } from '@angular/core';
+
+                    @Component({ selector: 'app-root', template: 'hi', standalone: true })
+                    export class AppComponent {}
+                "#,
+            ),
+        ])
+        .unwrap();
+        let options = NgCompilerOptions {
+            project: "/app/tsconfig.json".to_string(),
+            out_dir: Some("/app/out".to_string()),
+            ..NgCompilerOptions::default()
+        };
+        let mut program = NgtscProgram::new(
+            vec![
+                "/app/src/app/util.ts".to_string(),
+                "/app/src/app/app.component.ts".to_string(),
+            ],
+            options,
+            &fs,
+        );
+        program.load_ng_structure(Path::new("/app")).unwrap();
+
+        let diagnostics = program
+            .emit_with_output_paths(|file| {
+                let name = file.file_stem().unwrap().to_string_lossy();
+                PathBuf::from("/dist").join(format!("{}.mjs", name))
+            })
+            .unwrap();
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+        let read = |path: &str| fs.read_file(&AbsoluteFsPath::new(path.to_string()));
+        assert!(read("/dist/util.mjs").unwrap().contains("answer = 42"));
+        assert!(read("/dist/app.component.mjs").unwrap().contains("ɵcmp"));
+        assert!(read("/app/out/src/app/util.js").is_err());
+    }
 }
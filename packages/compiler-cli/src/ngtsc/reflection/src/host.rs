@@ -13,8 +13,9 @@ pub struct Decorator<'a> {
     /// `Import` by which the decorator was brought into the module in which it was invoked.
     pub import: Option<Import<'a>>,
 
-    /// Oxc AST reference to the decorator itself.
-    pub node: &'a ast::Decorator<'a>,
+    /// Oxc AST reference to the decorator itself. `None` for decorators inferred from the static
+    /// definition fields of an already compiled class.
+    pub node: Option<&'a ast::Decorator<'a>>,
 
     /// Arguments of the invocation of the decorator.
     pub args: Option<Vec<&'a ast::Expression<'a>>>,
@@ -1,4 +1,5 @@
 use super::host::*;
+use crate::ngtsc::annotations::common::{self, is_angular_core};
 use oxc_ast::ast as oxc;
 use std::collections::HashMap;
 use std::marker::PhantomData;
@@ -14,6 +15,51 @@ impl<'a> TypeScriptReflectionHost<'a> {
         }
     }
 
+    /// Get the decorators of a class which are imported from `@angular/core`, e.g. to classify it
+    /// as a component, directive, pipe, injectable or NgModule.
+    ///
+    /// Already compiled classes no longer have decorators, so for those the decorators are
+    /// inferred from their static definition fields (`ɵcmp`, `ɵdir`, `ɵpipe`, `ɵprov`, `ɵmod`).
+    pub fn get_angular_decorators_for_class(
+        &self,
+        program: &'a oxc::Program<'a>,
+        clazz: &'a ClassDeclaration<'a>,
+    ) -> Vec<Decorator<'a>> {
+        let mut decorators = self
+            .convert_decorators(&clazz.decorators)
+            .unwrap_or_default();
+        for decorator in &mut decorators {
+            decorator.import = decorator
+                .identifier
+                .as_ref()
+                .and_then(|id| find_import(program, &id.name));
+        }
+
+        for element in &clazz.body.body {
+            if let oxc::ClassElement::PropertyDefinition(prop) = element {
+                if let Some(decorator) = static_definition_decorator(program, prop) {
+                    decorators.push(decorator);
+                }
+            }
+        }
+
+        decorators
+            .into_iter()
+            .filter(|decorator| {
+                let import = decorator.import.as_ref().map(|import| common::Import {
+                    name: import.name.clone(),
+                    from: import.from.clone(),
+                });
+                is_angular_core(&common::Decorator {
+                    name: decorator.name.clone(),
+                    import,
+                    args: None,
+                    node: String::new(),
+                })
+            })
+            .collect()
+    }
+
     fn convert_decorators(
         &self,
         oxc_decorators: &'a [oxc::Decorator<'a>],
@@ -50,7 +96,7 @@ impl<'a> TypeScriptReflectionHost<'a> {
                     name,
                     identifier,
                     import: None, // Import resolution requires full TypeChecker
-                    node: decorator,
+                    node: Some(decorator),
                     args: Some(args),
                 });
             } else if let oxc::Expression::Identifier(ident) = &decorator.expression {
@@ -62,7 +108,7 @@ impl<'a> TypeScriptReflectionHost<'a> {
                         module_name: None,
                     }),
                     import: None,
-                    node: decorator,
+                    node: Some(decorator),
                     args: None,
                 });
             }
@@ -76,6 +122,93 @@ impl<'a> TypeScriptReflectionHost<'a> {
     }
 }
 
+/// Find the import which brings `local_name` into scope in `program`.
+fn find_import<'a>(program: &'a oxc::Program<'a>, local_name: &str) -> Option<Import<'a>> {
+    program.body.iter().find_map(|stmt| {
+        let oxc::Statement::ImportDeclaration(import) = stmt else {
+            return None;
+        };
+        import.specifiers.iter().flatten().find_map(|specifier| {
+            let name = match specifier {
+                oxc::ImportDeclarationSpecifier::ImportSpecifier(s)
+                    if s.local.name == local_name =>
+                {
+                    s.imported.name().to_string()
+                }
+                oxc::ImportDeclarationSpecifier::ImportDefaultSpecifier(s)
+                    if s.local.name == local_name =>
+                {
+                    "default".to_string()
+                }
+                oxc::ImportDeclarationSpecifier::ImportNamespaceSpecifier(s)
+                    if s.local.name == local_name =>
+                {
+                    s.local.name.to_string()
+                }
+                _ => return None,
+            };
+            Some(Import {
+                name,
+                from: import.source.value.to_string(),
+                node: import,
+            })
+        })
+    })
+}
+
+/// Infer the decorator of a compiled class from a static definition field like
+/// `static ɵcmp = i0.ɵɵdefineComponent(...)` or `static ɵcmp: i0.ɵɵComponentDeclaration<...>`.
+fn static_definition_decorator<'a>(
+    program: &'a oxc::Program<'a>,
+    prop: &'a oxc::PropertyDefinition<'a>,
+) -> Option<Decorator<'a>> {
+    if !prop.r#static {
+        return None;
+    }
+    let name = match prop.key.static_name()?.as_ref() {
+        "ɵcmp" => "Component",
+        "ɵdir" => "Directive",
+        "ɵpipe" => "Pipe",
+        "ɵprov" => "Injectable",
+        "ɵmod" => "NgModule",
+        _ => return None,
+    };
+
+    // The definition is referenced through a namespace import of the core package, e.g. `i0`.
+    let namespace = match (&prop.value, &prop.type_annotation) {
+        (Some(oxc::Expression::CallExpression(call)), _) => match &call.callee {
+            oxc::Expression::StaticMemberExpression(member) => match &member.object {
+                oxc::Expression::Identifier(ident) => Some(ident.name.as_str()),
+                _ => None,
+            },
+            _ => None,
+        },
+        (None, Some(annotation)) => match &annotation.type_annotation {
+            oxc::TSType::TSTypeReference(reference) => match &reference.type_name {
+                oxc::TSTypeName::QualifiedName(qualified) => match &qualified.left {
+                    oxc::TSTypeName::IdentifierReference(ident) => Some(ident.name.as_str()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }?;
+
+    let import = find_import(program, namespace).map(|import| Import {
+        name: name.to_string(),
+        ..import
+    });
+    Some(Decorator {
+        name: name.to_string(),
+        identifier: None,
+        import,
+        node: None,
+        args: None,
+    })
+}
+
 impl<'a> ReflectionHost<'a> for TypeScriptReflectionHost<'a> {
     fn get_decorators_of_declaration(
         &self,
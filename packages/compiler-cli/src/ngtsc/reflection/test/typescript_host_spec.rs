@@ -431,4 +431,67 @@ mod tests {
         assert_eq!(decorators.len(), 1);
         assert_eq!(decorators[0].name, "Dec");
     }
+
+    #[test]
+    fn test_angular_decorators_for_class() {
+        let source = r#"
+            import {Component, Injectable as Service} from '@angular/core';
+            import {Dec} from './dec';
+
+            @Component({selector: 'app-root', template: ''})
+            @Service()
+            @Dec()
+            export class AppComponent {}
+        "#;
+        let allocator = Allocator::default();
+        let program = TestProgram::new(&allocator, source);
+        let host = TypeScriptReflectionHost::new();
+
+        let class = program
+            .find_class("AppComponent")
+            .expect("Class AppComponent not found");
+        let decorators = host.get_angular_decorators_for_class(&program.program, class);
+
+        let names: Vec<&str> = decorators.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["Component", "Service"]);
+        let import = decorators[1].import.as_ref().expect("Import not found");
+        assert_eq!(import.name, "Injectable");
+        assert_eq!(import.from, "@angular/core");
+        assert!(decorators[0].node.is_some());
+    }
+
+    #[test]
+    fn test_angular_decorators_for_compiled_class() {
+        let source = r#"
+            import * as i0 from '@angular/core';
+
+            export class MyPipe {
+                static ɵfac = function MyPipe_Factory(t) { return new (t || MyPipe)(); };
+                static ɵpipe = i0.ɵɵdefinePipe({name: 'my', type: MyPipe, pure: true});
+            }
+
+            export declare class MyService {
+                static ɵfac: i0.ɵɵFactoryDeclaration<MyService, never>;
+                static ɵprov: i0.ɵɵInjectableDeclaration<MyService>;
+            }
+        "#;
+        let allocator = Allocator::default();
+        let program = TestProgram::new(&allocator, source);
+        let host = TypeScriptReflectionHost::new();
+
+        let pipe = program
+            .find_class("MyPipe")
+            .expect("Class MyPipe not found");
+        let decorators = host.get_angular_decorators_for_class(&program.program, pipe);
+        assert_eq!(decorators.len(), 1);
+        assert_eq!(decorators[0].name, "Pipe");
+        assert!(decorators[0].node.is_none());
+
+        let service = program
+            .find_class("MyService")
+            .expect("Class MyService not found");
+        let decorators = host.get_angular_decorators_for_class(&program.program, service);
+        assert_eq!(decorators.len(), 1);
+        assert_eq!(decorators[0].name, "Injectable");
+    }
 }
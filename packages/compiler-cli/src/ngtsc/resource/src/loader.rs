@@ -2,7 +2,11 @@
 //
 // Loads external resources (templates, styles).
 
+use crate::ngtsc::annotations::common::api::PreprocessFuture;
+use crate::ngtsc::annotations::common::{self, PreloadFuture, ResourceLoaderContext};
+use crate::ngtsc::file_system::{AbsoluteFsPath, ReadonlyFileSystem};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 /// Resource load error.
 #[derive(Debug, Clone)]
@@ -71,31 +75,93 @@ impl ResourceLoader for InMemoryResourceLoader {
     }
 }
 
-/// File-based resource loader.
-pub struct FileResourceLoader {
-    root_dir: String,
+/// Resource loader reading component resources through a `ReadonlyFileSystem`, caching their
+/// content.
+///
+/// `preload` reads a resource into the cache so that the synchronous `load` calls made during
+/// analysis don't hit the file system again.
+pub struct FileResourceLoader<F: ReadonlyFileSystem> {
+    fs: Arc<F>,
+    cache: Arc<Mutex<HashMap<String, String>>>,
 }
 
-impl FileResourceLoader {
-    pub fn new(root_dir: impl Into<String>) -> Self {
+impl<F: ReadonlyFileSystem + 'static> FileResourceLoader<F> {
+    pub fn new(fs: Arc<F>) -> Self {
         Self {
-            root_dir: root_dir.into(),
+            fs,
+            cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
+
+    /// Whether the content of the resource at the given URL is cached.
+    pub fn is_cached(&self, resolved_url: &str) -> bool {
+        self.cache.lock().unwrap().contains_key(resolved_url)
+    }
+
+    /// Forget the cached content of a resource, e.g. after it changed on disk.
+    pub fn invalidate(&self, resolved_url: &str) {
+        self.cache.lock().unwrap().remove(resolved_url);
+    }
 }
 
-impl ResourceLoader for FileResourceLoader {
-    fn can_preload(&self, url: &str) -> bool {
-        let path = format!("{}/{}", self.root_dir, url);
-        std::path::Path::new(&path).exists()
+fn read_resource<F: ReadonlyFileSystem>(fs: &F, resolved_url: &str) -> Result<String, String> {
+    fs.read_file(&AbsoluteFsPath::new(resolved_url.to_string()))
+        .map_err(|e| ResourceError::load_failed(resolved_url, &e.to_string()).message)
+}
+
+impl<F: ReadonlyFileSystem + 'static> common::ResourceLoader for FileResourceLoader<F> {
+    fn can_preload(&self) -> bool {
+        true
     }
 
-    fn preload(&self, _url: &str) -> Result<(), ResourceError> {
-        Ok(())
+    fn can_preprocess(&self) -> bool {
+        false
     }
 
-    fn load(&self, url: &str) -> Result<String, ResourceError> {
-        let path = format!("{}/{}", self.root_dir, url);
-        std::fs::read_to_string(&path).map_err(|e| ResourceError::load_failed(url, &e.to_string()))
+    fn resolve(&self, file: &str, base_path: &str) -> Result<String, String> {
+        let resolved = self.fs.resolve(&[&self.fs.dirname(base_path), file]);
+        if self.fs.exists(&resolved) {
+            Ok(resolved.into_string())
+        } else {
+            Err(format!(
+                "Could not find resource {} relative to {}",
+                file, base_path
+            ))
+        }
+    }
+
+    fn preload(
+        &self,
+        resolved_url: &str,
+        _context: &ResourceLoaderContext,
+    ) -> Option<PreloadFuture> {
+        if self.is_cached(resolved_url) {
+            return None;
+        }
+        let fs = self.fs.clone();
+        let cache = self.cache.clone();
+        let resolved_url = resolved_url.to_string();
+        Some(Box::pin(async move {
+            let content = read_resource(fs.as_ref(), &resolved_url)?;
+            cache.lock().unwrap().insert(resolved_url, content);
+            Ok(())
+        }))
+    }
+
+    fn preprocess_inline(&self, data: &str, _context: &ResourceLoaderContext) -> PreprocessFuture {
+        let data = data.to_string();
+        Box::pin(async move { Ok(data) })
+    }
+
+    fn load(&self, resolved_url: &str) -> Result<String, String> {
+        if let Some(content) = self.cache.lock().unwrap().get(resolved_url) {
+            return Ok(content.clone());
+        }
+        let content = read_resource(self.fs.as_ref(), resolved_url)?;
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(resolved_url.to_string(), content.clone());
+        Ok(content)
     }
 }
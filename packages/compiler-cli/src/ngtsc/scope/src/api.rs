@@ -2,6 +2,8 @@
 //
 // Public API types for scope resolution.
 
+use crate::ngtsc::imports::Reexport;
+
 /// Represents the exports of a component, directive, pipe, or NgModule.
 #[derive(Debug, Clone)]
 pub struct ExportScope {
@@ -60,6 +62,8 @@ pub struct CompilationScope {
     pub is_poisoned: bool,
     /// NgModule (if not standalone).
     pub ng_module: Option<String>,
+    /// Private re-exports the NgModule's file needs so that its exports can be imported.
+    pub reexports: Option<Vec<Reexport>>,
 }
 
 impl CompilationScope {
@@ -71,6 +75,7 @@ impl CompilationScope {
             has_external_styles: false,
             is_poisoned: false,
             ng_module: None,
+            reexports: None,
         }
     }
 }
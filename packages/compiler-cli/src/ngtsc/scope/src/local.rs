@@ -3,6 +3,9 @@
 // Responsible for tracking the compilation scope of NgModules.
 
 use super::api::{CompilationScope, DirectiveInScope, ExportScope};
+use crate::ngtsc::file_system::src::util::strip_extension;
+use crate::ngtsc::imports::src::emitter::calculate_relative_path;
+use crate::ngtsc::imports::{AliasingHost, Reexport};
 use std::collections::{HashMap, HashSet};
 
 /// Registry for local NgModule compilation scopes.
@@ -19,6 +22,12 @@ pub struct LocalModuleScopeRegistry {
     imports_by_module: HashMap<String, Vec<String>>,
     /// Exports by module.
     exports_by_module: HashMap<String, Vec<String>>,
+    /// Source files of modules and declarations.
+    source_files: HashMap<String, String>,
+    /// Host deciding which exports need a private re-export, if re-exports are generated.
+    aliasing_host: Option<Box<dyn AliasingHost + Send + Sync>>,
+    /// Diagnostics collected while computing scopes.
+    diagnostics: Vec<String>,
 }
 
 impl LocalModuleScopeRegistry {
@@ -30,9 +39,23 @@ impl LocalModuleScopeRegistry {
             declarations_by_module: HashMap::new(),
             imports_by_module: HashMap::new(),
             exports_by_module: HashMap::new(),
+            source_files: HashMap::new(),
+            aliasing_host: None,
+            diagnostics: Vec::new(),
         }
     }
 
+    /// Generate re-exports for module exports, as decided by `host`.
+    pub fn with_aliasing_host(mut self, host: impl AliasingHost + Send + Sync + 'static) -> Self {
+        self.aliasing_host = Some(Box::new(host));
+        self
+    }
+
+    /// Register the source file a module or declaration is declared in.
+    pub fn register_source_file(&mut self, reference: impl Into<String>, file: impl Into<String>) {
+        self.source_files.insert(reference.into(), file.into());
+    }
+
     /// Register a module's declarations, imports, and exports.
     pub fn register_ng_module_metadata(
         &mut self,
@@ -112,10 +135,54 @@ impl LocalModuleScopeRegistry {
             // Would add exported directives/pipes from imported modules
         }
 
+        scope.reexports = self.compute_reexports(module_ref);
+
         self.scope_cache.insert(module_ref.to_string(), scope);
         self.sealed_modules.insert(module_ref.to_string());
     }
 
+    /// Compute the private re-exports of a module's exports, if an aliasing host is configured.
+    ///
+    /// Two exports aliased to the same name poison the module.
+    fn compute_reexports(&mut self, module_ref: &str) -> Option<Vec<Reexport>> {
+        let host = self.aliasing_host.as_ref()?;
+        let module_file = self.source_files.get(module_ref)?;
+
+        let mut reexports: Vec<Reexport> = Vec::new();
+        let mut collisions = Vec::new();
+        for export in self.exports_by_module.get(module_ref).into_iter().flatten() {
+            let Some(decl_file) = self.source_files.get(export) else {
+                continue;
+            };
+            let Some(alias) =
+                host.maybe_alias_symbol_as(export, decl_file, module_file, module_ref, false)
+            else {
+                continue;
+            };
+
+            match reexports.iter().find(|r| r.as_alias == alias) {
+                Some(existing) if existing.symbol_name != *export => {
+                    collisions.push(format!(
+                        "NgModule {} exports both {} and {} under the private name {}",
+                        module_ref, existing.symbol_name, export, alias
+                    ));
+                }
+                Some(_) => {}
+                None => reexports.push(Reexport::new(
+                    export.clone(),
+                    alias,
+                    calculate_relative_path(module_file, &strip_extension(decl_file)),
+                )),
+            }
+        }
+
+        if !collisions.is_empty() {
+            self.poisoned_modules.insert(module_ref.to_string());
+            self.diagnostics.extend(collisions);
+        }
+        Some(reexports)
+    }
+
     /// Register a declaration.
     pub fn register_declaration(
         &mut self,
@@ -135,6 +202,7 @@ impl LocalModuleScopeRegistry {
         self.poisoned_modules
             .iter()
             .map(|m| format!("Module {} has scope errors", m))
+            .chain(self.diagnostics.iter().cloned())
             .collect()
     }
 }
@@ -144,3 +212,65 @@ impl Default for LocalModuleScopeRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ngtsc::imports::{ExportInfo, ExportMap, PrivateExportAliasingHost};
+
+    /// A registry for `LibModule` in `/lib/module.ts`, which exports `PublicDir` and
+    /// `InternalDir`. Only `PublicDir` is exported from the module's file.
+    fn lib_registry() -> LocalModuleScopeRegistry {
+        let host = PrivateExportAliasingHost::new(|file| {
+            (file == "/lib/module.ts").then(|| {
+                ExportMap::from([(
+                    "PublicDir".to_string(),
+                    ExportInfo {
+                        name: "PublicDir".to_string(),
+                        local_name: "PublicDir".to_string(),
+                        is_reexport: true,
+                    },
+                )])
+            })
+        });
+        let mut registry = LocalModuleScopeRegistry::new().with_aliasing_host(host);
+        let exports = vec!["PublicDir".to_string(), "InternalDir".to_string()];
+        registry.register_ng_module_metadata("LibModule", exports.clone(), vec![], exports);
+        registry.register_source_file("LibModule", "/lib/module.ts");
+        registry.register_source_file("PublicDir", "/lib/public.ts");
+        registry.register_source_file("InternalDir", "/lib/internal/dir.ts");
+        registry
+    }
+
+    #[test]
+    fn test_reexport_unexported_declaration() {
+        let mut registry = lib_registry();
+        let scope = registry.get_scope_of_module("LibModule").unwrap();
+
+        assert_eq!(
+            scope.reexports,
+            Some(vec![Reexport::new(
+                "InternalDir",
+                "ɵngExportɵLibModuleɵInternalDir",
+                "./internal/dir",
+            )])
+        );
+        assert!(!registry.is_poisoned("LibModule"));
+    }
+
+    #[test]
+    fn test_no_reexports_without_aliasing_host() {
+        let mut registry = LocalModuleScopeRegistry::new();
+        registry.register_ng_module_metadata(
+            "LibModule",
+            vec!["InternalDir".to_string()],
+            vec![],
+            vec!["InternalDir".to_string()],
+        );
+        registry.register_source_file("LibModule", "/lib/module.ts");
+        registry.register_source_file("InternalDir", "/lib/internal/dir.ts");
+
+        let scope = registry.get_scope_of_module("LibModule").unwrap();
+        assert!(scope.reexports.is_none());
+    }
+}
@@ -4,6 +4,7 @@ pub mod api;
 pub mod component_scope;
 pub mod dependency;
 pub mod local;
+pub mod selector;
 pub mod standalone;
 pub mod typecheck;
 pub mod util;
@@ -16,6 +17,9 @@ pub use api::{
 pub use component_scope::ComponentScopeReader;
 pub use dependency::{DependencyScopeReader, ExternalDirectiveMetadata, ExternalPipeMetadata};
 pub use local::LocalModuleScopeRegistry;
+pub use selector::{
+    parse_css_selector, AttributeOperator, AttributeSelector, CssSelector, SelectorParseError,
+};
 pub use standalone::{RemoteScope, StandaloneComponentScopeReader, StandaloneImport};
 pub use typecheck::{
     TypeCheckDirective, TypeCheckInput, TypeCheckOutput, TypeCheckPipe, TypeCheckScope,
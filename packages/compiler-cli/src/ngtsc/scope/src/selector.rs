@@ -0,0 +1,321 @@
+// Selector Parser
+//
+// Structured parsing of directive/component CSS selectors, intended for tools
+// (e.g. linters) that need to inspect a selector without running the matcher.
+// Selectors are parsed by the compiler's `directive_matching::CssSelector::parse`,
+// so they are read exactly like the matcher reads them.
+
+use angular_compiler::directive_matching;
+use std::fmt;
+
+/// The comparison operator of an attribute constraint.
+///
+/// Directive selectors only support `=`, other CSS operators are reported as errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOperator {
+    /// `[attr=value]`
+    Equals,
+}
+
+impl AttributeOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttributeOperator::Equals => "=",
+        }
+    }
+}
+
+/// An attribute constraint of a selector. `#id` is represented as `[id=id]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeSelector {
+    pub name: String,
+    /// Operator and value; `None` for presence-only constraints like `[appX]` (or `[appX=""]`).
+    pub value: Option<(AttributeOperator, String)>,
+}
+
+/// A single compound selector (one entry of a comma-separated selector list).
+///
+/// Like in the matcher, class names and attribute values are lowercase.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CssSelector {
+    pub element: Option<String>,
+    pub attributes: Vec<AttributeSelector>,
+    pub classes: Vec<String>,
+    /// Selectors from `:not(...)`. These never contain further negations.
+    pub negations: Vec<CssSelector>,
+}
+
+impl From<directive_matching::CssSelector> for CssSelector {
+    fn from(selector: directive_matching::CssSelector) -> Self {
+        CssSelector {
+            element: selector.element,
+            attributes: selector
+                .attrs
+                .chunks(2)
+                .map(|pair| AttributeSelector {
+                    name: pair[0].clone(),
+                    value: (!pair[1].is_empty())
+                        .then(|| (AttributeOperator::Equals, pair[1].clone())),
+                })
+                .collect(),
+            classes: selector.class_names,
+            negations: selector
+                .not_selectors
+                .into_iter()
+                .map(CssSelector::from)
+                .collect(),
+        }
+    }
+}
+
+/// Error produced when a selector cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorParseError {
+    pub message: String,
+    /// Byte offset in the source selector where the problem was found.
+    pub offset: usize,
+}
+
+impl SelectorParseError {
+    fn new(message: impl Into<String>, offset: usize) -> Self {
+        Self {
+            message: message.into(),
+            offset,
+        }
+    }
+}
+
+impl fmt::Display for SelectorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at offset {})", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for SelectorParseError {}
+
+/// Parse a comma-separated selector list into structured selectors.
+///
+/// Only the subset of CSS accepted by Angular directive selectors is supported:
+/// element names, `[attr]` / `[attr=value]`, `.class`, `#id` and a single
+/// level of `:not(...)`. Combinators are rejected.
+pub fn parse_css_selector(selector: &str) -> Result<Vec<CssSelector>, SelectorParseError> {
+    check_supported(selector)?;
+    let selectors = directive_matching::CssSelector::parse(selector)
+        .map_err(|message| SelectorParseError::new(message, 0))?;
+    Ok(selectors.into_iter().map(CssSelector::from).collect())
+}
+
+/// Report what `CssSelector::parse` would skip over without an error: combinators, pseudo-classes
+/// other than `:not`, attribute operators other than `=` and empty selectors.
+fn check_supported(selector: &str) -> Result<(), SelectorParseError> {
+    // A combinator seen after the content of the current compound selector
+    let mut combinator: Option<(usize, char)> = None;
+    let mut has_content = false;
+    let mut not_start: Option<usize> = None;
+    let mut list_start = 0;
+    let mut chars = selector.char_indices();
+
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if has_content && combinator.is_none() {
+                    combinator = Some((offset, ' '));
+                }
+            }
+            '>' | '+' | '~' => {
+                if combinator.is_none_or(|(_, c)| c == ' ') {
+                    combinator = Some((offset, c));
+                }
+            }
+            ',' | ')' => {
+                if let Some((offset, c)) = combinator.take().filter(|(_, c)| *c != ' ') {
+                    return Err(combinator_error(c, offset));
+                }
+                match (c, not_start) {
+                    (',', Some(_)) => {
+                        return Err(SelectorParseError::new(
+                            "Multiple selectors in :not are not supported",
+                            offset,
+                        ))
+                    }
+                    (',', None) if !has_content => {
+                        return Err(SelectorParseError::new("Expected a selector", list_start))
+                    }
+                    (',', None) => {
+                        has_content = false;
+                        list_start = offset + 1;
+                    }
+                    (_, Some(start)) if !has_content => {
+                        return Err(SelectorParseError::new("Empty ':not()' selector", start))
+                    }
+                    (_, Some(_)) => not_start = None,
+                    (_, None) => {
+                        return Err(SelectorParseError::new("Unexpected ')'", offset));
+                    }
+                }
+            }
+            _ => {
+                if let Some((offset, c)) = combinator.take() {
+                    return Err(combinator_error(c, offset));
+                }
+                match c {
+                    ':' if selector[offset..].starts_with(":not(") => {
+                        if not_start.is_some() {
+                            return Err(SelectorParseError::new(
+                                "Nesting :not in a selector is not allowed",
+                                offset,
+                            ));
+                        }
+                        not_start = Some(offset);
+                        has_content = false;
+                        for _ in 0.."not(".len() {
+                            chars.next();
+                        }
+                        continue;
+                    }
+                    ':' => {
+                        return Err(SelectorParseError::new(
+                            "Only the ':not(...)' pseudo-class is supported",
+                            offset,
+                        ))
+                    }
+                    '[' => check_attribute(selector, offset, &mut chars)?,
+                    _ => {}
+                }
+                has_content = true;
+            }
+        }
+    }
+
+    if let Some((offset, _)) = combinator.filter(|(_, c)| *c != ' ') {
+        return Err(SelectorParseError::new(
+            "Selector ends with a trailing combinator",
+            offset,
+        ));
+    }
+    if let Some(start) = not_start {
+        return Err(SelectorParseError::new("Unterminated ':not('", start));
+    }
+    if !has_content {
+        return Err(SelectorParseError::new("Expected a selector", list_start));
+    }
+    Ok(())
+}
+
+/// Consume the attribute selector starting at `start` and check that it is terminated and
+/// compares with `=`. `*` is part of the name, like in the matcher.
+fn check_attribute(
+    selector: &str,
+    start: usize,
+    chars: &mut std::str::CharIndices<'_>,
+) -> Result<(), SelectorParseError> {
+    let mut in_name = true;
+    let mut quote: Option<char> = None;
+    let mut previous = '[';
+    for (offset, c) in chars.by_ref() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') if !in_name => quote = Some(c),
+            (None, ']') => return Ok(()),
+            (None, '=') if in_name => {
+                if matches!(previous, '~' | '|' | '^' | '$')
+                    && !selector[..offset - 1].ends_with('\\')
+                {
+                    return Err(SelectorParseError::new(
+                        format!(
+                            "Attribute operator '{}=' is not supported in directive selectors",
+                            previous
+                        ),
+                        offset - 1,
+                    ));
+                }
+                in_name = false;
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+    Err(SelectorParseError::new(
+        "Unterminated attribute selector",
+        start,
+    ))
+}
+
+fn combinator_error(combinator: char, offset: usize) -> SelectorParseError {
+    SelectorParseError::new(
+        format!(
+            "Combinator '{}' is not supported in directive selectors",
+            combinator
+        ),
+        offset,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_selector_list() {
+        let selectors = parse_css_selector("app-root, [appX], .c:not(.d)").unwrap();
+        assert_eq!(selectors.len(), 3);
+
+        assert_eq!(selectors[0].element.as_deref(), Some("app-root"));
+        assert!(selectors[0].attributes.is_empty());
+
+        assert_eq!(selectors[1].element, None);
+        assert_eq!(
+            selectors[1].attributes,
+            vec![AttributeSelector {
+                name: "appX".to_string(),
+                value: None
+            }]
+        );
+
+        assert_eq!(selectors[2].classes, vec!["c".to_string()]);
+        assert_eq!(selectors[2].negations.len(), 1);
+        assert_eq!(selectors[2].negations[0].classes, vec!["d".to_string()]);
+    }
+
+    #[test]
+    fn should_parse_attribute_values_and_ids() {
+        let selectors = parse_css_selector("input[type=\"text\"][lang=en]#main").unwrap();
+        let attrs = &selectors[0].attributes;
+        assert_eq!(
+            attrs[0].value,
+            Some((AttributeOperator::Equals, "text".to_string()))
+        );
+        assert_eq!(attrs[1].name, "lang");
+        assert_eq!(
+            attrs[1].value,
+            Some((AttributeOperator::Equals, "en".to_string()))
+        );
+        assert_eq!(attrs[2].name, "id");
+        assert_eq!(
+            attrs[2].value,
+            Some((AttributeOperator::Equals, "main".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_reject_trailing_combinators() {
+        let err = parse_css_selector("app-root >").unwrap_err();
+        assert_eq!(err.message, "Selector ends with a trailing combinator");
+        assert_eq!(err.offset, 9);
+
+        assert!(parse_css_selector("app-root,").is_err());
+        assert!(parse_css_selector("div span").is_err());
+    }
+
+    #[test]
+    fn should_reject_malformed_selectors() {
+        assert!(parse_css_selector("").is_err());
+        assert!(parse_css_selector("[foo").is_err());
+        assert!(parse_css_selector(":not(:not(.a))").is_err());
+        assert!(parse_css_selector(".a:not(.b, .c)").is_err());
+        assert!(parse_css_selector("a:hover").is_err());
+        assert!(parse_css_selector("[lang|=en]").is_err());
+        assert!(parse_css_selector("[a$b]").is_err());
+    }
+}
@@ -3,6 +3,9 @@
 // Responsible for computing compilation scope for standalone components.
 
 use super::api::{CompilationScope, DirectiveInScope, PipeInScope};
+use crate::ngtsc::core::Diagnostic;
+use crate::ngtsc::diagnostics::ErrorCode;
+use crate::transformers::api::DiagnosticCategory;
 use std::collections::{HashMap, HashSet};
 
 /// Registry for standalone component scopes.
@@ -11,6 +14,10 @@ pub struct StandaloneComponentScopeReader {
     scope_cache: HashMap<String, CompilationScope>,
     /// Components with errors.
     poisoned_components: HashSet<String>,
+    /// The NgModule declaring each non-standalone declaration, and whether it exports it.
+    declaring_modules: HashMap<String, (String, bool)>,
+    /// Diagnostics for invalid imports.
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl StandaloneComponentScopeReader {
@@ -18,6 +25,8 @@ impl StandaloneComponentScopeReader {
         Self {
             scope_cache: HashMap::new(),
             poisoned_components: HashSet::new(),
+            declaring_modules: HashMap::new(),
+            diagnostics: Vec::new(),
         }
     }
 
@@ -29,7 +38,27 @@ impl StandaloneComponentScopeReader {
         self.scope_cache.get(component_ref)
     }
 
+    /// Register the declarations and exports of an NgModule, so that importing one of its
+    /// declarations directly can point to the module instead.
+    pub fn register_ng_module(
+        &mut self,
+        module_ref: impl Into<String>,
+        declarations: &[String],
+        exports: &[String],
+    ) {
+        let module = module_ref.into();
+        for declaration in declarations {
+            self.declaring_modules.insert(
+                declaration.clone(),
+                (module.clone(), exports.contains(declaration)),
+            );
+        }
+    }
+
     /// Register a standalone component's imports.
+    ///
+    /// Directives and pipes which are not standalone can't be imported directly, so they poison
+    /// the component and are reported. NgModules are always valid imports.
     pub fn register_standalone_component(
         &mut self,
         component_ref: impl Into<String>,
@@ -45,21 +74,37 @@ impl StandaloneComponentScopeReader {
                     name,
                     selector,
                     is_component,
+                    is_standalone,
                 } => {
+                    if !is_standalone {
+                        let kind = if is_component {
+                            "component"
+                        } else {
+                            "directive"
+                        };
+                        self.report_not_standalone(&component, &name, kind);
+                    }
                     scope.directives.push(DirectiveInScope {
                         directive: name,
                         selector,
                         has_inputs: false,
                         has_outputs: false,
                         is_component,
-                        is_standalone: true,
+                        is_standalone,
                     });
                 }
-                StandaloneImport::Pipe { name, pipe_name } => {
+                StandaloneImport::Pipe {
+                    name,
+                    pipe_name,
+                    is_standalone,
+                } => {
+                    if !is_standalone {
+                        self.report_not_standalone(&component, &name, "pipe");
+                    }
                     scope.pipes.push(PipeInScope {
                         pipe: name,
                         name: pipe_name,
-                        is_standalone: true,
+                        is_standalone,
                     });
                 }
                 StandaloneImport::Module { name: _ } => {
@@ -68,6 +113,7 @@ impl StandaloneComponentScopeReader {
             }
         }
 
+        scope.is_poisoned = self.poisoned_components.contains(&component);
         self.scope_cache.insert(component, scope);
     }
 
@@ -76,6 +122,40 @@ impl StandaloneComponentScopeReader {
         self.poisoned_components.contains(component_ref)
     }
 
+    /// Get all diagnostics for invalid imports.
+    pub fn get_diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn report_not_standalone(&mut self, component_ref: &str, import: &str, kind: &str) {
+        let mut message = format!(
+            "The {} '{}' appears in 'imports', but is not standalone and cannot be imported directly.",
+            kind, import
+        );
+        match self.declaring_modules.get(import) {
+            Some((module, true)) => message.push_str(&format!(
+                " It can be imported using its '{}' NgModule instead.",
+                module
+            )),
+            Some((module, false)) => message.push_str(&format!(
+                " It's declared in the '{}' NgModule, but is not exported. Consider exporting it and importing the NgModule instead.",
+                module
+            )),
+            None => message.push_str(" It must be imported via an NgModule."),
+        }
+
+        self.poisoned_components.insert(component_ref.to_string());
+        self.diagnostics.push(Diagnostic {
+            category: DiagnosticCategory::Error,
+            file: None,
+            message,
+            code: ErrorCode::ComponentImportNotStandalone as usize,
+            start: None,
+            length: None,
+            related_information: Vec::new(),
+        });
+    }
+
     fn compute_scope_for_component(&mut self, component_ref: &str) {
         // If not pre-registered, create empty scope
         if !self.scope_cache.contains_key(component_ref) {
@@ -105,9 +185,14 @@ pub enum StandaloneImport {
         name: String,
         selector: String,
         is_component: bool,
+        is_standalone: bool,
     },
     /// A pipe import.
-    Pipe { name: String, pipe_name: String },
+    Pipe {
+        name: String,
+        pipe_name: String,
+        is_standalone: bool,
+    },
     /// An NgModule import.
     Module { name: String },
 }
@@ -118,3 +203,101 @@ pub struct RemoteScope {
     pub used_directives: Vec<String>,
     pub used_pipes: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directive(name: &str, is_standalone: bool) -> StandaloneImport {
+        StandaloneImport::Directive {
+            name: name.to_string(),
+            selector: "[dir]".to_string(),
+            is_component: false,
+            is_standalone,
+        }
+    }
+
+    #[test]
+    fn test_importing_non_standalone_directive_is_an_error() {
+        let mut reader = StandaloneComponentScopeReader::new();
+        reader.register_ng_module(
+            "DirModule",
+            &["LegacyDir".to_string()],
+            &["LegacyDir".to_string()],
+        );
+        reader.register_standalone_component("AppCmp", vec![directive("LegacyDir", false)]);
+
+        let diagnostics = reader.get_diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].code,
+            ErrorCode::ComponentImportNotStandalone as usize
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "The directive 'LegacyDir' appears in 'imports', but is not standalone and cannot be imported directly. It can be imported using its 'DirModule' NgModule instead."
+        );
+        assert!(reader.is_poisoned("AppCmp"));
+        assert!(
+            reader
+                .get_scope_for_component("AppCmp")
+                .unwrap()
+                .is_poisoned
+        );
+    }
+
+    #[test]
+    fn test_importing_unexported_or_undeclared_directive() {
+        let mut reader = StandaloneComponentScopeReader::new();
+        reader.register_ng_module("DirModule", &["InternalDir".to_string()], &[]);
+        reader.register_standalone_component(
+            "AppCmp",
+            vec![
+                directive("InternalDir", false),
+                StandaloneImport::Pipe {
+                    name: "LegacyPipe".to_string(),
+                    pipe_name: "legacy".to_string(),
+                    is_standalone: false,
+                },
+            ],
+        );
+
+        let messages: Vec<&str> = reader
+            .get_diagnostics()
+            .iter()
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "The directive 'InternalDir' appears in 'imports', but is not standalone and cannot be imported directly. It's declared in the 'DirModule' NgModule, but is not exported. Consider exporting it and importing the NgModule instead.",
+                "The pipe 'LegacyPipe' appears in 'imports', but is not standalone and cannot be imported directly. It must be imported via an NgModule.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_importing_standalone_directive_or_ng_module_is_valid() {
+        let mut reader = StandaloneComponentScopeReader::new();
+        reader.register_ng_module(
+            "DirModule",
+            &["LegacyDir".to_string()],
+            &["LegacyDir".to_string()],
+        );
+        reader.register_standalone_component(
+            "AppCmp",
+            vec![
+                directive("StandaloneDir", true),
+                StandaloneImport::Module {
+                    name: "DirModule".to_string(),
+                },
+            ],
+        );
+
+        assert!(reader.get_diagnostics().is_empty());
+        assert!(!reader.is_poisoned("AppCmp"));
+        let scope = reader.get_scope_for_component("AppCmp").unwrap();
+        assert!(!scope.is_poisoned);
+        assert_eq!(scope.directives[0].directive, "StandaloneDir");
+    }
+}
@@ -1,6 +1,8 @@
 // Shim Adapter
 //
-// Generates shim files for type-checking.
+// Generates the `.ngfactory.ts` and `.ngsummary.ts` shims of source files.
+
+use super::util::get_shim_file_name;
 
 /// Shim type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -27,27 +29,71 @@ impl ShimGenerator {
     }
 
     pub fn generate(&self, original_file: &str, shim_type: ShimType) -> ShimFile {
+        match shim_type {
+            ShimType::Factory => self.generate_factory(original_file, &[]),
+            ShimType::Summary | ShimType::Ngsummary => self.generate_summary(original_file, &[]),
+        }
+    }
+
+    /// Generate the `.ngfactory.ts` shim of `original_file`, exporting an `NgModuleFactory` for
+    /// each of the `ng_modules` it declares.
+    pub fn generate_factory(&self, original_file: &str, ng_modules: &[String]) -> ShimFile {
+        let mut lines = Vec::new();
+        if !ng_modules.is_empty() {
+            lines.push("import * as i0 from '@angular/core';".to_string());
+            lines.push(format!(
+                "import {{{}}} from './{}';",
+                ng_modules.join(", "),
+                module_name(original_file)
+            ));
+            lines.extend(ng_modules.iter().map(|name| {
+                format!(
+                    "export const {0}NgFactory: i0.ɵNgModuleFactory<any> = new i0.ɵNgModuleFactory({0});",
+                    name
+                )
+            }));
+        }
+        // Every shim must be a module, even if the file declares no NgModule.
+        lines.push("export const ɵNonEmptyModule = true;".to_string());
+
+        self.shim_file(original_file, ShimType::Factory, lines)
+    }
+
+    /// Generate the `.ngsummary.ts` shim of `original_file`, with a placeholder summary for each
+    /// of its `exported_classes`.
+    pub fn generate_summary(&self, original_file: &str, exported_classes: &[String]) -> ShimFile {
+        let mut lines: Vec<String> = exported_classes
+            .iter()
+            .map(|name| format!("export const {}NgSummary: any = null;", name))
+            .collect();
+        if lines.is_empty() {
+            lines.push("export const ɵempty = null;".to_string());
+        }
+
+        self.shim_file(original_file, ShimType::Summary, lines)
+    }
+
+    fn shim_file(&self, original_file: &str, shim_type: ShimType, lines: Vec<String>) -> ShimFile {
         let suffix = match shim_type {
             ShimType::Factory => ".ngfactory",
-            ShimType::Summary => ".ngsummary",
-            ShimType::Ngsummary => ".ngsummary",
+            ShimType::Summary | ShimType::Ngsummary => ".ngsummary",
         };
-
-        let file_name = original_file.replace(".ts", &format!("{}.ts", suffix));
+        let mut content = self.base_content.clone();
+        content.push_str(&lines.join("\n"));
+        content.push('\n');
 
         ShimFile {
-            file_name,
-            content: self.generate_content(original_file, shim_type),
+            file_name: get_shim_file_name(original_file, suffix),
+            content,
             shim_type,
         }
     }
+}
 
-    fn generate_content(&self, _original: &str, shim_type: ShimType) -> String {
-        match shim_type {
-            ShimType::Factory => "// Factory shim\nexport {};\n".to_string(),
-            ShimType::Summary | ShimType::Ngsummary => "// Summary shim\nexport {};\n".to_string(),
-        }
-    }
+/// The name `original_file` is imported by from a file in the same directory.
+fn module_name(original_file: &str) -> &str {
+    let file_name = original_file.rsplit('/').next().unwrap_or(original_file);
+    file_name.strip_suffix(".ts").unwrap_or(file_name)
 }
 
 impl Default for ShimGenerator {
@@ -7,3 +7,4 @@ pub mod util;
 
 pub use adapter::*;
 pub use reference_tagger::*;
+pub use util::*;
@@ -2,6 +2,11 @@
 //
 // Utility functions for shims.
 
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Declaration, Expression, Statement};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
 /// Check if a file is a shim.
 pub fn is_shim(file_path: &str) -> bool {
     file_path.contains(".ngfactory") || file_path.contains(".ngsummary")
@@ -22,3 +27,53 @@ pub fn get_original_file(shim_path: &str) -> Option<String> {
 pub fn get_shim_file_name(original: &str, suffix: &str) -> String {
     original.replace(".ts", &format!("{}.ts", suffix))
 }
+
+/// A class exported by name from a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedClass {
+    pub name: String,
+    /// Names of the decorators of the class, e.g. `NgModule`.
+    pub decorators: Vec<String>,
+}
+
+impl ExportedClass {
+    pub fn is_ng_module(&self) -> bool {
+        self.decorators.iter().any(|d| d == "NgModule")
+    }
+}
+
+/// The classes exported by name from the TypeScript `source`, which the shims of the file
+/// refer to.
+pub fn get_exported_classes(source: &str) -> Vec<ExportedClass> {
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, source, SourceType::ts()).parse();
+
+    parsed
+        .program
+        .body
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::ClassDeclaration(class)) => Some(class),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter_map(|class| {
+            let name = class.id.as_ref()?.name.to_string();
+            let decorators = class
+                .decorators
+                .iter()
+                .filter_map(|decorator| match &decorator.expression {
+                    Expression::CallExpression(call) => match &call.callee {
+                        Expression::Identifier(id) => Some(id.name.to_string()),
+                        _ => None,
+                    },
+                    Expression::Identifier(id) => Some(id.name.to_string()),
+                    _ => None,
+                })
+                .collect();
+            Some(ExportedClass { name, decorators })
+        })
+        .collect()
+}
@@ -31,6 +31,56 @@ mod tests {
 
             assert_eq!(shim.shim_type, ShimType::Summary);
         }
+
+        #[test]
+        fn should_generate_factory_for_each_ng_module() {
+            let gen = ShimGenerator::new();
+            let shim = gen.generate_factory(
+                "/app/app.module.ts",
+                &["AppModule".to_string(), "SharedModule".to_string()],
+            );
+
+            assert_eq!(shim.file_name, "/app/app.module.ngfactory.ts");
+            assert_eq!(
+                shim.content,
+                "import * as i0 from '@angular/core';\n\
+                 import {AppModule, SharedModule} from './app.module';\n\
+                 export const AppModuleNgFactory: i0.ɵNgModuleFactory<any> = new i0.ɵNgModuleFactory(AppModule);\n\
+                 export const SharedModuleNgFactory: i0.ɵNgModuleFactory<any> = new i0.ɵNgModuleFactory(SharedModule);\n\
+                 export const ɵNonEmptyModule = true;\n"
+            );
+        }
+
+        #[test]
+        fn should_generate_summary_for_each_exported_class() {
+            let gen = ShimGenerator::new();
+
+            let shim = gen.generate_summary("/app/cmp.ts", &["Cmp".to_string()]);
+            assert_eq!(shim.file_name, "/app/cmp.ngsummary.ts");
+            assert_eq!(shim.content, "export const CmpNgSummary: any = null;\n");
+
+            let empty = gen.generate_summary("/app/util.ts", &[]);
+            assert_eq!(empty.content, "export const ɵempty = null;\n");
+        }
+    }
+
+    mod exported_classes_tests {
+        use super::*;
+
+        #[test]
+        fn should_find_exported_classes_and_their_decorators() {
+            let classes = get_exported_classes(
+                "@NgModule({}) export class AppModule {}\n\
+                 export class Helper {}\n\
+                 class Internal {}",
+            );
+
+            assert_eq!(classes.len(), 2);
+            assert_eq!(classes[0].name, "AppModule");
+            assert!(classes[0].is_ng_module());
+            assert_eq!(classes[1].name, "Helper");
+            assert!(!classes[1].is_ng_module());
+        }
     }
 
     mod shim_file_tests {
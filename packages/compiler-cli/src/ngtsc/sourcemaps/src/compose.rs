@@ -0,0 +1,119 @@
+// Source Map Composition
+//
+// Flattens two-stage source maps, e.g. template -> generated TS -> emitted JS.
+
+use super::raw_source_map::{decode_mappings, encode_mappings, MappingSegment, SourceMap};
+
+/// Compose two source maps into one that maps the outer map's generated file straight to the
+/// inner map's sources.
+///
+/// `outer` maps the emitted file to intermediate files, and `inner` maps the intermediate file
+/// named `inner.file` to the original sources. Segments of `outer` that point into `inner.file`
+/// are traced through `inner`; segments pointing at other sources are kept as they are.
+pub fn compose(outer: &SourceMap, inner: &SourceMap) -> Result<SourceMap, String> {
+    let outer_lines = decode_mappings(&outer.mappings)?;
+    let inner_lines = decode_mappings(&inner.mappings)?;
+
+    let mut result = SourceMap::new(outer.file.clone());
+    result.source_root = outer.source_root.clone();
+    let mut sources_content = Vec::new();
+
+    let mut lines = Vec::with_capacity(outer_lines.len());
+    for segments in &outer_lines {
+        let mut composed = Vec::with_capacity(segments.len());
+        for segment in segments {
+            let Some((source, line, column)) = segment.original else {
+                composed.push(*segment);
+                continue;
+            };
+
+            if outer.sources.get(source as usize) != Some(&inner.file) {
+                let source = add_source(&mut result, &mut sources_content, outer, source);
+                let name = segment
+                    .name
+                    .map(|name| add_name(&mut result, &outer.names[name as usize]));
+                composed.push(MappingSegment {
+                    generated_column: segment.generated_column,
+                    original: Some((source, line, column)),
+                    name,
+                });
+                continue;
+            }
+
+            // Find the last inner segment at or before the intermediate position, and shift its
+            // original column by how far past that segment the position is.
+            let traced = inner_lines.get(line as usize).and_then(|inner_segments| {
+                inner_segments
+                    .iter()
+                    .rev()
+                    .find(|inner_seg| inner_seg.generated_column <= column)
+            });
+            match traced {
+                Some(MappingSegment {
+                    generated_column,
+                    original: Some((inner_source, inner_line, inner_column)),
+                    name: inner_name,
+                }) => {
+                    let source =
+                        add_source(&mut result, &mut sources_content, inner, *inner_source);
+                    let name = inner_name
+                        .map(|name| add_name(&mut result, &inner.names[name as usize]))
+                        .or_else(|| {
+                            segment
+                                .name
+                                .map(|name| add_name(&mut result, &outer.names[name as usize]))
+                        });
+                    composed.push(MappingSegment {
+                        generated_column: segment.generated_column,
+                        original: Some((
+                            source,
+                            *inner_line,
+                            inner_column + (column - generated_column),
+                        )),
+                        name,
+                    });
+                }
+                // The intermediate position has no original; neither has the composed one.
+                _ => composed.push(MappingSegment {
+                    generated_column: segment.generated_column,
+                    original: None,
+                    name: None,
+                }),
+            }
+        }
+        lines.push(composed);
+    }
+
+    result.mappings = encode_mappings(&lines);
+    if sources_content.iter().any(Option::is_some) {
+        result.sources_content = Some(sources_content);
+    }
+    Ok(result)
+}
+
+fn add_source(
+    result: &mut SourceMap,
+    sources_content: &mut Vec<Option<String>>,
+    map: &SourceMap,
+    source: u32,
+) -> u32 {
+    let name = &map.sources[source as usize];
+    if let Some(idx) = result.sources.iter().position(|s| s == name) {
+        return idx as u32;
+    }
+    result.sources.push(name.clone());
+    sources_content.push(
+        map.sources_content
+            .as_ref()
+            .and_then(|contents| contents.get(source as usize).cloned().flatten()),
+    );
+    (result.sources.len() - 1) as u32
+}
+
+fn add_name(result: &mut SourceMap, name: &str) -> u32 {
+    if let Some(idx) = result.names.iter().position(|n| n == name) {
+        return idx as u32;
+    }
+    result.names.push(name.to_string());
+    (result.names.len() - 1) as u32
+}
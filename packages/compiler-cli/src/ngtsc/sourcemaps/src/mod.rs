@@ -1,11 +1,13 @@
 //! Sourcemaps Source
 
+pub mod compose;
 pub mod content_origin;
 pub mod raw_source_map;
 pub mod segment_marker;
 pub mod source_file;
 pub mod source_file_loader;
 
+pub use compose::compose;
 pub use raw_source_map::*;
 pub use segment_marker::*;
 pub use source_file::*;
@@ -62,3 +62,111 @@ pub fn encode_vlq(mut value: i32) -> String {
 
     result
 }
+
+/// Decode one VLQ value from the start of `chars`, returning it and the number of chars read.
+pub fn decode_vlq(chars: &[u8]) -> Option<(i32, usize)> {
+    let mut result: i32 = 0;
+    let mut shift = 0;
+    for (idx, &ch) in chars.iter().enumerate() {
+        let digit = match ch {
+            b'A'..=b'Z' => ch - b'A',
+            b'a'..=b'z' => ch - b'a' + 26,
+            b'0'..=b'9' => ch - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        } as i32;
+        result |= (digit & 0x1F) << shift;
+        if digit & 0x20 == 0 {
+            let value = result >> 1;
+            let value = if result & 1 == 1 { -value } else { value };
+            return Some((value, idx + 1));
+        }
+        shift += 5;
+    }
+    None
+}
+
+/// A decoded mapping segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MappingSegment {
+    /// Column in the generated line.
+    pub generated_column: u32,
+    /// Source index, line and column in the original file, if the segment has one.
+    pub original: Option<(u32, u32, u32)>,
+    /// Index into `names`.
+    pub name: Option<u32>,
+}
+
+/// Decode a `mappings` string into its segments, one `Vec` per generated line.
+pub fn decode_mappings(mappings: &str) -> Result<Vec<Vec<MappingSegment>>, String> {
+    let mut lines = Vec::new();
+    let (mut source, mut line, mut column, mut name) = (0i64, 0i64, 0i64, 0i64);
+    for encoded_line in mappings.split(';') {
+        let mut segments = Vec::new();
+        let mut generated_column = 0i64;
+        for encoded in encoded_line.split(',').filter(|s| !s.is_empty()) {
+            let mut fields = Vec::new();
+            let mut rest = encoded.as_bytes();
+            while !rest.is_empty() {
+                let (value, len) =
+                    decode_vlq(rest).ok_or_else(|| format!("Invalid VLQ segment '{}'", encoded))?;
+                fields.push(value as i64);
+                rest = &rest[len..];
+            }
+            generated_column += fields[0];
+            let mut segment = MappingSegment {
+                generated_column: generated_column as u32,
+                original: None,
+                name: None,
+            };
+            if fields.len() >= 4 {
+                source += fields[1];
+                line += fields[2];
+                column += fields[3];
+                segment.original = Some((source as u32, line as u32, column as u32));
+            }
+            if fields.len() >= 5 {
+                name += fields[4];
+                segment.name = Some(name as u32);
+            }
+            segments.push(segment);
+        }
+        lines.push(segments);
+    }
+    Ok(lines)
+}
+
+/// Encode segments, one `Vec` per generated line, into a `mappings` string.
+pub fn encode_mappings(lines: &[Vec<MappingSegment>]) -> String {
+    let (mut source, mut line, mut column, mut name) = (0i64, 0i64, 0i64, 0i64);
+    let mut result = String::new();
+    for (idx, segments) in lines.iter().enumerate() {
+        if idx > 0 {
+            result.push(';');
+        }
+        let mut generated_column = 0i64;
+        for (seg_idx, segment) in segments.iter().enumerate() {
+            if seg_idx > 0 {
+                result.push(',');
+            }
+            result.push_str(&encode_vlq(
+                (segment.generated_column as i64 - generated_column) as i32,
+            ));
+            generated_column = segment.generated_column as i64;
+            if let Some((src, src_line, src_col)) = segment.original {
+                result.push_str(&encode_vlq((src as i64 - source) as i32));
+                result.push_str(&encode_vlq((src_line as i64 - line) as i32));
+                result.push_str(&encode_vlq((src_col as i64 - column) as i32));
+                source = src as i64;
+                line = src_line as i64;
+                column = src_col as i64;
+                if let Some(name_idx) = segment.name {
+                    result.push_str(&encode_vlq((name_idx as i64 - name) as i32));
+                    name = name_idx as i64;
+                }
+            }
+        }
+    }
+    result
+}
@@ -67,4 +67,84 @@ mod tests {
             assert_eq!(idx, 0);
         }
     }
+
+    mod mappings_tests {
+        use super::*;
+
+        #[test]
+        fn should_round_trip_mappings() {
+            let mappings = "AAAA,IAAIA;;EACE,SAAS";
+            let decoded = decode_mappings(mappings).unwrap();
+            assert_eq!(decoded.len(), 3);
+            assert_eq!(decoded[0][1].name, Some(0));
+            assert!(decoded[1].is_empty());
+            assert_eq!(
+                decoded[2][1],
+                MappingSegment {
+                    generated_column: 11,
+                    original: Some((0, 1, 15)),
+                    name: None,
+                }
+            );
+            assert_eq!(encode_mappings(&decoded), mappings);
+        }
+    }
+
+    mod compose_tests {
+        use super::*;
+
+        fn map(file: &str, sources: &[&str], lines: Vec<Vec<MappingSegment>>) -> SourceMap {
+            let mut map = SourceMap::new(file);
+            map.sources = sources.iter().map(|s| s.to_string()).collect();
+            map.mappings = encode_mappings(&lines);
+            map
+        }
+
+        fn segment(generated_column: u32, original: (u32, u32, u32)) -> MappingSegment {
+            MappingSegment {
+                generated_column,
+                original: Some(original),
+                name: None,
+            }
+        }
+
+        #[test]
+        fn should_map_generated_positions_through_both_stages() {
+            // app.html -> app.ts: line 0, column 10 of the TS comes from line 2, column 4
+            // of the template.
+            let inner = map("app.ts", &["app.html"], vec![vec![segment(10, (0, 2, 4))]]);
+            // app.ts -> app.js: line 1, column 3 of the JS comes from line 0, column 12 of the TS.
+            let outer = map(
+                "app.js",
+                &["app.ts", "helpers.ts"],
+                vec![vec![segment(0, (1, 5, 0))], vec![segment(3, (0, 0, 12))]],
+            );
+
+            let composed = compose(&outer, &inner).unwrap();
+            assert_eq!(composed.file, "app.js");
+            assert_eq!(composed.sources, vec!["helpers.ts", "app.html"]);
+
+            let lines = decode_mappings(&composed.mappings).unwrap();
+            // Unrelated sources are kept as they are.
+            assert_eq!(lines[0], vec![segment(0, (0, 5, 0))]);
+            // Two columns past the inner segment in the TS is two columns past it in the
+            // template.
+            assert_eq!(lines[1], vec![segment(3, (1, 2, 6))]);
+        }
+
+        #[test]
+        fn should_drop_originals_that_the_inner_map_does_not_cover() {
+            let inner = map(
+                "app.ts",
+                &["app.html"],
+                vec![vec![], vec![segment(4, (0, 0, 0))]],
+            );
+            let outer = map("app.js", &["app.ts"], vec![vec![segment(0, (0, 0, 2))]]);
+
+            let composed = compose(&outer, &inner).unwrap();
+            let lines = decode_mappings(&composed.mappings).unwrap();
+            assert_eq!(lines[0][0].original, None);
+            assert!(composed.sources.is_empty());
+        }
+    }
 }
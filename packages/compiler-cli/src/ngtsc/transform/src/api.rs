@@ -7,18 +7,23 @@ use crate::ngtsc::reflection::ClassDeclaration;
 use std::collections::HashSet;
 use ts::Diagnostic;
 
+pub use crate::ngtsc::imports::Reexport;
+
 // ============================================================================
 // Placeholder types - to be replaced with actual implementations
 // ============================================================================
 
-/// Placeholder for ConstantPool from @angular/compiler
+/// Constant pool shared by all classes compiled into the same output file, so identical
+/// constants (e.g. `_c0`) are only hoisted once.
 pub struct ConstantPool {
-    // TODO: Implement constant pool for expression deduplication
+    pub pool: angular_compiler::constant_pool::ConstantPool,
 }
 
 impl ConstantPool {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            pool: angular_compiler::constant_pool::ConstantPool::new(false),
+        }
     }
 }
 
@@ -55,13 +60,6 @@ pub struct ImportManager;
 /// Placeholder for ReflectionHost
 pub trait ReflectionHost {}
 
-/// Placeholder for Reexport
-pub struct Reexport {
-    pub symbol_name: String,
-    pub as_alias: String,
-    pub module_name: String,
-}
-
 // ============================================================================
 // Core Enums
 // ============================================================================
@@ -4,8 +4,13 @@
 // to add static field declarations with types.
 
 use crate::ngtsc::transform::src::api::{
-    DtsTransform, ImportManager, ReferenceEmitter, ReflectionHost,
+    CompileResult, DtsTransform, ImportManager, ReferenceEmitter, ReflectionHost,
 };
+use crate::ngtsc::translator::src::type_translator::CORE_NAMESPACE;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Declaration, ExportDefaultDeclarationKind, Statement};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType};
 use std::collections::HashMap;
 
 // ============================================================================
@@ -99,6 +104,12 @@ impl IvyDeclarationField {
     }
 }
 
+impl From<&CompileResult> for IvyDeclarationField {
+    fn from(result: &CompileResult) -> Self {
+        Self::new(result.name.clone(), result.type_desc.clone())
+    }
+}
+
 // ============================================================================
 // Ivy Declaration DTS Transform
 // ============================================================================
@@ -135,6 +146,71 @@ impl IvyDeclarationDtsTransform {
     pub fn has_fields(&self) -> bool {
         !self.declaration_fields.is_empty()
     }
+
+    /// Add the registered fields to the top-level classes of a `.d.ts` file, e.g.
+    /// `static ɵdir: i0.ɵɵDirectiveDeclaration<...>;`, importing `@angular/core` as
+    /// `i0` when the field types need it. Returns the source unchanged if it cannot be parsed.
+    pub fn transform_declaration_file(&self, source: &str) -> String {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::d_ts()).parse();
+        if !ret.errors.is_empty() {
+            return source.to_string();
+        }
+
+        let mut insertions: Vec<(usize, String)> = Vec::new();
+        for stmt in &ret.program.body {
+            let class = match stmt {
+                Statement::ClassDeclaration(class) => class,
+                Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                    Some(Declaration::ClassDeclaration(class)) => class,
+                    _ => continue,
+                },
+                Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                    ExportDefaultDeclarationKind::ClassDeclaration(class) => class,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let Some(fields) = class
+                .id
+                .as_ref()
+                .and_then(|id| self.declaration_fields.get(id.name.as_str()))
+            else {
+                continue;
+            };
+
+            let members: String = fields
+                .iter()
+                .map(|field| format!("    static {}: {};\n", field.name, field.type_str))
+                .collect();
+            // Insert before the closing brace of the class body.
+            insertions.push((class.body.span().end as usize - 1, members));
+        }
+
+        if insertions.is_empty() {
+            return source.to_string();
+        }
+
+        let mut output = source.to_string();
+        for (offset, members) in insertions.iter().rev() {
+            let line_start = output[..*offset].rfind('\n').map_or(0, |i| i + 1);
+            if output[line_start..*offset].trim().is_empty() {
+                output.insert_str(line_start, members);
+            } else {
+                output.insert_str(*offset, &format!("\n{}", members));
+            }
+        }
+
+        let namespace_prefix = format!("{}.", CORE_NAMESPACE);
+        let needs_core_import = insertions
+            .iter()
+            .any(|(_, members)| members.contains(&namespace_prefix));
+        let core_import = format!("import * as {} from \"@angular/core\";\n", CORE_NAMESPACE);
+        if needs_core_import && !output.contains(core_import.trim_end()) {
+            output.insert_str(0, &core_import);
+        }
+        output
+    }
 }
 
 impl Default for IvyDeclarationDtsTransform {
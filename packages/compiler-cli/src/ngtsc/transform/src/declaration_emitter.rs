@@ -0,0 +1,325 @@
+// Declaration Emitter - Generates .d.ts files from TypeScript sources
+//
+// A declaration-only emit in the spirit of TypeScript's `isolatedDeclarations`: the declared
+// types are copied from the source text, and types that would need inference fall back to
+// `any`. Ivy fields are added afterwards by `IvyDeclarationDtsTransform`.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::*;
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+
+/// Generate the `.d.ts` contents for a TypeScript source file, or `None` if it does not parse.
+///
+/// Imports, re-exports and exported declarations are emitted; decorators and function bodies
+/// are dropped.
+pub fn emit_declaration_file(source: &str) -> Option<String> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+    if !ret.errors.is_empty() {
+        return None;
+    }
+
+    let emitter = DeclarationEmitter { source };
+    let mut output = String::new();
+    for stmt in &ret.program.body {
+        let declaration = match stmt {
+            Statement::ImportDeclaration(_)
+            | Statement::ExportAllDeclaration(_)
+            | Statement::TSExportAssignment(_) => Some(emitter.text(stmt.span()).to_string()),
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(decl) => emitter
+                    .declaration(decl)
+                    .map(|decl| format!("export {}", decl)),
+                None => Some(emitter.text(export.span).to_string()),
+            },
+            Statement::ExportDefaultDeclaration(export) => match &export.declaration {
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    Some(format!("export default {}", emitter.class(class)))
+                }
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    Some(format!("export default {}", emitter.function(func)))
+                }
+                ExportDefaultDeclarationKind::TSInterfaceDeclaration(decl) => {
+                    Some(format!("export default {}", emitter.text(decl.span)))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(declaration) = declaration {
+            output.push_str(&declaration);
+            output.push('\n');
+        }
+    }
+    Some(output)
+}
+
+struct DeclarationEmitter<'s> {
+    source: &'s str,
+}
+
+impl<'s> DeclarationEmitter<'s> {
+    fn text(&self, span: Span) -> &'s str {
+        &self.source[span.start as usize..span.end as usize]
+    }
+
+    fn declaration(&self, decl: &Declaration) -> Option<String> {
+        match decl {
+            Declaration::ClassDeclaration(class) => Some(format!("declare {}", self.class(class))),
+            Declaration::FunctionDeclaration(func) => {
+                Some(format!("declare {}", self.function(func)))
+            }
+            Declaration::VariableDeclaration(var) => Some(self.variable(var)),
+            Declaration::TSInterfaceDeclaration(decl) => Some(self.text(decl.span).to_string()),
+            Declaration::TSTypeAliasDeclaration(decl) => Some(self.text(decl.span).to_string()),
+            Declaration::TSEnumDeclaration(decl) if decl.declare => {
+                Some(self.text(decl.span).to_string())
+            }
+            Declaration::TSEnumDeclaration(decl) => {
+                Some(format!("declare {}", self.text(decl.span)))
+            }
+            _ => None,
+        }
+    }
+
+    fn class(&self, class: &Class) -> String {
+        let header = match &class.id {
+            Some(id) => self
+                .text(Span::new(id.span.start, class.body.span.start))
+                .trim_end(),
+            None => "",
+        };
+        let abstract_ = if class.r#abstract { "abstract " } else { "" };
+
+        let mut members = String::new();
+        for element in &class.body.body {
+            if let Some(member) = self.class_member(element) {
+                members.push_str("    ");
+                members.push_str(&member);
+                members.push('\n');
+            }
+        }
+        // Parameter properties become property declarations, as in tsc.
+        if let Some(ctor) = class.body.body.iter().find_map(|element| match element {
+            ClassElement::MethodDefinition(method)
+                if method.kind == MethodDefinitionKind::Constructor =>
+            {
+                Some(method)
+            }
+            _ => None,
+        }) {
+            for param in &ctor.value.params.items {
+                if param.accessibility.is_none() && !param.readonly {
+                    continue;
+                }
+                let modifiers = modifiers(param.accessibility, false, param.readonly);
+                let name = self.binding_name(&param.pattern);
+                members.push_str(&match param.accessibility {
+                    Some(TSAccessibility::Private) => format!("    {}{};\n", modifiers, name),
+                    _ => format!(
+                        "    {}{}: {};\n",
+                        modifiers,
+                        name,
+                        self.annotation(param.pattern.type_annotation.as_deref())
+                    ),
+                });
+            }
+        }
+
+        format!("{}class {} {{\n{}}}", abstract_, header, members)
+    }
+
+    fn class_member(&self, element: &ClassElement) -> Option<String> {
+        match element {
+            ClassElement::PropertyDefinition(prop) => {
+                if matches!(prop.key, PropertyKey::PrivateIdentifier(_)) {
+                    return None;
+                }
+                let modifiers = modifiers(prop.accessibility, prop.r#static, prop.readonly);
+                let key = self.property_key(&prop.key, prop.computed);
+                let optional = if prop.optional { "?" } else { "" };
+                if prop.accessibility == Some(TSAccessibility::Private) {
+                    return Some(format!("{}{}{};", modifiers, key, optional));
+                }
+                let type_ = match &prop.type_annotation {
+                    Some(annotation) => self.text(annotation.type_annotation.span()).to_string(),
+                    None => self.infer_type(prop.value.as_ref(), prop.readonly),
+                };
+                Some(format!("{}{}{}: {};", modifiers, key, optional, type_))
+            }
+            ClassElement::MethodDefinition(method) => {
+                if matches!(method.key, PropertyKey::PrivateIdentifier(_)) {
+                    return None;
+                }
+                let modifiers = modifiers(method.accessibility, method.r#static, false);
+                let abstract_ = if method.r#type == MethodDefinitionType::TSAbstractMethodDefinition
+                {
+                    "abstract "
+                } else {
+                    ""
+                };
+                let key = self.property_key(&method.key, method.computed);
+                if method.accessibility == Some(TSAccessibility::Private)
+                    && method.kind != MethodDefinitionKind::Constructor
+                {
+                    return Some(format!("{}{};", modifiers, key));
+                }
+                let func = &method.value;
+                let params = self.params(&func.params);
+                Some(match method.kind {
+                    MethodDefinitionKind::Constructor => format!("constructor({});", params),
+                    MethodDefinitionKind::Get => format!(
+                        "{}get {}(): {};",
+                        modifiers,
+                        key,
+                        self.annotation(func.return_type.as_deref())
+                    ),
+                    MethodDefinitionKind::Set => format!("{}set {}({});", modifiers, key, params),
+                    MethodDefinitionKind::Method => format!(
+                        "{}{}{}{}{}({}): {};",
+                        modifiers,
+                        abstract_,
+                        key,
+                        if method.optional { "?" } else { "" },
+                        self.type_parameters(func.type_parameters.as_deref()),
+                        params,
+                        self.annotation(func.return_type.as_deref())
+                    ),
+                })
+            }
+            ClassElement::TSIndexSignature(signature) => {
+                Some(self.text(signature.span).to_string())
+            }
+            _ => None,
+        }
+    }
+
+    fn function(&self, func: &Function) -> String {
+        format!(
+            "function {}{}({}): {};",
+            func.id.as_ref().map_or("", |id| id.name.as_str()),
+            self.type_parameters(func.type_parameters.as_deref()),
+            self.params(&func.params),
+            self.annotation(func.return_type.as_deref())
+        )
+    }
+
+    fn variable(&self, var: &VariableDeclaration) -> String {
+        let is_const = var.kind == VariableDeclarationKind::Const;
+        let declarators: Vec<String> = var
+            .declarations
+            .iter()
+            .map(|decl| {
+                let type_ = match &decl.id.type_annotation {
+                    Some(annotation) => self.text(annotation.type_annotation.span()).to_string(),
+                    None => self.infer_type(decl.init.as_ref(), is_const),
+                };
+                format!("{}: {}", self.binding_name(&decl.id), type_)
+            })
+            .collect();
+        let kind = if is_const { "const" } else { "let" };
+        format!("declare {} {};", kind, declarators.join(", "))
+    }
+
+    fn params(&self, params: &FormalParameters) -> String {
+        let mut items: Vec<String> = params
+            .items
+            .iter()
+            .map(|param| {
+                let (pattern, default_value) = match &param.pattern.kind {
+                    BindingPatternKind::AssignmentPattern(assignment) => {
+                        (&assignment.left, Some(&assignment.right))
+                    }
+                    _ => (&param.pattern, None),
+                };
+                let optional =
+                    if param.pattern.optional || pattern.optional || default_value.is_some() {
+                        "?"
+                    } else {
+                        ""
+                    };
+                let type_ = match param
+                    .pattern
+                    .type_annotation
+                    .as_deref()
+                    .or(pattern.type_annotation.as_deref())
+                {
+                    Some(annotation) => self.text(annotation.type_annotation.span()).to_string(),
+                    None => self.infer_type(default_value, false),
+                };
+                format!("{}{}: {}", self.binding_name(pattern), optional, type_)
+            })
+            .collect();
+        if let Some(rest) = &params.rest {
+            items.push(format!(
+                "...{}: {}",
+                self.binding_name(&rest.argument),
+                match rest.argument.type_annotation.as_deref() {
+                    Some(annotation) => self.text(annotation.type_annotation.span()).to_string(),
+                    None => "any[]".to_string(),
+                }
+            ));
+        }
+        items.join(", ")
+    }
+
+    fn binding_name(&self, pattern: &BindingPattern) -> &'s str {
+        let mut span = pattern.kind.span();
+        // The parser includes the `?` and type annotation of a parameter in its binding span.
+        if let Some(annotation) = &pattern.type_annotation {
+            span.end = span.end.min(annotation.span.start);
+        }
+        self.text(span).trim_end().trim_end_matches('?')
+    }
+
+    fn property_key(&self, key: &PropertyKey, computed: bool) -> String {
+        if computed {
+            format!("[{}]", self.text(key.span()))
+        } else {
+            self.text(key.span()).to_string()
+        }
+    }
+
+    fn type_parameters(&self, params: Option<&TSTypeParameterDeclaration>) -> &'s str {
+        params.map_or("", |params| self.text(params.span))
+    }
+
+    fn annotation(&self, annotation: Option<&TSTypeAnnotation>) -> &'s str {
+        annotation.map_or("any", |annotation| {
+            self.text(annotation.type_annotation.span())
+        })
+    }
+
+    /// The type of an unannotated declaration: primitive literals keep their literal type when
+    /// the declaration cannot be reassigned, other initializers need inference and become `any`.
+    fn infer_type(&self, init: Option<&Expression>, is_constant: bool) -> String {
+        match init {
+            Some(Expression::StringLiteral(lit)) if is_constant => self.text(lit.span).to_string(),
+            Some(Expression::NumericLiteral(lit)) if is_constant => self.text(lit.span).to_string(),
+            Some(Expression::BooleanLiteral(lit)) if is_constant => lit.value.to_string(),
+            Some(Expression::StringLiteral(_) | Expression::TemplateLiteral(_)) => {
+                "string".to_string()
+            }
+            Some(Expression::NumericLiteral(_)) => "number".to_string(),
+            Some(Expression::BooleanLiteral(_)) => "boolean".to_string(),
+            _ => "any".to_string(),
+        }
+    }
+}
+
+fn modifiers(accessibility: Option<TSAccessibility>, is_static: bool, readonly: bool) -> String {
+    let mut modifiers = String::new();
+    match accessibility {
+        Some(TSAccessibility::Private) => modifiers.push_str("private "),
+        Some(TSAccessibility::Protected) => modifiers.push_str("protected "),
+        Some(TSAccessibility::Public) | None => {}
+    }
+    if is_static {
+        modifiers.push_str("static ");
+    }
+    if readonly {
+        modifiers.push_str("readonly ");
+    }
+    modifiers
+}
@@ -7,6 +7,7 @@ pub mod alias;
 pub mod api;
 pub mod compilation;
 pub mod declaration;
+pub mod declaration_emitter;
 pub mod trait_;
 pub mod transform;
 
@@ -18,5 +19,6 @@ pub use api::{
 };
 pub use compilation::{ClassRecord, TraitCompiler};
 pub use declaration::{DtsTransformRegistry, IvyDeclarationDtsTransform, IvyDeclarationField};
+pub use declaration_emitter::emit_declaration_file;
 pub use trait_::{Trait, TraitFactory, TraitState};
 pub use transform::{IvyCompilationVisitor, IvyTransformConfig, IvyTransformationVisitor};
@@ -0,0 +1,159 @@
+// Declaration Tests - Tests for the .d.ts Ivy field transform
+
+use crate::ngtsc::annotations::directive::src::handler::DirectiveDecoratorHandler;
+use crate::ngtsc::metadata::{extract_directive_metadata, DecoratorMetadata};
+use crate::ngtsc::reflection::{ReflectionHost, TypeScriptReflectionHost};
+use crate::ngtsc::transform::src::declaration::{IvyDeclarationDtsTransform, IvyDeclarationField};
+use crate::ngtsc::transform::src::declaration_emitter::emit_declaration_file;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Declaration, Statement};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use std::collections::HashMap;
+
+/// Compile the exported `Dir` directive in `source` and register its fields for the `.d.ts`.
+fn transform_for_directive(source: &str) -> IvyDeclarationDtsTransform {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source, SourceType::ts()).parse();
+    assert!(ret.errors.is_empty(), "Parse errors: {:?}", ret.errors);
+    let declaration = ret
+        .program
+        .body
+        .iter()
+        .find_map(|stmt| match stmt {
+            Statement::ExportNamedDeclaration(export) => export.declaration.as_ref(),
+            _ => None,
+        })
+        .expect("Declaration not found");
+    let Declaration::ClassDeclaration(class) = declaration else {
+        panic!("Expected a class declaration");
+    };
+
+    let host = TypeScriptReflectionHost::new();
+    let decorators = host.get_decorators_of_declaration(declaration);
+    let decorator = decorators
+        .iter()
+        .find(|d| d.name == "Directive")
+        .expect("Directive decorator not found");
+    let metadata = extract_directive_metadata(
+        class,
+        decorator,
+        false,
+        std::path::Path::new("dir.ts"),
+        &HashMap::new(),
+    )
+    .expect("Metadata extraction failed");
+    assert!(matches!(metadata, DecoratorMetadata::Directive(_)));
+
+    let results = DirectiveDecoratorHandler::new(false).compile_ivy(&metadata);
+    let mut transform = IvyDeclarationDtsTransform::new();
+    transform.add_fields(
+        "Dir",
+        results.iter().map(IvyDeclarationField::from).collect(),
+    );
+    transform
+}
+
+#[test]
+fn test_directive_dts_declares_ivy_fields() {
+    let transform = transform_for_directive(
+        r#"
+        import {Directive, Input} from '@angular/core';
+
+        @Directive({selector: '[dir]', exportAs: 'dir'})
+        export class Dir {
+            @Input() value: string;
+            @Input({alias: 'label', required: true}) text: string;
+        }
+    "#,
+    );
+
+    let dts = transform.transform_declaration_file(
+        "export declare class Dir {\n    value: string;\n    text: string;\n}\n",
+    );
+
+    assert!(dts.starts_with("import * as i0 from \"@angular/core\";\n"));
+    assert!(dts.contains("    static ɵfac: i0.ɵɵFactoryDeclaration<Dir, never>;\n"));
+    assert!(
+        dts.contains(
+            "    static ɵdir: i0.ɵɵDirectiveDeclaration<Dir, \"[dir]\", [\"dir\"], \
+             { \"value\": { \"alias\": \"value\"; \"required\": false; }; \
+             \"text\": { \"alias\": \"label\"; \"required\": true; }; }, \
+             {}, never, never, true, never>;\n}\n"
+        ),
+        "{}",
+        dts
+    );
+}
+
+#[test]
+fn test_dts_without_registered_classes_is_unchanged() {
+    let mut transform = IvyDeclarationDtsTransform::new();
+    transform.add_fields(
+        "Other",
+        vec![IvyDeclarationField::new(
+            "ɵfac",
+            "i0.ɵɵFactoryDeclaration<Other, never>",
+        )],
+    );
+
+    let dts = "export declare class Dir {\n}\n";
+    assert_eq!(transform.transform_declaration_file(dts), dts);
+}
+
+#[test]
+fn test_emit_declaration_file() {
+    let dts = emit_declaration_file(
+        r#"
+import { Service } from './service';
+export * from './other';
+
+export const VERSION = '1.0';
+export let count = 0;
+
+export interface Options { debug?: boolean }
+
+export function create(options: Options, ...rest: string[]): Options {
+    return options;
+}
+
+@Decorator()
+export class Store<T> extends Base implements OnInit {
+    private cache = new Map();
+    protected readonly name: string = 'store';
+    static instances = 1;
+
+    constructor(private service: Service, public readonly id: number, label = 'x') {
+        super();
+    }
+
+    get size(): number { return 0; }
+    ngOnInit() {}
+}
+
+class Internal {}
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        dts,
+        r#"import { Service } from './service';
+export * from './other';
+export declare const VERSION: '1.0';
+export declare let count: number;
+export interface Options { debug?: boolean }
+export declare function create(options: Options, ...rest: string[]): Options;
+export declare class Store<T> extends Base implements OnInit {
+    private cache;
+    protected readonly name: string;
+    static instances: number;
+    constructor(service: Service, id: number, label?: string);
+    get size(): number;
+    ngOnInit(): any;
+    private service;
+    readonly id: number;
+}
+"#
+    );
+}
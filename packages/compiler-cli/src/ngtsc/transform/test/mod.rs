@@ -1,3 +1,4 @@
 // Test module for transform
 mod compilation_spec;
+mod declaration_spec;
 mod reproduction_spec;
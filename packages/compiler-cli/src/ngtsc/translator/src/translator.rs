@@ -1,7 +1,8 @@
 use angular_compiler::output::output_ast as o;
 
 use crate::ngtsc::translator::src::api::ast_factory::{
-    AstFactory, BinaryOperator, SourceMapLocation, SourceMapRange,
+    ArrowFunctionBody, AstFactory, BinaryOperator, LiteralValue, ObjectLiteralProperty,
+    SourceMapLocation, SourceMapRange, TemplateElement, TemplateLiteral, UnaryOperator,
 };
 use crate::ngtsc::translator::src::api::import_generator::{ImportGenerator, ImportRequest};
 use crate::ngtsc::translator::src::context::Context;
@@ -24,10 +25,7 @@ pub struct ExpressionTranslatorVisitor<'a, A: AstFactory, TFile> {
     record_wrapped_node: Option<RecordWrappedNodeFn<A::Expression>>,
 }
 
-impl<'a, A: AstFactory, TFile: Clone> ExpressionTranslatorVisitor<'a, A, TFile>
-where
-    A::Expression: Clone,
-{
+impl<'a, A: AstFactory, TFile: Clone> ExpressionTranslatorVisitor<'a, A, TFile> {
     pub fn new(
         factory: &'a A,
         imports: &'a mut dyn ImportGenerator<TFile, A::Expression>,
@@ -59,15 +57,28 @@ where
     pub fn visit_expression(&mut self, expr: &o::Expression, context: Context) -> A::Expression {
         match expr {
             o::Expression::ReadVar(e) => self.visit_read_var_expr(e, context),
+            o::Expression::WriteVar(e) => self.visit_write_var_expr(e, context),
+            o::Expression::WriteKey(e) => self.visit_write_key_expr(e, context),
+            o::Expression::WriteProp(e) => self.visit_write_prop_expr(e, context),
             o::Expression::InvokeFn(e) => self.visit_invoke_fn_expr(e, context),
             o::Expression::TaggedTemplate(e) => self.visit_tagged_template_expr(e, context),
             o::Expression::TemplateLiteral(e) => self.visit_template_literal_expr(e, context),
             o::Expression::Instantiate(e) => self.visit_instantiate_expr(e, context),
             o::Expression::Literal(e) => self.visit_literal_expr(e, context),
             o::Expression::External(e) => self.visit_external_expr(e, context),
+            o::Expression::ExternalRef(e) => self.visit_external_reference(e),
             o::Expression::Conditional(e) => self.visit_conditional_expr(e, context),
             o::Expression::DynamicImport(e) => self.visit_dynamic_import_expr(e, context),
             o::Expression::NotExpr(e) => self.visit_not_expr(e, context),
+            o::Expression::FnParam(e) => self.factory.create_identifier(&e.name),
+            o::Expression::IfNull(e) => self.factory.create_binary_expression(
+                self.visit_expression(&e.condition, context),
+                BinaryOperator::NullishCoalesce,
+                self.visit_expression(&e.null_case, context),
+            ),
+            // Type-only constructs: only the value is emitted.
+            o::Expression::AssertNotNull(e) => self.visit_expression(&e.condition, context),
+            o::Expression::Cast(e) => self.visit_expression(&e.value, context),
             o::Expression::Fn(e) => self.visit_function_expr(e, context),
             o::Expression::ArrowFn(e) => self.visit_arrow_function_expr(e, context),
             o::Expression::BinaryOp(e) => self.visit_binary_operator_expr(e, context),
@@ -84,7 +95,8 @@ where
             o::Expression::RegularExpressionLiteral(e) => {
                 self.visit_regular_expression_literal(e, context)
             }
-            // TODO: Handle others or panic/error
+            // Comma and raw code expressions, and the IR expressions of the template pipeline,
+            // never reach the translator.
             _ => panic!("Unsupported expression type in translator: {:?}", expr),
         }
     }
@@ -163,6 +175,47 @@ where
         self.set_source_map_range_expr(identifier, ast.source_span.as_ref())
     }
 
+    fn visit_write_var_expr(&mut self, ast: &o::WriteVarExpr, context: Context) -> A::Expression {
+        let target = self.factory.create_identifier(&ast.name);
+        let target = self.set_source_map_range_expr(target, ast.source_span.as_ref());
+        self.create_assignment(target, &ast.value, context)
+    }
+
+    fn visit_write_key_expr(&mut self, ast: &o::WriteKeyExpr, context: Context) -> A::Expression {
+        let expr_context = context.with_expression_mode();
+        let target = self.factory.create_element_access(
+            self.visit_expression(&ast.receiver, expr_context),
+            self.visit_expression(&ast.index, expr_context),
+        );
+        self.create_assignment(target, &ast.value, context)
+    }
+
+    fn visit_write_prop_expr(&mut self, ast: &o::WritePropExpr, context: Context) -> A::Expression {
+        let target = self.factory.create_property_access(
+            self.visit_expression(&ast.receiver, context.with_expression_mode()),
+            &ast.name,
+        );
+        self.create_assignment(target, &ast.value, context)
+    }
+
+    /// Assign `value` to `target`, parenthesized unless the assignment is a statement.
+    fn create_assignment(
+        &mut self,
+        target: A::Expression,
+        value: &o::Expression,
+        context: Context,
+    ) -> A::Expression {
+        let value = self.visit_expression(value, context.with_expression_mode());
+        let assignment = self
+            .factory
+            .create_assignment(target, BinaryOperator::Assign, value);
+        if context.is_statement {
+            assignment
+        } else {
+            self.factory.create_parenthesized_expression(assignment)
+        }
+    }
+
     fn visit_invoke_fn_expr(
         &mut self,
         ast: &o::InvokeFunctionExpr,
@@ -217,8 +270,18 @@ where
         }
     }
 
-    // ... Implement other visit methods ...
-    // Placeholder versions for brevity in this step, need to complete all
+    fn visit_external_reference(&mut self, value: &o::ExternalReference) -> A::Expression {
+        match (&value.module_name, &value.name) {
+            (Some(module_name), name) => self.imports.add_import(ImportRequest {
+                export_module_specifier: module_name.clone(),
+                export_symbol_name: name.clone(),
+                requested_file: self.context_file.clone(),
+                unsafe_alias_override: None,
+            }),
+            (None, Some(name)) => self.factory.create_identifier(name),
+            (None, None) => panic!("Invalid external reference: {:?}", value),
+        }
+    }
 
     fn visit_conditional_expr(
         &mut self,
@@ -287,107 +350,270 @@ where
         expr: A::Expression,
         span: Option<&angular_compiler::parse_util::ParseSourceSpan>,
     ) -> A::Expression {
-        if let Some(span) = span {
-            // Convert span to SourceMapRange
-            // Need access to file url/content from span.start.file
-            let start = &span.start;
-            let end = &span.end;
-            let url = start.file.url.to_string(); // Assuming methods exist
-            let content = start.file.content.to_string();
-
-            let range = SourceMapRange {
-                url,
-                content,
-                start: SourceMapLocation {
-                    offset: start.offset,
-                    line: start.line,
-                    column: start.col,
-                },
-                end: SourceMapLocation {
-                    offset: end.offset,
-                    line: end.line,
-                    column: end.col,
-                },
-            };
+        if span.is_some() {
             self.factory
-                .set_source_map_range_for_expr(expr, Some(range))
+                .set_source_map_range_for_expr(expr, source_map_range(span))
         } else {
             expr
         }
     }
 
-    // ... Stubbing remaining mandatory visit methods for compilation success,
-    // in real implementation I should fill them.
+    fn create_tagged_template_expression(
+        &mut self,
+        tag: A::Expression,
+        template: TemplateLiteral<A::Expression>,
+    ) -> A::Expression {
+        if self.downlevel_tagged_templates {
+            self.create_es5_tagged_template_function_call(tag, template)
+        } else {
+            self.factory.create_tagged_template(tag, template)
+        }
+    }
+
+    /// Call `tag` like an ES5 runtime would call a tagged template, with the template strings
+    /// created by the `__makeTemplateObject` helper of `tslib`.
+    fn create_es5_tagged_template_function_call(
+        &mut self,
+        tag: A::Expression,
+        template: TemplateLiteral<A::Expression>,
+    ) -> A::Expression {
+        let make_template_object = self.imports.add_import(ImportRequest {
+            export_module_specifier: "tslib".to_string(),
+            export_symbol_name: Some("__makeTemplateObject".to_string()),
+            requested_file: self.context_file.clone(),
+            unsafe_alias_override: None,
+        });
+        let mut cooked = Vec::new();
+        let mut raw = Vec::new();
+        for element in template.elements {
+            cooked.push(
+                self.factory.set_source_map_range_for_expr(
+                    self.factory
+                        .create_literal(LiteralValue::String(&element.cooked)),
+                    element.range,
+                ),
+            );
+            raw.push(
+                self.factory
+                    .create_literal(LiteralValue::String(&element.raw)),
+            );
+        }
+        let template_object = self.factory.create_call_expression(
+            make_template_object,
+            vec![
+                self.factory.create_array_literal(cooked),
+                self.factory.create_array_literal(raw),
+            ],
+            false,
+        );
+        let mut args = vec![template_object];
+        args.extend(template.expressions);
+        self.factory.create_call_expression(tag, args, false)
+    }
+
+    fn visit_template_literal(
+        &mut self,
+        template: &o::TemplateLiteral,
+        span: Option<&angular_compiler::parse_util::ParseSourceSpan>,
+        context: Context,
+    ) -> TemplateLiteral<A::Expression> {
+        TemplateLiteral {
+            elements: template
+                .elements
+                .iter()
+                .map(|element| TemplateElement {
+                    cooked: element.text.clone(),
+                    raw: element.raw_text.clone(),
+                    range: source_map_range(element.source_span.as_ref().or(span)),
+                })
+                .collect(),
+            expressions: template
+                .expressions
+                .iter()
+                .map(|expr| self.visit_expression(expr, context))
+                .collect(),
+        }
+    }
 
     fn visit_tagged_template_expr(
         &mut self,
-        _e: &o::TaggedTemplateLiteralExpr,
-        _c: Context,
+        ast: &o::TaggedTemplateLiteralExpr,
+        context: Context,
     ) -> A::Expression {
-        unimplemented!()
+        let tag = self.visit_expression(&ast.tag, context);
+        let template =
+            self.visit_template_literal(&ast.template, ast.source_span.as_ref(), context);
+        let expr = self.create_tagged_template_expression(tag, template);
+        self.set_source_map_range_expr(expr, ast.source_span.as_ref())
     }
+
     fn visit_template_literal_expr(
         &mut self,
-        _e: &o::TemplateLiteralExpr,
-        _c: Context,
+        ast: &o::TemplateLiteralExpr,
+        context: Context,
     ) -> A::Expression {
-        unimplemented!()
+        let template = o::TemplateLiteral {
+            elements: ast.elements.clone(),
+            expressions: ast.expressions.clone(),
+        };
+        let template = self.visit_template_literal(&template, None, context);
+        self.factory.create_template_literal(template)
     }
-    fn visit_instantiate_expr(&mut self, _e: &o::InstantiateExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_instantiate_expr(
+        &mut self,
+        ast: &o::InstantiateExpr,
+        context: Context,
+    ) -> A::Expression {
+        let class_expr = self.visit_expression(&ast.class_expr, context);
+        let args = ast
+            .args
+            .iter()
+            .map(|arg| self.visit_expression(arg, context))
+            .collect();
+        self.set_source_map_range_expr(
+            self.factory.create_new_expression(class_expr, args),
+            ast.source_span.as_ref(),
+        )
     }
+
     fn visit_dynamic_import_expr(
         &mut self,
-        _e: &o::DynamicImportExpr,
-        _c: Context,
+        ast: &o::DynamicImportExpr,
+        context: Context,
     ) -> A::Expression {
-        unimplemented!()
+        let url = match &ast.url {
+            o::DynamicImportUrl::Literal(url) => Ok(url.as_str()),
+            o::DynamicImportUrl::Expression(url) => Err(self.visit_expression(url, context)),
+        };
+        let expr = self.factory.create_dynamic_import(url);
+        self.set_source_map_range_expr(expr, ast.source_span.as_ref())
     }
-    fn visit_not_expr(&mut self, _e: &o::NotExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_not_expr(&mut self, ast: &o::NotExpr, context: Context) -> A::Expression {
+        let condition = self.visit_expression(&ast.condition, context);
+        self.factory
+            .create_unary_expression(UnaryOperator::Not, condition)
     }
-    fn visit_function_expr(&mut self, _e: &o::FunctionExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_function_expr(&mut self, ast: &o::FunctionExpr, context: Context) -> A::Expression {
+        let body = self
+            .factory
+            .create_block(self.visit_statements(&ast.statements, context.with_statement_mode()));
+        let params = ast.params.iter().map(|p| p.name.clone()).collect();
+        self.set_source_map_range_expr(
+            self.factory
+                .create_function_expression(ast.name.as_deref(), params, body),
+            ast.source_span.as_ref(),
+        )
     }
+
     fn visit_arrow_function_expr(
         &mut self,
-        _e: &o::ArrowFunctionExpr,
-        _c: Context,
+        ast: &o::ArrowFunctionExpr,
+        context: Context,
     ) -> A::Expression {
-        unimplemented!()
+        let body = match &ast.body {
+            o::ArrowFunctionBody::Expression(expr) => {
+                ArrowFunctionBody::Expr(self.visit_expression(expr, context.with_expression_mode()))
+            }
+            o::ArrowFunctionBody::Statements(statements) => ArrowFunctionBody::Stmt(
+                self.factory
+                    .create_block(self.visit_statements(statements, context.with_statement_mode())),
+            ),
+        };
+        let params = ast.params.iter().map(|p| p.name.clone()).collect();
+        self.set_source_map_range_expr(
+            self.factory.create_arrow_function_expression(params, body),
+            ast.source_span.as_ref(),
+        )
     }
-    fn visit_read_prop_expr(&mut self, _e: &o::ReadPropExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_read_prop_expr(&mut self, ast: &o::ReadPropExpr, context: Context) -> A::Expression {
+        let receiver = self.visit_expression(&ast.receiver, context);
+        self.set_source_map_range_expr(
+            self.factory.create_property_access(receiver, &ast.name),
+            ast.source_span.as_ref(),
+        )
     }
-    fn visit_read_key_expr(&mut self, _e: &o::ReadKeyExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_read_key_expr(&mut self, ast: &o::ReadKeyExpr, context: Context) -> A::Expression {
+        let receiver = self.visit_expression(&ast.receiver, context);
+        let index = self.visit_expression(&ast.index, context);
+        self.set_source_map_range_expr(
+            self.factory.create_element_access(receiver, index),
+            ast.source_span.as_ref(),
+        )
     }
-    fn visit_literal_array_expr(&mut self, _e: &o::LiteralArrayExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_literal_array_expr(
+        &mut self,
+        ast: &o::LiteralArrayExpr,
+        context: Context,
+    ) -> A::Expression {
+        let entries = ast
+            .entries
+            .iter()
+            .map(|entry| self.visit_expression(entry, context))
+            .collect();
+        self.set_source_map_range_expr(
+            self.factory.create_array_literal(entries),
+            ast.source_span.as_ref(),
+        )
     }
-    fn visit_literal_map_expr(&mut self, _e: &o::LiteralMapExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_literal_map_expr(
+        &mut self,
+        ast: &o::LiteralMapExpr,
+        context: Context,
+    ) -> A::Expression {
+        let properties = ast
+            .entries
+            .iter()
+            .map(|entry| ObjectLiteralProperty {
+                property_name: entry.key.clone(),
+                quoted: entry.quoted,
+                value: self.visit_expression(&entry.value, context),
+            })
+            .collect();
+        self.set_source_map_range_expr(
+            self.factory.create_object_literal(properties),
+            ast.source_span.as_ref(),
+        )
     }
+
     fn visit_unary_operator_expr(
         &mut self,
-        _e: &o::UnaryOperatorExpr,
-        _c: Context,
+        ast: &o::UnaryOperatorExpr,
+        context: Context,
     ) -> A::Expression {
-        unimplemented!()
+        let operator = match ast.operator {
+            o::UnaryOperator::Minus => UnaryOperator::Minus,
+            o::UnaryOperator::Plus => UnaryOperator::Plus,
+        };
+        let operand = self.visit_expression(&ast.expr, context);
+        self.factory.create_unary_expression(operator, operand)
     }
+
     fn visit_parenthesized_expr(
         &mut self,
-        _e: &o::ParenthesizedExpr,
-        _c: Context,
+        ast: &o::ParenthesizedExpr,
+        context: Context,
     ) -> A::Expression {
-        unimplemented!()
+        let expr = self.visit_expression(&ast.expr, context);
+        self.factory.create_parenthesized_expression(expr)
     }
-    fn visit_typeof_expr(&mut self, _e: &o::TypeofExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_typeof_expr(&mut self, ast: &o::TypeofExpr, context: Context) -> A::Expression {
+        let expr = self.visit_expression(&ast.expr, context);
+        self.factory.create_type_of_expression(expr)
     }
-    fn visit_void_expr(&mut self, _e: &o::VoidExpr, _c: Context) -> A::Expression {
-        unimplemented!()
+
+    fn visit_void_expr(&mut self, ast: &o::VoidExpr, context: Context) -> A::Expression {
+        let expr = self.visit_expression(&ast.expr, context);
+        self.factory.create_void_expression(expr)
     }
+
     fn visit_wrapped_node_expr(&mut self, e: &o::WrappedNodeExpr, _c: Context) -> A::Expression {
         if let Some(record) = &self.record_wrapped_node {
             if let Some(result) = record(e) {
@@ -396,8 +622,36 @@ where
         }
         panic!("WrappedNodeExpr visited but not handled by record_wrapped_node")
     }
-    fn visit_localized_string(&mut self, _e: &o::LocalizedString, _c: Context) -> A::Expression {
-        unimplemented!()
+    fn visit_localized_string(
+        &mut self,
+        ast: &o::LocalizedString,
+        context: Context,
+    ) -> A::Expression {
+        let template_element = |piece: o::CookedRawString| TemplateElement {
+            cooked: piece.cooked,
+            raw: piece.raw,
+            range: source_map_range(piece.range.as_ref()),
+        };
+        let mut elements = vec![template_element(ast.serialize_i18n_head())];
+        let mut expressions = Vec::new();
+        for (i, expr) in ast.expressions.iter().enumerate() {
+            let placeholder = self.visit_expression(expr, context);
+            let placeholder_span = ast
+                .placeholder_names
+                .get(i)
+                .map(|placeholder| &placeholder.source_span);
+            expressions.push(self.set_source_map_range_expr(placeholder, placeholder_span));
+            elements.push(template_element(ast.serialize_i18n_template_part(i + 1)));
+        }
+        let localize_tag = self.factory.create_identifier("$localize");
+        let expr = self.create_tagged_template_expression(
+            localize_tag,
+            TemplateLiteral {
+                elements,
+                expressions,
+            },
+        );
+        self.set_source_map_range_expr(expr, ast.source_span.as_ref())
     }
     fn visit_regular_expression_literal(
         &mut self,
@@ -415,16 +669,67 @@ where
     }
 }
 
+/// The location of `span` in its source file, to map the generated code back to it.
+fn source_map_range(
+    span: Option<&angular_compiler::parse_util::ParseSourceSpan>,
+) -> Option<SourceMapRange> {
+    let span = span?;
+    let start = &span.start;
+    let end = &span.end;
+    Some(SourceMapRange {
+        url: start.file.url.to_string(),
+        content: start.file.content.to_string(),
+        start: SourceMapLocation {
+            offset: start.offset,
+            line: start.line,
+            column: start.col,
+        },
+        end: SourceMapLocation {
+            offset: end.offset,
+            line: end.line,
+            column: end.col,
+        },
+    })
+}
+
 fn map_binary_operator(op: o::BinaryOperator) -> Option<BinaryOperator> {
     match op {
         o::BinaryOperator::And => Some(BinaryOperator::And),
         o::BinaryOperator::Bigger => Some(BinaryOperator::Greater),
-        // ... map others
+        o::BinaryOperator::BiggerEquals => Some(BinaryOperator::GreaterEquals),
+        o::BinaryOperator::BitwiseAnd => Some(BinaryOperator::BitAnd),
+        o::BinaryOperator::BitwiseOr => Some(BinaryOperator::BitOr),
+        o::BinaryOperator::Divide => Some(BinaryOperator::Divide),
+        o::BinaryOperator::Equals => Some(BinaryOperator::Equals),
+        o::BinaryOperator::Identical => Some(BinaryOperator::IdentityEquals),
+        o::BinaryOperator::Lower => Some(BinaryOperator::Less),
+        o::BinaryOperator::LowerEquals => Some(BinaryOperator::LessEquals),
+        o::BinaryOperator::Minus => Some(BinaryOperator::Minus),
+        o::BinaryOperator::Modulo => Some(BinaryOperator::Modulo),
+        o::BinaryOperator::Multiply => Some(BinaryOperator::Multiply),
+        o::BinaryOperator::Exponentiation => Some(BinaryOperator::Power),
+        o::BinaryOperator::NotEquals => Some(BinaryOperator::NotEquals),
+        o::BinaryOperator::NotIdentical => Some(BinaryOperator::IdentityNotEquals),
+        o::BinaryOperator::Or => Some(BinaryOperator::Or),
+        o::BinaryOperator::Plus => Some(BinaryOperator::Plus),
+        o::BinaryOperator::NullishCoalesce => Some(BinaryOperator::NullishCoalesce),
+        o::BinaryOperator::In => Some(BinaryOperator::In),
         _ => None,
     }
 }
 
 fn map_binary_operator_assignment(op: o::BinaryOperator) -> Option<BinaryOperator> {
-    // Map assignments
-    None
+    match op {
+        o::BinaryOperator::Assign => Some(BinaryOperator::Assign),
+        o::BinaryOperator::AdditionAssignment => Some(BinaryOperator::PlusAssign),
+        o::BinaryOperator::SubtractionAssignment => Some(BinaryOperator::MinusAssign),
+        o::BinaryOperator::MultiplicationAssignment => Some(BinaryOperator::MultiplyAssign),
+        o::BinaryOperator::DivisionAssignment => Some(BinaryOperator::DivideAssign),
+        o::BinaryOperator::RemainderAssignment => Some(BinaryOperator::ModuloAssign),
+        o::BinaryOperator::ExponentiationAssignment => Some(BinaryOperator::PowerAssign),
+        o::BinaryOperator::AndAssignment => Some(BinaryOperator::AndAssign),
+        o::BinaryOperator::OrAssignment => Some(BinaryOperator::OrAssign),
+        o::BinaryOperator::NullishCoalesceAssignment => Some(BinaryOperator::NullishCoalesceAssign),
+        _ => None,
+    }
 }
@@ -1,3 +1,143 @@
-pub fn translate_type() {
-    todo!("Implement type translation logic");
+// Type Translator
+//
+// Prints output AST types as TypeScript type annotations, as written into `.d.ts` files.
+
+use angular_compiler::output::output_ast as o;
+
+/// The namespace `@angular/core` is imported under in generated declarations.
+pub const CORE_NAMESPACE: &str = "i0";
+
+const CORE_MODULE: &str = "@angular/core";
+
+/// Translate an output AST type into its TypeScript source, e.g.
+/// `i0.ɵɵDirectiveDeclaration<Dir, "[dir]", never, {}, {}, never, never, true, never>`.
+///
+/// References into `@angular/core` are qualified with [`CORE_NAMESPACE`]; other external
+/// references are printed by their plain name.
+pub fn translate_type(type_: &o::Type) -> String {
+    match type_ {
+        o::Type::Builtin(builtin) => translate_builtin_type(&builtin.name).to_string(),
+        o::Type::Expression(expr_type) => {
+            let base = translate_type_expression(&expr_type.value);
+            match &expr_type.type_params {
+                Some(params) if !params.is_empty() => {
+                    let params: Vec<String> = params.iter().map(translate_type).collect();
+                    format!("{}<{}>", base, params.join(", "))
+                }
+                _ => base,
+            }
+        }
+        o::Type::Array(array) => format!("{}[]", translate_type(&array.of)),
+        o::Type::Map(map) => format!(
+            "{{ [key: string]: {}; }}",
+            map.value_type
+                .as_ref()
+                .map_or_else(|| "any".to_string(), |value| translate_type(value))
+        ),
+        o::Type::Transplanted(_) => "any".to_string(),
+    }
+}
+
+fn translate_builtin_type(name: &o::BuiltinTypeName) -> &'static str {
+    match name {
+        o::BuiltinTypeName::Dynamic => "any",
+        o::BuiltinTypeName::Bool => "boolean",
+        o::BuiltinTypeName::String => "string",
+        o::BuiltinTypeName::Int | o::BuiltinTypeName::Number => "number",
+        o::BuiltinTypeName::Function => "Function",
+        o::BuiltinTypeName::Inferred => "unknown",
+        o::BuiltinTypeName::None => "never",
+    }
+}
+
+/// Translate an expression used in type position, such as the literals that encode directive
+/// metadata in declaration types.
+fn translate_type_expression(expr: &o::Expression) -> String {
+    match expr {
+        o::Expression::ReadVar(read) => read.name.clone(),
+        o::Expression::ReadProp(read) => {
+            format!(
+                "{}.{}",
+                translate_type_expression(&read.receiver),
+                read.name
+            )
+        }
+        o::Expression::External(external) => {
+            let name = external.value.name.clone().unwrap_or_default();
+            match external.value.module_name.as_deref() {
+                Some(CORE_MODULE) => format!("{}.{}", CORE_NAMESPACE, name),
+                _ => name,
+            }
+        }
+        o::Expression::TypeOf(type_of) => {
+            format!("typeof {}", translate_type_expression(&type_of.expr))
+        }
+        o::Expression::Literal(literal) => match &literal.value {
+            o::LiteralValue::Null => "null".to_string(),
+            o::LiteralValue::Undefined => "undefined".to_string(),
+            o::LiteralValue::String(value) => quote(value),
+            o::LiteralValue::Number(value) => value.to_string(),
+            o::LiteralValue::Bool(value) => value.to_string(),
+        },
+        o::Expression::LiteralArray(array) => {
+            let entries: Vec<String> = array
+                .entries
+                .iter()
+                .map(translate_type_expression)
+                .collect();
+            format!("[{}]", entries.join(", "))
+        }
+        o::Expression::LiteralMap(map) => {
+            if map.entries.is_empty() {
+                return "{}".to_string();
+            }
+            let members: Vec<String> = map
+                .entries
+                .iter()
+                .map(|entry| {
+                    let key = if entry.quoted {
+                        quote(&entry.key)
+                    } else {
+                        entry.key.clone()
+                    };
+                    format!("{}: {};", key, translate_type_expression(&entry.value))
+                })
+                .collect();
+            format!("{{ {} }}", members.join(" "))
+        }
+        _ => "any".to_string(),
+    }
+}
+
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_literal_types() {
+        let type_ = o::Type::Expression(o::ExpressionType {
+            value: Box::new(o::Expression::LiteralMap(o::LiteralMapExpr {
+                entries: vec![o::LiteralMapEntry {
+                    key: "value".to_string(),
+                    value: Box::new(o::Expression::Literal(o::LiteralExpr {
+                        value: o::LiteralValue::String("a\"b".to_string()),
+                        type_: None,
+                        source_span: None,
+                    })),
+                    quoted: true,
+                }],
+                type_: None,
+                source_span: None,
+            })),
+            modifiers: o::TypeModifier::None,
+            type_params: None,
+        });
+
+        assert_eq!(translate_type(&type_), r#"{ "value": "a\"b"; }"#);
+        assert_eq!(translate_type(&o::none_type()), "never");
+    }
 }
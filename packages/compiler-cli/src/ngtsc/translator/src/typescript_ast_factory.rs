@@ -1,6 +1,11 @@
-use oxc_ast::ast::{Expression, ObjectPropertyKind, PropertyKey, PropertyKind, Statement};
+use oxc_ast::ast::{
+    BindingPattern, BindingRestElement, Expression, FormalParameterKind, FormalParameters,
+    FunctionBody, FunctionType, ObjectPropertyKind, PropertyKey, PropertyKind, RegExp, RegExpFlags,
+    RegExpPattern, Statement, TSThisParameter, TSTypeAnnotation, TSTypeParameterDeclaration,
+    TemplateElementValue, VariableDeclarationKind,
+};
 use oxc_ast::AstBuilder;
-use oxc_span::Span;
+use oxc_span::{Atom, Span};
 use oxc_syntax::operator::{
     AssignmentOperator, BinaryOperator as OxcBinaryOperator, UnaryOperator as OxcUnaryOperator,
 };
@@ -42,6 +47,7 @@ impl<'a> TypeScriptAstFactory<'a> {
             BinaryOperator::IdentityNotEquals => OxcBinaryOperator::StrictInequality,
             BinaryOperator::Plus => OxcBinaryOperator::Addition,
             BinaryOperator::Power => OxcBinaryOperator::Exponential,
+            BinaryOperator::In => OxcBinaryOperator::In,
             // Note: And, Or, NullishCoalesce are handled separately in create_binary_expression
             // as LogicalExpression nodes, not BinaryExpression
             _ => panic!("Unsupported binary operator: {:?}. Logical operators (And, Or, NullishCoalesce) should be handled separately.", op),
@@ -57,11 +63,92 @@ impl<'a> TypeScriptAstFactory<'a> {
             BinaryOperator::DivideAssign => AssignmentOperator::Division,
             BinaryOperator::ModuloAssign => AssignmentOperator::Remainder,
             BinaryOperator::PowerAssign => AssignmentOperator::Exponential,
-            BinaryOperator::AndAssign => AssignmentOperator::BitwiseAnd,
-            BinaryOperator::OrAssign => AssignmentOperator::BitwiseOR,
+            BinaryOperator::AndAssign => AssignmentOperator::LogicalAnd,
+            BinaryOperator::OrAssign => AssignmentOperator::LogicalOr,
+            BinaryOperator::NullishCoalesceAssign => AssignmentOperator::LogicalNullish,
             _ => panic!("Unsupported assignment operator: {:?}", op),
         }
     }
+
+    fn alloc_atom(&self, value: &str) -> Atom<'a> {
+        Atom::from(self.builder.allocator.alloc_str(value))
+    }
+
+    fn create_binding_pattern(&self, name: &str) -> BindingPattern<'a> {
+        self.builder.binding_pattern(
+            self.builder
+                .binding_pattern_kind_binding_identifier(Span::default(), self.alloc_atom(name)),
+            None::<oxc_allocator::Box<TSTypeAnnotation>>,
+            false,
+        )
+    }
+
+    fn create_parameters(
+        &self,
+        parameters: Vec<String>,
+    ) -> oxc_allocator::Box<'a, FormalParameters<'a>> {
+        let items = oxc_allocator::Vec::from_iter_in(
+            parameters.iter().map(|name| {
+                self.builder.formal_parameter(
+                    Span::default(),
+                    self.builder.vec(),
+                    self.create_binding_pattern(name),
+                    None,
+                    false,
+                    false,
+                )
+            }),
+            self.builder.allocator,
+        );
+        self.builder.alloc_formal_parameters(
+            Span::default(),
+            FormalParameterKind::FormalParameter,
+            items,
+            None::<oxc_allocator::Box<BindingRestElement>>,
+        )
+    }
+
+    /// The body of a function whose body statement is `body`, usually a block.
+    fn create_function_body(
+        &self,
+        body: Statement<'a>,
+    ) -> oxc_allocator::Box<'a, FunctionBody<'a>> {
+        let statements = match body {
+            Statement::BlockStatement(block) => block.unbox().body,
+            statement => self.builder.vec1(statement),
+        };
+        self.builder
+            .alloc_function_body(Span::default(), self.builder.vec(), statements)
+    }
+
+    fn create_template(
+        &self,
+        template: TemplateLiteral<Expression<'a>>,
+    ) -> oxc_ast::ast::TemplateLiteral<'a> {
+        let count = template.elements.len();
+        let quasis = oxc_allocator::Vec::from_iter_in(
+            template
+                .elements
+                .iter()
+                .enumerate()
+                .map(|(index, element)| {
+                    self.builder.template_element(
+                        Span::default(),
+                        TemplateElementValue {
+                            raw: self.alloc_atom(&element.raw),
+                            cooked: Some(self.alloc_atom(&element.cooked)),
+                        },
+                        index + 1 == count,
+                    )
+                }),
+            self.builder.allocator,
+        );
+        self.builder.template_literal(
+            Span::default(),
+            quasis,
+            oxc_allocator::Vec::from_iter_in(template.expressions, self.builder.allocator),
+        )
+    }
 }
 
 impl<'a> AstFactory for TypeScriptAstFactory<'a> {
@@ -243,32 +330,82 @@ impl<'a> AstFactory for TypeScriptAstFactory<'a> {
 
     fn create_function_declaration(
         &self,
-        _function_name: &str,
-        _parameters: Vec<String>,
-        _body: Self::Statement,
+        function_name: &str,
+        parameters: Vec<String>,
+        body: Self::Statement,
     ) -> Self::Statement {
-        // TODO: Implement proper function declaration with lifetime management
-        // The challenge is that `&self` has a different lifetime than `'a` in the struct
-        todo!("create_function_declaration requires proper lifetime handling")
+        Statement::FunctionDeclaration(
+            self.builder.alloc_function(
+                Span::default(),
+                FunctionType::FunctionDeclaration,
+                Some(
+                    self.builder
+                        .binding_identifier(Span::default(), self.alloc_atom(function_name)),
+                ),
+                false,
+                false,
+                false,
+                None::<oxc_allocator::Box<TSTypeParameterDeclaration>>,
+                None::<oxc_allocator::Box<TSThisParameter>>,
+                self.create_parameters(parameters),
+                None::<oxc_allocator::Box<TSTypeAnnotation>>,
+                Some(self.create_function_body(body)),
+            ),
+        )
     }
 
     fn create_function_expression(
         &self,
-        _function_name: Option<&str>,
-        _parameters: Vec<String>,
-        _body: Self::Statement,
+        function_name: Option<&str>,
+        parameters: Vec<String>,
+        body: Self::Statement,
     ) -> Self::Expression {
-        // TODO: Implement proper function expression with lifetime management
-        todo!("create_function_expression requires proper lifetime handling")
+        self.builder.expression_function(
+            Span::default(),
+            FunctionType::FunctionExpression,
+            function_name.map(|name| {
+                self.builder
+                    .binding_identifier(Span::default(), self.alloc_atom(name))
+            }),
+            false,
+            false,
+            false,
+            None::<oxc_allocator::Box<TSTypeParameterDeclaration>>,
+            None::<oxc_allocator::Box<TSThisParameter>>,
+            self.create_parameters(parameters),
+            None::<oxc_allocator::Box<TSTypeAnnotation>>,
+            Some(self.create_function_body(body)),
+        )
     }
 
     fn create_arrow_function_expression(
         &self,
-        _parameters: Vec<String>,
-        _body: ArrowFunctionBody<Self::Statement, Self::Expression>,
+        parameters: Vec<String>,
+        body: ArrowFunctionBody<Self::Statement, Self::Expression>,
     ) -> Self::Expression {
-        // TODO: Implement proper arrow function expression with lifetime management
-        todo!("create_arrow_function_expression requires proper lifetime handling")
+        let (expression, body) = match body {
+            ArrowFunctionBody::Expr(expression) => (
+                true,
+                self.builder.alloc_function_body(
+                    Span::default(),
+                    self.builder.vec(),
+                    self.builder.vec1(
+                        self.builder
+                            .statement_expression(Span::default(), expression),
+                    ),
+                ),
+            ),
+            ArrowFunctionBody::Stmt(statement) => (false, self.create_function_body(statement)),
+        };
+        self.builder.expression_arrow_function(
+            Span::default(),
+            expression,
+            false,
+            None::<oxc_allocator::Box<TSTypeParameterDeclaration>>,
+            self.create_parameters(parameters),
+            None::<oxc_allocator::Box<TSTypeAnnotation>>,
+            body,
+        )
     }
 
     fn create_dynamic_import(&self, url: Result<&str, Self::Expression>) -> Self::Expression {
@@ -430,36 +567,21 @@ impl<'a> AstFactory for TypeScriptAstFactory<'a> {
     fn create_tagged_template(
         &self,
         tag: Self::Expression,
-        _template: TemplateLiteral<Self::Expression>,
+        template: TemplateLiteral<Self::Expression>,
     ) -> Self::Expression {
-        // Implement template literal creation
-        // Placeholder
-        oxc_ast::ast::Expression::TaggedTemplateExpression(self.builder.alloc(
-            self.builder.tagged_template_expression(
-                Span::default(),
-                tag,
-                None::<oxc_allocator::Box<oxc_ast::ast::TSTypeParameterInstantiation>>,
-                self.builder.template_literal(
-                    Span::default(),
-                    self.builder.vec(),
-                    self.builder.vec(),
-                ),
-            ),
-        ))
+        self.builder.expression_tagged_template(
+            Span::default(),
+            tag,
+            None::<oxc_allocator::Box<oxc_ast::ast::TSTypeParameterInstantiation>>,
+            self.create_template(template),
+        )
     }
 
     fn create_template_literal(
         &self,
-        _template: TemplateLiteral<Self::Expression>,
+        template: TemplateLiteral<Self::Expression>,
     ) -> Self::Expression {
-        // Placeholder
-        oxc_ast::ast::Expression::TemplateLiteral(
-            self.builder.alloc(self.builder.template_literal(
-                Span::default(),
-                self.builder.vec(),
-                self.builder.vec(),
-            )),
-        )
+        Expression::TemplateLiteral(self.builder.alloc(self.create_template(template)))
     }
 
     fn create_throw_statement(&self, expression: Self::Expression) -> Self::Statement {
@@ -507,22 +629,51 @@ impl<'a> AstFactory for TypeScriptAstFactory<'a> {
 
     fn create_variable_declaration(
         &self,
-        _variable_name: &str,
-        _initializer: Option<Self::Expression>,
-        _type_: VariableDeclarationType,
+        variable_name: &str,
+        initializer: Option<Self::Expression>,
+        type_: VariableDeclarationType,
     ) -> Self::Statement {
-        // TODO: Implement proper variable declaration with lifetime management
-        // The challenge is that `&self` has a different lifetime than `'a` in the struct
-        todo!("create_variable_declaration requires proper lifetime handling")
+        let kind = match type_ {
+            VariableDeclarationType::Const => VariableDeclarationKind::Const,
+            VariableDeclarationType::Let => VariableDeclarationKind::Let,
+            VariableDeclarationType::Var => VariableDeclarationKind::Var,
+        };
+        let declarator = self.builder.variable_declarator(
+            Span::default(),
+            kind,
+            self.create_binding_pattern(variable_name),
+            initializer,
+            false,
+        );
+        Statement::VariableDeclaration(self.builder.alloc_variable_declaration(
+            Span::default(),
+            kind,
+            self.builder.vec1(declarator),
+            false,
+        ))
     }
 
     fn create_regular_expression_literal(
         &self,
-        _body: &str,
-        _flags: Option<&str>,
+        body: &str,
+        flags: Option<&str>,
     ) -> Self::Expression {
-        // TODO: Implement proper regex literal with lifetime management
-        todo!("create_regular_expression_literal requires proper lifetime handling")
+        let flags = flags.unwrap_or_default();
+        let regex = RegExp {
+            pattern: RegExpPattern {
+                text: self.alloc_atom(body),
+                pattern: None,
+            },
+            flags: flags
+                .chars()
+                .filter_map(|flag| RegExpFlags::try_from(flag).ok())
+                .fold(RegExpFlags::empty(), |all, flag| all | flag),
+        };
+        self.builder.expression_reg_exp_literal(
+            Span::default(),
+            regex,
+            Some(self.alloc_atom(&format!("/{}/{}", body, flags))),
+        )
     }
 
     fn set_source_map_range_for_stmt(
@@ -1,2 +1,3 @@
 pub mod import_manager_spec;
+pub mod translator_spec;
 pub mod typescript_ast_factory_spec;
@@ -0,0 +1,282 @@
+#[cfg(test)]
+mod tests {
+    use angular_compiler::output::output_ast as o;
+    use oxc_allocator::Allocator;
+    use oxc_ast::ast::Expression;
+    use oxc_ast::AstBuilder;
+    use oxc_codegen::{Codegen, CodegenOptions};
+
+    use crate::ngtsc::translator::src::api::ast_factory::AstFactory;
+    use crate::ngtsc::translator::src::api::import_generator::{ImportGenerator, ImportRequest};
+    use crate::ngtsc::translator::src::context::Context;
+    use crate::ngtsc::translator::src::translator::{
+        ExpressionTranslatorVisitor, TranslatorOptions,
+    };
+    use crate::ngtsc::translator::src::typescript_ast_factory::TypeScriptAstFactory;
+
+    /// Imports every symbol from the `i0` namespace.
+    struct NamespaceImports<'f, 'a> {
+        factory: &'f TypeScriptAstFactory<'a>,
+    }
+
+    impl<'f, 'a> ImportGenerator<(), Expression<'a>> for NamespaceImports<'f, 'a> {
+        fn add_import(&mut self, request: ImportRequest<()>) -> Expression<'a> {
+            self.factory.create_property_access(
+                self.factory.create_identifier("i0"),
+                &request.export_symbol_name.unwrap(),
+            )
+        }
+    }
+
+    fn translate_with(expr: &o::Expression, downlevel_tagged_templates: bool) -> String {
+        let allocator = Allocator::default();
+        let factory = TypeScriptAstFactory::new(AstBuilder::new(&allocator), false);
+        let mut imports = NamespaceImports { factory: &factory };
+        let mut translator = ExpressionTranslatorVisitor::new(
+            &factory,
+            &mut imports,
+            (),
+            TranslatorOptions {
+                downlevel_tagged_templates,
+                downlevel_variable_declarations: false,
+                record_wrapped_node: None,
+                annotate_for_closure_compiler: false,
+            },
+        );
+        let translated = translator.visit_expression(expr, Context::new(false));
+
+        let mut codegen = Codegen::new().with_options(CodegenOptions {
+            single_quote: true,
+            ..CodegenOptions::default()
+        });
+        codegen.print_expression(&translated);
+        codegen.into_source_text()
+    }
+
+    fn translate(expr: &o::Expression) -> String {
+        translate_with(expr, false)
+    }
+
+    fn param(name: &str) -> o::FnParam {
+        o::FnParam {
+            name: name.to_string(),
+            type_: None,
+        }
+    }
+
+    fn binary(
+        lhs: Box<o::Expression>,
+        operator: o::BinaryOperator,
+        rhs: Box<o::Expression>,
+    ) -> o::Expression {
+        o::Expression::BinaryOp(o::BinaryOperatorExpr {
+            operator,
+            lhs,
+            rhs,
+            type_: None,
+            source_span: None,
+        })
+    }
+
+    fn template_literal(texts: &[&str], expressions: Vec<o::Expression>) -> o::TemplateLiteral {
+        o::TemplateLiteral {
+            elements: texts
+                .iter()
+                .map(|text| o::TemplateLiteralElement {
+                    text: text.to_string(),
+                    raw_text: text.to_string(),
+                    source_span: None,
+                })
+                .collect(),
+            expressions,
+        }
+    }
+
+    #[test]
+    fn should_translate_arrow_function_with_expression_body() {
+        let expr = o::arrow_fn(
+            vec![param("a")],
+            o::ArrowFunctionBody::Expression(Box::new(binary(
+                o::variable("a"),
+                o::BinaryOperator::Plus,
+                o::literal(1.0),
+            ))),
+            None,
+        );
+
+        assert_eq!(translate(&expr), "(a) => a + 1");
+    }
+
+    #[test]
+    fn should_translate_arrow_function_with_statement_body() {
+        let expr = o::arrow_fn(
+            vec![param("a"), param("b")],
+            o::ArrowFunctionBody::Statements(vec![
+                o::Statement::DeclareVar(o::DeclareVarStmt {
+                    name: "sum".to_string(),
+                    value: Some(Box::new(binary(
+                        o::variable("a"),
+                        o::BinaryOperator::Plus,
+                        o::variable("b"),
+                    ))),
+                    type_: None,
+                    modifiers: o::StmtModifier::Final,
+                    source_span: None,
+                }),
+                o::Statement::Return(o::ReturnStatement {
+                    value: o::variable("sum"),
+                    source_span: None,
+                }),
+            ]),
+            None,
+        );
+
+        assert_eq!(
+            translate(&expr),
+            "(a, b) => {\n\tconst sum = a + b;\n\treturn sum;\n}"
+        );
+    }
+
+    #[test]
+    fn should_translate_function_expression() {
+        let expr = o::Expression::Fn(o::FunctionExpr {
+            params: vec![param("t")],
+            statements: vec![o::Statement::Return(o::ReturnStatement {
+                value: o::variable("t"),
+                source_span: None,
+            })],
+            type_: None,
+            source_span: None,
+            name: Some("Cmp_Factory".to_string()),
+        });
+
+        assert_eq!(
+            translate(&expr),
+            "(function Cmp_Factory(t) {\n\treturn t;\n})"
+        );
+    }
+
+    #[test]
+    fn should_translate_template_literal() {
+        let template = template_literal(&["Hello ", "!"], vec![*o::variable("name")]);
+        let expr = o::Expression::TemplateLiteral(o::TemplateLiteralExpr {
+            elements: template.elements,
+            expressions: template.expressions,
+        });
+
+        assert_eq!(translate(&expr), "`Hello ${name}!`");
+    }
+
+    #[test]
+    fn should_translate_tagged_template() {
+        let expr = o::Expression::TaggedTemplate(o::TaggedTemplateLiteralExpr {
+            tag: o::variable("tag"),
+            template: template_literal(&["a", "b"], vec![*o::variable("x")]),
+            type_: None,
+            source_span: None,
+        });
+
+        assert_eq!(translate(&expr), "tag`a${x}b`");
+        assert_eq!(
+            translate_with(&expr, true),
+            "tag(i0.__makeTemplateObject(['a', 'b'], ['a', 'b']), x)"
+        );
+    }
+
+    #[test]
+    fn should_translate_typeof_and_void() {
+        let type_of = o::Expression::TypeOf(o::TypeofExpr {
+            expr: o::variable("x"),
+            type_: None,
+            source_span: None,
+        });
+        let void = o::Expression::Void(o::VoidExpr {
+            expr: o::literal(0.0),
+            type_: None,
+            source_span: None,
+        });
+
+        assert_eq!(translate(&type_of), "typeof x");
+        assert_eq!(translate(&void), "void 0");
+    }
+
+    #[test]
+    fn should_translate_property_writes_and_reads() {
+        let write = o::Expression::WriteProp(o::WritePropExpr {
+            receiver: o::variable("ctx"),
+            name: "value".to_string(),
+            value: Box::new(o::Expression::ReadKey(o::ReadKeyExpr {
+                receiver: o::variable("items"),
+                index: o::literal(0.0),
+                type_: None,
+                source_span: None,
+            })),
+            type_: None,
+            source_span: None,
+        });
+
+        assert_eq!(translate(&write), "ctx.value = items[0]");
+    }
+
+    #[test]
+    fn should_translate_newer_binary_operators() {
+        let nullish = binary(
+            o::variable("a"),
+            o::BinaryOperator::NullishCoalesce,
+            o::variable("b"),
+        );
+        let in_expr = binary(o::literal("key"), o::BinaryOperator::In, o::variable("obj"));
+        let and_assign = binary(
+            o::variable("a"),
+            o::BinaryOperator::AndAssignment,
+            o::variable("b"),
+        );
+
+        assert_eq!(translate(&nullish), "a ?? b");
+        assert_eq!(translate(&in_expr), "'key' in obj");
+        assert_eq!(translate(&and_assign), "a &&= b");
+    }
+
+    #[test]
+    fn should_translate_dynamic_import_and_instantiation() {
+        let import = o::Expression::DynamicImport(o::DynamicImportExpr {
+            url: o::DynamicImportUrl::Literal("./lazy".to_string()),
+            url_comment: None,
+            source_span: None,
+        });
+        let instantiate = o::Expression::Instantiate(o::InstantiateExpr {
+            class_expr: o::import_expr("@angular/core", "EventEmitter"),
+            args: vec![*o::literal(true)],
+            type_: None,
+            source_span: None,
+        });
+
+        assert_eq!(translate(&import), "import('./lazy')");
+        assert_eq!(translate(&instantiate), "new i0.EventEmitter(true)");
+    }
+
+    #[test]
+    fn should_translate_literal_map_and_regular_expression() {
+        let map = o::literal_map(vec![
+            o::LiteralMapEntry {
+                key: "a".to_string(),
+                value: o::literal(1.0),
+                quoted: false,
+            },
+            o::LiteralMapEntry {
+                key: "data-b".to_string(),
+                value: o::literal_arr(vec![*o::literal("x")]),
+                quoted: true,
+            },
+        ]);
+        let regex = o::Expression::RegularExpressionLiteral(o::RegularExpressionLiteralExpr {
+            pattern: "ab+c".to_string(),
+            flags: "gi".to_string(),
+            type_: None,
+            source_span: None,
+        });
+
+        assert_eq!(translate(&map), "({\n\ta: 1,\n\t'data-b': ['x']\n})");
+        assert_eq!(translate(&regex), "/ab+c/gi");
+    }
+}
@@ -3,8 +3,10 @@
 // Corresponds to NgTscPlugin class in tsc_plugin.ts
 
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use super::host::{PluginCompilerHost, SimplePluginCompilerHost};
+use crate::ngtsc::typecheck::{TemplateCheck, TemplateTypeCheckerImpl, TypeCheckingConfig};
 
 /// Compilation setup result containing files to ignore for diagnostics and emit.
 #[derive(Debug, Clone, Default)]
@@ -112,6 +114,8 @@ pub struct NgTscPlugin {
     ignore_for_diagnostics: HashSet<String>,
     /// Files to ignore for emit.
     ignore_for_emit: HashSet<String>,
+    /// Template checks supplied by other crates, e.g. linters.
+    template_checks: Vec<Arc<dyn TemplateCheck>>,
 }
 
 /// Angular-specific compiler options.
@@ -156,9 +160,27 @@ impl NgTscPlugin {
             program: None,
             ignore_for_diagnostics: HashSet::new(),
             ignore_for_emit: HashSet::new(),
+            template_checks: Vec::new(),
         }
     }
 
+    /// Register a check that runs over every template alongside the built-in extended checks.
+    pub fn register_template_check(&mut self, check: impl TemplateCheck + 'static) {
+        self.template_checks.push(Arc::new(check));
+    }
+
+    /// Create a template type-checker that runs the registered template checks.
+    pub fn create_template_type_checker(
+        &self,
+        config: TypeCheckingConfig,
+    ) -> TemplateTypeCheckerImpl {
+        let mut checker = TemplateTypeCheckerImpl::new(config);
+        for check in &self.template_checks {
+            checker.register_template_check(check.clone());
+        }
+        checker
+    }
+
     /// Get the compiler (panics if setupCompilation hasn't been called).
     pub fn compiler(&self) -> Result<&Program, String> {
         self.program
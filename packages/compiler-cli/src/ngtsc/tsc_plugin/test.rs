@@ -77,4 +77,66 @@ mod tests {
             assert_eq!(options.compilation_mode, CompilationMode::Full);
         }
     }
+
+    mod template_check_tests {
+        use super::*;
+        use crate::ngtsc::typecheck::{
+            for_each_template_node, TemplateCheck, TemplateContext, TemplateTypeChecker,
+            TypeCheckError, TypeCheckingConfig,
+        };
+        use angular_compiler::render3::r3_ast as t;
+
+        /// Flags the obsolete `<marquee>` element.
+        struct NoMarqueeCheck;
+
+        impl TemplateCheck for NoMarqueeCheck {
+            fn name(&self) -> &str {
+                "noMarquee"
+            }
+
+            fn code(&self) -> i32 {
+                9001
+            }
+
+            fn run(&self, ctx: &TemplateContext, nodes: &[t::R3Node]) -> Vec<TypeCheckError> {
+                let mut diagnostics = Vec::new();
+                for_each_template_node(nodes, &mut |node| {
+                    if let t::R3Node::Element(el) = node {
+                        if &*el.name == "marquee" {
+                            diagnostics.push(ctx.make_template_diagnostic(
+                                self.code(),
+                                &el.start_source_span,
+                                "<marquee> is obsolete.",
+                            ));
+                        }
+                    }
+                });
+                diagnostics
+            }
+        }
+
+        #[test]
+        fn should_collect_diagnostics_of_registered_template_checks() {
+            let mut plugin = NgTscPlugin::new(NgCompilerOptions::default());
+            plugin.register_template_check(NoMarqueeCheck);
+            let mut checker = plugin.create_template_type_checker(TypeCheckingConfig::default());
+
+            let template = "<div>@if (show) {<marquee>Hi</marquee>}</div>";
+            let result = checker.type_check_component("TestCmp", template);
+
+            // Extended checks report warnings, which don't fail the compilation.
+            assert!(result.success);
+            assert_eq!(result.diagnostics.len(), 1);
+            let diagnostic = &result.diagnostics[0];
+            assert_eq!(diagnostic.code, "NG9001");
+            assert_eq!(diagnostic.category, ts::DiagnosticCategory::Warning);
+            assert_eq!(diagnostic.message, "<marquee> is obsolete.");
+            assert_eq!(diagnostic.start, template.find("<marquee>"));
+            assert_eq!(diagnostic.length, Some("<marquee>".len()));
+            assert_eq!(checker.get_diagnostics_for_component("TestCmp").len(), 1);
+
+            let clean = checker.type_check_component("OtherCmp", "<div>Hi</div>");
+            assert!(clean.success);
+        }
+    }
 }
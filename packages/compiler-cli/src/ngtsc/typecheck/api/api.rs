@@ -3,16 +3,23 @@
 // Public API types for template type-checking.
 
 use std::collections::HashMap;
+use ts::{DiagnosticCategory, DiagnosticRelatedInformation};
 
 /// Configuration for type-checking.
 #[derive(Debug, Clone)]
 pub struct TypeCheckingConfig {
     /// Whether to apply fullTemplateTypeCheck mode.
     pub apply_full_template_type_check_mode: bool,
-    /// Whether to check variable types.
-    pub check_type_of_inputs: bool,
-    /// Whether to check directives.
-    pub check_type_of_outputs: bool,
+    /// Whether to check the bodies of embedded templates (`checkTemplateBodies`).
+    pub check_template_bodies: bool,
+    /// Whether to check the types of directive input bindings (`checkTypeOfInputBindings`).
+    pub check_type_of_input_bindings: bool,
+    /// Whether `null` and `undefined` are reported when bound to inputs that don't accept them
+    /// (`strictNullInputTypes`).
+    pub strict_null_input_types: bool,
+    /// Whether to check the type of `$event` in directive output handlers
+    /// (`checkTypeOfOutputEvents`).
+    pub check_type_of_output_events: bool,
     /// Whether to use strict null checks.
     pub strict_null_checks: bool,
     /// Whether to honor access modifiers.
@@ -35,8 +42,10 @@ impl Default for TypeCheckingConfig {
     fn default() -> Self {
         Self {
             apply_full_template_type_check_mode: false,
-            check_type_of_inputs: true,
-            check_type_of_outputs: true,
+            check_template_bodies: true,
+            check_type_of_input_bindings: true,
+            strict_null_input_types: true,
+            check_type_of_output_events: true,
             strict_null_checks: true,
             honor_access_modifiers: true,
             check_type_of_queries: true,
@@ -134,10 +143,40 @@ pub struct TypeCheckError {
     pub message: String,
     /// Error code.
     pub code: String,
+    /// Whether the error fails the compilation or is only reported, as for extended checks.
+    pub category: DiagnosticCategory,
     /// File path.
     pub file: Option<String>,
     /// Start position.
     pub start: Option<usize>,
     /// Error length.
     pub length: Option<usize>,
+    /// Other locations relevant to the error, e.g. the declaration of a missing input.
+    pub related_information: Option<Vec<DiagnosticRelatedInformation>>,
+    /// Edit that an IDE can offer as a quick-fix.
+    pub fix: Option<TemplateFix>,
+}
+
+/// A text edit that resolves a diagnostic, in the template unless `file` says otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateFix {
+    /// The file to edit, when it isn't the template, e.g. the component's source file.
+    pub file: Option<String>,
+    /// Start offset of the replaced text.
+    pub start: usize,
+    /// Length of the replaced text; zero for an insertion.
+    pub length: usize,
+    /// Text to insert.
+    pub new_text: String,
+}
+
+/// Where an entry can be added to the `imports` array of a standalone component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportsInsertionPoint {
+    /// The component's source file.
+    pub file: String,
+    /// Offset of the closing `]` of the `imports` array.
+    pub offset: usize,
+    /// Whether the array already has entries, so a new one needs a separator.
+    pub has_entries: bool,
 }
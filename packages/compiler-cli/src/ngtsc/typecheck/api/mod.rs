@@ -6,8 +6,8 @@ pub mod symbols;
 
 // Re-exports
 pub use api::{
-    ControlFlowPrevention, PendingTypeCheckBlock, TcbLocation, TypeCheckBlockMetadata,
-    TypeCheckContext, TypeCheckError, TypeCheckOp, TypeCheckingConfig,
+    ControlFlowPrevention, ImportsInsertionPoint, PendingTypeCheckBlock, TcbLocation, TemplateFix,
+    TypeCheckBlockMetadata, TypeCheckContext, TypeCheckError, TypeCheckOp, TypeCheckingConfig,
 };
 pub use checker::{TemplateTypeChecker, TypeCheckResult};
 pub use symbols::{
@@ -5,8 +5,12 @@
 use super::super::api::{
     TemplateTypeChecker, TypeCheckContext, TypeCheckError, TypeCheckResult, TypeCheckingConfig,
 };
+use super::extended::TemplateCheck;
 use super::type_check_block::TypeCheckBlockGenerator;
+use crate::ngtsc::metadata::{DirectiveMeta, T2DirectiveMetadata};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use ts::DiagnosticCategory;
 
 /// Implementation of the template type-checker.
 pub struct TemplateTypeCheckerImpl {
@@ -18,6 +22,10 @@ pub struct TemplateTypeCheckerImpl {
     cached_diagnostics: HashMap<String, Vec<TypeCheckError>>,
     /// Global context.
     context: TypeCheckContext,
+    /// Extended checks run over every template.
+    template_checks: Vec<Arc<dyn TemplateCheck>>,
+    /// The declared member types of each component, keyed by component name.
+    member_types: HashMap<String, HashMap<String, String>>,
 }
 
 impl TemplateTypeCheckerImpl {
@@ -27,11 +35,60 @@ impl TemplateTypeCheckerImpl {
             checked_components: HashSet::new(),
             cached_diagnostics: HashMap::new(),
             context: TypeCheckContext::new(),
+            template_checks: Vec::new(),
+            member_types: HashMap::new(),
         }
     }
 
+    /// Run `check` alongside the built-in checks. Components checked before are checked again.
+    pub fn register_template_check(&mut self, check: Arc<dyn TemplateCheck>) {
+        self.template_checks.push(check);
+        self.invalidate_all();
+    }
+
+    /// Declare the types of a component's members, which the extended checks use to reason
+    /// about template expressions. The component is checked again.
+    pub fn register_component_members(
+        &mut self,
+        component: &str,
+        members: HashMap<String, String>,
+    ) {
+        self.member_types.insert(component.to_string(), members);
+        self.invalidate(component);
+    }
+
+    /// Type-check a component from its metadata, with the member types declared by its class.
+    /// Directives without a template have nothing to check.
+    pub fn type_check_directive(
+        &mut self,
+        meta: &DirectiveMeta<'_>,
+        directives: &[T2DirectiveMetadata],
+    ) -> TypeCheckResult {
+        let Some(component_meta) = &meta.component else {
+            return TypeCheckResult::success();
+        };
+        let Some(template) = &component_meta.template else {
+            return TypeCheckResult::success();
+        };
+        let component = &meta.t2.name;
+        if self.member_types.get(component) != Some(&component_meta.member_types) {
+            self.register_component_members(component, component_meta.member_types.clone());
+        }
+        self.type_check_component_with_directives(component, template, directives)
+    }
+
     /// Type-check a component.
     pub fn type_check_component(&mut self, component: &str, template: &str) -> TypeCheckResult {
+        self.type_check_component_with_directives(component, template, &[])
+    }
+
+    /// Type-check a component whose template can use the given directives.
+    pub fn type_check_component_with_directives(
+        &mut self,
+        component: &str,
+        template: &str,
+        directives: &[T2DirectiveMetadata],
+    ) -> TypeCheckResult {
         if self.checked_components.contains(component) {
             // Return cached result
             let diagnostics = self
@@ -40,20 +97,28 @@ impl TemplateTypeCheckerImpl {
                 .cloned()
                 .unwrap_or_default();
             return TypeCheckResult {
-                success: diagnostics.is_empty(),
+                success: !has_errors(&diagnostics),
                 diagnostics,
             };
         }
 
         // Generate type-check block
         let mut generator = TypeCheckBlockGenerator::new(self.config.clone());
-        let result = generator.generate(component, template);
+        for check in &self.template_checks {
+            generator.add_template_check(check.clone());
+        }
+        if let Some(members) = self.member_types.get(component) {
+            for (name, type_) in members {
+                generator.set_member_type(name.clone(), type_.clone());
+            }
+        }
+        let result = generator.generate_with_directives(component, template, directives);
 
         let diagnostics = match result {
             Ok(_tcb) => {
                 // In a real implementation, we would feed the TCB to TypeScript
-                // and collect diagnostics. For now, return empty.
-                Vec::new()
+                // and collect diagnostics. For now, only report out-of-band ones.
+                generator.out_of_band_diagnostics().to_vec()
             }
             Err(e) => vec![e],
         };
@@ -63,12 +128,19 @@ impl TemplateTypeCheckerImpl {
             .insert(component.to_string(), diagnostics.clone());
 
         TypeCheckResult {
-            success: diagnostics.is_empty(),
+            success: !has_errors(&diagnostics),
             diagnostics,
         }
     }
 }
 
+/// Whether any diagnostic fails the compilation. Extended checks only report warnings.
+fn has_errors(diagnostics: &[TypeCheckError]) -> bool {
+    diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.category == DiagnosticCategory::Error)
+}
+
 impl TemplateTypeChecker for TemplateTypeCheckerImpl {
     fn get_diagnostics_for_component(&self, component: &str) -> Vec<TypeCheckError> {
         self.cached_diagnostics
@@ -2,7 +2,9 @@
 //
 // Template type-check diagnostics handling.
 
-use super::super::api::TypeCheckError;
+use super::super::api::{TemplateFix, TypeCheckError};
+use angular_compiler::render3::r3_ast as t;
+use ts::{DiagnosticCategory, DiagnosticRelatedInformation};
 
 /// Diagnostic code for template errors.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +21,12 @@ pub enum TemplateDiagnosticCode {
     TypeMismatch = 8100,
     /// Required input not provided.
     MissingRequiredInput = 8101,
+    /// The left side of `??` can't be `null` or `undefined`.
+    NullishCoalescingNotNullable = 8102,
+    /// A known structural directive is used but not imported.
+    MissingControlFlowDirective = 8103,
+    /// An attribute looks like a binding but is written as plain text or an interpolation.
+    TextAttributeNotBinding = 8104,
     /// Unknown element.
     UnknownElement = 8001,
 }
@@ -41,9 +49,12 @@ pub fn create_unknown_property_diagnostic(
             property, element
         ),
         code: TemplateDiagnosticCode::UnknownProperty.code(),
+        category: DiagnosticCategory::Error,
         file: Some(file.to_string()),
         start: None,
         length: None,
+        related_information: None,
+        fix: None,
     }
 }
 
@@ -52,21 +63,62 @@ pub fn create_unknown_element_diagnostic(file: &str, element: &str) -> TypeCheck
     TypeCheckError {
         message: format!("'{}' is not a known element", element),
         code: TemplateDiagnosticCode::UnknownElement.code(),
+        category: DiagnosticCategory::Error,
         file: Some(file.to_string()),
         start: None,
         length: None,
+        related_information: None,
+        fix: None,
     }
 }
 
 /// Create a diagnostic for missing pipe.
-pub fn create_missing_pipe_diagnostic(file: &str, pipe_name: &str) -> TypeCheckError {
+///
+/// When one of `pipes_in_scope` is a likely misspelling target, the message suggests it.
+pub fn create_missing_pipe_diagnostic(
+    file: &str,
+    pipe_name: &str,
+    pipes_in_scope: &[String],
+) -> TypeCheckError {
+    let mut message = format!("The pipe '{}' could not be found", pipe_name);
+    if let Some(suggestion) = closest_name(pipe_name, pipes_in_scope) {
+        message.push_str(&format!(". Did you mean '{}'?", suggestion));
+    }
     TypeCheckError {
-        message: format!("The pipe '{}' could not be found", pipe_name),
+        message,
         code: TemplateDiagnosticCode::MissingPipe.code(),
+        category: DiagnosticCategory::Error,
         file: Some(file.to_string()),
         start: None,
         length: None,
+        related_information: None,
+        fix: None,
+    }
+}
+
+/// The candidate closest to `name`, if it is within a third of the name's length in edits.
+fn closest_name<'a>(name: &str, candidates: &'a [String]) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|candidate| (levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
     }
+    prev[b.len()]
 }
 
 /// Create a diagnostic for type mismatch.
@@ -74,26 +126,138 @@ pub fn create_type_mismatch_diagnostic(file: &str, expected: &str, actual: &str)
     TypeCheckError {
         message: format!("Type '{}' is not assignable to type '{}'", actual, expected),
         code: TemplateDiagnosticCode::TypeMismatch.code(),
+        category: DiagnosticCategory::Error,
         file: Some(file.to_string()),
         start: None,
         length: None,
+        related_information: None,
+        fix: None,
     }
 }
 
+/// Where a directive input is declared, e.g. `value = input.required<string>()`.
+#[derive(Debug, Clone)]
+pub struct InputDeclaration {
+    /// File containing the directive class.
+    pub file: String,
+    /// Start offset of the input declaration.
+    pub start: usize,
+    /// Length of the input declaration.
+    pub length: usize,
+}
+
 /// Create a diagnostic for missing required input.
+///
+/// The diagnostic covers the element's start tag. It points at the input's declaration, when
+/// known, and suggests adding an empty binding right after the tag name.
 pub fn create_missing_required_input_diagnostic(
     file: &str,
+    element: &t::Element,
     directive: &str,
     input: &str,
+    declaration: Option<&InputDeclaration>,
 ) -> TypeCheckError {
+    let start_tag = &element.start_source_span;
+    let related_information = declaration.map(|decl| {
+        vec![DiagnosticRelatedInformation {
+            category: DiagnosticCategory::Message,
+            code: 0,
+            file: Some(decl.file.clone()),
+            start: Some(decl.start),
+            length: Some(decl.length),
+            message_text: format!("The input '{}' is declared here.", input),
+        }]
+    });
+
     TypeCheckError {
         message: format!(
             "Required input '{}' from directive '{}' must be specified",
             input, directive
         ),
         code: TemplateDiagnosticCode::MissingRequiredInput.code(),
+        category: DiagnosticCategory::Error,
         file: Some(file.to_string()),
-        start: None,
-        length: None,
+        start: Some(start_tag.start.offset),
+        length: Some(start_tag.end.offset - start_tag.start.offset),
+        related_information,
+        fix: Some(TemplateFix {
+            file: None,
+            start: start_tag.start.offset + 1 + element.name.len(),
+            length: 0,
+            new_text: format!(" [{}]=\"\"", input),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use angular_compiler::render3::view::template::parse_template;
+
+    #[test]
+    fn test_missing_required_input_points_at_declaration() {
+        let source = "class ValueDir {\n  value = input.required<string>();\n}";
+        let template = "<div><span valueDir></span></div>";
+        let parsed = parse_template(template, "", Default::default());
+        let t::R3Node::Element(div) = &parsed.nodes[0] else {
+            panic!("expected an element");
+        };
+        let t::R3Node::Element(span) = &div.children[0] else {
+            panic!("expected an element");
+        };
+        let declaration = InputDeclaration {
+            file: "value_dir.ts".to_string(),
+            start: source.find("value =").unwrap(),
+            length: "value = input.required<string>()".len(),
+        };
+
+        let diag = create_missing_required_input_diagnostic(
+            "app.html",
+            span,
+            "ValueDir",
+            "value",
+            Some(&declaration),
+        );
+
+        assert_eq!(diag.code, "NG8101");
+        assert_eq!(diag.start, template.find("<span"));
+        assert_eq!(diag.length, Some("<span valueDir>".len()));
+
+        let related = diag.related_information.unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].file.as_deref(), Some("value_dir.ts"));
+        let decl_start = related[0].start.unwrap();
+        let decl_end = decl_start + related[0].length.unwrap();
+        assert_eq!(
+            &source[decl_start..decl_end],
+            "value = input.required<string>()"
+        );
+        assert_eq!(
+            related[0].message_text,
+            "The input 'value' is declared here."
+        );
+
+        let fix = diag.fix.unwrap();
+        let mut fixed = template.to_string();
+        fixed.replace_range(fix.start..fix.start + fix.length, &fix.new_text);
+        assert_eq!(fixed, "<div><span [value]=\"\" valueDir></span></div>");
+    }
+
+    #[test]
+    fn test_missing_pipe_suggests_nearest_name() {
+        let pipes = vec![
+            "lowercase".to_string(),
+            "uppercase".to_string(),
+            "date".to_string(),
+        ];
+        let diag = create_missing_pipe_diagnostic("app.html", "uppercas", &pipes);
+        assert_eq!(diag.code, "NG8004");
+        assert_eq!(
+            diag.message,
+            "The pipe 'uppercas' could not be found. Did you mean 'uppercase'?"
+        );
+
+        let diag = create_missing_pipe_diagnostic("app.html", "currency", &pipes);
+        assert_eq!(diag.message, "The pipe 'currency' could not be found");
     }
 }
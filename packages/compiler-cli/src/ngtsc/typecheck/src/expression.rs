@@ -0,0 +1,202 @@
+// TypeCheck Expression Translation
+//
+// Translates template expressions into the TypeScript used inside a type-check block.
+
+use angular_compiler::expression_parser::ast::{LiteralPrimitive, AST};
+
+/// Resolves the template-specific parts of an expression.
+pub trait TcbExpressionResolver {
+    /// Resolve a read of `name` on the implicit receiver to a template variable, if it is one.
+    fn resolve_name(&self, name: &str) -> Option<String>;
+
+    /// The expression for the instance of the pipe with the given name.
+    fn resolve_pipe(&mut self, name: &str) -> String;
+}
+
+/// Translate a template expression. Names not resolved by `resolver` are read from `ctx`.
+pub fn translate_expression(ast: &AST, resolver: &mut dyn TcbExpressionResolver) -> String {
+    match ast {
+        AST::PropertyRead(read) => {
+            if matches!(*read.receiver, AST::ImplicitReceiver(_)) {
+                resolver
+                    .resolve_name(&read.name)
+                    .unwrap_or_else(|| format!("ctx.{}", read.name))
+            } else {
+                format!(
+                    "{}.{}",
+                    translate_expression(&read.receiver, resolver),
+                    read.name
+                )
+            }
+        }
+        AST::SafePropertyRead(read) => format!(
+            "{}?.{}",
+            translate_expression(&read.receiver, resolver),
+            read.name
+        ),
+        AST::PropertyWrite(write) => {
+            let receiver = if matches!(*write.receiver, AST::ImplicitReceiver(_)) {
+                "ctx".to_string()
+            } else {
+                translate_expression(&write.receiver, resolver)
+            };
+            format!(
+                "{}.{} = {}",
+                receiver,
+                write.name,
+                translate_expression(&write.value, resolver)
+            )
+        }
+        AST::KeyedRead(read) => format!(
+            "{}[{}]",
+            translate_expression(&read.receiver, resolver),
+            translate_expression(&read.key, resolver)
+        ),
+        AST::SafeKeyedRead(read) => format!(
+            "{}?.[{}]",
+            translate_expression(&read.receiver, resolver),
+            translate_expression(&read.key, resolver)
+        ),
+        AST::KeyedWrite(write) => format!(
+            "{}[{}] = {}",
+            translate_expression(&write.receiver, resolver),
+            translate_expression(&write.key, resolver),
+            translate_expression(&write.value, resolver)
+        ),
+        // `$any(x)` opts `x` out of type checking. It is only the built-in when the name isn't
+        // shadowed by a template variable.
+        AST::Call(call) if is_any_cast(ast) && resolver.resolve_name("$any").is_none() => {
+            format!("({} as any)", translate_expression(&call.args[0], resolver))
+        }
+        AST::Call(call) => format!(
+            "{}({})",
+            translate_expression(&call.receiver, resolver),
+            translate_list(&call.args, resolver)
+        ),
+        AST::SafeCall(call) => format!(
+            "{}?.({})",
+            translate_expression(&call.receiver, resolver),
+            translate_list(&call.args, resolver)
+        ),
+        AST::BindingPipe(pipe) => {
+            let instance = resolver.resolve_pipe(&pipe.name);
+            let mut args = vec![translate_expression(&pipe.exp, resolver)];
+            args.extend(
+                pipe.args
+                    .iter()
+                    .map(|arg| translate_expression(arg, resolver)),
+            );
+            format!("{}.transform({})", instance, args.join(", "))
+        }
+        AST::Binary(binary) => format!(
+            "({} {} {})",
+            translate_expression(&binary.left, resolver),
+            binary.operation,
+            translate_expression(&binary.right, resolver)
+        ),
+        AST::Conditional(cond) => format!(
+            "({} ? {} : {})",
+            translate_expression(&cond.condition, resolver),
+            translate_expression(&cond.true_exp, resolver),
+            translate_expression(&cond.false_exp, resolver)
+        ),
+        AST::PrefixNot(not) => format!("!{}", translate_expression(&not.expression, resolver)),
+        AST::Unary(unary) => format!(
+            "{}{}",
+            unary.operator,
+            translate_expression(&unary.expr, resolver)
+        ),
+        AST::NonNullAssert(assert) => {
+            format!("{}!", translate_expression(&assert.expression, resolver))
+        }
+        AST::TypeofExpression(expr) => {
+            format!(
+                "typeof {}",
+                translate_expression(&expr.expression, resolver)
+            )
+        }
+        AST::VoidExpression(expr) => {
+            format!("void {}", translate_expression(&expr.expression, resolver))
+        }
+        AST::ParenthesizedExpression(expr) => {
+            format!("({})", translate_expression(&expr.expression, resolver))
+        }
+        AST::LiteralPrimitive(literal) => match literal {
+            LiteralPrimitive::String { value, .. } => format!("{:?}", value),
+            LiteralPrimitive::Number { value, .. } => value.to_string(),
+            LiteralPrimitive::Boolean { value, .. } => value.to_string(),
+            LiteralPrimitive::Null { .. } => "null".to_string(),
+            LiteralPrimitive::Undefined { .. } => "undefined".to_string(),
+        },
+        AST::LiteralArray(array) => format!("[{}]", translate_list(&array.expressions, resolver)),
+        AST::LiteralMap(map) => {
+            let entries: Vec<String> = map
+                .keys
+                .iter()
+                .zip(map.values.iter())
+                .map(|(key, value)| {
+                    format!("{:?}: {}", key.key, translate_expression(value, resolver))
+                })
+                .collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+        AST::Interpolation(interpolation) => {
+            let mut parts = Vec::new();
+            for (idx, string) in interpolation.strings.iter().enumerate() {
+                if idx == 0 || !string.is_empty() {
+                    parts.push(format!("{:?}", string));
+                }
+                if let Some(expr) = interpolation.expressions.get(idx) {
+                    parts.push(format!("({})", translate_expression(expr, resolver)));
+                }
+            }
+            parts.join(" + ")
+        }
+        AST::TemplateLiteral(literal) => {
+            let mut result = String::from("`");
+            for (idx, element) in literal.elements.iter().enumerate() {
+                result.push_str(&element.text);
+                if let Some(expr) = literal.expressions.get(idx) {
+                    result.push_str("${");
+                    result.push_str(&translate_expression(expr, resolver));
+                    result.push('}');
+                }
+            }
+            result.push('`');
+            result
+        }
+        AST::Chain(chain) => chain
+            .expressions
+            .iter()
+            .map(|expr| translate_expression(expr, resolver))
+            .collect::<Vec<_>>()
+            .join("; "),
+        AST::ImplicitReceiver(_) | AST::ThisReceiver(_) => "ctx".to_string(),
+        // Expressions that don't carry type information for the check.
+        AST::TaggedTemplateLiteral(_) | AST::RegularExpressionLiteral(_) | AST::EmptyExpr(_) => {
+            "(null as any)".to_string()
+        }
+    }
+}
+
+/// Whether `ast` is a call to `$any` with a single argument, read from the component context.
+pub fn is_any_cast(ast: &AST) -> bool {
+    let AST::Call(call) = ast else {
+        return false;
+    };
+    match call.receiver.as_ref() {
+        AST::PropertyRead(read) => {
+            read.name == "$any"
+                && matches!(*read.receiver, AST::ImplicitReceiver(_))
+                && call.args.len() == 1
+        }
+        _ => false,
+    }
+}
+
+fn translate_list(args: &[Box<AST>], resolver: &mut dyn TcbExpressionResolver) -> String {
+    args.iter()
+        .map(|arg| translate_expression(arg, resolver))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
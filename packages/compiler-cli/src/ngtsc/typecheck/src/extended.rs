@@ -0,0 +1,521 @@
+// Extended Template Checks
+//
+// Checks that inspect a bound template and report diagnostics which aren't type errors, e.g.
+// lints. Custom checks are registered through the tsc plugin and run next to the built-in ones.
+
+use super::super::api::{ImportsInsertionPoint, TemplateFix, TypeCheckError};
+use super::diagnostics::TemplateDiagnosticCode;
+use super::expression::is_any_cast;
+use crate::ngtsc::metadata::T2DirectiveMetadata;
+use angular_compiler::expression_parser::ast::{AbsoluteSourceSpan, BindingType, AST};
+use angular_compiler::parse_util::ParseSourceSpan;
+use angular_compiler::render3::r3_ast as t;
+use angular_compiler::render3::view::t2_api::{BoundTarget, DirectiveOwner};
+use std::collections::HashMap;
+use std::sync::Arc;
+use ts::DiagnosticCategory;
+
+/// The template being checked, with what the binder found in it.
+pub struct TemplateContext<'a> {
+    /// The name of the component that owns the template.
+    pub component: &'a str,
+    /// The bound template, which resolves the directives, references and pipes of its nodes.
+    pub bound: &'a dyn BoundTarget<T2DirectiveMetadata>,
+    /// The declared types of the component members, e.g. `title` -> `string | null`, as `ctx`
+    /// is typed in the type-check block.
+    pub member_types: &'a HashMap<String, String>,
+    /// Whether `strictNullChecks` is enabled. Without it, every type includes `null`.
+    pub strict_null_checks: bool,
+    /// Whether the component is standalone, and so imports its own dependencies.
+    pub is_standalone: bool,
+    /// Where missing imports can be added to the component, when known.
+    pub imports_insertion: Option<&'a ImportsInsertionPoint>,
+}
+
+impl TemplateContext<'_> {
+    /// The declared type of an expression, when it reads a component member.
+    pub fn type_of(&self, ast: &AST) -> Option<&str> {
+        match ast {
+            AST::PropertyRead(read)
+                if matches!(
+                    *read.receiver,
+                    AST::ImplicitReceiver(_) | AST::ThisReceiver(_)
+                ) =>
+            {
+                // Reads of template variables, references and `@let` are not component members.
+                if matches!(*read.receiver, AST::ImplicitReceiver(_))
+                    && self.bound.get_expression_target(ast).is_some()
+                {
+                    return None;
+                }
+                self.member_types.get(&read.name).map(String::as_str)
+            }
+            AST::ParenthesizedExpression(parens) => self.type_of(&parens.expression),
+            // `$any(x)`, and anything read from it, is not type-checked.
+            AST::Call(call)
+                if is_any_cast(ast)
+                    && self.bound.get_expression_target(&call.receiver).is_none() =>
+            {
+                Some("any")
+            }
+            AST::PropertyRead(read) if self.type_of(&read.receiver) == Some("any") => Some("any"),
+            AST::KeyedRead(read) if self.type_of(&read.receiver) == Some("any") => Some("any"),
+            _ => None,
+        }
+    }
+
+    /// Create a diagnostic with the given code for a template expression.
+    pub fn make_expression_diagnostic(
+        &self,
+        code: i32,
+        span: &AbsoluteSourceSpan,
+        message: impl Into<String>,
+    ) -> TypeCheckError {
+        TypeCheckError {
+            message: message.into(),
+            code: format!("NG{}", code),
+            category: DiagnosticCategory::Warning,
+            file: Some(self.component.to_string()),
+            start: Some(span.start),
+            length: Some(span.end - span.start),
+            related_information: None,
+            fix: None,
+        }
+    }
+
+    /// A quick-fix adding `name` to the `imports` of the component, when its location is known.
+    pub fn add_import_fix(&self, name: &str) -> Option<TemplateFix> {
+        self.imports_insertion.map(|point| TemplateFix {
+            file: Some(point.file.clone()),
+            start: point.offset,
+            length: 0,
+            new_text: if point.has_entries {
+                format!(", {}", name)
+            } else {
+                name.to_string()
+            },
+        })
+    }
+
+    /// Create a diagnostic with the given code for a template span.
+    pub fn make_template_diagnostic(
+        &self,
+        code: i32,
+        span: &ParseSourceSpan,
+        message: impl Into<String>,
+    ) -> TypeCheckError {
+        TypeCheckError {
+            message: message.into(),
+            code: format!("NG{}", code),
+            category: DiagnosticCategory::Warning,
+            file: Some(self.component.to_string()),
+            start: Some(span.start.offset),
+            length: Some(span.end.offset - span.start.offset),
+            related_information: None,
+            fix: None,
+        }
+    }
+}
+
+/// A check over the template of a component, e.g. one flagging deprecated elements.
+pub trait TemplateCheck: Send + Sync {
+    /// The name the check is configured by.
+    fn name(&self) -> &str;
+
+    /// The diagnostic code reported by the check, without the `NG` prefix.
+    fn code(&self) -> i32;
+
+    /// Inspect the template nodes and return the diagnostics found.
+    fn run(&self, ctx: &TemplateContext, nodes: &[t::R3Node]) -> Vec<TypeCheckError>;
+}
+
+/// Call `f` for every node of a template, including the contents of embedded views and
+/// control-flow blocks, in document order.
+pub fn for_each_template_node(nodes: &[t::R3Node], f: &mut dyn FnMut(&t::R3Node)) {
+    for node in nodes {
+        f(node);
+        match node {
+            t::R3Node::Element(el) => for_each_template_node(&el.children, f),
+            t::R3Node::Template(tmpl) => for_each_template_node(&tmpl.children, f),
+            t::R3Node::Component(comp) => for_each_template_node(&comp.children, f),
+            t::R3Node::Content(content) => for_each_template_node(&content.children, f),
+            t::R3Node::IfBlock(block) => {
+                for branch in &block.branches {
+                    for_each_template_node(&branch.children, f);
+                }
+            }
+            t::R3Node::SwitchBlock(block) => {
+                for case in &block.cases {
+                    for_each_template_node(&case.children, f);
+                }
+            }
+            t::R3Node::ForLoopBlock(block) => {
+                for_each_template_node(&block.children, f);
+                if let Some(empty) = &block.empty {
+                    for_each_template_node(&empty.children, f);
+                }
+            }
+            t::R3Node::DeferredBlock(block) => {
+                for_each_template_node(&block.children, f);
+                if let Some(placeholder) = &block.placeholder {
+                    for_each_template_node(&placeholder.children, f);
+                }
+                if let Some(loading) = &block.loading {
+                    for_each_template_node(&loading.children, f);
+                }
+                if let Some(error) = &block.error {
+                    for_each_template_node(&error.children, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Call `f` for every expression in a template, and every sub-expression of it.
+pub fn for_each_template_expression(nodes: &[t::R3Node], f: &mut dyn FnMut(&AST)) {
+    for_each_template_node(nodes, &mut |node| {
+        let mut visit = |ast: &AST| for_each_expression_node(ast, f);
+        match node {
+            t::R3Node::BoundText(text) => visit(&text.value),
+            t::R3Node::Element(el) => {
+                el.inputs.iter().for_each(|input| visit(&input.value));
+                el.outputs.iter().for_each(|output| visit(&output.handler));
+            }
+            t::R3Node::Template(tmpl) => {
+                tmpl.inputs.iter().for_each(|input| visit(&input.value));
+                tmpl.outputs
+                    .iter()
+                    .for_each(|output| visit(&output.handler));
+                for attr in &tmpl.template_attrs {
+                    if let t::TemplateAttr::Bound(attr) = attr {
+                        visit(&attr.value);
+                    }
+                }
+            }
+            t::R3Node::IfBlock(block) => {
+                for branch in &block.branches {
+                    if let Some(expression) = &branch.expression {
+                        visit(expression);
+                    }
+                }
+            }
+            t::R3Node::SwitchBlock(block) => {
+                visit(&block.expression);
+                for case in &block.cases {
+                    if let Some(expression) = &case.expression {
+                        visit(expression);
+                    }
+                }
+            }
+            t::R3Node::ForLoopBlock(block) => {
+                visit(&block.expression.ast);
+                visit(&block.track_by.ast);
+            }
+            t::R3Node::LetDeclaration(decl) => visit(&decl.value),
+            _ => {}
+        }
+    });
+}
+
+fn for_each_expression_node(ast: &AST, f: &mut dyn FnMut(&AST)) {
+    f(ast);
+    let children: Vec<&AST> = match ast {
+        AST::Binary(binary) => vec![&binary.left, &binary.right],
+        AST::Chain(chain) => chain.expressions.iter().map(|e| &**e).collect(),
+        AST::Conditional(cond) => vec![&cond.condition, &cond.true_exp, &cond.false_exp],
+        AST::PropertyRead(read) => vec![&read.receiver],
+        AST::SafePropertyRead(read) => vec![&read.receiver],
+        AST::KeyedRead(read) => vec![&read.receiver, &read.key],
+        AST::SafeKeyedRead(read) => vec![&read.receiver, &read.key],
+        AST::BindingPipe(pipe) => std::iter::once(&*pipe.exp)
+            .chain(pipe.args.iter().map(|e| &**e))
+            .collect(),
+        AST::LiteralArray(array) => array.expressions.iter().map(|e| &**e).collect(),
+        AST::LiteralMap(map) => map.values.iter().map(|e| &**e).collect(),
+        AST::Interpolation(interpolation) => {
+            interpolation.expressions.iter().map(|e| &**e).collect()
+        }
+        AST::PrefixNot(not) => vec![&not.expression],
+        AST::Unary(unary) => vec![&unary.expr],
+        AST::TypeofExpression(expr) => vec![&expr.expression],
+        AST::VoidExpression(expr) => vec![&expr.expression],
+        AST::NonNullAssert(expr) => vec![&expr.expression],
+        AST::Call(call) => std::iter::once(&*call.receiver)
+            .chain(call.args.iter().map(|e| &**e))
+            .collect(),
+        AST::SafeCall(call) => std::iter::once(&*call.receiver)
+            .chain(call.args.iter().map(|e| &**e))
+            .collect(),
+        AST::PropertyWrite(write) => vec![&write.receiver, &write.value],
+        AST::KeyedWrite(write) => vec![&write.receiver, &write.key, &write.value],
+        AST::TemplateLiteral(literal) => literal.expressions.iter().map(|e| &**e).collect(),
+        AST::TaggedTemplateLiteral(tagged) => std::iter::once(&*tagged.tag)
+            .chain(tagged.template.expressions.iter().map(|e| &**e))
+            .collect(),
+        AST::ParenthesizedExpression(parens) => vec![&parens.expression],
+        _ => Vec::new(),
+    };
+    for child in children {
+        for_each_expression_node(child, f);
+    }
+}
+
+/// Whether a declared type admits `null` or `undefined`.
+fn is_nullable_type(type_: &str) -> bool {
+    type_.split('|').any(|part| {
+        matches!(
+            part.trim(),
+            "null" | "undefined" | "void" | "any" | "unknown"
+        )
+    })
+}
+
+/// The diagnostics reported by the built-in extended checks, in the order they run.
+const BUILT_IN_CHECKS: &[TemplateDiagnosticCode] = &[
+    TemplateDiagnosticCode::NullishCoalescingNotNullable,
+    TemplateDiagnosticCode::TextAttributeNotBinding,
+    TemplateDiagnosticCode::MissingControlFlowDirective,
+];
+
+/// The built-in extended check that reports `code`, if there is one.
+pub fn template_check_for(code: TemplateDiagnosticCode) -> Option<Arc<dyn TemplateCheck>> {
+    match code {
+        TemplateDiagnosticCode::NullishCoalescingNotNullable => {
+            Some(Arc::new(NullishCoalescingNotNullableCheck))
+        }
+        TemplateDiagnosticCode::TextAttributeNotBinding => {
+            Some(Arc::new(TextAttributeNotBindingCheck))
+        }
+        TemplateDiagnosticCode::MissingControlFlowDirective => {
+            Some(Arc::new(MissingControlFlowAttributeCheck))
+        }
+        _ => None,
+    }
+}
+
+/// The extended checks that run on every template.
+pub fn built_in_template_checks() -> Vec<Arc<dyn TemplateCheck>> {
+    BUILT_IN_CHECKS
+        .iter()
+        .filter_map(|code| template_check_for(*code))
+        .collect()
+}
+
+/// Warns about `a ?? b` when `a` cannot be `null` or `undefined`, so the `??` is pointless.
+pub struct NullishCoalescingNotNullableCheck;
+
+impl TemplateCheck for NullishCoalescingNotNullableCheck {
+    fn name(&self) -> &str {
+        "nullishCoalescingNotNullable"
+    }
+
+    fn code(&self) -> i32 {
+        TemplateDiagnosticCode::NullishCoalescingNotNullable as i32
+    }
+
+    fn run(&self, ctx: &TemplateContext, nodes: &[t::R3Node]) -> Vec<TypeCheckError> {
+        // Without `strictNullChecks` every type is nullable.
+        if !ctx.strict_null_checks {
+            return Vec::new();
+        }
+        let mut diagnostics = Vec::new();
+        for_each_template_expression(nodes, &mut |ast| {
+            let AST::Binary(binary) = ast else {
+                return;
+            };
+            if binary.operation != "??" {
+                return;
+            }
+            if let Some(type_) = ctx.type_of(&binary.left) {
+                if !is_nullable_type(type_) {
+                    diagnostics.push(ctx.make_expression_diagnostic(
+                        self.code(),
+                        &binary.source_span,
+                        "The left side of this nullish coalescing operation does not include \
+                         'null' or 'undefined' in its type, therefore the '??' operator can be \
+                         safely removed.",
+                    ));
+                }
+            }
+        });
+        diagnostics
+    }
+}
+
+/// Flags attributes that were most likely meant as bindings: `attr.x="..."`, `class.x="..."`
+/// and `style.x="..."` written as text, and attributes whose only content is one interpolation,
+/// such as `role="{{role}}"`.
+pub struct TextAttributeNotBindingCheck;
+
+impl TextAttributeNotBindingCheck {
+    fn check_text_attribute(
+        &self,
+        ctx: &TemplateContext,
+        attr: &t::TextAttribute,
+        diagnostics: &mut Vec<TypeCheckError>,
+    ) {
+        let name: &str = &attr.name;
+        if !["attr.", "class.", "style."]
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        {
+            return;
+        }
+        let message = if name.starts_with("class.") {
+            format!(
+                "Attribute, style, and class bindings should be enclosed with square braces. \
+                 For example, '[{}]=\"true\"'.",
+                name
+            )
+        } else {
+            format!(
+                "Attribute, style, and class bindings should be enclosed with square braces. \
+                 For example, '[{}]=\"{}\"'.",
+                name, attr.value
+            )
+        };
+        diagnostics.push(ctx.make_template_diagnostic(self.code(), &attr.source_span, message));
+    }
+
+    fn check_bound_attribute(
+        &self,
+        ctx: &TemplateContext,
+        input: &t::BoundAttribute,
+        diagnostics: &mut Vec<TypeCheckError>,
+    ) {
+        let AST::Interpolation(interpolation) = &input.value else {
+            return;
+        };
+        if interpolation.expressions.len() != 1
+            || interpolation.strings.iter().any(|s| !s.trim().is_empty())
+        {
+            return;
+        }
+        let name = match input.type_ {
+            BindingType::Attribute => format!("attr.{}", input.name),
+            BindingType::Class => format!("class.{}", input.name),
+            BindingType::Style => format!("style.{}", input.name),
+            _ => input.name.to_string(),
+        };
+        diagnostics.push(ctx.make_template_diagnostic(
+            self.code(),
+            &input.source_span,
+            format!(
+                "The value of '{}' is a single interpolation, which is better written as a \
+                 binding. For example, '[{}]'.",
+                input.name, name
+            ),
+        ));
+    }
+}
+
+impl TemplateCheck for TextAttributeNotBindingCheck {
+    fn name(&self) -> &str {
+        "textAttributeNotBinding"
+    }
+
+    fn code(&self) -> i32 {
+        TemplateDiagnosticCode::TextAttributeNotBinding as i32
+    }
+
+    fn run(&self, ctx: &TemplateContext, nodes: &[t::R3Node]) -> Vec<TypeCheckError> {
+        let mut diagnostics = Vec::new();
+        for_each_template_node(nodes, &mut |node| {
+            let (attributes, inputs) = match node {
+                t::R3Node::Element(el) => (&el.attributes, &el.inputs),
+                t::R3Node::Template(tmpl) => (&tmpl.attributes, &tmpl.inputs),
+                _ => return,
+            };
+            for attr in attributes {
+                self.check_text_attribute(ctx, attr, &mut diagnostics);
+            }
+            for input in inputs {
+                self.check_bound_attribute(ctx, input, &mut diagnostics);
+            }
+        });
+        diagnostics
+    }
+}
+
+/// Control-flow directives from `CommonModule` that are applied as attributes rather than with
+/// the `*` syntax: the attribute and the directive class.
+const CONTROL_FLOW_ATTRIBUTE_DIRECTIVES: &[(&str, &str)] =
+    &[("ngSwitch", "NgSwitch"), ("ngPlural", "NgPlural")];
+
+/// Flags `[ngSwitch]` and `[ngPlural]` in a standalone component that imports neither the
+/// directive nor `CommonModule`. Structural uses such as `*ngFor` are reported while the
+/// type-check block is generated.
+pub struct MissingControlFlowAttributeCheck;
+
+impl TemplateCheck for MissingControlFlowAttributeCheck {
+    fn name(&self) -> &str {
+        "missingControlFlowDirective"
+    }
+
+    fn code(&self) -> i32 {
+        TemplateDiagnosticCode::MissingControlFlowDirective as i32
+    }
+
+    fn run(&self, ctx: &TemplateContext, nodes: &[t::R3Node]) -> Vec<TypeCheckError> {
+        // The imports of a non-standalone component belong to its NgModule.
+        if !ctx.is_standalone {
+            return Vec::new();
+        }
+        let mut diagnostics = Vec::new();
+        for_each_template_node(nodes, &mut |node| {
+            let (owner, inputs, attributes) = match node {
+                t::R3Node::Element(el) => (
+                    DirectiveOwner::Element((**el).clone()),
+                    &el.inputs,
+                    &el.attributes,
+                ),
+                t::R3Node::Template(tmpl) => (
+                    DirectiveOwner::Template((**tmpl).clone()),
+                    &tmpl.inputs,
+                    &tmpl.attributes,
+                ),
+                _ => return,
+            };
+            let used = inputs
+                .iter()
+                .map(|input| (&input.name, &input.source_span))
+                .chain(
+                    attributes
+                        .iter()
+                        .map(|attr| (&attr.name, &attr.source_span)),
+                );
+            for (name, span) in used {
+                let Some(&(_, directive)) = CONTROL_FLOW_ATTRIBUTE_DIRECTIVES
+                    .iter()
+                    .find(|(attr_name, _)| *attr_name == &**name)
+                else {
+                    continue;
+                };
+                let matched = ctx
+                    .bound
+                    .get_directives_of_node(&owner)
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|dir| dir.inputs.has_binding_property_name(name));
+                if matched {
+                    continue;
+                }
+                let mut diagnostic = ctx.make_template_diagnostic(
+                    self.code(),
+                    span,
+                    format!(
+                        "The `[{}]` directive was used in the template, but neither the `{}` \
+                         directive nor the `CommonModule` was imported. Make sure that either \
+                         the `{}` directive or the `CommonModule` is included in the \
+                         `@Component.imports` array of this component.",
+                        name, directive, directive
+                    ),
+                );
+                diagnostic.fix = ctx.add_import_fix("CommonModule");
+                diagnostics.push(diagnostic);
+            }
+        });
+        diagnostics
+    }
+}
@@ -3,6 +3,8 @@
 pub mod checker;
 pub mod context;
 pub mod diagnostics;
+pub mod expression;
+pub mod extended;
 pub mod type_check_block;
 
 // Re-exports
@@ -11,6 +13,11 @@ pub use context::{TypeCheckEnvironment, TypeCheckingContext};
 pub use diagnostics::{
     create_missing_pipe_diagnostic, create_missing_required_input_diagnostic,
     create_type_mismatch_diagnostic, create_unknown_element_diagnostic,
-    create_unknown_property_diagnostic, TemplateDiagnosticCode,
+    create_unknown_property_diagnostic, InputDeclaration, TemplateDiagnosticCode,
 };
-pub use type_check_block::{OutOfBandDiagnosticRecorder, TypeCheckBlockGenerator};
+pub use extended::{
+    built_in_template_checks, for_each_template_expression, for_each_template_node,
+    template_check_for, MissingControlFlowAttributeCheck, NullishCoalescingNotNullableCheck,
+    TemplateCheck, TemplateContext, TextAttributeNotBindingCheck,
+};
+pub use type_check_block::{bind_template, OutOfBandDiagnosticRecorder, TypeCheckBlockGenerator};
@@ -2,8 +2,23 @@
 //
 // Generates type-check blocks for templates.
 
-use super::super::api::{TypeCheckError, TypeCheckingConfig};
+use super::super::api::{ImportsInsertionPoint, TypeCheckError, TypeCheckingConfig};
+use super::diagnostics::{create_missing_pipe_diagnostic, TemplateDiagnosticCode};
+use super::expression::{translate_expression, TcbExpressionResolver};
+use super::extended::{built_in_template_checks, TemplateCheck, TemplateContext};
+use crate::ngtsc::metadata::T2DirectiveMetadata;
+use angular_compiler::directive_matching::{CssSelector, SelectorMatcher};
+use angular_compiler::expression_parser::ast::{BindingType, ParsedEventType, AST};
+use angular_compiler::render3::r3_ast as t;
+use angular_compiler::render3::view::t2_api::{
+    BoundTarget, DirectiveMeta, DirectiveOwner, ReferenceTarget, Target, TargetBinder,
+};
+use angular_compiler::render3::view::t2_binder::{DirectiveMatcher, R3TargetBinder};
+use angular_compiler::render3::view::template::parse_template;
+use std::collections::HashMap;
 use std::fmt::Write;
+use std::sync::Arc;
+use ts::DiagnosticCategory;
 
 /// Generates a type-check block (TCB) for a component template.
 pub struct TypeCheckBlockGenerator {
@@ -13,6 +28,18 @@ pub struct TypeCheckBlockGenerator {
     output: String,
     /// Indentation level.
     indent: usize,
+    /// Pipe class names by pipe name.
+    pipes: HashMap<String, String>,
+    /// Whether the component is standalone, which decides where missing imports belong.
+    is_standalone: bool,
+    /// Diagnostics found while generating the block.
+    oob_recorder: OutOfBandDiagnosticRecorder,
+    /// Extended checks run over each template.
+    template_checks: Vec<Arc<dyn TemplateCheck>>,
+    /// The declared types of the component members that `ctx` exposes.
+    member_types: HashMap<String, String>,
+    /// Where missing imports can be added to the component, for quick-fixes.
+    imports_insertion: Option<ImportsInsertionPoint>,
 }
 
 impl TypeCheckBlockGenerator {
@@ -21,16 +48,64 @@ impl TypeCheckBlockGenerator {
             config,
             output: String::new(),
             indent: 0,
+            pipes: HashMap::new(),
+            is_standalone: true,
+            oob_recorder: OutOfBandDiagnosticRecorder::new(),
+            template_checks: built_in_template_checks(),
+            member_types: HashMap::new(),
+            imports_insertion: None,
         }
     }
 
+    /// Declare the type of a member of the checked component, e.g. `("title", "string")`.
+    pub fn set_member_type(&mut self, name: impl Into<String>, type_: impl Into<String>) {
+        self.member_types.insert(name.into(), type_.into());
+    }
+
+    /// Run `check` over the templates checked by this generator. Its diagnostics are reported
+    /// with the out-of-band ones.
+    pub fn add_template_check(&mut self, check: Arc<dyn TemplateCheck>) {
+        self.template_checks.push(check);
+    }
+
+    /// Set where missing imports can be added to the `imports` array of the checked component.
+    pub fn set_imports_insertion_point(&mut self, point: ImportsInsertionPoint) {
+        self.imports_insertion = Some(point);
+    }
+
+    /// Set whether the checked component is standalone (the default) or declared in an NgModule.
+    pub fn set_standalone(&mut self, is_standalone: bool) {
+        self.is_standalone = is_standalone;
+    }
+
+    /// Diagnostics recorded while generating the last block.
+    pub fn out_of_band_diagnostics(&self) -> &[TypeCheckError] {
+        self.oob_recorder.diagnostics()
+    }
+
+    /// Make a pipe available to the templates checked by this generator.
+    pub fn register_pipe(&mut self, name: impl Into<String>, class_name: impl Into<String>) {
+        self.pipes.insert(name.into(), class_name.into());
+    }
+
     /// Generate TCB for a component.
     pub fn generate(
         &mut self,
         component_name: &str,
         template: &str,
+    ) -> Result<String, TypeCheckError> {
+        self.generate_with_directives(component_name, template, &[])
+    }
+
+    /// Generate TCB for a component whose template can use the given directives.
+    pub fn generate_with_directives(
+        &mut self,
+        component_name: &str,
+        template: &str,
+        directives: &[T2DirectiveMetadata],
     ) -> Result<String, TypeCheckError> {
         self.output.clear();
+        self.oob_recorder = OutOfBandDiagnosticRecorder::new();
 
         // Generate function signature
         self.write_line(&format!(
@@ -39,10 +114,51 @@ impl TypeCheckBlockGenerator {
         ));
         self.indent += 1;
 
-        // For now, generate placeholder
         self.write_line("// Template type-check block");
         self.write_line(&format!("// Template: {}", template.replace('\n', " ")));
 
+        let parsed = parse_template(template, "", Default::default());
+        let bound = bind_template(parsed.nodes, directives);
+        let mut scope = TcbScope::new(&self.pipes, self.config.check_type_of_pipes);
+        if let Some(nodes) = bound.target().template.as_ref() {
+            let mut references = Vec::new();
+            collect_references(nodes, &mut references);
+            for reference in references {
+                let ref_type = self.reference_type(component_name, bound.as_ref(), reference)?;
+                let var = scope.allocate_var();
+                self.write_line(&format!(
+                    "var {} = null! as {}; // #{}",
+                    var, ref_type, reference.name
+                ));
+                scope
+                    .references
+                    .entry(reference.name.to_string())
+                    .or_insert(var);
+            }
+            self.visit_nodes(component_name, nodes, bound.as_ref(), &mut scope);
+
+            let ctx = TemplateContext {
+                component: component_name,
+                bound: bound.as_ref(),
+                member_types: &self.member_types,
+                strict_null_checks: self.config.strict_null_checks,
+                is_standalone: self.is_standalone,
+                imports_insertion: self.imports_insertion.as_ref(),
+            };
+            for check in &self.template_checks {
+                for diagnostic in check.run(&ctx, nodes) {
+                    self.oob_recorder.record(diagnostic);
+                }
+            }
+        }
+        if let Some(pipe_name) = scope.missing_pipes.first() {
+            return Err(create_missing_pipe_diagnostic(
+                component_name,
+                pipe_name,
+                &self.pipe_names(),
+            ));
+        }
+
         // Close function
         self.indent -= 1;
         self.write_line("}");
@@ -50,6 +166,216 @@ impl TypeCheckBlockGenerator {
         Ok(self.output.clone())
     }
 
+    /// The type of a template reference variable: the DOM interface of a plain element,
+    /// `TemplateRef` for an `<ng-template>` and the directive class for `#ref="exportAs"`.
+    fn reference_type(
+        &self,
+        component_name: &str,
+        bound: &dyn BoundTarget<T2DirectiveMetadata>,
+        reference: &t::Reference,
+    ) -> Result<String, TypeCheckError> {
+        match bound.get_reference_target(reference) {
+            Some(ReferenceTarget::DirectiveOnNode { directive, .. }) => Ok(directive.name.clone()),
+            Some(ReferenceTarget::Template(_)) => Ok("TemplateRef<any>".to_string()),
+            Some(ReferenceTarget::Element(el)) => {
+                if self.config.check_type_of_dom_references {
+                    Ok(dom_element_type(&el.name).to_string())
+                } else {
+                    Ok("any".to_string())
+                }
+            }
+            None => Err(TypeCheckError {
+                message: format!("No directive found with exportAs '{}'.", reference.value),
+                code: "NG8003".to_string(),
+                category: DiagnosticCategory::Error,
+                file: Some(component_name.to_string()),
+                start: Some(reference.source_span.start.offset),
+                length: Some(reference.source_span.end.offset - reference.source_span.start.offset),
+                related_information: None,
+                fix: None,
+            }),
+        }
+    }
+
+    fn visit_nodes(
+        &mut self,
+        component_name: &str,
+        nodes: &[t::R3Node],
+        bound: &dyn BoundTarget<T2DirectiveMetadata>,
+        scope: &mut TcbScope,
+    ) {
+        for node in nodes {
+            match node {
+                t::R3Node::Element(el) => {
+                    let owner = DirectiveOwner::Element((**el).clone());
+                    let inputs: Vec<&t::BoundAttribute> = el.inputs.iter().collect();
+                    self.visit_directives(&owner, &inputs, &el.outputs, bound, scope);
+                    self.visit_nodes(component_name, &el.children, bound, scope);
+                }
+                t::R3Node::Template(tmpl) => {
+                    let owner = DirectiveOwner::Template((**tmpl).clone());
+                    let inputs: Vec<&t::BoundAttribute> = tmpl
+                        .inputs
+                        .iter()
+                        .chain(tmpl.template_attrs.iter().filter_map(|attr| match attr {
+                            t::TemplateAttr::Bound(attr) => Some(attr),
+                            t::TemplateAttr::Text(_) => None,
+                        }))
+                        .collect();
+                    let directives = bound.get_directives_of_node(&owner).unwrap_or_default();
+                    if !directives.iter().any(|dir| dir.is_structural) {
+                        self.check_missing_control_flow_directive(component_name, tmpl);
+                    }
+                    self.visit_directives(&owner, &inputs, &tmpl.outputs, bound, scope);
+                    // Embedded views are only checked in full mode.
+                    if self.config.check_template_bodies {
+                        self.visit_nodes(component_name, &tmpl.children, bound, scope);
+                    }
+                }
+                t::R3Node::BoundText(text) => {
+                    let expr = self.translate(&text.value, scope);
+                    self.write_line(&format!("{};", expr));
+                }
+                t::R3Node::Component(comp) => {
+                    self.visit_nodes(component_name, &comp.children, bound, scope)
+                }
+                t::R3Node::Content(content) => {
+                    self.visit_nodes(component_name, &content.children, bound, scope)
+                }
+                t::R3Node::IfBlock(block) => {
+                    for branch in &block.branches {
+                        self.visit_nodes(component_name, &branch.children, bound, scope);
+                    }
+                }
+                t::R3Node::SwitchBlock(block) => {
+                    for case in &block.cases {
+                        self.visit_nodes(component_name, &case.children, bound, scope);
+                    }
+                }
+                t::R3Node::ForLoopBlock(block) => {
+                    self.visit_nodes(component_name, &block.children, bound, scope);
+                    if let Some(empty) = &block.empty {
+                        self.visit_nodes(component_name, &empty.children, bound, scope);
+                    }
+                }
+                t::R3Node::DeferredBlock(block) => {
+                    self.visit_nodes(component_name, &block.children, bound, scope);
+                    if let Some(placeholder) = &block.placeholder {
+                        self.visit_nodes(component_name, &placeholder.children, bound, scope);
+                    }
+                    if let Some(loading) = &block.loading {
+                        self.visit_nodes(component_name, &loading.children, bound, scope);
+                    }
+                    if let Some(error) = &block.error {
+                        self.visit_nodes(component_name, &error.children, bound, scope);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Report a template that uses a known structural directive, e.g. `*ngIf`, which isn't
+    /// in scope.
+    fn check_missing_control_flow_directive(&mut self, component_name: &str, tmpl: &t::Template) {
+        for attr in &tmpl.template_attrs {
+            let (name, span) = match attr {
+                t::TemplateAttr::Bound(attr) => (&attr.name, &attr.source_span),
+                t::TemplateAttr::Text(attr) => (&attr.name, &attr.source_span),
+            };
+            if let Some(&(_, directive, built_in)) = KNOWN_CONTROL_FLOW_DIRECTIVES
+                .iter()
+                .find(|(attr_name, _, _)| **attr_name == **name)
+            {
+                self.oob_recorder.missing_control_flow_directive(
+                    component_name,
+                    name,
+                    directive,
+                    built_in,
+                    self.is_standalone,
+                    span.start.offset,
+                    span.end.offset - span.start.offset,
+                );
+                return;
+            }
+        }
+    }
+
+    /// Declare the directives matched on a node and check the bindings to their inputs and
+    /// outputs.
+    fn visit_directives(
+        &mut self,
+        owner: &DirectiveOwner,
+        inputs: &[&t::BoundAttribute],
+        outputs: &[t::BoundEvent],
+        bound: &dyn BoundTarget<T2DirectiveMetadata>,
+        scope: &mut TcbScope,
+    ) {
+        for directive in bound.get_directives_of_node(owner).unwrap_or_default() {
+            let var = scope.allocate_var();
+            self.write_line(&format!("var {} = null! as {};", var, directive.name));
+
+            for input in inputs {
+                if !matches!(input.type_, BindingType::Property | BindingType::TwoWay) {
+                    continue;
+                }
+                let Some((_, mapping)) = directive
+                    .inputs
+                    .iter()
+                    .find(|(_, mapping)| mapping.binding_property_name == *input.name)
+                else {
+                    continue;
+                };
+                let mut value = self.translate(&input.value, scope);
+                if !self.config.strict_null_input_types {
+                    value = format!("({})!", value);
+                }
+                if !self.config.check_type_of_input_bindings {
+                    value = format!("({} as any)", value);
+                }
+                self.write_line(&format!(
+                    "{}.{} = {};",
+                    var, mapping.class_property_name, value
+                ));
+            }
+
+            for output in outputs {
+                if !matches!(
+                    output.type_,
+                    ParsedEventType::Regular | ParsedEventType::TwoWay
+                ) {
+                    continue;
+                }
+                let Some((_, mapping)) = directive
+                    .outputs
+                    .iter()
+                    .find(|(_, mapping)| mapping.binding_property_name == *output.name)
+                else {
+                    continue;
+                };
+                let param = if self.config.check_type_of_output_events {
+                    "$event"
+                } else {
+                    "$event: any"
+                };
+                let handler = self.translate(&output.handler, scope);
+                self.write_line(&format!(
+                    "{}.{}.subscribe(({}) => {{ {}; }});",
+                    var, mapping.class_property_name, param, handler
+                ));
+            }
+        }
+    }
+
+    /// Translate an expression, declaring any pipe instances it uses first.
+    fn translate(&mut self, ast: &AST, scope: &mut TcbScope) -> String {
+        let expr = translate_expression(ast, scope);
+        for declaration in std::mem::take(&mut scope.pending_declarations) {
+            self.write_line(&declaration);
+        }
+        expr
+    }
+
     /// Generate element type-check.
     pub fn generate_element(&mut self, tag: &str, attrs: &[(String, String)]) {
         self.write_line(&format!("// Element: <{}>", tag));
@@ -73,12 +399,243 @@ impl TypeCheckBlockGenerator {
         self.write_line(&format!("_pipe.transform({});", args_str));
     }
 
+    fn pipe_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.pipes.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
     fn write_line(&mut self, line: &str) {
         let indent = "  ".repeat(self.indent);
         writeln!(self.output, "{}{}", indent, line).ok();
     }
 }
 
+/// Structural directives from `CommonModule`: the template attribute, the directive class and
+/// the built-in control flow that replaces it.
+const KNOWN_CONTROL_FLOW_DIRECTIVES: &[(&str, &str, Option<&str>)] = &[
+    ("ngIf", "NgIf", Some("@if")),
+    ("ngFor", "NgFor", Some("@for")),
+    ("ngSwitchCase", "NgSwitchCase", Some("@switch with @case")),
+    (
+        "ngSwitchDefault",
+        "NgSwitchDefault",
+        Some("@switch with @default"),
+    ),
+];
+
+/// Variables declared so far in a type-check block.
+struct TcbScope {
+    /// Pipe class names by pipe name.
+    pipe_classes: HashMap<String, String>,
+    check_type_of_pipes: bool,
+    /// Reference variables by reference name.
+    references: HashMap<String, String>,
+    /// Pipe instance variables by pipe name.
+    pipes: HashMap<String, String>,
+    /// Declarations to emit before the expression currently being translated.
+    pending_declarations: Vec<String>,
+    /// Names of used pipes that aren't in scope.
+    missing_pipes: Vec<String>,
+    next_var: usize,
+}
+
+impl TcbScope {
+    fn new(pipe_classes: &HashMap<String, String>, check_type_of_pipes: bool) -> Self {
+        Self {
+            pipe_classes: pipe_classes.clone(),
+            check_type_of_pipes,
+            references: HashMap::new(),
+            pipes: HashMap::new(),
+            pending_declarations: Vec::new(),
+            missing_pipes: Vec::new(),
+            next_var: 1,
+        }
+    }
+
+    fn allocate_var(&mut self) -> String {
+        let var = format!("_t{}", self.next_var);
+        self.next_var += 1;
+        var
+    }
+}
+
+impl TcbExpressionResolver for TcbScope {
+    fn resolve_name(&self, name: &str) -> Option<String> {
+        if name == "$event" {
+            return Some(name.to_string());
+        }
+        self.references.get(name).cloned()
+    }
+
+    fn resolve_pipe(&mut self, name: &str) -> String {
+        let var = match self.pipes.get(name) {
+            Some(var) => var.clone(),
+            None => {
+                let var = format!("_pipe{}", self.pipes.len() + 1);
+                let class = match self.pipe_classes.get(name) {
+                    Some(class) => class.as_str(),
+                    None => {
+                        self.missing_pipes.push(name.to_string());
+                        "any"
+                    }
+                };
+                self.pending_declarations
+                    .push(format!("var {} = null! as {};", var, class));
+                self.pipes.insert(name.to_string(), var.clone());
+                var
+            }
+        };
+        if self.check_type_of_pipes {
+            var
+        } else {
+            format!("({} as any)", var)
+        }
+    }
+}
+
+/// Bind parsed template nodes, matching `directives` by their selectors.
+pub fn bind_template(
+    nodes: Vec<t::R3Node>,
+    directives: &[T2DirectiveMetadata],
+) -> Box<dyn BoundTarget<T2DirectiveMetadata>> {
+    let mut matcher = SelectorMatcher::<Vec<T2DirectiveMetadata>>::new();
+    for directive in directives {
+        let Some(selector) = directive.selector() else {
+            continue;
+        };
+        if let Ok(selectors) = CssSelector::parse(selector) {
+            for css_selector in selectors {
+                matcher.add_selectable(css_selector, vec![directive.clone()]);
+            }
+        }
+    }
+    let binder = R3TargetBinder::new(Some(DirectiveMatcher::Selector(matcher)));
+    binder.bind(Target {
+        template: Some(nodes),
+        host: None,
+    })
+}
+
+/// Collect the references declared in a template, in document order.
+fn collect_references<'n>(nodes: &'n [t::R3Node], references: &mut Vec<&'n t::Reference>) {
+    for node in nodes {
+        match node {
+            t::R3Node::Element(el) => {
+                references.extend(el.references.iter());
+                collect_references(&el.children, references);
+            }
+            t::R3Node::Template(tmpl) => {
+                references.extend(tmpl.references.iter());
+                collect_references(&tmpl.children, references);
+            }
+            t::R3Node::Component(comp) => {
+                references.extend(comp.references.iter());
+                collect_references(&comp.children, references);
+            }
+            t::R3Node::Content(content) => collect_references(&content.children, references),
+            t::R3Node::IfBlock(block) => {
+                for branch in &block.branches {
+                    collect_references(&branch.children, references);
+                }
+            }
+            t::R3Node::SwitchBlock(block) => {
+                for case in &block.cases {
+                    collect_references(&case.children, references);
+                }
+            }
+            t::R3Node::ForLoopBlock(block) => {
+                collect_references(&block.children, references);
+                if let Some(empty) = &block.empty {
+                    collect_references(&empty.children, references);
+                }
+            }
+            t::R3Node::DeferredBlock(block) => {
+                collect_references(&block.children, references);
+                if let Some(placeholder) = &block.placeholder {
+                    collect_references(&placeholder.children, references);
+                }
+                if let Some(loading) = &block.loading {
+                    collect_references(&loading.children, references);
+                }
+                if let Some(error) = &block.error {
+                    collect_references(&error.children, references);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The DOM interface that `document.createElement(tag)` returns, after `HTMLElementTagNameMap`.
+fn dom_element_type(tag: &str) -> &'static str {
+    match tag {
+        "a" => "HTMLAnchorElement",
+        "area" => "HTMLAreaElement",
+        "audio" => "HTMLAudioElement",
+        "base" => "HTMLBaseElement",
+        "blockquote" | "q" => "HTMLQuoteElement",
+        "body" => "HTMLBodyElement",
+        "br" => "HTMLBRElement",
+        "button" => "HTMLButtonElement",
+        "canvas" => "HTMLCanvasElement",
+        "col" | "colgroup" => "HTMLTableColElement",
+        "data" => "HTMLDataElement",
+        "datalist" => "HTMLDataListElement",
+        "del" | "ins" => "HTMLModElement",
+        "details" => "HTMLDetailsElement",
+        "dialog" => "HTMLDialogElement",
+        "div" => "HTMLDivElement",
+        "dl" => "HTMLDListElement",
+        "embed" => "HTMLEmbedElement",
+        "fieldset" => "HTMLFieldSetElement",
+        "form" => "HTMLFormElement",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "HTMLHeadingElement",
+        "head" => "HTMLHeadElement",
+        "hr" => "HTMLHRElement",
+        "html" => "HTMLHtmlElement",
+        "iframe" => "HTMLIFrameElement",
+        "img" => "HTMLImageElement",
+        "input" => "HTMLInputElement",
+        "label" => "HTMLLabelElement",
+        "legend" => "HTMLLegendElement",
+        "li" => "HTMLLIElement",
+        "link" => "HTMLLinkElement",
+        "map" => "HTMLMapElement",
+        "menu" => "HTMLMenuElement",
+        "meta" => "HTMLMetaElement",
+        "meter" => "HTMLMeterElement",
+        "object" => "HTMLObjectElement",
+        "ol" => "HTMLOListElement",
+        "optgroup" => "HTMLOptGroupElement",
+        "option" => "HTMLOptionElement",
+        "output" => "HTMLOutputElement",
+        "p" => "HTMLParagraphElement",
+        "picture" => "HTMLPictureElement",
+        "pre" => "HTMLPreElement",
+        "progress" => "HTMLProgressElement",
+        "script" => "HTMLScriptElement",
+        "select" => "HTMLSelectElement",
+        "slot" => "HTMLSlotElement",
+        "source" => "HTMLSourceElement",
+        "span" => "HTMLSpanElement",
+        "style" => "HTMLStyleElement",
+        "table" => "HTMLTableElement",
+        "caption" => "HTMLTableCaptionElement",
+        "td" | "th" => "HTMLTableCellElement",
+        "tbody" | "tfoot" | "thead" => "HTMLTableSectionElement",
+        "tr" => "HTMLTableRowElement",
+        "template" => "HTMLTemplateElement",
+        "textarea" => "HTMLTextAreaElement",
+        "time" => "HTMLTimeElement",
+        "title" => "HTMLTitleElement",
+        "track" => "HTMLTrackElement",
+        "ul" => "HTMLUListElement",
+        "video" => "HTMLVideoElement",
+        _ => "HTMLElement",
+    }
+}
+
 /// Out-of-band checker for template errors.
 pub struct OutOfBandDiagnosticRecorder {
     /// Collected diagnostics.
@@ -92,15 +649,18 @@ impl OutOfBandDiagnosticRecorder {
         }
     }
 
-    /// Record a missing pipe error.
-    pub fn missing_pipe(&mut self, component: &str, pipe_name: &str) {
-        self.diagnostics.push(TypeCheckError {
-            message: format!("The pipe '{}' could not be found", pipe_name),
-            code: "NG8004".to_string(),
-            file: Some(component.to_string()),
-            start: None,
-            length: None,
-        });
+    /// Record a diagnostic found by a template check.
+    pub fn record(&mut self, diagnostic: TypeCheckError) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Record a missing pipe error, suggesting the closest of `pipes_in_scope`.
+    pub fn missing_pipe(&mut self, component: &str, pipe_name: &str, pipes_in_scope: &[String]) {
+        self.diagnostics.push(create_missing_pipe_diagnostic(
+            component,
+            pipe_name,
+            pipes_in_scope,
+        ));
     }
 
     /// Record a missing directive error.
@@ -108,9 +668,57 @@ impl OutOfBandDiagnosticRecorder {
         self.diagnostics.push(TypeCheckError {
             message: format!("There is no directive with selector '{}'", selector),
             code: "NG8002".to_string(),
+            category: DiagnosticCategory::Error,
             file: Some(component.to_string()),
             start: None,
             length: None,
+            related_information: None,
+            fix: None,
+        });
+    }
+
+    /// Record a structural directive from `CommonModule` that is used but not imported.
+    #[allow(clippy::too_many_arguments)]
+    pub fn missing_control_flow_directive(
+        &mut self,
+        component: &str,
+        attr_name: &str,
+        directive: &str,
+        built_in: Option<&str>,
+        is_standalone: bool,
+        start: usize,
+        length: usize,
+    ) {
+        let (decorator, owner) = if is_standalone {
+            ("Component", "component")
+        } else {
+            ("NgModule", "module")
+        };
+        let mut message = format!(
+            "The `*{}` directive was used in the template, but neither the `{}` directive nor \
+             the `CommonModule` was imported. ",
+            attr_name, directive
+        );
+        if let Some(built_in) = built_in {
+            message.push_str(&format!(
+                "Use Angular's built-in control flow {} or ",
+                built_in
+            ));
+        }
+        message.push_str(&format!(
+            "make sure that either the `{}` directive or the `CommonModule` is included in the \
+             `@{}.imports` array of this {}.",
+            directive, decorator, owner
+        ));
+        self.diagnostics.push(TypeCheckError {
+            message,
+            code: TemplateDiagnosticCode::MissingControlFlowDirective.code(),
+            category: DiagnosticCategory::Warning,
+            file: Some(component.to_string()),
+            start: Some(start),
+            length: Some(length),
+            related_information: None,
+            fix: None,
         });
     }
 
@@ -125,3 +733,293 @@ impl Default for OutOfBandDiagnosticRecorder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ngtsc::core::NgCompilerOptions;
+    use crate::ngtsc::metadata::{ClassPropertyMapping, InputOrOutput};
+    use crate::ngtsc::typecheck::api::TemplateFix;
+
+    fn directive(name: &str, selector: &str, export_as: &[&str]) -> T2DirectiveMetadata {
+        T2DirectiveMetadata {
+            name: name.to_string(),
+            selector: Some(selector.to_string()),
+            export_as: Some(export_as.iter().map(|s| s.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reference_to_element_has_dom_type() {
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        let tcb = generator
+            .generate(
+                "TestCmp",
+                "<div><input #input></div><span #s></span><my-cmp #c></my-cmp>",
+            )
+            .unwrap();
+        assert!(tcb.contains("var _t1 = null! as HTMLInputElement; // #input"));
+        assert!(tcb.contains("var _t2 = null! as HTMLSpanElement; // #s"));
+        assert!(tcb.contains("var _t3 = null! as HTMLElement; // #c"));
+    }
+
+    #[test]
+    fn test_reference_to_directive_has_directive_type() {
+        let directives = vec![directive("NgForm", "form:not([ngNoForm])", &["ngForm"])];
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        let tcb = generator
+            .generate_with_directives("TestCmp", "<form #f=\"ngForm\"></form>", &directives)
+            .unwrap();
+        assert!(tcb.contains("var _t1 = null! as NgForm; // #f"));
+    }
+
+    #[test]
+    fn test_reference_to_template_is_template_ref() {
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        let tcb = generator
+            .generate("TestCmp", "<ng-template #tpl></ng-template>")
+            .unwrap();
+        assert!(tcb.contains("var _t1 = null! as TemplateRef<any>; // #tpl"));
+    }
+
+    #[test]
+    fn test_reference_to_dom_is_any_without_dom_reference_checks() {
+        let config = TypeCheckingConfig {
+            check_type_of_dom_references: false,
+            ..Default::default()
+        };
+        let mut generator = TypeCheckBlockGenerator::new(config);
+        let tcb = generator.generate("TestCmp", "<input #input>").unwrap();
+        assert!(tcb.contains("var _t1 = null! as any; // #input"));
+    }
+
+    #[test]
+    fn test_report_reference_to_unknown_export_as() {
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        let template = "<form #f=\"ngForm\"></form>";
+        let err = generator.generate("TestCmp", template).unwrap_err();
+        assert_eq!(err.code, "NG8003");
+        assert_eq!(err.message, "No directive found with exportAs 'ngForm'.");
+        assert_eq!(err.start, template.find("#f"));
+    }
+
+    fn directive_with_input(name: &str, selector: &str, input: &str) -> T2DirectiveMetadata {
+        let mut inputs = ClassPropertyMapping::new();
+        inputs.insert(InputOrOutput {
+            class_property_name: input.to_string(),
+            binding_property_name: input.to_string(),
+            is_signal: false,
+            required: false,
+            transform: None,
+        });
+        T2DirectiveMetadata {
+            name: name.to_string(),
+            selector: Some(selector.to_string()),
+            inputs,
+            ..Default::default()
+        }
+    }
+
+    fn generate_with_pipe(config: TypeCheckingConfig, template: &str) -> String {
+        let directives = vec![directive_with_input("ValueDir", "[valueDir]", "value")];
+        let mut generator = TypeCheckBlockGenerator::new(config);
+        generator.register_pipe("upper", "UpperPipe");
+        generator
+            .generate_with_directives("TestCmp", template, &directives)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_checks_input_bindings_and_pipes() {
+        let tcb = generate_with_pipe(
+            TypeCheckingConfig::default(),
+            "<div valueDir [value]=\"name | upper:1\"></div>",
+        );
+        assert!(tcb.contains("var _t1 = null! as ValueDir;"));
+        assert!(tcb.contains("var _pipe1 = null! as UpperPipe;"));
+        assert!(tcb.contains("_t1.value = _pipe1.transform(ctx.name, 1);"));
+    }
+
+    #[test]
+    fn test_check_type_of_pipes_off_keeps_input_checks() {
+        let config = TypeCheckingConfig {
+            check_type_of_pipes: false,
+            ..Default::default()
+        };
+        let tcb = generate_with_pipe(
+            config,
+            "<div valueDir [value]=\"count\"></div>{{ name | upper:1 }}",
+        );
+        // The pipe call is no longer checked against the pipe's signature...
+        assert!(tcb.contains("(_pipe1 as any).transform(ctx.name, 1)"));
+        // ...while the input binding still is.
+        assert!(tcb.contains("_t1.value = ctx.count;"));
+    }
+
+    #[test]
+    fn test_strict_templates_sub_flags_from_options() {
+        let options = NgCompilerOptions {
+            strict_templates: true,
+            strict_input_types: Some(false),
+            ..NgCompilerOptions::default()
+        };
+        let config = options.type_checking_config();
+        assert!(config.check_type_of_pipes);
+        assert!(config.strict_null_input_types);
+        assert!(!config.check_type_of_input_bindings);
+
+        let tcb = generate_with_pipe(config, "<div valueDir [value]=\"count\"></div>");
+        assert!(tcb.contains("_t1.value = (ctx.count as any);"));
+    }
+
+    #[test]
+    fn test_basic_mode_skips_template_bodies() {
+        let config = NgCompilerOptions::default().type_checking_config();
+        assert!(!config.check_template_bodies);
+        let tcb = generate_with_pipe(
+            config,
+            "{{ a }}<ng-template><div valueDir [value]=\"b\"></div></ng-template>",
+        );
+        assert!(tcb.contains("\"\" + (ctx.a);"));
+        assert!(!tcb.contains("ValueDir"));
+    }
+
+    #[test]
+    fn test_report_unknown_pipe_with_suggestion() {
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        generator.register_pipe("uppercase", "UpperCasePipe");
+        generator.register_pipe("lowercase", "LowerCasePipe");
+        let err = generator
+            .generate("TestCmp", "{{ x | uppercas }}")
+            .unwrap_err();
+        assert_eq!(err.code, "NG8004");
+        assert!(err.message.contains("Did you mean 'uppercase'?"));
+    }
+
+    #[test]
+    fn test_report_structural_directive_that_is_not_imported() {
+        let template = "<div *ngIf=\"show\"></div>";
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        generator.generate("TestCmp", template).unwrap();
+
+        let diagnostics = generator.out_of_band_diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "NG8103");
+        assert_eq!(diagnostics[0].start, template.find("ngIf"));
+        assert_eq!(
+            diagnostics[0].message,
+            "The `*ngIf` directive was used in the template, but neither the `NgIf` directive \
+             nor the `CommonModule` was imported. Use Angular's built-in control flow @if or \
+             make sure that either the `NgIf` directive or the `CommonModule` is included in the \
+             `@Component.imports` array of this component."
+        );
+    }
+
+    #[test]
+    fn test_no_missing_control_flow_diagnostic_when_directive_is_in_scope() {
+        let ng_if = T2DirectiveMetadata {
+            name: "NgIf".to_string(),
+            selector: Some("[ngIf]".to_string()),
+            is_structural: true,
+            ..Default::default()
+        };
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        generator
+            .generate_with_directives("TestCmp", "<div *ngIf=\"show\"></div>", &[ng_if])
+            .unwrap();
+        assert!(generator.out_of_band_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_nullish_coalescing_on_non_nullable_member() {
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        generator.set_member_type("notNullString", "string");
+        generator.set_member_type("maybeNull", "string | null");
+
+        let template = "{{ notNullString ?? 'x' }}";
+        generator.generate("TestCmp", template).unwrap();
+        let diagnostics = generator.out_of_band_diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "NG8102");
+        assert_eq!(diagnostics[0].start, template.find("notNullString"));
+
+        generator
+            .generate("TestCmp", "{{ maybeNull ?? 'x' }}")
+            .unwrap();
+        assert!(generator.out_of_band_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_interpolated_attribute_is_not_binding() {
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        let template = "<div role=\"{{r}}\"></div>";
+        generator.generate("TestCmp", template).unwrap();
+        let diagnostics = generator.out_of_band_diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "NG8104");
+        assert_eq!(diagnostics[0].start, template.find("role"));
+
+        generator
+            .generate("TestCmp", "<div [attr.role]=\"r\"></div>")
+            .unwrap();
+        assert!(generator.out_of_band_diagnostics().is_empty());
+
+        generator
+            .generate("TestCmp", "<div attr.id=\"my-id\"></div>")
+            .unwrap();
+        let diagnostics = generator.out_of_band_diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("'[attr.id]=\"my-id\"'"));
+    }
+
+    #[test]
+    fn test_missing_control_flow_attribute_directive_suggests_common_module() {
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        generator.set_imports_insertion_point(ImportsInsertionPoint {
+            file: "test.component.ts".to_string(),
+            offset: 120,
+            has_entries: true,
+        });
+        let template = "<div [ngSwitch]=\"mode\"></div>";
+        generator.generate("TestCmp", template).unwrap();
+        let diagnostics = generator.out_of_band_diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "NG8103");
+        assert_eq!(diagnostics[0].start, template.find("[ngSwitch]"));
+        assert_eq!(
+            diagnostics[0].fix,
+            Some(TemplateFix {
+                file: Some("test.component.ts".to_string()),
+                start: 120,
+                length: 0,
+                new_text: ", CommonModule".to_string(),
+            })
+        );
+
+        let ng_switch = directive_with_input("NgSwitch", "[ngSwitch]", "ngSwitch");
+        generator
+            .generate_with_directives("TestCmp", template, &[ng_switch])
+            .unwrap();
+        assert!(generator.out_of_band_diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_any_cast_opts_out_of_type_checking() {
+        let mut generator = TypeCheckBlockGenerator::new(TypeCheckingConfig::default());
+        generator.set_member_type("obj", "Options");
+
+        let tcb = generator
+            .generate("TestCmp", "{{ $any(obj).missing }}{{ $any(obj) ?? 'x' }}")
+            .unwrap();
+        assert!(tcb.contains("(ctx.obj as any).missing"));
+        assert!(tcb.contains("((ctx.obj as any) ?? \"x\")"));
+        assert!(generator.out_of_band_diagnostics().is_empty());
+
+        // A template variable named `$any` is called like any other function.
+        let tcb = generator
+            .generate("TestCmp", "<input #$any>{{ $any(obj) }}")
+            .unwrap();
+        assert!(!tcb.contains("as any"));
+    }
+}
@@ -3,4 +3,5 @@
 pub mod rules;
 pub mod source_file_validator;
 
+pub use rules::*;
 pub use source_file_validator::*;
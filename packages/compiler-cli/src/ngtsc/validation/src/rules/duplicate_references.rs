@@ -0,0 +1,119 @@
+// Duplicate References
+//
+// Reports template reference variables declared twice in the same view.
+
+use crate::ngtsc::diagnostics::ErrorCode;
+use crate::ngtsc::typecheck::api::TypeCheckError;
+use angular_compiler::render3::r3_ast as t;
+use std::collections::HashMap;
+use ts::{DiagnosticCategory, DiagnosticRelatedInformation};
+
+/// Report each reference (`#name`) of a template that is declared again in the same view, e.g.
+/// on two sibling elements. Expressions can only read one of them, so the duplicate is usually a
+/// copy-paste error.
+///
+/// The diagnostic covers the duplicate reference, and its related information points at the
+/// first declaration. Views nested in `@if`, `@for`, `<ng-template>`, etc. have their own
+/// references. `file` is the file the template was read from.
+pub fn check_duplicate_references(file: &str, nodes: &[t::R3Node]) -> Vec<TypeCheckError> {
+    let mut errors = Vec::new();
+    check_view(file, nodes, &mut errors);
+    errors
+}
+
+fn check_view(file: &str, nodes: &[t::R3Node], errors: &mut Vec<TypeCheckError>) {
+    let mut declared = HashMap::new();
+    check_nodes(file, nodes, &mut declared, errors);
+}
+
+/// Check the references of `nodes`, which belong to the view whose references are `declared`.
+fn check_nodes<'n>(
+    file: &str,
+    nodes: &'n [t::R3Node],
+    declared: &mut HashMap<&'n str, &'n t::Reference>,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    for node in nodes {
+        match node {
+            t::R3Node::Element(element) => {
+                declare(file, &element.references, declared, errors);
+                check_nodes(file, &element.children, declared, errors);
+            }
+            t::R3Node::Component(component) => {
+                declare(file, &component.references, declared, errors);
+                check_nodes(file, &component.children, declared, errors);
+            }
+            t::R3Node::Content(content) => check_nodes(file, &content.children, declared, errors),
+            // The references of a template are declared in the view containing it.
+            t::R3Node::Template(template) => {
+                declare(file, &template.references, declared, errors);
+                check_view(file, &template.children, errors);
+            }
+            t::R3Node::IfBlock(block) => {
+                for branch in &block.branches {
+                    check_view(file, &branch.children, errors);
+                }
+            }
+            t::R3Node::ForLoopBlock(block) => {
+                check_view(file, &block.children, errors);
+                if let Some(empty) = &block.empty {
+                    check_view(file, &empty.children, errors);
+                }
+            }
+            t::R3Node::SwitchBlock(block) => {
+                for case in &block.cases {
+                    check_view(file, &case.children, errors);
+                }
+            }
+            t::R3Node::DeferredBlock(block) => {
+                check_view(file, &block.children, errors);
+                if let Some(placeholder) = &block.placeholder {
+                    check_view(file, &placeholder.children, errors);
+                }
+                if let Some(loading) = &block.loading {
+                    check_view(file, &loading.children, errors);
+                }
+                if let Some(error) = &block.error {
+                    check_view(file, &error.children, errors);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn declare<'n>(
+    file: &str,
+    references: &'n [t::Reference],
+    declared: &mut HashMap<&'n str, &'n t::Reference>,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    for reference in references {
+        let Some(first) = declared.get(&*reference.name) else {
+            declared.insert(&reference.name, reference);
+            continue;
+        };
+        let first_span = &first.source_span;
+        let span = &reference.source_span;
+        errors.push(TypeCheckError {
+            message: format!(
+                "Cannot redeclare reference '#{}' as it was previously declared elsewhere in the same view.",
+                reference.name
+            ),
+            code: format!("NG{}", ErrorCode::DuplicateVariableDeclaration as i32),
+            category: DiagnosticCategory::Error,
+            file: Some(file.to_string()),
+            start: Some(span.start.offset),
+            length: Some(span.end.offset - span.start.offset),
+            related_information: Some(vec![DiagnosticRelatedInformation {
+                category: DiagnosticCategory::Message,
+                code: 0,
+                file: Some(file.to_string()),
+                start: Some(first_span.start.offset),
+                length: Some(first_span.end.offset - first_span.start.offset),
+                message_text: format!("The reference '#{}' is first declared here.", reference.name),
+            }]),
+            fix: None,
+        });
+    }
+}
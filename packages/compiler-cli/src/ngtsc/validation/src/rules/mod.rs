@@ -1,5 +1,6 @@
 //! Validation Rules
 
+pub mod duplicate_references;
 pub mod mod_placeholder;
 
-// Individual rule implementations from validation/src/rules/
+pub use duplicate_references::*;
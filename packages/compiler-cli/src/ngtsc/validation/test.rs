@@ -59,6 +59,48 @@ mod tests {
         }
     }
 
+    mod duplicate_references_tests {
+        use super::*;
+        use angular_compiler::render3::view::template::parse_template;
+
+        fn check(template: &str) -> Vec<crate::ngtsc::typecheck::api::TypeCheckError> {
+            let parsed = parse_template(template, "cmp.html", Default::default());
+            check_duplicate_references("cmp.html", &parsed.nodes)
+        }
+
+        #[test]
+        fn should_report_duplicate_reference_in_same_view() {
+            let template = "<input #ref><input #ref>";
+            let errors = check(template);
+
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].code, "NG8006");
+            assert_eq!(
+                errors[0].message,
+                "Cannot redeclare reference '#ref' as it was previously declared elsewhere in the same view."
+            );
+            let start = errors[0].start.unwrap();
+            assert_eq!(&template[start..start + errors[0].length.unwrap()], "#ref");
+            assert_eq!(start, 19);
+
+            let related = errors[0].related_information.as_ref().unwrap();
+            assert_eq!(related.len(), 1);
+            assert_eq!(related[0].start, Some(7));
+            assert_eq!(related[0].length, Some(4));
+        }
+
+        #[test]
+        fn should_allow_same_reference_in_different_views() {
+            let errors = check(
+                "<div #ref></div>\
+                 <ng-template><div #ref></div></ng-template>\
+                 @if (a) { <span #ref></span> } @else { <span #ref></span> }",
+            );
+
+            assert!(errors.is_empty());
+        }
+    }
+
     mod validation_result_tests {
         use super::*;
 
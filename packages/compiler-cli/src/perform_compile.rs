@@ -311,7 +311,7 @@ pub fn perform_compilation(
     // Aggregate diagnostics from analysis
     for d in program.get_diagnostics() {
         diagnostics.push(Diagnostic {
-            category: DiagnosticCategory::Error,
+            category: d.category,
             code: d.code as i32,
             message: d.message,
             file: d.file.map(|p| p.to_string_lossy().to_string()),
@@ -379,7 +379,10 @@ pub fn perform_compilation_simple(
         }
 
         // Get outDir from tsconfig if available
-        let mut opts = NgCompilerOptions::default();
+        let mut opts = NgCompilerOptions {
+            project: p.to_string(),
+            ..NgCompilerOptions::default()
+        };
 
         // Parse tsconfig again to get compilerOptions
         let tsconfig_path = Path::new(p);
@@ -393,6 +396,53 @@ pub fn perform_compilation_simple(
                         let resolved_out_dir = base_dir.join(out_dir);
                         opts.out_dir = Some(resolved_out_dir.to_string_lossy().to_string());
                     }
+                    // Outputs mirror the source tree below rootDir, which is relative to the
+                    // tsconfig too
+                    if let Some(root_dir) = compiler_opts.get("rootDir").and_then(|v| v.as_str()) {
+                        let base_dir = tsconfig_path.parent().unwrap_or(Path::new("."));
+                        opts.root_dir = Some(base_dir.join(root_dir).to_string_lossy().to_string());
+                    }
+                    opts.emit_declaration_only = compiler_opts
+                        .get("emitDeclarationOnly")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    opts.inline_source_map = compiler_opts
+                        .get("inlineSourceMap")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                }
+                opts.enable_i18n_legacy_message_id_format = config
+                    .pointer("/angularCompilerOptions/enableI18nLegacyMessageIdFormat")
+                    .and_then(|v| v.as_bool());
+                if let Some(ng_opts) = config.get("angularCompilerOptions") {
+                    let string_opt = |key: &str| {
+                        ng_opts
+                            .get(key)
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string)
+                    };
+                    opts.flat_module_out_file = string_opt("flatModuleOutFile");
+                    opts.flat_module_id = string_opt("flatModuleId");
+                    opts.report_unused_imports = ng_opts
+                        .get("reportUnusedImports")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    opts.hmr = ng_opts
+                        .get("_enableHmr")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    opts.trace_performance = ng_opts
+                        .get("tracePerformance")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    opts.generate_ng_factory_shims = ng_opts
+                        .get("generateNgFactoryShims")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    opts.generate_ng_summary_shims = ng_opts
+                        .get("generateNgSummaryShims")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
                 }
             }
         }
@@ -425,7 +475,7 @@ pub fn perform_compilation_simple(
     // Collect diagnostics from analysis
     for d in program.get_diagnostics() {
         diagnostics.push(Diagnostic {
-            category: DiagnosticCategory::Error,
+            category: d.category,
             code: d.code as i32,
             message: d.message,
             file: d.file.map(|p| p.to_string_lossy().to_string()),
@@ -80,6 +80,7 @@ pub enum I18nFormat {
     Xlf2,
     Xmb,
     Json,
+    Arb,
 }
 
 /// Diagnostic message.
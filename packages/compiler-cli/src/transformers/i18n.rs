@@ -25,7 +25,7 @@ impl MessageLoader {
         match format {
             I18nFormat::Xlf | I18nFormat::Xlf2 => self.load_xliff(content),
             I18nFormat::Xmb => self.load_xmb(content),
-            I18nFormat::Json => self.load_json(content),
+            I18nFormat::Json | I18nFormat::Arb => self.load_json(content),
         }
     }
 
@@ -3,6 +3,8 @@
 //! Corresponds to packages/compiler-cli/src/version.ts
 //! Version information for the compiler.
 
+use std::cmp::Ordering;
+
 /// Angular compiler version.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -12,7 +14,7 @@ pub fn version_string() -> String {
 }
 
 /// Version information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Version {
     /// Major version.
     pub major: u32,
@@ -54,6 +56,79 @@ impl Version {
         })
     }
 
+    /// Whether this version is in a semver `range`, e.g. `>=17.0.0 <18.0.0` or `^16.1.0`.
+    ///
+    /// A range is a `||`-separated list of alternatives, each a space-separated list of
+    /// comparators (`>=`, `>`, `<=`, `<`, `=`, or a caret range). Versions in comparators may
+    /// omit the minor and patch numbers, which default to zero. Returns `false` for a range that
+    /// can't be parsed.
+    pub fn satisfies(&self, range: &str) -> bool {
+        range.split("||").any(|alternative| {
+            let comparators: Vec<&str> = alternative.split_whitespace().collect();
+            !comparators.is_empty()
+                && comparators
+                    .iter()
+                    .all(|comparator| self.satisfies_comparator(comparator).unwrap_or(false))
+        })
+    }
+
+    fn satisfies_comparator(&self, comparator: &str) -> Option<bool> {
+        if let Some(version) = comparator.strip_prefix('^') {
+            let min = Self::parse_partial(version)?;
+            // `^` allows changes that keep the leftmost non-zero number.
+            let max = if min.major > 0 {
+                Self::release(min.major + 1, 0, 0)
+            } else if min.minor > 0 {
+                Self::release(0, min.minor + 1, 0)
+            } else {
+                Self::release(0, 0, min.patch + 1)
+            };
+            return Some(*self >= min && *self < max);
+        }
+        let (op, version) = [">=", "<=", ">", "<", "="]
+            .iter()
+            .find_map(|op| comparator.strip_prefix(op).map(|version| (*op, version)))
+            .unwrap_or(("=", comparator));
+        let ordering = self.cmp(&Self::parse_partial(version.trim())?);
+        Some(match op {
+            ">=" => ordering != Ordering::Less,
+            "<=" => ordering != Ordering::Greater,
+            ">" => ordering == Ordering::Greater,
+            "<" => ordering == Ordering::Less,
+            _ => ordering == Ordering::Equal,
+        })
+    }
+
+    /// Parse a version whose minor and patch numbers may be omitted, e.g. `16` or `16.1`.
+    fn parse_partial(version: &str) -> Option<Self> {
+        let (numbers, prerelease) = match version.split_once('-') {
+            Some((numbers, prerelease)) => (numbers, Some(prerelease.to_string())),
+            None => (version, None),
+        };
+        let mut parts = numbers.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+        let patch = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            major,
+            minor,
+            patch,
+            prerelease,
+        })
+    }
+
+    fn release(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            prerelease: None,
+        }
+    }
+
     /// Format version as string.
     pub fn to_string(&self) -> String {
         let base = format!("{}.{}.{}", self.major, self.minor, self.patch);
@@ -65,8 +140,90 @@ impl Version {
     }
 }
 
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.prerelease, &other.prerelease) {
+                // A prerelease comes before the release it precedes.
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_prerelease(a, b),
+            })
+    }
+}
+
+/// Compare prerelease tags identifier by identifier: numeric identifiers compare numerically
+/// and sort before alphanumeric ones, and a tag that is a prefix of another sorts first.
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a_ids = a.split('.');
+    let mut b_ids = b.split('.');
+    loop {
+        let ordering = match (a_ids.next(), b_ids.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// Check if TypeScript version is supported.
 pub fn check_typescript_version(_ts_version: &str) -> Result<(), String> {
     // Would check TypeScript version compatibility
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert!(version("17.2.0") >= version("17.0.0"));
+        assert!(version("17.0.0-next.1") < version("17.0.0"));
+        assert!(version("17.2.0").satisfies(">=17.0.0"));
+    }
+
+    #[test]
+    fn test_compare_prerelease_identifiers() {
+        assert!(version("17.0.0-next.2") < version("17.0.0-next.10"));
+        assert!(version("17.0.0-next.10") < version("17.0.0-rc.0"));
+        assert!(version("17.0.0-next") < version("17.0.0-next.0"));
+        assert!(version("17.0.0-1") < version("17.0.0-alpha"));
+        assert!(version("17.0.0-rc.1").satisfies(">=17.0.0-next.10"));
+    }
+
+    #[test]
+    fn test_caret_range() {
+        assert!(version("17.9.9").satisfies("^17.0.0"));
+        assert!(!version("18.0.0").satisfies("^17.0.0"));
+        assert!(!version("0.3.0").satisfies("^0.2.1"));
+    }
+
+    #[test]
+    fn test_less_than_range() {
+        assert!(!version("17.2.0").satisfies("<16"));
+        assert!(version("15.2.10").satisfies("<16"));
+        assert!(version("15.0.0").satisfies(">=14.0.0 <16 || ^18.0.0"));
+    }
+}
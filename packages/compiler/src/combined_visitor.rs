@@ -5,6 +5,7 @@
 //! Useful for cases where every single node needs to be visited.
 
 use crate::expression_parser::ast::{ASTWithSource, RecursiveAstVisitor, AST};
+use crate::ml_parser::ast as html;
 use crate::render3::r3_ast as t;
 use crate::render3::r3_ast::Visitor;
 
@@ -385,3 +386,444 @@ impl Visitor for CombinedRecursiveAstVisitor {
         // Comments don't contain AST expressions to visit
     }
 }
+
+/// Runs several HTML AST visitors in a single traversal.
+///
+/// The combined visitor owns the traversal: every node is dispatched to each registered
+/// visitor (in registration order) and the combined visitor then descends into the node's
+/// attributes, directives, parameters, cases and children. Registered visitors should
+/// therefore only handle the node they are given and not recurse themselves, otherwise
+/// descendants are visited more than once.
+///
+/// A visitor can stop early by returning `Some(Box::new(VisitFlow::Break))` from any of its
+/// methods. It then receives no further nodes, while the other visitors carry on; once every
+/// visitor has broken out, the traversal itself stops.
+pub struct CombinedHtmlVisitor<'a> {
+    visitors: Vec<&'a mut dyn html::Visitor>,
+    /// Whether each visitor (by index) still wants to receive nodes.
+    active: Vec<bool>,
+}
+
+/// Returned (boxed) by a visitor registered with [`CombinedHtmlVisitor`] to signal whether it
+/// wants to keep receiving nodes. Returning `None` is equivalent to `Continue`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitFlow {
+    Continue,
+    Break,
+}
+
+impl<'a> CombinedHtmlVisitor<'a> {
+    pub fn builder() -> CombinedHtmlVisitorBuilder<'a> {
+        CombinedHtmlVisitorBuilder::new()
+    }
+
+    /// Visit all `nodes` (and their descendants) with every registered visitor.
+    pub fn visit_all(&mut self, nodes: &[html::Node]) {
+        self.visit_nodes(nodes, &mut ());
+    }
+
+    /// Whether every registered visitor has broken out of the traversal.
+    pub fn is_done(&self) -> bool {
+        !self.active.contains(&true)
+    }
+
+    fn dispatch<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(&mut dyn html::Visitor) -> Option<Box<dyn std::any::Any>>,
+    {
+        for (visitor, active) in self.visitors.iter_mut().zip(self.active.iter_mut()) {
+            if !*active {
+                continue;
+            }
+            let result = visit(&mut **visitor);
+            if let Some(VisitFlow::Break) = result.as_deref().and_then(|r| r.downcast_ref()) {
+                *active = false;
+            }
+        }
+    }
+
+    fn visit_nodes(&mut self, nodes: &[html::Node], context: &mut dyn std::any::Any) {
+        for node in nodes {
+            if self.is_done() {
+                break;
+            }
+            html::visit_all(self, std::slice::from_ref(node), context);
+        }
+    }
+
+    fn visit_attributes(&mut self, attrs: &[html::Attribute], context: &mut dyn std::any::Any) {
+        for attr in attrs {
+            if self.is_done() {
+                break;
+            }
+            html::Visitor::visit_attribute(self, attr, context);
+        }
+    }
+
+    fn visit_directives(
+        &mut self,
+        directives: &[html::Directive],
+        context: &mut dyn std::any::Any,
+    ) {
+        for directive in directives {
+            if self.is_done() {
+                break;
+            }
+            html::Visitor::visit_directive(self, directive, context);
+        }
+    }
+}
+
+/// Builder for [`CombinedHtmlVisitor`].
+pub struct CombinedHtmlVisitorBuilder<'a> {
+    visitors: Vec<&'a mut dyn html::Visitor>,
+}
+
+impl<'a> CombinedHtmlVisitorBuilder<'a> {
+    pub fn new() -> Self {
+        CombinedHtmlVisitorBuilder {
+            visitors: Vec::new(),
+        }
+    }
+
+    /// Register a visitor. Visitors are invoked in registration order.
+    pub fn with_visitor(mut self, visitor: &'a mut dyn html::Visitor) -> Self {
+        self.visitors.push(visitor);
+        self
+    }
+
+    pub fn build(self) -> CombinedHtmlVisitor<'a> {
+        CombinedHtmlVisitor {
+            active: vec![true; self.visitors.len()],
+            visitors: self.visitors,
+        }
+    }
+}
+
+impl Default for CombinedHtmlVisitorBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl html::Visitor for CombinedHtmlVisitor<'_> {
+    fn visit_element(
+        &mut self,
+        element: &html::Element,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_element(element, context));
+        self.visit_attributes(&element.attrs, context);
+        self.visit_directives(&element.directives, context);
+        self.visit_nodes(&element.children, context);
+        None
+    }
+
+    fn visit_attribute(
+        &mut self,
+        attribute: &html::Attribute,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_attribute(attribute, context));
+        None
+    }
+
+    fn visit_text(
+        &mut self,
+        text: &html::Text,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_text(text, context));
+        None
+    }
+
+    fn visit_comment(
+        &mut self,
+        comment: &html::Comment,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_comment(comment, context));
+        None
+    }
+
+    fn visit_expansion(
+        &mut self,
+        expansion: &html::Expansion,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_expansion(expansion, context));
+        for case in &expansion.cases {
+            if self.is_done() {
+                break;
+            }
+            html::Visitor::visit_expansion_case(self, case, context);
+        }
+        None
+    }
+
+    fn visit_expansion_case(
+        &mut self,
+        expansion_case: &html::ExpansionCase,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_expansion_case(expansion_case, context));
+        self.visit_nodes(&expansion_case.expression, context);
+        None
+    }
+
+    fn visit_block(
+        &mut self,
+        block: &html::Block,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_block(block, context));
+        for parameter in &block.parameters {
+            if self.is_done() {
+                break;
+            }
+            html::Visitor::visit_block_parameter(self, parameter, context);
+        }
+        self.visit_nodes(&block.children, context);
+        None
+    }
+
+    fn visit_block_parameter(
+        &mut self,
+        parameter: &html::BlockParameter,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_block_parameter(parameter, context));
+        None
+    }
+
+    fn visit_let_declaration(
+        &mut self,
+        decl: &html::LetDeclaration,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_let_declaration(decl, context));
+        None
+    }
+
+    fn visit_component(
+        &mut self,
+        component: &html::Component,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_component(component, context));
+        self.visit_attributes(&component.attrs, context);
+        self.visit_directives(&component.directives, context);
+        self.visit_nodes(&component.children, context);
+        None
+    }
+
+    fn visit_directive(
+        &mut self,
+        directive: &html::Directive,
+        context: &mut dyn std::any::Any,
+    ) -> Option<Box<dyn std::any::Any>> {
+        self.dispatch(|v| v.visit_directive(directive, context));
+        self.visit_attributes(&directive.attrs, context);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ml_parser::html_parser::HtmlParser;
+    use crate::ml_parser::lexer::TokenizeOptions;
+
+    /// Records the kind of every node it is handed, without recursing. When `find` is set, it
+    /// breaks out of the traversal once it reaches an element with that name.
+    #[derive(Default)]
+    struct CountingVisitor {
+        seen: Vec<&'static str>,
+        find: Option<&'static str>,
+    }
+
+    impl html::Visitor for CountingVisitor {
+        fn visit_element(
+            &mut self,
+            element: &html::Element,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("element");
+            if self.find == Some(&*element.name) {
+                return Some(Box::new(VisitFlow::Break));
+            }
+            None
+        }
+
+        fn visit_attribute(
+            &mut self,
+            _attribute: &html::Attribute,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("attribute");
+            None
+        }
+
+        fn visit_text(
+            &mut self,
+            _text: &html::Text,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("text");
+            None
+        }
+
+        fn visit_comment(
+            &mut self,
+            _comment: &html::Comment,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("comment");
+            None
+        }
+
+        fn visit_expansion(
+            &mut self,
+            _expansion: &html::Expansion,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("expansion");
+            None
+        }
+
+        fn visit_expansion_case(
+            &mut self,
+            _expansion_case: &html::ExpansionCase,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("expansion_case");
+            None
+        }
+
+        fn visit_block(
+            &mut self,
+            _block: &html::Block,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("block");
+            None
+        }
+
+        fn visit_block_parameter(
+            &mut self,
+            _parameter: &html::BlockParameter,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("block_parameter");
+            None
+        }
+
+        fn visit_let_declaration(
+            &mut self,
+            _decl: &html::LetDeclaration,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("let_declaration");
+            None
+        }
+
+        fn visit_component(
+            &mut self,
+            _component: &html::Component,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("component");
+            None
+        }
+
+        fn visit_directive(
+            &mut self,
+            _directive: &html::Directive,
+            _context: &mut dyn std::any::Any,
+        ) -> Option<Box<dyn std::any::Any>> {
+            self.seen.push("directive");
+            None
+        }
+    }
+
+    #[test]
+    fn should_dispatch_every_node_to_all_visitors_once() {
+        let mut options = TokenizeOptions::default();
+        options.tokenize_blocks = true;
+        options.tokenize_let = true;
+        let parse_result = HtmlParser::new().parse(
+            "<div a=\"1\"><span>x</span><!--c-->@if (cond) {<b></b>}</div>@let y = 1;",
+            "test.html",
+            Some(options),
+        );
+        assert!(parse_result.errors.is_empty());
+
+        let mut first = CountingVisitor::default();
+        let mut second = CountingVisitor::default();
+        CombinedHtmlVisitor::builder()
+            .with_visitor(&mut first)
+            .with_visitor(&mut second)
+            .build()
+            .visit_all(&parse_result.root_nodes);
+
+        let expected = vec![
+            "element",
+            "attribute",
+            "element",
+            "text",
+            "comment",
+            "block",
+            "block_parameter",
+            "element",
+            "let_declaration",
+        ];
+        assert_eq!(first.seen, expected);
+        assert_eq!(second.seen, expected);
+    }
+
+    #[test]
+    fn should_stop_visiting_once_a_visitor_breaks() {
+        let parse_result = HtmlParser::new().parse(
+            "<a x=\"1\"></a><b><i></i></b><c></c>text",
+            "test.html",
+            None,
+        );
+        assert!(parse_result.errors.is_empty());
+
+        let mut finder = CountingVisitor {
+            find: Some("b"),
+            ..Default::default()
+        };
+        let mut combined = CombinedHtmlVisitor::builder()
+            .with_visitor(&mut finder)
+            .build();
+        combined.visit_all(&parse_result.root_nodes);
+        assert!(combined.is_done());
+        assert_eq!(finder.seen, vec!["element", "attribute", "element"]);
+
+        // Other visitors keep going after one of them breaks.
+        let mut finder = CountingVisitor {
+            find: Some("b"),
+            ..Default::default()
+        };
+        let mut counter = CountingVisitor::default();
+        let mut combined = CombinedHtmlVisitor::builder()
+            .with_visitor(&mut finder)
+            .with_visitor(&mut counter)
+            .build();
+        combined.visit_all(&parse_result.root_nodes);
+        assert!(!combined.is_done());
+        assert_eq!(finder.seen, vec!["element", "attribute", "element"]);
+        assert_eq!(
+            counter.seen,
+            vec![
+                "element",
+                "attribute",
+                "element",
+                "element",
+                "element",
+                "text"
+            ]
+        );
+    }
+}
@@ -9,6 +9,8 @@ pub struct CompilerConfig {
     pub default_encapsulation: Option<ViewEncapsulation>,
     pub preserve_whitespaces: bool,
     pub strict_injection_parameters: bool,
+    /// Emit development-only metadata, such as where each template element is defined.
+    pub debug: bool,
 }
 
 impl CompilerConfig {
@@ -16,18 +18,20 @@ impl CompilerConfig {
         default_encapsulation: Option<ViewEncapsulation>,
         preserve_whitespaces: Option<bool>,
         strict_injection_parameters: Option<bool>,
+        debug: Option<bool>,
     ) -> Self {
         CompilerConfig {
             default_encapsulation: default_encapsulation.or(Some(ViewEncapsulation::Emulated)),
             preserve_whitespaces: preserve_whitespaces_default(preserve_whitespaces, false),
             strict_injection_parameters: strict_injection_parameters.unwrap_or(false),
+            debug: debug.unwrap_or(false),
         }
     }
 }
 
 impl Default for CompilerConfig {
     fn default() -> Self {
-        Self::new(None, None, None)
+        Self::new(None, None, None, None)
     }
 }
 
@@ -45,11 +45,48 @@ pub struct GenericKeyFn;
 impl GenericKeyFn {
     pub const INSTANCE: GenericKeyFn = GenericKeyFn;
 
-    /// Generate key for an expression
+    /// Generate key for an expression.
+    ///
+    /// The key only depends on the content of the expression (not on its source span), so
+    /// identical literals coming from different templates map to the same pooled constant.
     pub fn key_of(&self, expr: &o::Expression) -> String {
-        // Simplified key generation using debug formatting for now
-        // TODO: Implement proper key visitor
-        format!("{:?}", expr)
+        match expr {
+            o::Expression::Literal(lit) => match &lit.value {
+                o::LiteralValue::String(s) => format!("{:?}", s),
+                o::LiteralValue::Number(n) => n.to_string(),
+                o::LiteralValue::Bool(b) => b.to_string(),
+                o::LiteralValue::Null => "null".to_string(),
+                o::LiteralValue::Undefined => "undefined".to_string(),
+            },
+            o::Expression::LiteralArray(arr) => {
+                let entries: Vec<String> = arr.entries.iter().map(|e| self.key_of(e)).collect();
+                format!("[{}]", entries.join(","))
+            }
+            o::Expression::LiteralMap(map) => {
+                let entries: Vec<String> = map
+                    .entries
+                    .iter()
+                    .map(|e| {
+                        let key = if e.quoted {
+                            format!("{:?}", e.key)
+                        } else {
+                            e.key.clone()
+                        };
+                        format!("{}:{}", key, self.key_of(&e.value))
+                    })
+                    .collect();
+                format!("{{{}}}", entries.join(","))
+            }
+            o::Expression::External(ext) => format!(
+                "EX:{}:{}",
+                ext.value.module_name.as_deref().unwrap_or(""),
+                ext.value.name.as_deref().unwrap_or("")
+            ),
+            o::Expression::ReadVar(var) => format!("VAR:{}", var.name),
+            o::Expression::TypeOf(type_of) => format!("TYPEOF:{}", self.key_of(&type_of.expr)),
+            o::Expression::PureFunctionParameter(param) => format!("PARAM:{}", param.index),
+            _ => format!("{:?}", expr),
+        }
     }
 }
 
@@ -263,9 +300,16 @@ impl ConstantPool {
         }
     }
 
-    pub fn unique_name(&mut self, preferred_name: String, _always_include_suffix: bool) -> String {
-        // Rust specific: TS has `alwaysIncludeSuffix` (boolean).
-        // I'll ignore it for now or assume false implies check existing.
+    pub fn unique_name(&mut self, preferred_name: String, always_include_suffix: bool) -> String {
+        if always_include_suffix {
+            let count = self
+                .claimed_names
+                .entry(preferred_name.clone())
+                .or_insert(0);
+            let unique = format!("{}{}", preferred_name, count);
+            *count += 1;
+            return unique;
+        }
 
         if !self.claimed_names.contains_key(&preferred_name) {
             self.claimed_names.insert(preferred_name.clone(), 0);
@@ -296,7 +340,7 @@ impl ConstantPool {
     }
 
     fn key_of_expression(&self, expr: &o::Expression) -> String {
-        format!("{:?}", expr) // Placeholder
+        GenericKeyFn::INSTANCE.key_of(expr)
     }
 }
 
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::directive_matching::CssSelector;
+
 // Default value for emitDistinctChangesOnly
 pub const EMIT_DISTINCT_CHANGES_ONLY_DEFAULT_VALUE: bool = true;
 
@@ -111,10 +113,97 @@ pub enum SelectorFlags {
     CLASS = 0b1000,
 }
 
+/// A single entry of an R3 selector: a name/value, or a `SelectorFlags` marker (possibly
+/// combined, e.g. `NOT | CLASS`) that changes how the following entries are interpreted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum R3SelectorEntry {
+    Value(String),
+    Flags(u32),
+}
+
 // R3 CSS Selector types
-pub type R3CssSelector = Vec<String>; // Simplified - actual implementation more complex
+pub type R3CssSelector = Vec<R3SelectorEntry>;
 pub type R3CssSelectorList = Vec<R3CssSelector>;
 
+fn parser_selector_to_simple_selector(selector: &CssSelector) -> R3CssSelector {
+    let element_name = match selector.element.as_deref() {
+        Some(element) if element != "*" => element.to_string(),
+        _ => String::new(),
+    };
+    let mut result = vec![R3SelectorEntry::Value(element_name)];
+    result.extend(selector.attrs.iter().cloned().map(R3SelectorEntry::Value));
+    if !selector.class_names.is_empty() {
+        result.push(R3SelectorEntry::Flags(SelectorFlags::CLASS as u32));
+        result.extend(
+            selector
+                .class_names
+                .iter()
+                .cloned()
+                .map(R3SelectorEntry::Value),
+        );
+    }
+    result
+}
+
+fn parser_selector_to_negative_selector(selector: &CssSelector) -> R3CssSelector {
+    let classes = selector
+        .class_names
+        .iter()
+        .cloned()
+        .map(R3SelectorEntry::Value);
+    let mut result = Vec::new();
+    if let Some(element) = &selector.element {
+        result.push(R3SelectorEntry::Flags(
+            SelectorFlags::NOT as u32 | SelectorFlags::ELEMENT as u32,
+        ));
+        result.push(R3SelectorEntry::Value(element.clone()));
+        result.extend(selector.attrs.iter().cloned().map(R3SelectorEntry::Value));
+        if !selector.class_names.is_empty() {
+            result.push(R3SelectorEntry::Flags(SelectorFlags::CLASS as u32));
+            result.extend(classes);
+        }
+    } else if !selector.attrs.is_empty() {
+        result.push(R3SelectorEntry::Flags(
+            SelectorFlags::NOT as u32 | SelectorFlags::ATTRIBUTE as u32,
+        ));
+        result.extend(selector.attrs.iter().cloned().map(R3SelectorEntry::Value));
+        if !selector.class_names.is_empty() {
+            result.push(R3SelectorEntry::Flags(SelectorFlags::CLASS as u32));
+            result.extend(classes);
+        }
+    } else if !selector.class_names.is_empty() {
+        result.push(R3SelectorEntry::Flags(
+            SelectorFlags::NOT as u32 | SelectorFlags::CLASS as u32,
+        ));
+        result.extend(classes);
+    }
+    result
+}
+
+fn parser_selector_to_r3_selector(selector: &CssSelector) -> R3CssSelector {
+    let mut result = parser_selector_to_simple_selector(selector);
+    for not_selector in &selector.not_selectors {
+        result.extend(parser_selector_to_negative_selector(not_selector));
+    }
+    result
+}
+
+/// Parse a CSS selector string into the R3 selector format used by the runtime, e.g.
+/// `div[foo].bar:not(.baz)` becomes `[["div", "foo", "", 8, "bar", 9, "baz"]]`.
+pub fn parse_selector_to_r3_selector(selector: Option<&str>) -> R3CssSelectorList {
+    match selector {
+        Some(selector) if !selector.is_empty() => CssSelector::parse(selector)
+            .map(|selectors| {
+                selectors
+                    .iter()
+                    .map(parser_selector_to_r3_selector)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RenderFlags {
     Create = 0b01,
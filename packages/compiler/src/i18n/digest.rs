@@ -44,6 +44,49 @@ pub fn compute_decimal_digest(message: &Message) -> String {
     compute_msg_id(&parts.join(""), &message.meaning)
 }
 
+/// The algorithm used to compute a message id when the message has no custom id.
+///
+/// Serializers and `MessageBundle` take one through `with_id_strategy`; messages with a custom id
+/// keep it whatever the strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageIdStrategy {
+    /// The decimal fingerprint used by XMB, XLIFF2 and `$localize`.
+    #[default]
+    Decimal,
+    /// The SHA1 digest used by XLIFF1.
+    XliffDigest,
+    /// A 64 bit FNV-1a hash of the same content as `Decimal`.
+    ///
+    /// These ids are not understood by other Angular tooling.
+    Fnv,
+}
+
+/// Return the message id or compute it using the given strategy.
+pub fn digest_with(message: &Message, strategy: MessageIdStrategy) -> String {
+    if !message.id.is_empty() {
+        message.id.clone()
+    } else {
+        compute_msg_id_with(message, strategy)
+    }
+}
+
+/// Compute the message id using the given strategy, ignoring any custom id.
+pub fn compute_msg_id_with(message: &Message, strategy: MessageIdStrategy) -> String {
+    match strategy {
+        MessageIdStrategy::Decimal => compute_decimal_digest(message),
+        MessageIdStrategy::XliffDigest => compute_digest(message),
+        MessageIdStrategy::Fnv => {
+            let mut visitor = SerializerIgnoreIcuExpVisitor;
+            let parts: Vec<String> = message
+                .nodes
+                .iter()
+                .map(|node| *node.visit(&mut visitor, None).downcast::<String>().unwrap())
+                .collect();
+            fnv1a(&format!("{}[{}]", parts.join(""), message.meaning)).to_string()
+        }
+    }
+}
+
 /// Serialize the i18n ast to something xml-like in order to generate an UID.
 pub fn serialize_nodes(nodes: &[Node]) -> Vec<String> {
     let mut visitor = SerializerVisitor;
@@ -301,11 +344,11 @@ pub fn sha1(s: &str) -> String {
     let mut e = 0xc3d2e1f0u32;
 
     let mut words32_mut = words32;
-    words32_mut[len >> 5] |= 0x80 << (24 - (len % 32));
     let idx = (((len + 64) >> 9) << 4) + 15;
     if idx >= words32_mut.len() {
         words32_mut.resize(idx + 1, 0);
     }
+    words32_mut[len >> 5] |= 0x80 << (24 - (len % 32));
     words32_mut[idx] = len as u32;
 
     for i in (0..words32_mut.len()).step_by(16) {
@@ -380,6 +423,17 @@ pub fn fingerprint(s: &str) -> u64 {
     ((hi as u64) << 32) | (lo as u64)
 }
 
+/// Compute the 64 bit FNV-1a hash of the given string
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Compute the decimal id of a message from its text and meaning.
+///
+/// The meaning is hashed separately and mixed into the text fingerprint, so messages with the
+/// same text but different meanings get different ids.
 pub fn compute_msg_id(msg: &str, meaning: &str) -> String {
     let mut msg_fingerprint = fingerprint(msg);
 
@@ -398,8 +452,7 @@ fn hash32(bytes: &[u8], length: usize, mut c: u32) -> u32 {
     let mut b = 0x9e3779b9u32;
     let mut index = 0;
 
-    let end = length.saturating_sub(12);
-    while index <= end {
+    while index + 12 <= length {
         a = a.wrapping_add(read_u32_le(bytes, index));
         b = b.wrapping_add(read_u32_le(bytes, index + 4));
         c = c.wrapping_add(read_u32_le(bytes, index + 8));
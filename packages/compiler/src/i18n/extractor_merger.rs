@@ -139,6 +139,7 @@ impl Visitor {
             container_blocks,
             !self.preserve_significant_whitespace,
             self.preserve_significant_whitespace,
+            false,
         ));
     }
 
@@ -185,6 +186,7 @@ impl Visitor {
             end_source_span: Some(ParseSourceSpan::new(end_loc.clone(), end_loc)),
             is_void: false,
             i18n: None,
+            id: 0,
         };
 
         let mut context: *mut () = std::ptr::null_mut();
@@ -447,6 +449,7 @@ impl html::Visitor for Visitor {
                 end_source_span: element.end_source_span.clone(),
                 is_void: element.is_void,
                 i18n: None,
+                id: element.id,
             }))
         } else {
             None
@@ -561,6 +564,7 @@ impl html::Visitor for Visitor {
                 start_source_span: component.start_source_span.clone(),
                 end_source_span: component.end_source_span.clone(),
                 i18n: None,
+                id: component.id,
             }))
         } else {
             None
@@ -701,6 +705,7 @@ impl html::Visitor for Visitor {
                 source_span: expansion.source_span.clone(),
                 switch_value_source_span: expansion.switch_value_source_span.clone(),
                 i18n: None,
+                id: expansion.id,
             }))
         } else {
             None
@@ -725,6 +730,7 @@ impl html::Visitor for Visitor {
                 source_span: icu_case.source_span.clone(),
                 value_source_span: icu_case.value_source_span.clone(),
                 exp_source_span: icu_case.exp_source_span.clone(),
+                id: icu_case.id,
             }))
         } else {
             None
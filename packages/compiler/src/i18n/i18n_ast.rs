@@ -4,6 +4,7 @@
 //! Defines the AST nodes for internationalization messages
 
 use crate::parse_util::ParseSourceSpan;
+use indexmap::IndexMap;
 use std::collections::HashMap;
 
 /// Describes the text contents of a placeholder as it appears in an ICU expression,
@@ -164,16 +165,19 @@ impl Container {
 pub struct Icu {
     pub expression: String,
     pub type_: String,
-    pub cases: HashMap<String, Node>,
+    /// Cases in source order, keyed by selector (`=0`, `other`, `male`, ...).
+    pub cases: IndexMap<String, Node>,
     pub source_span: ParseSourceSpan,
     pub expression_placeholder: Option<String>,
+    /// Placeholder name of the ICU in its parent message (e.g. `ICU`), set by the i18n meta pass.
+    pub name: Option<String>,
 }
 
 impl Icu {
     pub fn new(
         expression: String,
         type_: String,
-        cases: HashMap<String, Node>,
+        cases: IndexMap<String, Node>,
         source_span: ParseSourceSpan,
         expression_placeholder: Option<String>,
     ) -> Self {
@@ -183,6 +187,7 @@ impl Icu {
             cases,
             source_span,
             expression_placeholder,
+            name: None,
         }
     }
 }
@@ -391,7 +396,7 @@ impl Visitor for CloneVisitor {
         icu: &Icu,
         _context: Option<&mut dyn std::any::Any>,
     ) -> Box<dyn std::any::Any> {
-        let mut cases = HashMap::new();
+        let mut cases = IndexMap::new();
         for (key, node) in &icu.cases {
             let result = node.visit(self, None); // TODO: Fix context passing
             cases.insert(key.clone(), *result.downcast::<Node>().unwrap());
@@ -15,6 +15,7 @@ use crate::ml_parser::html_tags::get_html_tag_definition;
 use crate::ml_parser::tags::TagDefinition;
 use crate::ml_parser::tokens::{InterpolationToken, Token};
 use crate::parse_util::{ParseLocation, ParseSourceFile, ParseSourceSpan};
+use indexmap::IndexMap;
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
@@ -42,15 +43,20 @@ pub trait I18nMessageFactory {
 }
 
 /// Returns a function converting html nodes to an i18n Message
+///
+/// With `stable_placeholder_names`, interpolation placeholders are named after their expression,
+/// see `PlaceholderRegistry::with_stable_names`.
 pub fn create_i18n_message_factory(
     container_blocks: HashSet<String>,
     retain_empty_tokens: bool,
     preserve_expression_whitespace: bool,
+    stable_placeholder_names: bool,
 ) -> Box<dyn I18nMessageFactory> {
     Box::new(I18nVisitor::new(
         container_blocks,
         retain_empty_tokens,
         preserve_expression_whitespace,
+        stable_placeholder_names,
     ))
 }
 
@@ -72,6 +78,7 @@ struct I18nVisitor {
     container_blocks: HashSet<String>,
     retain_empty_tokens: bool,
     preserve_expression_whitespace: bool,
+    stable_placeholder_names: bool,
 }
 
 impl I18nVisitor {
@@ -79,12 +86,14 @@ impl I18nVisitor {
         container_blocks: HashSet<String>,
         retain_empty_tokens: bool,
         preserve_expression_whitespace: bool,
+        stable_placeholder_names: bool,
     ) -> Self {
         I18nVisitor {
             expression_parser: ExpressionParser::new(),
             container_blocks,
             retain_empty_tokens,
             preserve_expression_whitespace,
+            stable_placeholder_names,
         }
     }
 
@@ -101,7 +110,8 @@ impl I18nVisitor {
         let mut context = I18nMessageVisitorContext {
             is_icu,
             icu_depth: 0,
-            placeholder_registry: PlaceholderRegistry::new(),
+            placeholder_registry: PlaceholderRegistry::new()
+                .with_stable_names(self.stable_placeholder_names),
             placeholder_to_content: HashMap::new(),
             placeholder_to_message: HashMap::new(),
             visit_node_fn,
@@ -211,11 +221,14 @@ impl I18nVisitor {
                     let parts = &interp_token.parts;
                     if parts.len() >= 3 {
                         let expression = &parts[1];
-                        let base_name = extract_placeholder_name(expression)
-                            .unwrap_or_else(|| "INTERPOLATION".to_string());
-                        let ph_name = context
-                            .placeholder_registry
-                            .get_placeholder_name(&base_name, expression);
+                        let ph_name = match extract_placeholder_name(expression) {
+                            Some(base_name) => context
+                                .placeholder_registry
+                                .get_placeholder_name(&base_name, expression),
+                            None => context
+                                .placeholder_registry
+                                .get_interpolation_placeholder_name(expression),
+                        };
 
                         if self.preserve_expression_whitespace {
                             context.placeholder_to_content.insert(
@@ -252,11 +265,14 @@ impl I18nVisitor {
                     let parts = &attr_interp_token.parts;
                     if parts.len() >= 3 {
                         let expression = &parts[1];
-                        let base_name = extract_placeholder_name(expression)
-                            .unwrap_or_else(|| "INTERPOLATION".to_string());
-                        let ph_name = context
-                            .placeholder_registry
-                            .get_placeholder_name(&base_name, expression);
+                        let ph_name = match extract_placeholder_name(expression) {
+                            Some(base_name) => context
+                                .placeholder_registry
+                                .get_placeholder_name(&base_name, expression),
+                            None => context
+                                .placeholder_registry
+                                .get_interpolation_placeholder_name(expression),
+                        };
 
                         if self.preserve_expression_whitespace {
                             context.placeholder_to_content.insert(
@@ -531,7 +547,13 @@ impl html::Visitor for I18nVisitor {
         context: &mut dyn std::any::Any,
     ) -> Option<Box<dyn std::any::Any>> {
         let ctx = context.downcast_mut::<I18nMessageVisitorContext>().unwrap();
-        let node = if text.tokens.len() == 1 {
+        // A lone interpolation may not be surrounded by (empty) text tokens
+        let is_plain_text = text.tokens.len() == 1
+            && !matches!(
+                text.tokens[0],
+                Token::Interpolation(_) | Token::AttrValueInterpolation(_)
+            );
+        let node = if is_plain_text {
             i18n::Node::Text(I18nText::new(
                 text.value.to_string(),
                 text.source_span.clone(),
@@ -575,7 +597,7 @@ impl html::Visitor for I18nVisitor {
         }
 
         // Visit all cases (this needs mutable access to context)
-        let mut i18n_icu_cases: HashMap<String, i18n::Node> = HashMap::new();
+        let mut i18n_icu_cases: IndexMap<String, i18n::Node> = IndexMap::new();
         for case in &expansion.cases {
             let case_nodes: Vec<i18n::Node> = html::visit_all(self, &case.expression, context)
                 .into_iter()
@@ -600,6 +622,7 @@ impl html::Visitor for I18nVisitor {
             cases: i18n_icu_cases.clone(),
             source_span: expansion.source_span.clone(),
             expression_placeholder: None,
+            name: None,
         };
 
         if ctx.is_icu || ctx.icu_depth > 0 {
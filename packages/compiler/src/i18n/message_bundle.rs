@@ -3,23 +3,29 @@
 //! Corresponds to packages/compiler/src/i18n/message_bundle.ts
 //! A container for messages extracted from templates
 
+use crate::i18n::digest::{compute_msg_id_with, digest_with, MessageIdStrategy};
 use crate::i18n::extractor_merger::extract_messages;
 use crate::i18n::i18n_ast::{Message, Node, Visitor};
 use crate::i18n::serializers::serializer::{PlaceholderMapper, Serializer};
 use crate::ml_parser::ast::Node as HtmlNode;
 use crate::ml_parser::html_parser::HtmlParser;
 use crate::ml_parser::lexer::TokenizeOptions;
-use crate::parse_util::ParseError;
+use crate::parse_util::{ParseError, ParseSourceSpan};
+use indexmap::IndexMap;
 use std::collections::HashMap;
 
 /// A container for message extracted from the templates
 pub struct MessageBundle {
     messages: Vec<Message>,
+    /// The index in `messages` of each message id, so that duplicates are found without
+    /// digesting every message again.
+    message_indices: HashMap<String, usize>,
     html_parser: HtmlParser,
     implicit_tags: Vec<String>,
     implicit_attrs: HashMap<String, Vec<String>>,
     locale: Option<String>,
     preserve_whitespace: bool,
+    id_strategy: Option<MessageIdStrategy>,
 }
 
 impl MessageBundle {
@@ -32,14 +38,28 @@ impl MessageBundle {
     ) -> Self {
         MessageBundle {
             messages: Vec::new(),
+            message_indices: HashMap::new(),
             html_parser,
             implicit_tags,
             implicit_attrs,
             locale,
             preserve_whitespace,
+            id_strategy: None,
         }
     }
 
+    /// Compute message ids with the given strategy instead of the serializer's own digest.
+    pub fn with_id_strategy(mut self, strategy: MessageIdStrategy) -> Self {
+        self.id_strategy = Some(strategy);
+        self.message_indices = self
+            .messages
+            .iter()
+            .enumerate()
+            .map(|(index, message)| (digest_with(message, strategy), index))
+            .collect();
+        self
+    }
+
     pub fn update_from_template(&mut self, source: &str, url: &str) -> Vec<ParseError> {
         // Parse HTML with tokenizeExpansionForms: true
         let mut tokenize_options = TokenizeOptions::default();
@@ -73,26 +93,86 @@ impl MessageBundle {
             return i18n_parser_result.errors;
         }
 
-        // Push messages to self.messages
-        self.messages.extend(i18n_parser_result.messages);
-        vec![]
+        i18n_parser_result
+            .messages
+            .into_iter()
+            .filter_map(|message| self.add(message).err())
+            .collect()
+    }
+
+    /// Add a message to the bundle.
+    ///
+    /// A message whose id is already in the bundle is merged into the existing message when both
+    /// have the same content. When the content differs, the bundle is left untouched and an error
+    /// pointing at the new message, and naming the location of the existing one, is returned.
+    pub fn add(&mut self, message: Message) -> Result<(), ParseError> {
+        let id = digest_with(&message, self.id_strategy.unwrap_or_default());
+        let existing = match self.message_indices.get(&id) {
+            Some(&index) => &mut self.messages[index],
+            None => {
+                self.message_indices.insert(id, self.messages.len());
+                self.messages.push(message);
+                return Ok(());
+            }
+        };
+
+        if existing.message_string == message.message_string {
+            existing.sources.extend(message.sources);
+            return Ok(());
+        }
+
+        let existing_span = message_span(existing);
+        let span = message_span(&message)
+            .or_else(|| existing_span.clone())
+            .expect("messages with different content cannot both be empty");
+        let existing_location = existing_span
+            .map(|span| span.start.to_string())
+            .unwrap_or_else(|| "an empty message".to_string());
+        Err(ParseError::new(
+            span,
+            format!(
+                "Duplicate message id \"{}\" with different content, first defined at {}",
+                id, existing_location
+            ),
+        ))
     }
 
     pub fn get_messages(&self) -> &[Message] {
         &self.messages
     }
 
+    /// Compute the legacy (View Engine) ids of the extracted messages, so that serializers write
+    /// them next to the primary id and existing translations keep matching.
+    ///
+    /// Messages with a custom id have no legacy ids.
+    pub fn compute_legacy_ids(&mut self) {
+        for message in &mut self.messages {
+            message.legacy_ids = if message.custom_id.is_empty() {
+                vec![
+                    compute_msg_id_with(message, MessageIdStrategy::XliffDigest),
+                    compute_msg_id_with(message, MessageIdStrategy::Decimal),
+                ]
+            } else {
+                vec![]
+            };
+        }
+    }
+
     pub fn write(
         &self,
         serializer: &dyn Serializer,
         filter_sources: Option<fn(&str) -> String>,
     ) -> String {
-        let mut messages: HashMap<String, Message> = HashMap::new();
+        // Keep messages in extraction order so that the output is stable across runs
+        let mut messages: IndexMap<String, Message> = IndexMap::new();
         let mapper_visitor = MapPlaceholderNames;
 
         // Deduplicate messages based on their ID
         for message in &self.messages {
-            let id = serializer.digest(message);
+            let id = match self.id_strategy {
+                Some(strategy) => digest_with(message, strategy),
+                None => serializer.digest(message),
+            };
             if !messages.contains_key(&id) {
                 messages.insert(id.clone(), message.clone());
             } else {
@@ -123,6 +203,11 @@ impl MessageBundle {
                     id.clone(),
                 );
                 transformed_message.sources = src.sources;
+                transformed_message.legacy_ids = src
+                    .legacy_ids
+                    .into_iter()
+                    .filter(|legacy_id| *legacy_id != id)
+                    .collect();
                 transformed_message.id = id;
 
                 // Apply filter_sources if provided
@@ -140,6 +225,67 @@ impl MessageBundle {
     }
 }
 
+/// Ids of the messages that differ between two extractions of the same application
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageBundleDiff {
+    /// Messages that are new in the newer bundle
+    pub added: Vec<String>,
+    /// Messages that are gone from the newer bundle
+    pub removed: Vec<String>,
+    /// Messages present in both bundles whose source text changed, which only happens for
+    /// messages with a custom id
+    pub changed: Vec<String>,
+}
+
+impl MessageBundleDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare an existing catalog with a newly extracted one, e.g. to detect messages whose
+/// translations are missing or out of date.
+///
+/// Messages are matched by the id each bundle computes for them. Added and changed ids are
+/// listed in the order of the newer bundle, removed ids in the order of the older one.
+pub fn diff_message_bundles(old: &MessageBundle, new: &MessageBundle) -> MessageBundleDiff {
+    let old_messages = messages_by_id(old);
+    let new_messages = messages_by_id(new);
+
+    let mut diff = MessageBundleDiff::default();
+    for (id, message) in &new_messages {
+        match old_messages.get(id) {
+            None => diff.added.push(id.clone()),
+            Some(old_message) if old_message.message_string != message.message_string => {
+                diff.changed.push(id.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    diff.removed = old_messages
+        .keys()
+        .filter(|id| !new_messages.contains_key(*id))
+        .cloned()
+        .collect();
+    diff
+}
+
+fn messages_by_id(bundle: &MessageBundle) -> IndexMap<String, &Message> {
+    let strategy = bundle.id_strategy.unwrap_or_default();
+    bundle
+        .messages
+        .iter()
+        .map(|message| (digest_with(message, strategy), message))
+        .collect()
+}
+
+/// The span covering all the nodes of a message, if it has any.
+fn message_span(message: &Message) -> Option<ParseSourceSpan> {
+    let first = message.nodes.first()?.source_span();
+    let last = message.nodes.last()?.source_span();
+    Some(ParseSourceSpan::new(first.start.clone(), last.end.clone()))
+}
+
 /// Transform an i18n AST by renaming the placeholder nodes with the given mapper
 struct MapPlaceholderNames;
 
@@ -193,7 +339,7 @@ impl Visitor for MapPlaceholderNamesVisitor<'_> {
         icu: &crate::i18n::i18n_ast::Icu,
         _context: Option<&mut dyn std::any::Any>,
     ) -> Box<dyn std::any::Any> {
-        let cases: HashMap<String, crate::i18n::i18n_ast::Node> = icu
+        let cases: IndexMap<String, crate::i18n::i18n_ast::Node> = icu
             .cases
             .iter()
             .map(|(k, v)| {
@@ -207,6 +353,7 @@ impl Visitor for MapPlaceholderNamesVisitor<'_> {
             cases,
             source_span: icu.source_span.clone(),
             expression_placeholder: icu.expression_placeholder.clone(),
+            name: icu.name.clone(),
         }))
     }
 
@@ -15,9 +15,9 @@ pub mod translation_bundle;
 // Re-export commonly used items (matching index.ts exports)
 pub use digest::compute_msg_id;
 pub use i18n_html_parser::I18NHtmlParser;
-pub use message_bundle::MessageBundle;
+pub use message_bundle::{diff_message_bundles, MessageBundle, MessageBundleDiff};
 pub use serializers::serializer::Serializer;
-pub use serializers::{Xliff, Xliff2, Xmb, Xtb};
+pub use serializers::{Arb, Json, Xliff, Xliff2, Xmb, Xtb};
 
 // Additional exports for internal use
 pub use i18n_ast::{
@@ -26,11 +26,14 @@ pub use i18n_ast::{
 };
 
 pub use digest::{
-    compute_decimal_digest, compute_digest, decimal_digest, digest, fingerprint, sha1,
+    compute_decimal_digest, compute_digest, compute_msg_id_with, decimal_digest, digest,
+    digest_with, fingerprint, sha1, MessageIdStrategy,
 };
 
 pub use extractor_merger::{extract_messages, merge_translations, ExtractionResult};
 pub use i18n_parser::{create_i18n_message_factory, I18nMessageFactory};
 pub use serializers::placeholder::PlaceholderRegistry;
-pub use serializers::xml_helper::{escape_xml, serialize};
+pub use serializers::xml_helper::{
+    escape_xml, escape_xml_with, serialize, InvalidXmlCharError, InvalidXmlChars,
+};
 pub use translation_bundle::TranslationBundle;
@@ -0,0 +1,95 @@
+//! ARB Serializer Module
+//!
+//! Mirrors the `arb` format of angular/packages/localize/tools (extract and translate)
+//! Application Resource Bundle format, see https://github.com/google/app-resource-bundle
+
+use crate::i18n::digest::{digest_with, MessageIdStrategy};
+use crate::i18n::i18n_ast::Message;
+use crate::i18n::serializers::json::serialize_message_text;
+use crate::i18n::serializers::serializer::Serializer;
+use crate::i18n::translation_bundle::LoadResult;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// ARB serializer
+///
+/// Each message is written as `id: text`, followed by an `@id` entry holding its description
+/// and source locations.
+pub struct Arb {
+    id_strategy: MessageIdStrategy,
+}
+
+impl Arb {
+    pub fn new() -> Self {
+        Arb {
+            id_strategy: MessageIdStrategy::Decimal,
+        }
+    }
+
+    pub fn with_id_strategy(mut self, strategy: MessageIdStrategy) -> Self {
+        self.id_strategy = strategy;
+        self
+    }
+}
+
+impl Default for Arb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serializer for Arb {
+    fn write(&self, messages: &[Message], locale: Option<&str>) -> String {
+        let mut root = Map::new();
+        if let Some(locale) = locale {
+            root.insert("@@locale".to_string(), Value::String(locale.to_string()));
+        }
+
+        for message in messages {
+            root.insert(
+                message.id.clone(),
+                Value::String(serialize_message_text(&message.nodes)),
+            );
+
+            let mut meta = Map::new();
+            if !message.description.is_empty() {
+                meta.insert(
+                    "description".to_string(),
+                    Value::String(message.description.clone()),
+                );
+            }
+            if !message.sources.is_empty() {
+                let locations = message
+                    .sources
+                    .iter()
+                    .map(|source| {
+                        json!({
+                            "file": source.file_path,
+                            "start": {"line": source.start_line, "column": source.start_col},
+                            "end": {"line": source.end_line, "column": source.end_col},
+                        })
+                    })
+                    .collect();
+                meta.insert("x-locations".to_string(), Value::Array(locations));
+            }
+            if !meta.is_empty() {
+                root.insert(format!("@{}", message.id), Value::Object(meta));
+            }
+        }
+
+        serde_json::to_string_pretty(&Value::Object(root)).unwrap()
+    }
+
+    fn load(&self, _content: &str, _url: &str) -> LoadResult {
+        // TODO: Implement ARB load
+        LoadResult {
+            locale: None,
+            i18n_nodes_by_msg_id: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn digest(&self, message: &Message) -> String {
+        digest_with(message, self.id_strategy)
+    }
+}
@@ -0,0 +1,179 @@
+//! JSON Serializer Module
+//!
+//! Mirrors the `json` format of angular/packages/localize/tools (extract and translate)
+//! Simple JSON translation file format: `{"locale": ..., "translations": {id: text}}`
+
+use crate::i18n::digest::{digest_with, MessageIdStrategy};
+use crate::i18n::i18n_ast::{self as i18n, Message, Node, Visitor};
+use crate::i18n::serializers::serializer::Serializer;
+use crate::i18n::translation_bundle::LoadResult;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// JSON serializer
+///
+/// Placeholders are written as `{$NAME}` and ICU expressions in ICU message syntax.
+pub struct Json {
+    id_strategy: MessageIdStrategy,
+}
+
+impl Json {
+    pub fn new() -> Self {
+        Json {
+            id_strategy: MessageIdStrategy::Decimal,
+        }
+    }
+
+    pub fn with_id_strategy(mut self, strategy: MessageIdStrategy) -> Self {
+        self.id_strategy = strategy;
+        self
+    }
+}
+
+impl Default for Json {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serializer for Json {
+    fn write(&self, messages: &[Message], locale: Option<&str>) -> String {
+        let mut translations = Map::new();
+        for message in messages {
+            translations.insert(
+                message.id.clone(),
+                Value::String(serialize_message_text(&message.nodes)),
+            );
+        }
+
+        let mut root = Map::new();
+        if let Some(locale) = locale {
+            root.insert("locale".to_string(), Value::String(locale.to_string()));
+        }
+        root.insert("translations".to_string(), Value::Object(translations));
+
+        serde_json::to_string_pretty(&Value::Object(root)).unwrap()
+    }
+
+    fn load(&self, _content: &str, _url: &str) -> LoadResult {
+        // TODO: Implement JSON load
+        LoadResult {
+            locale: None,
+            i18n_nodes_by_msg_id: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    fn digest(&self, message: &Message) -> String {
+        digest_with(message, self.id_strategy)
+    }
+}
+
+/// Serialize message nodes to the text used by the JSON based formats (JSON and ARB), e.g.
+/// `Hello {$INTERPOLATION}!` or `{VAR_PLURAL, plural, =0 {none} other {{$INTERPOLATION}}}`.
+pub(crate) fn serialize_message_text(nodes: &[Node]) -> String {
+    let mut visitor = MessageTextVisitor;
+    nodes
+        .iter()
+        .map(|node| *node.visit(&mut visitor, None).downcast::<String>().unwrap())
+        .collect()
+}
+
+struct MessageTextVisitor;
+
+impl MessageTextVisitor {
+    fn serialize_children(&mut self, nodes: &[Node]) -> String {
+        nodes
+            .iter()
+            .map(|node| *node.visit(self, None).downcast::<String>().unwrap())
+            .collect()
+    }
+}
+
+impl Visitor for MessageTextVisitor {
+    fn visit_text(
+        &mut self,
+        text: &i18n::Text,
+        _context: Option<&mut dyn std::any::Any>,
+    ) -> Box<dyn std::any::Any> {
+        Box::new(text.value.clone())
+    }
+
+    fn visit_container(
+        &mut self,
+        container: &i18n::Container,
+        _context: Option<&mut dyn std::any::Any>,
+    ) -> Box<dyn std::any::Any> {
+        Box::new(self.serialize_children(&container.children))
+    }
+
+    fn visit_icu(
+        &mut self,
+        icu: &i18n::Icu,
+        _context: Option<&mut dyn std::any::Any>,
+    ) -> Box<dyn std::any::Any> {
+        let expression = icu
+            .expression_placeholder
+            .as_deref()
+            .unwrap_or(&icu.expression);
+        let cases: Vec<String> = icu
+            .cases
+            .iter()
+            .map(|(k, v)| {
+                let case = *v.visit(self, None).downcast::<String>().unwrap();
+                format!("{} {{{}}}", k, case)
+            })
+            .collect();
+        Box::new(format!(
+            "{{{}, {}, {}}}",
+            expression,
+            icu.type_,
+            cases.join(" ")
+        ))
+    }
+
+    fn visit_tag_placeholder(
+        &mut self,
+        ph: &i18n::TagPlaceholder,
+        _context: Option<&mut dyn std::any::Any>,
+    ) -> Box<dyn std::any::Any> {
+        if ph.is_void {
+            return Box::new(format!("{{${}}}", ph.start_name));
+        }
+        Box::new(format!(
+            "{{${}}}{}{{${}}}",
+            ph.start_name,
+            self.serialize_children(&ph.children),
+            ph.close_name
+        ))
+    }
+
+    fn visit_placeholder(
+        &mut self,
+        ph: &i18n::Placeholder,
+        _context: Option<&mut dyn std::any::Any>,
+    ) -> Box<dyn std::any::Any> {
+        Box::new(format!("{{${}}}", ph.name))
+    }
+
+    fn visit_block_placeholder(
+        &mut self,
+        ph: &i18n::BlockPlaceholder,
+        _context: Option<&mut dyn std::any::Any>,
+    ) -> Box<dyn std::any::Any> {
+        Box::new(format!(
+            "{{${}}}{}{{${}}}",
+            ph.start_name,
+            self.serialize_children(&ph.children),
+            ph.close_name
+        ))
+    }
+
+    fn visit_icu_placeholder(
+        &mut self,
+        ph: &i18n::IcuPlaceholder,
+        _context: Option<&mut dyn std::any::Any>,
+    ) -> Box<dyn std::any::Any> {
+        Box::new(format!("{{${}}}", ph.name))
+    }
+}
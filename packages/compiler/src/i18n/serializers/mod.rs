@@ -3,6 +3,8 @@
 //! Corresponds to packages/compiler/src/i18n/serializers/
 //! Contains various i18n serialization formats
 
+pub mod arb;
+pub mod json;
 pub mod placeholder;
 pub mod serializer;
 pub mod xliff;
@@ -12,10 +14,15 @@ pub mod xml_helper;
 pub mod xtb;
 
 // Re-export commonly used items
+pub use arb::Arb;
+pub use json::Json;
 pub use placeholder::PlaceholderRegistry;
 pub use serializer::{PlaceholderMapper, Serializer, SimplePlaceholderMapper};
 pub use xliff::Xliff;
-pub use xliff2::Xliff2;
+pub use xliff2::{SegmentState, Xliff2};
 pub use xmb::Xmb;
-pub use xml_helper::{serialize, Declaration, Doctype, Node, Tag, Text, CR};
+pub use xml_helper::{
+    escape_xml_with, serialize, Declaration, Doctype, InvalidXmlCharError, InvalidXmlChars, Node,
+    Tag, Text, CR,
+};
 pub use xtb::Xtb;
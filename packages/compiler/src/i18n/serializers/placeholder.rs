@@ -52,6 +52,8 @@ pub struct PlaceholderRegistry {
     place_holder_name_counts: HashMap<String, usize>,
     // Maps signature to placeholder names
     signature_to_name: HashMap<String, String>,
+    // Derive interpolation placeholder names from their expression
+    stable_names: bool,
 }
 
 impl PlaceholderRegistry {
@@ -59,9 +61,22 @@ impl PlaceholderRegistry {
         PlaceholderRegistry {
             place_holder_name_counts: HashMap::new(),
             signature_to_name: HashMap::new(),
+            stable_names: false,
         }
     }
 
+    /// Name interpolation placeholders after their expression (`{{user.name}}` becomes
+    /// `USER_NAME`) instead of `INTERPOLATION`, `INTERPOLATION_1`, ...
+    ///
+    /// Names then don't depend on the position of the interpolation in the message, so
+    /// reordering the markup doesn't rename the placeholders of the other interpolations.
+    /// Different expressions deriving the same name are disambiguated with a numeric suffix in
+    /// order of appearance.
+    pub fn with_stable_names(mut self, stable_names: bool) -> Self {
+        self.stable_names = stable_names;
+        self
+    }
+
     pub fn get_start_tag_placeholder_name(
         &mut self,
         tag: &str,
@@ -121,6 +136,16 @@ impl PlaceholderRegistry {
         unique_name
     }
 
+    /// Returns the placeholder name of an interpolation without a custom placeholder name.
+    pub fn get_interpolation_placeholder_name(&mut self, expression: &str) -> String {
+        let base_name = if self.stable_names {
+            expression_to_placeholder_name(expression)
+        } else {
+            None
+        };
+        self.get_placeholder_name(base_name.as_deref().unwrap_or("INTERPOLATION"), expression)
+    }
+
     pub fn get_unique_placeholder(&mut self, name: &str) -> String {
         self.generate_unique_name(&name.to_uppercase())
     }
@@ -214,6 +239,35 @@ impl PlaceholderRegistry {
     }
 }
 
+/// Converts an expression to an upper snake case placeholder name, e.g. `user.firstName` to
+/// `USER_FIRST_NAME`. Returns `None` when the expression yields no valid name.
+fn expression_to_placeholder_name(expression: &str) -> Option<String> {
+    // Drop trailing comments such as `// i18n(...)`
+    let expression = expression.split("//").next().unwrap_or_default();
+
+    let mut name = String::new();
+    let mut prev: Option<char> = None;
+    for c in expression.chars() {
+        if c.is_ascii_alphanumeric() {
+            let camel_boundary =
+                c.is_ascii_uppercase() && prev.is_some_and(|p| p.is_ascii_lowercase());
+            if camel_boundary && !name.ends_with('_') {
+                name.push('_');
+            }
+            name.push(c.to_ascii_uppercase());
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+        prev = Some(c);
+    }
+
+    let name = name.trim_end_matches('_');
+    match name.chars().next() {
+        Some(first) if first.is_ascii_alphabetic() => Some(name.to_string()),
+        _ => None,
+    }
+}
+
 impl Default for PlaceholderRegistry {
     fn default() -> Self {
         Self::new()
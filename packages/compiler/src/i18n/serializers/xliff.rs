@@ -4,7 +4,7 @@
 //! XLIFF 1.2 format serializer
 #![allow(dead_code)]
 
-use crate::i18n::digest::digest;
+use crate::i18n::digest::{digest_with, MessageIdStrategy};
 use crate::i18n::i18n_ast::{self as i18n, Message, Node, Visitor};
 use crate::i18n::serializers::serializer::{PlaceholderMapper, Serializer};
 use crate::i18n::serializers::xml_helper as xml;
@@ -29,12 +29,19 @@ const CONTEXT_TAG: &str = "context";
 /// See https://docs.oasis-open.org/xliff/v1.2/os/xliff-core.html
 /// See https://docs.oasis-open.org/xliff/v1.2/xliff-profile-html/xliff-profile-html-1.2.html
 pub struct Xliff {
-    // Implementation fields
+    id_strategy: MessageIdStrategy,
 }
 
 impl Xliff {
     pub fn new() -> Self {
-        Xliff {}
+        Xliff {
+            id_strategy: MessageIdStrategy::XliffDigest,
+        }
+    }
+
+    pub fn with_id_strategy(mut self, strategy: MessageIdStrategy) -> Self {
+        self.id_strategy = strategy;
+        self
     }
 }
 
@@ -66,8 +73,11 @@ impl Serializer for Xliff {
             unit_attrs.insert("id".to_string(), message.id.clone());
             let mut unit_tag = xml::Tag::new(UNIT_TAG.to_string(), unit_attrs, Vec::new());
 
-            // Add context-group with context for meaning and description
-            if !message.meaning.is_empty() || !message.description.is_empty() {
+            // Add context-group with context for meaning, description and legacy ids
+            if !message.meaning.is_empty()
+                || !message.description.is_empty()
+                || !message.legacy_ids.is_empty()
+            {
                 let mut context_group =
                     xml::Tag::new(CONTEXT_GROUP_TAG.to_string(), HashMap::new(), Vec::new());
 
@@ -93,6 +103,17 @@ impl Serializer for Xliff {
                     context_group.children.push(Box::new(context_tag));
                 }
 
+                for legacy_id in &message.legacy_ids {
+                    let mut context_attrs = HashMap::new();
+                    context_attrs.insert("context-type".to_string(), "x-legacy-id".to_string());
+                    let mut context_tag =
+                        xml::Tag::new(CONTEXT_TAG.to_string(), context_attrs, Vec::new());
+                    context_tag
+                        .children
+                        .push(Box::new(xml::Text::new(legacy_id.clone())));
+                    context_group.children.push(Box::new(context_tag));
+                }
+
                 unit_tag.children.push(Box::new(context_group));
             }
 
@@ -124,11 +145,12 @@ impl Serializer for Xliff {
         LoadResult {
             locale: None,
             i18n_nodes_by_msg_id: HashMap::new(),
+            errors: Vec::new(),
         }
     }
 
     fn digest(&self, message: &Message) -> String {
-        digest(message)
+        digest_with(message, self.id_strategy)
     }
 
     fn create_name_mapper(&self, _message: &Message) -> Option<Box<dyn PlaceholderMapper>> {
@@ -183,26 +205,18 @@ impl Visitor for XliffVisitor {
         icu: &i18n::Icu,
         _context: Option<&mut dyn std::any::Any>,
     ) -> Box<dyn std::any::Any> {
-        // ICU messages are converted to text representation
-        let mut result: Vec<Box<dyn xml::Node>> = Vec::new();
-        let mut case_texts = Vec::new();
+        let mut result: Vec<Box<dyn xml::Node>> = vec![Box::new(xml::Text::new(format!(
+            "{{{}, {}, ",
+            icu.expression, icu.type_
+        )))];
         for (k, v) in &icu.cases {
-            let case_nodes = v.visit(self, None);
-            let case_text = if let Ok(nodes_vec) = case_nodes.downcast::<Vec<Box<dyn xml::Node>>>()
-            {
-                xml::serialize(&nodes_vec)
-            } else {
-                String::new()
-            };
-            case_texts.push(format!("{} {{{}}}", k, case_text));
+            result.push(Box::new(xml::Text::new(format!("{} {{", k))));
+            if let Ok(nodes_vec) = v.visit(self, None).downcast::<Vec<Box<dyn xml::Node>>>() {
+                result.extend(*nodes_vec);
+            }
+            result.push(Box::new(xml::Text::new("} ".to_string())));
         }
-        let icu_text = format!(
-            "{{{{{}}}, {}, {}}}",
-            icu.expression,
-            icu.type_,
-            case_texts.join(" ")
-        );
-        result.push(Box::new(xml::Text::new(icu_text)));
+        result.push(Box::new(xml::Text::new("}".to_string())));
         Box::new(result)
     }
 
@@ -260,8 +274,21 @@ impl Visitor for XliffVisitor {
         ph: &i18n::IcuPlaceholder,
         _context: Option<&mut dyn std::any::Any>,
     ) -> Box<dyn std::any::Any> {
+        let cases: Vec<String> = ph
+            .value
+            .cases
+            .keys()
+            .map(|value| format!("{} {{...}}", value))
+            .collect();
+        let equiv_text = format!(
+            "{{{}, {}, {}}}",
+            ph.value.expression,
+            ph.value.type_,
+            cases.join(" ")
+        );
         let mut attrs = HashMap::new();
         attrs.insert("id".to_string(), ph.name.clone());
+        attrs.insert("equiv-text".to_string(), equiv_text);
         let ph_tag = xml::Tag::new(PLACEHOLDER_TAG.to_string(), attrs, Vec::new());
         Box::new(vec![Box::new(ph_tag) as Box<dyn xml::Node>])
     }
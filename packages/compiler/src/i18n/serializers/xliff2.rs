@@ -4,11 +4,16 @@
 //! XLIFF 2.0 format serializer
 #![allow(dead_code)]
 
-use crate::i18n::digest::decimal_digest;
+use crate::i18n::digest::{digest_with, MessageIdStrategy};
 use crate::i18n::i18n_ast::{self as i18n, Message, Node, Visitor};
 use crate::i18n::serializers::serializer::{PlaceholderMapper, Serializer};
 use crate::i18n::serializers::xml_helper as xml;
 use crate::i18n::translation_bundle::LoadResult;
+use crate::ml_parser::ast as html;
+use crate::ml_parser::lexer::TokenizeOptions;
+use crate::ml_parser::xml_parser::XmlParser;
+use crate::parse_util::{ParseError, ParseErrorLevel, ParseSourceSpan};
+use indexmap::IndexMap;
 use std::collections::HashMap;
 
 const VERSION: &str = "2.0";
@@ -21,16 +26,98 @@ const XLIFF_TAG: &str = "xliff";
 const SOURCE_TAG: &str = "source";
 const TARGET_TAG: &str = "target";
 const UNIT_TAG: &str = "unit";
+const SEGMENT_TAG: &str = "segment";
+
+/// Translation-workflow state of a `<segment>`, from its `state` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentState {
+    /// Not translated yet
+    Initial,
+    Translated,
+    Reviewed,
+    /// Signed off, the translation is not expected to change anymore
+    Final,
+}
+
+impl SegmentState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SegmentState::Initial => "initial",
+            SegmentState::Translated => "translated",
+            SegmentState::Reviewed => "reviewed",
+            SegmentState::Final => "final",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "initial" => Some(SegmentState::Initial),
+            "translated" => Some(SegmentState::Translated),
+            "reviewed" => Some(SegmentState::Reviewed),
+            "final" => Some(SegmentState::Final),
+            _ => None,
+        }
+    }
+}
 
 /// XLIFF 2.0 serializer
 /// See https://docs.oasis-open.org/xliff/xliff-core/v2.0/os/xliff-core-v2.0-os.html
 pub struct Xliff2 {
-    // Implementation fields
+    id_strategy: MessageIdStrategy,
+    segment_states: HashMap<String, SegmentState>,
 }
 
 impl Xliff2 {
     pub fn new() -> Self {
-        Xliff2 {}
+        Xliff2 {
+            id_strategy: MessageIdStrategy::Decimal,
+            segment_states: HashMap::new(),
+        }
+    }
+
+    pub fn with_id_strategy(mut self, strategy: MessageIdStrategy) -> Self {
+        self.id_strategy = strategy;
+        self
+    }
+
+    /// Write the given states, keyed by message id, on the `<segment>` of the messages.
+    ///
+    /// Pass the states returned by [`Xliff2::load_with_states`] to keep them across a
+    /// re-extraction.
+    pub fn with_segment_states(mut self, states: HashMap<String, SegmentState>) -> Self {
+        self.segment_states = states;
+        self
+    }
+
+    /// Load translations along with the state of their segment, keyed by message id.
+    ///
+    /// Only segments with a `state` attribute get an entry. A segment in the `initial` state
+    /// may have no `<target>`; it's then reported through its state rather than as an error.
+    pub fn load_with_states(
+        &self,
+        content: &str,
+        url: &str,
+    ) -> (LoadResult, HashMap<String, SegmentState>) {
+        let options = TokenizeOptions {
+            tokenize_expansion_forms: true,
+            ..Default::default()
+        };
+        let parse_result = XmlParser::new().parse(content, url, Some(options));
+
+        let mut parser = Xliff2Parser {
+            locale: None,
+            i18n_nodes_by_msg_id: HashMap::new(),
+            segment_states: HashMap::new(),
+            errors: parse_result.errors,
+        };
+        parser.visit_all(&parse_result.root_nodes);
+
+        let result = LoadResult {
+            locale: parser.locale,
+            i18n_nodes_by_msg_id: parser.i18n_nodes_by_msg_id,
+            errors: parser.errors,
+        };
+        (result, parser.segment_states)
     }
 }
 
@@ -83,7 +170,11 @@ impl Serializer for Xliff2 {
 
             // Add segment with source
             let source_nodes = visitor.serialize(&message.nodes);
-            let mut segment_tag = xml::Tag::new("segment".to_string(), HashMap::new(), Vec::new());
+            let mut segment_attrs = HashMap::new();
+            if let Some(state) = self.segment_states.get(&message.id) {
+                segment_attrs.insert("state".to_string(), state.as_str().to_string());
+            }
+            let mut segment_tag = xml::Tag::new(SEGMENT_TAG.to_string(), segment_attrs, Vec::new());
             let mut source_tag = xml::Tag::new(SOURCE_TAG.to_string(), HashMap::new(), Vec::new());
             source_tag.children.extend(source_nodes);
             segment_tag.children.push(Box::new(source_tag));
@@ -105,16 +196,12 @@ impl Serializer for Xliff2 {
         xml::serialize(&nodes)
     }
 
-    fn load(&self, _content: &str, _url: &str) -> LoadResult {
-        // TODO: Implement XLIFF 2.0 load
-        LoadResult {
-            locale: None,
-            i18n_nodes_by_msg_id: HashMap::new(),
-        }
+    fn load(&self, content: &str, url: &str) -> LoadResult {
+        self.load_with_states(content, url).0
     }
 
     fn digest(&self, message: &Message) -> String {
-        decimal_digest(message)
+        digest_with(message, self.id_strategy)
     }
 
     fn create_name_mapper(&self, _message: &Message) -> Option<Box<dyn PlaceholderMapper>> {
@@ -122,6 +209,204 @@ impl Serializer for Xliff2 {
     }
 }
 
+/// Extracts the locale, the translations and the segment states from a parsed XLIFF 2.0 file
+struct Xliff2Parser {
+    locale: Option<String>,
+    i18n_nodes_by_msg_id: HashMap<String, Vec<i18n::Node>>,
+    segment_states: HashMap<String, SegmentState>,
+    errors: Vec<ParseError>,
+}
+
+impl Xliff2Parser {
+    fn visit_all(&mut self, nodes: &[html::Node]) {
+        for node in nodes {
+            if let html::Node::Element(element) = node {
+                self.visit_element(element);
+            }
+        }
+    }
+
+    fn visit_element(&mut self, element: &html::Element) {
+        match &*element.name {
+            XLIFF_TAG => {
+                self.locale = find_attr(element, "trgLang");
+                let version = find_attr(element, "version").unwrap_or_default();
+                if version != VERSION {
+                    self.add_error(
+                        &element.source_span,
+                        format!(
+                            "The XLIFF file version {} is not compatible with XLIFF 2.0 serializer",
+                            version
+                        ),
+                    );
+                }
+                self.visit_all(&element.children);
+            }
+            UNIT_TAG => self.visit_unit(element),
+            _ => self.visit_all(&element.children),
+        }
+    }
+
+    fn visit_unit(&mut self, unit: &html::Element) {
+        let Some(id) = find_attr(unit, "id") else {
+            self.add_error(
+                &unit.source_span,
+                format!("<{}> misses the \"id\" attribute", UNIT_TAG),
+            );
+            return;
+        };
+
+        if self.i18n_nodes_by_msg_id.contains_key(&id) || self.segment_states.contains_key(&id) {
+            self.add_error(
+                &unit.source_span,
+                format!("Duplicated translations for msg {}", id),
+            );
+            return;
+        }
+
+        let segment = child_elements(unit).find(|el| &*el.name == SEGMENT_TAG);
+        let state = segment.and_then(|segment| {
+            let value = find_attr(segment, "state")?;
+            let state = SegmentState::parse(&value);
+            if state.is_none() {
+                self.errors.push(ParseError {
+                    span: segment.source_span.clone(),
+                    msg: format!("Unknown segment state \"{}\"", value),
+                    level: ParseErrorLevel::Warning,
+                });
+            }
+            state
+        });
+        if let Some(state) = state {
+            self.segment_states.insert(id.clone(), state);
+        }
+
+        let target =
+            segment.and_then(|segment| child_elements(segment).find(|el| &*el.name == TARGET_TAG));
+        match target {
+            Some(target) => {
+                let nodes = XmlToI18n {
+                    errors: &mut self.errors,
+                }
+                .convert(&target.children);
+                self.i18n_nodes_by_msg_id.insert(id, nodes);
+            }
+            None if state == Some(SegmentState::Initial) => {}
+            None => self.add_error(
+                &unit.source_span,
+                format!("Message {} misses a translation", id),
+            ),
+        }
+    }
+
+    fn add_error(&mut self, span: &ParseSourceSpan, msg: String) {
+        self.errors.push(ParseError::new(span.clone(), msg));
+    }
+}
+
+/// Converts the content of a `<target>` to i18n nodes
+struct XmlToI18n<'a> {
+    errors: &'a mut Vec<ParseError>,
+}
+
+impl XmlToI18n<'_> {
+    fn convert(&mut self, nodes: &[html::Node]) -> Vec<i18n::Node> {
+        nodes
+            .iter()
+            .flat_map(|node| self.convert_node(node))
+            .collect()
+    }
+
+    fn convert_node(&mut self, node: &html::Node) -> Vec<i18n::Node> {
+        match node {
+            html::Node::Text(text) => vec![i18n::Node::Text(i18n::Text::new(
+                text.value.to_string(),
+                text.source_span.clone(),
+            ))],
+            html::Node::Element(element) => match &*element.name {
+                PLACEHOLDER_TAG => match find_attr(element, "equiv") {
+                    Some(name) => vec![placeholder(name, &element.source_span)],
+                    None => {
+                        self.add_error(
+                            element,
+                            format!("<{}> misses the \"equiv\" attribute", PLACEHOLDER_TAG),
+                        );
+                        Vec::new()
+                    }
+                },
+                PLACEHOLDER_SPANNING_TAG => {
+                    let start = find_attr(element, "equivStart");
+                    let end = find_attr(element, "equivEnd");
+                    let (Some(start), Some(end)) = (start, end) else {
+                        self.add_error(
+                            element,
+                            format!(
+                                "<{}> misses the \"equivStart\" or \"equivEnd\" attribute",
+                                PLACEHOLDER_SPANNING_TAG
+                            ),
+                        );
+                        return Vec::new();
+                    };
+                    let mut nodes = vec![placeholder(start, &element.source_span)];
+                    nodes.extend(self.convert(&element.children));
+                    nodes.push(placeholder(end, &element.source_span));
+                    nodes
+                }
+                MARKER_TAG => self.convert(&element.children),
+                _ => {
+                    self.add_error(element, "Unexpected tag".to_string());
+                    Vec::new()
+                }
+            },
+            html::Node::Expansion(expansion) => {
+                let mut cases = IndexMap::new();
+                for case in &expansion.cases {
+                    let children = self.convert(&case.expression);
+                    cases.insert(
+                        case.value.to_string(),
+                        i18n::Node::Container(i18n::Container::new(
+                            children,
+                            case.exp_source_span.clone(),
+                        )),
+                    );
+                }
+                vec![i18n::Node::Icu(i18n::Icu::new(
+                    expansion.switch_value.to_string(),
+                    expansion.expansion_type.to_string(),
+                    cases,
+                    expansion.source_span.clone(),
+                    None,
+                ))]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn add_error(&mut self, element: &html::Element, msg: String) {
+        self.errors
+            .push(ParseError::new(element.source_span.clone(), msg));
+    }
+}
+
+fn placeholder(name: String, span: &ParseSourceSpan) -> i18n::Node {
+    i18n::Node::Placeholder(i18n::Placeholder::new(String::new(), name, span.clone()))
+}
+
+fn child_elements(element: &html::Element) -> impl Iterator<Item = &html::Element> {
+    element.children.iter().filter_map(|node| match node {
+        html::Node::Element(child) => Some(child),
+        _ => None,
+    })
+}
+
+fn find_attr(element: &html::Element, name: &str) -> Option<String> {
+    element
+        .attrs
+        .iter()
+        .find(|attr| &*attr.name == name)
+        .map(|attr| attr.value.to_string())
+}
+
 /// Visitor that converts i18n AST nodes to XLIFF2 XML nodes
 struct Xliff2Visitor;
 
@@ -4,7 +4,7 @@
 //! XMB (XML Message Bundle) format serializer
 #![allow(dead_code)]
 
-use crate::i18n::digest::decimal_digest;
+use crate::i18n::digest::{digest_with, MessageIdStrategy};
 use crate::i18n::i18n_ast::{self as i18n, Message, Node, Visitor};
 use crate::i18n::serializers::serializer::{
     PlaceholderMapper, Serializer, SimplePlaceholderMapper,
@@ -31,6 +31,7 @@ const DOCTYPE: &str = r#"<!ELEMENT messagebundle (msg)*>
 <!ATTLIST msg name CDATA #IMPLIED>
 <!ATTLIST msg desc CDATA #IMPLIED>
 <!ATTLIST msg meaning CDATA #IMPLIED>
+<!ATTLIST msg legacy CDATA #IMPLIED>
 <!ATTLIST msg obsolete (obsolete) #IMPLIED>
 <!ATTLIST msg xml:space (default|preserve) "default">
 <!ATTLIST msg is_hidden CDATA #IMPLIED>
@@ -44,12 +45,19 @@ const DOCTYPE: &str = r#"<!ELEMENT messagebundle (msg)*>
 
 /// XMB (XML Message Bundle) serializer
 pub struct Xmb {
-    // Implementation fields
+    id_strategy: MessageIdStrategy,
 }
 
 impl Xmb {
     pub fn new() -> Self {
-        Xmb {}
+        Xmb {
+            id_strategy: MessageIdStrategy::Decimal,
+        }
+    }
+
+    pub fn with_id_strategy(mut self, strategy: MessageIdStrategy) -> Self {
+        self.id_strategy = strategy;
+        self
     }
 }
 
@@ -78,6 +86,10 @@ impl Serializer for Xmb {
                 attrs.insert("meaning".to_string(), message.meaning.clone());
             }
 
+            if !message.legacy_ids.is_empty() {
+                attrs.insert("legacy".to_string(), message.legacy_ids.join(" "));
+            }
+
             let mut source_tags: Vec<Box<dyn xml::Node>> = Vec::new();
             for source in &message.sources {
                 let source_attrs = HashMap::new();
@@ -125,7 +137,7 @@ impl Serializer for Xmb {
     }
 
     fn digest(&self, message: &Message) -> String {
-        decimal_digest(message)
+        digest_with(message, self.id_strategy)
     }
 
     fn create_name_mapper(&self, message: &Message) -> Option<Box<dyn PlaceholderMapper>> {
@@ -250,7 +262,17 @@ impl Visitor for XmbVisitor {
     ) -> Box<dyn std::any::Any> {
         let mut attrs = HashMap::new();
         attrs.insert("name".to_string(), ph.name.clone());
-        let ph_tag = xml::Tag::new(PLACEHOLDER_TAG.to_string(), attrs, Vec::new());
+        // The original interpolation gives translators some context about the placeholder
+        let ex_tag = xml::Tag::new(
+            EXAMPLE_TAG.to_string(),
+            HashMap::new(),
+            vec![Box::new(xml::Text::new(format!("{{{{{}}}}}", ph.value)))],
+        );
+        let ph_tag = xml::Tag::new(
+            PLACEHOLDER_TAG.to_string(),
+            attrs,
+            vec![Box::new(ex_tag) as Box<dyn xml::Node>],
+        );
         Box::new(vec![Box::new(ph_tag) as Box<dyn xml::Node>])
     }
 
@@ -35,7 +35,15 @@ impl IVisitor for Visitor {
     }
 
     fn visit_text(&mut self, text: &Text) -> String {
-        text.value.clone()
+        if text.cdata {
+            // "]]>" can't appear inside a section, so end the section between "]]" and ">"
+            format!(
+                "<![CDATA[{}]]>",
+                text.value.replace("]]>", "]]]]><![CDATA[>")
+            )
+        } else {
+            text.value.clone()
+        }
     }
 
     fn visit_declaration(&mut self, decl: &Declaration) -> String {
@@ -189,14 +197,23 @@ impl Node for Tag {
 #[derive(Debug, Clone)]
 pub struct Text {
     pub value: String,
+    /// Emit `value` verbatim in a `<![CDATA[...]]>` section instead of as escaped text
+    pub cdata: bool,
 }
 
 impl Text {
     pub fn new(unescaped_value: String) -> Self {
         Text {
             value: escape_xml(&unescaped_value),
+            cdata: false,
         }
     }
+
+    /// Text serialized as a CDATA section, which keeps content full of `<` and `&` (such as
+    /// ICU examples) readable
+    pub fn cdata(value: String) -> Self {
+        Text { value, cdata: true }
+    }
 }
 
 impl Node for Text {
@@ -227,6 +244,7 @@ impl Node for CR {
     fn visit(&self, visitor: &mut dyn IVisitor) -> String {
         visitor.visit_text(&Text {
             value: self.value.clone(),
+            cdata: false,
         })
     }
 
@@ -236,6 +254,9 @@ impl Node for CR {
 }
 
 /// Escape special XML characters
+///
+/// Characters that are not allowed in XML 1.0 documents are kept as is, use `escape_xml_with`
+/// to replace or reject them.
 pub fn escape_xml(text: &str) -> String {
     text.replace('&', "&amp;")
         .replace('"', "&quot;")
@@ -243,3 +264,69 @@ pub fn escape_xml(text: &str) -> String {
         .replace('<', "&lt;")
         .replace('>', "&gt;")
 }
+
+/// How `escape_xml_with` handles characters that are not allowed in XML 1.0 documents, such as
+/// `\x00`-`\x08`, `\x0B` (vertical tab), `\x0C` and `\x0E`-`\x1F`.
+///
+/// These characters can't even be written as character references, and translation tools
+/// reject files containing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidXmlChars {
+    /// Keep the characters, producing a document XML parsers may reject.
+    #[default]
+    Keep,
+    /// Replace each character with U+FFFD (the replacement character).
+    Replace,
+    /// Fail on the first such character.
+    Reject,
+}
+
+/// Error returned by `escape_xml_with` when rejecting a character not allowed in XML 1.0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidXmlCharError {
+    pub ch: char,
+    /// Byte offset of the character in the unescaped text.
+    pub offset: usize,
+}
+
+impl std::fmt::Display for InvalidXmlCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Character U+{:04X} at offset {} is not allowed in XML 1.0",
+            self.ch as u32, self.offset
+        )
+    }
+}
+
+impl std::error::Error for InvalidXmlCharError {}
+
+/// Escape special XML characters, handling characters not allowed in XML 1.0 as requested.
+pub fn escape_xml_with(
+    text: &str,
+    invalid_chars: InvalidXmlChars,
+) -> Result<String, InvalidXmlCharError> {
+    if invalid_chars == InvalidXmlChars::Keep {
+        return Ok(escape_xml(text));
+    }
+
+    let mut valid = String::with_capacity(text.len());
+    for (offset, ch) in text.char_indices() {
+        if is_valid_xml_char(ch) {
+            valid.push(ch);
+        } else if invalid_chars == InvalidXmlChars::Replace {
+            valid.push(char::REPLACEMENT_CHARACTER);
+        } else {
+            return Err(InvalidXmlCharError { ch, offset });
+        }
+    }
+    Ok(escape_xml(&valid))
+}
+
+/// See https://www.w3.org/TR/xml/#charsets
+fn is_valid_xml_char(ch: char) -> bool {
+    matches!(
+        ch,
+        '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..
+    )
+}
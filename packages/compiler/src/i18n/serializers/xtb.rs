@@ -2,15 +2,20 @@
 //!
 //! Corresponds to packages/compiler/src/i18n/serializers/xtb.ts
 //! XTB (XML Translation Bundle) format loader
-#![allow(dead_code)]
 
-use crate::i18n::i18n_ast::Message;
+use crate::i18n::digest::MessageIdStrategy;
+use crate::i18n::i18n_ast::{self as i18n, Message};
 use crate::i18n::serializers::serializer::{
     PlaceholderMapper, Serializer, SimplePlaceholderMapper,
 };
 use crate::i18n::serializers::xmb::{to_public_name, Xmb};
 use crate::i18n::translation_bundle::LoadResult;
-use std::collections::HashMap;
+use crate::ml_parser::ast as html;
+use crate::ml_parser::lexer::TokenizeOptions;
+use crate::ml_parser::xml_parser::XmlParser;
+use crate::parse_util::{ParseError, ParseErrorLevel, ParseSourceSpan};
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
 
 const TRANSLATIONS_TAG: &str = "translationbundle";
 const TRANSLATION_TAG: &str = "translation";
@@ -20,11 +25,27 @@ const PLACEHOLDER_TAG: &str = "ph";
 /// This format is read-only and pairs with XMB
 pub struct Xtb {
     xmb: Xmb,
+    source_messages: Option<Vec<Message>>,
 }
 
 impl Xtb {
     pub fn new() -> Self {
-        Xtb { xmb: Xmb::new() }
+        Xtb {
+            xmb: Xmb::new(),
+            source_messages: None,
+        }
+    }
+
+    pub fn with_id_strategy(mut self, strategy: MessageIdStrategy) -> Self {
+        self.xmb = self.xmb.with_id_strategy(strategy);
+        self
+    }
+
+    /// Warn about translations whose id doesn't belong to any of the given source messages,
+    /// e.g. translations of messages that were since removed or reworded.
+    pub fn with_source_messages(mut self, messages: &[Message]) -> Self {
+        self.source_messages = Some(messages.to_vec());
+        self
     }
 }
 
@@ -39,11 +60,31 @@ impl Serializer for Xtb {
         panic!("Unsupported: XTB is a read-only format. Use XMB to write messages.");
     }
 
-    fn load(&self, _content: &str, _url: &str) -> LoadResult {
-        // TODO: Implement XTB load
-        LoadResult {
+    fn load(&self, content: &str, url: &str) -> LoadResult {
+        let options = TokenizeOptions {
+            tokenize_expansion_forms: true,
+            ..Default::default()
+        };
+        let parse_result = XmlParser::new().parse(content, url, Some(options));
+
+        let known_ids = self.source_messages.as_ref().map(|messages| {
+            messages
+                .iter()
+                .map(|message| self.digest(message))
+                .collect()
+        });
+        let mut parser = XtbParser {
             locale: None,
             i18n_nodes_by_msg_id: HashMap::new(),
+            known_ids,
+            errors: parse_result.errors,
+        };
+        parser.visit_all(&parse_result.root_nodes, false);
+
+        LoadResult {
+            locale: parser.locale,
+            i18n_nodes_by_msg_id: parser.i18n_nodes_by_msg_id,
+            errors: parser.errors,
         }
     }
 
@@ -59,7 +100,151 @@ impl Serializer for Xtb {
     }
 }
 
-// TODO: Implement XtbParser for parsing XTB XML
-// This parser extracts translations from XTB format
+/// Extracts the locale and the translations from a parsed XTB file
+struct XtbParser {
+    locale: Option<String>,
+    i18n_nodes_by_msg_id: HashMap<String, Vec<i18n::Node>>,
+    /// Ids of the source messages, when translations are checked against them
+    known_ids: Option<HashSet<String>>,
+    errors: Vec<ParseError>,
+}
+
+impl XtbParser {
+    fn visit_all(&mut self, nodes: &[html::Node], in_bundle: bool) {
+        for node in nodes {
+            if let html::Node::Element(element) = node {
+                self.visit_element(element, in_bundle);
+            }
+        }
+    }
+
+    fn visit_element(&mut self, element: &html::Element, in_bundle: bool) {
+        match &*element.name {
+            TRANSLATIONS_TAG => {
+                if in_bundle {
+                    self.add_error(
+                        &element.source_span,
+                        format!("Unexpected <{}> tag", TRANSLATIONS_TAG),
+                    );
+                } else {
+                    self.locale = find_attr(element, "lang");
+                    self.visit_all(&element.children, true);
+                }
+            }
+            TRANSLATION_TAG => {
+                let Some(id) = find_attr(element, "id") else {
+                    self.add_error(
+                        &element.source_span,
+                        format!("<{}> misses the \"id\" attribute", TRANSLATION_TAG),
+                    );
+                    return;
+                };
+
+                if self.i18n_nodes_by_msg_id.contains_key(&id) {
+                    self.add_error(
+                        &element.source_span,
+                        format!("Duplicated translations for msg {}", id),
+                    );
+                    return;
+                }
+
+                if let Some(known_ids) = &self.known_ids {
+                    if !known_ids.contains(&id) {
+                        self.errors.push(ParseError {
+                            span: element.source_span.clone(),
+                            msg: format!("Translation for unknown msg {}", id),
+                            level: ParseErrorLevel::Warning,
+                        });
+                    }
+                }
 
-// TODO: Implement XmlToI18n for converting XTB XML to i18n nodes
+                let nodes = XmlToI18n {
+                    errors: &mut self.errors,
+                }
+                .convert(&element.children);
+                self.i18n_nodes_by_msg_id.insert(id, nodes);
+            }
+            _ => self.add_error(&element.source_span, "Unexpected tag".to_string()),
+        }
+    }
+
+    fn add_error(&mut self, span: &ParseSourceSpan, msg: String) {
+        self.errors.push(ParseError::new(span.clone(), msg));
+    }
+}
+
+/// Converts the content of a `<translation>` to i18n nodes
+struct XmlToI18n<'a> {
+    errors: &'a mut Vec<ParseError>,
+}
+
+impl XmlToI18n<'_> {
+    fn convert(&mut self, nodes: &[html::Node]) -> Vec<i18n::Node> {
+        nodes
+            .iter()
+            .filter_map(|node| self.convert_node(node))
+            .collect()
+    }
+
+    fn convert_node(&mut self, node: &html::Node) -> Option<i18n::Node> {
+        match node {
+            html::Node::Text(text) => Some(i18n::Node::Text(i18n::Text::new(
+                text.value.to_string(),
+                text.source_span.clone(),
+            ))),
+            // The `<ex>` example of a placeholder is only there for translators
+            html::Node::Element(element) if &*element.name == PLACEHOLDER_TAG => {
+                match find_attr(element, "name") {
+                    Some(name) => Some(i18n::Node::Placeholder(i18n::Placeholder::new(
+                        String::new(),
+                        name,
+                        element.source_span.clone(),
+                    ))),
+                    None => {
+                        self.errors.push(ParseError::new(
+                            element.source_span.clone(),
+                            format!("<{}> misses the \"name\" attribute", PLACEHOLDER_TAG),
+                        ));
+                        None
+                    }
+                }
+            }
+            html::Node::Element(element) => {
+                self.errors.push(ParseError::new(
+                    element.source_span.clone(),
+                    "Unexpected tag".to_string(),
+                ));
+                None
+            }
+            html::Node::Expansion(expansion) => {
+                let mut cases = IndexMap::new();
+                for case in &expansion.cases {
+                    let children = self.convert(&case.expression);
+                    cases.insert(
+                        case.value.to_string(),
+                        i18n::Node::Container(i18n::Container::new(
+                            children,
+                            case.exp_source_span.clone(),
+                        )),
+                    );
+                }
+                Some(i18n::Node::Icu(i18n::Icu::new(
+                    expansion.switch_value.to_string(),
+                    expansion.expansion_type.to_string(),
+                    cases,
+                    expansion.source_span.clone(),
+                    None,
+                )))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn find_attr(element: &html::Element, name: &str) -> Option<String> {
+    element
+        .attrs
+        .iter()
+        .find(|attr| &*attr.name == name)
+        .map(|attr| attr.value.to_string())
+}
@@ -13,17 +13,23 @@ use crate::i18n::serializers::xml_helper::escape_xml;
 use crate::ml_parser::ast as html;
 use crate::ml_parser::html_parser::HtmlParser;
 use crate::ml_parser::lexer::TokenizeOptions;
-use crate::parse_util::ParseError;
+use crate::parse_util::{ParseError, ParseErrorLevel};
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
 
+/// Creates the placeholder mapper used to convert the placeholders of a message.
+type MapperFactory = Rc<dyn Fn(&Message) -> Box<dyn PlaceholderMapper>>;
+
+/// Maps a placeholder name to its public name.
+type NameMapper = Box<dyn Fn(&str) -> String>;
+
 /// A container for translated messages
 pub struct TranslationBundle {
     i18n_nodes_by_msg_id: HashMap<String, Vec<Node>>,
     locale: Option<String>,
     digest_fn: Rc<dyn Fn(&Message) -> String>,
-    mapper_factory: Option<Rc<dyn Fn(&Message) -> Box<dyn PlaceholderMapper>>>,
+    mapper_factory: Option<MapperFactory>,
     missing_translation_strategy: MissingTranslationStrategy,
     i18n_to_html: I18nToHtmlVisitor,
     // Store serializer for load method - wrapped in Arc for sharing
@@ -35,7 +41,7 @@ impl TranslationBundle {
         i18n_nodes_by_msg_id: HashMap<String, Vec<Node>>,
         locale: Option<String>,
         digest_fn: Rc<dyn Fn(&Message) -> String>,
-        mapper_factory: Option<Rc<dyn Fn(&Message) -> Box<dyn PlaceholderMapper>>>,
+        mapper_factory: Option<MapperFactory>,
         missing_translation_strategy: MissingTranslationStrategy,
     ) -> Self {
         let i18n_to_html = I18nToHtmlVisitor::new(
@@ -66,6 +72,15 @@ impl TranslationBundle {
         missing_translation_strategy: MissingTranslationStrategy,
     ) -> Result<Self, String> {
         let load_result = serializer.load(content, url);
+        let errors: Vec<String> = load_result
+            .errors
+            .iter()
+            .filter(|e| e.level == ParseErrorLevel::Error)
+            .map(|e| e.to_string())
+            .collect();
+        if !errors.is_empty() {
+            return Err(format!("{} parse errors:\n{}", url, errors.join("\n")));
+        }
 
         // Wrap serializer in Arc for sharing
         let serializer_arc = Arc::new(serializer);
@@ -76,12 +91,11 @@ impl TranslationBundle {
             Rc::new(move |m: &Message| serializer_clone.digest(m));
 
         let serializer_clone2 = serializer_arc.clone();
-        let mapper_factory: Option<Rc<dyn Fn(&Message) -> Box<dyn PlaceholderMapper>>> =
-            Some(Rc::new(move |m: &Message| {
-                serializer_clone2
-                    .create_name_mapper(m)
-                    .unwrap_or_else(|| Box::new(NoOpPlaceholderMapper))
-            }));
+        let mapper_factory: Option<MapperFactory> = Some(Rc::new(move |m: &Message| {
+            serializer_clone2
+                .create_name_mapper(m)
+                .unwrap_or_else(|| Box::new(NoOpPlaceholderMapper))
+        }));
 
         let mut bundle = TranslationBundle::new(
             load_result.i18n_nodes_by_msg_id,
@@ -94,6 +108,23 @@ impl TranslationBundle {
         Ok(bundle)
     }
 
+    /// Use `fallback` for the messages this bundle has no translation for.
+    ///
+    /// Fallbacks are consulted in the order they were added, each followed by its own fallbacks,
+    /// so `fr_ca.with_fallback(fr)` translates a message missing in `fr-CA` with `fr` before
+    /// falling back to the source message.
+    pub fn with_fallback(mut self, fallback: TranslationBundle) -> Self {
+        self.i18n_to_html.fallbacks.push(LocaleTranslations {
+            i18n_nodes_by_msg_id: fallback.i18n_nodes_by_msg_id,
+            digest_fn: fallback.digest_fn,
+            mapper_factory: fallback.mapper_factory,
+        });
+        self.i18n_to_html
+            .fallbacks
+            .extend(fallback.i18n_to_html.fallbacks);
+        self
+    }
+
     /// Returns the translation as HTML nodes from the given source message.
     pub fn get(&mut self, src_msg: &Message) -> Result<Vec<html::Node>, String> {
         let result = self.i18n_to_html.convert(src_msg);
@@ -113,6 +144,24 @@ impl TranslationBundle {
     pub fn has(&self, src_msg: &Message) -> bool {
         let id = (self.digest_fn)(src_msg);
         self.i18n_nodes_by_msg_id.contains_key(&id)
+            || self
+                .i18n_to_html
+                .fallbacks
+                .iter()
+                .any(|fallback| fallback.get(src_msg).is_some())
+    }
+}
+
+/// The translations of a fallback locale, with the functions used to look them up.
+struct LocaleTranslations {
+    i18n_nodes_by_msg_id: HashMap<String, Vec<Node>>,
+    digest_fn: Rc<dyn Fn(&Message) -> String>,
+    mapper_factory: Option<MapperFactory>,
+}
+
+impl LocaleTranslations {
+    fn get(&self, src_msg: &Message) -> Option<&Vec<Node>> {
+        self.i18n_nodes_by_msg_id.get(&(self.digest_fn)(src_msg))
     }
 }
 
@@ -125,12 +174,13 @@ struct I18nToHtmlVisitor {
     i18n_nodes_by_msg_id: HashMap<String, Vec<Node>>,
     locale: Option<String>,
     digest_fn: Rc<dyn Fn(&Message) -> String>,
-    mapper_factory: Option<Rc<dyn Fn(&Message) -> Box<dyn PlaceholderMapper>>>,
+    mapper_factory: Option<MapperFactory>,
     missing_translation_strategy: MissingTranslationStrategy,
+    fallbacks: Vec<LocaleTranslations>,
     src_msg: Option<Message>,
     errors: Vec<ParseError>,
     context_stack: Vec<ContextEntry>,
-    mapper: Option<Box<dyn Fn(&str) -> String>>,
+    mapper: Option<NameMapper>,
 }
 
 struct ContextEntry {
@@ -141,7 +191,7 @@ struct ContextEntry {
     // But that won't work either...
     // Actually, we don't need to clone ContextEntry, so we can keep it as is
     // The issue is in convert_to_text where we try to clone mapper
-    mapper: Option<Box<dyn Fn(&str) -> String>>,
+    mapper: Option<NameMapper>,
 }
 
 // No-op placeholder mapper for when create_name_mapper returns None
@@ -162,7 +212,7 @@ impl I18nToHtmlVisitor {
         i18n_nodes_by_msg_id: HashMap<String, Vec<Node>>,
         locale: Option<String>,
         digest_fn: Rc<dyn Fn(&Message) -> String>,
-        mapper_factory: Option<Rc<dyn Fn(&Message) -> Box<dyn PlaceholderMapper>>>,
+        mapper_factory: Option<MapperFactory>,
         missing_translation_strategy: MissingTranslationStrategy,
     ) -> Self {
         I18nToHtmlVisitor {
@@ -171,6 +221,7 @@ impl I18nToHtmlVisitor {
             digest_fn,
             mapper_factory,
             missing_translation_strategy,
+            fallbacks: Vec::new(),
             src_msg: None,
             errors: Vec::new(),
             context_stack: Vec::new(),
@@ -209,10 +260,18 @@ impl I18nToHtmlVisitor {
 
     fn convert_to_text(&mut self, src_msg: &Message) -> String {
         let id = (self.digest_fn)(src_msg);
-        let mapper = self.mapper_factory.as_ref().map(|factory| factory(src_msg));
+        // Look the message up in this locale first, then in the fallback locales
+        let translation = match self.i18n_nodes_by_msg_id.get(&id) {
+            Some(nodes) => Some((nodes.clone(), self.mapper_factory.clone())),
+            None => self.fallbacks.iter().find_map(|fallback| {
+                fallback
+                    .get(src_msg)
+                    .map(|nodes| (nodes.clone(), fallback.mapper_factory.clone()))
+            }),
+        };
 
         let nodes: Vec<Node>;
-        let mapper_fn: Box<dyn Fn(&str) -> String>;
+        let mapper_fn: NameMapper;
 
         // Save current context
         let current_msg = self.src_msg.clone();
@@ -235,11 +294,11 @@ impl I18nToHtmlVisitor {
         // Set new context
         self.src_msg = Some(src_msg.clone());
 
-        if self.i18n_nodes_by_msg_id.contains_key(&id) {
+        if let Some((translated_nodes, mapper_factory)) = translation {
             // When there is a translation use its nodes as the source
             // And create a mapper to convert serialized placeholder names to internal names
-            nodes = self.i18n_nodes_by_msg_id[&id].clone();
-            if let Some(m) = mapper {
+            nodes = translated_nodes;
+            if let Some(m) = mapper_factory.map(|factory| factory(src_msg)) {
                 // Wrap mapper in Rc to share it
                 let mapper_rc: Rc<dyn PlaceholderMapper> = Rc::from(m);
                 mapper_fn = Box::new(move |name: &str| {
@@ -469,4 +528,6 @@ impl Visitor for I18nToHtmlVisitor {
 pub struct LoadResult {
     pub locale: Option<String>,
     pub i18n_nodes_by_msg_id: HashMap<String, Vec<Node>>,
+    /// Problems found in the content; warnings don't prevent using the translations
+    pub errors: Vec<ParseError>,
 }
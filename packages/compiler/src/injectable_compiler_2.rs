@@ -5,40 +5,21 @@
 //! Compiles @Injectable decorators to generate factory functions
 //! for dependency injection.
 
-use serde::{Deserialize, Serialize};
-
-/// Expression reference (simplified)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Expression {
-    pub value: serde_json::Value,
-}
-
-/// R3 Reference (to a type)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct R3Reference {
-    pub value: Expression,
-    #[serde(rename = "type")]
-    pub type_ref: Expression,
-}
-
-/// Maybe forward ref expression
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MaybeForwardRefExpression {
-    pub expression: Expression,
-    pub forward_ref: bool,
-}
-
-/// Dependency metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct R3DependencyMetadata {
-    pub token: Expression,
-    pub attribute: Option<String>,
-    pub host: bool,
-    pub optional: bool,
-    #[serde(rename = "self")]
-    pub self_dep: bool,
-    pub skip_self: bool,
-}
+use crate::output::output_ast::{
+    arrow_fn, dynamic_type, variable, ArrowFunctionBody, Expression, ExpressionType, ExternalExpr,
+    FnParam, LiteralValue, Type, TypeModifier,
+};
+use crate::render3::r3_factory::{
+    compile_factory_function, DepsOrInvalid, FactoryTarget, R3ConstructorFactoryMetadata,
+    R3DelegatedFnOrClassMetadata, R3DependencyMetadata, R3ExpressionFactoryMetadata,
+    R3FactoryDelegateType, R3FactoryMetadata,
+};
+use crate::render3::r3_identifiers::Identifiers as R3;
+use crate::render3::util::{
+    convert_from_maybe_forward_ref_expression, type_with_parameters, MaybeForwardRefExpression,
+    R3CompiledExpression, R3Reference,
+};
+use crate::render3::view::util::DefinitionMap;
 
 /// Injectable metadata for compilation
 ///
@@ -56,63 +37,37 @@ pub struct R3DependencyMetadata {
 ///   deps?: R3DependencyMetadata[];
 /// }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct R3InjectableMetadata {
     /// Injectable class name
     pub name: String,
 
     /// Type reference
-    #[serde(rename = "type")]
-    pub type_ref: R3Reference,
+    pub type_: R3Reference,
 
     /// Number of type arguments (for generics)
-    pub type_argument_count: u32,
+    pub type_argument_count: usize,
 
-    /// Where this injectable is provided (root, platform, any, or a specific module)
+    /// Where this injectable is provided: a literal scope (`'root'`, `'platform'`, `'any'`), a
+    /// reference to a module, or a literal `null` when it has no scope
     pub provided_in: MaybeForwardRefExpression,
 
     /// Alternative class to use
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub use_class: Option<MaybeForwardRefExpression>,
 
     /// Factory function to use
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub use_factory: Option<Expression>,
 
     /// Existing injectable to reference
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub use_existing: Option<MaybeForwardRefExpression>,
 
     /// Direct value to use
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub use_value: Option<MaybeForwardRefExpression>,
 
     /// Dependencies for factory
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub deps: Option<Vec<R3DependencyMetadata>>,
 }
 
-/// Compiled expression result
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct R3CompiledExpression {
-    /// Compiled expression
-    pub expression: Expression,
-
-    /// Generated type
-    #[serde(rename = "type")]
-    pub type_expr: Expression,
-
-    /// Additional statements
-    pub statements: Vec<Statement>,
-}
-
-/// Statement (simplified)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Statement {
-    pub kind: String,
-    pub content: String,
-}
-
 /// Compile injectable to factory definition
 ///
 /// TypeScript equivalent:
@@ -123,133 +78,119 @@ pub struct Statement {
 /// ): R3CompiledExpression
 /// ```
 pub fn compile_injectable(
-    meta: R3InjectableMetadata,
+    meta: &R3InjectableMetadata,
     resolve_forward_refs: bool,
-) -> Result<R3CompiledExpression, String> {
-    // Determine which factory strategy to use
-    let result = if let Some(use_class) = meta.use_class.clone() {
-        compile_injectable_use_class(meta, use_class, resolve_forward_refs)?
-    } else if let Some(use_factory) = meta.use_factory.clone() {
-        compile_injectable_use_factory(meta, use_factory)?
-    } else if let Some(use_value) = meta.use_value.clone() {
-        compile_injectable_use_value(meta, use_value)?
-    } else if let Some(use_existing) = meta.use_existing.clone() {
-        compile_injectable_use_existing(meta, use_existing)?
-    } else {
-        // Default: use the type's own factory
-        compile_injectable_default(meta)?
-    };
-
-    Ok(result)
-}
-
-/// Compile injectable with useClass
-fn compile_injectable_use_class(
-    meta: R3InjectableMetadata,
-    use_class: MaybeForwardRefExpression,
-    _resolve_forward_refs: bool,
-) -> Result<R3CompiledExpression, String> {
-    // Check if useClass is the same as the type itself
-    let use_class_on_self = is_expression_equivalent(&use_class.expression, &meta.type_ref.value);
-
-    if use_class_on_self && meta.deps.is_none() {
-        // useClass: Type where Type is self - just use default factory
-        return compile_injectable_default(meta);
-    }
-
-    // Generate factory that instantiates useClass
-    let expression = Expression {
-        value: serde_json::json!({
-            "kind": "factory",
-            "target": "Injectable",
-            "useClass": use_class.expression.value,
-            "deps": meta.deps
-        }),
+) -> R3CompiledExpression {
+    let factory_meta = R3ConstructorFactoryMetadata {
+        name: meta.name.clone(),
+        type_: meta.type_.clone(),
+        type_argument_count: meta.type_argument_count,
+        deps: Some(DepsOrInvalid::Valid(vec![])),
+        target: FactoryTarget::Injectable,
     };
 
-    Ok(R3CompiledExpression {
-        expression,
-        type_expr: create_injectable_type(&meta),
-        statements: vec![],
-    })
-}
-
-/// Compile injectable with useFactory
-fn compile_injectable_use_factory(
-    meta: R3InjectableMetadata,
-    use_factory: Expression,
-) -> Result<R3CompiledExpression, String> {
-    let expression = Expression {
-        value: serde_json::json!({
-            "kind": "factory",
-            "target": "Injectable",
-            "useFactory": use_factory.value,
-            "deps": meta.deps
-        }),
-    };
-
-    Ok(R3CompiledExpression {
-        expression,
-        type_expr: create_injectable_type(&meta),
-        statements: vec![],
-    })
-}
-
-/// Compile injectable with useValue
-fn compile_injectable_use_value(
-    meta: R3InjectableMetadata,
-    use_value: MaybeForwardRefExpression,
-) -> Result<R3CompiledExpression, String> {
-    let expression = Expression {
-        value: serde_json::json!({
-            "kind": "factory",
-            "target": "Injectable",
-            "useValue": use_value.expression.value
-        }),
-    };
-
-    Ok(R3CompiledExpression {
-        expression,
-        type_expr: create_injectable_type(&meta),
-        statements: vec![],
-    })
-}
-
-/// Compile injectable with useExisting
-fn compile_injectable_use_existing(
-    meta: R3InjectableMetadata,
-    use_existing: MaybeForwardRefExpression,
-) -> Result<R3CompiledExpression, String> {
-    // useExisting is an inject() call on the existing token
-    let expression = Expression {
-        value: serde_json::json!({
-            "kind": "inject",
-            "token": use_existing.expression.value
-        }),
+    let (expression, statements) = if let Some(use_class) = &meta.use_class {
+        // meta.useClass has two modes of operation. Either deps are specified, in which case
+        // `new` is used to instantiate the class with dependencies injected, or deps are not
+        // specified and the factory of the class is used to instantiate it.
+        let use_class_on_self = use_class.expression.is_equivalent(&meta.type_.value);
+
+        if let Some(deps) = &meta.deps {
+            compiled(compile_factory_function(&R3FactoryMetadata::Delegated(
+                R3DelegatedFnOrClassMetadata {
+                    base: factory_meta,
+                    delegate: use_class.expression.clone(),
+                    delegate_type: R3FactoryDelegateType::Class,
+                    delegate_deps: deps.clone(),
+                },
+            )))
+        } else if use_class_on_self {
+            compiled(compile_factory_function(&R3FactoryMetadata::Constructor(
+                factory_meta,
+            )))
+        } else {
+            let factory = delegate_to_factory(
+                &meta.type_.value,
+                &use_class.expression,
+                resolve_forward_refs,
+            );
+            (factory, vec![])
+        }
+    } else if let Some(use_factory) = &meta.use_factory {
+        if let Some(deps) = &meta.deps {
+            compiled(compile_factory_function(&R3FactoryMetadata::Delegated(
+                R3DelegatedFnOrClassMetadata {
+                    base: factory_meta,
+                    delegate: use_factory.clone(),
+                    delegate_type: R3FactoryDelegateType::Function,
+                    delegate_deps: deps.clone(),
+                },
+            )))
+        } else {
+            // e.g. `() => createService()`
+            let factory = arrow_fn(
+                vec![],
+                ArrowFunctionBody::Expression(use_factory.call_fn(vec![], None, None)),
+                None,
+            );
+            (*factory, vec![])
+        }
+    } else if let Some(use_value) = &meta.use_value {
+        // Note: it's safe to use `meta.useValue` instead of the `USE_VALUE in meta` check used
+        // for other cases, because `undefined` is not a valid value for it.
+        compiled(compile_factory_function(&R3FactoryMetadata::Expression(
+            R3ExpressionFactoryMetadata {
+                base: factory_meta,
+                expression: use_value.expression.clone(),
+            },
+        )))
+    } else if let Some(use_existing) = &meta.use_existing {
+        // useExisting is an `inject` call on the existing token.
+        let inject = Expression::External(ExternalExpr {
+            value: R3::inject(),
+            type_: None,
+            source_span: None,
+        });
+        compiled(compile_factory_function(&R3FactoryMetadata::Expression(
+            R3ExpressionFactoryMetadata {
+                base: factory_meta,
+                expression: *inject.call_fn(vec![use_existing.expression.clone()], None, None),
+            },
+        )))
+    } else {
+        let factory =
+            delegate_to_factory(&meta.type_.value, &meta.type_.value, resolve_forward_refs);
+        (factory, vec![])
     };
 
-    Ok(R3CompiledExpression {
-        expression,
-        type_expr: create_injectable_type(&meta),
-        statements: vec![],
-    })
-}
-
-/// Compile injectable with default factory
-fn compile_injectable_default(meta: R3InjectableMetadata) -> Result<R3CompiledExpression, String> {
-    let expression = Expression {
-        value: serde_json::json!({
-            "kind": "factory",
-            "target": "Injectable",
-            "type": meta.type_ref.value.value
-        }),
-    };
+    let mut injectable_props = DefinitionMap::new();
+    injectable_props.set("token", Some(meta.type_.value.clone()));
+    injectable_props.set("factory", Some(expression));
+
+    // Only generate providedIn property if it has a non-null value
+    let has_scope = !matches!(
+        &meta.provided_in.expression,
+        Expression::Literal(literal) if literal.value == LiteralValue::Null
+    );
+    if has_scope {
+        injectable_props.set(
+            "providedIn",
+            Some(convert_from_maybe_forward_ref_expression(&meta.provided_in)),
+        );
+    }
 
-    Ok(R3CompiledExpression {
-        expression,
-        type_expr: create_injectable_type(&meta),
-        statements: vec![],
-    })
+    let define_injectable = Expression::External(ExternalExpr {
+        value: R3::define_injectable(),
+        type_: None,
+        source_span: None,
+    });
+    let expression = define_injectable.call_fn(
+        vec![Expression::LiteralMap(injectable_props.to_literal_map())],
+        None,
+        Some(true),
+    );
+
+    R3CompiledExpression::new(*expression, create_injectable_type(meta), statements)
 }
 
 /// Create injectable type expression
@@ -264,160 +205,193 @@ fn compile_injectable_default(meta: R3InjectableMetadata) -> Result<R3CompiledEx
 ///   );
 /// }
 /// ```
-pub fn create_injectable_type(meta: &R3InjectableMetadata) -> Expression {
-    Expression {
-        value: serde_json::json!({
-            "kind": "InjectableDeclaration",
-            "type": meta.type_ref.type_ref.value,
-            "typeArguments": meta.type_argument_count
-        }),
-    }
+pub fn create_injectable_type(meta: &R3InjectableMetadata) -> Type {
+    Type::Expression(ExpressionType {
+        value: Box::new(Expression::External(ExternalExpr {
+            value: R3::injectable_declaration(),
+            type_: None,
+            source_span: None,
+        })),
+        modifiers: TypeModifier::None,
+        type_params: Some(vec![type_with_parameters(
+            meta.type_.type_expr.clone(),
+            meta.type_argument_count,
+        )]),
+    })
 }
 
-/// Check if two expressions are equivalent
-fn is_expression_equivalent(expr1: &Expression, expr2: &Expression) -> bool {
-    // Simplified comparison - compare JSON values
-    expr1.value == expr2.value
+fn compiled(
+    result: R3CompiledExpression,
+) -> (Expression, Vec<crate::output::output_ast::Statement>) {
+    (result.expression, result.statements)
 }
 
-/// Helper to create factory function expression
-fn _create_factory_function(use_type: &Expression) -> Expression {
-    Expression {
-        value: serde_json::json!({
-            "kind": "arrowFunction",
-            "params": ["__ngFactoryType__"],
-            "body": {
-                "kind": "callExpression",
-                "callee": {
-                    "kind": "propertyAccess",
-                    "object": use_type.value,
-                    "property": "ɵfac"
-                },
-                "arguments": ["__ngFactoryType__"]
-            }
-        }),
+fn delegate_to_factory(
+    type_: &Expression,
+    use_type: &Expression,
+    unwrap_forward_refs: bool,
+) -> Expression {
+    if type_.is_equivalent(use_type) {
+        // The injectable delegates to its own factory, e.g. `factory: SomeType.ɵfac`.
+        return *use_type.prop("ɵfac", None);
+    }
+
+    if !unwrap_forward_refs {
+        // e.g. `(__ngFactoryType__) => SomeType.ɵfac(__ngFactoryType__)`
+        return create_factory_function(use_type.clone());
     }
+
+    // e.g. `(__ngFactoryType__) => ɵɵresolveForwardRef(SomeType).ɵfac(__ngFactoryType__)`
+    let resolve_forward_ref = Expression::External(ExternalExpr {
+        value: R3::resolve_forward_ref(),
+        type_: None,
+        source_span: None,
+    });
+    create_factory_function(*resolve_forward_ref.call_fn(vec![use_type.clone()], None, None))
+}
+
+fn create_factory_function(type_: Expression) -> Expression {
+    let t = FnParam {
+        name: "__ngFactoryType__".to_string(),
+        type_: Some(dynamic_type()),
+    };
+    let body = type_
+        .prop("ɵfac", None)
+        .call_fn(vec![*variable(t.name.clone())], None, None);
+    *arrow_fn(vec![t], ArrowFunctionBody::Expression(body), None)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::abstract_emitter::EmitterVisitorContext;
+    use crate::output::abstract_js_emitter::AbstractJsEmitterVisitor;
+    use crate::output::output_ast::{literal, ExpressionTrait, WrappedNodeExpr};
+    use crate::render3::util::{wrap_reference, ForwardRefHandling};
+
+    fn source(text: &str) -> Expression {
+        Expression::WrappedNode(WrappedNodeExpr {
+            node: Box::new(text.to_string()),
+            type_: None,
+            source_span: None,
+        })
+    }
 
-    #[test]
-    fn test_compile_injectable_default() {
-        let meta = R3InjectableMetadata {
+    fn reference(text: &str) -> MaybeForwardRefExpression {
+        MaybeForwardRefExpression::new(source(text), ForwardRefHandling::None)
+    }
+
+    fn injectable_meta(provided_in: Expression) -> R3InjectableMetadata {
+        R3InjectableMetadata {
             name: "MyService".to_string(),
-            type_ref: R3Reference {
-                value: Expression {
-                    value: serde_json::json!("MyService"),
-                },
-                type_ref: Expression {
-                    value: serde_json::json!("MyService"),
-                },
-            },
+            type_: wrap_reference("MyService".to_string()),
             type_argument_count: 0,
-            provided_in: MaybeForwardRefExpression {
-                expression: Expression {
-                    value: serde_json::json!("root"),
-                },
-                forward_ref: false,
-            },
+            provided_in: MaybeForwardRefExpression::new(provided_in, ForwardRefHandling::None),
             use_class: None,
             use_factory: None,
             use_existing: None,
             use_value: None,
             deps: None,
-        };
+        }
+    }
 
-        let result = compile_injectable(meta, false);
-        assert!(result.is_ok());
+    fn emit(meta: &R3InjectableMetadata) -> String {
+        let compiled = compile_injectable(meta, false);
+        let mut emitter = AbstractJsEmitterVisitor::new();
+        let mut ctx = EmitterVisitorContext::create_root();
+        compiled.expression.visit_expression(&mut emitter, &mut ctx);
+        ctx.to_source()
+    }
 
-        let compiled = result.unwrap();
-        assert_eq!(compiled.statements.len(), 0);
+    fn dep(token: &str) -> R3DependencyMetadata {
+        R3DependencyMetadata {
+            token: Some(source(token)),
+            attribute_name_type: None,
+            host: false,
+            optional: false,
+            self_: false,
+            skip_self: false,
+        }
     }
 
     #[test]
-    fn test_compile_injectable_use_value() {
-        let meta = R3InjectableMetadata {
-            name: "MY_TOKEN".to_string(),
-            type_ref: R3Reference {
-                value: Expression {
-                    value: serde_json::json!("MY_TOKEN"),
-                },
-                type_ref: Expression {
-                    value: serde_json::json!("any"),
-                },
-            },
-            type_argument_count: 0,
-            provided_in: MaybeForwardRefExpression {
-                expression: Expression {
-                    value: serde_json::json!("root"),
-                },
-                forward_ref: false,
-            },
-            use_class: None,
-            use_factory: None,
-            use_existing: None,
-            use_value: Some(MaybeForwardRefExpression {
-                expression: Expression {
-                    value: serde_json::json!(42),
-                },
-                forward_ref: false,
-            }),
-            deps: None,
-        };
+    fn test_compile_injectable_provided_in() {
+        for scope in ["root", "platform", "any"] {
+            let js = emit(&injectable_meta(*literal(scope)));
+            assert!(
+                js.starts_with("i0.ɵɵdefineInjectable({token: MyService,"),
+                "{}",
+                js
+            );
+            assert!(js.contains(&format!("providedIn: \"{}\"", scope)), "{}", js);
+        }
+
+        let js = emit(&injectable_meta(source("SomeModule")));
+        assert!(js.contains("providedIn: SomeModule"), "{}", js);
+    }
 
-        let result = compile_injectable(meta, false);
-        assert!(result.is_ok());
+    #[test]
+    fn test_compile_injectable_without_provided_in() {
+        let js = emit(&injectable_meta(*literal(LiteralValue::Null)));
+        assert_eq!(
+            js,
+            "i0.ɵɵdefineInjectable({token: MyService, factory: MyService.ɵfac})"
+        );
     }
 
     #[test]
-    fn test_create_injectable_type() {
-        let meta = R3InjectableMetadata {
-            name: "TestService".to_string(),
-            type_ref: R3Reference {
-                value: Expression {
-                    value: serde_json::json!("TestService"),
-                },
-                type_ref: Expression {
-                    value: serde_json::json!("TestService"),
-                },
-            },
-            type_argument_count: 2,
-            provided_in: MaybeForwardRefExpression {
-                expression: Expression {
-                    value: serde_json::json!("root"),
-                },
-                forward_ref: false,
-            },
-            use_class: None,
-            use_factory: None,
-            use_existing: None,
-            use_value: None,
-            deps: None,
+    fn test_compile_injectable_provider_forms() {
+        let use_class = R3InjectableMetadata {
+            use_class: Some(reference("OtherService")),
+            ..injectable_meta(*literal("root"))
         };
+        assert!(emit(&use_class)
+            .contains("factory: (__ngFactoryType__) => OtherService.ɵfac(__ngFactoryType__)"));
 
-        let type_expr = create_injectable_type(&meta);
+        let use_class_with_deps = R3InjectableMetadata {
+            use_class: Some(reference("OtherService")),
+            deps: Some(vec![dep("Dep")]),
+            ..injectable_meta(*literal("root"))
+        };
+        assert!(emit(&use_class_with_deps)
+            .contains("__ngConditionalFactory__ = new OtherService(i0.ɵɵinject(Dep));"));
 
-        // Check that type contains InjectableDeclaration
-        let json = type_expr.value.to_string();
-        assert!(json.contains("InjectableDeclaration"));
-        assert!(json.contains("2")); // typeArguments
+        let use_factory = R3InjectableMetadata {
+            use_factory: Some(source("createService")),
+            ..injectable_meta(*literal("root"))
+        };
+        assert!(emit(&use_factory).contains("factory: () => createService()"));
+
+        let use_existing = R3InjectableMetadata {
+            use_existing: Some(reference("ExistingService")),
+            ..injectable_meta(*literal("root"))
+        };
+        assert!(emit(&use_existing)
+            .contains("__ngConditionalFactory__ = i0.ɵɵinject(ExistingService);"));
+
+        let use_value = R3InjectableMetadata {
+            use_value: Some(MaybeForwardRefExpression::new(
+                *literal(42.0),
+                ForwardRefHandling::None,
+            )),
+            ..injectable_meta(*literal("root"))
+        };
+        assert!(emit(&use_value).contains("__ngConditionalFactory__ = 42;"));
     }
 
     #[test]
-    fn test_expression_equivalence() {
-        let expr1 = Expression {
-            value: serde_json::json!("MyClass"),
-        };
-        let expr2 = Expression {
-            value: serde_json::json!("MyClass"),
+    fn test_create_injectable_type() {
+        let meta = R3InjectableMetadata {
+            type_argument_count: 2,
+            ..injectable_meta(*literal("root"))
         };
-        let expr3 = Expression {
-            value: serde_json::json!("OtherClass"),
+        let Type::Expression(type_) = create_injectable_type(&meta) else {
+            panic!("expected an expression type");
         };
-
-        assert!(is_expression_equivalent(&expr1, &expr2));
-        assert!(!is_expression_equivalent(&expr1, &expr3));
+        assert!(matches!(
+            type_.value.as_ref(),
+            Expression::External(e) if e.value.name.as_deref() == Some("ɵɵInjectableDeclaration")
+        ));
+        assert_eq!(type_.type_params.map(|params| params.len()), Some(1));
     }
 }
@@ -1,5 +1,5 @@
 use indexmap::IndexMap;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::compiler_facade_interface::{
     self as facade, CompilerFacade, CoreEnvironment, R3ComponentMetadataFacade,
@@ -16,12 +16,14 @@ use crate::injectable_compiler_2::{
     compile_injectable, R3InjectableMetadata as InjectableMetadata,
 };
 use crate::output::output_ast::{
-    DeclareVarStmt, Expression, Statement, StmtModifier, WrappedNodeExpr,
+    literal, DeclareVarStmt, Expression, FunctionExpr, LiteralValue, Statement, StmtModifier,
+    WrappedNodeExpr,
 };
 use crate::output::output_jit::{ExternalReferenceResolver, JitEvaluator};
 use crate::parse_util::{ParseLocation, ParseSourceFile, ParseSourceSpan};
 use crate::render3::r3_factory::{
-    compile_factory_function, FactoryTarget, R3ConstructorFactoryMetadata, R3FactoryMetadata,
+    compile_factory_function, FactoryTarget, R3ConstructorFactoryMetadata, R3DependencyMetadata,
+    R3FactoryMetadata,
 };
 use crate::render3::r3_injector_compiler::{compile_injector, R3InjectorMetadata};
 use crate::render3::r3_jit::{
@@ -34,7 +36,7 @@ use crate::render3::r3_module_compiler::{
 use crate::render3::r3_pipe_compiler::{compile_pipe_from_metadata, R3PipeMetadata};
 use crate::render3::util::{
     create_may_be_forward_ref_expression, get_safe_property_access_string, wrap_reference,
-    R3Reference,
+    ForwardRefHandling, MaybeForwardRefExpression, R3Reference,
 };
 use crate::render3::view::api::{
     ChangeDetectionOrExpression, R3ComponentMetadata, R3ComponentTemplate, R3DirectiveMetadata,
@@ -361,8 +363,8 @@ impl CompilerFacade for CompilerFacadeImpl {
     ) -> Result<serde_json::Value, String> {
         let meta = InjectableMetadata {
             name: facade.name.clone(),
-            type_ref: create_injectable_reference(facade.type_ref),
-            type_argument_count: facade.type_argument_count,
+            type_: wrap_reference(facade.type_ref),
+            type_argument_count: facade.type_argument_count as usize,
             provided_in: compute_provided_in(facade.provided_in),
             use_class: convert_to_maybe_forward_ref_expression(facade.use_class),
             use_factory: convert_to_provider_expression(facade.use_factory),
@@ -371,12 +373,8 @@ impl CompilerFacade for CompilerFacadeImpl {
             deps: convert_r3_dependency_metadata_array(facade.deps),
         };
 
-        if let Ok(res) = compile_injectable(meta, false) {
-            let expr = convert_injectable_expression_to_expression(res.expression);
-            self.jit_expression(expr, angular_core_env, source_map_url, vec![])
-        } else {
-            Err("Compile injectable failed".to_string())
-        }
+        let res = compile_injectable(&meta, false);
+        self.jit_expression(res.expression, angular_core_env, source_map_url, vec![])
     }
 
     fn compile_injectable_declaration(
@@ -387,7 +385,7 @@ impl CompilerFacade for CompilerFacadeImpl {
     ) -> Result<serde_json::Value, String> {
         let meta = InjectableMetadata {
             name: facade.type_ref.clone(),
-            type_ref: create_injectable_reference(facade.type_ref.clone()),
+            type_: wrap_reference(facade.type_ref.clone()),
             type_argument_count: 0,
             provided_in: compute_provided_in(facade.provided_in),
             use_class: convert_to_maybe_forward_ref_expression(facade.use_class),
@@ -397,12 +395,8 @@ impl CompilerFacade for CompilerFacadeImpl {
             deps: convert_declare_dependency_metadata_array(facade.deps),
         };
 
-        if let Ok(res) = compile_injectable(meta, false) {
-            let expr = convert_injectable_expression_to_expression(res.expression);
-            self.jit_expression(expr, angular_core_env, source_map_url, vec![])
-        } else {
-            Err("Compile injectable declaration failed".to_string())
-        }
+        let res = compile_injectable(&meta, true);
+        self.jit_expression(res.expression, angular_core_env, source_map_url, vec![])
     }
 
     fn create_parse_source_span(
@@ -434,8 +428,78 @@ impl CompilerFacade for CompilerFacadeImpl {
     }
 }
 
+/// A component definition compiled for JIT.
+///
+/// Without a JavaScript engine to evaluate it in, the definition is kept as the output AST of the
+/// fields passed to `ɵɵdefineComponent`, along with the declarations those fields reference.
+#[derive(Debug, Clone)]
+pub struct JitDef {
+    /// The fields of the definition, in the order they are passed to `ɵɵdefineComponent`.
+    pub fields: IndexMap<String, Expression>,
+    /// Constants and embedded view functions referenced by the fields.
+    pub statements: Vec<Statement>,
+}
+
+impl JitDef {
+    /// The function rendering the component's template.
+    pub fn template_function(&self) -> Option<&FunctionExpr> {
+        match self.fields.get("template")? {
+            Expression::Fn(template) => Some(template),
+            _ => None,
+        }
+    }
+}
+
+/// Compile a component the way `CompilerFacade::compile_component` does, without evaluating the
+/// definition. Useful where no JavaScript runtime is available, e.g. in test harnesses or to
+/// prewarm server-side rendering.
+pub fn compile_component_jit(facade: R3ComponentMetadataFacade) -> JitDef {
+    let mut constant_pool = ConstantPool::new(false);
+    let mut binding_parser = make_binding_parser(false);
+
+    let source_map_url = format!("ng:///{}.js", facade.name);
+    let meta = convert_component_facade_to_metadata(facade, source_map_url);
+    let res = compile_component_from_metadata(&meta, &mut constant_pool, &mut binding_parser);
+
+    let fields = match res.expression {
+        Expression::InvokeFn(define) => match define.args.into_iter().next() {
+            Some(Expression::LiteralMap(map)) => map
+                .entries
+                .into_iter()
+                .map(|entry| (entry.key, *entry.value))
+                .collect(),
+            _ => IndexMap::new(),
+        },
+        _ => IndexMap::new(),
+    };
+
+    // The compiled statements start with the constants of the template; the pool also has the
+    // ones added by host bindings.
+    let pooled: HashSet<String> = constant_pool
+        .statements
+        .iter()
+        .filter_map(declared_name)
+        .collect();
+    let mut statements = constant_pool.statements;
+    statements.extend(
+        res.statements
+            .into_iter()
+            .filter(|stmt| declared_name(stmt).is_none_or(|name| !pooled.contains(&name))),
+    );
+
+    JitDef { fields, statements }
+}
+
 // Helpers
 
+fn declared_name(stmt: &Statement) -> Option<String> {
+    match stmt {
+        Statement::DeclareVar(decl) => Some(decl.name.clone()),
+        Statement::DeclareFn(decl) => Some(decl.name.clone()),
+        _ => None,
+    }
+}
+
 struct R3JitReflectorAdapter(R3JitReflector);
 
 impl ExternalReferenceResolver for R3JitReflectorAdapter {
@@ -821,6 +885,8 @@ fn convert_component_facade_to_metadata(
             .change_detection
             .map(|_cd| ChangeDetectionOrExpression::Strategy(ChangeDetectionStrategy::Default)),
         relative_template_path: None,
+        enable_debug_locations: false,
+        template_start: None,
         has_directive_dependencies: false,
         raw_imports: None,
     }
@@ -870,6 +936,8 @@ fn convert_declare_component_facade_to_metadata(
             .change_detection
             .map(|_cd| ChangeDetectionOrExpression::Strategy(ChangeDetectionStrategy::Default)),
         relative_template_path: None,
+        enable_debug_locations: false,
+        template_start: None,
         has_directive_dependencies: false,
         raw_imports: None,
     }
@@ -898,27 +966,6 @@ fn create_dummy_source_span() -> ParseSourceSpan {
     )
 }
 
-// Helper for Injectable conversion
-fn create_injectable_reference(type_ref: String) -> crate::injectable_compiler_2::R3Reference {
-    crate::injectable_compiler_2::R3Reference {
-        value: create_injectable_expr_json(type_ref.clone()),
-        type_ref: create_injectable_expr_json(type_ref),
-    }
-}
-
-fn create_injectable_expr_json(val: String) -> crate::injectable_compiler_2::Expression {
-    crate::injectable_compiler_2::Expression {
-        value: serde_json::Value::String(val),
-    }
-}
-
-#[allow(dead_code)]
-fn create_injectable_expr_from_value(
-    val: serde_json::Value,
-) -> crate::injectable_compiler_2::Expression {
-    crate::injectable_compiler_2::Expression { value: val }
-}
-
 fn convert_host_declaration_to_metadata(host: Option<HashMap<String, String>>) -> R3HostMetadata {
     let host = host.unwrap_or_default();
 
@@ -1031,49 +1078,38 @@ fn parse_legacy_input_partial_output_array(value: &[serde_json::Value]) -> R3Inp
 
 fn compute_provided_in(
     provided_in: Option<crate::compiler_facade_interface::ProvidedIn>,
-) -> crate::injectable_compiler_2::MaybeForwardRefExpression {
-    let value = match provided_in {
+) -> MaybeForwardRefExpression {
+    let expression = match provided_in {
         Some(crate::compiler_facade_interface::ProvidedIn::Type(type_ref)) => {
-            serde_json::Value::String(type_ref)
-        }
-        Some(crate::compiler_facade_interface::ProvidedIn::Scope(scope)) => {
-            serde_json::Value::String(scope)
+            new_wrapped_node_expr(serde_json::Value::String(type_ref))
         }
-        None => serde_json::Value::Null,
+        Some(crate::compiler_facade_interface::ProvidedIn::Scope(scope)) => *literal(scope),
+        None => *literal(LiteralValue::Null),
     };
-
-    crate::injectable_compiler_2::MaybeForwardRefExpression {
-        expression: crate::injectable_compiler_2::Expression { value },
-        forward_ref: false,
-    }
+    create_may_be_forward_ref_expression(expression, ForwardRefHandling::None)
 }
 
 fn convert_to_maybe_forward_ref_expression(
     val: Option<serde_json::Value>,
-) -> Option<crate::injectable_compiler_2::MaybeForwardRefExpression> {
-    val.map(
-        |v| crate::injectable_compiler_2::MaybeForwardRefExpression {
-            expression: crate::injectable_compiler_2::Expression { value: v },
-            forward_ref: false,
-        },
-    )
+) -> Option<MaybeForwardRefExpression> {
+    val.map(|v| {
+        create_may_be_forward_ref_expression(new_wrapped_node_expr(v), ForwardRefHandling::None)
+    })
 }
 
-fn convert_to_provider_expression(
-    val: Option<serde_json::Value>,
-) -> Option<crate::injectable_compiler_2::Expression> {
-    val.map(|v| crate::injectable_compiler_2::Expression { value: v })
+fn convert_to_provider_expression(val: Option<serde_json::Value>) -> Option<Expression> {
+    val.map(new_wrapped_node_expr)
 }
 
 fn convert_r3_dependency_metadata_array(
     deps: Option<Vec<R3DependencyMetadataFacade>>,
-) -> Option<Vec<crate::injectable_compiler_2::R3DependencyMetadata>> {
+) -> Option<Vec<R3DependencyMetadata>> {
     deps.map(|d| d.into_iter().map(convert_r3_dependency_metadata).collect())
 }
 
 fn convert_declare_dependency_metadata_array(
     deps: Option<Vec<R3DeclareDependencyMetadataFacade>>,
-) -> Option<Vec<crate::injectable_compiler_2::R3DependencyMetadata>> {
+) -> Option<Vec<R3DependencyMetadata>> {
     deps.map(|d| {
         d.into_iter()
             .map(convert_r3_declare_dependency_metadata)
@@ -1081,42 +1117,34 @@ fn convert_declare_dependency_metadata_array(
     })
 }
 
-fn convert_r3_dependency_metadata(
-    dep: R3DependencyMetadataFacade,
-) -> crate::injectable_compiler_2::R3DependencyMetadata {
-    crate::injectable_compiler_2::R3DependencyMetadata {
-        token: crate::injectable_compiler_2::Expression { value: dep.token },
-        attribute: dep.attribute,
+fn convert_r3_dependency_metadata(dep: R3DependencyMetadataFacade) -> R3DependencyMetadata {
+    R3DependencyMetadata {
+        token: Some(new_wrapped_node_expr(dep.token)),
+        attribute_name_type: dep.attribute.map(|attribute| *literal(attribute)),
         host: dep.host,
         optional: dep.optional,
-        self_dep: dep.self_dep,
+        self_: dep.self_dep,
         skip_self: dep.skip_self,
     }
 }
 
 fn convert_r3_declare_dependency_metadata(
     dep: R3DeclareDependencyMetadataFacade,
-) -> crate::injectable_compiler_2::R3DependencyMetadata {
-    crate::injectable_compiler_2::R3DependencyMetadata {
-        token: crate::injectable_compiler_2::Expression { value: dep.token },
-        attribute: dep
-            .attribute
-            .and_then(|t| if t { Some("unknown".to_string()) } else { None }), // TODO: Attribute logic in TS handles unknown?
+) -> R3DependencyMetadata {
+    let is_attribute = dep.attribute.unwrap_or(false);
+    R3DependencyMetadata {
+        // The token of an attribute dependency is the name of the attribute
+        attribute_name_type: is_attribute.then(|| new_wrapped_node_expr(dep.token.clone())),
+        token: Some(new_wrapped_node_expr(dep.token)),
         host: dep.host.unwrap_or(false),
         optional: dep.optional.unwrap_or(false),
-        self_dep: dep.self_dep.unwrap_or(false),
+        self_: dep.self_dep.unwrap_or(false),
         skip_self: dep.skip_self.unwrap_or(false),
     }
 }
 
 // Helpers
 
-fn convert_injectable_expression_to_expression(
-    expr: crate::injectable_compiler_2::Expression,
-) -> Expression {
-    new_wrapped_node_expr(expr.value)
-}
-
 fn convert_special_attributes(
     attrs: crate::render3::view::compiler::HostSpecialAttributes,
 ) -> crate::render3::view::api::R3HostSpecialAttributes {
@@ -1322,3 +1350,73 @@ fn extract_host_bindings(
 
     Ok(bindings)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler_facade_interface::{DirectiveLifecycle, ViewEncapsulation};
+
+    fn component_facade(name: &str, template: &str) -> R3ComponentMetadataFacade {
+        let location = facade::ParseLocation {
+            file: facade::ParseSourceFile {
+                content: String::new(),
+                url: format!("{}.ts", name),
+            },
+            offset: 0,
+            line: 0,
+            col: 0,
+        };
+        R3ComponentMetadataFacade {
+            name: name.to_string(),
+            type_ref: name.to_string(),
+            type_source_span: facade::ParseSourceSpan {
+                start: location.clone(),
+                end: location,
+            },
+            selector: Some("app-greeting".to_string()),
+            queries: vec![],
+            host: HashMap::new(),
+            prop_metadata: HashMap::new(),
+            lifecycle: DirectiveLifecycle {
+                uses_on_changes: false,
+            },
+            inputs: vec![],
+            outputs: vec![],
+            uses_inheritance: false,
+            export_as: None,
+            providers: None,
+            view_queries: vec![],
+            is_standalone: true,
+            host_directives: None,
+            is_signal: false,
+            template: template.to_string(),
+            preserve_whitespaces: false,
+            animations: None,
+            declarations: vec![],
+            styles: vec![],
+            encapsulation: ViewEncapsulation::Emulated,
+            change_detection: None,
+            view_providers: None,
+            interpolation: None,
+            defer_blocks: None,
+        }
+    }
+
+    #[test]
+    fn test_compile_component_jit() {
+        let def = compile_component_jit(component_facade(
+            "GreetingCmp",
+            "<p>Hello {{ name }}</p>@if (show) {<span></span>}",
+        ));
+
+        assert!(def.fields.contains_key("selectors"));
+        let template = def.template_function().expect("template function");
+        assert_eq!(template.name.as_deref(), Some("GreetingCmp_Template"));
+        assert_eq!(template.params.len(), 2);
+        assert!(!template.statements.is_empty());
+        // The embedded view of the @if block is declared next to the definition.
+        assert!(def.statements.iter().any(
+            |stmt| declared_name(stmt).as_deref() == Some("GreetingCmp_Conditional_2_Template")
+        ));
+    }
+}
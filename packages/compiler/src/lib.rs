@@ -48,9 +48,11 @@ pub mod schema;
 
 // Re-exports
 pub use config::CompilerConfig as RustCompilerConfig;
+pub use jit_compiler_facade::{compile_component_jit, JitDef};
 pub use util::Version;
 pub use version::VERSION;
 
+#[cfg(feature = "napi-bindings")]
 use error::Result as CompilerResult;
 
 /// Compiler configuration
@@ -220,8 +222,10 @@ fn node_to_json_depth(
 
             serde_json::json!({
                 "type": "Element",
+                "id": node.id(),
                 "name": el.name,
                 "attrs": el.attrs.iter().take(10).map(|a| serde_json::json!({
+                    "id": a.id,
                     "name": a.name,
                     "value": if a.value.len() > 50 {
                         format!("{}...", &a.value[..50.min(a.value.len())])
@@ -241,6 +245,7 @@ fn node_to_json_depth(
             };
             serde_json::json!({
                 "type": "Text",
+                "id": node.id(),
                 "value": value
             })
         }
@@ -256,6 +261,7 @@ fn node_to_json_depth(
             };
             serde_json::json!({
                 "type": "Comment",
+                "id": node.id(),
                 "value": value
             })
         }
@@ -274,6 +280,7 @@ fn node_to_json_depth(
 
             serde_json::json!({
                 "type": "Block",
+                "id": node.id(),
                 "name": block.name,
                 "paramCount": block.parameters.len(),
                 "childCount": block.children.len(),
@@ -283,6 +290,7 @@ fn node_to_json_depth(
         Node::LetDeclaration(let_decl) => {
             serde_json::json!({
                 "type": "LetDeclaration",
+                "id": node.id(),
                 "name": let_decl.name,
                 "value": if let_decl.value.len() > 100 {
                     format!("{}...", &let_decl.value[..50])
@@ -294,6 +302,7 @@ fn node_to_json_depth(
         Node::Component(comp) => {
             serde_json::json!({
                 "type": "Component",
+                "id": node.id(),
                 "name": comp.component_name,
                 "childCount": comp.children.len()
             })
@@ -301,6 +310,7 @@ fn node_to_json_depth(
         Node::Expansion(exp) => {
             serde_json::json!({
                 "type": "Expansion",
+                "id": node.id(),
                 "switchValue": exp.switch_value,
                 "caseCount": exp.cases.len()
             })
@@ -308,12 +318,14 @@ fn node_to_json_depth(
         Node::ExpansionCase(case) => {
             serde_json::json!({
                 "type": "ExpansionCase",
+                "id": node.id(),
                 "value": case.value
             })
         }
         Node::Attribute(attr) => {
             serde_json::json!({
                 "type": "Attribute",
+                "id": node.id(),
                 "name": attr.name,
                 "value": attr.value
             })
@@ -321,12 +333,14 @@ fn node_to_json_depth(
         Node::Directive(dir) => {
             serde_json::json!({
                 "type": "Directive",
+                "id": node.id(),
                 "name": dir.name
             })
         }
         Node::BlockParameter(param) => {
             serde_json::json!({
                 "type": "BlockParameter",
+                "id": node.id(),
                 "expression": param.expression
             })
         }
@@ -472,17 +486,17 @@ pub fn compile_component(
 ) -> Result<CompilationResult> {
     let start = std::time::Instant::now();
 
-    let _config = config.unwrap_or(CompilerConfig {
+    let config = config.unwrap_or(CompilerConfig {
         debug: Some(false),
         preserve_whitespaces: Some(false),
         strict: Some(true),
     });
 
-    // Full compilation pipeline
-    let template_ast = parse_template_internal(&metadata.template)?;
-    let expressions = parse_expressions_internal(&template_ast)?;
-    let ir = process_pipeline_internal(&expressions)?;
-    let js_code = generate_code_internal(&ir)?;
+    let js_code = compile_component_internal(
+        &metadata,
+        config.debug.unwrap_or(false),
+        config.preserve_whitespaces.unwrap_or(false),
+    )?;
 
     let elapsed = start.elapsed().as_micros() as f64 / 1000.0;
 
@@ -538,42 +552,117 @@ pub fn is_available() -> bool {
 }
 
 // Internal implementations
-fn parse_template_internal(template: &str) -> CompilerResult<String> {
-    // TODO: Implement real HTML parser
-    Ok(format!(
-        "{{\"type\":\"template\",\"content\":\"{}\"}}",
-        template
-    ))
-}
 
-fn parse_expressions_internal(template_ast: &str) -> CompilerResult<String> {
-    // TODO: Implement real expression parser
-    Ok(format!(
-        "{{\"type\":\"expressions\",\"ast\":{}}}",
-        template_ast
-    ))
-}
-
-fn process_pipeline_internal(expressions: &str) -> CompilerResult<String> {
-    // TODO: Implement real pipeline processing
-    Ok(format!("{{\"type\":\"ir\",\"data\":{}}}", expressions))
-}
+/// Compile the template of a component into its `ɵcmp` definition. In `debug` mode the
+/// location of each element in the template is attached to the DOM. Whitespace-only text is
+/// removed from the template unless `preserve_whitespaces` is set.
+#[cfg(feature = "napi-bindings")]
+fn compile_component_internal(
+    metadata: &ComponentMetadata,
+    debug: bool,
+    preserve_whitespaces: bool,
+) -> CompilerResult<String> {
+    use constant_pool::ConstantPool;
+    use output::abstract_emitter::EmitterVisitorContext;
+    use output::abstract_js_emitter::AbstractJsEmitterVisitor;
+    use output::output_ast::{Expression, ExpressionTrait, ReadVarExpr};
+    use parse_util::{ParseLocation, ParseSourceFile, ParseSourceSpan};
+    use render3::util::R3Reference;
+    use render3::view::api::{
+        DeclarationListEmitMode, R3ComponentDeferMetadata, R3ComponentMetadata,
+        R3ComponentTemplate, R3DirectiveMetadata, R3HostMetadata, R3LifecycleMetadata,
+    };
+    use render3::view::compiler::compile_component_from_metadata;
+    use render3::view::template::{make_binding_parser, parse_template, ParseTemplateOptions};
+
+    let template_url = format!("{}.html", metadata.name);
+    let parsed = parse_template(
+        &metadata.template,
+        &template_url,
+        ParseTemplateOptions {
+            preserve_whitespaces: Some(preserve_whitespaces),
+            ..Default::default()
+        },
+    );
+    if let Some(error) = parsed.errors.as_ref().and_then(|errors| errors.first()) {
+        return Err(error::CompilerError::ParseError {
+            message: error.msg.clone(),
+        });
+    }
 
-fn generate_code_internal(ir: &str) -> CompilerResult<String> {
-    // TODO: Implement real code generation
-    Ok(format!(
-        r#"
-        function Component() {{
-            // Generated from IR: {}
-            return {{
-                render: function() {{
-                    // Template rendering code
-                }}
-            }};
-        }}
-        "#,
-        ir
-    ))
+    let type_ = Expression::ReadVar(ReadVarExpr {
+        name: metadata.name.clone(),
+        type_: None,
+        source_span: None,
+    });
+    let file = std::sync::Arc::new(ParseSourceFile::new(
+        metadata.template.clone(),
+        template_url.clone(),
+    ));
+    let location = ParseLocation::new(file, 0, 0, 0);
+    let meta = R3ComponentMetadata {
+        directive: R3DirectiveMetadata {
+            name: metadata.name.clone(),
+            type_: R3Reference {
+                value: type_.clone(),
+                type_expr: type_,
+            },
+            type_argument_count: 0,
+            type_source_span: ParseSourceSpan::new(location.clone(), location),
+            deps: None,
+            selector: metadata.selector.clone(),
+            queries: vec![],
+            view_queries: vec![],
+            host: R3HostMetadata::default(),
+            lifecycle: R3LifecycleMetadata::default(),
+            inputs: Default::default(),
+            outputs: Default::default(),
+            uses_inheritance: false,
+            export_as: None,
+            providers: None,
+            is_standalone: true,
+            is_signal: false,
+            host_directives: None,
+        },
+        template: R3ComponentTemplate {
+            nodes: parsed.nodes,
+            ng_content_selectors: parsed.ng_content_selectors,
+            preserve_whitespaces,
+        },
+        declarations: vec![],
+        defer: R3ComponentDeferMetadata::PerComponent {
+            dependencies_fn: None,
+        },
+        declaration_list_emit_mode: DeclarationListEmitMode::Direct,
+        styles: metadata.styles.clone().unwrap_or_default(),
+        external_styles: None,
+        encapsulation: core::ViewEncapsulation::Emulated,
+        animations: None,
+        view_providers: None,
+        relative_context_file_path: String::new(),
+        i18n_use_external_ids: false,
+        change_detection: None,
+        relative_template_path: Some(template_url),
+        enable_debug_locations: debug,
+        template_start: None,
+        has_directive_dependencies: false,
+        raw_imports: None,
+    };
+
+    let mut constant_pool = ConstantPool::new(false);
+    let mut binding_parser = make_binding_parser(false);
+    let compiled = compile_component_from_metadata(&meta, &mut constant_pool, &mut binding_parser);
+
+    let mut emitter = AbstractJsEmitterVisitor::new();
+    let mut ctx = EmitterVisitorContext::create_root();
+    // The template constants lead the compiled statements
+    for stmt in &compiled.statements {
+        stmt.visit_statement(&mut emitter, &mut ctx);
+    }
+    ctx.print(None, "const ɵcmp = ", false);
+    compiled.expression.visit_expression(&mut emitter, &mut ctx);
+    ctx.println(None, ";");
+    Ok(ctx.to_source())
 }
 
 // Helper trait to make ComponentMetadata cloneable for benchmarks
@@ -18,6 +18,11 @@ pub trait BaseNode {
     ) -> Option<Box<dyn std::any::Any>>;
 }
 
+/// Identifies a node within a single parse. The tree builder numbers nodes in document order
+/// starting at 1, so the same input always gets the same ids; nodes created outside of parsing
+/// have id 0.
+pub type NodeId = usize;
+
 /// Node type union
 #[derive(Debug, Clone)]
 pub enum Node {
@@ -34,6 +39,24 @@ pub enum Node {
     LetDeclaration(LetDeclaration),
 }
 
+impl Node {
+    pub fn id(&self) -> NodeId {
+        match self {
+            Node::Attribute(n) => n.id,
+            Node::Comment(n) => n.id,
+            Node::Element(n) => n.id,
+            Node::Expansion(n) => n.id,
+            Node::ExpansionCase(n) => n.id,
+            Node::Text(n) => n.id,
+            Node::Block(n) => n.id,
+            Node::BlockParameter(n) => n.id,
+            Node::Component(n) => n.id,
+            Node::Directive(n) => n.id,
+            Node::LetDeclaration(n) => n.id,
+        }
+    }
+}
+
 /// Base node with i18n support
 #[derive(Debug, Clone)]
 pub struct NodeWithI18n {
@@ -54,6 +77,7 @@ pub struct Text {
     pub source_span: ParseSourceSpan,
     pub tokens: Vec<InterpolatedTextToken>,
     pub i18n: Option<I18nMeta>,
+    pub id: NodeId,
 }
 
 impl Text {
@@ -68,6 +92,7 @@ impl Text {
             source_span,
             tokens,
             i18n,
+            id: 0,
         }
     }
 }
@@ -81,6 +106,7 @@ pub struct Expansion {
     pub source_span: ParseSourceSpan,
     pub switch_value_source_span: ParseSourceSpan,
     pub i18n: Option<I18nMeta>,
+    pub id: NodeId,
 }
 
 /// Expansion case
@@ -91,6 +117,7 @@ pub struct ExpansionCase {
     pub source_span: ParseSourceSpan,
     pub value_source_span: ParseSourceSpan,
     pub exp_source_span: ParseSourceSpan,
+    pub id: NodeId,
 }
 
 /// Attribute node
@@ -103,6 +130,7 @@ pub struct Attribute {
     pub value_span: Option<ParseSourceSpan>,
     pub value_tokens: Option<Vec<InterpolatedAttributeToken>>,
     pub i18n: Option<I18nMeta>,
+    pub id: NodeId,
 }
 
 /// Element node
@@ -118,6 +146,7 @@ pub struct Element {
     pub end_source_span: Option<ParseSourceSpan>,
     pub is_void: bool,
     pub i18n: Option<I18nMeta>,
+    pub id: NodeId,
 }
 
 /// Comment node
@@ -125,11 +154,16 @@ pub struct Element {
 pub struct Comment {
     pub value: Option<Arc<str>>,
     pub source_span: ParseSourceSpan,
+    pub id: NodeId,
 }
 
 impl Comment {
     pub fn new(value: Option<Arc<str>>, source_span: ParseSourceSpan) -> Self {
-        Comment { value, source_span }
+        Comment {
+            value,
+            source_span,
+            id: 0,
+        }
     }
 }
 
@@ -145,6 +179,7 @@ pub struct Block {
     pub start_source_span: ParseSourceSpan,
     pub end_source_span: Option<ParseSourceSpan>,
     pub i18n: Option<I18nMeta>,
+    pub id: NodeId,
 }
 
 /// Component node (Angular component usage)
@@ -161,6 +196,7 @@ pub struct Component {
     pub start_source_span: ParseSourceSpan,
     pub end_source_span: Option<ParseSourceSpan>,
     pub i18n: Option<I18nMeta>,
+    pub id: NodeId,
 }
 
 /// Directive node
@@ -171,6 +207,7 @@ pub struct Directive {
     pub source_span: ParseSourceSpan,
     pub start_source_span: ParseSourceSpan,
     pub end_source_span: Option<ParseSourceSpan>,
+    pub id: NodeId,
 }
 
 /// Block parameter
@@ -178,6 +215,7 @@ pub struct Directive {
 pub struct BlockParameter {
     pub expression: Arc<str>,
     pub source_span: ParseSourceSpan,
+    pub id: NodeId,
 }
 
 impl BlockParameter {
@@ -185,6 +223,7 @@ impl BlockParameter {
         BlockParameter {
             expression,
             source_span,
+            id: 0,
         }
     }
 }
@@ -197,6 +236,7 @@ pub struct LetDeclaration {
     pub source_span: ParseSourceSpan,
     pub name_span: ParseSourceSpan,
     pub value_span: ParseSourceSpan,
+    pub id: NodeId,
 }
 
 /// Visitor trait for traversing AST
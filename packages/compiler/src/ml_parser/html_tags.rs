@@ -448,6 +448,20 @@ mod tests {
         assert_eq!(style_def.get_content_type(None), TagContentType::RawText);
     }
 
+    #[test]
+    fn test_entity_decoding_by_content_type() {
+        let decodes = |name| {
+            get_html_tag_definition(name)
+                .get_content_type(None)
+                .decodes_entities()
+        };
+        assert!(!decodes("script"));
+        assert!(!decodes("style"));
+        assert!(decodes("textarea"));
+        assert!(decodes("title"));
+        assert!(decodes("div"));
+    }
+
     #[test]
     fn test_ignore_first_lf() {
         assert!(get_html_tag_definition("pre").ignore_first_lf);
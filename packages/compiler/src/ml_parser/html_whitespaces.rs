@@ -14,6 +14,8 @@ use crate::ml_parser::parser::ParseTreeResult;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
 
 pub const PRESERVE_WS_ATTR_NAME: &str = "ngPreserveWhitespaces";
 
@@ -37,6 +39,42 @@ static NO_WS_REGEXP: Lazy<Regex> =
 static WS_REPLACE_REGEXP: Lazy<Regex> =
     Lazy::new(|| Regex::new(&format!("[{}]+", regex::escape(WS_CHARS))).unwrap());
 
+/// Policy deciding which elements keep their whitespace untouched.
+///
+/// Elements carrying the `ngPreserveWhitespaces` attribute are always preserved,
+/// regardless of the policy.
+#[derive(Clone, Default)]
+pub enum WhitespacePolicy {
+    /// Trim whitespace inside every element.
+    CollapseAll,
+    /// Preserve whitespace inside `pre`, `template`, `textarea`, `script` and `style`.
+    #[default]
+    PreserveInPre,
+    /// Preserve whitespace inside elements for which the predicate returns `true`.
+    Custom(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+impl fmt::Debug for WhitespacePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WhitespacePolicy::CollapseAll => write!(f, "CollapseAll"),
+            WhitespacePolicy::PreserveInPre => write!(f, "PreserveInPre"),
+            WhitespacePolicy::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+impl WhitespacePolicy {
+    /// Whether whitespace inside an element with the given tag name should be preserved.
+    pub fn should_preserve(&self, tag_name: &str) -> bool {
+        match self {
+            WhitespacePolicy::CollapseAll => false,
+            WhitespacePolicy::PreserveInPre => SKIP_WS_TRIM_TAGS.contains(tag_name),
+            WhitespacePolicy::Custom(predicate) => predicate(tag_name),
+        }
+    }
+}
+
 fn has_preserve_whitespaces_attr(attrs: &[Attribute]) -> bool {
     attrs
         .iter()
@@ -74,6 +112,7 @@ pub struct WhitespaceVisitor {
     original_node_map: Option<HashMap<String, Node>>,
     require_context: bool,
     icu_expansion_depth: usize,
+    policy: WhitespacePolicy,
 }
 
 impl WhitespaceVisitor {
@@ -87,15 +126,22 @@ impl WhitespaceVisitor {
             original_node_map,
             require_context,
             icu_expansion_depth: 0,
+            policy: WhitespacePolicy::default(),
         }
     }
 
+    /// Use the given policy to decide which elements keep their whitespace.
+    pub fn with_policy(mut self, policy: WhitespacePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     pub fn visit_element(
         &mut self,
         element: &Element,
         _context: Option<&SiblingVisitorContext>,
     ) -> Option<Node> {
-        if SKIP_WS_TRIM_TAGS.contains(element.name.as_ref())
+        if self.policy.should_preserve(element.name.as_ref())
             || has_preserve_whitespaces_attr(&element.attrs)
         {
             // don't descend into elements where we need to preserve whitespaces
@@ -112,6 +158,7 @@ impl WhitespaceVisitor {
                 end_source_span: element.end_source_span.clone(),
                 is_void: element.is_void,
                 i18n: element.i18n.clone(),
+                id: element.id,
             };
             return Some(Node::Element(new_element));
         }
@@ -128,6 +175,7 @@ impl WhitespaceVisitor {
             end_source_span: element.end_source_span.clone(),
             is_void: element.is_void,
             i18n: element.i18n.clone(),
+            id: element.id,
         };
         Some(Node::Element(new_element))
     }
@@ -181,6 +229,7 @@ impl WhitespaceVisitor {
                 source_span: text.source_span.clone(),
                 tokens: text.tokens.clone(),
                 i18n: text.i18n.clone(),
+                id: text.id,
             };
             return Some(Node::Text(result));
         }
@@ -214,6 +263,7 @@ impl WhitespaceVisitor {
             source_span: expansion.source_span.clone(),
             switch_value_source_span: expansion.switch_value_source_span.clone(),
             i18n: expansion.i18n.clone(),
+            id: expansion.id,
         };
 
         Some(Node::Expansion(new_expansion))
@@ -232,6 +282,7 @@ impl WhitespaceVisitor {
             source_span: expansion_case.source_span.clone(),
             value_source_span: expansion_case.value_source_span.clone(),
             exp_source_span: expansion_case.exp_source_span.clone(),
+            id: expansion_case.id,
         }
     }
 
@@ -252,6 +303,7 @@ impl WhitespaceVisitor {
             start_source_span: block.start_source_span.clone(),
             end_source_span: block.end_source_span.clone(),
             i18n: block.i18n.clone(),
+            id: block.id,
         };
 
         Some(Node::Block(new_block))
@@ -278,7 +330,9 @@ impl WhitespaceVisitor {
         component: &Component,
         _context: Option<&SiblingVisitorContext>,
     ) -> Option<Node> {
-        if SKIP_WS_TRIM_TAGS.contains(component.component_name.as_ref())
+        if self
+            .policy
+            .should_preserve(component.component_name.as_ref())
             || has_preserve_whitespaces_attr(&component.attrs)
         {
             let new_attrs = visit_all_with_siblings_attrs(self, &component.attrs);
@@ -294,6 +348,7 @@ impl WhitespaceVisitor {
                 start_source_span: component.start_source_span.clone(),
                 end_source_span: component.end_source_span.clone(),
                 i18n: component.i18n.clone(),
+                id: component.id,
             };
             return Some(Node::Component(new_component));
         }
@@ -311,6 +366,7 @@ impl WhitespaceVisitor {
             start_source_span: component.start_source_span.clone(),
             end_source_span: component.end_source_span.clone(),
             i18n: component.i18n.clone(),
+            id: component.id,
         };
         Some(Node::Component(new_component))
     }
@@ -389,7 +445,21 @@ pub fn remove_whitespaces(
     html_ast_with_errors: ParseTreeResult,
     preserve_significant_whitespace: bool,
 ) -> ParseTreeResult {
-    let mut visitor = WhitespaceVisitor::new(preserve_significant_whitespace, None, false);
+    remove_whitespaces_with_policy(
+        html_ast_with_errors,
+        preserve_significant_whitespace,
+        WhitespacePolicy::default(),
+    )
+}
+
+/// Remove whitespaces from HTML AST, using `policy` to pick the elements left untouched
+pub fn remove_whitespaces_with_policy(
+    html_ast_with_errors: ParseTreeResult,
+    preserve_significant_whitespace: bool,
+    policy: WhitespacePolicy,
+) -> ParseTreeResult {
+    let mut visitor =
+        WhitespaceVisitor::new(preserve_significant_whitespace, None, false).with_policy(policy);
     let root_nodes = visit_all_with_siblings_nodes(&mut visitor, &html_ast_with_errors.root_nodes);
 
     ParseTreeResult {
@@ -495,6 +565,7 @@ mod tests {
             value_span: None,
             value_tokens: None,
             i18n: None,
+            id: 0,
         }];
 
         assert!(has_preserve_whitespaces_attr(&attrs));
@@ -45,6 +45,9 @@ pub struct TokenizeOptions {
     pub tokenize_blocks: bool,
     pub tokenize_let: bool,
     pub selectorless_enabled: bool,
+    /// Tokenize `<?target data?>` as a processing instruction rather than as a tag. Only XML
+    /// documents have them; in HTML they stay off.
+    pub tokenize_processing_instructions: bool,
 }
 
 impl Default for TokenizeOptions {
@@ -59,6 +62,7 @@ impl Default for TokenizeOptions {
             tokenize_blocks: true,
             tokenize_let: true,
             selectorless_enabled: false,
+            tokenize_processing_instructions: false,
         }
     }
 }
@@ -87,12 +91,19 @@ static CR_OR_CRLF_REGEXP: Lazy<Regex> = Lazy::new(|| Regex::new(r"\r\n?").unwrap
 
 /// Character reference types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)]
 enum CharacterReferenceType {
     Hex,
     Dec,
 }
 
+/// Result of reading a `&...` character reference
+enum EntityRead {
+    /// A well-formed reference: its source text and decoded value
+    Decoded { content: String, decoded: String },
+    /// A numeric reference that isn't terminated by `;`
+    Malformed { content: String, error: String },
+}
+
 /// Supported block names
 #[allow(dead_code)]
 const SUPPORTED_BLOCKS: &[&str] = &[
@@ -529,6 +540,7 @@ struct Tokenizer {
     tokenize_blocks: bool,
     tokenize_let: bool,
     selectorless_enabled: bool,
+    tokenize_processing_instructions: bool,
     block_depth: usize, // Track open blocks
     tokens: Vec<Token>,
     errors: Vec<ParseError>,
@@ -570,6 +582,7 @@ impl Tokenizer {
             tokenize_blocks: options.tokenize_blocks,
             tokenize_let: options.tokenize_let,
             selectorless_enabled: options.selectorless_enabled,
+            tokenize_processing_instructions: options.tokenize_processing_instructions,
             block_depth: 0,
             tokens: Vec::new(),
             errors: Vec::new(),
@@ -621,6 +634,9 @@ impl Tokenizer {
                 } else if self.attempt_char_code('/') {
                     // </tag>
                     self.consume_tag_close(start);
+                } else if self.tokenize_processing_instructions && self.attempt_char_code('?') {
+                    // <?target data?>
+                    self.consume_processing_instruction(start);
                 } else {
                     // <tag>
                     self.consume_tag_open(start);
@@ -816,9 +832,15 @@ impl Tokenizer {
 
             // Check for entity
             if self.cursor.peek() == '&' {
-                if let Some((_, decoded)) = self.try_read_entity() {
-                    content.push_str(&decoded);
-                    continue;
+                let entity_start = self.cursor.clone_cursor();
+                match self.try_read_entity() {
+                    Some(EntityRead::Decoded { decoded, .. }) => {
+                        content.push_str(&decoded);
+                        continue;
+                    }
+                    // Malformed references are left as they are inside interpolations
+                    Some(EntityRead::Malformed { .. }) => self.cursor = entity_start,
+                    None => {}
                 }
             }
 
@@ -838,21 +860,26 @@ impl Tokenizer {
     fn consume_entity(&mut self) -> bool {
         // Match TypeScript's _consumeEntity which calls _beginToken inside
         self.begin_token(TokenType::EncodedEntity);
-        let _start = self.cursor.clone_cursor();
 
-        if let Some((content, decoded)) = self.try_read_entity() {
-            // DON'T set current_token_start here - it should already be set by begin_token
-            // before consume_entity is called (matching TypeScript behavior)
-            self.end_token(vec![decoded, content]);
-            return true;
-        } else {
-            // Entity parsing failed - token was begun but not ended
-            // This shouldn't happen in normal flow as try_read_entity handles invalid entities
-            false
+        match self.try_read_entity() {
+            Some(EntityRead::Decoded { content, decoded }) => {
+                // DON'T set current_token_start here - it should already be set by begin_token
+                // before consume_entity is called (matching TypeScript behavior)
+                self.end_token(vec![decoded, content]);
+                true
+            }
+            Some(EntityRead::Malformed { content, error }) => {
+                // Keep the source text so nothing is dropped from the output, but report it
+                self.handle_error(error);
+                self.end_token(vec![content.clone(), content]);
+                true
+            }
+            // Not an entity - token was begun but not ended, the caller re-begins a text token
+            None => false,
         }
     }
 
-    fn try_read_entity(&mut self) -> Option<(String, String)> {
+    fn try_read_entity(&mut self) -> Option<EntityRead> {
         let start = self.cursor.clone_cursor();
         self.cursor.advance(); // consume '&'
 
@@ -861,36 +888,51 @@ impl Tokenizer {
 
         if self.attempt_char_code('#') {
             content.push('#');
-            let is_hex = self.attempt_char_code('x');
-            if is_hex {
+            let is_hex = if self.attempt_char_code('x') {
                 content.push('x');
-            }
+                true
+            } else if self.attempt_char_code('X') {
+                content.push('X');
+                true
+            } else {
+                false
+            };
+            let (radix, ref_type) = if is_hex {
+                (16, CharacterReferenceType::Hex)
+            } else {
+                (10, CharacterReferenceType::Dec)
+            };
 
             let mut num_str = String::new();
-            while (is_hex && self.cursor.peek().is_digit(16))
-                || (!is_hex && self.cursor.peek().is_digit(10))
-            {
+            while self.cursor.peek().is_digit(radix) {
                 let ch = self.cursor.peek();
                 num_str.push(ch);
                 content.push(ch);
                 self.cursor.advance();
             }
 
-            if num_str.is_empty() {
-                self.cursor = start;
-                return None;
-            }
-
-            if self.attempt_char_code(';') {
-                content.push(';');
+            if self.cursor.peek() == chars::EOF {
+                return Some(EntityRead::Malformed {
+                    content,
+                    error: unexpected_character_error_msg(chars::EOF),
+                });
             }
-
-            let num = u32::from_str_radix(&num_str, if is_hex { 16 } else { 10 }).unwrap_or(0);
-            if let Some(c) = std::char::from_u32(num) {
-                decoded.push(c);
-            } else {
-                decoded.push('\u{FFFD}');
+            if num_str.is_empty() || self.cursor.peek() != ';' {
+                // Include the offending character in the error, as TypeScript does
+                content.push(self.cursor.peek());
+                self.cursor.advance();
+                let error = unparsable_entity_error_msg(ref_type, &content);
+                return Some(EntityRead::Malformed { content, error });
             }
+            self.cursor.advance();
+            content.push(';');
+
+            // Out of range code points and lone surrogates decode to the replacement character
+            let c = u32::from_str_radix(&num_str, radix)
+                .ok()
+                .and_then(std::char::from_u32)
+                .unwrap_or('\u{FFFD}');
+            decoded.push(c);
         } else {
             let mut name = String::new();
             while self.cursor.peek().is_alphanumeric() {
@@ -917,7 +959,7 @@ impl Tokenizer {
             }
         }
 
-        Some((content, decoded))
+        Some(EntityRead::Decoded { content, decoded })
     }
 
     fn attempt_str(&mut self, s: &str) -> bool {
@@ -1071,6 +1113,9 @@ impl Tokenizer {
                 source_span,
             }),
             TokenType::DocType => Token::DocType(DocTypeToken { parts, source_span }),
+            TokenType::ProcessingInstruction => {
+                Token::ProcessingInstruction(ProcessingInstructionToken { parts, source_span })
+            }
             TokenType::RawText => Token::RawText(RawTextToken { parts, source_span }),
             TokenType::EscapableRawText => {
                 Token::EscapableRawText(EscapableRawTextToken { parts, source_span })
@@ -1209,8 +1254,27 @@ impl Tokenizer {
 
         let content_start = self.cursor.clone_cursor();
 
-        // Read until '>'
-        while self.cursor.peek() != '>' && self.cursor.peek() != chars::EOF {
+        // Read until '>', skipping over quoted literals and the internal subset
+        // (`<!DOCTYPE x [ <!ELEMENT x (#PCDATA)> ]>`), whose declarations contain '>' too
+        let mut quote: Option<char> = None;
+        let mut in_internal_subset = false;
+        while self.cursor.peek() != chars::EOF {
+            let ch = self.cursor.peek();
+            match quote {
+                Some(q) if ch == q => quote = None,
+                Some(_) => {}
+                None if ch == '"' || ch == '\'' => quote = Some(ch),
+                None if in_internal_subset && self.attempt_str_peek("<!--") => {
+                    while self.cursor.peek() != chars::EOF && !self.attempt_str("-->") {
+                        self.cursor.advance();
+                    }
+                    continue;
+                }
+                None if ch == '[' => in_internal_subset = true,
+                None if ch == ']' => in_internal_subset = false,
+                None if ch == '>' && !in_internal_subset => break,
+                None => {}
+            }
             self.cursor.advance();
         }
 
@@ -1233,6 +1297,39 @@ impl Tokenizer {
         self.end_token(vec![content]);
     }
 
+    fn consume_processing_instruction(&mut self, start: Box<dyn CharacterCursor>) {
+        // Processing instruction format: <?target data?>
+        self.begin_token(TokenType::ProcessingInstruction);
+        self.current_token_start = Some(start);
+
+        let mut target = String::new();
+        while !chars::is_whitespace(self.cursor.peek())
+            && self.cursor.peek() != chars::EOF
+            && !self.attempt_str_peek("?>")
+        {
+            target.push(self.cursor.peek());
+            self.cursor.advance();
+        }
+        while chars::is_whitespace(self.cursor.peek()) {
+            self.cursor.advance();
+        }
+
+        let data_start = self.cursor.clone_cursor();
+        while self.cursor.peek() != chars::EOF && !self.attempt_str_peek("?>") {
+            self.cursor.advance();
+        }
+        let data = self.cursor.get_chars(&*data_start);
+
+        if !self.attempt_str("?>") {
+            self.handle_error(format!(
+                "{}, expected \"?>\"",
+                unexpected_character_error_msg(self.cursor.peek())
+            ));
+        }
+
+        self.end_token(vec![target, self.process_carriage_returns(data)]);
+    }
+
     fn consume_tag_open(&mut self, start: Box<dyn CharacterCursor>) {
         // Parse <tagName> or <prefix:tagName>
         self.current_token_start = Some(start);
@@ -1823,7 +1920,7 @@ impl Tokenizer {
         }
 
         // Check tag content type to determine how to consume content
-        let tag_def = html_tags::get_html_tag_definition(&tag_name);
+        let tag_def = (self.get_tag_definition)(&tag_name);
         let content_type = tag_def.get_content_type(if prefix.is_empty() {
             None
         } else {
@@ -1831,15 +1928,17 @@ impl Tokenizer {
         });
 
         match content_type {
-            html_tags::TagContentType::RawText => {
-                // Consume raw text (script, style tags)
-                self.consume_raw_text_with_tag_close(false, &tag_name, &prefix, is_component);
-            }
-            html_tags::TagContentType::EscapableRawText => {
-                // Consume escapable raw text (title, textarea tags)
-                self.consume_raw_text_with_tag_close(true, &tag_name, &prefix, is_component);
-            }
-            _ => {
+            html_tags::TagContentType::RawText | html_tags::TagContentType::EscapableRawText => {
+                // Consume raw text (script, style tags) or escapable raw text (title, textarea
+                // tags), only the latter decodes entities
+                self.consume_raw_text_with_tag_close(
+                    content_type.decodes_entities(),
+                    &tag_name,
+                    &prefix,
+                    is_component,
+                );
+            }
+            html_tags::TagContentType::ParsableData => {
                 // Normal parsable data - continue with normal tokenization
                 // Will consume text/interpolations as usual
             }
@@ -2135,11 +2234,11 @@ impl Tokenizer {
         }
     }
 
-    fn consume_block_start(&mut self, _start: Box<dyn CharacterCursor>) {
-        // Parse @if, @for, @switch, etc.
-        self.require_char_code('@');
-
+    fn consume_block_start(&mut self, start: Box<dyn CharacterCursor>) {
+        // Parse @if, @for, @switch, etc. The token's span includes the '@'
         self.begin_token(TokenType::BlockOpenStart);
+        self.current_token_start = Some(start);
+        self.require_char_code('@');
 
         // Read block name
         let mut block_name = String::new();
@@ -2282,8 +2381,8 @@ impl Tokenizer {
 
         // Expect '{'
         if self.cursor.peek() == '{' {
-            self.cursor.advance();
             self.begin_token(TokenType::BlockOpenEnd);
+            self.cursor.advance();
             self.end_token(vec![]);
         } else {
             // Missing '{'. Convert BlockOpenStart to IncompleteBlockOpen.
@@ -2775,7 +2874,6 @@ fn merge_text_tokens(src_tokens: Vec<Token>) -> Vec<Token> {
 
 // Helper functions
 
-#[allow(dead_code)]
 fn unexpected_character_error_msg(char_code: char) -> String {
     let ch = if char_code == chars::EOF {
         "EOF".to_string()
@@ -2793,7 +2891,6 @@ fn unknown_entity_error_msg(entity_src: &str) -> String {
     )
 }
 
-#[allow(dead_code)]
 fn unparsable_entity_error_msg(ref_type: CharacterReferenceType, entity_str: &str) -> String {
     let type_str = match ref_type {
         CharacterReferenceType::Hex => "hexadecimal",
@@ -20,7 +20,8 @@ pub use ast::*;
 pub use defaults::*;
 pub use html_tags::*;
 pub use html_whitespaces::{
-    process_whitespace, remove_whitespaces, replace_ngsp, WhitespaceVisitor, PRESERVE_WS_ATTR_NAME,
+    process_whitespace, remove_whitespaces, remove_whitespaces_with_policy, replace_ngsp,
+    WhitespacePolicy, WhitespaceVisitor, PRESERVE_WS_ATTR_NAME,
 };
 pub use lexer::{tokenize, TokenizeOptions};
 pub use parser::{ParseOptions, ParseTreeResult, Parser, TreeError};
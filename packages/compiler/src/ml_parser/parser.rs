@@ -114,7 +114,75 @@ impl Parser {
                 .map(|e| ParseError::new(e.span, e.msg)),
         );
 
-        ParseTreeResult::new(tree_builder.root_nodes, all_errors)
+        let mut root_nodes = tree_builder.root_nodes;
+        assign_node_ids(&mut root_nodes, &mut 0);
+
+        ParseTreeResult::new(root_nodes, all_errors)
+    }
+}
+
+/// Numbers `nodes` in document order: each node comes before its attributes, directives,
+/// block parameters, expansion cases and children.
+fn assign_node_ids(nodes: &mut [Node], next_id: &mut NodeId) {
+    fn next(next_id: &mut NodeId) -> NodeId {
+        *next_id += 1;
+        *next_id
+    }
+
+    fn number_attrs(attrs: &mut [Attribute], directives: &mut [Directive], next_id: &mut NodeId) {
+        for attr in attrs {
+            attr.id = next(next_id);
+        }
+        for directive in directives {
+            directive.id = next(next_id);
+            for attr in &mut directive.attrs {
+                attr.id = next(next_id);
+            }
+        }
+    }
+
+    for node in nodes {
+        match node {
+            Node::Element(el) => {
+                el.id = next(next_id);
+                number_attrs(&mut el.attrs, &mut el.directives, next_id);
+                assign_node_ids(&mut el.children, next_id);
+            }
+            Node::Component(comp) => {
+                comp.id = next(next_id);
+                number_attrs(&mut comp.attrs, &mut comp.directives, next_id);
+                assign_node_ids(&mut comp.children, next_id);
+            }
+            Node::Block(block) => {
+                block.id = next(next_id);
+                for param in &mut block.parameters {
+                    param.id = next(next_id);
+                }
+                assign_node_ids(&mut block.children, next_id);
+            }
+            Node::Expansion(expansion) => {
+                expansion.id = next(next_id);
+                for case in &mut expansion.cases {
+                    case.id = next(next_id);
+                    assign_node_ids(&mut case.expression, next_id);
+                }
+            }
+            Node::ExpansionCase(case) => {
+                case.id = next(next_id);
+                assign_node_ids(&mut case.expression, next_id);
+            }
+            Node::Directive(directive) => {
+                directive.id = next(next_id);
+                for attr in &mut directive.attrs {
+                    attr.id = next(next_id);
+                }
+            }
+            Node::Attribute(n) => n.id = next(next_id),
+            Node::Text(n) => n.id = next(next_id),
+            Node::Comment(n) => n.id = next(next_id),
+            Node::BlockParameter(n) => n.id = next(next_id),
+            Node::LetDeclaration(n) => n.id = next(next_id),
+        }
     }
 }
 
@@ -243,7 +311,7 @@ impl TreeBuilder {
             }
         }
 
-        // Flush all remaining containers to root_nodes
+        // Flush all remaining containers, adding each one to the container it was opened in
         // NOTE: Only add unclosed error if it's truly unclosed
         while !self.container_stack.is_empty() {
             let container = self.container_stack.pop().unwrap();
@@ -264,20 +332,21 @@ impl TreeBuilder {
                         ));
                     }
                     */
-                    self.root_nodes.push(Node::Element(el));
+                    self.add_to_parent(Node::Element(el));
                 }
                 NodeContainer::Block(block) => {
                     // Check if unclosed (missing closing brace)
                     // Only report error if block had opening { but no closing }
                     // Blocks without { (incomplete like @if()) should not error
+                    // The block's children are kept so callers still get a usable partial tree
                     if block.has_opening_brace && block.end_source_span.is_none() {
                         self.errors.push(TreeError::create(
                             Some(block.name.to_string()),
-                            block.source_span.clone(),
+                            block.start_source_span.clone(),
                             format!("Unclosed block \"@{}\"", block.name),
                         ));
                     }
-                    self.root_nodes.push(Node::Block(block));
+                    self.add_to_parent(Node::Block(block));
                 }
                 NodeContainer::Component(comp) => {
                     // Check if unclosed (missing closing tag)
@@ -291,7 +360,7 @@ impl TreeBuilder {
                         ));
                     }
                     */
-                    self.root_nodes.push(Node::Component(comp));
+                    self.add_to_parent(Node::Component(comp));
                 }
             }
         }
@@ -482,6 +551,7 @@ impl TreeBuilder {
                 source_span,
                 switch_value_source_span: switch_value_span,
                 i18n: None,
+                id: 0,
             };
 
             self.add_to_parent(Node::Expansion(expansion));
@@ -565,6 +635,7 @@ impl TreeBuilder {
                 source_span,
                 value_source_span: value_span,
                 exp_source_span: exp_span,
+                id: 0,
             })
         } else {
             None
@@ -677,6 +748,7 @@ impl TreeBuilder {
                 end_source_span: None,
                 is_void: tag_def.is_void(),
                 i18n: None,
+                id: 0,
             };
 
             // Push to container stack
@@ -977,6 +1049,7 @@ impl TreeBuilder {
                 Some(value_tokens)
             },
             i18n: None,
+            id: 0,
         }
     }
 
@@ -1036,6 +1109,7 @@ impl TreeBuilder {
                     Some(value_tokens.clone())
                 },
                 i18n: None,
+                id: 0,
             });
         }
 
@@ -1083,6 +1157,7 @@ impl TreeBuilder {
             source_span,
             start_source_span: start_span,
             end_source_span,
+            id: 0,
         }
     }
 
@@ -1129,6 +1204,7 @@ impl TreeBuilder {
             source_span,
             start_source_span: start_span,
             end_source_span,
+            id: 0,
         }
     }
 
@@ -1147,15 +1223,21 @@ impl TreeBuilder {
             }
 
             // Check for BLOCK_OPEN_END (the opening { brace)
-            let has_opening_brace = if let Some(Token::BlockOpenEnd(_)) = self.peek {
-                self.advance();
-                true
+            let open_end = if let Some(Token::BlockOpenEnd(_)) = self.peek {
+                self.advance()
             } else {
-                false
+                None
             };
+            let has_opening_brace = open_end.is_some();
 
-            let span = block_token.source_span.clone();
-            let name_span = span.clone();
+            let name_span = block_token.source_span.clone();
+            // The start span covers the whole opening, e.g. `@if (cond) {`
+            let span = match &open_end {
+                Some(Token::BlockOpenEnd(end)) => {
+                    ParseSourceSpan::new(name_span.start.clone(), end.source_span.end.clone())
+                }
+                _ => name_span.clone(),
+            };
             let start_span = span.clone();
 
             let block = Block {
@@ -1168,6 +1250,7 @@ impl TreeBuilder {
                 start_source_span: start_span,
                 end_source_span: None,
                 i18n: None,
+                id: 0,
             };
 
             // Don't add to parent yet - will add when block is closed
@@ -1208,6 +1291,7 @@ impl TreeBuilder {
                 start_source_span: span.clone(),
                 end_source_span: None,
                 i18n: None,
+                id: 0,
             };
 
             self.container_stack.push(NodeContainer::Block(block));
@@ -1231,6 +1315,7 @@ impl TreeBuilder {
                 source_span: span.clone(),
                 name_span: span.clone(),
                 value_span: span.clone(),
+                id: 0,
             };
             self.add_to_parent(Node::LetDeclaration(decl));
 
@@ -1328,6 +1413,7 @@ impl TreeBuilder {
                 source_span: let_token.source_span.clone(),
                 name_span: let_token.source_span.clone(),
                 value_span: end_span,
+                id: 0,
             };
 
             self.add_to_parent(Node::LetDeclaration(decl));
@@ -1393,6 +1479,7 @@ impl TreeBuilder {
                 start_source_span: start_span,
                 end_source_span: None,
                 i18n: None,
+                id: 0,
             };
 
             if self_closing {
@@ -1770,6 +1857,12 @@ fn create_token_discriminant(token_type: TokenType) -> Token {
             parts: vec![],
             source_span: dummy_span,
         }),
+        TokenType::ProcessingInstruction => {
+            Token::ProcessingInstruction(ProcessingInstructionToken {
+                parts: vec![],
+                source_span: dummy_span,
+            })
+        }
         TokenType::ComponentOpenStart => Token::ComponentOpenStart(ComponentOpenStartToken {
             parts: vec![],
             source_span: dummy_span,
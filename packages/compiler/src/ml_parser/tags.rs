@@ -10,6 +10,16 @@ pub enum TagContentType {
     ParsableData,
 }
 
+impl TagContentType {
+    /// Whether character references (`&amp;`, `&#169;`, ...) are decoded in this content.
+    ///
+    /// Raw text (`<script>`, `<style>`) is kept verbatim; escapable raw text (`<textarea>`,
+    /// `<title>`) and parsable data decode them.
+    pub fn decodes_entities(self) -> bool {
+        !matches!(self, TagContentType::RawText)
+    }
+}
+
 /// Tag definition interface
 pub trait TagDefinition {
     fn closed_by_parent(&self) -> bool;
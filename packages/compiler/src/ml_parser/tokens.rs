@@ -29,6 +29,7 @@ pub enum TokenType {
     AttrValueText,
     AttrValueInterpolation,
     DocType,
+    ProcessingInstruction,
     ExpansionFormStart,
     ExpansionCaseValue,
     ExpansionCaseExpStart,
@@ -83,6 +84,7 @@ pub enum Token {
     AttrValueText(AttributeValueTextToken),
     AttrValueInterpolation(AttributeValueInterpolationToken),
     DocType(DocTypeToken),
+    ProcessingInstruction(ProcessingInstructionToken),
     ExpansionFormStart(ExpansionFormStartToken),
     ExpansionCaseValue(ExpansionCaseValueToken),
     ExpansionCaseExpStart(ExpansionCaseExpressionStartToken),
@@ -214,6 +216,13 @@ pub struct DocTypeToken {
     pub source_span: ParseSourceSpan,
 }
 
+/// `<?target data?>`; parts are the target and the (possibly empty) data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingInstructionToken {
+    pub parts: Vec<Arc<str>>,
+    pub source_span: ParseSourceSpan,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExpansionFormStartToken {
     pub parts: Vec<Arc<str>>,
@@ -1,36 +1,58 @@
 //! XML Parser
 //!
 //! Corresponds to packages/compiler/src/ml_parser/xml_parser.ts (27 lines)
+//!
+//! Simple wrapper around Parser with XML tag definitions
 
-// NOTE: This depends on parser.rs which is not yet implemented
-// Will be completed after parser.rs is done
-
-/*
 use super::lexer::TokenizeOptions;
-use super::parser::{Parser, ParseTreeResult};
+use super::parser::{ParseTreeResult, Parser};
+use super::tags::TagDefinition;
 use super::xml_tags::get_xml_tag_definition;
 
 /// XML parser (extends generic Parser with XML tag definitions)
-pub struct XmlParser {
-    parser: Parser,
-}
+///
+/// TypeScript equivalent:
+/// ```typescript
+/// export class XmlParser extends Parser {
+///   constructor() {
+///     super(getXmlTagDefinition);
+///   }
+/// }
+/// ```
+pub struct XmlParser {}
 
 impl XmlParser {
+    /// Create new XML parser
     pub fn new() -> Self {
-        XmlParser {
-            parser: Parser::new(get_xml_tag_definition),
-        }
+        XmlParser {}
     }
 
-    pub fn parse(&self, source: &str, url: &str, options: Option<TokenizeOptions>) -> ParseTreeResult {
-        // Blocks and let declarations aren't supported in an XML context
+    /// Parse an XML document (e.g. an XLIFF or XMB translation file)
+    ///
+    /// Processing instructions and the DOCTYPE declaration, including its internal subset,
+    /// are skipped over and don't produce nodes.
+    pub fn parse(
+        &self,
+        source: &str,
+        url: &str,
+        options: Option<TokenizeOptions>,
+    ) -> ParseTreeResult {
+        fn tag_def(name: &str) -> &'static dyn TagDefinition {
+            get_xml_tag_definition(name)
+        }
+
+        // Blocks, let declarations and selectorless components aren't supported in an XML
+        // context
         let xml_options = TokenizeOptions {
             tokenize_blocks: false,
             tokenize_let: false,
             selectorless_enabled: false,
+            tokenize_processing_instructions: true,
             ..options.unwrap_or_default()
         };
-        self.parser.parse(source, url, Some(xml_options))
+
+        let parser = Parser::new(tag_def);
+        parser.parse(source, url, Some(xml_options))
     }
 }
 
@@ -39,7 +61,3 @@ impl Default for XmlParser {
         Self::new()
     }
 }
-*/
-
-// Placeholder until parser.rs is implemented
-pub struct XmlParser;
@@ -293,8 +293,7 @@ impl o::ExpressionVisitor for AbstractEmitterVisitor {
         context: &mut dyn Any,
     ) -> Box<dyn Any> {
         let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
-        let name = escape_identifier(&expr.name, false, false);
-        ctx.print(Some(expr as &dyn HasSourceSpan), &name, false);
+        ctx.print(Some(expr as &dyn HasSourceSpan), &expr.name, false);
         Box::new(())
     }
 
@@ -474,10 +473,30 @@ impl o::ExpressionVisitor for AbstractEmitterVisitor {
 
     fn visit_localized_string(
         &mut self,
-        _expr: &o::LocalizedString,
-        _context: &mut dyn std::any::Any,
+        expr: &o::LocalizedString,
+        context: &mut dyn std::any::Any,
     ) -> Box<dyn std::any::Any> {
-        // TODO: Implement localized string
+        {
+            let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
+            let head = expr.serialize_i18n_head();
+            ctx.print(Some(expr), &format!("$localize `{}", head.raw), false);
+        }
+        for i in 1..expr.message_parts.len() {
+            {
+                let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
+                ctx.print(Some(expr), "${", false);
+            }
+            expr.expressions[i - 1].visit_expression(self, context);
+            {
+                let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
+                let part = expr.serialize_i18n_template_part(i);
+                ctx.print(Some(expr), &format!("}}{}", part.raw), false);
+            }
+        }
+        {
+            let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
+            ctx.print(Some(expr), "`", false);
+        }
         Box::new(())
     }
 
@@ -847,10 +866,21 @@ impl o::ExpressionVisitor for AbstractEmitterVisitor {
         {
             let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
             ctx.print(Some(expr), "import(", false);
-            let url = escape_identifier(&expr.url, true, true);
-            ctx.print(Some(expr), &url, false);
-            ctx.print(Some(expr), ")", false);
+            if let Some(comment) = &expr.url_comment {
+                ctx.print(Some(expr), &format!("/* {} */ ", comment), false);
+            }
+        }
+        match &expr.url {
+            o::DynamicImportUrl::Literal(url) => {
+                let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
+                ctx.print(Some(expr), &escape_identifier(url, true, true), false);
+            }
+            o::DynamicImportUrl::Expression(url) => {
+                url.visit_expression(self, context);
+            }
         }
+        let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
+        ctx.print(Some(expr), ")", false);
         Box::new(())
     }
 
@@ -63,8 +63,7 @@ impl AbstractJsEmitterVisitor {
     }
 
     fn emit_read_var_expr(&mut self, expr: &o::ReadVarExpr, ctx: &mut EmitterVisitorContext) {
-        let name = escape_identifier(&expr.name, false, false);
-        ctx.print(Some(expr as &dyn HasSourceSpan), &name, false);
+        ctx.print(Some(expr as &dyn HasSourceSpan), &expr.name, false);
     }
 
     fn emit_literal_expr(&mut self, expr: &o::LiteralExpr, ctx: &mut EmitterVisitorContext) {
@@ -137,16 +136,17 @@ impl AbstractJsEmitterVisitor {
 
     fn emit_external_expr(&mut self, expr: &o::ExternalExpr, ctx: &mut EmitterVisitorContext) {
         let ref_expr = &expr.value;
+        // A reference without a name is the namespace of the module itself.
+        let separator = if ref_expr.name.is_some() { "." } else { "" };
         if let Some(module_name) = &ref_expr.module_name {
             if module_name == "@angular/core" {
-                ctx.print(Some(expr), "i0.", false);
+                ctx.print(Some(expr), "i0", false);
             } else if let Some(alias) = self.imports.get(module_name) {
                 ctx.print(Some(expr), alias, false);
-                ctx.print(Some(expr), ".", false);
             } else {
                 ctx.print(Some(expr), module_name, false);
-                ctx.print(Some(expr), ".", false);
             }
+            ctx.print(Some(expr), separator, false);
         }
         if let Some(name) = &ref_expr.name {
             ctx.print(Some(expr), name, false);
@@ -436,8 +436,15 @@ impl AbstractJsEmitterVisitor {
         ctx: &mut EmitterVisitorContext,
     ) {
         ctx.print(Some(expr), "import(", false);
-        let url = escape_identifier(&expr.url, true, true);
-        ctx.print(Some(expr), &url, false);
+        if let Some(comment) = &expr.url_comment {
+            ctx.print(Some(expr), &format!("/* {} */ ", comment), false);
+        }
+        match &expr.url {
+            o::DynamicImportUrl::Literal(url) => {
+                ctx.print(Some(expr), &escape_identifier(url, true, true), false);
+            }
+            o::DynamicImportUrl::Expression(url) => self.emit_expression(url, ctx),
+        }
         ctx.print(Some(expr), ")", false);
     }
 
@@ -530,10 +537,21 @@ impl AbstractJsEmitterVisitor {
 
     fn emit_localized_string(
         &mut self,
-        _expr: &o::LocalizedString,
-        _ctx: &mut EmitterVisitorContext,
+        expr: &o::LocalizedString,
+        ctx: &mut EmitterVisitorContext,
     ) {
-        // TODO: Implement localized string
+        ctx.print(
+            Some(expr),
+            &format!("$localize `{}", expr.serialize_i18n_head().raw),
+            false,
+        );
+        for i in 1..expr.message_parts.len() {
+            ctx.print(Some(expr), "${", false);
+            self.emit_expression(&expr.expressions[i - 1], ctx);
+            let part = expr.serialize_i18n_template_part(i);
+            ctx.print(Some(expr), &format!("}}{}", part.raw), false);
+        }
+        ctx.print(Some(expr), "`", false);
     }
 }
 
@@ -752,14 +770,17 @@ impl o::ExpressionVisitor for AbstractJsEmitterVisitor {
     ) -> Box<dyn Any> {
         let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
         let ref_expr = &expr.value;
+        // A reference without a name is the namespace of the module itself.
+        let separator = if ref_expr.name.is_some() { "." } else { "" };
 
         // Handle common Angular imports aliasing and configured imports
         if let Some(module_name) = &ref_expr.module_name {
             if module_name == "@angular/core" {
-                ctx.print(Some(expr), "i0.", false);
+                ctx.print(Some(expr), "i0", false);
+                ctx.print(Some(expr), separator, false);
             } else if let Some(alias) = self.imports.get(module_name) {
                 ctx.print(Some(expr), alias, false);
-                ctx.print(Some(expr), ".", false);
+                ctx.print(Some(expr), separator, false);
             }
             // If module_name is present but not in our imports map,
             // we assume the symbol is already available in scope via a local import
@@ -1123,13 +1144,8 @@ impl o::ExpressionVisitor for AbstractJsEmitterVisitor {
         expr: &o::DynamicImportExpr,
         context: &mut dyn Any,
     ) -> Box<dyn Any> {
-        {
-            let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
-            ctx.print(Some(expr), "import(", false);
-            let url = escape_identifier(&expr.url, true, true);
-            ctx.print(Some(expr), &url, false);
-            ctx.print(Some(expr), ")", false);
-        }
+        let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
+        self.emit_dynamic_import_expr(expr, ctx);
         Box::new(())
     }
 
@@ -1187,12 +1203,10 @@ impl o::ExpressionVisitor for AbstractJsEmitterVisitor {
         context: &mut dyn Any,
     ) -> Box<dyn Any> {
         if let Some(s) = expr.node.downcast_ref::<String>() {
-            println!("DEBUG: visit_wrapped_node_expr found string: {}", s);
             let ctx = context.downcast_mut::<EmitterVisitorContext>().unwrap();
             ctx.print(None, s, false);
             return Box::new(());
         }
-        println!("DEBUG: visit_wrapped_node_expr failed to downcast");
         // WrappedNodeExpr should throw error in JavaScript emitter if not valid
         panic!(
             "Cannot emit a wrapped node expression as JavaScript code: {:?}",
@@ -407,6 +407,8 @@ pub struct TemplateLiteralElement {
 
 #[derive(Debug, Clone)]
 pub struct LocalizedString {
+    /// The unescaped `meaning|description@@id` metadata block of the message, including any
+    /// legacy ids. Empty when the message has no metadata.
     pub meta_block: String,
     pub message_parts: Vec<LiteralPiece>,
     pub placeholder_names: Vec<PlaceholderPiece>,
@@ -424,6 +426,95 @@ pub struct LiteralPiece {
 pub struct PlaceholderPiece {
     pub text: String,
     pub source_span: ParseSourceSpan,
+    /// The id of the sub-message (e.g. an ICU) this placeholder stands for, when it must be
+    /// encoded in the placeholder metadata.
+    pub associated_message_id: Option<String>,
+}
+
+/// A piece of a `$localize` tagged template, in both its cooked and raw forms.
+#[derive(Debug, Clone)]
+pub struct CookedRawString {
+    pub cooked: String,
+    pub raw: String,
+    pub range: Option<ParseSourceSpan>,
+}
+
+impl LocalizedString {
+    /// Serialize the first message part, prefixed with the metadata block.
+    ///
+    /// The result looks like `:meaning|description@@id:Hello `.
+    pub fn serialize_i18n_head(&self) -> CookedRawString {
+        let head = self.message_parts.first();
+        create_cooked_raw_string(
+            &self.meta_block,
+            head.map(|part| part.text.as_str()).unwrap_or(""),
+            head.map(|part| part.source_span.clone()),
+        )
+    }
+
+    /// Serialize the message part at `part_index`, prefixed with the name of the placeholder
+    /// that precedes it, e.g. `:INTERPOLATION: world`.
+    pub fn serialize_i18n_template_part(&self, part_index: usize) -> CookedRawString {
+        let placeholder = &self.placeholder_names[part_index - 1];
+        let message_part = &self.message_parts[part_index];
+        let mut meta_block = placeholder.text.clone();
+        if let Some(id) = &placeholder.associated_message_id {
+            meta_block.push_str(I18N_ID_SEPARATOR);
+            meta_block.push_str(id);
+        }
+        create_cooked_raw_string(
+            &meta_block,
+            &message_part.text,
+            Some(message_part.source_span.clone()),
+        )
+    }
+}
+
+const I18N_ID_SEPARATOR: &str = "@@";
+
+fn escape_slashes(s: &str) -> String {
+    s.replace('\\', "\\\\")
+}
+
+fn escape_starting_colon(s: &str) -> String {
+    match s.strip_prefix(':') {
+        Some(rest) => format!("\\:{}", rest),
+        None => s.to_string(),
+    }
+}
+
+fn escape_colons(s: &str) -> String {
+    s.replace(':', "\\:")
+}
+
+fn escape_for_template_literal(s: &str) -> String {
+    s.replace('`', "\\`").replace("${", "$\\{")
+}
+
+/// Create the cooked and raw strings of a `$localize` message part. The metadata block, if
+/// any, is wrapped in colons and its own colons are escaped in the raw string.
+fn create_cooked_raw_string(
+    meta_block: &str,
+    message_part: &str,
+    range: Option<ParseSourceSpan>,
+) -> CookedRawString {
+    if meta_block.is_empty() {
+        CookedRawString {
+            cooked: message_part.to_string(),
+            raw: escape_for_template_literal(&escape_starting_colon(&escape_slashes(message_part))),
+            range,
+        }
+    } else {
+        CookedRawString {
+            cooked: format!(":{}:{}", meta_block, message_part),
+            raw: escape_for_template_literal(&format!(
+                ":{}:{}",
+                escape_colons(&escape_slashes(meta_block)),
+                escape_slashes(message_part)
+            )),
+            range,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -467,10 +558,19 @@ pub struct ConditionalExpr {
 
 #[derive(Debug, Clone)]
 pub struct DynamicImportExpr {
-    pub url: String,
+    pub url: DynamicImportUrl,
+    /// Comment printed before the url, e.g. `@vite-ignore` to keep bundlers from resolving it.
+    pub url_comment: Option<String>,
     pub source_span: Option<ParseSourceSpan>,
 }
 
+/// The module loaded by a dynamic import: a module specifier, or an expression computing one.
+#[derive(Debug, Clone)]
+pub enum DynamicImportUrl {
+    Literal(String),
+    Expression(Box<Expression>),
+}
+
 #[derive(Debug, Clone)]
 pub struct NotExpr {
     pub condition: Box<Expression>,
@@ -613,8 +713,13 @@ pub struct WrappedNodeExpr {
 
 impl Clone for WrappedNodeExpr {
     fn clone(&self) -> Self {
+        // `Box<dyn Any>` can't be cloned in general; keep nodes given as source text
+        let node: Box<dyn std::any::Any> = match self.node.downcast_ref::<String>() {
+            Some(text) => Box::new(text.clone()),
+            None => Box::new(()),
+        };
         WrappedNodeExpr {
-            node: Box::new(()), // Cannot clone Box<dyn Any>, so use placeholder
+            node,
             type_: self.type_.clone(),
             source_span: self.source_span.clone(),
         }
@@ -1115,6 +1220,15 @@ impl Expression {
             (Expression::LiteralArray(a), Expression::LiteralArray(b)) => a.is_equivalent(b),
             (Expression::External(a), Expression::External(b)) => a.is_equivalent(b),
             (Expression::ReadVar(a), Expression::ReadVar(b)) => a.name == b.name,
+            (Expression::WrappedNode(a), Expression::WrappedNode(b)) => {
+                match (
+                    a.node.downcast_ref::<String>(),
+                    b.node.downcast_ref::<String>(),
+                ) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => false,
+                }
+            }
             // Add more cases as needed, or fall back to false
             _ => false,
         }
@@ -1566,6 +1680,12 @@ impl HasSourceSpan for DynamicImportExpr {
     }
 }
 
+impl HasSourceSpan for LocalizedString {
+    fn source_span(&self) -> Option<&ParseSourceSpan> {
+        self.source_span.as_ref()
+    }
+}
+
 impl HasSourceSpan for TemplateLiteralExpr {
     fn source_span(&self) -> Option<&ParseSourceSpan> {
         // TemplateLiteralExpr doesn't have source_span, return None
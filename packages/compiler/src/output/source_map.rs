@@ -34,6 +34,7 @@ pub struct SourceMap {
 pub struct SourceMapGenerator {
     file: Option<String>,
     sources_content: HashMap<String, Option<String>>,
+    include_sources_content: bool,
     lines: Vec<Vec<Segment>>,
     last_col0: usize,
     has_mappings: bool,
@@ -44,6 +45,7 @@ impl SourceMapGenerator {
         SourceMapGenerator {
             file,
             sources_content: HashMap::new(),
+            include_sources_content: true,
             lines: Vec::new(),
             last_col0: 0,
             has_mappings: false,
@@ -56,6 +58,14 @@ impl SourceMapGenerator {
         self
     }
 
+    /// Whether the source text is embedded in `sourcesContent` (the default). Embedding lets
+    /// debuggers show the original template and TypeScript code without reading them from disk;
+    /// when disabled every entry is `null` and tools load the sources by URL instead.
+    pub fn set_include_sources_content(&mut self, include: bool) -> &mut Self {
+        self.include_sources_content = include;
+        self
+    }
+
     pub fn add_line(&mut self) -> &mut Self {
         self.lines.push(Vec::new());
         self.last_col0 = 0;
@@ -127,7 +137,11 @@ impl SourceMapGenerator {
         for (i, url) in sorted_keys.iter().enumerate() {
             sources_index.insert((*url).clone(), i);
             sources.push((*url).clone());
-            sources_content.push(self.sources_content.get(*url).and_then(|c| c.clone()));
+            sources_content.push(if self.include_sources_content {
+                self.sources_content.get(*url).and_then(|c| c.clone())
+            } else {
+                None
+            });
         }
 
         let mut mappings = String::new();
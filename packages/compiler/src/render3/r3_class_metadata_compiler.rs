@@ -5,8 +5,9 @@
 
 use crate::output::output_ast::dynamic_type;
 use crate::output::output_ast::{
-    ArrowFunctionBody, ArrowFunctionExpr, DynamicImportExpr, Expression, ExternalExpr, FnParam,
-    InvokeFunctionExpr, LiteralArrayExpr, LiteralExpr, LiteralValue, ReadPropExpr, ReadVarExpr,
+    ArrowFunctionBody, ArrowFunctionExpr, DynamicImportExpr, DynamicImportUrl, Expression,
+    ExternalExpr, FnParam, InvokeFunctionExpr, LiteralArrayExpr, LiteralExpr, LiteralValue,
+    ReadPropExpr, ReadVarExpr,
 };
 
 use super::r3_identifiers::Identifiers as R3;
@@ -223,7 +224,8 @@ pub fn compile_component_metadata_async_resolver(
 
             // e.g. `import('./cmp-a').then(...)`
             let dynamic_import = Expression::DynamicImport(DynamicImportExpr {
-                url: dep.import_path.clone(),
+                url: DynamicImportUrl::Literal(dep.import_path.clone()),
+                url_comment: None,
                 source_span: None,
             });
 
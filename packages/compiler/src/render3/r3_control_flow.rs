@@ -5,11 +5,12 @@
 
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::collections::HashSet;
 
 use crate::expression_parser::ast::{ASTWithSource, AST};
 use crate::i18n;
 use crate::ml_parser::ast as html;
-use crate::parse_util::{ParseError, ParseSourceSpan};
+use crate::parse_util::{ParseError, ParseErrorLevel, ParseSourceSpan};
 use crate::template_parser::binding_parser::BindingParser;
 
 use super::r3_ast::{
@@ -411,18 +412,19 @@ fn parse_for_loop_parameters(
     let secondary_params = &block.parameters[1..];
 
     let stripped = strip_optional_parentheses(expression_param, errors)?;
-    let captures = FOR_LOOP_EXPRESSION_PATTERN.captures(&stripped)?;
-
-    let item_name_str = captures.get(1)?.as_str();
-    let raw_expression = captures.get(2)?.as_str();
-
-    if raw_expression.trim().is_empty() {
+    let captures = FOR_LOOP_EXPRESSION_PATTERN
+        .captures(&stripped)
+        .filter(|captures| !captures[2].trim().is_empty());
+    let Some(captures) = captures else {
         errors.push(ParseError::new(
             expression_param.source_span.clone(),
             "Cannot parse expression. @for loop expression must match the pattern \"<identifier> of <expression>\"".to_string(),
         ));
         return None;
-    }
+    };
+
+    let item_name_str = captures.get(1)?.as_str();
+    let raw_expression = captures.get(2)?.as_str();
 
     let allowed_vars = allowed_for_loop_let_variables();
     if allowed_vars.contains(&item_name_str) {
@@ -666,6 +668,7 @@ fn validate_if_connected_blocks(connected_blocks: &[html::Block]) -> Vec<ParseEr
 fn validate_switch_block(ast: &html::Block) -> Vec<ParseError> {
     let mut errors: Vec<ParseError> = Vec::new();
     let mut has_default = false;
+    let mut case_values: HashSet<&str> = HashSet::new();
 
     if ast.parameters.len() != 1 {
         errors.push(ParseError::new(
@@ -706,6 +709,20 @@ fn validate_switch_block(ast: &html::Block) -> Vec<ParseError> {
                         block.start_source_span.clone(),
                         "@case block must have exactly one parameter".to_string(),
                     ));
+                } else if block.name.as_ref() == "case" {
+                    // Only the first matching case is rendered, so a repeated value is dead code.
+                    // Angular accepts it, so this is a warning and the output is unchanged.
+                    let value = block.parameters[0].expression.trim();
+                    if !case_values.insert(value) {
+                        errors.push(ParseError {
+                            span: block.start_source_span.clone(),
+                            msg: format!(
+                                "@switch block has more than one @case with the value \"{}\"",
+                                value
+                            ),
+                            level: ParseErrorLevel::Warning,
+                        });
+                    }
                 }
             }
             _ => {
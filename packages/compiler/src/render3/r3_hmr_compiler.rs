@@ -6,9 +6,9 @@
 use crate::output::output_ast::dynamic_type;
 use crate::output::output_ast::{
     ArrowFunctionBody, ArrowFunctionExpr, BinaryOperator, BinaryOperatorExpr, DeclareFunctionStmt,
-    DeclareVarStmt, DynamicImportExpr, Expression, ExternalExpr, ExternalReference, FnParam,
-    InvokeFunctionExpr, LiteralArrayExpr, LiteralExpr, LiteralValue, ReadPropExpr, ReadVarExpr,
-    Statement, StmtModifier, WritePropExpr,
+    DeclareVarStmt, DynamicImportExpr, DynamicImportUrl, Expression, ExternalExpr,
+    ExternalReference, FnParam, InvokeFunctionExpr, LiteralArrayExpr, LiteralExpr, LiteralValue,
+    ReadKeyExpr, ReadPropExpr, ReadVarExpr, Statement, StmtModifier, WritePropExpr,
 };
 
 use super::r3_identifiers::Identifiers as R3;
@@ -183,7 +183,7 @@ pub fn compile_hmr_initializer(meta: &R3HmrMetadata) -> Expression {
     let get_replace_metadata_url_ref = R3::get_replace_metadata_url();
     let get_replace_metadata_url_expr = external_expr(get_replace_metadata_url_ref);
 
-    let _url_expr = Expression::InvokeFn(InvokeFunctionExpr {
+    let url_expr = Expression::InvokeFn(InvokeFunctionExpr {
         fn_: Box::new(get_replace_metadata_url_expr),
         args: vec![
             Expression::ReadVar(ReadVarExpr {
@@ -217,13 +217,10 @@ pub fn compile_hmr_initializer(meta: &R3HmrMetadata) -> Expression {
         pure: false,
     });
 
-    // import(url).then(replaceCallback)
-    // Note: DynamicImportExpr in Rust expects a String, but TypeScript accepts an Expression
-    // We need to use the url_expr as a template string or evaluate it
-    // For now, we'll create a DynamicImportExpr with a placeholder and handle the expression separately
-    // The comment '@vite-ignore' would be handled by the emitter
+    // import(/* @vite-ignore */ url).then(replaceCallback)
     let dynamic_import = Expression::DynamicImport(DynamicImportExpr {
-        url: "/* @vite-ignore */ import(url)".to_string(), // This will need proper stringification of url_expr
+        url: DynamicImportUrl::Expression(Box::new(url_expr)),
+        url_comment: Some("@vite-ignore".to_string()),
         source_span: None,
     });
 
@@ -466,13 +463,17 @@ pub fn compile_hmr_update_callback(
     for (i, dep) in meta.namespace_dependencies.iter().enumerate() {
         body.push(Statement::DeclareVar(DeclareVarStmt {
             name: dep.assigned_name.clone(),
-            value: Some(Box::new(Expression::ReadProp(ReadPropExpr {
+            value: Some(Box::new(Expression::ReadKey(ReadKeyExpr {
                 receiver: Box::new(Expression::ReadVar(ReadVarExpr {
                     name: namespaces.to_string(),
                     type_: None,
                     source_span: None,
                 })),
-                name: i.to_string(),
+                index: Box::new(Expression::Literal(LiteralExpr {
+                    value: LiteralValue::Number(i as f64),
+                    type_: None,
+                    source_span: None,
+                })),
                 type_: None,
                 source_span: None,
             }))),
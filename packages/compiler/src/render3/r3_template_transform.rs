@@ -9,10 +9,10 @@ use std::sync::Arc;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::expression_parser::ast::AST;
+use crate::expression_parser::ast::{Interpolation, ParseSpan, AST};
 use crate::i18n::i18n_ast as i18n;
 use crate::ml_parser::ast as html;
-use crate::ml_parser::html_whitespaces::process_whitespace;
+use crate::ml_parser::html_whitespaces::{process_whitespace, replace_ngsp};
 use crate::ml_parser::tags::is_ng_template;
 use crate::ml_parser::tokens::Token;
 use crate::parse_util::{ParseError, ParseSourceSpan};
@@ -27,6 +27,7 @@ use super::r3_control_flow::{
     preprocess_if_block,
 };
 use super::r3_deferred_blocks::{create_deferred_block, is_connected_defer_loop_block};
+use super::view::i18n::util::I18N_ICU_VAR_PREFIX;
 
 lazy_static! {
     /// Regex to match binding prefixes
@@ -100,12 +101,15 @@ pub struct Render3ParseResult {
 #[derive(Debug, Clone)]
 pub struct Render3ParseOptions {
     pub collect_comment_nodes: bool,
+    /// Keep text nodes verbatim (only `&ngsp;` is replaced) instead of collapsing whitespace.
+    pub preserve_whitespaces: bool,
 }
 
 impl Default for Render3ParseOptions {
     fn default() -> Self {
         Render3ParseOptions {
             collect_comment_nodes: false,
+            preserve_whitespaces: false,
         }
     }
 }
@@ -259,6 +263,7 @@ impl<'a, 'b> HtmlAstToIvyAst<'a, 'b> {
                 source_span: a.source_span.clone(),
                 start_source_span: a.source_span.clone(),
                 end_source_span: None,
+                id: 0,
             })
             .collect();
 
@@ -488,56 +493,53 @@ impl<'a, 'b> HtmlAstToIvyAst<'a, 'b> {
     }
 
     fn visit_expansion(&mut self, expansion: &html::Expansion) -> Option<t::R3Node> {
+        let Some(i18n::I18nMeta::Message(message)) = &expansion.i18n else {
+            // do not generate Icu in case it was created outside of i18n block in a template
+            return None;
+        };
+
         let mut vars = HashMap::new();
         let mut placeholders = HashMap::new();
 
-        // 1. Process switch value (as a variable)
-        let switch_value = &expansion.switch_value;
-        let switch_span = &expansion.switch_value_source_span;
-
-        let switch_expr = self.binding_parser.parse_binding(
-            switch_value,
-            false,
-            switch_span.clone(),
-            switch_span.start.offset,
-        );
-
-        let bound_text = t::BoundText::new(
-            (*switch_expr.ast).clone(),
-            switch_span.clone(),
-            None, // switch value itself doesn't have i18n meta usually
-        );
-        vars.insert(switch_value.clone(), bound_text);
-
-        // 2. Process cases recursively
-        for case in &expansion.cases {
-            for node in &case.expression {
-                let r3_node = match node {
-                    html::Node::Element(e) => self.visit_element(e),
-                    html::Node::Text(txt) => self.visit_text(txt),
-                    // html::Node::BoundText does not exist in ml_parser
-                    html::Node::Expansion(e) => self.visit_expansion(e),
-                    _ => None,
+        // Visit placeholders in a stable order, the message stores them in a hash map.
+        let mut keys: Vec<&String> = message.placeholders.keys().collect();
+        keys.sort();
+        for key in keys {
+            let value = &message.placeholders[key];
+            if key.starts_with(I18N_ICU_VAR_PREFIX) {
+                // Currently when the `plural` or `select` keywords in an ICU contain trailing
+                // spaces (e.g. `{count, select , ...}`), these spaces are also included into the
+                // key names in ICU vars (e.g. "VAR_SELECT "). These trailing spaces are not
+                // desirable, since they will later be converted into `_` symbols while
+                // normalizing placeholder names, which might lead to mismatches at runtime.
+                let formatted_key: Arc<str> = key.trim().into();
+                let ast = self
+                    .binding_parser
+                    .parse_interpolation_expression(&value.text, &value.source_span);
+                let expression = *ast.ast;
+                let source_span = expression.source_span();
+                let interpolation = AST::Interpolation(Interpolation {
+                    span: ParseSpan {
+                        start: 0,
+                        end: value.text.len(),
+                    },
+                    source_span,
+                    strings: vec![String::new(), String::new()],
+                    expressions: vec![Box::new(expression)],
+                });
+                vars.insert(
+                    formatted_key,
+                    t::BoundText::new(interpolation, value.source_span.clone(), None),
+                );
+            } else if let Some(node) =
+                self.visit_text_with_interpolation(&value.text, &value.source_span, &None, None)
+            {
+                let placeholder = match node {
+                    t::R3Node::BoundText(bound_text) => t::IcuPlaceholder::BoundText(*bound_text),
+                    t::R3Node::Text(text) => t::IcuPlaceholder::Text(*text),
+                    _ => continue,
                 };
-
-                if let Some(node) = r3_node {
-                    match node {
-                        t::R3Node::BoundText(bt) => {
-                            let key: Arc<str> = bt.source_span.to_string().into();
-                            placeholders.insert(key, t::IcuPlaceholder::BoundText(*bt));
-                        }
-                        t::R3Node::Text(txt) => {
-                            let key: Arc<str> = txt.source_span.to_string().into();
-                            placeholders.insert(key, t::IcuPlaceholder::Text(*txt));
-                        }
-                        t::R3Node::Icu(nested_icu) => {
-                            // Merge variables and placeholders from nested ICU
-                            vars.extend(nested_icu.vars);
-                            placeholders.extend(nested_icu.placeholders);
-                        }
-                        _ => {}
-                    }
-                }
+                placeholders.insert(key.as_str().into(), placeholder);
             }
         }
 
@@ -1477,8 +1479,13 @@ impl<'a, 'b> HtmlAstToIvyAst<'a, 'b> {
         tokens: Option<Vec<Token>>,
     ) -> Option<t::R3Node> {
         // Apply whitespace collapsing: consecutive WS chars (newlines, tabs, spaces) -> single space
-        // This matches NGTSC's behavior for interpolation text processing
-        let value_processed = process_whitespace(value);
+        // This matches NGTSC's behavior for interpolation text processing. When whitespace is
+        // preserved only the `&ngsp;` placeholder is replaced, like `replaceNgsp` in Angular.
+        let value_processed = if self.options.preserve_whitespaces {
+            replace_ngsp(value)
+        } else {
+            process_whitespace(value)
+        };
         let expr = self
             .binding_parser
             .parse_interpolation(&value_processed, source_span, None);
@@ -1897,26 +1904,19 @@ fn add_events(events: &[ParsedEvent], bound_events: &mut Vec<t::BoundEvent>) {
                 ExprParsedEventType::Animation
             }
             crate::template_parser::binding_parser::ParsedEventType::TwoWay => {
-                ExprParsedEventType::Regular
-            } // TwoWay not in ExprParsedEventType
+                ExprParsedEventType::TwoWay
+            }
             crate::template_parser::binding_parser::ParsedEventType::LegacyAnimation => {
-                ExprParsedEventType::Animation
+                ExprParsedEventType::LegacyAnimation
             }
         };
 
-        // Parse target_or_phase to target and phase
-        let (target, phase) = if let Some(ref top) = e.target_or_phase {
-            if top.contains(':') {
-                let parts: Vec<&str> = top.split(':').collect();
-                (Some(parts[0].to_string()), Some(parts[1..].join(":")))
-            } else if top.contains('.') {
-                let parts: Vec<&str> = top.split('.').collect();
-                (Some(parts[0].to_string()), Some(parts[1..].join(".")))
-            } else {
-                (Some(top.clone()), None)
-            }
-        } else {
-            (None, None)
+        // `target_or_phase` is the target of a regular event (`window:resize`) or the phase of a
+        // legacy animation event (`@trigger.done`).
+        let (target, phase) = match expr_event_type {
+            ExprParsedEventType::Regular => (e.target_or_phase.clone(), None),
+            ExprParsedEventType::LegacyAnimation => (None, e.target_or_phase.clone()),
+            _ => (None, None),
         };
 
         bound_events.push(t::BoundEvent::new(
@@ -8,8 +8,8 @@ use crate::output::abstract_emitter::escape_identifier;
 use crate::output::output_ast::{
     ArrowFunctionBody, ArrowFunctionExpr, BinaryOperator, BinaryOperatorExpr, BuiltinType,
     BuiltinTypeName, Expression, ExpressionType, ExternalExpr, ExternalReference,
-    InvokeFunctionExpr, LiteralArrayExpr, LiteralExpr, LiteralValue, Statement, Type, TypeModifier,
-    TypeofExpr, WrappedNodeExpr,
+    InvokeFunctionExpr, LiteralArrayExpr, LiteralExpr, LiteralValue, ParenthesizedExpr, Statement,
+    Type, TypeModifier, TypeofExpr, WrappedNodeExpr,
 };
 
 /// Creates an expression type with the given number of type parameters
@@ -132,10 +132,15 @@ pub fn guarded_expression(guard: &str, expr: Expression) -> Expression {
         source_span: None,
     });
 
-    let guard_undefined_or_true = Expression::BinaryOp(BinaryOperatorExpr {
-        operator: BinaryOperator::Or,
-        lhs: Box::new(guard_not_defined),
-        rhs: Box::new(guard_expr),
+    // `||` binds looser than `&&`, so the guard is parenthesized.
+    let guard_undefined_or_true = Expression::Parens(ParenthesizedExpr {
+        expr: Box::new(Expression::BinaryOp(BinaryOperatorExpr {
+            operator: BinaryOperator::Or,
+            lhs: Box::new(guard_not_defined),
+            rhs: Box::new(guard_expr),
+            type_: None,
+            source_span: None,
+        })),
         type_: None,
         source_span: None,
     });
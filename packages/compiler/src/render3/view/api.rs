@@ -8,7 +8,7 @@ use std::collections::HashMap;
 
 use crate::core::{ChangeDetectionStrategy, ViewEncapsulation};
 use crate::output::output_ast::Expression;
-use crate::parse_util::ParseSourceSpan;
+use crate::parse_util::{ParseLocation, ParseSourceSpan};
 use crate::render3::r3_ast as t;
 use crate::render3::r3_factory::R3DependencyMetadata;
 use crate::render3::util::{MaybeForwardRefExpression, R3Reference};
@@ -128,6 +128,12 @@ pub struct R3ComponentMetadata {
     pub change_detection: Option<ChangeDetectionOrExpression>,
     /// Relative path to the component's template.
     pub relative_template_path: Option<String>,
+    /// Whether to attach the location of each element in the template to the DOM, so that
+    /// development tooling can map nodes back to their definition.
+    pub enable_debug_locations: bool,
+    /// Where an inline template starts in the component source, so that the debug locations of
+    /// its elements point into that file. `None` when the template is its own file.
+    pub template_start: Option<ParseLocation>,
     /// Whether any of the component's dependencies are directives.
     pub has_directive_dependencies: bool,
     /// The imports expression for standalone components.
@@ -10,9 +10,10 @@ use crate::constant_pool::ConstantPool;
 use crate::core::{ChangeDetectionStrategy, ViewEncapsulation};
 use crate::directive_matching::CssSelector;
 use crate::output::output_ast::{
-    ArrowFunctionBody, ArrowFunctionExpr, DynamicImportExpr, Expression, ExternalExpr, FnParam,
-    FunctionExpr, InvokeFunctionExpr, LiteralArrayExpr, LiteralExpr, LiteralMapEntry,
-    LiteralMapExpr, LiteralValue, ReadPropExpr, ReadVarExpr, ReturnStatement, Statement, Type,
+    ArrowFunctionBody, ArrowFunctionExpr, DynamicImportExpr, DynamicImportUrl, Expression,
+    ExternalExpr, FnParam, FunctionExpr, InvokeFunctionExpr, LiteralArrayExpr, LiteralExpr,
+    LiteralMapEntry, LiteralMapExpr, LiteralValue, ReadPropExpr, ReadVarExpr, ReturnStatement,
+    Statement, Type,
 };
 use crate::parse_util::{ParseError, ParseSourceSpan};
 use crate::render3::r3_identifiers::Identifiers as R3;
@@ -83,7 +84,29 @@ pub fn compile_component_from_metadata(
     constant_pool: &mut ConstantPool,
     binding_parser: &mut BindingParser,
 ) -> R3CompiledExpression {
+    compile_component_with_diagnostics(meta, constant_pool, binding_parser).0
+}
+
+/// Compile a component for the render3 runtime, also returning the diagnostics reported by the
+/// template pipeline (e.g. reading a `@let` declaration before it is defined).
+pub fn compile_component_with_diagnostics(
+    meta: &R3ComponentMetadata,
+    constant_pool: &mut ConstantPool,
+    binding_parser: &mut BindingParser,
+) -> (R3CompiledExpression, Vec<ParseError>) {
     // eprintln!("DEBUG: compile_component_from_metadata called for {}, inputs len: {}", meta.directive.name, meta.directive.inputs.len());
+    // In per-component mode all defer blocks share a single dependency function.
+    let all_deferrable_deps_fn = match &meta.defer {
+        super::api::R3ComponentDeferMetadata::PerComponent {
+            dependencies_fn: Some(dependencies_fn),
+        } => Some(constant_pool.get_shared_function_reference(
+            dependencies_fn.clone(),
+            format!("{}_DeferFn", meta.directive.name),
+            false,
+        )),
+        _ => None,
+    };
+
     // 1. Ingest
     let mut job = crate::template::pipeline::src::ingest::ingest_component(
         meta.directive.name.clone(),
@@ -93,15 +116,16 @@ pub fn compile_component_from_metadata(
         meta.relative_context_file_path.clone(),
         meta.i18n_use_external_ids,
         meta.defer.clone(),
-        None, // all_deferrable_deps_fn
+        all_deferrable_deps_fn,
         meta.relative_template_path.clone(),
-        false, // enable_debug_locations
+        meta.enable_debug_locations,
         meta.change_detection.as_ref().and_then(|cd| match cd {
             super::api::ChangeDetectionOrExpression::Strategy(s) => Some(*s),
             _ => None,
         }),
         meta.declarations.clone(),
     );
+    job.template_start = meta.template_start.clone();
 
     // 2. Run phases
     crate::template::pipeline::src::phases::run(&mut job);
@@ -156,7 +180,9 @@ pub fn compile_component_from_metadata(
     };
 
     // 4. Emit
-    crate::template::pipeline::src::emit::emit_component(&job, meta, host_job.as_ref())
+    let compiled =
+        crate::template::pipeline::src::emit::emit_component(&job, meta, host_job.as_ref());
+    (compiled, job.diagnostics)
 }
 
 /// Helper to create R3 selector array from CssSelector
@@ -500,39 +526,183 @@ pub fn create_host_directives_mapping_array(
 
 /// Creates the type specification for a directive.
 pub fn create_directive_type(meta: &R3DirectiveMetadata) -> Type {
-    let type_params = create_base_directive_type_params(meta);
-    // TODO: Add remaining type params
-    Type::Expression(crate::output::output_ast::ExpressionType {
-        value: Box::new(external_expr(R3::directive_declaration())),
-        modifiers: crate::output::output_ast::TypeModifier::None,
-        type_params: Some(type_params),
-    })
+    let mut type_params = create_base_directive_type_params(meta);
+    // Directives have no NgContentSelectors slot, but instead express a `never` type
+    // so that future fields align.
+    type_params.push(crate::output::output_ast::none_type());
+    type_params.push(expression_type(literal(LiteralValue::Bool(
+        meta.is_standalone,
+    ))));
+    type_params.push(create_host_directives_type(meta));
+    if meta.is_signal {
+        type_params.push(expression_type(literal(LiteralValue::Bool(true))));
+    }
+    declaration_type(R3::directive_declaration(), type_params)
 }
 
 /// Creates the type specification for a component.
 pub fn create_component_type(meta: &R3ComponentMetadata) -> Type {
-    let type_params = create_base_directive_type_params(&meta.directive);
-    // TODO: Add remaining type params
-    Type::Expression(crate::output::output_ast::ExpressionType {
-        value: Box::new(external_expr(R3::component_declaration())),
-        modifiers: crate::output::output_ast::TypeModifier::None,
-        type_params: Some(type_params),
-    })
+    let mut type_params = create_base_directive_type_params(&meta.directive);
+    type_params.push(string_array_as_type(&meta.template.ng_content_selectors));
+    type_params.push(expression_type(literal(LiteralValue::Bool(
+        meta.directive.is_standalone,
+    ))));
+    type_params.push(create_host_directives_type(&meta.directive));
+    if meta.directive.is_signal {
+        type_params.push(expression_type(literal(LiteralValue::Bool(true))));
+    }
+    declaration_type(R3::component_declaration(), type_params)
 }
 
 fn create_base_directive_type_params(meta: &R3DirectiveMetadata) -> Vec<Type> {
     let selector_for_type = meta.selector.as_ref().map(|s| s.replace('\n', ""));
+    let query_names: Vec<String> = meta
+        .queries
+        .iter()
+        .map(|query| query.property_name.clone())
+        .collect();
 
     vec![
         type_with_parameters(meta.type_.type_expr.clone(), meta.type_argument_count),
-        selector_for_type.map_or(
-            crate::output::output_ast::none_type(),
-            |_| crate::output::output_ast::string_type(), // TODO: Create literal type from string
-        ),
-        // TODO: Add remaining type params
+        selector_for_type.map_or(crate::output::output_ast::none_type(), |selector| {
+            expression_type(literal(LiteralValue::String(selector)))
+        }),
+        meta.export_as
+            .as_ref()
+            .map_or(crate::output::output_ast::none_type(), |export_as| {
+                string_array_as_type(export_as)
+            }),
+        expression_type(get_inputs_type_expression(meta)),
+        expression_type(string_map_as_literal_expression(&meta.outputs)),
+        string_array_as_type(&query_names),
     ]
 }
 
+/// The type literal describing the inputs of a directive, e.g.
+/// `{"value": {"alias": "value"; "required": false}}`.
+fn get_inputs_type_expression(meta: &R3DirectiveMetadata) -> Expression {
+    let entries = meta
+        .inputs
+        .iter()
+        .map(|(key, input)| {
+            let mut values = vec![
+                quoted_entry(
+                    "alias",
+                    literal(LiteralValue::String(input.binding_property_name.clone())),
+                ),
+                quoted_entry("required", literal(LiteralValue::Bool(input.required))),
+            ];
+            if input.is_signal {
+                values.push(quoted_entry("isSignal", literal(LiteralValue::Bool(true))));
+            }
+            quoted_entry(key, literal_map_expr(values))
+        })
+        .collect();
+    literal_map_expr(entries)
+}
+
+fn create_host_directives_type(meta: &R3DirectiveMetadata) -> Type {
+    let Some(host_directives) = meta.host_directives.as_ref().filter(|d| !d.is_empty()) else {
+        return crate::output::output_ast::none_type();
+    };
+
+    let entries = host_directives
+        .iter()
+        .map(|host| {
+            let sorted = |map: &Option<HashMap<String, String>>| {
+                let mut entries: Vec<(&String, &String)> = map.iter().flatten().collect();
+                entries.sort();
+                string_map_as_literal_expression(entries)
+            };
+            literal_map_expr(vec![
+                LiteralMapEntry {
+                    key: "directive".to_string(),
+                    value: Box::new(Expression::TypeOf(crate::output::output_ast::TypeofExpr {
+                        expr: Box::new(host.directive.type_expr.clone()),
+                        type_: None,
+                        source_span: None,
+                    })),
+                    quoted: false,
+                },
+                LiteralMapEntry {
+                    key: "inputs".to_string(),
+                    value: Box::new(sorted(&host.inputs)),
+                    quoted: false,
+                },
+                LiteralMapEntry {
+                    key: "outputs".to_string(),
+                    value: Box::new(sorted(&host.outputs)),
+                    quoted: false,
+                },
+            ])
+        })
+        .collect();
+    expression_type(Expression::LiteralArray(LiteralArrayExpr {
+        entries,
+        type_: None,
+        source_span: None,
+    }))
+}
+
+fn string_map_as_literal_expression<'a>(
+    map: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> Expression {
+    literal_map_expr(
+        map.into_iter()
+            .map(|(key, value)| quoted_entry(key, literal(LiteralValue::String(value.clone()))))
+            .collect(),
+    )
+}
+
+fn string_array_as_type(values: &[String]) -> Type {
+    if values.is_empty() {
+        return crate::output::output_ast::none_type();
+    }
+    expression_type(Expression::LiteralArray(LiteralArrayExpr {
+        entries: values
+            .iter()
+            .map(|value| literal(LiteralValue::String(value.clone())))
+            .collect(),
+        type_: None,
+        source_span: None,
+    }))
+}
+
+fn quoted_entry(key: &str, value: Expression) -> LiteralMapEntry {
+    LiteralMapEntry {
+        key: key.to_string(),
+        value: Box::new(value),
+        quoted: true,
+    }
+}
+
+fn literal_map_expr(entries: Vec<LiteralMapEntry>) -> Expression {
+    Expression::LiteralMap(LiteralMapExpr {
+        entries,
+        type_: None,
+        source_span: None,
+    })
+}
+
+fn expression_type(value: Expression) -> Type {
+    Type::Expression(crate::output::output_ast::ExpressionType {
+        value: Box::new(value),
+        modifiers: crate::output::output_ast::TypeModifier::None,
+        type_params: None,
+    })
+}
+
+fn declaration_type(
+    declaration: crate::output::output_ast::ExternalReference,
+    type_params: Vec<Type>,
+) -> Type {
+    Type::Expression(crate::output::output_ast::ExpressionType {
+        value: Box::new(external_expr(declaration)),
+        modifiers: crate::output::output_ast::TypeModifier::None,
+        type_params: Some(type_params),
+    })
+}
+
 fn compile_declaration_list(list: Expression, mode: DeclarationListEmitMode) -> Expression {
     match mode {
         DeclarationListEmitMode::Direct => list,
@@ -756,7 +926,8 @@ pub fn compile_defer_resolver_function(meta: &R3DeferResolverFunctionMetadata) -
 
                     if let Some(ref import_path) = dep.import_path {
                         let dynamic_import = Expression::DynamicImport(DynamicImportExpr {
-                            url: import_path.clone(),
+                            url: DynamicImportUrl::Literal(import_path.clone()),
+                            url_comment: None,
                             source_span: None,
                         });
                         let then_call = Expression::InvokeFn(InvokeFunctionExpr {
@@ -806,7 +977,8 @@ pub fn compile_defer_resolver_function(meta: &R3DeferResolverFunctionMetadata) -
                 });
 
                 let dynamic_import = Expression::DynamicImport(DynamicImportExpr {
-                    url: dep.import_path.clone(),
+                    url: DynamicImportUrl::Literal(dep.import_path.clone()),
+                    url_comment: None,
                     source_span: None,
                 });
                 let then_call = Expression::InvokeFn(InvokeFunctionExpr {
@@ -114,6 +114,8 @@ fn should_emit_inherit_definition_feature_and_host_attrs() {
             ChangeDetectionStrategy::OnPush,
         )),
         relative_template_path: None,
+        enable_debug_locations: false,
+        template_start: None,
         has_directive_dependencies: false,
         raw_imports: None,
     };
@@ -477,6 +479,8 @@ fn should_compile_host_bindings_for_mat_divider() {
         relative_context_file_path: "test.ts".to_string(),
         i18n_use_external_ids: false,
         relative_template_path: None,
+        enable_debug_locations: false,
+        template_start: None,
         has_directive_dependencies: false,
         raw_imports: None,
     };
@@ -7,7 +7,9 @@ use std::collections::HashMap;
 
 use crate::i18n::digest::{compute_decimal_digest, compute_digest, decimal_digest};
 use crate::i18n::i18n_ast as i18n;
+use crate::i18n::i18n_parser::create_i18n_message_factory;
 use crate::ml_parser::ast as html;
+use crate::ml_parser::defaults::DEFAULT_CONTAINER_BLOCKS;
 use crate::ml_parser::parser::ParseTreeResult;
 /// JSDoc tag name
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,6 +38,7 @@ impl JSDocComment {
     }
 }
 use crate::parse_util::ParseError;
+use crate::schema::trusted_types_sinks::is_trusted_types_sink;
 
 use super::util::{has_i18n_attrs, I18N_ATTR, I18N_ATTR_PREFIX};
 
@@ -50,8 +53,10 @@ pub struct I18nMeta {
 }
 
 /// i18n separators for metadata
-const I18N_MEANING_SEPARATOR: char = '|';
-const I18N_ID_SEPARATOR: &str = "@@";
+pub(crate) const I18N_MEANING_SEPARATOR: char = '|';
+pub(crate) const I18N_ID_SEPARATOR: &str = "@@";
+/// Prefix of each legacy id in a `$localize` metadata block.
+pub(crate) const I18N_LEGACY_ID_INDICATOR: char = '\u{241F}';
 
 /// This visitor walks over HTML parse tree and converts information stored in
 /// i18n-related attributes ("i18n" and "i18n-*") into i18n meta objects.
@@ -118,33 +123,81 @@ impl I18nMetaVisitor {
     fn visit_element_like(&mut self, node: &mut html::Element) {
         if has_i18n_attrs(node) {
             self.has_i18n_meta = true;
-            let mut attrs: Vec<html::Attribute> = vec![];
             let mut attrs_meta: HashMap<String, String> = HashMap::new();
 
             for attr in &node.attrs {
                 if attr.name.as_ref() == I18N_ATTR {
                     // Root 'i18n' node attribute
-                    // TODO: Generate i18n message
+                    let previous_message = match &node.i18n {
+                        Some(i18n::I18nMeta::Message(message)) => Some(message.clone()),
+                        _ => None,
+                    };
+                    let mut message =
+                        self._generate_i18n_message(&node.children, attr.value.as_ref());
+                    if let Some(previous) = previous_message {
+                        // Keep the metadata computed by the first pass, see `set_i18n_refs`
+                        reuse_message_meta(&mut message, &previous);
+                    }
+                    if message.nodes.is_empty() {
+                        // Ignore the message if it is empty.
+                        node.i18n = None;
+                    } else {
+                        set_i18n_refs(&mut node.children, &message.nodes);
+                        node.i18n = Some(i18n::I18nMeta::Message(message));
+                    }
                 } else if attr.name.starts_with(I18N_ATTR_PREFIX) {
                     // 'i18n-*' attributes
                     let name = attr.name[I18N_ATTR_PREFIX.len()..].to_string();
-                    attrs_meta.insert(name, attr.value.to_string());
-                } else {
-                    // non-i18n attributes
-                    attrs.push(attr.clone());
+                    if is_trusted_types_sink(&node.name, &name) {
+                        self.errors.push(ParseError::new(
+                            attr.source_span.clone(),
+                            format!(
+                                "Translating attribute '{}' is disallowed for security reasons.",
+                                name
+                            ),
+                        ));
+                    } else {
+                        attrs_meta.insert(name, attr.value.to_string());
+                    }
+                }
+            }
+
+            if !attrs_meta.is_empty() {
+                for attr in node.attrs.iter_mut() {
+                    // Do not create translations for empty attributes
+                    let Some(meta) = attrs_meta.get(attr.name.as_ref()) else {
+                        continue;
+                    };
+                    if attr.value.is_empty() {
+                        continue;
+                    }
+                    let message =
+                        self._generate_i18n_message(&[html::Node::Attribute(attr.clone())], meta);
+                    attr.i18n = Some(i18n::I18nMeta::Message(message));
                 }
             }
 
             if !self.keep_i18n_attrs {
-                node.attrs = attrs;
+                node.attrs.retain(|attr| {
+                    attr.name.as_ref() != I18N_ATTR && !attr.name.starts_with(I18N_ATTR_PREFIX)
+                });
             }
         }
 
-        // Visit children
+        // Visit children, ICUs directly inside a translated element share its message metadata
+        let message = match &node.i18n {
+            Some(i18n::I18nMeta::Message(message)) => Some(message.clone()),
+            _ => None,
+        };
         let children: Vec<html::Node> = node
             .children
             .drain(..)
-            .map(|child| self.visit_node(child))
+            .map(|child| match child {
+                html::Node::Expansion(expansion) => {
+                    self.visit_expansion(expansion, message.as_ref())
+                }
+                child => self.visit_node(child),
+            })
             .collect();
         node.children = children;
     }
@@ -159,11 +212,37 @@ impl I18nMetaVisitor {
 
     fn visit_expansion(
         &mut self,
-        expansion: html::Expansion,
-        _current_message: Option<&i18n::Message>,
+        mut expansion: html::Expansion,
+        current_message: Option<&i18n::Message>,
     ) -> html::Node {
         self.has_i18n_meta = true;
-        // TODO: Generate i18n message for expansion
+        let nodes = [html::Node::Expansion(expansion.clone())];
+        let message = match expansion.i18n.take() {
+            Some(i18n::I18nMeta::Node(i18n::Node::IcuPlaceholder(placeholder))) => {
+                // The ICU is part of an enclosing message: name its own message after the
+                // placeholder so that the enclosing message can refer to it.
+                let mut message = self._generate_i18n_message(&nodes, "");
+                if let Some(previous) = placeholder.previous_message {
+                    reuse_message_meta(&mut message, &previous);
+                }
+                if let Some(i18n::Node::Icu(icu)) = message.nodes.first_mut() {
+                    icu.name = Some(placeholder.name);
+                }
+                message
+            }
+            meta => {
+                let mut message = self._generate_i18n_message(&nodes, "");
+                let previous = match meta {
+                    Some(i18n::I18nMeta::Message(previous)) => Some(previous),
+                    _ => None,
+                };
+                if let Some(previous) = current_message.or(previous.as_ref()) {
+                    reuse_message_meta(&mut message, previous);
+                }
+                message
+            }
+        };
+        expansion.i18n = Some(i18n::I18nMeta::Message(message));
         html::Node::Expansion(expansion)
     }
 
@@ -189,6 +268,32 @@ impl I18nMetaVisitor {
         decl
     }
 
+    /// Generates an i18n message for the given nodes, using the `i18n` metadata string (e.g.
+    /// `meaning|description@@id`) that was attached to them.
+    fn _generate_i18n_message(&mut self, nodes: &[html::Node], meta: &str) -> i18n::Message {
+        let parsed = self._parse_metadata(meta);
+        let container_blocks = DEFAULT_CONTAINER_BLOCKS
+            .iter()
+            .map(|block| block.to_string())
+            .collect();
+        let mut factory = create_i18n_message_factory(
+            container_blocks,
+            self.retain_empty_tokens,
+            !self.preserve_significant_whitespace,
+            false,
+        );
+        let mut message = factory.create_message(
+            nodes,
+            parsed.meaning.as_deref(),
+            parsed.description.as_deref(),
+            parsed.custom_id.as_deref(),
+            None,
+        );
+        self._set_message_id(&mut message, &parsed);
+        self._set_legacy_ids(&mut message, &parsed);
+        message
+    }
+
     fn _parse_metadata(&self, meta: &str) -> I18nMeta {
         parse_i18n_meta(meta)
     }
@@ -212,6 +317,70 @@ impl I18nMetaVisitor {
     }
 }
 
+/// Copies the metadata of a message generated by a previous pass (or of the enclosing message)
+/// so that the ids stay stable once the message nodes are regenerated.
+fn reuse_message_meta(message: &mut i18n::Message, previous: &i18n::Message) {
+    message.id = previous.id.clone();
+    message.custom_id = previous.custom_id.clone();
+    message.legacy_ids = previous.legacy_ids.clone();
+    message.meaning = previous.meaning.clone();
+    message.description = previous.description.clone();
+}
+
+/// Attaches the i18n nodes of a message to the html nodes they were created from, so that later
+/// passes can find the placeholders of each element, text and ICU of a translated block.
+///
+/// Nodes are matched by source span; nested ICUs keep the placeholder and get their own message
+/// when the expansion itself is visited.
+fn set_i18n_refs(nodes: &mut [html::Node], message_nodes: &[i18n::Node]) {
+    let mut refs: HashMap<(usize, usize), i18n::Node> = HashMap::new();
+    collect_i18n_refs(message_nodes, &mut refs);
+    apply_i18n_refs(nodes, &refs);
+}
+
+fn span_key(span: &crate::parse_util::ParseSourceSpan) -> (usize, usize) {
+    (span.start.offset, span.end.offset)
+}
+
+fn collect_i18n_refs(nodes: &[i18n::Node], refs: &mut HashMap<(usize, usize), i18n::Node>) {
+    for node in nodes {
+        refs.entry(span_key(node.source_span()))
+            .or_insert_with(|| node.clone());
+        match node {
+            i18n::Node::Container(container) => collect_i18n_refs(&container.children, refs),
+            i18n::Node::TagPlaceholder(placeholder) => {
+                collect_i18n_refs(&placeholder.children, refs)
+            }
+            i18n::Node::BlockPlaceholder(placeholder) => {
+                collect_i18n_refs(&placeholder.children, refs)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn apply_i18n_refs(nodes: &mut [html::Node], refs: &HashMap<(usize, usize), i18n::Node>) {
+    for node in nodes.iter_mut() {
+        if let Some(i18n_ref) = refs.get(&span_key(&get_node_source_span(node))) {
+            let i18n_ref = Some(i18n::I18nMeta::Node(i18n_ref.clone()));
+            match node {
+                html::Node::Text(text) => text.i18n = i18n_ref,
+                html::Node::Expansion(expansion) => expansion.i18n = i18n_ref,
+                html::Node::Element(element) => element.i18n = i18n_ref,
+                html::Node::Component(component) => component.i18n = i18n_ref,
+                html::Node::Block(block) => block.i18n = i18n_ref,
+                _ => {}
+            }
+        }
+        match node {
+            html::Node::Element(element) => apply_i18n_refs(&mut element.children, refs),
+            html::Node::Component(component) => apply_i18n_refs(&mut component.children, refs),
+            html::Node::Block(block) => apply_i18n_refs(&mut block.children, refs),
+            _ => {}
+        }
+    }
+}
+
 /// Helper to get source_span from an html::Node enum
 fn get_node_source_span(node: &html::Node) -> crate::parse_util::ParseSourceSpan {
     match node {
@@ -107,6 +107,16 @@ pub fn find_matching_directives_and_pipes(
     directive_selectors: &[String],
 ) -> MatchingDirectivesAndPipes {
     use super::template::parse_template;
+
+    let parsed_template = parse_template(template, "", Default::default());
+    find_matching_directives_and_pipes_in_nodes(parsed_template.nodes, directive_selectors)
+}
+
+/// Find matching directives and pipes in already parsed template nodes.
+pub fn find_matching_directives_and_pipes_in_nodes(
+    nodes: Vec<t::R3Node>,
+    directive_selectors: &[String],
+) -> MatchingDirectivesAndPipes {
     use crate::directive_matching::CssSelector;
 
     // Create a SelectorMatcher and add fake directives for each selector
@@ -125,13 +135,10 @@ pub fn find_matching_directives_and_pipes(
         }
     }
 
-    // Parse the template
-    let parsed_template = parse_template(template, "", Default::default());
-
     // Create binder with matcher
     let binder = R3TargetBinder::new(Some(DirectiveMatcher::Selector(matcher)));
     let target = Target {
-        template: Some(parsed_template.nodes),
+        template: Some(nodes),
         host: None,
     };
 
@@ -1728,13 +1735,43 @@ impl<DirectiveT: DirectiveMeta + Clone + 'static> BoundTarget<DirectiveT>
     }
 
     fn get_reference_target(&self, reference: &Reference) -> Option<ReferenceTarget<DirectiveT>> {
-        // TODO: Implement full reference resolution
-        // For now, try to find element with matching name
-        if let Some(ref template_nodes) = self.target.template {
-            find_reference_target_in_nodes(template_nodes, reference)
-        } else {
-            None
+        let template_nodes = self.target.template.as_ref()?;
+        let node_target = find_reference_target_in_nodes(template_nodes, reference)?;
+        let owner = match &node_target {
+            ReferenceTarget::Element(el) => DirectiveOwner::Element(el.clone()),
+            ReferenceTarget::Template(tmpl) => DirectiveOwner::Template(tmpl.clone()),
+            ReferenceTarget::DirectiveOnNode { .. } => return Some(node_target),
+        };
+        let directives = self
+            .directives_map
+            .get(&DirectiveOwnerWrapper::from(&owner))
+            .map(|dirs| dirs.as_slice())
+            .unwrap_or(&[]);
+
+        // A reference without a value points to the component on the node, if any,
+        // and otherwise to the node itself.
+        let value = reference.value.trim();
+        if value.is_empty() {
+            return match directives.iter().find(|dir| dir.is_component()) {
+                Some(component) => Some(ReferenceTarget::DirectiveOnNode {
+                    directive: component.clone(),
+                    node: owner,
+                }),
+                None => Some(node_target),
+            };
         }
+
+        // Otherwise the value names a directive through one of its `exportAs` names.
+        directives
+            .iter()
+            .find(|dir| {
+                dir.export_as()
+                    .is_some_and(|names| names.iter().any(|name| name == value))
+            })
+            .map(|dir| ReferenceTarget::DirectiveOnNode {
+                directive: dir.clone(),
+                node: owner,
+            })
     }
 
     fn get_consumer_of_binding(
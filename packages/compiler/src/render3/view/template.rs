@@ -10,6 +10,7 @@ use crate::ml_parser::lexer::{LexerRange, TokenizeOptions};
 use crate::parse_util::ParseError;
 use crate::render3::r3_ast as t;
 use crate::render3::r3_template_transform::{html_ast_to_render3_ast, Render3ParseOptions};
+use crate::render3::view::i18n::meta::I18nMetaVisitor;
 use crate::schema::dom_element_schema_registry::DomElementSchemaRegistry;
 use crate::template_parser::binding_parser::BindingParser;
 
@@ -108,17 +109,47 @@ pub fn parse_template(
 
     let parse_result = html_parser.parse(template, template_url, Some(tokenize_options));
 
-    // Process i18n metadata (simplified for now)
-    let mut html_nodes = parse_result.root_nodes;
+    let mut errors = parse_result.errors;
+    let preserve_whitespaces = options.preserve_whitespaces == Some(true);
+    let preserve_significant_whitespace = options.preserve_significant_whitespace.unwrap_or(true);
+
+    // Process i18n meta information (scan attributes, generate ids) before whitespace removal,
+    // because the extraction process relies on the raw content to generate message ids.
+    let mut i18n_meta_visitor = I18nMetaVisitor::new(
+        !preserve_whitespaces,
+        options
+            .enable_i18n_legacy_message_id_format
+            .unwrap_or(false),
+        preserve_significant_whitespace,
+        !preserve_significant_whitespace,
+    );
+    let i18n_meta_result = i18n_meta_visitor.visit_all_with_errors(parse_result.root_nodes);
+    errors.extend(i18n_meta_result.errors);
+    let mut html_nodes = i18n_meta_result.root_nodes;
 
     // Handle whitespace preservation
-    if options.preserve_whitespaces != Some(true) {
+    if !preserve_whitespaces {
         let mut visitor = WhitespaceVisitor::new(
-            options.preserve_significant_whitespace.unwrap_or(true), // Default to true based on view_util
+            preserve_significant_whitespace, // Default to true based on view_util
             None,
             false,
         );
         html_nodes = visit_all_with_siblings_nodes(&mut visitor, &html_nodes);
+
+        // Run the i18n meta visitor again, as removed whitespace might affect the generated
+        // messages. This pass also drops the `i18n` attributes kept by the first one.
+        if i18n_meta_visitor.has_i18n_meta {
+            html_nodes = I18nMetaVisitor::new(
+                false,
+                options
+                    .enable_i18n_legacy_message_id_format
+                    .unwrap_or(false),
+                preserve_significant_whitespace,
+                !preserve_significant_whitespace,
+            )
+            .visit_all_with_errors(html_nodes)
+            .root_nodes;
+        }
     }
 
     // Create binding parser
@@ -129,11 +160,11 @@ pub fn parse_template(
     let collect_comment_nodes = options.collect_comment_nodes.unwrap_or(false);
     let r3_options = Render3ParseOptions {
         collect_comment_nodes,
+        preserve_whitespaces: options.preserve_whitespaces == Some(true),
     };
 
     let r3_result = html_ast_to_render3_ast(&html_nodes, &mut binding_parser, &r3_options);
 
-    let mut errors = parse_result.errors;
     errors.extend(r3_result.errors);
 
     ParsedTemplate {
@@ -450,6 +450,17 @@ impl DomElementSchemaRegistry {
         }
     }
 
+    /// Get the schema type (`boolean`, `number`, `object` or `string`) of a DOM property of an
+    /// element, or `None` if the element does not have the property.
+    pub fn property_type(&self, tag_name: &str, prop_name: &str) -> Option<&str> {
+        let prop_name = ATTR_TO_PROP.get(prop_name).copied().unwrap_or(prop_name);
+        self.schema
+            .get(&tag_name.to_lowercase())
+            .or_else(|| self.schema.get("unknown"))
+            .and_then(|props| props.get(prop_name))
+            .map(|type_| type_.as_str())
+    }
+
     /// Get all known events of an element
     pub fn all_known_events_of_element(&self, tag_name: &str) -> Vec<String> {
         self.event_schema
@@ -724,6 +735,16 @@ mod tests {
         assert!(registry.has_property("div", "id", &[]));
     }
 
+    #[test]
+    fn test_property_type() {
+        let registry = DomElementSchemaRegistry::new();
+        assert_eq!(registry.property_type("button", "disabled"), Some(BOOLEAN));
+        assert_eq!(registry.property_type("input", "readonly"), Some(BOOLEAN));
+        assert_eq!(registry.property_type("div", "hidden"), Some(BOOLEAN));
+        assert_eq!(registry.property_type("div", "title"), Some(STRING));
+        assert_eq!(registry.property_type("div", "unknownProp"), None);
+    }
+
     #[test]
     fn test_get_mapped_prop_name() {
         let registry = DomElementSchemaRegistry::new();
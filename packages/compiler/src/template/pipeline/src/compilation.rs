@@ -6,8 +6,8 @@
 use crate::constant_pool::ConstantPool;
 use crate::core::ChangeDetectionStrategy;
 use crate::directive_matching::{CssSelector, SelectorMatcher};
-use crate::output::output_ast::Expression;
-use crate::parse_util::ParseError;
+use crate::output::output_ast::{Expression, Statement};
+use crate::parse_util::{ParseError, ParseLocation};
 use crate::render3::view::api::{R3ComponentDeferMetadata, R3TemplateDependencyMetadata};
 use crate::schema::dom_element_schema_registry::DomElementSchemaRegistry;
 use crate::template::pipeline::ir;
@@ -94,13 +94,15 @@ pub struct ComponentCompilationJob {
     pub all_deferrable_deps_fn: Option<Expression>,
     pub relative_template_path: Option<String>,
     pub enable_debug_locations: bool,
+    /// Where the template starts in the file at `relative_template_path`.
+    pub template_start: Option<ParseLocation>,
     pub change_detection: Option<ChangeDetectionStrategy>,
 
     pub root: ViewCompilationUnit,
     pub views: indexmap::IndexMap<ir::XrefId, ViewCompilationUnit>,
     pub content_selectors: Option<Expression>,
     pub consts: Vec<Expression>,
-    pub consts_initializers: Vec<Expression>,
+    pub consts_initializers: Vec<Statement>,
 
     pub available_dependencies: Vec<R3TemplateDependencyMetadata>,
     pub used_dependencies: HashSet<usize>,
@@ -152,6 +154,7 @@ impl ComponentCompilationJob {
             all_deferrable_deps_fn,
             relative_template_path,
             enable_debug_locations,
+            template_start: None,
             change_detection,
             root,
             views,
@@ -197,7 +200,7 @@ impl ComponentCompilationJob {
     pub fn add_const(
         &mut self,
         new_const: Expression,
-        initializers: Option<Vec<Expression>>,
+        initializers: Option<Vec<Statement>>,
     ) -> ir::ConstIndex {
         // Check for equivalent constants
         for (idx, existing) in self.consts.iter().enumerate() {
@@ -0,0 +1,689 @@
+#[cfg(test)]
+mod tests {
+    use crate::constant_pool::ConstantPool;
+    use crate::core::ViewEncapsulation;
+    use crate::expression_parser::parser::Parser;
+    use crate::output::abstract_emitter::EmitterVisitorContext;
+    use crate::output::abstract_js_emitter::AbstractJsEmitterVisitor;
+    use crate::output::output_ast::{self as o, ExpressionTrait};
+    use crate::parse_util::{ParseError, ParseLocation, ParseSourceFile, ParseSourceSpan};
+    use crate::render3::util::R3Reference;
+    use crate::render3::view::api::{
+        DeclarationListEmitMode, R3ComponentDeferMetadata, R3ComponentMetadata,
+        R3ComponentTemplate, R3DirectiveMetadata, R3HostMetadata, R3LifecycleMetadata,
+    };
+    use crate::render3::view::compiler::compile_component_with_diagnostics;
+    use crate::render3::view::template::{parse_template, ParseTemplateOptions};
+    use crate::schema::dom_element_schema_registry::DomElementSchemaRegistry;
+    use crate::template_parser::binding_parser::BindingParser;
+    use indexmap::IndexMap;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    /// A compiled component, emitted as JavaScript.
+    struct Compiled {
+        /// The `ɵɵdefineComponent` call.
+        initializer: String,
+        /// The pooled constants followed by the template functions.
+        statements: Vec<String>,
+        diagnostics: Vec<ParseError>,
+    }
+
+    fn compile(name: &str, template: &str) -> Compiled {
+        compile_with_host(name, template, R3HostMetadata::default())
+    }
+
+    /// Compile a standalone component with the given template and host bindings.
+    fn compile_with_host(name: &str, template: &str, host: R3HostMetadata) -> Compiled {
+        let parsed = parse_template(
+            template,
+            "inline-template.html",
+            ParseTemplateOptions {
+                preserve_whitespaces: Some(false),
+                ..Default::default()
+            },
+        );
+        let source_file = Arc::new(ParseSourceFile::new(String::new(), String::new()));
+        let location = ParseLocation::new(source_file, 0, 0, 0);
+
+        let parser = Parser::new();
+        let schema_registry = DomElementSchemaRegistry::new();
+        let mut binding_parser = BindingParser::new(&parser, &schema_registry, vec![]);
+
+        let meta = R3ComponentMetadata {
+            directive: R3DirectiveMetadata {
+                name: name.to_string(),
+                type_: R3Reference {
+                    value: *o::variable(name),
+                    type_expr: *o::variable(name),
+                },
+                type_argument_count: 0,
+                type_source_span: ParseSourceSpan::new(location.clone(), location),
+                deps: None,
+                selector: Some(name.to_lowercase()),
+                queries: vec![],
+                view_queries: vec![],
+                host,
+                lifecycle: R3LifecycleMetadata::default(),
+                inputs: IndexMap::new(),
+                outputs: IndexMap::new(),
+                uses_inheritance: false,
+                export_as: None,
+                providers: None,
+                is_standalone: true,
+                is_signal: false,
+                host_directives: None,
+            },
+            template: R3ComponentTemplate {
+                nodes: parsed.nodes,
+                ng_content_selectors: parsed.ng_content_selectors,
+                preserve_whitespaces: false,
+            },
+            declarations: vec![],
+            defer: R3ComponentDeferMetadata::PerComponent {
+                dependencies_fn: None,
+            },
+            declaration_list_emit_mode: DeclarationListEmitMode::Direct,
+            styles: vec![],
+            external_styles: None,
+            encapsulation: ViewEncapsulation::Emulated,
+            animations: None,
+            view_providers: None,
+            relative_context_file_path: String::new(),
+            i18n_use_external_ids: false,
+            change_detection: None,
+            relative_template_path: None,
+            enable_debug_locations: false,
+            template_start: None,
+            has_directive_dependencies: false,
+            raw_imports: None,
+        };
+
+        let mut constant_pool = ConstantPool::new(false);
+        let (compiled, pipeline_diagnostics) =
+            compile_component_with_diagnostics(&meta, &mut constant_pool, &mut binding_parser);
+
+        let mut emitter = AbstractJsEmitterVisitor::new();
+        let mut ctx = EmitterVisitorContext::create_root();
+        compiled.expression.visit_expression(&mut emitter, &mut ctx);
+        let initializer = ctx.to_source();
+
+        // Template functions are also declared in the pool; emit each of them once.
+        let pooled: HashSet<&str> = constant_pool
+            .statements
+            .iter()
+            .filter_map(declared_name)
+            .collect();
+        let statements = constant_pool
+            .statements
+            .iter()
+            .chain(
+                compiled
+                    .statements
+                    .iter()
+                    .filter(|stmt| declared_name(stmt).is_none_or(|name| !pooled.contains(name))),
+            )
+            .map(|stmt| {
+                let mut ctx = EmitterVisitorContext::create_root();
+                stmt.visit_statement(&mut emitter, &mut ctx);
+                ctx.to_source()
+            })
+            .collect();
+
+        let mut diagnostics = parsed.errors.unwrap_or_default();
+        diagnostics.extend(pipeline_diagnostics);
+        Compiled {
+            initializer,
+            statements,
+            diagnostics,
+        }
+    }
+
+    fn declared_name(stmt: &o::Statement) -> Option<&str> {
+        match stmt {
+            o::Statement::DeclareVar(decl) => Some(&decl.name),
+            o::Statement::DeclareFn(decl) => Some(&decl.name),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_share_pure_function_for_identical_literals() {
+        let result = compile(
+            "StyleCmp",
+            r#"<div [ngStyle]="{color: c}"></div><span [ngStyle]="{color: c}"></span><p [ngStyle]="{color: c, width: w}"></p>"#,
+        );
+
+        // Both `{color: c}` literals use one definition, each with its own memoization slot.
+        assert_eq!(
+            result.statements,
+            vec![
+                "var _c0 = (a0) => ({color: a0});\n",
+                "var _c1 = (a0, a1) => ({color: a0, width: a1});\n",
+            ]
+        );
+        let initializer = result.initializer;
+        assert!(initializer.contains(r#"ɵɵproperty("ngStyle", i0.ɵɵpureFunction1(3, _c0, ctx.c))"#));
+        assert!(initializer.contains(r#"ɵɵproperty("ngStyle", i0.ɵɵpureFunction1(5, _c0, ctx.c))"#));
+        assert!(initializer.contains(r#"i0.ɵɵpureFunction2(7, _c1, ctx.c, ctx.w)"#));
+    }
+
+    #[test]
+    fn test_compile_defer_triggers_and_timings() {
+        let result = compile(
+            "DeferCmp",
+            r#"<button #trigger>go</button> @defer (on timer(500ms), hover(trigger); when ready; prefetch on idle) { <b>main</b> } @loading (after 100ms; minimum 1s) { <i>loading</i> } @placeholder (minimum 500ms) { <p>wait</p> } @error { err }"#,
+        );
+        assert!(result.diagnostics.is_empty());
+
+        let initializer = result.initializer;
+        assert!(initializer.contains(r#"consts: [["trigger", ""], [1000, 100], [500]]"#));
+        assert!(initializer.contains(
+            "i0.ɵɵtemplate(3, DeferCmp_Defer_3_Template, 2, 0)(4, DeferCmp_DeferLoading_4_Template, 2, 0)(5, DeferCmp_DeferPlaceholder_5_Template, 2, 0)(6, DeferCmp_DeferError_6_Template, 1, 0)"
+        ));
+        // Without dependencies to load there is no dependency function.
+        assert!(initializer
+            .contains("i0.ɵɵdefer(7, 3, null, 4, 5, 6, 1, 2, i0.ɵɵdeferEnableTimerScheduling)"));
+        assert!(initializer.contains("i0.ɵɵdeferOnTimer(500)"));
+        assert!(initializer.contains("i0.ɵɵdeferOnHover(0)"));
+        assert!(initializer.contains("i0.ɵɵdeferPrefetchOnIdle()"));
+        assert!(initializer.contains("i0.ɵɵdeferWhen(ctx.ready)"));
+    }
+
+    #[test]
+    fn test_compile_switch_with_default() {
+        let result = compile(
+            "SwitchCmp",
+            r#"@switch (mode) { @case ('a') { <span>A</span> } @case ('b') { <b>B</b> } @default { <i>D</i> } }"#,
+        );
+        assert!(result.diagnostics.is_empty());
+
+        let initializer = result.initializer;
+        assert!(initializer.contains(
+            r#"i0.ɵɵconditionalCreate(0, SwitchCmp_Case_0_Template, 2, 0, "span")(1, SwitchCmp_Case_1_Template, 2, 0, "b")(2, SwitchCmp_Case_2_Template, 2, 0, "i")"#
+        ));
+        // A matching case selects its own slot; anything else falls through to `@default`.
+        assert!(initializer.contains(
+            r#"i0.ɵɵconditional(((tmp_0_0 = ctx.mode) === "a" ? 0 : (tmp_0_0 === "b" ? 1 : 2)))"#
+        ));
+    }
+
+    #[test]
+    fn test_compile_switch_without_default() {
+        let result = compile("SwitchCmp", r#"@switch (mode) { @case (1) { one } }"#);
+        let initializer = result.initializer;
+        // Nothing is rendered when no case matches.
+        assert!(initializer.contains(r#"i0.ɵɵconditional(((tmp_0_0 = ctx.mode) === 1 ? 0 : -1))"#));
+    }
+
+    #[test]
+    fn test_compile_let_used_by_later_siblings() {
+        let template = r#"@let name = 'x'; <span>{{name}}</span><p>{{name}}</p>"#;
+        let result = compile("LetCmp", template);
+        assert!(result.diagnostics.is_empty());
+
+        let initializer = result.initializer;
+        // The @let is only read in its own view, so no declareLet slot is needed.
+        assert!(initializer.contains("decls: 4"));
+        assert!(!initializer.contains("declareLet"));
+        assert!(initializer.contains(r#"const name_r1 = "x";"#));
+        assert_eq!(initializer.matches("textInterpolate(name_r1)").count(), 2);
+    }
+
+    #[test]
+    fn test_coalesce_namespace_changes() {
+        let result = compile(
+            "SvgCmp",
+            "<svg><g><rect/><circle/></g></svg><svg></svg><div></div>",
+        );
+        let initializer = result.initializer;
+
+        assert_eq!(initializer.matches("namespaceSVG").count(), 1);
+        assert_eq!(initializer.matches("namespaceHTML").count(), 1);
+        let svg = initializer.find("namespaceSVG").unwrap();
+        let html = initializer.find("namespaceHTML").unwrap();
+        assert!(svg < initializer.find(r#""svg""#).unwrap());
+        assert!(html > initializer.rfind(r#""svg""#).unwrap());
+        assert!(html < initializer.find(r#""div""#).unwrap());
+    }
+
+    #[test]
+    fn test_text_interpolation_arity() {
+        let cases = [
+            ("<p>{{a}}</p>", "i0.ɵɵtextInterpolate(ctx.a);"),
+            (
+                "<p>{{a}}{{b}}</p>",
+                r#"i0.ɵɵtextInterpolate2("", ctx.a, "", ctx.b);"#,
+            ),
+            (
+                "<p>x{{a}}y{{b}}z</p>",
+                r#"i0.ɵɵtextInterpolate2("x", ctx.a, "y", ctx.b, "z");"#,
+            ),
+        ];
+        for (template, expected) in cases {
+            let result = compile("TextCmp", template);
+            let initializer = result.initializer;
+            assert!(
+                initializer.contains(expected),
+                "{} compiled to:\n{}",
+                template,
+                initializer
+            );
+            assert_eq!(initializer.matches("textInterpolate").count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_compile_multi_slot_projection() {
+        let result = compile(
+            "SlotsCmp",
+            r#"<ng-content select="[foo]"></ng-content><ng-content select="bar, .baz"></ng-content><ng-content></ng-content>"#,
+        );
+        let initializer = result.initializer;
+
+        assert_eq!(
+            result.statements,
+            vec![
+                "var _c0 = [[[\"\", \"foo\", \"\"]], [[\"bar\"], [\"\", 8, \"baz\"]], \"*\"];\n",
+                "var _c1 = [\"[foo]\", \"bar, .baz\", \"*\"];\n",
+            ]
+        );
+        assert!(initializer.contains("i0.ɵɵprojectionDef(_c0);"));
+        assert!(initializer.contains("i0.ɵɵprojection(0);"));
+        assert!(initializer.contains("i0.ɵɵprojection(1, 1);"));
+        assert!(initializer.contains("i0.ɵɵprojection(2, 2);"));
+        assert!(initializer.contains("ngContentSelectors: _c1"));
+    }
+
+    #[test]
+    fn test_compile_ng_project_as() {
+        let result = compile(
+            "ProjectAsCmp",
+            r#"<child><span ngProjectAs="[foo]">x</span><bar></bar></child>"#,
+        );
+        let initializer = result.initializer;
+
+        assert!(initializer.contains(r#"consts: [["ngProjectAs", "[foo]", 5, ["", "foo", ""]]]"#));
+        assert!(initializer.contains(r#"i0.ɵɵelementStart(0, "child")(1, "span", 0);"#));
+    }
+
+    #[test]
+    fn test_compile_animation_trigger_bindings() {
+        let result = compile(
+            "AnimCmp",
+            r#"<div [@myTrigger]="state" (@myTrigger.done)="onDone($event)" [@.disabled]="off"></div>"#,
+        );
+        let initializer = result.initializer;
+
+        assert!(initializer.contains(
+            r#"i0.ɵɵlistener("@myTrigger.done", function AnimCmp_Template_div_animation_myTrigger_done_0_listener($event) {"#
+        ));
+        assert!(initializer
+            .contains(r#"i0.ɵɵproperty("@myTrigger", ctx.state)("@.disabled", ctx.off);"#));
+        // Animation bindings are not matchable attributes, so nothing is extracted into consts.
+        assert!(initializer.contains("consts: []"));
+    }
+
+    #[test]
+    fn test_compile_boolean_property_and_attribute_bindings() {
+        let result = compile(
+            "BoolCmp",
+            r#"<button [disabled]="off"></button><button [attr.disabled]="off"></button><input [attr.readonly]="false" [attr.title]="false">"#,
+        );
+        let initializer = result.initializer;
+
+        assert!(initializer.contains(r#"i0.ɵɵproperty("disabled", ctx.off);"#));
+        assert!(initializer.contains(r#"i0.ɵɵattribute("disabled", ctx.off);"#));
+        assert!(initializer.contains(r#"i0.ɵɵattribute("readonly", false)("title", false);"#));
+    }
+
+    #[test]
+    fn test_compile_style_unit_and_custom_property_bindings() {
+        let mut host = R3HostMetadata::default();
+        host.properties
+            .insert("style.height.%".to_string(), "h".to_string());
+        host.properties
+            .insert("style.--host-var".to_string(), "v".to_string());
+        let result = compile_with_host(
+            "StyleCmp",
+            r#"<div style="--static-var: 1px" [style.width.px]="w" [style.--my-var]="v"></div>"#,
+            host,
+        );
+        let initializer = result.initializer;
+
+        assert!(initializer.contains(r#"consts: [[2, "--static-var", "1px"]]"#));
+        assert!(initializer.contains(r#"i0.ɵɵstyleProp("width", ctx.w, "px")("--my-var", ctx.v);"#));
+        // Host bindings encode the unit in the property name, which is split off before emit.
+        assert!(initializer.contains(r#"("height", ctx.h, "%")"#));
+        assert!(initializer.contains(r#"("--host-var", ctx.v)"#));
+    }
+
+    #[test]
+    fn test_compile_class_bindings() {
+        let compile_template = |template: &str| compile("ClassCmp", template).initializer;
+
+        let class_map = compile_template(r#"<div [class]="obj"></div>"#);
+        assert!(class_map.contains("i0.ɵɵclassMap(ctx.obj);"));
+
+        let class_props = compile_template(r#"<div [class.x]="a" [class.y]="b"></div>"#);
+        assert!(class_props.contains(r#"i0.ɵɵclassProp("x", ctx.a)("y", ctx.b);"#));
+
+        let static_and_dynamic = compile_template(r#"<div class="a" [class.b]="on"></div>"#);
+        assert!(static_and_dynamic.contains(r#"consts: [[1, "a"]]"#));
+        assert!(static_and_dynamic.contains(r#"i0.ɵɵelement(0, "div", 0);"#));
+        assert!(static_and_dynamic.contains(r#"i0.ɵɵclassProp("b", ctx.on);"#));
+    }
+
+    #[test]
+    fn test_compile_styling_bindings_in_runtime_order() {
+        let result = compile(
+            "StylingCmp",
+            r#"<div [title]="t" [class.b]="on" [class]="obj" [style.width.px]="w" [style]="s"></div><span [class.c]="off"></span>"#,
+        );
+        let initializer = result.initializer;
+
+        // Maps are applied before individual bindings, and styling before properties, while
+        // bindings of different elements are not reordered across each other.
+        assert!(initializer.contains(
+            "i0.ɵɵstyleMap(ctx.s);\n    i0.ɵɵclassMap(ctx.obj);\n    i0.ɵɵstyleProp(\"width\", ctx.w, \"px\");\n    i0.ɵɵclassProp(\"b\", ctx.on);\n    i0.ɵɵproperty(\"title\", ctx.t);\n    i0.ɵɵadvance();\n    i0.ɵɵclassProp(\"c\", ctx.off);"
+        ));
+    }
+
+    #[test]
+    fn test_compile_i18n_attribute() {
+        let result = compile(
+            "I18nCmp",
+            r#"<img i18n-alt="Logo description" alt="hello" src="a.png">"#,
+        );
+        let initializer = result.initializer;
+
+        // The translation is declared in a consts function and replaces the original value.
+        assert!(initializer.contains("consts: () => {"));
+        assert!(initializer.contains("const MSG__0 = goog.getMsg(\"hello\");"));
+        assert!(initializer.contains("i18n_0 = $localize `:Logo description:hello`;"));
+        assert!(initializer.contains(r#"return [["alt", i18n_0, "src", "a.png"]];"#));
+        assert!(!initializer.contains("i18n-alt"));
+        assert!(!initializer.contains(r#""alt", "hello""#));
+    }
+
+    #[test]
+    fn test_compile_icu_in_i18n_block() {
+        let result = compile(
+            "IcuCmp",
+            "<span i18n>{count, plural, =0 {none} other {# items}}</span>",
+        );
+        let output = result.initializer;
+
+        // The ICU is the whole message of the i18n block, which has no element children.
+        assert!(output.contains(
+            "i0.ɵɵelementStart(0, \"span\");\n    i0.ɵɵi18n(1, 0);\n    i0.ɵɵelementEnd();"
+        ));
+        assert!(output.contains("$localize `{VAR_PLURAL, plural, =0 {none} other {# items}}`"));
+        assert!(output.contains(
+            "i18n_0 = i0.ɵɵi18nPostprocess(i18n_0, {'VAR_PLURAL': \"\u{FFFD}0\u{FFFD}\"});"
+        ));
+        // `#` is left to the runtime, which binds the count expression through the ICU var.
+        assert!(output
+            .contains("i0.ɵɵadvance();\n    i0.ɵɵi18nExp(ctx.count);\n    i0.ɵɵi18nApply(1);"));
+    }
+
+    #[test]
+    fn test_compile_icu_outside_i18n_block() {
+        let result = compile(
+            "IcuCmp",
+            "<div>{count, plural, =1 {one} other {{{count}} many}}</div>",
+        );
+        let output = result.initializer;
+
+        // The ICU gets wrapped in an i18n block of its own.
+        assert!(output.contains("i0.ɵɵi18n(1, 0);"));
+        assert!(output
+            .contains("$localize `{VAR_PLURAL, plural, =1 {one} other {{INTERPOLATION} many}}`"));
+        assert!(output.contains(
+            "{'INTERPOLATION': \"\u{FFFD}1\u{FFFD}\", 'VAR_PLURAL': \"\u{FFFD}0\u{FFFD}\"}"
+        ));
+        assert!(output.contains("i0.ɵɵi18nExp(ctx.count)(ctx.count);"));
+        assert!(output.contains("i0.ɵɵi18nApply(1);"));
+    }
+
+    #[test]
+    fn test_compile_slots_are_stable_across_rebuilds() {
+        let template = r#"<div #ref><span>{{ a | uppercase }}</span></div>
+@if (show) { <p [title]="t">{{ ref.id }}</p> } @else { <i></i> }
+@for (item of items; track item.id) { <b>{{ item.name }}</b> } @empty { <em></em> }
+<ng-template #tpl><input [value]="v"></ng-template>"#;
+        let compile_template = || compile("StableCmp", template).initializer;
+
+        let first = compile_template();
+        for _ in 0..4 {
+            assert_eq!(compile_template(), first);
+        }
+    }
+
+    #[test]
+    fn test_compile_inserted_element_shifts_following_slots() {
+        let compile_template = |template: &str| compile("ShiftCmp", template).initializer;
+
+        let before = compile_template(r#"<div></div><span>{{ a }}</span><p></p>"#);
+        assert!(before.contains(r#"i0.ɵɵelement(0, "div");"#));
+        assert!(before.contains(r#"i0.ɵɵelementStart(1, "span");"#));
+        assert!(before.contains(r#"i0.ɵɵtext(2);"#));
+        assert!(before.contains(r#"i0.ɵɵelement(3, "p");"#));
+        assert!(before.contains("i0.ɵɵadvance(2);"));
+        assert!(before.contains("decls: 4"));
+
+        // Inserting an element after the first one leaves slot 0 alone and moves every later
+        // declaration up by exactly the number of slots the new element consumes.
+        let after = compile_template(r#"<div></div><hr><span>{{ a }}</span><p></p>"#);
+        assert!(after.contains(r#"i0.ɵɵelement(0, "div")(1, "hr");"#));
+        assert!(after.contains(r#"i0.ɵɵelementStart(2, "span");"#));
+        assert!(after.contains(r#"i0.ɵɵtext(3);"#));
+        assert!(after.contains(r#"i0.ɵɵelement(4, "p");"#));
+        assert!(after.contains("i0.ɵɵadvance(3);"));
+        assert!(after.contains("decls: 5"));
+    }
+
+    #[test]
+    fn test_compile_listener_without_view_access_skips_restore_view() {
+        let compile_template = |template: &str| compile("ListenerCmp", template).initializer;
+
+        // The local ref is in scope of the listener, but never read by it.
+        let unused_ref = compile_template(r#"<input #box><button (click)="go()"></button>"#);
+        assert!(!unused_ref.contains("getCurrentView"));
+        assert!(!unused_ref.contains("restoreView"));
+        assert!(!unused_ref.contains("resetView"));
+        assert!(unused_ref.contains("return ctx.go();"));
+
+        // Only the listener reading the ref restores the view.
+        let mixed = compile_template(
+            r#"<button (click)="go()"></button><input #box><button (click)="go(box.value)"></button>"#,
+        );
+        assert_eq!(mixed.matches("i0.ɵɵgetCurrentView()").count(), 1);
+        assert_eq!(mixed.matches("i0.ɵɵrestoreView(_r1);").count(), 1);
+        assert!(mixed.contains(
+            "function ListenerCmp_Template_button_click_0_listener() {\n      return ctx.go();"
+        ));
+        assert!(mixed.contains("return i0.ɵɵresetView(ctx.go(box_r2.value));"));
+    }
+
+    #[test]
+    fn test_compile_chains_consecutive_property_bindings() {
+        let compile_template = |template: &str| compile("ChainCmp", template).initializer;
+
+        let single = compile_template(r#"<div [title]="a" [id]="b" [tabIndex]="c"></div>"#);
+        assert!(
+            single.contains(r#"i0.ɵɵproperty("title", ctx.a)("id", ctx.b)("tabIndex", ctx.c);"#)
+        );
+        assert_eq!(single.matches("i0.ɵɵproperty(").count(), 1);
+
+        // `advance` is not chainable, so a new chain starts for the next element.
+        let across_advance = compile_template(
+            r#"<div [title]="a" [id]="b"></div><p>x</p><span [title]="c" [id]="d"></span>"#,
+        );
+        assert!(across_advance.contains(
+            "i0.ɵɵproperty(\"title\", ctx.a)(\"id\", ctx.b);\n    i0.ɵɵadvance(3);\n    i0.ɵɵproperty(\"title\", ctx.c)(\"id\", ctx.d);"
+        ));
+    }
+
+    #[test]
+    fn test_compile_safe_calls_and_keyed_reads() {
+        let compile_template = |template: &str| compile("SafeCmp", template).initializer;
+
+        // The call happens inside the guard, not on the result of the conditional.
+        let safe_method = compile_template("{{ a?.method() }}");
+        assert!(
+            safe_method.contains("i0.ɵɵtextInterpolate((ctx.a == null ? null : ctx.a.method()));")
+        );
+
+        let safe_key = compile_template("{{ a?.[k] }}");
+        assert!(safe_key.contains("i0.ɵɵtextInterpolate((ctx.a == null ? null : ctx.a[ctx.k]));"));
+
+        // Each `?.` short-circuits the rest of the chain.
+        let chained = compile_template("{{ a?.b?.c() }}");
+        assert!(chained.contains(
+            "i0.ɵɵtextInterpolate((ctx.a == null ? null : (ctx.a.b == null ? null : ctx.a.b.c())));"
+        ));
+
+        // A receiver with side effects is evaluated once and read back from a temporary.
+        let call_receiver = compile_template("{{ f()?.b?.c() }}");
+        assert_eq!(call_receiver.matches("ctx.f()").count(), 1);
+        assert!(call_receiver.contains(
+            "((tmp_0_0 = ctx.f()) == null ? null : (tmp_0_0.b == null ? null : tmp_0_0.b.c()))"
+        ));
+
+        // Listener handlers are expanded as well.
+        let listener = compile_template(r#"<button (click)="a?.b?.c()"></button>"#);
+        assert!(listener
+            .contains("return (ctx.a == null ? null : (ctx.a.b == null ? null : ctx.a.b.c()));"));
+    }
+
+    #[test]
+    fn test_compile_keeps_required_parentheses() {
+        let compile_template = |template: &str| compile("ParensCmp", template).initializer;
+
+        let precedence = compile_template("{{ (a + b) * c }}");
+        assert!(precedence.contains("i0.ɵɵtextInterpolate((ctx.a + ctx.b) * ctx.c);"));
+
+        let redundant = compile_template("{{ ((a)) }}");
+        assert!(redundant.contains("i0.ɵɵtextInterpolate(ctx.a);"));
+
+        let left_assoc = compile_template("{{ (a + b) + c }}");
+        assert!(left_assoc.contains("i0.ɵɵtextInterpolate(ctx.a + ctx.b + ctx.c);"));
+
+        let right_operand = compile_template("{{ a + (b + c) }}");
+        assert!(right_operand.contains("i0.ɵɵtextInterpolate(ctx.a + (ctx.b + ctx.c));"));
+
+        // `??` can't be mixed with `&&`/`||` without parentheses.
+        let nullish_lhs = compile_template("{{ (a ?? b) || c }}");
+        assert!(nullish_lhs.contains("i0.ɵɵtextInterpolate((ctx.a ?? ctx.b) || ctx.c);"));
+        let nullish_rhs = compile_template("{{ a ?? (b || c) }}");
+        assert!(nullish_rhs.contains("i0.ɵɵtextInterpolate(ctx.a ?? (ctx.b || ctx.c));"));
+
+        let unary_base = compile_template("{{ (-a) ** 3 }}");
+        assert!(unary_base.contains("i0.ɵɵtextInterpolate((-ctx.a) ** 3);"));
+
+        let call_args = compile_template("{{ f((a), (b + c)) }}");
+        assert!(call_args.contains("i0.ɵɵtextInterpolate(ctx.f(ctx.a, ctx.b + ctx.c));"));
+    }
+
+    #[test]
+    fn test_compile_host_bindings_strip_redundant_parentheses() {
+        let mut host = R3HostMetadata::default();
+        host.properties
+            .insert("title".to_string(), "((a))".to_string());
+        host.properties
+            .insert("tabIndex".to_string(), "(a + b) * c".to_string());
+        let result = compile_with_host("ParensCmp", "", host);
+        let initializer = result.initializer;
+
+        assert!(initializer.contains(r#"("title", ctx.a)"#));
+        assert!(initializer.contains(r#"("tabIndex", (ctx.a + ctx.b) * ctx.c)"#));
+    }
+
+    #[test]
+    fn test_compile_variadic_pipe_bindings() {
+        let compile_template = |template: &str| compile("PipeCmp", template).initializer;
+
+        let variadic = compile_template("{{ x | myPipe:a:b:c:d:e }}");
+        assert!(variadic.contains(
+            "i0.ɵɵtextInterpolate(i0.ɵɵpipeBindV(1, 1, [ctx.x, ctx.a, ctx.b, ctx.c, ctx.d, ctx.e]));"
+        ));
+        assert!(variadic.contains("vars: 8"));
+
+        let fixed = compile_template("{{ x | myPipe:a:b:c }}");
+        assert!(fixed.contains("i0.ɵɵpipeBind4(1, 1, ctx.x, ctx.a, ctx.b, ctx.c)"));
+
+        let nested = compile_template("<div [title]=\"x | myPipe:a:b:c:(d | other):e\"></div>");
+        assert!(nested.contains(
+            "i0.ɵɵproperty(\"title\", i0.ɵɵpipeBindV(0, 3, [ctx.x, ctx.a, ctx.b, ctx.c, i0.ɵɵpipeBind1(1, 1, ctx.d), ctx.e]));"
+        ));
+        assert!(nested.contains("vars: 10"));
+    }
+
+    #[test]
+    fn test_compile_merges_next_context_in_nested_views() {
+        let compile_template = |template: &str| compile("NestCmp", template).statements;
+        let view_fn = |statements: &[String], name: &str| {
+            statements
+                .iter()
+                .find(|stmt| stmt.starts_with(&format!("function {}(", name)))
+                .unwrap()
+                .clone()
+        };
+
+        let loops = compile_template(
+            "@for (a of items; track a) { @for (b of a.bs; track b) { @for (c of b.cs; track c) { {{ a.name }} } } }",
+        );
+        let innermost = view_fn(&loops, "NestCmp_For_1_For_1_For_1_Template");
+        assert_eq!(innermost.matches("i0.ɵɵnextContext(").count(), 1);
+        assert!(innermost.contains("= i0.ɵɵnextContext(2);"));
+
+        // Each skipped context adds a step, so the listener walks all the way up to the root.
+        let conditionals = compile_template(
+            "@if (x) { @if (y) { @if (z) { <button (click)=\"pick()\"></button> } } }",
+        );
+        let innermost = view_fn(
+            &conditionals,
+            "NestCmp_Conditional_0_Conditional_0_Conditional_0_Template",
+        );
+        assert_eq!(innermost.matches("i0.ɵɵnextContext(").count(), 1);
+        assert!(innermost.contains("= i0.ɵɵnextContext(3);"));
+    }
+
+    #[test]
+    fn test_compile_listener_dollar_event() {
+        let result = compile("EventCmp", r#"<button (click)="f($event)"></button>"#);
+        assert!(result.diagnostics.is_empty());
+
+        let initializer = result.initializer;
+        assert!(
+            initializer.contains("function EventCmp_Template_button_click_0_listener($event) {")
+        );
+        assert!(initializer.contains("return ctx.f($event);"));
+    }
+
+    #[test]
+    fn test_compile_any_cast_reads_its_argument() {
+        let result = compile("AnyCmp", "{{ $any(obj).missing }}");
+        assert!(result.diagnostics.is_empty());
+        assert!(result
+            .initializer
+            .contains("i0.ɵɵtextInterpolate(ctx.obj.missing);"));
+    }
+
+    #[test]
+    fn test_compile_host_animation_bindings() {
+        let mut host = R3HostMetadata::default();
+        host.listeners
+            .insert("@slide.start".to_string(), "onStart()".to_string());
+        host.properties
+            .insert("@slide".to_string(), "state".to_string());
+        let result = compile_with_host("AnimCmp", "", host);
+        let initializer = result.initializer;
+
+        assert!(initializer.contains(
+            r#"i0.ɵɵsyntheticHostListener("@slide.start", function AnimCmp_animation_slide_start_HostBindingHandler() {"#
+        ));
+        assert!(initializer.contains(r#"i0.ɵɵsyntheticHostProperty("@slide", ctx.state);"#));
+    }
+}
@@ -134,6 +134,21 @@ impl LiteralType {
     }
 }
 
+impl From<&crate::core::R3CssSelector> for LiteralType {
+    fn from(selector: &crate::core::R3CssSelector) -> Self {
+        use crate::core::R3SelectorEntry;
+        LiteralType::Array(
+            selector
+                .iter()
+                .map(|entry| match entry {
+                    R3SelectorEntry::Value(value) => LiteralType::String(value.clone()),
+                    R3SelectorEntry::Flags(flags) => LiteralType::Number(*flags as f64),
+                })
+                .collect(),
+        )
+    }
+}
+
 /// Convert literal or array literal to Expression
 pub fn literal_or_array_literal(value: LiteralType) -> Expression {
     value.to_expression()
@@ -632,16 +647,19 @@ fn convert_ast_internal(
             })
         }
         AST::ParenthesizedExpression(paren) => {
-            // Parenthesized expressions don't affect semantics, so we can just unwrap
-            // In TypeScript, this returns a ParenthesizedExpr, but in Rust output_ast doesn't have it
-            // So we just return the inner expression
-            convert_ast_internal(
-                &paren.expression,
-                job,
-                view_xref,
-                external_view,
-                base_source_span,
-            )
+            // Parentheses are kept so they can group operators; the ones that aren't required are
+            // removed by the `strip_nonrequired_parentheses` phase.
+            Expression::Parens(crate::output::output_ast::ParenthesizedExpr {
+                expr: Box::new(convert_ast_internal(
+                    &paren.expression,
+                    job,
+                    view_xref,
+                    external_view,
+                    base_source_span,
+                )),
+                type_: None,
+                source_span: convert_source_span(&paren.span, base_source_span),
+            })
         }
         AST::PropertyWrite(prop) => {
             // Whether this is an implicit receiver, *excluding* explicit reads of `this`.
@@ -10,6 +10,7 @@ use crate::render3::r3_identifiers::Identifiers as R3;
 use crate::render3::util::R3CompiledExpression;
 use crate::render3::view::api::{R3ComponentMetadata, R3TemplateDependencyMetadata};
 use crate::render3::view::compiler::compile_styles;
+use crate::render3::view::query_generation::get_query_predicate;
 use crate::render3::view::util::{
     conditionally_create_directive_binding_literal, InputBindingValue,
 };
@@ -370,14 +371,31 @@ pub fn emit_component(
         });
     }
 
-    // consts - collected element attributes from const_collection phase
-    definition_entries.push(o::LiteralMapEntry {
-        key: "consts".into(),
-        value: Box::new(o::Expression::LiteralArray(o::LiteralArrayExpr {
-            entries: job.consts.iter().cloned().collect(),
+    // consts - collected element attributes from const_collection phase. When translations are
+    // declared, the array is returned from a function that declares them first.
+    let consts = o::Expression::LiteralArray(o::LiteralArrayExpr {
+        entries: job.consts.to_vec(),
+        type_: None,
+        source_span: None,
+    });
+    let consts = if job.consts_initializers.is_empty() {
+        consts
+    } else {
+        let mut body = job.consts_initializers.clone();
+        body.push(o::Statement::Return(o::ReturnStatement {
+            value: Box::new(consts),
+            source_span: None,
+        }));
+        o::Expression::ArrowFn(o::ArrowFunctionExpr {
+            params: vec![],
+            body: o::ArrowFunctionBody::Statements(body),
             type_: None,
             source_span: None,
-        })),
+        })
+    };
+    definition_entries.push(o::LiteralMapEntry {
+        key: "consts".into(),
+        value: Box::new(consts),
         quoted: false,
     });
 
@@ -459,19 +477,18 @@ pub fn emit_component(
         });
     }
 
-    // Add changeDetection if set (OnPush = 0)
+    // Add changeDetection if set. `Default` is the runtime default, so like Angular we
+    // only emit the field for a non-default strategy (OnPush = 0).
     if let Some(ref change_detection) = metadata.change_detection {
         match change_detection {
             crate::render3::view::api::ChangeDetectionOrExpression::Strategy(strategy) => {
-                let value = match strategy {
-                    crate::core::ChangeDetectionStrategy::OnPush => 0,
-                    crate::core::ChangeDetectionStrategy::Default => 1,
-                };
-                definition_entries.push(o::LiteralMapEntry {
-                    key: "changeDetection".into(),
-                    value: Box::new(*o::literal(value as f64)),
-                    quoted: false,
-                });
+                if *strategy != crate::core::ChangeDetectionStrategy::Default {
+                    definition_entries.push(o::LiteralMapEntry {
+                        key: "changeDetection".into(),
+                        value: Box::new(*o::literal(*strategy as u8 as f64)),
+                        quoted: false,
+                    });
+                }
             }
             crate::render3::view::api::ChangeDetectionOrExpression::Expression(expr) => {
                 definition_entries.push(o::LiteralMapEntry {
@@ -1556,6 +1573,7 @@ fn emit_view_query_function(
 
     // Generate create block (rf & 1)
     if !view_queries.is_empty() {
+        let mut constant_pool = crate::constant_pool::ConstantPool::new(false);
         let mut chain_expr: Option<o::Expression> = None;
 
         for query in view_queries {
@@ -1568,19 +1586,8 @@ fn emit_view_query_function(
                     }));
                 }
 
-                // Emit ɵɵviewQuerySignal(ctx.prop, selector, flags)
-                let selector = match &query.predicate {
-                    crate::render3::view::api::R3QueryPredicate::Selectors(selectors) => {
-                        selectors.first().cloned().unwrap_or_default()
-                    }
-                    _ => String::new(),
-                };
-
-                let selector_arr = o::Expression::LiteralArray(o::LiteralArrayExpr {
-                    entries: vec![*o::literal(selector.clone())],
-                    type_: None,
-                    source_span: None,
-                });
+                // Emit ɵɵviewQuerySignal(ctx.prop, predicate, flags)
+                let selector_arr = get_query_predicate(query, &mut constant_pool);
 
                 // Flags: 1 = Descendants, 2 = Static, 4 = EmitDistinctChangesOnly
                 let mut flags = 0.0;
@@ -1621,19 +1628,7 @@ fn emit_view_query_function(
                 }));
             } else {
                 // Non-signal based queries can be chained
-                let selector = match &query.predicate {
-                    crate::render3::view::api::R3QueryPredicate::Selectors(selectors) => {
-                        selectors.first().cloned().unwrap_or_default()
-                    }
-                    _ => String::new(),
-                };
-
-                // Create _cN constant reference for the selector string
-                let selector_arr = o::Expression::LiteralArray(o::LiteralArrayExpr {
-                    entries: vec![*o::literal(selector.clone())],
-                    type_: None,
-                    source_span: None,
-                });
+                let selector_arr = get_query_predicate(query, &mut constant_pool);
 
                 // Flags: 5 = DescendantsOnly (for ViewChild with descendants=false)
                 let flags = if query.first { 5.0 } else { 4.0 };
@@ -493,7 +493,7 @@ fn ingest_element(
         let i18n_start_op = ir::ops::create::create_i18n_start_op(
             i18n_block_id.unwrap(),
             msg.clone(),
-            Some(id),
+            None,
             Some(element.start_source_span.clone()),
         );
         view.create.push(i18n_start_op);
@@ -1134,77 +1134,73 @@ fn ingest_defer_block(
     deferred_block: t::DeferredBlock,
     job: &mut ComponentCompilationJob,
 ) {
-    let defer_xref = job.allocate_xref_id();
-
-    // 1. Ingest main block
-    let main_view_xref = job.allocate_view(Some(view.xref));
-
-    // Extract children for main view
-    let main_children = deferred_block.children.clone();
-
-    // Ingest children into main view
-    ingest_children_into_view(job, main_view_xref, main_children);
-
-    // Resolver (not yet supported in Rust AST?)
-    let own_resolver_fn = None;
-
-    // 2. Ingest @loading block if present
-    let (loading_view, loading_minimum_time, loading_after_time) =
-        if let Some(loading) = &deferred_block.loading {
-            let view_xref = job.allocate_view(Some(view.xref));
-            ingest_children_into_view(job, view_xref, loading.children.clone());
-
-            let min = loading.minimum_time.map(|t| t as f64);
-            let after = loading.after_time.map(|t| t as f64);
-            (Some(view_xref), min, after)
-        } else {
-            (None, None, None)
-        };
-
-    // 3. Ingest @placeholder block if present
-    let (placeholder_view, placeholder_minimum_time) =
-        if let Some(placeholder) = &deferred_block.placeholder {
-            let view_xref = job.allocate_view(Some(view.xref));
-            ingest_children_into_view(job, view_xref, placeholder.children.clone());
-
-            let min = placeholder.minimum_time.map(|t| t as f64);
-            (Some(view_xref), min)
-        } else {
-            (None, None)
-        };
-
-    // 4. Ingest @error block if present
-    let error_view = if let Some(error) = &deferred_block.error {
-        let view_xref = job.allocate_view(Some(view.xref));
-        ingest_children_into_view(job, view_xref, error.children.clone());
-
-        Some(view_xref)
-    } else {
-        None
-    };
+    // Generate the defer main view and all secondary views.
+    let (main_view, main_slot) = ingest_defer_view(
+        view,
+        job,
+        "",
+        deferred_block.children.clone(),
+        &deferred_block.block.source_span,
+    );
+    let loading = deferred_block.loading.as_ref().map(|loading| {
+        ingest_defer_view(
+            view,
+            job,
+            "Loading",
+            loading.children.clone(),
+            &loading.block.source_span,
+        )
+    });
+    let placeholder = deferred_block.placeholder.as_ref().map(|placeholder| {
+        ingest_defer_view(
+            view,
+            job,
+            "Placeholder",
+            placeholder.children.clone(),
+            &placeholder.block.source_span,
+        )
+    });
+    let error = deferred_block.error.as_ref().map(|error| {
+        ingest_defer_view(
+            view,
+            job,
+            "Error",
+            error.children.clone(),
+            &error.block.source_span,
+        )
+    });
 
-    // Create DeferOp
+    // Create the main defer op, and ops for all secondary views.
+    let defer_xref = job.allocate_xref_id();
     let mut defer_op = ir::ops::create::DeferOp::new(
         defer_xref,
-        main_view_xref,
-        ir::handle::SlotHandle::with_slot(0), // main_slot - TODO: extract from view
-        own_resolver_fn,
-        job.all_deferrable_deps_fn.clone(), // resolver_fn
+        main_view,
+        main_slot,
+        None, // own_resolver_fn: only set in per-block mode
+        job.all_deferrable_deps_fn.clone(),
         deferred_block.block.source_span.clone(),
     );
-
-    // Set secondary views and their slots
-    defer_op.placeholder_view = placeholder_view;
-    defer_op.placeholder_slot = placeholder_view.map(|_| ir::handle::SlotHandle::with_slot(0)); // TODO: extract
-    defer_op.loading_view = loading_view;
-    defer_op.loading_slot = loading_view.map(|_| ir::handle::SlotHandle::with_slot(0)); // TODO: extract
-    defer_op.error_view = error_view;
-    defer_op.error_slot = error_view.map(|_| ir::handle::SlotHandle::with_slot(0)); // TODO: extract
-
-    // Set minimum times
-    defer_op.placeholder_minimum_time = placeholder_minimum_time;
-    defer_op.loading_minimum_time = loading_minimum_time;
-    defer_op.loading_after_time = loading_after_time;
+    defer_op.loading_view = loading.as_ref().map(|(xref, _)| *xref);
+    defer_op.loading_slot = loading.map(|(_, slot)| slot);
+    defer_op.placeholder_view = placeholder.as_ref().map(|(xref, _)| *xref);
+    defer_op.placeholder_slot = placeholder.map(|(_, slot)| slot);
+    defer_op.error_view = error.as_ref().map(|(xref, _)| *xref);
+    defer_op.error_slot = error.map(|(_, slot)| slot);
+    defer_op.placeholder_minimum_time = deferred_block
+        .placeholder
+        .as_ref()
+        .and_then(|placeholder| placeholder.minimum_time)
+        .map(|t| t as f64);
+    defer_op.loading_minimum_time = deferred_block
+        .loading
+        .as_ref()
+        .and_then(|loading| loading.minimum_time)
+        .map(|t| t as f64);
+    defer_op.loading_after_time = deferred_block
+        .loading
+        .as_ref()
+        .and_then(|loading| loading.after_time)
+        .map(|t| t as f64);
 
     // Calculate flags
     if !deferred_block.hydrate_triggers.when.is_none()
@@ -1293,6 +1289,32 @@ fn ingest_defer_block(
     }
 }
 
+/// Ingest the children of a `@defer` block or one of its secondary blocks into their own view,
+/// declared by a template op in the parent view. Returns the view and the slot of its template.
+fn ingest_defer_view(
+    view: &mut ViewCompilationUnit,
+    job: &mut ComponentCompilationJob,
+    suffix: &str,
+    children: Vec<t::R3Node>,
+    source_span: &crate::parse_util::ParseSourceSpan,
+) -> (ir::XrefId, ir::handle::SlotHandle) {
+    let secondary_view = job.allocate_view(Some(view.xref));
+    ingest_children_into_view(job, secondary_view, children);
+    let template_op = ir::ops::create::TemplateOp::new(
+        secondary_view,
+        ir::TemplateKind::Block,
+        None,
+        format!("Defer{}", suffix),
+        ir::Namespace::HTML,
+        None,
+        source_span.clone(),
+        source_span.clone(),
+    );
+    let handle = template_op.base.base.handle.clone();
+    view.create.push(Box::new(template_op));
+    (secondary_view, handle)
+}
+
 /// Ingest defer triggers and create DeferOnOp/DeferWhenOp operations
 fn ingest_defer_triggers(
     unit_xref: ir::XrefId,
@@ -1432,11 +1454,11 @@ fn ingest_defer_triggers(
 fn icu_from_i18n_message(message: &crate::i18n::i18n_ast::Message) -> Option<String> {
     use crate::i18n::i18n_ast::Node as I18nNode;
 
-    // Get first node which should be IcuPlaceholder for single ICU
-    if let Some(I18nNode::IcuPlaceholder(icu_ph)) = message.nodes.first() {
-        Some(icu_ph.name.clone())
-    } else {
-        None
+    // The first node of a single ICU message is the ICU, named by the i18n meta pass
+    match message.nodes.first() {
+        Some(I18nNode::Icu(icu)) => icu.name.clone(),
+        Some(I18nNode::IcuPlaceholder(icu_ph)) => Some(icu_ph.name.clone()),
+        _ => None,
     }
 }
 
@@ -1462,7 +1484,9 @@ fn ingest_icu(view: &mut ViewCompilationUnit, icu: t::Icu, job: &mut ComponentCo
 
             // Ingest ICU variables and placeholders
             // Iterate over icu.vars (BoundText) - pass placeholder name
-            for (placeholder, bound_text) in &icu.vars {
+            let mut vars: Vec<_> = icu.vars.iter().collect();
+            vars.sort_by(|a, b| a.0.cmp(b.0));
+            for (placeholder, bound_text) in vars {
                 ingest_bound_text(
                     view,
                     bound_text.clone(),
@@ -1473,7 +1497,9 @@ fn ingest_icu(view: &mut ViewCompilationUnit, icu: t::Icu, job: &mut ComponentCo
             }
 
             // Iterate over icu.placeholders (IcuPlaceholder enum) - pass placeholder name
-            for (placeholder, icu_ph) in &icu.placeholders {
+            let mut placeholders: Vec<_> = icu.placeholders.iter().collect();
+            placeholders.sort_by(|a, b| a.0.cmp(b.0));
+            for (placeholder, icu_ph) in placeholders {
                 match icu_ph {
                     t::IcuPlaceholder::Text(text) => {
                         ingest_text(view, text.clone(), Some(placeholder.to_string()), job);
@@ -2175,8 +2201,10 @@ fn ingest_element_bindings(
             crate::expression_parser::ast::BindingType::Class => ir::BindingKind::ClassName,
             crate::expression_parser::ast::BindingType::Style => ir::BindingKind::StyleProperty,
             crate::expression_parser::ast::BindingType::Animation => ir::BindingKind::Animation,
+            crate::expression_parser::ast::BindingType::LegacyAnimation => {
+                ir::BindingKind::LegacyAnimation
+            }
             crate::expression_parser::ast::BindingType::TwoWay => ir::BindingKind::TwoWayProperty,
-            _ => ir::BindingKind::Property, // Default fallback
         };
 
         // Convert input value
@@ -2325,7 +2353,7 @@ fn ingest_element_events(
                     output.name.clone(),
                     Some(element_tag.to_string()),
                     handler_ops,
-                    output.target.as_ref().map(|t| t.to_string()), // legacy_animation_phase (phase is stored in target for LegacyAnimation)
+                    output.phase.as_ref().map(|p| p.to_string()), // legacy_animation_phase
                     None,  // event_target (null for LegacyAnimation)
                     false, // host_listener
                     output.source_span.clone(),
@@ -2835,8 +2863,10 @@ fn ingest_template_bindings(
             crate::expression_parser::ast::BindingType::Class => ir::BindingKind::ClassName,
             crate::expression_parser::ast::BindingType::Style => ir::BindingKind::StyleProperty,
             crate::expression_parser::ast::BindingType::Animation => ir::BindingKind::Animation,
+            crate::expression_parser::ast::BindingType::LegacyAnimation => {
+                ir::BindingKind::LegacyAnimation
+            }
             crate::expression_parser::ast::BindingType::TwoWay => ir::BindingKind::TwoWayProperty,
-            _ => ir::BindingKind::Property, // Default fallback
         };
 
         // Convert input value
@@ -2925,10 +2955,12 @@ fn ingest_template_bindings(
                     crate::expression_parser::ast::BindingType::Animation => {
                         ir::BindingKind::Animation
                     }
+                    crate::expression_parser::ast::BindingType::LegacyAnimation => {
+                        ir::BindingKind::LegacyAnimation
+                    }
                     crate::expression_parser::ast::BindingType::TwoWay => {
                         ir::BindingKind::TwoWayProperty
                     }
-                    _ => ir::BindingKind::Property,
                 };
 
                 // Convert input value
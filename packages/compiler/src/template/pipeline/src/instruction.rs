@@ -5,6 +5,8 @@
 use crate::output::output_ast as o;
 use crate::parse_util::ParseSourceSpan;
 use crate::render3::r3_identifiers::Identifiers;
+use crate::template::pipeline::ir::enums::DeferOpModifierKind;
+use crate::template::pipeline::ir::ops::create::DeferTrigger;
 use o::ExternalReference;
 
 pub fn call(
@@ -155,6 +157,18 @@ pub fn property<S: AsRef<str>>(
     call(Identifiers::property(), args, Some(source_span))
 }
 
+pub fn synthetic_host_property<S: AsRef<str>>(
+    name: S,
+    expression: o::Expression,
+    source_span: ParseSourceSpan,
+) -> o::Statement {
+    call(
+        Identifiers::synthetic_host_property(),
+        vec![*o::literal(name.as_ref()), expression],
+        Some(source_span),
+    )
+}
+
 pub fn attribute<S: AsRef<str>>(
     name: S,
     expression: o::Expression,
@@ -177,17 +191,33 @@ pub fn enable_bindings() -> o::Statement {
     call(Identifiers::enable_bindings(), vec![], None)
 }
 
+/// Generates ɵɵattachSourceLocations(templatePath, locations), where each location is a
+/// `[slot, offset, line, column]` array.
+pub fn attach_source_locations(template_path: String, locations: o::Expression) -> o::Statement {
+    call(
+        Identifiers::attach_source_locations(),
+        vec![*o::literal(template_path), locations],
+        None,
+    )
+}
+
 pub fn listener(
     name: String,
     handler_fn: o::Expression,
     event_target: Option<String>,
+    synthetic_host: bool,
     source_span: Option<ParseSourceSpan>,
 ) -> o::Statement {
     let mut args = vec![*o::literal(name), handler_fn];
     if let Some(target) = event_target {
         args.push(*o::literal(target));
     }
-    call(Identifiers::listener(), args, source_span)
+    let instruction = if synthetic_host {
+        Identifiers::synthetic_host_listener()
+    } else {
+        Identifiers::listener()
+    };
+    call(instruction, args, source_span)
 }
 
 pub fn two_way_listener<S: AsRef<str>>(
@@ -343,37 +373,41 @@ pub fn conditional_create(
     call(Identifiers::conditional_create(), args, Some(source_span))
 }
 
-/// Creates a pipe binding expression.
+/// Generates ɵɵpipeBind1/2/3/4 based on number of arguments; larger pipes use [`pipe_bind_v`].
 /// Generates ɵɵpipeBind1/2/3/4/V based on number of arguments.
 /// The signature is: ɵɵpipeBind(pipeSlot, varOffset, ...args)
 pub fn pipe_bind(pipe_slot: i32, var_offset: i32, args: Vec<o::Expression>) -> o::Expression {
-    let num_args = args.len();
-    let id = match num_args {
+    let id = match args.len() {
         1 => Identifiers::pipe_bind1(),
         2 => Identifiers::pipe_bind2(),
         3 => Identifiers::pipe_bind3(),
         4 => Identifiers::pipe_bind4(),
-        _ => Identifiers::pipe_bind_v(),
+        _ => panic!("pipeBind() argument count out of bounds"),
     };
 
     let mut call_args = vec![
         *o::literal(pipe_slot as f64),
         *o::literal(var_offset as f64),
     ];
-    if num_args > 4 {
-        // Box args into array for pipeBindV
-        call_args.push(o::Expression::LiteralArray(o::LiteralArrayExpr {
-            entries: args,
-            type_: None,
-            source_span: None,
-        }));
-    } else {
-        call_args.extend(args);
-    }
+    call_args.extend(args);
 
     *o::import_ref(id).call_fn(call_args, None, None)
 }
 
+/// Creates a ɵɵpipeBindV expression for pipes with more than 4 arguments, which are passed as a
+/// single array.
+pub fn pipe_bind_v(pipe_slot: i32, var_offset: i32, args: o::Expression) -> o::Expression {
+    *o::import_ref(Identifiers::pipe_bind_v()).call_fn(
+        vec![
+            *o::literal(pipe_slot as f64),
+            *o::literal(var_offset as f64),
+            args,
+        ],
+        None,
+        None,
+    )
+}
+
 /// Creates a reference expression for local template refs.
 /// Generates ɵɵreference(slot) expression.
 pub fn reference(slot: i32) -> o::Expression {
@@ -408,3 +442,190 @@ pub fn style_prop<S: AsRef<str>, U: AsRef<str>>(
     }
     call(Identifiers::style_prop(), args, source_span)
 }
+
+fn i18n_base(
+    instruction: o::ExternalReference,
+    slot: i32,
+    const_index: i32,
+    sub_template_index: Option<usize>,
+    source_span: Option<ParseSourceSpan>,
+) -> o::Statement {
+    let mut args = vec![*o::literal(slot as f64), *o::literal(const_index as f64)];
+    if let Some(index) = sub_template_index {
+        args.push(*o::literal(index as f64));
+    }
+    call(instruction, args, source_span)
+}
+
+/// Creates an i18nStart instruction.
+/// Generates ɵɵi18nStart(slot, constIndex, subTemplateIndex?) statement.
+pub fn i18n_start(
+    slot: i32,
+    const_index: i32,
+    sub_template_index: Option<usize>,
+    source_span: Option<ParseSourceSpan>,
+) -> o::Statement {
+    i18n_base(
+        Identifiers::i18n_start(),
+        slot,
+        const_index,
+        sub_template_index,
+        source_span,
+    )
+}
+
+/// Creates an i18n instruction for an i18n block without element children.
+/// Generates ɵɵi18n(slot, constIndex, subTemplateIndex?) statement.
+pub fn i18n(
+    slot: i32,
+    const_index: i32,
+    sub_template_index: Option<usize>,
+    source_span: Option<ParseSourceSpan>,
+) -> o::Statement {
+    i18n_base(
+        Identifiers::i18n(),
+        slot,
+        const_index,
+        sub_template_index,
+        source_span,
+    )
+}
+
+/// Creates an i18nEnd instruction.
+pub fn i18n_end(source_span: Option<ParseSourceSpan>) -> o::Statement {
+    call(Identifiers::i18n_end(), vec![], source_span)
+}
+
+/// Creates an i18nExp instruction.
+/// Generates ɵɵi18nExp(expression) statement.
+pub fn i18n_exp(expression: o::Expression, source_span: Option<ParseSourceSpan>) -> o::Statement {
+    call(Identifiers::i18n_exp(), vec![expression], source_span)
+}
+
+/// Creates an i18nApply instruction.
+/// Generates ɵɵi18nApply(slot) statement.
+pub fn i18n_apply(slot: i32, source_span: Option<ParseSourceSpan>) -> o::Statement {
+    call(
+        Identifiers::i18n_apply(),
+        vec![*o::literal(slot as f64)],
+        source_span,
+    )
+}
+
+/// Slots and configuration of a `@defer` block, passed to the ɵɵdefer instruction.
+pub struct DeferConfig {
+    pub self_slot: i32,
+    pub primary_slot: i32,
+    pub dependency_resolver_fn: Option<o::Expression>,
+    pub loading_slot: Option<i32>,
+    pub placeholder_slot: Option<i32>,
+    pub error_slot: Option<i32>,
+    pub loading_config: Option<o::Expression>,
+    pub placeholder_config: Option<o::Expression>,
+    pub enable_timer_scheduling: bool,
+    pub flags: Option<u8>,
+}
+
+pub fn defer(config: DeferConfig, source_span: ParseSourceSpan) -> o::Statement {
+    let DeferConfig {
+        self_slot,
+        primary_slot,
+        dependency_resolver_fn,
+        loading_slot,
+        placeholder_slot,
+        error_slot,
+        loading_config,
+        placeholder_config,
+        enable_timer_scheduling,
+        flags,
+    } = config;
+    let slot = |slot: Option<i32>| match slot {
+        Some(slot) => *o::literal(slot as f64),
+        None => *o::literal(o::LiteralValue::Null),
+    };
+    let mut args = vec![
+        *o::literal(self_slot as f64),
+        *o::literal(primary_slot as f64),
+        dependency_resolver_fn.unwrap_or_else(|| *o::literal(o::LiteralValue::Null)),
+        slot(loading_slot),
+        slot(placeholder_slot),
+        slot(error_slot),
+        loading_config.unwrap_or_else(|| *o::literal(o::LiteralValue::Null)),
+        placeholder_config.unwrap_or_else(|| *o::literal(o::LiteralValue::Null)),
+        if enable_timer_scheduling {
+            *o::import_ref(Identifiers::defer_enable_timer_scheduling())
+        } else {
+            *o::literal(o::LiteralValue::Null)
+        },
+        slot(flags.map(i32::from)),
+    ];
+
+    // Drop trailing null arguments, which the runtime treats as absent.
+    while matches!(
+        args.last(),
+        Some(o::Expression::Literal(o::LiteralExpr {
+            value: o::LiteralValue::Null,
+            ..
+        }))
+    ) {
+        args.pop();
+    }
+
+    call(Identifiers::defer(), args, Some(source_span))
+}
+
+pub fn defer_on(
+    trigger: &DeferTrigger,
+    args: Vec<i32>,
+    modifier: DeferOpModifierKind,
+    source_span: ParseSourceSpan,
+) -> o::Statement {
+    use DeferOpModifierKind as Modifier;
+    let instruction = match (trigger, modifier) {
+        (DeferTrigger::Idle, Modifier::None) => Identifiers::defer_on_idle(),
+        (DeferTrigger::Idle, Modifier::Prefetch) => Identifiers::defer_prefetch_on_idle(),
+        (DeferTrigger::Idle, Modifier::Hydrate) => Identifiers::defer_hydrate_on_idle(),
+        (DeferTrigger::Immediate, Modifier::None) => Identifiers::defer_on_immediate(),
+        (DeferTrigger::Immediate, Modifier::Prefetch) => Identifiers::defer_prefetch_on_immediate(),
+        (DeferTrigger::Immediate, Modifier::Hydrate) => Identifiers::defer_hydrate_on_immediate(),
+        (DeferTrigger::Timer { .. }, Modifier::None) => Identifiers::defer_on_timer(),
+        (DeferTrigger::Timer { .. }, Modifier::Prefetch) => Identifiers::defer_prefetch_on_timer(),
+        (DeferTrigger::Timer { .. }, Modifier::Hydrate) => Identifiers::defer_hydrate_on_timer(),
+        (DeferTrigger::Hover { .. }, Modifier::None) => Identifiers::defer_on_hover(),
+        (DeferTrigger::Hover { .. }, Modifier::Prefetch) => Identifiers::defer_prefetch_on_hover(),
+        (DeferTrigger::Hover { .. }, Modifier::Hydrate) => Identifiers::defer_hydrate_on_hover(),
+        (DeferTrigger::Interaction { .. }, Modifier::None) => Identifiers::defer_on_interaction(),
+        (DeferTrigger::Interaction { .. }, Modifier::Prefetch) => {
+            Identifiers::defer_prefetch_on_interaction()
+        }
+        (DeferTrigger::Interaction { .. }, Modifier::Hydrate) => {
+            Identifiers::defer_hydrate_on_interaction()
+        }
+        (DeferTrigger::Viewport { .. }, Modifier::None) => Identifiers::defer_on_viewport(),
+        (DeferTrigger::Viewport { .. }, Modifier::Prefetch) => {
+            Identifiers::defer_prefetch_on_viewport()
+        }
+        (DeferTrigger::Viewport { .. }, Modifier::Hydrate) => {
+            Identifiers::defer_hydrate_on_viewport()
+        }
+        (DeferTrigger::Never, _) => Identifiers::defer_hydrate_never(),
+    };
+    let args = args
+        .into_iter()
+        .map(|arg| *o::literal(arg as f64))
+        .collect();
+    call(instruction, args, Some(source_span))
+}
+
+pub fn defer_when(
+    modifier: DeferOpModifierKind,
+    expr: o::Expression,
+    source_span: ParseSourceSpan,
+) -> o::Statement {
+    let instruction = match modifier {
+        DeferOpModifierKind::None => Identifiers::defer_when(),
+        DeferOpModifierKind::Prefetch => Identifiers::defer_prefetch_when(),
+        DeferOpModifierKind::Hydrate => Identifiers::defer_hydrate_when(),
+    };
+    call(instruction, vec![expr], Some(source_span))
+}
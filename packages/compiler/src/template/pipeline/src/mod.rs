@@ -3,6 +3,7 @@
 //! Corresponds to packages/compiler/src/template/pipeline/src/
 
 pub mod compilation;
+mod component_compilation_tests;
 pub mod conversion;
 pub mod emit;
 mod host_binding_tests;
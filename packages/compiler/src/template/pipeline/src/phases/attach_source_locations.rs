@@ -3,6 +3,7 @@
 //! Corresponds to packages/compiler/src/template/pipeline/src/phases/attach_source_locations.ts
 //! Locates all elements defined in a creation block and outputs an op that exposes their definition locations
 
+use crate::parse_util::ParseLocation;
 use crate::template::pipeline::ir;
 use crate::template::pipeline::ir::enums::OpKind;
 use crate::template::pipeline::ir::ops::create::{
@@ -31,18 +32,41 @@ pub fn attach_source_locations(job: &mut dyn CompilationJob) {
         .unwrap()
         .clone();
 
+    let template_start = component_job.template_start.as_ref();
+
     // Process root unit
-    process_unit(&mut component_job.root, &template_path);
+    process_unit(&mut component_job.root, &template_path, template_start);
 
     // Process all view units
     for (_, unit) in component_job.views.iter_mut() {
-        process_unit(unit, &template_path);
+        process_unit(unit, &template_path, template_start);
+    }
+}
+
+/// Locate `location` in the file the template starts at `template_start` in.
+fn locate(
+    location: &ParseLocation,
+    template_start: Option<&ParseLocation>,
+) -> (usize, usize, usize) {
+    match template_start {
+        Some(start) if location.line == 0 => (
+            start.offset + location.offset,
+            start.line,
+            start.col + location.col,
+        ),
+        Some(start) => (
+            start.offset + location.offset,
+            start.line + location.line,
+            location.col,
+        ),
+        None => (location.offset, location.line, location.col),
     }
 }
 
 fn process_unit(
     unit: &mut crate::template::pipeline::src::compilation::ViewCompilationUnit,
     template_path: &str,
+    template_start: Option<&ParseLocation>,
 ) {
     let mut locations: Vec<ElementSourceLocation> = Vec::new();
 
@@ -53,12 +77,15 @@ fn process_unit(
                 let elem_start_ptr = op_ptr as *const ElementStartOp;
                 let elem_start = &*elem_start_ptr;
 
-                let start = &elem_start.base.base.start_source_span.start;
+                let (offset, line, column) = locate(
+                    &elem_start.base.base.start_source_span.start,
+                    template_start,
+                );
                 locations.push(ElementSourceLocation {
                     target_slot: elem_start.base.base.handle.clone(),
-                    offset: start.offset,
-                    line: start.line,
-                    column: start.col,
+                    offset,
+                    line,
+                    column,
                 });
             }
         } else if op.kind() == OpKind::Element {
@@ -67,12 +94,13 @@ fn process_unit(
                 let elem_ptr = op_ptr as *const ElementOp;
                 let elem = &*elem_ptr;
 
-                let start = &elem.base.base.start_source_span.start;
+                let (offset, line, column) =
+                    locate(&elem.base.base.start_source_span.start, template_start);
                 locations.push(ElementSourceLocation {
                     target_slot: elem.base.base.handle.clone(),
-                    offset: start.offset,
-                    line: start.line,
-                    column: start.col,
+                    offset,
+                    line,
+                    column,
                 });
             }
         }
@@ -3,7 +3,7 @@
 //! Corresponds to packages/compiler/src/template/pipeline/src/phases/const_collection.ts
 //! Converts the semantic attributes of element-like operations into constant array expressions
 
-use crate::core::AttributeMarker;
+use crate::core::{parse_selector_to_r3_selector, AttributeMarker};
 use crate::output::output_ast::{
     Expression, LiteralArrayExpr, LiteralExpr, LiteralValue, TaggedTemplateLiteralExpr,
     TemplateLiteral, TemplateLiteralElement,
@@ -17,6 +17,7 @@ use crate::template::pipeline::src::compilation::{
     CompilationJob, CompilationJobKind, CompilationUnit, ComponentCompilationJob,
     HostBindingCompilationJob,
 };
+use crate::template::pipeline::src::conversion::{literal_or_array_literal, LiteralType};
 use std::collections::HashMap;
 
 /// Container for all of the various kinds of attributes which are applied on an element.
@@ -83,10 +84,11 @@ impl ElementAttributes {
             if let Some(Expression::Literal(LiteralExpr {
                 value: LiteralValue::String(s),
                 ..
-            })) = value
+            })) = &value
             {
-                self.project_as = Some(s);
-                return; // ngProjectAs is handled separately
+                // TODO: TemplateDefinitionBuilder allows `ngProjectAs` to also be assigned as a
+                // literal attribute. Is this sane?
+                self.project_as = Some(s.clone());
             } else {
                 panic!("ngProjectAs must have a string literal value");
             }
@@ -485,82 +487,20 @@ fn serialize_attributes(attrs: ElementAttributes) -> LiteralArrayExpr {
 
     // Add projectAs if present
     if let Some(project_as) = attrs.project_as {
-        // Parse selector to R3 selector
-        // TODO: Implement parse_selector_to_r3_selector properly
-        // For now, use a simplified approach - parse selector string to R3 format
-        // In TypeScript: parseSelectorToR3Selector returns R3CssSelectorList
-        // We'll create a placeholder literal array for now
+        // Parse the attribute value into a CssSelectorList. Note that we only take the
+        // first selector, because we don't support multiple selectors in ngProjectAs.
+        let parsed_r3_selector = parse_selector_to_r3_selector(Some(&project_as))
+            .into_iter()
+            .next()
+            .unwrap_or_default();
         attr_array.push(Expression::Literal(LiteralExpr {
             value: LiteralValue::Number(AttributeMarker::ProjectAs as u8 as f64),
             type_: None,
             source_span: None,
         }));
-        // Parse selector - for now use a simplified representation
-        // The selector is a string that needs to be parsed into an R3 selector
-        // R3 selector is an array of arrays: [[element, attr1, attr2, ...], ...]
-        // For simple selectors like "div", it becomes [["", "div", ""]]
-        // For now, we'll create a basic structure
-        let selector_parts: Vec<&str> = project_as.split(',').map(|s| s.trim()).collect();
-        let mut selector_array = Vec::new();
-        for part in selector_parts {
-            // Simple parsing: if it starts with a dot, it's a class; if it starts with #, it's an id
-            // Otherwise, it's an element name
-            let mut selector_item = vec![Expression::Literal(LiteralExpr {
-                value: LiteralValue::String("".to_string()),
-                type_: None,
-                source_span: None,
-            })];
-
-            if part.starts_with('.') {
-                // Class selector
-                selector_item.push(Expression::Literal(LiteralExpr {
-                    value: LiteralValue::String("".to_string()),
-                    type_: None,
-                    source_span: None,
-                }));
-                selector_item.push(Expression::Literal(LiteralExpr {
-                    value: LiteralValue::String(part[1..].to_string()),
-                    type_: None,
-                    source_span: None,
-                }));
-            } else if part.starts_with('#') {
-                // ID selector
-                selector_item.push(Expression::Literal(LiteralExpr {
-                    value: LiteralValue::String(part[1..].to_string()),
-                    type_: None,
-                    source_span: None,
-                }));
-                selector_item.push(Expression::Literal(LiteralExpr {
-                    value: LiteralValue::String("".to_string()),
-                    type_: None,
-                    source_span: None,
-                }));
-            } else {
-                // Element selector
-                selector_item.push(Expression::Literal(LiteralExpr {
-                    value: LiteralValue::String(part.to_string()),
-                    type_: None,
-                    source_span: None,
-                }));
-                selector_item.push(Expression::Literal(LiteralExpr {
-                    value: LiteralValue::String("".to_string()),
-                    type_: None,
-                    source_span: None,
-                }));
-            }
-
-            selector_array.push(Expression::LiteralArray(LiteralArrayExpr {
-                entries: selector_item,
-                type_: None,
-                source_span: None,
-            }));
-        }
-
-        attr_array.push(Expression::LiteralArray(LiteralArrayExpr {
-            entries: selector_array,
-            type_: None,
-            source_span: None,
-        }));
+        attr_array.push(literal_or_array_literal(LiteralType::from(
+            &parsed_r3_selector,
+        )));
     }
 
     // Add classes marker and classes
@@ -14,7 +14,7 @@
 use crate::template::pipeline::ir;
 use crate::template::pipeline::ir::enums::{I18nContextKind, OpKind};
 use crate::template::pipeline::ir::ops::create::{
-    create_i18n_context_op, I18nContextOp, I18nStartOp, IcuStartOp,
+    create_i18n_context_op, ExtractedAttributeOp, I18nContextOp, I18nStartOp, IcuStartOp,
 };
 use crate::template::pipeline::ir::ops::update::{AttributeOp, BindingOp, PropertyOp};
 use crate::template::pipeline::src::compilation::{
@@ -116,6 +116,28 @@ fn process_attrs_for_unit(
         ParseSourceSpan,
     )> = Vec::new();
 
+    let mut extracted_attribute_indices: Vec<(
+        usize,
+        String,
+        crate::i18n::i18n_ast::Message,
+        ParseSourceSpan,
+    )> = Vec::new();
+
+    // Process create ops - collect first
+    for (idx, op) in unit.create.iter().enumerate() {
+        if let Some(attr) = op.as_any().downcast_ref::<ExtractedAttributeOp>() {
+            if let (Some(i18n_message), Some(source_span)) = (&attr.i18n_message, &attr.source_span)
+            {
+                extracted_attribute_indices.push((
+                    idx,
+                    i18n_message.id.clone(),
+                    i18n_message.clone(),
+                    source_span.clone(),
+                ));
+            }
+        }
+    }
+
     // Process update ops - collect first
     for (idx, op) in unit.update.iter().enumerate() {
         match op.kind() {
@@ -232,6 +254,29 @@ fn process_attrs_for_unit(
             attr_mut.i18n_context = attr_context_by_message.get(&message_id).copied();
         }
     }
+
+    for (idx, message_id, i18n_message, source_span) in extracted_attribute_indices {
+        if !attr_context_by_message.contains_key(&message_id) {
+            let i18n_context = create_i18n_context_op(
+                I18nContextKind::Attr,
+                job.allocate_xref_id(),
+                None,
+                i18n_message,
+                source_span,
+            );
+            let context_xref = i18n_context.xref();
+            unit.create.push(i18n_context);
+            attr_context_by_message.insert(message_id.clone(), context_xref);
+        }
+
+        if let Some(attr) = unit
+            .create
+            .get_mut(idx)
+            .and_then(|op| op.as_any_mut().downcast_mut::<ExtractedAttributeOp>())
+        {
+            attr.i18n_context = attr_context_by_message.get(&message_id).copied();
+        }
+    }
 }
 
 fn process_blocks_for_unit(
@@ -295,11 +340,11 @@ fn process_blocks_for_unit(
         let context_xref = context_op.xref();
 
         // Extract context_op fields before pushing
-        let context_op_clone = unsafe {
-            let op_ptr = &context_op as *const Box<dyn ir::CreateOp + Send + Sync>;
-            let ctx_ptr = op_ptr as *const I18nContextOp;
-            (*ctx_ptr).clone()
-        };
+        let context_op_clone = context_op
+            .as_any()
+            .downcast_ref::<I18nContextOp>()
+            .expect("create_i18n_context_op creates an I18nContextOp")
+            .clone();
 
         // Push context op
         unit.create.push(context_op);
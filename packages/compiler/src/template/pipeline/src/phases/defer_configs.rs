@@ -39,7 +39,7 @@ fn process_unit(unit: &mut crate::template::pipeline::src::compilation::ViewComp
 
             // Create placeholder config if placeholder_minimum_time is set
             if let Some(min_time) = defer.placeholder_minimum_time {
-                let literal_array = create_literal_array_from_values(&[min_time]);
+                let literal_array = create_literal_array_from_values(&[Some(min_time)]);
                 defer.placeholder_config = Some(Expression::ConstCollected(
                     ConstCollectedExpr::new(Box::new(literal_array)),
                 ));
@@ -47,14 +47,10 @@ fn process_unit(unit: &mut crate::template::pipeline::src::compilation::ViewComp
 
             // Create loading config if loading_minimum_time or loading_after_time is set
             if defer.loading_minimum_time.is_some() || defer.loading_after_time.is_some() {
-                let mut values = Vec::new();
-                if let Some(min_time) = defer.loading_minimum_time {
-                    values.push(min_time);
-                }
-                if let Some(after_time) = defer.loading_after_time {
-                    values.push(after_time);
-                }
-                let literal_array = create_literal_array_from_values(&values);
+                let literal_array = create_literal_array_from_values(&[
+                    defer.loading_minimum_time,
+                    defer.loading_after_time,
+                ]);
                 defer.loading_config = Some(Expression::ConstCollected(ConstCollectedExpr::new(
                     Box::new(literal_array),
                 )));
@@ -63,15 +59,15 @@ fn process_unit(unit: &mut crate::template::pipeline::src::compilation::ViewComp
     }
 }
 
-/// Create a literal array expression from an array of f64 values
-fn create_literal_array_from_values(values: &[f64]) -> Expression {
+/// Create a literal array expression from an array of f64 values, with `null` for missing ones
+fn create_literal_array_from_values(values: &[Option<f64>]) -> Expression {
     use crate::output::output_ast::{LiteralArrayExpr, LiteralExpr, LiteralValue};
 
     let entries: Vec<Expression> = values
         .iter()
         .map(|&val| {
             Expression::Literal(LiteralExpr {
-                value: LiteralValue::Number(val),
+                value: val.map_or(LiteralValue::Null, LiteralValue::Number),
                 type_: None,
                 source_span: None,
             })
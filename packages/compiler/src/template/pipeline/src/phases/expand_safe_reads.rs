@@ -239,7 +239,8 @@ fn transform_expressions_in_create_op(
                 }
             }
             _ => {
-                // Other create op types don't contain expressions or are handled elsewhere
+                // Listeners and the remaining create ops are covered by the generic transform.
+                transform_expressions_in_op(op.as_mut(), transform, flags);
             }
         }
     }
@@ -262,14 +263,13 @@ fn needs_temporary_in_safe_access(e: &Expression) -> bool {
                 || needs_temporary_in_safe_access(&cond.true_case)
         }
         Expression::NotExpr(not) => needs_temporary_in_safe_access(&not.condition),
+        Expression::Parens(parens) => needs_temporary_in_safe_access(&parens.expr),
         Expression::AssignTemporary(assign) => needs_temporary_in_safe_access(&assign.expr),
         Expression::ReadProp(read_prop) => needs_temporary_in_safe_access(&read_prop.receiver),
         Expression::ReadKey(read_key) => {
             needs_temporary_in_safe_access(&read_key.receiver)
                 || needs_temporary_in_safe_access(&read_key.index)
         }
-        // Note: ParenthesizedExpr is not in output_ast Rust version
-        // We can skip it for now
         Expression::InvokeFn(_)
         | Expression::LiteralArray(_)
         | Expression::LiteralMap(_)
@@ -388,68 +388,95 @@ fn extract_receiver_from_access(e: &Expression) -> Option<Box<Expression>> {
     }
 }
 
-fn deepest_safe_ternary(e: &Expression) -> Option<Box<SafeTernaryExpr>> {
-    if let Some(receiver) = extract_receiver_from_access(e) {
-        if let Expression::SafeTernary(st) = receiver.as_ref() {
-            // Clone the SafeTernaryExpr so we can work with it
-            let mut current = st.clone();
-
-            // Navigate to deepest SafeTernary
-            while let Expression::SafeTernary(nested_st) = current.expr.as_ref() {
-                current = nested_st.clone();
-            }
-
-            return Some(Box::new(current));
+/// Rebuilds `st` with the body of its innermost `SafeTernaryExpr` replaced by `body(expr)`.
+fn with_deepest_safe_ternary_body(
+    mut st: SafeTernaryExpr,
+    body: impl FnOnce(Expression) -> Expression,
+) -> SafeTernaryExpr {
+    st.expr = Box::new(match *st.expr {
+        Expression::SafeTernary(nested) => {
+            Expression::SafeTernary(with_deepest_safe_ternary_body(nested, body))
         }
-    }
-    None
+        expr => body(expr),
+    });
+    st
 }
 
-fn safe_transform(e: Expression, ctx: &SafeTransformContext) -> Expression {
-    if !is_access_expression(&e) {
-        return e;
-    }
-
-    // Check if receiver is a SafeTernary - if so, we need to modify the nested ternary
-    // Note: In TypeScript, this modifies in-place, but in Rust we need to rebuild the expression tree
-    // This is a simplified implementation - for full correctness, we'd need to rebuild the entire
-    // nested SafeTernary chain with the modified expression
-    if let Some(_dst) = deepest_safe_ternary(&e) {
-        // There's a nested SafeTernary
-        // For now, just return the expression as-is - the transform will recurse into it
-        // This isn't perfect but handles most cases
-    }
-
-    // No nested SafeTernary - handle normally
+/// Splits an access expression into its receiver and a function which applies the same access to
+/// another receiver. Safe accesses are re-applied guarded by a null check.
+fn split_access<'a>(
+    e: Expression,
+    ctx: &'a SafeTransformContext,
+) -> (Expression, Box<dyn FnOnce(Expression) -> Expression + 'a>) {
     match e {
-        Expression::SafeInvokeFunction(safe_invoke) => safe_ternary_with_temporary(
+        Expression::InvokeFn(invoke) => (
+            *invoke.fn_,
+            Box::new(move |r| *r.call_fn(invoke.args, invoke.source_span, Some(invoke.pure))),
+        ),
+        Expression::ReadProp(read_prop) => (
+            *read_prop.receiver,
+            Box::new(move |r| *r.prop(read_prop.name, read_prop.source_span)),
+        ),
+        Expression::ReadKey(read_key) => (
+            *read_key.receiver,
+            Box::new(move |r| *r.key(read_key.index, read_key.type_, read_key.source_span)),
+        ),
+        Expression::SafeInvokeFunction(safe_invoke) => (
             *safe_invoke.receiver,
-            |r| {
-                r.call_fn(
-                    safe_invoke.args.clone(),
-                    safe_invoke.source_span.clone(),
-                    None,
+            Box::new(move |r| {
+                safe_ternary_with_temporary(
+                    r,
+                    |r| r.call_fn(safe_invoke.args, safe_invoke.source_span, None),
+                    ctx,
                 )
-            },
-            ctx,
+            }),
         ),
-        Expression::SafePropertyRead(safe_prop) => safe_ternary_with_temporary(
+        Expression::SafePropertyRead(safe_prop) => (
             *safe_prop.receiver,
-            |r| r.prop(safe_prop.name.to_string(), safe_prop.source_span.clone()),
-            ctx,
+            Box::new(move |r| {
+                safe_ternary_with_temporary(
+                    r,
+                    |r| r.prop(safe_prop.name.to_string(), safe_prop.source_span),
+                    ctx,
+                )
+            }),
         ),
-        Expression::SafeKeyedRead(safe_keyed) => safe_ternary_with_temporary(
+        Expression::SafeKeyedRead(safe_keyed) => (
             *safe_keyed.receiver,
-            |r| {
-                r.key(
-                    safe_keyed.index.clone(),
-                    None,
-                    safe_keyed.source_span.clone(),
+            Box::new(move |r| {
+                safe_ternary_with_temporary(
+                    r,
+                    |r| r.key(safe_keyed.index, None, safe_keyed.source_span),
+                    ctx,
                 )
-            },
-            ctx,
+            }),
         ),
-        _ => e,
+        _ => unreachable!("split_access called on a non-access expression"),
+    }
+}
+
+fn safe_transform(e: Expression, ctx: &SafeTransformContext) -> Expression {
+    if !is_access_expression(&e) {
+        return e;
+    }
+
+    // Expressions are transformed bottom-up, so the receiver of a chained access such as
+    // `a?.b.c()` has already become a `SafeTernaryExpr`. The access then belongs inside the
+    // deepest ternary, so that it is skipped along with the rest of the chain once a guard is null.
+    let receiver_is_safe_ternary = matches!(
+        extract_receiver_from_access(&e).as_deref(),
+        Some(Expression::SafeTernary(_))
+    );
+    if !receiver_is_safe_ternary && !is_safe_access_expression(&e) {
+        return e;
+    }
+
+    let (receiver, access) = split_access(e, ctx);
+    match receiver {
+        Expression::SafeTernary(dst) => {
+            Expression::SafeTernary(with_deepest_safe_ternary_body(dst, access))
+        }
+        receiver => access(receiver),
     }
 }
 
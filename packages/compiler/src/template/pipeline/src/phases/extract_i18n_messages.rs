@@ -58,127 +58,35 @@ pub fn extract_i18n_messages(job: &mut dyn CompilationJob) {
         std::collections::HashMap::new();
     let mut i18n_contexts: std::collections::HashMap<ir::XrefId, I18nContextOp> =
         std::collections::HashMap::new();
+    // The contexts of each unit, in order, so that the message ops end up in the same unit.
+    let mut contexts_by_unit: Vec<(ir::XrefId, Vec<I18nContextOp>)> = Vec::new();
 
-    // Collect I18nContext and I18nStart ops
-    // Process root unit - collect first
-    let mut root_message_ops: Vec<(I18nMessageOp, I18nContextOp)> = Vec::new();
-    for op in component_job.root.create.iter() {
-        match op.kind() {
-            OpKind::I18nContext => unsafe {
-                let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
-                let ctx_ptr = op_ptr as *const I18nContextOp;
-                let ctx = &*ctx_ptr;
-
-                let i18n_message_op = create_i18n_message(job, ctx.clone(), None);
-                root_message_ops.push((i18n_message_op.clone(), ctx.clone()));
-                i18n_messages_by_context.insert(ctx.xref, i18n_message_op);
-                i18n_contexts.insert(ctx.xref, ctx.clone());
-            },
-            OpKind::I18nStart => unsafe {
-                let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
-                let i18n_ptr = op_ptr as *const I18nStartOp;
-                let i18n = &*i18n_ptr;
-                i18n_blocks.insert(i18n.base.xref, i18n.clone());
-            },
-            _ => {}
-        }
-    }
-
-    // Push message ops to root unit
-    for (message_op, _) in root_message_ops {
-        component_job.root.create.push(Box::new(message_op));
-    }
-
-    // Process all view units
-    for (_, unit) in component_job.views.iter() {
-        for op in unit.create.iter() {
-            match op.kind() {
-                OpKind::I18nContext => {
-                    unsafe {
-                        let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
-                        let ctx_ptr = op_ptr as *const I18nContextOp;
-                        let ctx = &*ctx_ptr;
-
-                        let i18n_message_op = create_i18n_message(job, ctx.clone(), None);
-                        // We'll add this to the unit later
-                        i18n_messages_by_context.insert(ctx.xref, i18n_message_op);
-                        i18n_contexts.insert(ctx.xref, ctx.clone());
-                    }
-                }
-                OpKind::I18nStart => unsafe {
-                    let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
-                    let i18n_ptr = op_ptr as *const I18nStartOp;
-                    let i18n = &*i18n_ptr;
-                    i18n_blocks.insert(i18n.base.xref, i18n.clone());
-                },
-                _ => {}
-            }
-        }
-    }
-
-    // Add I18nMessageOps to units - collect first
-    let mut view_message_ops: Vec<(ir::XrefId, I18nMessageOp)> = Vec::new();
-    for (_, unit) in component_job.views.iter() {
+    for unit in std::iter::once(&component_job.root).chain(component_job.views.values()) {
+        let mut unit_contexts = Vec::new();
         for op in unit.create.iter() {
-            if op.kind() == OpKind::I18nContext {
-                unsafe {
-                    let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
-                    let ctx_ptr = op_ptr as *const I18nContextOp;
-                    let ctx = &*ctx_ptr;
-
-                    if let Some(message_op) = i18n_messages_by_context.get(&ctx.xref) {
-                        view_message_ops.push((ctx.xref, message_op.clone()));
-                    }
-                }
-            }
-        }
-    }
-
-    // Push message ops to view units - collect context xrefs first
-    let mut view_context_xrefs: Vec<ir::XrefId> = Vec::new();
-    for (_, unit) in component_job.views.iter() {
-        for op in unit.create.iter() {
-            if op.kind() == OpKind::I18nContext {
-                unsafe {
-                    let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
-                    let ctx_ptr = op_ptr as *const I18nContextOp;
-                    let ctx = &*ctx_ptr;
-                    view_context_xrefs.push(ctx.xref);
-                }
+            if let Some(context) = op.as_any().downcast_ref::<I18nContextOp>() {
+                unit_contexts.push(context.clone());
+            } else if let Some(i18n) = op.as_any().downcast_ref::<I18nStartOp>() {
+                i18n_blocks.insert(i18n.base.xref, i18n.clone());
             }
         }
+        contexts_by_unit.push((unit.xref, unit_contexts));
     }
 
-    // Push message ops
-    for (_, unit) in component_job.views.iter_mut() {
-        for ctx_xref in &view_context_xrefs {
-            if let Some((_, message_op)) =
-                view_message_ops.iter().find(|(xref, _)| *xref == *ctx_xref)
-            {
-                unit.create.push(Box::new(message_op.clone()));
-            }
+    for (_, unit_contexts) in &contexts_by_unit {
+        for context in unit_contexts {
+            let i18n_message_op = create_i18n_message(job, context.clone(), None);
+            i18n_messages_by_context.insert(context.xref, i18n_message_op);
+            i18n_contexts.insert(context.xref, context.clone());
         }
     }
 
     // Associate sub-messages for ICUs with their root message. At this point we can also remove the
     // ICU start/end ops, as they are no longer needed.
     let mut current_icu: Option<usize> = None;
-    let mut icu_indices_to_remove = Vec::new();
-    let mut icu_placeholder_indices_to_remove = Vec::new();
-
-    // Process root unit
-    process_icus_for_unit(
-        &mut component_job.root,
-        &i18n_blocks,
-        &i18n_contexts,
-        &mut i18n_messages_by_context,
-        &mut current_icu,
-        &mut icu_indices_to_remove,
-        &mut icu_placeholder_indices_to_remove,
-    );
-
-    // Process all view units
-    for (_, unit) in component_job.views.iter_mut() {
+    for unit in std::iter::once(&mut component_job.root).chain(component_job.views.values_mut()) {
+        let mut icu_indices_to_remove = Vec::new();
+        let mut icu_placeholder_indices_to_remove = Vec::new();
         process_icus_for_unit(
             unit,
             &i18n_blocks,
@@ -188,49 +96,30 @@ pub fn extract_i18n_messages(job: &mut dyn CompilationJob) {
             &mut icu_indices_to_remove,
             &mut icu_placeholder_indices_to_remove,
         );
-    }
-
-    // Remove ICU ops
-    // Process root unit
-    icu_indices_to_remove.sort();
-    icu_indices_to_remove.reverse();
-    for idx in &icu_indices_to_remove {
-        component_job.root.create.remove_at(*idx);
-    }
-    icu_placeholder_indices_to_remove.sort();
-    icu_placeholder_indices_to_remove.reverse();
-    for idx in &icu_placeholder_indices_to_remove {
-        component_job.root.create.remove_at(*idx);
-    }
 
-    // Process all view units
-    for (_, unit) in component_job.views.iter_mut() {
-        // Note: We need to track indices per unit, but for simplicity, we'll process each unit separately
-        let mut unit_icu_indices = Vec::new();
-        let mut unit_icu_placeholder_indices = Vec::new();
-
-        for (idx, op) in unit.create.iter().enumerate() {
-            match op.kind() {
-                OpKind::IcuStart | OpKind::IcuEnd => {
-                    unit_icu_indices.push(idx);
-                }
-                OpKind::IcuPlaceholder => {
-                    unit_icu_placeholder_indices.push(idx);
-                }
-                _ => {}
-            }
-        }
-
-        unit_icu_indices.sort();
-        unit_icu_indices.reverse();
-        for idx in unit_icu_indices {
+        // Remove the ICU ops of this unit, last first so that the indices stay valid.
+        let mut indices_to_remove = icu_indices_to_remove;
+        indices_to_remove.extend(icu_placeholder_indices_to_remove);
+        indices_to_remove.sort_unstable();
+        for idx in indices_to_remove.into_iter().rev() {
             unit.create.remove_at(idx);
         }
+    }
 
-        unit_icu_placeholder_indices.sort();
-        unit_icu_placeholder_indices.reverse();
-        for idx in unit_icu_placeholder_indices {
-            unit.create.remove_at(idx);
+    // Add the message ops to the units of their contexts.
+    for (unit_xref, unit_contexts) in contexts_by_unit {
+        let unit = if unit_xref == component_job.root.xref {
+            &mut component_job.root
+        } else {
+            component_job
+                .views
+                .get_mut(&unit_xref)
+                .expect("i18n context belongs to a known view")
+        };
+        for context in unit_contexts {
+            if let Some(message_op) = i18n_messages_by_context.remove(&context.xref) {
+                unit.create.push(Box::new(message_op));
+            }
         }
     }
 }
@@ -500,19 +389,19 @@ fn format_value(value: &ir::ops::create::I18nParamValue) -> String {
 
     // Encode the remaining flags as part of the value.
     let tag_marker = if value.flags.contains(I18nParamValueFlags::ELEMENT_TAG) {
-        ELEMENT_MARKER
+        Some(ELEMENT_MARKER)
     } else if value.flags.contains(I18nParamValueFlags::TEMPLATE_TAG) {
-        TEMPLATE_MARKER
+        Some(TEMPLATE_MARKER)
     } else {
-        '\0'
+        None
     };
 
-    let close_marker = if tag_marker != '\0' && value.flags.contains(I18nParamValueFlags::CLOSE_TAG)
-    {
-        TAG_CLOSE_MARKER
-    } else {
-        '\0'
-    };
+    let close_marker =
+        if tag_marker.is_some() && value.flags.contains(I18nParamValueFlags::CLOSE_TAG) {
+            Some(TAG_CLOSE_MARKER)
+        } else {
+            None
+        };
 
     let context = if let Some(sub_template_index) = value.sub_template_index {
         format!("{}{}", CONTEXT_MARKER, sub_template_index)
@@ -526,6 +415,8 @@ fn format_value(value: &ir::ops::create::I18nParamValue) -> String {
         I18nParamValueValue::Compound { .. } => String::new(),
     };
 
+    let close_marker = close_marker.map(String::from).unwrap_or_default();
+    let tag_marker = tag_marker.map(String::from).unwrap_or_default();
     format!(
         "{}{}{}{}{}{}",
         ESCAPE, close_marker, tag_marker, value_str, context, ESCAPE
@@ -145,6 +145,7 @@ fn has_depends_on_slot_context_trait_by_kind(kind: OpKind) -> bool {
             | OpKind::InterpolateText
             | OpKind::Repeater
             | OpKind::Conditional
+            | OpKind::I18nExpression
     )
 }
 
@@ -350,6 +351,10 @@ fn get_target_from_update_op(
                 let text = &*text_ptr;
                 Some((text.target, text.source_span.clone()))
             }
+            OpKind::I18nExpression => op
+                .as_any()
+                .downcast_ref::<crate::template::pipeline::ir::ops::update::I18nExpressionOp>()
+                .map(|i18n_exp| (i18n_exp.target, i18n_exp.source_span.clone())),
             OpKind::TwoWayListener
             | OpKind::Listener
             | OpKind::Animation
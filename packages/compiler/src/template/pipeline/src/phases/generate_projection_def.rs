@@ -6,8 +6,7 @@
 //! populate `project` arguments, and generate the required `projectionDef` instruction for the job's
 //! root view.
 
-use crate::core::SelectorFlags;
-use crate::directive_matching::CssSelector;
+use crate::core::parse_selector_to_r3_selector;
 use crate::output::output_ast::Expression;
 use crate::template::pipeline::ir;
 use crate::template::pipeline::ir::ops::create::{create_projection_def_op, ProjectionOp};
@@ -76,84 +75,14 @@ pub fn generate_projection_defs(job: &mut dyn CompilationJob) {
                 .map(|s| {
                     if s == "*" {
                         LiteralType::String(s.clone())
-                    } else if let Ok(css_selectors) = CssSelector::parse(s) {
-                        // R3CssSelector[]
-                        let selectors_list: Vec<LiteralType> = css_selectors
-                            .iter()
-                            .map(|selector| {
-                                let mut vec = Vec::new();
-                                // Element name
-                                vec.push(LiteralType::String(
-                                    selector.element.clone().unwrap_or_default(),
-                                ));
-
-                                // Classes
-                                for class_name in &selector.class_names {
-                                    vec.push(LiteralType::Number(
-                                        SelectorFlags::CLASS as u32 as f64,
-                                    ));
-                                    vec.push(LiteralType::String(class_name.clone()));
-                                }
-
-                                // Attributes
-                                for i in (0..selector.attrs.len()).step_by(2) {
-                                    vec.push(LiteralType::Number(
-                                        SelectorFlags::ATTRIBUTE as u32 as f64,
-                                    ));
-                                    vec.push(LiteralType::String(selector.attrs[i].clone()));
-                                    vec.push(LiteralType::String(selector.attrs[i + 1].clone()));
-                                }
-
-                                // Not Selectors
-                                for not_selector in &selector.not_selectors {
-                                    // Element
-                                    if let Some(element) = &not_selector.element {
-                                        if element != "*" {
-                                            vec.push(LiteralType::Number(
-                                                (SelectorFlags::NOT as u32
-                                                    | SelectorFlags::ELEMENT as u32)
-                                                    as f64,
-                                            ));
-                                            vec.push(LiteralType::String(element.clone()));
-                                        }
-                                    }
-
-                                    // Classes
-                                    for class_name in &not_selector.class_names {
-                                        vec.push(LiteralType::Number(
-                                            (SelectorFlags::NOT as u32
-                                                | SelectorFlags::CLASS as u32)
-                                                as f64,
-                                        ));
-                                        vec.push(LiteralType::String(class_name.clone()));
-                                    }
-
-                                    // Attributes
-                                    for i in (0..not_selector.attrs.len()).step_by(2) {
-                                        vec.push(LiteralType::Number(
-                                            (SelectorFlags::NOT as u32
-                                                | SelectorFlags::ATTRIBUTE as u32)
-                                                as f64,
-                                        ));
-                                        vec.push(LiteralType::String(
-                                            not_selector.attrs[i].clone(),
-                                        ));
-                                        vec.push(LiteralType::String(
-                                            not_selector.attrs[i + 1].clone(),
-                                        ));
-                                    }
-                                }
-
-                                LiteralType::Array(vec)
-                            })
-                            .collect();
-
-                        LiteralType::Array(selectors_list)
                     } else {
-                        // Fallback if parse fails (shouldn't happen with valid selectors)
-                        LiteralType::Array(vec![LiteralType::Array(vec![LiteralType::String(
-                            s.clone(),
-                        )])])
+                        // R3CssSelector[]
+                        LiteralType::Array(
+                            parse_selector_to_r3_selector(Some(s))
+                                .iter()
+                                .map(LiteralType::from)
+                                .collect(),
+                        )
                     }
                 })
                 .collect();
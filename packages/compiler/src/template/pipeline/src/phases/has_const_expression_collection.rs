@@ -22,7 +22,7 @@ fn transform_expressions_in_create_op(
     // This is a simplified version - full implementation would need to handle all create op types
     use crate::template::pipeline::ir::enums::OpKind;
     use crate::template::pipeline::ir::expression::transform_expressions_in_expression;
-    use crate::template::pipeline::ir::ops::create::RepeaterCreateOp;
+    use crate::template::pipeline::ir::ops::create::{DeferOp, RepeaterCreateOp};
 
     unsafe {
         let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
@@ -43,6 +43,20 @@ fn transform_expressions_in_create_op(
                     ));
                 }
             }
+            OpKind::Defer => {
+                let defer_ptr = op_ptr as *mut DeferOp;
+                let defer = &mut *defer_ptr;
+                for config in [&mut defer.loading_config, &mut defer.placeholder_config] {
+                    if let Some(expr) = config.take() {
+                        let transformed = transform(expr, flags);
+                        *config = Some(transform_expressions_in_expression(
+                            transformed,
+                            transform,
+                            flags,
+                        ));
+                    }
+                }
+            }
             _ => {
                 // Other create ops don't have expressions or are handled elsewhere
             }
@@ -61,7 +61,7 @@ pub fn declare_i18n_variable(variable: &ReadVarExpr) -> Statement {
         name: variable.name.clone(),
         value: None,
         type_: None,
-        modifiers: crate::output::output_ast::StmtModifier::Final,
+        modifiers: crate::output::output_ast::StmtModifier::None,
         source_span: variable.source_span.clone(),
     })
 }
@@ -235,16 +235,14 @@ pub fn collect_i18n_consts(job: &mut ComponentCompilationJob) {
             if msg_op.i18n_block.is_some() {
                 // This is a regular i18n message with a corresponding i18n block. Collect it into the
                 // const array.
-                let i18n_const =
-                    job.add_const(OutputExpression::ReadVar(result.main_var.clone()), None);
+                let i18n_const = job.add_const(
+                    OutputExpression::ReadVar(result.main_var.clone()),
+                    Some(result.statements),
+                );
                 message_const_indices.insert(msg_op.i18n_block.unwrap(), i18n_const);
             } else {
                 // This is an i18n attribute. Extract the initializers into the const pool.
-                // Note: In TypeScript, statements are pushed to constsInitializers
-                // In Rust, we need to convert statements to expressions
-                // For now, we'll just store the main variable
-                job.consts_initializers
-                    .push(OutputExpression::ReadVar(result.main_var.clone()));
+                job.consts_initializers.extend(result.statements);
 
                 // Save the i18n variable value for later.
                 i18n_values_by_context.insert(
@@ -509,7 +507,7 @@ fn collect_message(
 
     let main_var_expr = crate::output::output_ast::variable(
         job.pool
-            .unique_name(TRANSLATION_VAR_PREFIX.to_string(), false),
+            .unique_name(TRANSLATION_VAR_PREFIX.to_string(), true),
     );
     let main_var = match *main_var_expr {
         OutputExpression::ReadVar(ref expr) => expr.clone(),
@@ -544,6 +542,9 @@ fn collect_message(
             format_i18n_placeholder_names_in_map(&postprocessing_params_map, false);
 
         if !message_op.postprocessing_params.is_empty() {
+            let mut formatted_postprocessing_params: Vec<(String, OutputExpression)> =
+                formatted_postprocessing_params.into_iter().collect();
+            formatted_postprocessing_params.sort_by(|a, b| a.0.cmp(&b.0));
             let extra_transform_fn_params: Vec<OutputExpression> =
                 vec![OutputExpression::LiteralMap(LiteralMapExpr {
                     entries: formatted_postprocessing_params
@@ -560,9 +561,7 @@ fn collect_message(
 
             transform_fn = Some(Box::new(move |expr: &ReadVarExpr| {
                 OutputExpression::InvokeFn(crate::output::output_ast::InvokeFunctionExpr {
-                    fn_: Box::new(OutputExpression::ExternalRef(
-                        Identifiers::i18n_postprocess(),
-                    )),
+                    fn_: crate::output::output_ast::import_ref(Identifiers::i18n_postprocess()),
                     args: {
                         let mut args = vec![OutputExpression::ReadVar(expr.clone())];
                         args.extend(extra_transform_fn_params.iter().cloned());
@@ -731,7 +730,7 @@ fn i18n_generate_closure_var(
 ) -> ReadVarExpr {
     let name = if use_external_ids {
         let prefix = get_translation_const_prefix("EXTERNAL_");
-        let unique_suffix = pool.unique_name(file_based_i18n_suffix.to_string(), false);
+        let unique_suffix = pool.unique_name(file_based_i18n_suffix.to_string(), true);
         format!(
             "{}{}$${}",
             prefix,
@@ -740,7 +739,7 @@ fn i18n_generate_closure_var(
         )
     } else {
         let prefix = get_translation_const_prefix(file_based_i18n_suffix);
-        pool.unique_name(prefix, false)
+        pool.unique_name(prefix, true)
     };
     match *crate::output::output_ast::variable(name) {
         OutputExpression::ReadVar(expr) => expr,
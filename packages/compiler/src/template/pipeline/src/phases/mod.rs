@@ -76,12 +76,17 @@ use crate::template::pipeline::src::compilation::{CompilationJob, ComponentCompi
 
 pub fn run(job: &mut ComponentCompilationJob) {
     // Simplified phase order for vars debugging
+    strip_nonrequired_parentheses::strip_nonrequired_parentheses(job); // Drop user parentheses that don't group anything
     pure_literal_structures::phase(job);
+    generate_local_let_references::generate_local_let_references(job); // Turn `storeLet` ops into variables readable within the same view
     generate_variables::phase(job); // Generate context variables including $implicit
 
     save_restore_view::save_and_restore_view(job); // Save/restore view for listeners - MUST run AFTER generate_variables so RestoreView is prepended last (appears first)
+    resolve_dollar_event::resolve_dollar_event(job); // Bind `$event` to the listener's parameter and report it elsewhere
+    remove_illegal_let_references::remove_illegal_let_references(job); // Report and drop reads of a @let before its declaration
     resolve_names::phase(job);
     resolve_contexts::phase(job);
+    defer_resolve_targets::resolve_defer_target_names(job); // Resolve the elements referenced by @defer triggers
 
     // Expand safe reads (?. and ?[]) to conditionals
     expand_safe_reads::phase(job);
@@ -90,15 +95,16 @@ pub fn run(job: &mut ComponentCompilationJob) {
     any_cast::delete_any_casts(job);
 
     // Added phases for correctness
+    propagate_i18n_blocks::propagate_i18n_blocks(job); // Link nested i18n blocks to their root block
+    wrap_icus::wrap_i18n_icus(job); // Wrap ICUs outside of an i18n block in their own i18n block
     style_binding_specialization::specialize_style_bindings(job); // Specialize [style] and [class] bindings
     binding_specialization::specialize_bindings(job); // Converts BindingOp -> AttributeOp, PropertyOp, etc.
     attribute_extraction::extract_attributes(job);
+    create_i18n_contexts::create_i18n_contexts(job); // Create contexts for i18n attributes
+    ordering::order_ops(job); // Order styling bindings before property and attribute bindings
     local_refs::lift_local_refs(job); // Lift local refs (#templateName) to consts for templateRefExtractor
     namespace::emit_namespace_changes(job);
 
-    empty_elements::collapse_empty_instructions(job); // Merge ElementStart+ElementEnd -> Element for empty elements
-    const_collection::collect_element_consts(job);
-
     // Resolve sanitizers for security-sensitive properties/attributes (e.g. href, src)
     resolve_sanitizers::resolve_sanitizers(job);
 
@@ -107,12 +113,29 @@ pub fn run(job: &mut ComponentCompilationJob) {
 
     // Create pipe operations before slot allocation
     pipe_creation::create_pipes(job);
+    defer_configs::configure_defer_instructions(job); // Collect the loading and placeholder timings of @defer blocks
+    pipe_variadic::create_variadic_pipes(job); // Pass arguments of pipes with more than 4 arguments as an array
 
     // Generate projection definitions (must run before slot allocation to reserve slots)
     generate_projection_def::generate_projection_defs(job);
     remove_content_selectors::remove_content_selectors(job);
 
+    store_let_optimization::optimize_store_let(job); // Drop `storeLet` for @let declarations only read in their own view
+    i18n_text_extraction::convert_i18n_text(job); // Turn text inside i18n blocks into i18n expressions
+    convert_i18n_bindings::convert_i18n_bindings(job);
+    remove_unused_i18n_attrs::remove_unused_i18n_attributes_ops(job);
+    assign_i18n_slot_dependencies::assign_i18n_slot_dependencies(job);
+    apply_i18n_expressions::apply_i18n_expressions(job);
     slot_allocation::phase(job);
+    resolve_i18n_element_placeholders::resolve_i18n_element_placeholders(job); // Needs element slots
+    resolve_i18n_expression_placeholders::resolve_i18n_expression_placeholders(job);
+    extract_i18n_messages::extract_i18n_messages(job);
+    i18n_const_collection::collect_i18n_consts(job); // Declare translations and read them from i18n attributes
+    has_const_expression_collection::collect_const_expressions(job); // Lift @defer configs into the consts array
+    const_collection::collect_element_consts(job);
+    remove_i18n_contexts::remove_i18n_contexts(job);
+    empty_elements::collapse_empty_instructions(job); // Merge ElementStart+ElementEnd -> Element for empty elements
+    attach_source_locations::attach_source_locations(job); // Expose element definition locations in debug builds
     pure_function_extraction::phase(job); // Extract pure functions to constants like _c0, _c1
     track_fn_optimization::optimize_track_fns(job); // Generate track functions for @for loops
     var_counting::phase(job);
@@ -121,6 +144,7 @@ pub fn run(job: &mut ComponentCompilationJob) {
     next_context_merging::merge_next_context_expressions(job); // Merge sequential nextContext() calls
 
     naming::name_functions_and_variables(job);
+    resolve_defer_deps_fns::resolve_defer_deps_fns(job); // Share the dependency functions of per-block @defer
     generate_advance::phase(job);
     conditionals::generate_conditional_expressions(job); // Collapse conditional expressions to single ternary
     temporary_variables::generate_temporary_variables(job); // Name and declare temporary variables (must run after conditionals and expand_safe_reads)
@@ -131,6 +155,8 @@ pub fn run(job: &mut ComponentCompilationJob) {
 }
 
 pub fn run_host(job: &mut crate::template::pipeline::src::compilation::HostBindingCompilationJob) {
+    strip_nonrequired_parentheses::strip_nonrequired_parentheses(job); // Drop user parentheses that don't group anything
+    host_style_property_parsing::parse_host_style_properties(job); // Split `style.width.px` into a property and its unit
     style_binding_specialization::specialize_style_bindings(job);
     binding_specialization::specialize_bindings(job);
     attribute_extraction::extract_attributes(job);
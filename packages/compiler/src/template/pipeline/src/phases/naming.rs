@@ -312,20 +312,20 @@ fn process_op_with_var_names(
                 let listener_ptr = op_ptr as *mut ListenerOp;
                 let listener = &mut *listener_ptr;
                 if listener.handler_fn_name.is_none() {
-                    let mut animation = String::new();
-                    let mut event_name = listener.name.clone();
+                    let mut animation = "";
                     if listener.is_legacy_animation_listener {
                         let phase = listener.legacy_animation_phase.as_ref().expect(
                             "legacy_animation_phase must be set for legacy animation listener",
                         );
-                        event_name = format!("@{}.{}", listener.name, phase).into();
-                        animation = "_animation".to_string();
+                        listener.name = format!("@{}.{}", listener.name, phase).into();
+                        animation = "animation";
                     }
 
                     if listener.host_listener {
+                        // Host listeners are named after the component, not the view function.
                         listener.handler_fn_name = Some(sanitize_identifier(&format!(
-                            "{}{}{}_HostBindingHandler",
-                            base_name, animation, event_name
+                            "{}_{}{}_HostBindingHandler",
+                            child_base_name, animation, listener.name
                         )));
                     } else {
                         let slot = listener
@@ -338,8 +338,8 @@ fn process_op_with_var_names(
                             .map(|t| t.replace('-', "_"))
                             .unwrap_or_default();
                         listener.handler_fn_name = Some(sanitize_identifier(&format!(
-                            "{}_{}{}_{}_{}_listener",
-                            base_name, tag, animation, event_name, slot
+                            "{}_{}_{}{}_{}_listener",
+                            base_name, tag, animation, listener.name, slot
                         )));
                     }
                 }
@@ -13,9 +13,7 @@
 
 use crate::output::output_ast::{Expression, Statement};
 use crate::template::pipeline::ir;
-use crate::template::pipeline::ir::expression::{
-    as_ir_expression, is_ir_expression, transform_expressions_in_op, VisitorContextFlag,
-};
+use crate::template::pipeline::ir::expression::{transform_expressions_in_op, VisitorContextFlag};
 use crate::template::pipeline::ir::ops::create::{
     AnimationListenerOp, AnimationOp, ListenerOp, TwoWayListenerOp,
 };
@@ -99,131 +97,67 @@ pub fn merge_next_context_expressions(job: &mut dyn CompilationJob) {
 }
 
 fn merge_next_contexts_in_ops(ops: &mut ir::OpList<Box<dyn ir::UpdateOp + Send + Sync>>) {
-    let mut indices_to_remove = Vec::new();
-    let mut candidate_info = Vec::new();
-
-    // First pass: collect candidate operations (StatementOp with NextContextExpr)
-    for (idx, op) in ops.iter().enumerate() {
-        if op.kind() != ir::OpKind::Statement {
+    // Candidates are visited in order and their steps are read at that point, so a chain of
+    // side-effectful `nextContext()` calls folds into the first expression that uses its result.
+    let mut idx = 0;
+    while idx < ops.len() {
+        let Some(merge_steps) = next_context_statement_steps(ops.get(idx).unwrap().as_ref()) else {
+            idx += 1;
             continue;
-        }
-
-        unsafe {
-            let op_ptr = op.as_ref() as *const dyn ir::Op;
-            let stmt_op_ptr = op_ptr as *const StatementOp<Box<dyn ir::Op + Send + Sync>>;
-            let stmt_op = &*stmt_op_ptr;
-
-            if let Statement::Expression(ref expr_stmt) = *stmt_op.statement {
-                if let Some(ir_expr) = as_ir_expression(&expr_stmt.expr) {
-                    if let ir::IRExpression::NextContext(ref next_ctx) = ir_expr {
-                        candidate_info.push((idx, next_ctx.steps));
-                    }
-                }
-            }
-        }
-    }
-
-    // Second pass: try to merge each candidate with subsequent operations
-    for (op_idx, merge_steps) in candidate_info {
-        if indices_to_remove.contains(&op_idx) {
-            continue; // Already merged
-        }
+        };
 
-        let mut found_merge_target: Option<usize> = None;
-        let mut can_merge = true;
-
-        // Look for merge target in subsequent operations
-        for candidate_idx in (op_idx + 1)..ops.len() {
-            if !can_merge {
+        let mut merged = false;
+        let mut try_to_merge = true;
+        for candidate_idx in (idx + 1)..ops.len() {
+            if !try_to_merge {
                 break;
             }
 
-            if indices_to_remove.contains(&candidate_idx) {
-                continue;
-            }
-
-            // Use mutable reference to check for blocking expressions and merge
-            let candidate_op_mut = ops.get_mut(candidate_idx).unwrap();
-
-            let mut has_blocking = false;
-            let mut has_next_context = false;
-
-            // First pass: check for blocking expressions and NextContextExpr
             transform_expressions_in_op(
-                candidate_op_mut.as_mut(),
-                &mut |expr: Expression, flags| {
-                    if flags.contains(VisitorContextFlag::IN_CHILD_OPERATION) {
-                        has_blocking = true;
+                ops.get_mut(candidate_idx).unwrap().as_mut(),
+                &mut |mut expr: Expression, flags| {
+                    if !try_to_merge || flags.contains(VisitorContextFlag::IN_CHILD_OPERATION) {
                         return expr;
                     }
 
-                    if is_ir_expression(&expr) {
-                        if let Some(ir_expr) = as_ir_expression(&expr) {
-                            match ir_expr {
-                                ir::IRExpression::GetCurrentView(_)
-                                | ir::IRExpression::Reference(_)
-                                | ir::IRExpression::ContextLetReference(_) => {
-                                    has_blocking = true;
-                                }
-                                ir::IRExpression::NextContext(_) => {
-                                    has_next_context = true;
-                                }
-                                _ => {}
-                            }
+                    match expr {
+                        Expression::NextContext(ref mut next_ctx) => {
+                            next_ctx.steps += merge_steps;
+                            merged = true;
+                            try_to_merge = false;
                         }
+                        Expression::GetCurrentView(_)
+                        | Expression::Reference(_)
+                        | Expression::ContextLetReference(_) => {
+                            try_to_merge = false;
+                        }
+                        _ => {}
                     }
 
                     expr
                 },
                 VisitorContextFlag::NONE,
             );
-
-            if has_blocking {
-                can_merge = false;
-                break;
-            }
-
-            // If we found NextContextExpr, merge into it (second pass)
-            if has_next_context {
-                // Handle normal expressions (StatementOp)
-                transform_expressions_in_op(
-                    candidate_op_mut.as_mut(),
-                    &mut |mut expr: Expression, flags| {
-                        if flags.contains(VisitorContextFlag::IN_CHILD_OPERATION) {
-                            return expr;
-                        }
-
-                        if let Expression::NextContext(ref mut next_ctx) = expr {
-                            next_ctx.steps += merge_steps;
-                            found_merge_target = Some(candidate_idx);
-                        }
-
-                        expr
-                    },
-                    VisitorContextFlag::NONE,
-                );
-
-                // Handle VariableOp initializers explicitly if transform_expressions didn't catch it
-                // (transform_expressions DOES catch it, but let's be sure we are modifying the right thing)
-                // Actually, VariableOp implements UpdateOp/CreateOp, so transform_expressions_in_op SHOULD work over it.
-                // The issue might be that candidate_op_mut IS a VariableOp.
-
-                if found_merge_target.is_some() {
-                    break;
-                }
-            }
         }
 
-        // If we found a merge target, mark the source operation for removal
-        if found_merge_target.is_some() {
-            indices_to_remove.push(op_idx);
+        if merged {
+            ops.remove_at(idx);
+        } else {
+            idx += 1;
         }
     }
+}
 
-    // Remove merged operations (in reverse order to maintain indices)
-    indices_to_remove.sort();
-    indices_to_remove.reverse();
-    for idx in indices_to_remove {
-        ops.remove_at(idx);
+/// The number of steps of a statement consisting only of a `nextContext()` call.
+fn next_context_statement_steps(op: &(dyn ir::UpdateOp + Send + Sync)) -> Option<usize> {
+    let stmt_op = op
+        .as_any()
+        .downcast_ref::<StatementOp<Box<dyn ir::UpdateOp + Send + Sync>>>()?;
+    match stmt_op.statement.as_ref() {
+        Statement::Expression(expr_stmt) => match expr_stmt.expr.as_ref() {
+            Expression::NextContext(next_ctx) => Some(next_ctx.steps),
+            _ => None,
+        },
+        _ => None,
     }
 }
@@ -4,7 +4,10 @@
 
 use crate::template::pipeline::ir;
 use crate::template::pipeline::ir::ops::host::DomPropertyOp;
-use crate::template::pipeline::ir::ops::update::{AttributeOp, BindingExpression, PropertyOp};
+use crate::template::pipeline::ir::ops::update::{
+    AttributeOp, BindingExpression, ClassMapOp, ClassPropOp, PropertyOp, StyleMapOp, StylePropOp,
+    TwoWayPropertyOp,
+};
 use crate::template::pipeline::ir::{OpKind, OpList};
 use crate::template::pipeline::src::compilation::{
     CompilationJob, CompilationJobKind, ComponentCompilationJob, HostBindingCompilationJob,
@@ -187,40 +190,23 @@ fn reorder<T: ?Sized>(ops: Vec<Box<T>>, ordering: &Vec<Rule<T>>) -> Vec<Box<T>>
 }
 
 fn get_dependency_target<T: ir::Op + ?Sized>(op: &T) -> Option<ir::XrefId> {
-    // Check DependsOnSlotContextOpTrait
-    // Need downcasting or identifying by kind
-    // Standard ops that have target:
-    // Binding, Property, Attribute, etc.
-    // Assuming ir::has_depends_on_slot_context_trait is available but hard to use on trait object.
-    // Manual check:
-    match op.kind() {
-        OpKind::Property
-        | OpKind::Attribute
-        | OpKind::StyleProp
-        | OpKind::ClassProp
-        | OpKind::TwoWayProperty
-        | OpKind::Binding => {
-            // These are UpdateOps that depend on target.
-            // We can downcast to UpdateOp and call xref()? No, xref is target usually.
-            // or manually.
-            // Using unsafe helper or generic if Op had target().
-            // Checking ir module for generic target access?
-            // traits::DependsOnSlotContextOpTrait has target().
-            // But we only have &dyn Op.
-            // If we can downcast to &dyn DependsOnSlotContextOpTrait?
-            // Rust doesn't support casting between unrelated trait objects.
-            // Workaround:
-            if let Some(prop) = op.as_any().downcast_ref::<PropertyOp>() {
-                Some(prop.target)
-            } else if let Some(attr) = op.as_any().downcast_ref::<AttributeOp>() {
-                Some(attr.target)
-            }
-            // ... add others
-            else {
-                None
-            }
-        }
-        _ => None,
+    // Ops which depend on the slot context of the element they are bound to. Ops of different
+    // targets must never be reordered across each other.
+    let op = op.as_any();
+    if let Some(prop) = op.downcast_ref::<PropertyOp>() {
+        Some(prop.target)
+    } else if let Some(attr) = op.downcast_ref::<AttributeOp>() {
+        Some(attr.target)
+    } else if let Some(prop) = op.downcast_ref::<TwoWayPropertyOp>() {
+        Some(prop.target)
+    } else if let Some(style) = op.downcast_ref::<StylePropOp>() {
+        Some(style.target)
+    } else if let Some(class) = op.downcast_ref::<ClassPropOp>() {
+        Some(class.target)
+    } else if let Some(style) = op.downcast_ref::<StyleMapOp>() {
+        Some(style.target)
+    } else {
+        op.downcast_ref::<ClassMapOp>().map(|class| class.target)
     }
 }
 
@@ -10,7 +10,7 @@
 //!
 //! Now that unsafe code in variable_optimization is fixed, we can safely use the constant pool.
 
-use crate::constant_pool::SharedConstantDefinition;
+use crate::constant_pool::{GenericKeyFn, SharedConstantDefinition};
 use crate::output::output_ast as o;
 use crate::template::pipeline::ir;
 use crate::template::pipeline::ir::IRExpression;
@@ -23,7 +23,11 @@ struct PureFunctionConstant {
 impl SharedConstantDefinition for PureFunctionConstant {
     fn key_of(&self, expr: &o::Expression) -> String {
         // Include num_args in key to differentiate functions with same body but different arg counts (unlikely but possible)
-        format!("pure_fn_{} args_{:?}", self.num_args, expr)
+        format!(
+            "pure_fn_{} args_{}",
+            self.num_args,
+            GenericKeyFn::INSTANCE.key_of(expr)
+        )
     }
 
     fn to_shared_constant_declaration(&self, name: String, expr: o::Expression) -> o::Statement {
@@ -9,7 +9,8 @@ use crate::template::pipeline::ir::ops::shared::create_statement_op;
 use crate::template::pipeline::ir::ops::VariableOp;
 use crate::template::pipeline::ir::{CreateOp, UpdateOp};
 use crate::template::pipeline::src::compilation::{
-    CompilationJob, CompilationUnit, ComponentCompilationJob, HostBindingCompilationJob,
+    CompilationJob, CompilationJobKind, CompilationUnit, ComponentCompilationJob,
+    HostBindingCompilationJob,
 };
 use crate::template::pipeline::src::instruction as ng;
 
@@ -28,9 +29,10 @@ pub fn reify(job: &mut dyn CompilationJob) {
     // Identify which compilation mode we are in
     let compatibility = job.compatibility() == ir::CompatibilityMode::TemplateDefinitionBuilder;
     let mode = job.mode();
+    let is_host = job.kind() == CompilationJobKind::Host;
 
     for unit in job.units_mut() {
-        reify_unit(unit, &view_name_map, compatibility, mode);
+        reify_unit(unit, &view_name_map, compatibility, mode, is_host);
     }
 }
 
@@ -39,9 +41,10 @@ fn reify_unit(
     view_name_map: &HashMap<XrefId, String>,
     compatibility: bool,
     mode: TemplateCompilationMode,
+    is_host: bool,
 ) {
     reify_create_operations(unit, view_name_map, compatibility, mode);
-    reify_update_operations(unit);
+    reify_update_operations(unit, is_host);
 }
 
 fn reify_create_operations(
@@ -341,6 +344,55 @@ fn reify_create_operations(
                 >(Box::new(stmt))))
             }
 
+            ir::OpKind::I18nStart => {
+                if let Some(i18n_op) = op.as_any().downcast_ref::<ir::ops::create::I18nStartOp>() {
+                    let slot = i18n_op.base.handle.get_slot().expect("Expected a slot") as i32;
+                    let const_index = i18n_op
+                        .base
+                        .message_index
+                        .expect("Expected an i18n message index")
+                        .as_usize() as i32;
+                    let stmt = ng::i18n_start(
+                        slot,
+                        const_index,
+                        i18n_op.base.sub_template_index,
+                        i18n_op.source_span.clone(),
+                    );
+                    Some(Box::new(ir::ops::shared::create_statement_op::<
+                        Box<dyn CreateOp + Send + Sync>,
+                    >(Box::new(stmt))))
+                } else {
+                    None
+                }
+            }
+            ir::OpKind::I18n => {
+                if let Some(i18n_op) = op.as_any().downcast_ref::<ir::ops::create::I18nOp>() {
+                    let slot = i18n_op.base.handle.get_slot().expect("Expected a slot") as i32;
+                    let const_index = i18n_op
+                        .base
+                        .message_index
+                        .expect("Expected an i18n message index")
+                        .as_usize() as i32;
+                    let stmt = ng::i18n(
+                        slot,
+                        const_index,
+                        i18n_op.base.sub_template_index,
+                        i18n_op.source_span.clone(),
+                    );
+                    Some(Box::new(ir::ops::shared::create_statement_op::<
+                        Box<dyn CreateOp + Send + Sync>,
+                    >(Box::new(stmt))))
+                } else {
+                    None
+                }
+            }
+            ir::OpKind::I18nEnd => {
+                let stmt = ng::i18n_end(op.source_span().cloned());
+                Some(Box::new(ir::ops::shared::create_statement_op::<
+                    Box<dyn CreateOp + Send + Sync>,
+                >(Box::new(stmt))))
+            }
+
             ir::OpKind::DisableBindings => {
                 let stmt = ng::disable_bindings();
                 Some(Box::new(ir::ops::shared::create_statement_op::<
@@ -353,6 +405,125 @@ fn reify_create_operations(
                     Box<dyn CreateOp + Send + Sync>,
                 >(Box::new(stmt))))
             }
+            ir::OpKind::SourceLocation => {
+                if let Some(location_op) = op
+                    .as_any()
+                    .downcast_ref::<ir::ops::create::SourceLocationOp>()
+                {
+                    let locations = location_op
+                        .locations
+                        .iter()
+                        .map(|location| {
+                            let slot = location
+                                .target_slot
+                                .get_slot()
+                                .expect("Expected a slot to have been allocated");
+                            *o::literal_arr(vec![
+                                *o::literal(slot as f64),
+                                *o::literal(location.offset as f64),
+                                *o::literal(location.line as f64),
+                                *o::literal(location.column as f64),
+                            ])
+                        })
+                        .collect();
+                    let stmt = ng::attach_source_locations(
+                        location_op.template_path.clone(),
+                        *o::literal_arr(locations),
+                    );
+                    Some(Box::new(ir::ops::shared::create_statement_op::<
+                        Box<dyn CreateOp + Send + Sync>,
+                    >(Box::new(stmt))))
+                } else {
+                    None
+                }
+            }
+            ir::OpKind::Defer => {
+                if let Some(defer_op) = op.as_any().downcast_ref::<ir::ops::create::DeferOp>() {
+                    let slot = |handle: &ir::handle::SlotHandle| {
+                        handle.get_slot().expect("Expected a slot") as i32
+                    };
+                    let timer_scheduling = defer_op.loading_minimum_time.is_some()
+                        || defer_op.loading_after_time.is_some()
+                        || defer_op.placeholder_minimum_time.is_some();
+                    let stmt = ng::defer(
+                        ng::DeferConfig {
+                            self_slot: slot(&defer_op.handle),
+                            primary_slot: slot(&defer_op.main_slot),
+                            dependency_resolver_fn: defer_op.resolver_fn.clone(),
+                            loading_slot: defer_op.loading_slot.as_ref().map(slot),
+                            placeholder_slot: defer_op.placeholder_slot.as_ref().map(slot),
+                            error_slot: defer_op.error_slot.as_ref().map(slot),
+                            loading_config: defer_op.loading_config.clone(),
+                            placeholder_config: defer_op.placeholder_config.clone(),
+                            enable_timer_scheduling: timer_scheduling,
+                            flags: defer_op.flags,
+                        },
+                        defer_op.source_span.clone(),
+                    );
+                    Some(Box::new(ir::ops::shared::create_statement_op::<
+                        Box<dyn CreateOp + Send + Sync>,
+                    >(Box::new(stmt))))
+                } else {
+                    None
+                }
+            }
+            ir::OpKind::DeferOn => {
+                if let Some(defer_on) = op.as_any().downcast_ref::<ir::ops::create::DeferOnOp>() {
+                    use ir::ops::create::DeferTrigger;
+                    let args = match &defer_on.trigger {
+                        DeferTrigger::Idle | DeferTrigger::Immediate | DeferTrigger::Never => {
+                            vec![]
+                        }
+                        DeferTrigger::Timer { delay } => vec![*delay as i32],
+                        // `hydrate` triggers don't have a target.
+                        DeferTrigger::Hover { .. }
+                        | DeferTrigger::Interaction { .. }
+                        | DeferTrigger::Viewport { .. }
+                            if defer_on.modifier == ir::enums::DeferOpModifierKind::Hydrate =>
+                        {
+                            vec![]
+                        }
+                        DeferTrigger::Hover {
+                            target_slot,
+                            target_slot_view_steps,
+                            ..
+                        }
+                        | DeferTrigger::Interaction {
+                            target_slot,
+                            target_slot_view_steps,
+                            ..
+                        }
+                        | DeferTrigger::Viewport {
+                            target_slot,
+                            target_slot_view_steps,
+                            ..
+                        } => {
+                            let (Some(slot), Some(steps)) = (
+                                target_slot.as_ref().and_then(|slot| slot.get_slot()),
+                                *target_slot_view_steps,
+                            ) else {
+                                panic!("Slot or view steps not set in trigger reification");
+                            };
+                            let mut args = vec![slot as i32];
+                            if steps != 0 {
+                                args.push(steps as i32);
+                            }
+                            args
+                        }
+                    };
+                    let stmt = ng::defer_on(
+                        &defer_on.trigger,
+                        args,
+                        defer_on.modifier,
+                        defer_on.source_span.clone(),
+                    );
+                    Some(Box::new(ir::ops::shared::create_statement_op::<
+                        Box<dyn CreateOp + Send + Sync>,
+                    >(Box::new(stmt))))
+                } else {
+                    None
+                }
+            }
             ir::OpKind::Variable => {
                 if let Some(var_op) = op
                     .as_any()
@@ -492,6 +663,7 @@ fn reify_create_operations(
                         listener_op.name.to_string(),
                         handler_fn,
                         listener_op.event_target.clone(),
+                        listener_op.host_listener && listener_op.is_legacy_animation_listener,
                         listener_op.source_span.clone().into(),
                     );
 
@@ -616,7 +788,7 @@ fn reify_create_operations(
     }
 }
 
-fn reify_update_operations(unit: &mut dyn CompilationUnit) {
+fn reify_update_operations(unit: &mut dyn CompilationUnit, is_host: bool) {
     for op in unit.update_mut().iter_mut() {
         ir::transform_expressions_in_op(
             op.as_mut(),
@@ -638,12 +810,21 @@ fn reify_update_operations(unit: &mut dyn CompilationUnit) {
             ir::OpKind::Property => {
                 if let Some(prop) = op.as_any().downcast_ref::<ir::ops::update::PropertyOp>() {
                     if let ir::ops::update::BindingExpression::Expression(expr) = &prop.expression {
-                        let stmt = ng::property(
-                            prop.name.clone(),
-                            expr.clone(),
-                            prop.sanitizer.clone(),
-                            prop.source_span.clone(),
-                        );
+                        let stmt =
+                            if is_host && prop.binding_kind == ir::BindingKind::LegacyAnimation {
+                                ng::synthetic_host_property(
+                                    prop.name.clone(),
+                                    expr.clone(),
+                                    prop.source_span.clone(),
+                                )
+                            } else {
+                                ng::property(
+                                    prop.name.clone(),
+                                    expr.clone(),
+                                    prop.sanitizer.clone(),
+                                    prop.source_span.clone(),
+                                )
+                            };
                         Some(Box::new(ir::ops::shared::create_statement_op::<
                             Box<dyn UpdateOp + Send + Sync>,
                         >(Box::new(stmt))))
@@ -654,6 +835,33 @@ fn reify_update_operations(unit: &mut dyn CompilationUnit) {
                     None
                 }
             }
+            ir::OpKind::I18nExpression => {
+                if let Some(i18n_exp) = op
+                    .as_any()
+                    .downcast_ref::<ir::ops::update::I18nExpressionOp>()
+                {
+                    let stmt = ng::i18n_exp(
+                        i18n_exp.expression.clone(),
+                        Some(i18n_exp.source_span.clone()),
+                    );
+                    Some(Box::new(ir::ops::shared::create_statement_op::<
+                        Box<dyn UpdateOp + Send + Sync>,
+                    >(Box::new(stmt))))
+                } else {
+                    None
+                }
+            }
+            ir::OpKind::I18nApply => {
+                if let Some(apply) = op.as_any().downcast_ref::<ir::ops::update::I18nApplyOp>() {
+                    let slot = apply.handle.get_slot().expect("Expected a slot") as i32;
+                    let stmt = ng::i18n_apply(slot, Some(apply.source_span.clone()));
+                    Some(Box::new(ir::ops::shared::create_statement_op::<
+                        Box<dyn UpdateOp + Send + Sync>,
+                    >(Box::new(stmt))))
+                } else {
+                    None
+                }
+            }
             ir::OpKind::TwoWayProperty => {
                 if let Some(prop) = op
                     .as_any()
@@ -762,6 +970,21 @@ fn reify_update_operations(unit: &mut dyn CompilationUnit) {
                     None
                 }
             }
+            ir::OpKind::DeferWhen => {
+                if let Some(defer_when) = op.as_any().downcast_ref::<ir::ops::update::DeferWhenOp>()
+                {
+                    let stmt = ng::defer_when(
+                        defer_when.modifier,
+                        reify_ir_expression(defer_when.expr.clone(), ir::VisitorContextFlag::NONE),
+                        defer_when.source_span.clone(),
+                    );
+                    Some(Box::new(ir::ops::shared::create_statement_op::<
+                        Box<dyn UpdateOp + Send + Sync>,
+                    >(Box::new(stmt))))
+                } else {
+                    None
+                }
+            }
             ir::OpKind::Attribute => {
                 if let Some(attr) = op.as_any().downcast_ref::<ir::ops::update::AttributeOp>() {
                     let expression = match &attr.expression {
@@ -970,10 +1193,7 @@ fn reify_ir_expression(expr: o::Expression, flags: ir::VisitorContextFlag) -> o:
             let reified_args = reify_ir_expression(*pipe.args.clone(), flags);
             let pipe_slot = pipe.target_slot.get_slot().unwrap_or(0) as i32;
             let var_offset = pipe.var_offset.unwrap_or(0) as i32;
-
-            // For variadic, wrap in array and call pipeBindV
-            let args_array = vec![reified_args];
-            ng::pipe_bind(pipe_slot, var_offset, args_array)
+            ng::pipe_bind_v(pipe_slot, var_offset, reified_args)
         }
         o::Expression::Reference(ref_expr) => {
             // Reify ReferenceExpr to ɵɵreference(slot + 1 + offset) expression
@@ -3,9 +3,10 @@
 //! Corresponds to packages/compiler/src/template/pipeline/src/phases/remove_illegal_let_references.ts
 //! It's not allowed to access a `@let` declaration before it has been defined. This is enforced
 //! already via template type checking, however it can trip some of the assertions in the pipeline.
-//! This phase detects illegal forward references and replaces them with `undefined`.
+//! This phase detects illegal forward references, reports them and replaces them with `undefined`.
 
 use crate::output::output_ast::{Expression, ExpressionTrait};
+use crate::parse_util::ParseError;
 use crate::template::pipeline::ir;
 use crate::template::pipeline::ir::enums::OpKind;
 use crate::template::pipeline::ir::expression::transform_expressions_in_op;
@@ -16,7 +17,7 @@ use crate::template::pipeline::src::compilation::{
 };
 
 /// It's not allowed to access a `@let` declaration before it has been defined. This phase detects
-/// illegal forward references and replaces them with `undefined`.
+/// illegal forward references, records a diagnostic for each and replaces them with `undefined`.
 pub fn remove_illegal_let_references(job: &mut dyn CompilationJob) {
     let job_kind = job.kind();
 
@@ -30,17 +31,24 @@ pub fn remove_illegal_let_references(job: &mut dyn CompilationJob) {
             &mut *job_ptr
         };
 
+        let mut diagnostics = Vec::new();
+
         // Process root unit
-        process_unit(&mut component_job.root);
+        process_unit(&mut component_job.root, &mut diagnostics);
 
         // Process all view units
         for (_, unit) in component_job.views.iter_mut() {
-            process_unit(unit);
+            process_unit(unit, &mut diagnostics);
         }
+
+        component_job.diagnostics.extend(diagnostics);
     }
 }
 
-fn process_unit(unit: &mut crate::template::pipeline::src::compilation::ViewCompilationUnit) {
+fn process_unit(
+    unit: &mut crate::template::pipeline::src::compilation::ViewCompilationUnit,
+    diagnostics: &mut Vec<ParseError>,
+) {
     // Collect @let variable names with their indices
     let mut let_vars: Vec<(usize, String)> = Vec::new();
 
@@ -76,12 +84,26 @@ fn process_unit(unit: &mut crate::template::pipeline::src::compilation::ViewComp
             if let Some(prev_op_mut) = unit.update_mut().get_mut(prev_index) {
                 unsafe {
                     let prev_op_mut_ptr = prev_op_mut.as_mut() as *mut dyn ir::UpdateOp;
+                    // Expression spans are only recorded outside of compatibility mode, so fall
+                    // back to the span of the op containing the read.
+                    let op_span = (*prev_op_mut_ptr).source_span().cloned();
 
                     transform_expressions_in_op(
                         &mut *prev_op_mut_ptr,
                         &mut |expr, _flags| {
                             if let Expression::LexicalRead(lexical_read) = &expr {
                                 if &*lexical_read.name == let_name.as_str() {
+                                    if let Some(span) =
+                                        lexical_read.source_span.as_ref().or(op_span.as_ref())
+                                    {
+                                        diagnostics.push(ParseError::new(
+                                            span.clone(),
+                                            format!(
+                                                "Cannot read @let declaration '{}' before it has been defined.",
+                                                let_name
+                                            ),
+                                        ));
+                                    }
                                     // Replace with undefined literal (use null as equivalent to undefined)
                                     return Expression::Literal(
                                         crate::output::output_ast::LiteralExpr {
@@ -4,19 +4,21 @@
 //! Any variable inside a listener with the name `$event` will be transformed into a output lexical
 //! read immediately, and does not participate in any of the normal logic for handling variables.
 
-use crate::output::output_ast::Expression;
+use crate::output::output_ast::{Expression, ReadVarExpr};
+use crate::parse_util::ParseError;
 use crate::template::pipeline::ir;
-use crate::template::pipeline::ir::enums::OpKind;
 use crate::template::pipeline::ir::expression::transform_expressions_in_op;
 use crate::template::pipeline::ir::ops::create::{
     AnimationListenerOp, ListenerOp, TwoWayListenerOp,
 };
 use crate::template::pipeline::src::compilation::{
     CompilationJob, CompilationJobKind, CompilationUnit, ComponentCompilationJob,
+    ViewCompilationUnit,
 };
 
 /// Any variable inside a listener with the name `$event` will be transformed into a output lexical
 /// read immediately, and does not participate in any of the normal logic for handling variables.
+/// There is no event outside of a listener, so reading `$event` anywhere else is reported.
 pub fn resolve_dollar_event(job: &mut dyn CompilationJob) {
     let job_kind = job.kind();
 
@@ -30,121 +32,99 @@ pub fn resolve_dollar_event(job: &mut dyn CompilationJob) {
             &mut *job_ptr
         };
 
+        let mut diagnostics = Vec::new();
+
         // Process root unit
-        transform_dollar_event_in_unit(&mut component_job.root);
+        transform_dollar_event_in_unit(&mut component_job.root, &mut diagnostics);
 
         // Process all view units
         for (_, unit) in component_job.views.iter_mut() {
-            transform_dollar_event_in_unit(unit);
+            transform_dollar_event_in_unit(unit, &mut diagnostics);
         }
+
+        component_job.diagnostics.extend(diagnostics);
     }
 }
 
 fn transform_dollar_event_in_unit(
-    unit: &mut crate::template::pipeline::src::compilation::ViewCompilationUnit,
+    unit: &mut ViewCompilationUnit,
+    diagnostics: &mut Vec<ParseError>,
 ) {
-    // Transform in create ops (listeners)
     for op in unit.create_mut().iter_mut() {
-        match op.kind() {
-            OpKind::Listener | OpKind::AnimationListener | OpKind::TwoWayListener => {
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-
-                    match op.kind() {
-                        OpKind::Listener => {
-                            let listener_ptr = op_ptr as *mut ListenerOp;
-                            let listener = &mut *listener_ptr;
+        let op = op.as_any_mut();
+        if let Some(listener) = op.downcast_mut::<ListenerOp>() {
+            if resolve_dollar_event_in_handler(&mut listener.handler_ops) {
+                listener.consumes_dollar_event = true;
+            }
+        } else if let Some(listener) = op.downcast_mut::<AnimationListenerOp>() {
+            if resolve_dollar_event_in_handler(&mut listener.handler_ops) {
+                listener.consumes_dollar_event = true;
+            }
+        } else if let Some(listener) = op.downcast_mut::<TwoWayListenerOp>() {
+            // Two-way listeners always consume `$event` so they omit this field.
+            resolve_dollar_event_in_handler(&mut listener.handler_ops);
+        }
+    }
 
-                            // Transform expressions in handler_ops
-                            // The transformExpressionsInOp with InChildOperation flag in TypeScript
-                            // means we only transform expressions in child operations (handler_ops)
-                            for handler_op in listener.handler_ops.iter_mut() {
-                                transform_expressions_in_op(
-                                    handler_op.as_mut(),
-                                    &mut |expr, _flags| {
-                                        if let Expression::LexicalRead(ref lexical_read) = expr {
-                                            if &*lexical_read.name == "$event" {
-                                                listener.consumes_dollar_event = true;
-                                                return Expression::ReadVar(
-                                                    crate::output::output_ast::ReadVarExpr {
-                                                        name: "$event".to_string(),
-                                                        type_: None,
-                                                        source_span: lexical_read
-                                                            .source_span
-                                                            .clone(),
-                                                    },
-                                                );
-                                            }
-                                        }
-                                        expr
-                                    },
-                                    ir::VisitorContextFlag::NONE,
-                                );
-                            }
-                        }
-                        OpKind::AnimationListener => {
-                            let animation_listener_ptr = op_ptr as *mut AnimationListenerOp;
-                            let animation_listener = &mut *animation_listener_ptr;
+    for op in unit.create_mut().iter_mut() {
+        if !matches!(
+            op.kind(),
+            ir::OpKind::Listener | ir::OpKind::AnimationListener | ir::OpKind::TwoWayListener
+        ) {
+            report_dollar_event(op.as_mut(), diagnostics);
+        }
+    }
+    for op in unit.update_mut().iter_mut() {
+        report_dollar_event(op.as_mut(), diagnostics);
+    }
+}
 
-                            // Transform expressions in handler_ops
-                            for handler_op in animation_listener.handler_ops.iter_mut() {
-                                transform_expressions_in_op(
-                                    handler_op.as_mut(),
-                                    &mut |expr, _flags| {
-                                        if let Expression::LexicalRead(ref lexical_read) = expr {
-                                            if &*lexical_read.name == "$event" {
-                                                animation_listener.consumes_dollar_event = true;
-                                                return Expression::ReadVar(
-                                                    crate::output::output_ast::ReadVarExpr {
-                                                        name: "$event".to_string(),
-                                                        type_: None,
-                                                        source_span: lexical_read
-                                                            .source_span
-                                                            .clone(),
-                                                    },
-                                                );
-                                            }
-                                        }
-                                        expr
-                                    },
-                                    ir::VisitorContextFlag::NONE,
-                                );
-                            }
-                        }
-                        OpKind::TwoWayListener => {
-                            let two_way_listener_ptr = op_ptr as *mut TwoWayListenerOp;
-                            let two_way_listener = &mut *two_way_listener_ptr;
+/// Turns reads of `$event` in a listener's handler into reads of the handler's parameter, returning
+/// whether there were any.
+fn resolve_dollar_event_in_handler(
+    handler_ops: &mut ir::OpList<Box<dyn ir::UpdateOp + Send + Sync>>,
+) -> bool {
+    let mut consumes_dollar_event = false;
+    for handler_op in handler_ops.iter_mut() {
+        transform_expressions_in_op(
+            handler_op.as_mut(),
+            &mut |expr, _flags| match expr {
+                Expression::LexicalRead(lexical_read) if &*lexical_read.name == "$event" => {
+                    consumes_dollar_event = true;
+                    Expression::ReadVar(ReadVarExpr {
+                        name: "$event".to_string(),
+                        type_: None,
+                        source_span: lexical_read.source_span,
+                    })
+                }
+                expr => expr,
+            },
+            ir::VisitorContextFlag::NONE,
+        );
+    }
+    consumes_dollar_event
+}
 
-                            // Transform expressions in handler_ops
-                            // Note: Two-way listeners always consume `$event` so they omit this field
-                            for handler_op in two_way_listener.handler_ops.iter_mut() {
-                                transform_expressions_in_op(
-                                    handler_op.as_mut(),
-                                    &mut |expr, _flags| {
-                                        if let Expression::LexicalRead(ref lexical_read) = expr {
-                                            if &*lexical_read.name == "$event" {
-                                                return Expression::ReadVar(
-                                                    crate::output::output_ast::ReadVarExpr {
-                                                        name: "$event".to_string(),
-                                                        type_: None,
-                                                        source_span: lexical_read
-                                                            .source_span
-                                                            .clone(),
-                                                    },
-                                                );
-                                            }
-                                        }
-                                        expr
-                                    },
-                                    ir::VisitorContextFlag::NONE,
-                                );
-                            }
-                        }
-                        _ => {}
+fn report_dollar_event(op: &mut dyn ir::Op, diagnostics: &mut Vec<ParseError>) {
+    // Expression spans are only recorded outside of compatibility mode, so fall back to the span
+    // of the op containing the read.
+    let op_span = op.source_span().cloned();
+    transform_expressions_in_op(
+        op,
+        &mut |expr, _flags| {
+            if let Expression::LexicalRead(lexical_read) = &expr {
+                if &*lexical_read.name == "$event" {
+                    if let Some(span) = lexical_read.source_span.as_ref().or(op_span.as_ref()) {
+                        diagnostics.push(ParseError::new(
+                            span.clone(),
+                            "The '$event' variable is only available in event bindings."
+                                .to_string(),
+                        ));
                     }
                 }
             }
-            _ => {}
-        }
-    }
+            expr
+        },
+        ir::VisitorContextFlag::NONE,
+    );
 }
@@ -82,13 +82,24 @@ pub fn resolve_i18n_element_placeholders(job: &mut dyn CompilationJob) {
         &elements,
         None,
     );
+
+    // The params were recorded on copies of the contexts, store them back on the context ops.
+    for unit in std::iter::once(&mut component_job.root).chain(component_job.views.values_mut()) {
+        for op in unit.create.iter_mut() {
+            if let Some(context) = op.as_any_mut().downcast_mut::<I18nContextOp>() {
+                if let Some(resolved) = i18n_contexts.remove(&context.xref) {
+                    context.params = resolved.params;
+                }
+            }
+        }
+    }
 }
 
 /// Helper function to handle template kind ops (ConditionalCreate, ConditionalBranchCreate, Template)
 fn handle_template_kind_op(
     job: *mut ComponentCompilationJob,
     unit: &mut ViewCompilationUnit,
-    op: &Box<dyn ir::CreateOp + Send + Sync>,
+    op: &(dyn ir::CreateOp + Send + Sync),
     op_kind: OpKind,
     op_xref: ir::XrefId,
     idx: usize,
@@ -98,7 +109,7 @@ fn handle_template_kind_op(
     pending_structural_directive: Option<usize>,
 ) {
     unsafe {
-        let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
+        let op_ptr = op as *const dyn ir::CreateOp;
         let view_xref = op_xref;
 
         let job_ref = &mut *job;
@@ -549,9 +560,9 @@ fn resolve_placeholders_for_view(
             OpKind::ConditionalCreate | OpKind::ConditionalBranchCreate | OpKind::Template => {
                 // Get op reference and convert to raw pointer to avoid borrow conflicts
                 let op_ref = unit.create.get(idx).unwrap();
-                let op_ptr = op_ref.as_ref() as *const dyn ir::CreateOp;
+                let op_ptr = op_ref.as_ref() as *const (dyn ir::CreateOp + Send + Sync);
                 // Now we can borrow unit mutably
-                let op = unsafe { &*(op_ptr as *const Box<dyn ir::CreateOp + Send + Sync>) };
+                let op = unsafe { &*op_ptr };
                 handle_template_kind_op(
                     job,
                     unit,
@@ -159,6 +159,18 @@ pub fn resolve_i18n_expression_placeholders(job: &mut dyn CompilationJob) {
             update_placeholder(&expr_op, value, &mut i18n_contexts, unit);
         }
     }
+
+    // The params were recorded on copies of the contexts, store them back on the context ops.
+    for unit in std::iter::once(&mut component_job.root).chain(component_job.views.values_mut()) {
+        for op in unit.create.iter_mut() {
+            if let Some(context) = op.as_any_mut().downcast_mut::<I18nContextOp>() {
+                if let Some(resolved) = i18n_contexts.remove(&context.xref) {
+                    context.params = resolved.params;
+                    context.postprocessing_params = resolved.postprocessing_params;
+                }
+            }
+        }
+    }
 }
 
 fn update_placeholder(
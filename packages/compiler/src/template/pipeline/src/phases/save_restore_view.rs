@@ -73,9 +73,9 @@ fn process_unit(
     let root_xref = component_job.root.xref();
     let is_root = unit_xref == root_xref;
 
-    // Collect all listener indices and check if ANY needs restoreView
+    // Collect the listeners which need to restore the view. This is decided per listener, so a
+    // listener that doesn't touch the view never restores it, even if a sibling listener does.
     let mut listener_indices: Vec<usize> = Vec::new();
-    let mut any_listener_needs_restore_view = false;
 
     for (idx, op) in unit.create_mut().iter_mut().enumerate() {
         match op.kind() {
@@ -83,12 +83,10 @@ fn process_unit(
             | OpKind::TwoWayListener
             | OpKind::Animation
             | OpKind::AnimationListener => {
-                listener_indices.push(idx);
-
                 // We need restoreView if we're in a nested view (to restore the correct ctx),
                 // OR if we're in any view (including root) and use template references/variables.
                 if !is_root || check_needs_restore_view(op) {
-                    any_listener_needs_restore_view = true;
+                    listener_indices.push(idx);
                 }
             }
             _ => {}
@@ -96,7 +94,7 @@ fn process_unit(
     }
 
     // Only proceed with save/restore if actually needed
-    if any_listener_needs_restore_view {
+    if !listener_indices.is_empty() {
         // Prepend a variable op with SavedView for this unit
         let saved_view_xref = component_job.allocate_xref_id();
         let saved_view_variable = SemanticVariable::SavedView(SavedViewVariable::new(unit_xref));
@@ -141,52 +139,74 @@ fn check_needs_restore_view(op: &Box<dyn ir::CreateOp + Send + Sync>) -> bool {
             let listener_ptr = op_ptr as *const ListenerOp;
             let listener = &*listener_ptr;
 
-            for handler_op in listener.handler_ops.iter() {
-                if contains_reference_expr(handler_op) {
-                    return true;
-                }
-            }
+            handler_needs_restore_view(&listener.handler_ops)
         },
         OpKind::TwoWayListener => unsafe {
             let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
             let two_way_listener_ptr = op_ptr as *const TwoWayListenerOp;
             let two_way_listener = &*two_way_listener_ptr;
 
-            for handler_op in two_way_listener.handler_ops.iter() {
-                if contains_reference_expr(handler_op) {
-                    return true;
-                }
-            }
+            handler_needs_restore_view(&two_way_listener.handler_ops)
         },
         OpKind::Animation => unsafe {
             let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
             let animation_ptr = op_ptr as *const AnimationOp;
             let animation = &*animation_ptr;
 
-            for handler_op in animation.handler_ops.iter() {
-                if contains_reference_expr(handler_op) {
-                    return true;
-                }
-            }
+            handler_needs_restore_view(&animation.handler_ops)
         },
         OpKind::AnimationListener => unsafe {
             let op_ptr = op.as_ref() as *const dyn ir::CreateOp;
             let animation_listener_ptr = op_ptr as *const AnimationListenerOp;
             let animation_listener = &*animation_listener_ptr;
 
-            for handler_op in animation_listener.handler_ops.iter() {
-                if contains_reference_expr(handler_op) {
-                    return true;
-                }
-            }
+            handler_needs_restore_view(&animation_listener.handler_ops)
         },
-        _ => {}
+        _ => false,
+    }
+}
+
+/// Local refs and `@let` declarations in scope are declared as variables in every listener, but only
+/// the ones the handler actually reads require the view to be restored.
+fn handler_needs_restore_view(
+    handler_ops: &ir::OpList<Box<dyn ir::UpdateOp + Send + Sync>>,
+) -> bool {
+    let is_view_reference = |expr: &Expression| {
+        matches!(
+            expr,
+            Expression::Reference(_) | Expression::ContextLetReference(_)
+        )
+    };
+
+    handler_ops.iter().any(|handler_op| {
+        let Some(identifier) = view_reference_identifier(handler_op.as_ref()) else {
+            return op_contains(handler_op.as_ref(), &is_view_reference);
+        };
+        let reads_identifier = |expr: &Expression| {
+            matches!(expr, Expression::LexicalRead(read) if *read.name == *identifier)
+        };
+        handler_ops
+            .iter()
+            .any(|other| op_contains(other.as_ref(), &reads_identifier))
+    })
+}
+
+/// Returns the template identifier of a variable op that reads a local ref or `@let` declaration.
+fn view_reference_identifier(op: &(dyn ir::UpdateOp + Send + Sync)) -> Option<&str> {
+    let variable_op = op
+        .as_any()
+        .downcast_ref::<VariableOp<Box<dyn ir::UpdateOp + Send + Sync>>>()?;
+    match (&variable_op.variable, variable_op.initializer.as_ref()) {
+        (
+            SemanticVariable::Identifier(variable),
+            Expression::Reference(_) | Expression::ContextLetReference(_),
+        ) => Some(&variable.identifier),
+        _ => None,
     }
-    false
 }
 
-fn contains_reference_expr(op: &Box<dyn ir::UpdateOp + Send + Sync>) -> bool {
-    // We need to traverse expressions to check for ReferenceExpr or ContextLetReferenceExpr.
+fn op_contains(op: &(dyn ir::UpdateOp + Send + Sync), pred: &dyn Fn(&Expression) -> bool) -> bool {
+    // We need to traverse expressions to check for expressions matching `pred`.
     // Since transform_expressions_in_op requires &mut, we traverse handler ops directly
     // by downcasting to specific op types and checking their handler_ops.
     // This avoids the need for invalid reference casting.
@@ -195,11 +215,11 @@ fn contains_reference_expr(op: &Box<dyn ir::UpdateOp + Send + Sync>) -> bool {
         OpKind::Statement => {
             // Check if statement contains expressions with ReferenceExpr
             // We'll traverse expressions in StatementOp
-            check_expressions_in_statement_op(op)
+            statement_op_contains(op, pred)
         }
         OpKind::Variable => {
             // Check initializer expression
-            check_expressions_in_variable_op(op)
+            variable_op_contains(op, pred)
         }
         _ => {
             // For other ops, we can't easily traverse without mutable access
@@ -210,35 +230,44 @@ fn contains_reference_expr(op: &Box<dyn ir::UpdateOp + Send + Sync>) -> bool {
     }
 }
 
-fn check_expressions_in_statement_op(op: &Box<dyn ir::UpdateOp + Send + Sync>) -> bool {
+fn statement_op_contains(
+    op: &(dyn ir::UpdateOp + Send + Sync),
+    pred: &dyn Fn(&Expression) -> bool,
+) -> bool {
     unsafe {
-        let op_ptr = op.as_ref() as *const dyn ir::UpdateOp;
+        let op_ptr = op as *const dyn ir::UpdateOp;
         let statement_op_ptr = op_ptr as *const StatementOp<Box<dyn ir::UpdateOp + Send + Sync>>;
         let statement_op = &*statement_op_ptr;
 
         // Check expressions in the statement
-        check_expressions_in_statement(&statement_op.statement)
+        statement_contains(&statement_op.statement, pred)
     }
 }
 
-fn check_expressions_in_variable_op(op: &Box<dyn ir::UpdateOp + Send + Sync>) -> bool {
+fn variable_op_contains(
+    op: &(dyn ir::UpdateOp + Send + Sync),
+    pred: &dyn Fn(&Expression) -> bool,
+) -> bool {
     unsafe {
-        let op_ptr = op.as_ref() as *const dyn ir::UpdateOp;
+        let op_ptr = op as *const dyn ir::UpdateOp;
         let variable_op_ptr = op_ptr as *const VariableOp<Box<dyn ir::UpdateOp + Send + Sync>>;
         let variable_op = &*variable_op_ptr;
 
         // Check expressions in the initializer
-        check_expressions_recursive(&variable_op.initializer)
+        expression_contains(&variable_op.initializer, pred)
     }
 }
 
-fn check_expressions_in_statement(stmt: &crate::output::output_ast::Statement) -> bool {
+fn statement_contains(
+    stmt: &crate::output::output_ast::Statement,
+    pred: &dyn Fn(&Expression) -> bool,
+) -> bool {
     match stmt {
-        Statement::Return(ref return_stmt) => check_expressions_recursive(&return_stmt.value),
-        Statement::Expression(ref expr_stmt) => check_expressions_recursive(&expr_stmt.expr),
+        Statement::Return(ref return_stmt) => expression_contains(&return_stmt.value, pred),
+        Statement::Expression(ref expr_stmt) => expression_contains(&expr_stmt.expr, pred),
         Statement::DeclareVar(ref var_stmt) => {
             if let Some(ref value) = var_stmt.value {
-                check_expressions_recursive(value)
+                expression_contains(value, pred)
             } else {
                 false
             }
@@ -251,42 +280,41 @@ fn check_expressions_in_statement(stmt: &crate::output::output_ast::Statement) -
     }
 }
 
-fn check_expressions_recursive(expr: &Expression) -> bool {
+fn expression_contains(expr: &Expression, pred: &dyn Fn(&Expression) -> bool) -> bool {
+    if pred(expr) {
+        return true;
+    }
     match expr {
-        // IR expressions we're looking for
-        Expression::Reference(_) | Expression::ContextLetReference(_) => {
-            return true;
-        }
         // Expressions that may contain nested expressions
         Expression::BinaryOp(bin) => {
-            check_expressions_recursive(&bin.lhs) || check_expressions_recursive(&bin.rhs)
+            expression_contains(&bin.lhs, pred) || expression_contains(&bin.rhs, pred)
         }
         Expression::Unary(un) => {
-            check_expressions_recursive(&un.expr)
+            expression_contains(&un.expr, pred)
         }
         Expression::ReadProp(prop) => {
-            check_expressions_recursive(&prop.receiver)
+            expression_contains(&prop.receiver, pred)
         }
         Expression::ReadKey(key) => {
-            check_expressions_recursive(&key.receiver) || check_expressions_recursive(&key.index)
+            expression_contains(&key.receiver, pred) || expression_contains(&key.index, pred)
         }
         Expression::WriteVar(write) => {
-            check_expressions_recursive(&write.value)
+            expression_contains(&write.value, pred)
         }
         Expression::WriteKey(write) => {
-            check_expressions_recursive(&write.receiver) ||
-            check_expressions_recursive(&write.index) ||
-            check_expressions_recursive(&write.value)
+            expression_contains(&write.receiver, pred) ||
+            expression_contains(&write.index, pred) ||
+            expression_contains(&write.value, pred)
         }
         Expression::WriteProp(write) => {
-            check_expressions_recursive(&write.receiver) || check_expressions_recursive(&write.value)
+            expression_contains(&write.receiver, pred) || expression_contains(&write.value, pred)
         }
         Expression::InvokeFn(invoke) => {
-            if check_expressions_recursive(&invoke.fn_) {
+            if expression_contains(&invoke.fn_, pred) {
                 return true;
             }
             for arg in &invoke.args {
-                if check_expressions_recursive(arg) {
+                if expression_contains(arg, pred) {
                     return true;
                 }
             }
@@ -294,7 +322,7 @@ fn check_expressions_recursive(expr: &Expression) -> bool {
         }
         Expression::LiteralArray(arr) => {
             for entry in &arr.entries {
-                if check_expressions_recursive(entry) {
+                if expression_contains(entry, pred) {
                     return true;
                 }
             }
@@ -302,64 +330,64 @@ fn check_expressions_recursive(expr: &Expression) -> bool {
         }
         Expression::LiteralMap(map) => {
             for entry in &map.entries {
-                if check_expressions_recursive(&entry.value) {
+                if expression_contains(&entry.value, pred) {
                     return true;
                 }
             }
             false
         }
         Expression::Conditional(cond) => {
-            check_expressions_recursive(&cond.condition) ||
-            check_expressions_recursive(&cond.true_case) ||
-            (cond.false_case.as_ref().map_or(false, |e| check_expressions_recursive(e)))
+            expression_contains(&cond.condition, pred) ||
+            expression_contains(&cond.true_case, pred) ||
+            (cond.false_case.as_ref().is_some_and(|e| expression_contains(e, pred)))
         }
         Expression::TypeOf(type_of) => {
-            check_expressions_recursive(&type_of.expr)
+            expression_contains(&type_of.expr, pred)
         }
         Expression::Void(void) => {
-            check_expressions_recursive(&void.expr)
+            expression_contains(&void.expr, pred)
         }
         Expression::Parens(parens) => {
-            check_expressions_recursive(&parens.expr)
+            expression_contains(&parens.expr, pred)
         }
         Expression::NotExpr(not) => {
-            check_expressions_recursive(&not.condition)
+            expression_contains(&not.condition, pred)
         }
         Expression::TaggedTemplate(tagged) => {
-            check_expressions_recursive(&tagged.tag) ||
-            tagged.template.expressions.iter().any(|e| check_expressions_recursive(e))
+            expression_contains(&tagged.tag, pred) ||
+            tagged.template.expressions.iter().any(|e| expression_contains(e, pred))
         }
         Expression::TemplateLiteral(template) => {
-            template.expressions.iter().any(|e| check_expressions_recursive(e))
+            template.expressions.iter().any(|e| expression_contains(e, pred))
         }
         Expression::Instantiate(inst) => {
-            if check_expressions_recursive(&inst.class_expr) {
+            if expression_contains(&inst.class_expr, pred) {
                 return true;
             }
             for arg in &inst.args {
-                if check_expressions_recursive(arg) {
+                if expression_contains(arg, pred) {
                     return true;
                 }
             }
             false
         }
         Expression::Localized(localized) => {
-            localized.expressions.iter().any(|e| check_expressions_recursive(e))
+            localized.expressions.iter().any(|e| expression_contains(e, pred))
         }
         Expression::Cast(cast) => {
-            check_expressions_recursive(&cast.value)
+            expression_contains(&cast.value, pred)
         }
         Expression::IfNull(if_null) => {
-            check_expressions_recursive(&if_null.condition) || check_expressions_recursive(&if_null.null_case)
+            expression_contains(&if_null.condition, pred) || expression_contains(&if_null.null_case, pred)
         }
         Expression::AssertNotNull(assert) => {
-            check_expressions_recursive(&assert.condition)
+            expression_contains(&assert.condition, pred)
         }
         Expression::ArrowFn(arrow) => {
             // Check body - could be expression or statements
             match &arrow.body {
                 crate::output::output_ast::ArrowFunctionBody::Expression(expr) => {
-                    check_expressions_recursive(expr)
+                    expression_contains(expr, pred)
                 }
                 crate::output::output_ast::ArrowFunctionBody::Statements(_) => {
                     // Statements are less likely to contain ReferenceExpr directly
@@ -372,9 +400,10 @@ fn check_expressions_recursive(expr: &Expression) -> bool {
             false
         }
         Expression::CommaExpr(comma) => {
-            comma.parts.iter().any(|e| check_expressions_recursive(e))
+            comma.parts.iter().any(|e| expression_contains(e, pred))
         }
-        // IR expressions that don't contain nested ReferenceExpr
+        // IR expressions that don't contain nested expressions we look into
+        Expression::Reference(_) | Expression::ContextLetReference(_) |
         Expression::Context(_) | Expression::NextContext(_) | Expression::GetCurrentView(_) |
         Expression::RestoreView(_) | Expression::ResetView(_) | Expression::ReadVariable(_) |
         Expression::PureFunction(_) | Expression::PureFunctionParameter(_) |
@@ -24,66 +24,12 @@ pub fn phase(job: &mut ComponentCompilationJob) {
     let mut slot_map: std::collections::HashMap<ir::XrefId, usize> =
         std::collections::HashMap::new();
 
-    // Process all views in the component and assign slot indexes.
-    // First, process root view
-    {
-        let unit = &mut job.root;
-        let mut slot_count = 0;
-
-        for op in unit.create_mut().iter_mut() {
-            // Only consider declarations which consume data slots.
-            if !op_kind_has_consumes_slot_trait(op.kind()) {
-                continue;
-            }
-
-            // Get xref before borrowing mutably
-            let xref = op.xref();
-
-            // Assign slots to this declaration starting at the current `slotCount`.
-            if let Some((handle, num_slots)) = get_slot_handle_and_num_slots_mut(op.as_mut()) {
-                handle.set_slot(slot_count);
-
-                // And track its assigned slot in the `slotMap`.
-                slot_map.insert(xref, handle.get_slot().unwrap());
-
-                // Each declaration may use more than 1 slot, so increment `slotCount` to reserve the number
-                // of slots required.
-                slot_count += num_slots;
-            }
-        }
-
-        // Record the total number of slots used on the view itself.
-        unit.decls = Some(slot_count);
-    }
-
-    // Process all other views
+    // Process all views in the component and assign slot indexes. Views are visited in declaration
+    // order (root first), and slot indexes only depend on the order of create ops within a view, so
+    // an unchanged template always receives the same slots.
+    allocate_slots_in_unit(&mut job.root, &mut slot_map);
     for (_, unit) in job.views.iter_mut() {
-        let mut slot_count = 0;
-
-        for op in unit.create_mut().iter_mut() {
-            // Only consider declarations which consume data slots.
-            if !op_kind_has_consumes_slot_trait(op.kind()) {
-                continue;
-            }
-
-            // Get xref before borrowing mutably
-            let xref = op.xref();
-
-            // Assign slots to this declaration starting at the current `slotCount`.
-            if let Some((handle, num_slots)) = get_slot_handle_and_num_slots_mut(op.as_mut()) {
-                handle.set_slot(slot_count);
-
-                // And track its assigned slot in the `slotMap`.
-                slot_map.insert(xref, handle.get_slot().unwrap());
-
-                // Each declaration may use more than 1 slot, so increment `slotCount` to reserve the number
-                // of slots required.
-                slot_count += num_slots;
-            }
-        }
-
-        // Record the total number of slots used on the view itself.
-        unit.decls = Some(slot_count);
+        allocate_slots_in_unit(unit, &mut slot_map);
     }
 
     // After slot assignment, `slotMap` now contains slot assignments for every declaration in the
@@ -161,6 +107,40 @@ pub fn phase(job: &mut ComponentCompilationJob) {
     }
 }
 
+/// Assign consecutive slots to the declarations of a single view, starting at 0, and record the
+/// total number of slots used as the view's `decls`.
+fn allocate_slots_in_unit(
+    unit: &mut crate::template::pipeline::src::compilation::ViewCompilationUnit,
+    slot_map: &mut std::collections::HashMap<ir::XrefId, usize>,
+) {
+    let mut slot_count = 0;
+
+    for op in unit.create_mut().iter_mut() {
+        // Only consider declarations which consume data slots.
+        if !op_kind_has_consumes_slot_trait(op.kind()) {
+            continue;
+        }
+
+        // Get xref before borrowing mutably
+        let xref = op.xref();
+
+        // Assign slots to this declaration starting at the current `slotCount`.
+        if let Some((handle, num_slots)) = get_slot_handle_and_num_slots_mut(op.as_mut()) {
+            handle.set_slot(slot_count);
+
+            // And track its assigned slot in the `slotMap`.
+            slot_map.insert(xref, slot_count);
+
+            // Each declaration may use more than 1 slot, so increment `slotCount` to reserve the number
+            // of slots required.
+            slot_count += num_slots;
+        }
+    }
+
+    // Record the total number of slots used on the view itself.
+    unit.decls = Some(slot_count);
+}
+
 /// Propagate slot indexes from slot_map to expressions in a unit
 fn propagate_slot_indexes_in_unit(
     unit: &mut crate::template::pipeline::src::compilation::ViewCompilationUnit,
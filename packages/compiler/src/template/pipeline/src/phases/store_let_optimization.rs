@@ -133,6 +133,25 @@ fn optimize_unit(
     let_used_externally: &std::collections::HashSet<ir::XrefId>,
     declare_let_ops: &mut std::collections::HashMap<ir::XrefId, usize>,
 ) {
+    // Find the @let declarations whose DeclareLetOp can be dropped. This has to happen before
+    // the StoreLetExpr are unwrapped below, otherwise there is nothing left to inspect.
+    let mut removable_targets: std::collections::HashSet<ir::XrefId> =
+        std::collections::HashSet::new();
+    for op in unit.update_mut().iter_mut() {
+        transform_expressions_in_op(
+            op.as_mut(),
+            &mut |expr, _flags| {
+                if let Expression::StoreLet(ref store_let) = expr {
+                    if !let_used_externally.contains(&store_let.target) && !has_pipe(store_let) {
+                        removable_targets.insert(store_let.target);
+                    }
+                }
+                expr // Identity transform - don't modify
+            },
+            ir::VisitorContextFlag::NONE,
+        );
+    }
+
     // Transform StoreLetExpr in update ops
     for op in unit.update_mut().iter_mut() {
         transform_expressions_in_op(
@@ -142,7 +161,6 @@ fn optimize_unit(
                     if !let_used_externally.contains(&store_let.target) {
                         // If @let isn't used in other views, we don't have to store its value
                         // Furthermore, if the @let isn't using pipes, we can also drop its declareLet op
-                        // The DeclareLetOp will be removed later if needed
                         // Return the value expression instead of StoreLetExpr
                         return (*store_let.value).clone();
                     }
@@ -155,32 +173,11 @@ fn optimize_unit(
 
     // Remove DeclareLetOp that are no longer needed
     // Collect indices to remove (in reverse order)
-    let mut indices_to_remove: Vec<usize> = Vec::new();
-    for (target_xref, index) in declare_let_ops.iter() {
-        if !let_used_externally.contains(target_xref) {
-            // Check if there's a StoreLetExpr for this target that doesn't have pipes
-            // We need to check all StoreLetExpr in the unit
-            let mut has_store_let_without_pipe = false;
-            for op in unit.update_mut().iter_mut() {
-                transform_expressions_in_op(
-                    op.as_mut(),
-                    &mut |expr, _flags| {
-                        if let Expression::StoreLet(ref store_let) = expr {
-                            if store_let.target == *target_xref && !has_pipe(store_let) {
-                                has_store_let_without_pipe = true;
-                            }
-                        }
-                        expr // Identity transform - don't modify
-                    },
-                    ir::VisitorContextFlag::NONE,
-                );
-            }
-
-            if has_store_let_without_pipe {
-                indices_to_remove.push(*index);
-            }
-        }
-    }
+    let mut indices_to_remove: Vec<usize> = declare_let_ops
+        .iter()
+        .filter(|(target_xref, _)| removable_targets.contains(target_xref))
+        .map(|(_, index)| *index)
+        .collect();
 
     // Remove DeclareLetOps in reverse order to maintain indices
     indices_to_remove.sort_by(|a, b| b.cmp(a));
@@ -6,10 +6,11 @@
 //! generate the correct output.
 
 use crate::output::output_ast::BinaryOperator;
-use crate::output::output_ast::{BinaryOperatorExpr, Expression, ParenthesizedExpr};
+use crate::output::output_ast::{Expression, ParenthesizedExpr};
 use crate::template::pipeline::ir;
-use crate::template::pipeline::src::compilation::{CompilationUnit, ComponentCompilationJob};
-use std::collections::HashSet;
+use crate::template::pipeline::ir::expression::transform_expressions_in_op;
+use crate::template::pipeline::ir::ops::create::RepeaterCreateOp;
+use crate::template::pipeline::src::compilation::{CompilationJob, CompilationUnit};
 
 /// In most cases we can drop user added parentheses from expressions. However, in some cases
 /// parentheses are needed for the expression to be considered valid JavaScript or for Typescript to
@@ -30,390 +31,193 @@ use std::collections::HashSet;
 ///    typescript AST, the parentheses node is removed, and then the remaining AST is printed, it
 ///    incorrectly prints `a ? b : c ?? d`. This is different from how it handles the same situation
 ///    with `||` and `&&` where it prints the parentheses even if they are not present in the AST.
-pub fn strip_nonrequired_parentheses(job: &mut ComponentCompilationJob) {
-    // Check which parentheses are required.
-    let mut required_parens: HashSet<*const ParenthesizedExpr> = HashSet::new();
-
-    // Process root view
-    {
-        let unit = &mut job.root;
-        collect_required_parens_in_unit(unit, &mut required_parens);
-    }
-
-    // Process all other views
-    for (_, unit) in job.views.iter_mut() {
-        collect_required_parens_in_unit(unit, &mut required_parens);
-    }
-
-    // Remove any non-required parentheses.
-    {
-        let unit = &mut job.root;
-        strip_nonrequired_parens_in_unit(unit, &required_parens);
-    }
-
-    for (_, unit) in job.views.iter_mut() {
-        strip_nonrequired_parens_in_unit(unit, &required_parens);
+///
+/// Unlike the TypeScript printer, our emitter doesn't re-insert parentheses based on operator
+/// precedence, so parentheses that group a lower-precedence operation (`(a + b) * c`) are kept too.
+pub fn strip_nonrequired_parentheses(job: &mut dyn CompilationJob) {
+    for unit in job.units_mut() {
+        strip_nonrequired_parens_in_unit(unit);
     }
 }
 
-fn collect_required_parens_in_unit(
-    unit: &mut crate::template::pipeline::src::compilation::ViewCompilationUnit,
-    required_parens: &mut HashSet<*const ParenthesizedExpr>,
-) {
-    use crate::template::pipeline::ir::expression::visit_expressions_in_op;
+fn strip_nonrequired_parens_in_unit(unit: &mut dyn CompilationUnit) {
+    let flags = ir::VisitorContextFlag::NONE;
+    let mut transform = |expr: Expression, _flags| strip_nonrequired_parens(expr);
 
-    // Process create ops
     for op in unit.create_mut().iter_mut() {
-        visit_expressions_in_op(op.as_mut(), &mut |expr: &Expression, _flags| {
-            check_nested_expressions(expr, required_parens);
-        });
-    }
-
-    // Process update ops
-    for op in unit.update_mut().iter_mut() {
-        visit_expressions_in_op(op.as_mut(), &mut |expr: &Expression, _flags| {
-            check_nested_expressions(expr, required_parens);
-        });
-    }
-
-    // Process nested operations (listeners, trackByOps)
-    for op in unit.create_mut().iter_mut() {
-        match op.kind() {
-            ir::OpKind::Listener => {
-                use crate::template::pipeline::ir::ops::create::ListenerOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let listener_ptr = op_ptr as *mut ListenerOp;
-                    let listener = &mut *listener_ptr;
-
-                    for handler_op in listener.handler_ops.iter_mut() {
-                        visit_expressions_in_op(
-                            handler_op.as_mut(),
-                            &mut |expr: &Expression, _flags| {
-                                check_nested_expressions(expr, required_parens);
-                            },
-                        );
-                    }
-                }
-            }
-            ir::OpKind::TwoWayListener => {
-                use crate::template::pipeline::ir::ops::create::TwoWayListenerOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let two_way_ptr = op_ptr as *mut TwoWayListenerOp;
-                    let two_way = &mut *two_way_ptr;
+        transform_expressions_in_op(&mut **op, &mut transform, flags);
 
-                    for handler_op in two_way.handler_ops.iter_mut() {
-                        visit_expressions_in_op(
-                            handler_op.as_mut(),
-                            &mut |expr: &Expression, _flags| {
-                                check_nested_expressions(expr, required_parens);
-                            },
-                        );
-                    }
+        if let Some(repeater) = op.as_any_mut().downcast_mut::<RepeaterCreateOp>() {
+            if let Some(ref mut track_by_ops) = repeater.track_by_ops {
+                for track_by_op in track_by_ops.iter_mut() {
+                    transform_expressions_in_op(track_by_op.as_mut(), &mut transform, flags);
                 }
             }
-            ir::OpKind::Animation => {
-                use crate::template::pipeline::ir::ops::create::AnimationOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let anim_ptr = op_ptr as *mut AnimationOp;
-                    let anim = &mut *anim_ptr;
-
-                    for handler_op in anim.handler_ops.iter_mut() {
-                        visit_expressions_in_op(
-                            handler_op.as_mut(),
-                            &mut |expr: &Expression, _flags| {
-                                check_nested_expressions(expr, required_parens);
-                            },
-                        );
-                    }
-                }
-            }
-            ir::OpKind::AnimationListener => {
-                use crate::template::pipeline::ir::ops::create::AnimationListenerOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let anim_listener_ptr = op_ptr as *mut AnimationListenerOp;
-                    let anim_listener = &mut *anim_listener_ptr;
-
-                    for handler_op in anim_listener.handler_ops.iter_mut() {
-                        visit_expressions_in_op(
-                            handler_op.as_mut(),
-                            &mut |expr: &Expression, _flags| {
-                                check_nested_expressions(expr, required_parens);
-                            },
-                        );
-                    }
-                }
-            }
-            ir::OpKind::RepeaterCreate => {
-                use crate::template::pipeline::ir::ops::create::RepeaterCreateOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let repeater_ptr = op_ptr as *mut RepeaterCreateOp;
-                    let repeater = &mut *repeater_ptr;
-
-                    if let Some(ref mut track_by_ops) = repeater.track_by_ops {
-                        for track_by_op in track_by_ops.iter_mut() {
-                            visit_expressions_in_op(
-                                track_by_op.as_mut(),
-                                &mut |expr: &Expression, _flags| {
-                                    check_nested_expressions(expr, required_parens);
-                                },
-                            );
-                        }
-                    }
-                }
-            }
-            _ => {}
         }
     }
-}
-
-fn strip_nonrequired_parens_in_unit(
-    unit: &mut crate::template::pipeline::src::compilation::ViewCompilationUnit,
-    required_parens: &HashSet<*const ParenthesizedExpr>,
-) {
-    use crate::template::pipeline::ir::expression::transform_expressions_in_op;
-
-    // Process create ops
-    for op in unit.create_mut().iter_mut() {
-        transform_expressions_in_op(
-            &mut **op,
-            &mut |expr: Expression, _flags| {
-                if let Expression::Parens(ref parens_expr) = &expr {
-                    let ptr = parens_expr as *const ParenthesizedExpr;
-                    if !required_parens.contains(&ptr) {
-                        return *parens_expr.expr.clone();
-                    }
-                }
-                expr
-            },
-            ir::VisitorContextFlag::NONE,
-        );
-    }
 
-    // Process update ops
     for op in unit.update_mut().iter_mut() {
-        transform_expressions_in_op(
-            &mut **op,
-            &mut |expr: Expression, _flags| {
-                if let Expression::Parens(ref parens_expr) = &expr {
-                    let ptr = parens_expr as *const ParenthesizedExpr;
-                    if !required_parens.contains(&ptr) {
-                        return *parens_expr.expr.clone();
-                    }
-                }
-                expr
-            },
-            ir::VisitorContextFlag::NONE,
-        );
+        transform_expressions_in_op(&mut **op, &mut transform, flags);
     }
+}
 
-    // Process nested operations (listeners, trackByOps)
-    for op in unit.create_mut().iter_mut() {
-        match op.kind() {
-            ir::OpKind::Listener => {
-                use crate::template::pipeline::ir::ops::create::ListenerOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let listener_ptr = op_ptr as *mut ListenerOp;
-                    let listener = &mut *listener_ptr;
-
-                    for handler_op in listener.handler_ops.iter_mut() {
-                        transform_expressions_in_op(
-                            handler_op.as_mut(),
-                            &mut |expr: Expression, _flags| {
-                                if let Expression::Parens(ref parens_expr) = &expr {
-                                    let ptr = parens_expr as *const ParenthesizedExpr;
-                                    if !required_parens.contains(&ptr) {
-                                        return *parens_expr.expr.clone();
-                                    }
-                                }
-                                expr
-                            },
-                            ir::VisitorContextFlag::NONE,
-                        );
-                    }
-                }
-            }
-            ir::OpKind::TwoWayListener => {
-                use crate::template::pipeline::ir::ops::create::TwoWayListenerOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let two_way_ptr = op_ptr as *mut TwoWayListenerOp;
-                    let two_way = &mut *two_way_ptr;
-
-                    for handler_op in two_way.handler_ops.iter_mut() {
-                        transform_expressions_in_op(
-                            handler_op.as_mut(),
-                            &mut |expr: Expression, _flags| {
-                                if let Expression::Parens(ref parens_expr) = &expr {
-                                    let ptr = parens_expr as *const ParenthesizedExpr;
-                                    if !required_parens.contains(&ptr) {
-                                        return *parens_expr.expr.clone();
-                                    }
-                                }
-                                expr
-                            },
-                            ir::VisitorContextFlag::NONE,
-                        );
-                    }
-                }
-            }
-            ir::OpKind::Animation => {
-                use crate::template::pipeline::ir::ops::create::AnimationOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let anim_ptr = op_ptr as *mut AnimationOp;
-                    let anim = &mut *anim_ptr;
-
-                    for handler_op in anim.handler_ops.iter_mut() {
-                        transform_expressions_in_op(
-                            handler_op.as_mut(),
-                            &mut |expr: Expression, _flags| {
-                                if let Expression::Parens(ref parens_expr) = &expr {
-                                    let ptr = parens_expr as *const ParenthesizedExpr;
-                                    if !required_parens.contains(&ptr) {
-                                        return *parens_expr.expr.clone();
-                                    }
-                                }
-                                expr
-                            },
-                            ir::VisitorContextFlag::NONE,
-                        );
-                    }
-                }
-            }
-            ir::OpKind::AnimationListener => {
-                use crate::template::pipeline::ir::ops::create::AnimationListenerOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let anim_listener_ptr = op_ptr as *mut AnimationListenerOp;
-                    let anim_listener = &mut *anim_listener_ptr;
-
-                    for handler_op in anim_listener.handler_ops.iter_mut() {
-                        transform_expressions_in_op(
-                            handler_op.as_mut(),
-                            &mut |expr: Expression, _flags| {
-                                if let Expression::Parens(ref parens_expr) = &expr {
-                                    let ptr = parens_expr as *const ParenthesizedExpr;
-                                    if !required_parens.contains(&ptr) {
-                                        return *parens_expr.expr.clone();
-                                    }
-                                }
-                                expr
-                            },
-                            ir::VisitorContextFlag::NONE,
-                        );
-                    }
-                }
-            }
-            ir::OpKind::RepeaterCreate => {
-                use crate::template::pipeline::ir::ops::create::RepeaterCreateOp;
-                unsafe {
-                    let op_ptr = op.as_mut() as *mut dyn ir::CreateOp;
-                    let repeater_ptr = op_ptr as *mut RepeaterCreateOp;
-                    let repeater = &mut *repeater_ptr;
-
-                    if let Some(ref mut track_by_ops) = repeater.track_by_ops {
-                        for track_by_op in track_by_ops.iter_mut() {
-                            transform_expressions_in_op(
-                                track_by_op.as_mut(),
-                                &mut |expr: Expression, _flags| {
-                                    if let Expression::Parens(ref parens_expr) = &expr {
-                                        let ptr = parens_expr as *const ParenthesizedExpr;
-                                        if !required_parens.contains(&ptr) {
-                                            return *parens_expr.expr.clone();
-                                        }
-                                    }
-                                    expr
-                                },
-                                ir::VisitorContextFlag::NONE,
-                            );
-                        }
-                    }
-                }
+/// Expressions are transformed bottom-up, so by the time an expression is visited the parentheses
+/// inside of it that never matter have been removed, and only the ones whose need depends on the
+/// surrounding operator are left for the parent to decide on.
+fn strip_nonrequired_parens(expr: Expression) -> Expression {
+    match expr {
+        Expression::Parens(parens) if !needs_grouping(&parens.expr) => *parens.expr,
+        Expression::BinaryOp(mut binary) => {
+            binary.lhs = strip_operand_parens(binary.lhs, binary.operator, true);
+            binary.rhs = strip_operand_parens(binary.rhs, binary.operator, false);
+            Expression::BinaryOp(binary)
+        }
+        Expression::InvokeFn(mut invoke) => {
+            invoke.args = invoke
+                .args
+                .into_iter()
+                .map(strip_list_item_parens)
+                .collect();
+            Expression::InvokeFn(invoke)
+        }
+        Expression::PipeBinding(mut pipe) => {
+            pipe.args = pipe.args.into_iter().map(strip_list_item_parens).collect();
+            Expression::PipeBinding(pipe)
+        }
+        Expression::LiteralArray(mut array) => {
+            array.entries = array
+                .entries
+                .into_iter()
+                .map(strip_list_item_parens)
+                .collect();
+            Expression::LiteralArray(array)
+        }
+        Expression::LiteralMap(mut map) => {
+            for entry in map.entries.iter_mut() {
+                *entry.value = strip_list_item_parens((*entry.value).clone());
             }
-            _ => {}
+            Expression::LiteralMap(map)
         }
+        expr => expr,
     }
 }
 
-fn check_nested_expressions(
-    expr: &Expression,
-    required_parens: &mut HashSet<*const ParenthesizedExpr>,
-) {
-    if let Expression::BinaryOp(ref bin_op) = expr {
-        match bin_op.operator {
-            BinaryOperator::Exponentiation => {
-                check_exponentiation_parens(bin_op, required_parens);
-            }
-            BinaryOperator::NullishCoalesce => {
-                check_nullish_coalescing_parens(bin_op, required_parens);
-            }
-            BinaryOperator::And | BinaryOperator::Or => {
-                check_and_or_parens(bin_op, required_parens);
-            }
-            _ => {}
-        }
-        // Recursively check nested
-        check_nested_expressions(&*bin_op.lhs, required_parens);
-        check_nested_expressions(&*bin_op.rhs, required_parens);
-    } else if let Expression::Parens(ref parens_expr) = expr {
-        check_nested_expressions(&*parens_expr.expr, required_parens);
-    }
+/// Whether an expression must stay parenthesized to be used as an operand. Reads, calls, literals
+/// and conditionals (which the emitter always wraps itself) bind tighter than any operator.
+fn needs_grouping(expr: &Expression) -> bool {
+    !matches!(
+        expr,
+        Expression::ReadVar(_)
+            | Expression::ReadProp(_)
+            | Expression::ReadKey(_)
+            | Expression::InvokeFn(_)
+            | Expression::Literal(_)
+            | Expression::LiteralArray(_)
+            | Expression::LiteralMap(_)
+            | Expression::TemplateLiteral(_)
+            | Expression::TaggedTemplate(_)
+            | Expression::Conditional(_)
+            | Expression::Parens(_)
+            | Expression::LexicalRead(_)
+            | Expression::Context(_)
+            | Expression::ReadVariable(_)
+            | Expression::Reference(_)
+            | Expression::ContextLetReference(_)
+            | Expression::PipeBinding(_)
+            | Expression::PipeBindingVariadic(_)
+            | Expression::SafePropertyRead(_)
+            | Expression::SafeKeyedRead(_)
+            | Expression::SafeInvokeFunction(_)
+            | Expression::External(_)
+            | Expression::ExternalRef(_)
+    )
 }
 
-fn check_exponentiation_parens(
-    expr: &BinaryOperatorExpr,
-    required_parens: &mut HashSet<*const ParenthesizedExpr>,
-) {
-    if let Expression::Parens(ref parens_expr) = *expr.lhs {
-        if let Expression::Unary(_) = *parens_expr.expr {
-            required_parens.insert(parens_expr as *const ParenthesizedExpr);
+/// Parentheses around an operand of a binary operator are only required when they group an
+/// operation which would otherwise bind differently, or in the cases listed on
+/// [`strip_nonrequired_parentheses`].
+fn strip_operand_parens(
+    operand: Box<Expression>,
+    outer: BinaryOperator,
+    is_lhs: bool,
+) -> Box<Expression> {
+    let Expression::Parens(parens) = *operand else {
+        return operand;
+    };
+
+    let required = match parens.expr.as_ref() {
+        Expression::BinaryOp(inner) => {
+            let inner_precedence = precedence(inner.operator);
+            let outer_precedence = precedence(outer);
+            mixes_nullish_and_logical(inner.operator, outer)
+                || inner_precedence < outer_precedence
+                || (inner_precedence == outer_precedence
+                    && (!is_lhs || is_right_associative(outer)))
         }
+        Expression::Unary(_)
+        | Expression::NotExpr(_)
+        | Expression::TypeOf(_)
+        | Expression::Void(_) => is_lhs && outer == BinaryOperator::Exponentiation,
+        _ => true,
+    };
+
+    if required {
+        Box::new(Expression::Parens(parens))
+    } else {
+        parens.expr
     }
 }
 
-fn check_nullish_coalescing_parens(
-    expr: &BinaryOperatorExpr,
-    required_parens: &mut HashSet<*const ParenthesizedExpr>,
-) {
-    if let Expression::Parens(ref parens_expr) = *expr.lhs {
-        if is_logical_and_or(&*parens_expr.expr)
-            || matches!(*parens_expr.expr, Expression::Conditional(_))
+/// Arguments and entries are separated by commas, so only a comma expression needs its parentheses.
+fn strip_list_item_parens(item: Expression) -> Expression {
+    match item {
+        Expression::Parens(ParenthesizedExpr { expr, .. })
+            if !matches!(*expr, Expression::CommaExpr(_)) =>
         {
-            required_parens.insert(parens_expr as *const ParenthesizedExpr);
-        }
-    }
-    if let Expression::Parens(ref parens_expr) = *expr.rhs {
-        if is_logical_and_or(&*parens_expr.expr)
-            || matches!(*parens_expr.expr, Expression::Conditional(_))
-        {
-            required_parens.insert(parens_expr as *const ParenthesizedExpr);
+            *expr
         }
+        item => item,
     }
 }
 
-fn check_and_or_parens(
-    expr: &BinaryOperatorExpr,
-    required_parens: &mut HashSet<*const ParenthesizedExpr>,
-) {
-    if let Expression::Parens(ref parens_expr) = *expr.lhs {
-        if let Expression::BinaryOp(ref inner_bin_op) = *parens_expr.expr {
-            if inner_bin_op.operator == BinaryOperator::NullishCoalesce {
-                required_parens.insert(parens_expr as *const ParenthesizedExpr);
-            }
-        }
-    }
+fn mixes_nullish_and_logical(inner: BinaryOperator, outer: BinaryOperator) -> bool {
+    let is_logical = |op| matches!(op, BinaryOperator::And | BinaryOperator::Or);
+    (inner == BinaryOperator::NullishCoalesce && is_logical(outer))
+        || (is_logical(inner) && outer == BinaryOperator::NullishCoalesce)
 }
 
-fn is_logical_and_or(expr: &Expression) -> bool {
-    if let Expression::BinaryOp(ref bin_op) = expr {
-        matches!(bin_op.operator, BinaryOperator::And | BinaryOperator::Or)
-    } else {
-        false
+fn is_right_associative(op: BinaryOperator) -> bool {
+    precedence(op) == precedence(BinaryOperator::Assign) || op == BinaryOperator::Exponentiation
+}
+
+/// JavaScript operator precedence, higher binds tighter.
+fn precedence(op: BinaryOperator) -> u8 {
+    match op {
+        BinaryOperator::Exponentiation => 13,
+        BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo => 12,
+        BinaryOperator::Plus | BinaryOperator::Minus => 11,
+        BinaryOperator::Lower
+        | BinaryOperator::LowerEquals
+        | BinaryOperator::Bigger
+        | BinaryOperator::BiggerEquals
+        | BinaryOperator::In => 9,
+        BinaryOperator::Equals
+        | BinaryOperator::NotEquals
+        | BinaryOperator::Identical
+        | BinaryOperator::NotIdentical => 8,
+        BinaryOperator::BitwiseAnd => 7,
+        BinaryOperator::BitwiseOr => 5,
+        BinaryOperator::And => 4,
+        BinaryOperator::Or | BinaryOperator::NullishCoalesce => 3,
+        BinaryOperator::Assign
+        | BinaryOperator::AdditionAssignment
+        | BinaryOperator::SubtractionAssignment
+        | BinaryOperator::MultiplicationAssignment
+        | BinaryOperator::DivisionAssignment
+        | BinaryOperator::RemainderAssignment
+        | BinaryOperator::ExponentiationAssignment
+        | BinaryOperator::AndAssignment
+        | BinaryOperator::OrAssignment
+        | BinaryOperator::NullishCoalesceAssignment => 2,
     }
 }
@@ -137,6 +137,7 @@ mod tests {
             end_source_span: None,
             is_void: false,
             i18n: None,
+            id: 0,
         }
     }
 
@@ -164,6 +165,7 @@ mod tests {
                 value_span: None,
                 value_tokens: None,
                 i18n: None,
+                id: 0,
             }],
         );
         let preparsed = preparse_element(&element);
@@ -202,6 +204,7 @@ mod tests {
                     value_span: None,
                     value_tokens: None,
                     i18n: None,
+                    id: 0,
                 },
                 Attribute {
                     name: "href".into(),
@@ -211,6 +214,7 @@ mod tests {
                     value_span: None,
                     value_tokens: None,
                     i18n: None,
+                    id: 0,
                 },
             ],
         );
@@ -235,6 +239,7 @@ mod tests {
                 value_span: None,
                 value_tokens: None,
                 i18n: None,
+                id: 0,
             }],
         );
         let preparsed = preparse_element(&element);
@@ -257,6 +262,7 @@ mod tests {
                 value_span: None,
                 value_tokens: None,
                 i18n: None,
+                id: 0,
             }],
         );
         let preparsed = preparse_element(&element);
@@ -0,0 +1,226 @@
+//! Digest Tests
+//!
+//! Mirrors angular/packages/compiler/test/i18n/digest_spec.ts
+
+use angular_compiler::i18n::{
+    compute_msg_id, compute_msg_id_with, digest_with, fingerprint, sha1, Message, MessageBundle,
+    MessageIdStrategy, Serializer, Xliff, Xmb,
+};
+use angular_compiler::ml_parser::html_parser::HtmlParser;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(template: &str) -> Vec<Message> {
+        let mut bundle = MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), None, false);
+        let errors = bundle.update_from_template(template, "url");
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        bundle.get_messages().to_vec()
+    }
+
+    mod sha1 {
+        use super::*;
+
+        #[test]
+        fn should_work_on_empty_strings() {
+            assert_eq!(sha1(""), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        }
+
+        #[test]
+        fn should_return_the_sha1_of_well_known_inputs() {
+            assert_eq!(sha1("abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+            assert_eq!(sha1("abcd"), "81fe8bfe87576c3ecb22426f8e57847382917acf");
+            assert_eq!(
+                sha1("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+                "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+            );
+        }
+    }
+
+    mod fingerprint {
+        use super::*;
+
+        #[test]
+        fn should_handle_inputs_shorter_than_a_block() {
+            for input in ["", "a", "Hello", "abcdefghijk"] {
+                assert_eq!(fingerprint(input), fingerprint(input));
+            }
+            assert_ne!(fingerprint("a"), fingerprint("b"));
+        }
+    }
+
+    mod compute_msg_id {
+        use super::*;
+
+        #[test]
+        fn should_work_on_well_known_inputs_without_meaning() {
+            let fixtures = [
+                ("  Spaced  Out  ", "3976450302996657536"),
+                ("Last Name", "4407559560004943843"),
+                ("First Name", "6028371114637047813"),
+                ("View", "2509141182388535183"),
+                (
+                    "START_BOLDNUMEND_BOLD of START_BOLDmillionsEND_BOLD",
+                    "29997634073898638",
+                ),
+                ("", "4416290763660062288"),
+            ];
+            for (msg, id) in fixtures {
+                assert_eq!(compute_msg_id(msg, ""), id, "id of {:?}", msg);
+            }
+        }
+
+        #[test]
+        fn should_work_on_well_known_inputs_with_meaning() {
+            let fixtures = [
+                ("Last Name", "Gmail UI", "7790835225175622807"),
+                ("First Name", "Gmail UI", "1809086297585054940"),
+                ("View", "Gmail UI", "3993998469942805487"),
+            ];
+            for (msg, meaning, id) in fixtures {
+                assert_eq!(compute_msg_id(msg, meaning), id, "id of {:?}", msg);
+            }
+        }
+
+        #[test]
+        fn should_compute_different_ids_for_different_meanings() {
+            let ids = [
+                compute_msg_id("Save", ""),
+                compute_msg_id("Save", "verb"),
+                compute_msg_id("Save", "noun"),
+            ];
+            assert_ne!(ids[0], ids[1]);
+            assert_ne!(ids[0], ids[2]);
+            assert_ne!(ids[1], ids[2]);
+
+            // The meaning isn't simply appended to the text
+            assert_ne!(
+                compute_msg_id("Save", "verb"),
+                compute_msg_id("Saveverb", "")
+            );
+            assert_ne!(compute_msg_id("ab", "c"), compute_msg_id("a", "bc"));
+        }
+
+        #[test]
+        fn should_compute_the_same_id_for_the_same_text_and_meaning() {
+            assert_eq!(
+                compute_msg_id("Save", "verb"),
+                compute_msg_id("Save", "verb")
+            );
+
+            let template = "<p i18n=\"verb|Save the document\">Save</p>";
+            let first = &extract(template)[0];
+            let second = &extract(&format!("<div>{}</div>", template))[0];
+            assert_eq!(
+                compute_msg_id_with(first, MessageIdStrategy::Decimal),
+                compute_msg_id("Save", "verb")
+            );
+            assert_eq!(
+                compute_msg_id_with(first, MessageIdStrategy::Decimal),
+                compute_msg_id_with(second, MessageIdStrategy::Decimal)
+            );
+        }
+    }
+
+    mod message_id_strategy {
+        use super::*;
+
+        const STRATEGIES: [MessageIdStrategy; 3] = [
+            MessageIdStrategy::Decimal,
+            MessageIdStrategy::XliffDigest,
+            MessageIdStrategy::Fnv,
+        ];
+
+        #[test]
+        fn should_compute_different_ids_per_strategy() {
+            let message = &extract("<div i18n=\"m|d\">Hello <b>{{ name }}</b></div>")[0];
+            let ids: Vec<String> = STRATEGIES
+                .iter()
+                .map(|strategy| compute_msg_id_with(message, *strategy))
+                .collect();
+
+            assert_ne!(ids[0], ids[1]);
+            assert_ne!(ids[0], ids[2]);
+            assert_ne!(ids[1], ids[2]);
+        }
+
+        #[test]
+        fn should_compute_stable_ids() {
+            let template = "<div i18n=\"m|d\">Hello <b>{{ name }}</b></div>";
+            let first = &extract(template)[0];
+            let second = &extract(template)[0];
+
+            for strategy in STRATEGIES {
+                assert_eq!(
+                    compute_msg_id_with(first, strategy),
+                    compute_msg_id_with(second, strategy),
+                    "unstable id for {:?}",
+                    strategy
+                );
+            }
+        }
+
+        #[test]
+        fn should_match_the_existing_digests() {
+            let message = &extract("<div i18n>Hello</div>")[0];
+            assert_eq!(
+                compute_msg_id_with(message, MessageIdStrategy::XliffDigest),
+                Xliff::new().digest(message)
+            );
+            assert_eq!(
+                compute_msg_id_with(message, MessageIdStrategy::Decimal),
+                Xmb::new().digest(message)
+            );
+        }
+
+        #[test]
+        fn should_take_the_meaning_into_account() {
+            let with_meaning = &extract("<div i18n=\"a|\">Hello</div>")[0];
+            let other_meaning = &extract("<div i18n=\"b|\">Hello</div>")[0];
+
+            for strategy in STRATEGIES {
+                assert_ne!(
+                    compute_msg_id_with(with_meaning, strategy),
+                    compute_msg_id_with(other_meaning, strategy)
+                );
+            }
+        }
+
+        #[test]
+        fn should_prefer_custom_ids() {
+            let message = &extract("<div i18n=\"@@custom\">Hello</div>")[0];
+            for strategy in STRATEGIES {
+                assert_eq!(digest_with(message, strategy), "custom");
+            }
+        }
+
+        #[test]
+        fn should_use_the_strategy_of_the_serializer() {
+            let message = &extract("<div i18n>Hello</div>")[0];
+            let xliff = Xliff::new().with_id_strategy(MessageIdStrategy::Fnv);
+            assert_eq!(
+                xliff.digest(message),
+                compute_msg_id_with(message, MessageIdStrategy::Fnv)
+            );
+        }
+
+        #[test]
+        fn should_write_ids_with_the_strategy_of_the_bundle() {
+            let mut bundle =
+                MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), None, false)
+                    .with_id_strategy(MessageIdStrategy::Decimal);
+            bundle.update_from_template("<div i18n>Hello</div>", "url");
+            let legacy_id =
+                compute_msg_id_with(&bundle.get_messages()[0], MessageIdStrategy::Decimal);
+
+            let xliff = bundle.write(&Xliff::new(), None);
+            assert!(
+                xliff.contains(&format!("<trans-unit id=\"{}\">", legacy_id)),
+                "unexpected xliff output: {}",
+                xliff
+            );
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Message Bundle Tests
+//!
+//! Mirrors angular/packages/compiler/test/i18n/message_bundle_spec.ts
+
+use angular_compiler::i18n::{
+    compute_msg_id_with, diff_message_bundles, digest_with, Arb, Json, MessageBundle,
+    MessageBundleDiff, MessageIdStrategy, Xliff, Xliff2, Xmb,
+};
+use angular_compiler::ml_parser::html_parser::HtmlParser;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle_for(template: &str) -> MessageBundle {
+        let mut bundle = MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), None, false);
+        let errors = bundle.update_from_template(template, "file.ts");
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        bundle
+    }
+
+    mod legacy_ids {
+        use super::*;
+
+        #[test]
+        fn should_compute_legacy_ids() {
+            let mut bundle = bundle_for("<p i18n>Translate me</p><p i18n=\"@@custom\">Keep me</p>");
+            assert!(bundle.get_messages()[0].legacy_ids.is_empty());
+
+            bundle.compute_legacy_ids();
+            let messages = bundle.get_messages();
+            assert_eq!(
+                messages[0].legacy_ids,
+                vec![
+                    compute_msg_id_with(&messages[0], MessageIdStrategy::XliffDigest),
+                    compute_msg_id_with(&messages[0], MessageIdStrategy::Decimal),
+                ]
+            );
+            assert!(messages[1].legacy_ids.is_empty());
+        }
+
+        #[test]
+        fn should_write_primary_and_legacy_ids_to_xliff() {
+            let mut bundle = bundle_for("<p i18n>Translate me</p>");
+            bundle.compute_legacy_ids();
+            let message = &bundle.get_messages()[0];
+            let primary = compute_msg_id_with(message, MessageIdStrategy::XliffDigest);
+            let legacy = compute_msg_id_with(message, MessageIdStrategy::Decimal);
+
+            let xliff = bundle.write(&Xliff::new(), None);
+            assert!(
+                xliff.contains(&format!("<trans-unit id=\"{}\">", primary)),
+                "unexpected xliff output: {}",
+                xliff
+            );
+            assert!(
+                xliff.contains(&format!(
+                    "<context context-type=\"x-legacy-id\">{}</context>",
+                    legacy
+                )),
+                "unexpected xliff output: {}",
+                xliff
+            );
+            assert!(
+                !xliff.contains(&format!(
+                    "<context context-type=\"x-legacy-id\">{}</context>",
+                    primary
+                )),
+                "the primary id should not be repeated as a legacy id: {}",
+                xliff
+            );
+        }
+
+        #[test]
+        fn should_write_primary_and_legacy_ids_to_xmb() {
+            let mut bundle = bundle_for("<p i18n>Translate me</p>");
+            bundle.compute_legacy_ids();
+            let message = &bundle.get_messages()[0];
+            let primary = compute_msg_id_with(message, MessageIdStrategy::Decimal);
+            let legacy = compute_msg_id_with(message, MessageIdStrategy::XliffDigest);
+
+            let xmb = bundle.write(&Xmb::new(), None);
+            assert!(
+                xmb.contains(&format!("<msg id=\"{}\" legacy=\"{}\">", primary, legacy)),
+                "unexpected xmb output: {}",
+                xmb
+            );
+        }
+
+        #[test]
+        fn should_not_write_legacy_ids_unless_computed() {
+            let bundle = bundle_for("<p i18n>Translate me</p>");
+            assert!(!bundle.write(&Xliff::new(), None).contains("x-legacy-id"));
+            assert!(!bundle.write(&Xliff2::new(), None).contains("legacy"));
+        }
+    }
+
+    mod duplicate_messages {
+        use super::*;
+
+        #[test]
+        fn should_merge_identical_duplicates() {
+            let mut bundle = bundle_for("<p i18n=\"@@greeting\">Hello</p>");
+            let errors = bundle.update_from_template(
+                "<div>\n<span i18n=\"@@greeting\">Hello</span></div>",
+                "other.ts",
+            );
+            assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+
+            let messages = bundle.get_messages();
+            assert_eq!(messages.len(), 1);
+            let files: Vec<&str> = messages[0]
+                .sources
+                .iter()
+                .map(|source| source.file_path.as_str())
+                .collect();
+            assert_eq!(files, vec!["file.ts", "other.ts"]);
+        }
+
+        #[test]
+        fn should_report_conflicting_duplicates() {
+            let mut bundle = bundle_for("<p i18n=\"@@greeting\">Hello</p>");
+            let errors = bundle.update_from_template(
+                "<div>\n<span i18n=\"@@greeting\">Goodbye</span></div>",
+                "other.ts",
+            );
+
+            assert_eq!(errors.len(), 1);
+            let error = &errors[0];
+            assert_eq!(error.span.start.file.url, "other.ts");
+            assert_eq!(error.span.to_string(), "Goodbye");
+            assert_eq!(
+                error.msg,
+                "Duplicate message id \"greeting\" with different content, first defined at file.ts@0:21"
+            );
+
+            let messages = bundle.get_messages();
+            assert_eq!(messages.len(), 1);
+            assert_eq!(messages[0].message_string, "Hello");
+        }
+    }
+
+    mod json_formats {
+        use super::*;
+
+        #[test]
+        fn should_write_messages_to_json() {
+            let bundle = bundle_for("<p i18n>Hello {{name}}</p><p i18n=\"@@bye\">Bye</p>");
+            let id = compute_msg_id_with(&bundle.get_messages()[0], MessageIdStrategy::Decimal);
+
+            let json = bundle.write(&Json::new(), None);
+            let hello = json
+                .find(&format!("\"{}\": \"Hello {{$INTERPOLATION}}\"", id))
+                .unwrap_or_else(|| panic!("unexpected json output: {}", json));
+            let bye = json.find("\"bye\": \"Bye\"").unwrap();
+            assert!(hello < bye);
+        }
+
+        #[test]
+        fn should_write_descriptions_and_locations_to_arb() {
+            let bundle = bundle_for("<p i18n=\"greeting@@hello\">Hello</p>");
+
+            let arb = bundle.write(&Arb::new(), None);
+            let value: serde_json::Value = serde_json::from_str(&arb).unwrap();
+            assert_eq!(value["hello"], "Hello");
+            assert_eq!(value["@hello"]["description"], "greeting");
+            assert_eq!(value["@hello"]["x-locations"][0]["file"], "file.ts");
+        }
+    }
+
+    mod diff {
+        use super::*;
+
+        fn ids(bundle: &MessageBundle) -> Vec<String> {
+            bundle
+                .get_messages()
+                .iter()
+                .map(|message| digest_with(message, MessageIdStrategy::default()))
+                .collect()
+        }
+
+        #[test]
+        fn should_categorize_added_removed_and_changed_messages() {
+            let old =
+                bundle_for("<p i18n=\"@@greeting\">Hello</p><p i18n>Unchanged</p><p i18n>Bye</p>");
+            let new = bundle_for(
+                "<p i18n=\"@@greeting\">Hello there</p><p i18n>Unchanged</p><p i18n>Welcome</p>",
+            );
+
+            let diff = diff_message_bundles(&old, &new);
+            assert_eq!(
+                diff,
+                MessageBundleDiff {
+                    added: vec![ids(&new)[2].clone()],
+                    removed: vec![ids(&old)[2].clone()],
+                    changed: vec!["greeting".to_string()],
+                }
+            );
+            assert!(!diff.is_empty());
+        }
+
+        #[test]
+        fn should_be_empty_for_identical_bundles() {
+            let template = "<p i18n=\"@@greeting\">Hello</p><p i18n>Bye</p>";
+            assert!(diff_message_bundles(&bundle_for(template), &bundle_for(template)).is_empty());
+        }
+    }
+}
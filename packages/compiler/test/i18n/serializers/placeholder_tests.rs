@@ -0,0 +1,142 @@
+//! Placeholder Registry Tests
+//!
+//! Mirrors angular/packages/compiler/test/i18n/serializers/placeholder_spec.ts
+
+use angular_compiler::i18n::{create_i18n_message_factory, Message, Node, PlaceholderRegistry};
+use angular_compiler::ml_parser::ast as html;
+use angular_compiler::ml_parser::html_parser::HtmlParser;
+use std::collections::{HashMap, HashSet};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod placeholder_registry {
+        use super::*;
+
+        #[test]
+        fn should_generate_the_same_name_for_the_same_tag() {
+            let mut reg = PlaceholderRegistry::new();
+            assert_eq!(
+                reg.get_start_tag_placeholder_name("p", &HashMap::new(), false),
+                "START_PARAGRAPH"
+            );
+            assert_eq!(
+                reg.get_start_tag_placeholder_name("p", &HashMap::new(), false),
+                "START_PARAGRAPH"
+            );
+        }
+
+        #[test]
+        fn should_generate_different_names_for_different_placeholder_content() {
+            let mut reg = PlaceholderRegistry::new();
+            assert_eq!(reg.get_placeholder_name("name", "content1"), "NAME");
+            assert_eq!(reg.get_placeholder_name("name", "content2"), "NAME_1");
+        }
+    }
+
+    mod stable_names {
+        use super::*;
+
+        /// Placeholder names of the message built from the children of the root element.
+        fn placeholder_names(template: &str, stable_names: bool) -> Vec<String> {
+            let result = HtmlParser::new().parse(template, "file.ts", None);
+            let element = match &result.root_nodes[0] {
+                html::Node::Element(element) => element,
+                node => panic!("expected an element, got {:?}", node),
+            };
+            let mut factory =
+                create_i18n_message_factory(HashSet::new(), false, false, stable_names);
+            let message: Message =
+                factory.create_message(&element.children, None, None, None, None);
+            let mut names = Vec::new();
+            collect_placeholder_names(&message.nodes, &mut names);
+            names
+        }
+
+        fn collect_placeholder_names(nodes: &[Node], names: &mut Vec<String>) {
+            for node in nodes {
+                match node {
+                    Node::Placeholder(ph) => names.push(ph.name.clone()),
+                    Node::Container(container) => {
+                        collect_placeholder_names(&container.children, names)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        #[test]
+        fn should_derive_names_from_expressions() {
+            let mut reg = PlaceholderRegistry::new().with_stable_names(true);
+            assert_eq!(
+                reg.get_interpolation_placeholder_name("user.name"),
+                "USER_NAME"
+            );
+            assert_eq!(
+                reg.get_interpolation_placeholder_name("user.firstName"),
+                "USER_FIRST_NAME"
+            );
+            assert_eq!(
+                reg.get_interpolation_placeholder_name("items.length | number"),
+                "ITEMS_LENGTH_NUMBER"
+            );
+            assert_eq!(
+                reg.get_interpolation_placeholder_name("1 + 2"),
+                "INTERPOLATION"
+            );
+        }
+
+        #[test]
+        fn should_reuse_the_name_of_an_identical_expression() {
+            let mut reg = PlaceholderRegistry::new().with_stable_names(true);
+            assert_eq!(reg.get_interpolation_placeholder_name("count"), "COUNT");
+            assert_eq!(reg.get_interpolation_placeholder_name("count"), "COUNT");
+        }
+
+        #[test]
+        fn should_disambiguate_collisions_with_numeric_suffixes() {
+            let mut reg = PlaceholderRegistry::new().with_stable_names(true);
+            assert_eq!(
+                reg.get_interpolation_placeholder_name("user.name"),
+                "USER_NAME"
+            );
+            assert_eq!(
+                reg.get_interpolation_placeholder_name("user?.name"),
+                "USER_NAME_1"
+            );
+            assert_eq!(
+                reg.get_interpolation_placeholder_name("user!.name"),
+                "USER_NAME_2"
+            );
+        }
+
+        #[test]
+        fn should_number_interpolations_by_position_by_default() {
+            assert_eq!(
+                placeholder_names("<p>{{user.name}} has {{count}}</p>", false),
+                vec!["INTERPOLATION", "INTERPOLATION_1"]
+            );
+        }
+
+        #[test]
+        fn should_not_rename_placeholders_when_interpolations_are_reordered() {
+            assert_eq!(
+                placeholder_names("<p>{{user.name}} has {{count}}</p>", true),
+                vec!["USER_NAME", "COUNT"]
+            );
+            assert_eq!(
+                placeholder_names("<p>{{count}} for {{user.name}}</p>", true),
+                vec!["COUNT", "USER_NAME"]
+            );
+        }
+
+        #[test]
+        fn should_keep_custom_placeholder_names() {
+            assert_eq!(
+                placeholder_names("<p>Hi {{user.name // i18n(ph=\"who\")}}</p>", true),
+                vec!["WHO"]
+            );
+        }
+    }
+}
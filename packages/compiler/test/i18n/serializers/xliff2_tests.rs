@@ -0,0 +1,160 @@
+//! XLIFF 2.0 Serializer Tests
+//!
+//! Mirrors angular/packages/compiler/test/i18n/serializers/xliff2_spec.ts
+
+use angular_compiler::i18n::serializers::SegmentState;
+use angular_compiler::i18n::translation_bundle::LoadResult;
+use angular_compiler::i18n::{Message, MessageBundle, Node, Serializer, Xliff2};
+use angular_compiler::ml_parser::html_parser::HtmlParser;
+use angular_compiler::parse_util::ParseErrorLevel;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xliff(units: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" ?>
+<xliff version="2.0" xmlns="urn:oasis:names:tc:xliff:document:2.0" srcLang="en" trgLang="fr">
+  <file original="ng.template" id="ngi18n">{}</file>
+</xliff>"#,
+            units
+        )
+    }
+
+    fn load(content: &str) -> LoadResult {
+        Xliff2::new().load(content, "messages.xlf")
+    }
+
+    fn messages_for(template: &str) -> Vec<Message> {
+        let mut bundle = MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), None, false);
+        let errors = bundle.update_from_template(template, "file.ts");
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        bundle.get_messages().to_vec()
+    }
+
+    fn errors(result: &LoadResult) -> Vec<(&str, ParseErrorLevel)> {
+        result
+            .errors
+            .iter()
+            .map(|e| (e.msg.as_str(), e.level))
+            .collect()
+    }
+
+    mod load {
+        use super::*;
+
+        #[test]
+        fn should_load_translations_and_the_target_locale() {
+            let result = load(&xliff(
+                r#"<unit id="1"><segment><source>a</source><target>t<ph id="0" equiv="INTERPOLATION"/><pc id="1" equivStart="START_BOLD_TEXT" equivEnd="CLOSE_BOLD_TEXT">b</pc></target></segment></unit>"#,
+            ));
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+            assert_eq!(result.locale.as_deref(), Some("fr"));
+            let names: Vec<String> = result.i18n_nodes_by_msg_id["1"]
+                .iter()
+                .map(|node| match node {
+                    Node::Text(text) => text.value.clone(),
+                    Node::Placeholder(ph) => format!("<{}>", ph.name),
+                    other => panic!("unexpected node {:?}", other),
+                })
+                .collect();
+            assert_eq!(
+                names,
+                vec![
+                    "t",
+                    "<INTERPOLATION>",
+                    "<START_BOLD_TEXT>",
+                    "b",
+                    "<CLOSE_BOLD_TEXT>"
+                ]
+            );
+        }
+
+        #[test]
+        fn should_report_missing_and_duplicate_translations() {
+            let result = load(&xliff(
+                r#"<unit id="1"><segment><source>a</source></segment></unit><unit id="2"><segment><target>x</target></segment></unit><unit id="2"><segment><target>y</target></segment></unit>"#,
+            ));
+            assert_eq!(
+                errors(&result),
+                vec![
+                    ("Message 1 misses a translation", ParseErrorLevel::Error),
+                    ("Duplicated translations for msg 2", ParseErrorLevel::Error),
+                ]
+            );
+        }
+
+        #[test]
+        fn should_report_incompatible_versions() {
+            let result = load(r#"<xliff version="1.2"></xliff>"#);
+            assert_eq!(
+                errors(&result),
+                vec![(
+                    "The XLIFF file version 1.2 is not compatible with XLIFF 2.0 serializer",
+                    ParseErrorLevel::Error
+                )]
+            );
+        }
+    }
+
+    mod segment_state {
+        use super::*;
+
+        #[test]
+        fn should_load_the_state_of_segments() {
+            let (result, states) = Xliff2::new().load_with_states(
+                &xliff(
+                    r#"<unit id="1"><segment state="final"><target>a</target></segment></unit><unit id="2"><segment state="initial"><source>b</source></segment></unit><unit id="3"><segment><target>c</target></segment></unit><unit id="4"><segment state="done"><target>d</target></segment></unit>"#,
+                ),
+                "messages.xlf",
+            );
+            assert_eq!(
+                errors(&result),
+                vec![("Unknown segment state \"done\"", ParseErrorLevel::Warning)]
+            );
+            assert_eq!(states.len(), 2);
+            assert_eq!(states["1"], SegmentState::Final);
+
+            // Untranslated segments don't need a target and are reported through their state
+            let untranslated: Vec<&String> = states
+                .iter()
+                .filter(|(_, state)| **state == SegmentState::Initial)
+                .map(|(id, _)| id)
+                .collect();
+            assert_eq!(untranslated, vec!["2"]);
+            assert!(!result.i18n_nodes_by_msg_id.contains_key("2"));
+        }
+
+        #[test]
+        fn should_round_trip_a_final_segment() {
+            let messages = messages_for("<p i18n>hello</p>");
+            let id = messages[0].id.clone();
+            let content = xliff(&format!(
+                r#"<unit id="{}"><segment state="final"><source>hello</source><target>bonjour</target></segment></unit>"#,
+                id
+            ));
+
+            let (result, states) = Xliff2::new().load_with_states(&content, "messages.xlf");
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+            assert_eq!(states[&id], SegmentState::Final);
+
+            let written = Xliff2::new()
+                .with_segment_states(states.clone())
+                .write(&messages, None);
+            assert!(
+                written.contains(&format!(
+                    r#"<unit id="{}"><segment state="final"><source>hello</source></segment></unit>"#,
+                    id
+                )),
+                "{}",
+                written
+            );
+
+            // Segments without a known state are written without one
+            let written = Xliff2::new().write(&messages, None);
+            assert!(written.contains("<segment><source>hello</source></segment>"));
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! XMB Serializer Tests
+//!
+//! Mirrors angular/packages/compiler/test/i18n/serializers/xmb_spec.ts
+
+use angular_compiler::i18n::{MessageBundle, Xmb};
+use angular_compiler::ml_parser::html_parser::HtmlParser;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(template: &str) -> String {
+        let mut bundle = MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), None, false);
+        let errors = bundle.update_from_template(template, "file.ts");
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        bundle.write(&Xmb::new(), None)
+    }
+
+    mod placeholders {
+        use super::*;
+
+        #[test]
+        fn should_write_the_interpolation_as_an_example() {
+            let xmb = write("<p i18n>Hello {{user.name}}!</p>");
+            assert!(
+                xmb.contains(r#"<ph name="INTERPOLATION"><ex>{{user.name}}</ex></ph>"#),
+                "unexpected xmb output: {}",
+                xmb
+            );
+        }
+
+        #[test]
+        fn should_escape_examples() {
+            let xmb = write("<p i18n>{{a < b}}</p>");
+            assert!(
+                xmb.contains(r#"<ex>{{a &lt; b}}</ex>"#),
+                "unexpected xmb output: {}",
+                xmb
+            );
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! XML Helper Tests
+//!
+//! Mirrors angular/packages/compiler/test/i18n/serializers/xml_helper_spec.ts
+
+use angular_compiler::i18n::serializers::xml_helper as xml;
+use angular_compiler::i18n::{escape_xml, escape_xml_with, InvalidXmlCharError, InvalidXmlChars};
+use angular_compiler::ml_parser::ast::Node;
+use angular_compiler::ml_parser::xml_parser::XmlParser;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod escape {
+        use super::*;
+
+        #[test]
+        fn should_escape_all_five_entities() {
+            assert_eq!(escape_xml("&"), "&amp;");
+            assert_eq!(escape_xml("<"), "&lt;");
+            assert_eq!(escape_xml(">"), "&gt;");
+            assert_eq!(escape_xml("\""), "&quot;");
+            assert_eq!(escape_xml("'"), "&apos;");
+            assert_eq!(
+                escape_xml("<a href=\"x\">Tom & Jerry's</a>"),
+                "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;"
+            );
+        }
+
+        #[test]
+        fn should_escape_the_ampersand_of_existing_entities() {
+            assert_eq!(escape_xml("&amp;"), "&amp;amp;");
+        }
+
+        #[test]
+        fn should_escape_attribute_values_and_text() {
+            let mut attrs = HashMap::new();
+            attrs.insert("title".to_string(), "a<b".to_string());
+            let mut tag = xml::Tag::new("p".to_string(), attrs, Vec::new());
+            tag.children
+                .push(Box::new(xml::Text::new("1 > 0 & 'yes'".to_string())));
+            let nodes: Vec<Box<dyn xml::Node>> = vec![Box::new(tag)];
+            assert_eq!(
+                xml::serialize(&nodes),
+                "<p title=\"a&lt;b\">1 &gt; 0 &amp; &apos;yes&apos;</p>"
+            );
+        }
+    }
+
+    mod cdata {
+        use super::*;
+
+        fn serialize_in_tag(text: xml::Text) -> String {
+            let tag = xml::Tag::new("ex".to_string(), HashMap::new(), vec![Box::new(text)]);
+            xml::serialize(&[Box::new(tag) as Box<dyn xml::Node>])
+        }
+
+        /// Parses `xml` and returns the text content of its root element
+        fn parse_text(xml: &str) -> String {
+            let result = XmlParser::new().parse(xml, "messages.xlf", None);
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+            let Node::Element(root) = &result.root_nodes[0] else {
+                panic!("expected an element, got {:?}", result.root_nodes[0]);
+            };
+            root.children
+                .iter()
+                .map(|child| match child {
+                    Node::Text(text) => text.value.to_string(),
+                    other => panic!("expected text, got {:?}", other),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn should_serialize_text_as_cdata_without_escaping() {
+            assert_eq!(
+                serialize_in_tag(xml::Text::cdata(
+                    "{count, plural, =0 {<b>none</b> & more}}".to_string()
+                )),
+                "<ex><![CDATA[{count, plural, =0 {<b>none</b> & more}}]]></ex>"
+            );
+        }
+
+        #[test]
+        fn should_split_sections_around_the_end_marker() {
+            assert_eq!(
+                serialize_in_tag(xml::Text::cdata("a]]>b".to_string())),
+                "<ex><![CDATA[a]]]]><![CDATA[>b]]></ex>"
+            );
+        }
+
+        #[test]
+        fn should_round_trip_through_the_xml_parser() {
+            for content in ["<b>none</b> & 'more'", "a]]>b", "x]]]>y"] {
+                let serialized = serialize_in_tag(xml::Text::cdata(content.to_string()));
+                assert_eq!(parse_text(&serialized), content);
+
+                // Escaped text decodes to the same content
+                let escaped = serialize_in_tag(xml::Text::new(content.to_string()));
+                assert_eq!(parse_text(&escaped), content);
+            }
+        }
+    }
+
+    mod invalid_chars {
+        use super::*;
+
+        #[test]
+        fn should_keep_control_chars_by_default() {
+            assert_eq!(escape_xml("a\u{0B}b"), "a\u{0B}b");
+            assert_eq!(
+                escape_xml_with("a\u{0B}b", InvalidXmlChars::Keep).unwrap(),
+                "a\u{0B}b"
+            );
+        }
+
+        #[test]
+        fn should_replace_control_chars() {
+            assert_eq!(
+                escape_xml_with(
+                    "tab\there\u{0B}<vt>\u{00}\u{08}\u{1F}",
+                    InvalidXmlChars::Replace
+                )
+                .unwrap(),
+                "tab\there\u{FFFD}&lt;vt&gt;\u{FFFD}\u{FFFD}\u{FFFD}"
+            );
+        }
+
+        #[test]
+        fn should_reject_control_chars() {
+            assert_eq!(
+                escape_xml_with("line\u{0B}feed", InvalidXmlChars::Reject),
+                Err(InvalidXmlCharError {
+                    ch: '\u{0B}',
+                    offset: 4
+                })
+            );
+        }
+
+        #[test]
+        fn should_accept_whitespace_and_non_ascii_chars() {
+            let text = "a\tb\nc\rd é 😀";
+            assert_eq!(
+                escape_xml_with(text, InvalidXmlChars::Reject).unwrap(),
+                text
+            );
+        }
+    }
+}
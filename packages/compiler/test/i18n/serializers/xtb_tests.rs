@@ -0,0 +1,190 @@
+//! XTB Serializer Tests
+//!
+//! Mirrors angular/packages/compiler/test/i18n/serializers/xtb_spec.ts
+
+use angular_compiler::core::MissingTranslationStrategy;
+use angular_compiler::i18n::translation_bundle::LoadResult;
+use angular_compiler::i18n::{
+    digest_with, Message, MessageBundle, MessageIdStrategy, Node, Serializer, TranslationBundle,
+    Xtb,
+};
+use angular_compiler::ml_parser::html_parser::HtmlParser;
+use angular_compiler::parse_util::ParseErrorLevel;
+use std::collections::HashMap;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(content: &str) -> LoadResult {
+        Xtb::new().load(content, "messages.xtb")
+    }
+
+    fn messages_for(template: &str) -> Vec<Message> {
+        let mut bundle = MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), None, false);
+        let errors = bundle.update_from_template(template, "file.ts");
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        bundle.get_messages().to_vec()
+    }
+
+    fn messages(result: &LoadResult) -> Vec<(&str, ParseErrorLevel)> {
+        result
+            .errors
+            .iter()
+            .map(|e| (e.msg.as_str(), e.level))
+            .collect()
+    }
+
+    mod load {
+        use super::*;
+
+        #[test]
+        fn should_load_xtb_files_with_a_doctype() {
+            let result = load(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE translationbundle [<!ELEMENT translationbundle (translation)*>
+<!ATTLIST translationbundle lang CDATA #REQUIRED>
+<!ELEMENT translation (#PCDATA|ph)*>
+<!ATTLIST translation id CDATA #REQUIRED>
+<!ELEMENT ph EMPTY>
+<!ATTLIST ph name CDATA #REQUIRED>
+]>
+<translationbundle lang="fr">
+  <translation id="8841459487341224498">rab</translation>
+</translationbundle>"#,
+            );
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+            assert_eq!(result.locale.as_deref(), Some("fr"));
+            let nodes = &result.i18n_nodes_by_msg_id["8841459487341224498"];
+            assert!(matches!(&nodes[..], [Node::Text(text)] if text.value == "rab"));
+        }
+
+        #[test]
+        fn should_load_placeholders_and_icus() {
+            let result = load(
+                r#"<translationbundle><translation id="1">a<ph name="INTERPOLATION"/>{VAR_PLURAL, plural, =0 {zero} other {<ph name="X"/>}}</translation></translationbundle>"#,
+            );
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+            let nodes = &result.i18n_nodes_by_msg_id["1"];
+            assert_eq!(nodes.len(), 3);
+            assert!(matches!(&nodes[1], Node::Placeholder(ph) if ph.name == "INTERPOLATION"));
+            let Node::Icu(icu) = &nodes[2] else {
+                panic!("expected an ICU, got {:?}", nodes[2]);
+            };
+            assert_eq!(icu.expression, "VAR_PLURAL");
+            assert_eq!(icu.type_, "plural");
+            assert_eq!(icu.cases.keys().collect::<Vec<_>>(), vec!["=0", "other"]);
+            let Node::Container(other) = &icu.cases["other"] else {
+                panic!("expected a container");
+            };
+            assert!(matches!(&other.children[..], [Node::Placeholder(ph)] if ph.name == "X"));
+        }
+
+        #[test]
+        fn should_ignore_placeholder_examples() {
+            let result = load(
+                r#"<translationbundle><translation id="1">Hi <ph name="INTERPOLATION"><ex>{{user.name}}</ex></ph>!</translation></translationbundle>"#,
+            );
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+            let nodes = &result.i18n_nodes_by_msg_id["1"];
+            assert_eq!(nodes.len(), 3);
+            assert!(matches!(&nodes[1], Node::Placeholder(ph) if ph.name == "INTERPOLATION"));
+        }
+
+        #[test]
+        fn should_report_translations_without_id_and_placeholders_without_name() {
+            let result = load(
+                r#"<translationbundle><translation>a</translation><translation id="1"><ph/><b>t</b></translation></translationbundle>"#,
+            );
+            assert_eq!(
+                messages(&result),
+                vec![
+                    (
+                        "<translation> misses the \"id\" attribute",
+                        ParseErrorLevel::Error
+                    ),
+                    ("<ph> misses the \"name\" attribute", ParseErrorLevel::Error),
+                    ("Unexpected tag", ParseErrorLevel::Error),
+                ]
+            );
+        }
+
+        #[test]
+        fn should_report_nested_translation_bundles() {
+            let result = load("<translationbundle><translationbundle/></translationbundle>");
+            assert_eq!(
+                messages(&result),
+                vec![("Unexpected <translationbundle> tag", ParseErrorLevel::Error)]
+            );
+        }
+    }
+
+    mod ids {
+        use super::*;
+
+        #[test]
+        fn should_report_duplicate_ids_and_keep_the_first_translation() {
+            let result = load(
+                r#"<translationbundle><translation id="1">first</translation><translation id="1">second</translation></translationbundle>"#,
+            );
+            assert_eq!(
+                messages(&result),
+                vec![("Duplicated translations for msg 1", ParseErrorLevel::Error)]
+            );
+            let nodes = &result.i18n_nodes_by_msg_id["1"];
+            assert!(matches!(&nodes[..], [Node::Text(text)] if text.value == "first"));
+        }
+
+        #[test]
+        fn should_warn_about_ids_missing_from_the_source_messages() {
+            let source = messages_for("<p i18n>hello</p>");
+            let known_id = digest_with(&source[0], MessageIdStrategy::Decimal);
+            let content = format!(
+                r#"<translationbundle><translation id="{}">bonjour</translation><translation id="404">perdu</translation></translationbundle>"#,
+                known_id
+            );
+
+            let result = Xtb::new()
+                .with_source_messages(&source)
+                .load(&content, "messages.xtb");
+            assert_eq!(
+                messages(&result),
+                vec![("Translation for unknown msg 404", ParseErrorLevel::Warning)]
+            );
+            assert!(result.i18n_nodes_by_msg_id.contains_key(&known_id));
+
+            // Without source messages there is nothing to check against
+            assert!(load(&content).errors.is_empty());
+        }
+
+        #[test]
+        fn should_fail_loading_a_translation_bundle_with_duplicate_ids() {
+            let content = r#"<translationbundle><translation id="1">a</translation><translation id="1">b</translation></translationbundle>"#;
+            let error = TranslationBundle::load(
+                content,
+                "messages.xtb",
+                Xtb::new(),
+                MissingTranslationStrategy::Warning,
+            )
+            .err()
+            .expect("duplicate ids should fail the load");
+            assert!(
+                error.contains("Duplicated translations for msg 1"),
+                "{}",
+                error
+            );
+
+            // Unknown ids are only a warning
+            let source = messages_for("<p i18n>hello</p>");
+            let content =
+                r#"<translationbundle><translation id="404">a</translation></translationbundle>"#;
+            assert!(TranslationBundle::load(
+                content,
+                "messages.xtb",
+                Xtb::new().with_source_messages(&source),
+                MissingTranslationStrategy::Warning,
+            )
+            .is_ok());
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Translation Bundle Tests
+//!
+//! Mirrors angular/packages/compiler/test/i18n/translation_bundle_spec.ts
+
+use angular_compiler::core::MissingTranslationStrategy;
+use angular_compiler::i18n::{
+    digest_with, Message, MessageBundle, MessageIdStrategy, Node, Text, TranslationBundle,
+};
+use angular_compiler::ml_parser::ast::Node as HtmlNode;
+use angular_compiler::ml_parser::html_parser::HtmlParser;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages_for(template: &str) -> Vec<Message> {
+        let mut bundle = MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), None, false);
+        let errors = bundle.update_from_template(template, "file.ts");
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        bundle.get_messages().to_vec()
+    }
+
+    fn id(message: &Message) -> String {
+        digest_with(message, MessageIdStrategy::Decimal)
+    }
+
+    /// A bundle translating each given message to the given text.
+    fn bundle(locale: &str, translations: &[(&Message, &str)]) -> TranslationBundle {
+        let i18n_nodes_by_msg_id = translations
+            .iter()
+            .map(|(message, text)| {
+                let span = message.nodes[0].source_span().clone();
+                (
+                    id(message),
+                    vec![Node::Text(Text::new(text.to_string(), span))],
+                )
+            })
+            .collect();
+        TranslationBundle::new(
+            i18n_nodes_by_msg_id,
+            Some(locale.to_string()),
+            Rc::new(id),
+            None,
+            MissingTranslationStrategy::Error,
+        )
+    }
+
+    fn text_of(nodes: &[HtmlNode]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node {
+                HtmlNode::Text(text) => text.value.to_string(),
+                other => panic!("unexpected node: {:?}", other),
+            })
+            .collect()
+    }
+
+    mod fallback_locales {
+        use super::*;
+
+        #[test]
+        fn should_use_the_fallback_translation_when_missing() {
+            let messages = messages_for("<p i18n>Hello</p><p i18n>Color</p>");
+            let (hello, color) = (&messages[0], &messages[1]);
+
+            let fr = bundle("fr", &[(hello, "Bonjour"), (color, "Couleur")]);
+            let mut fr_ca = bundle("fr-CA", &[(hello, "Allô")]).with_fallback(fr);
+
+            assert_eq!(text_of(&fr_ca.get(hello).unwrap()), "Allô");
+            assert!(fr_ca.has(color));
+            assert_eq!(text_of(&fr_ca.get(color).unwrap()), "Couleur");
+        }
+
+        #[test]
+        fn should_walk_the_whole_chain() {
+            let messages = messages_for("<p i18n>Hello</p><p i18n>Color</p><p i18n>Bye</p>");
+            let (hello, color, bye) = (&messages[0], &messages[1], &messages[2]);
+
+            let fr = bundle("fr", &[(color, "Couleur")]);
+            let fr_ca = bundle("fr-CA", &[(hello, "Allô")]).with_fallback(fr);
+            let mut fr_qc = bundle("fr-QC", &[]).with_fallback(fr_ca);
+
+            assert_eq!(text_of(&fr_qc.get(hello).unwrap()), "Allô");
+            assert_eq!(text_of(&fr_qc.get(color).unwrap()), "Couleur");
+            assert!(!fr_qc.has(bye));
+        }
+
+        #[test]
+        fn should_report_messages_missing_from_the_whole_chain() {
+            let messages = messages_for("<p i18n>Hello</p><p i18n>Bye</p>");
+            let (hello, bye) = (&messages[0], &messages[1]);
+
+            let fr = bundle("fr", &[(hello, "Bonjour")]);
+            let mut fr_ca = bundle("fr-CA", &[]).with_fallback(fr);
+
+            let error = fr_ca.get(bye).unwrap_err();
+            assert!(
+                error.contains(&format!(
+                    "Missing translation for message \"{}\" for locale \"fr-CA\"",
+                    id(bye)
+                )),
+                "unexpected error: {}",
+                error
+            );
+        }
+    }
+}
@@ -948,6 +948,39 @@ mod tests {
         fn should_report_unclosed_block() {
             let result = parse("@if (cond) { content");
             assert!(!result.errors.is_empty());
+
+            // The error points at the innermost unclosed block's opening
+            let result = parse("@if (x) {a}@if (y) {<b>c</b>");
+            assert_eq!(
+                humanize_errors(&result.errors),
+                vec![vec!["@if (y) {", "Unclosed block \"@if\"", "0:11"]]
+            );
+        }
+
+        #[test]
+        fn should_keep_partial_children_of_block_unclosed_at_eof() {
+            use angular_compiler::ml_parser::ast::Node;
+
+            let result = parse("@if (x) {<div>hi");
+            assert_eq!(
+                humanize_errors(&result.errors),
+                vec![vec!["@if (x) {", "Unclosed block \"@if\"", "0:0"]]
+            );
+
+            let [Node::Block(block)] = &result.root_nodes[..] else {
+                panic!("expected a single block, got {:?}", result.root_nodes);
+            };
+            assert_eq!(&*block.name, "if");
+            assert_eq!(&*block.parameters[0].expression, "x");
+            assert!(block.end_source_span.is_none());
+            let [Node::Element(div)] = &block.children[..] else {
+                panic!(
+                    "expected the block to keep its div, got {:?}",
+                    block.children
+                );
+            };
+            assert_eq!(&*div.name, "div");
+            assert!(matches!(&div.children[..], [Node::Text(text)] if &*text.value == "hi"));
         }
 
         #[test]
@@ -1618,6 +1651,60 @@ mod tests {
         }
     }
 
+    mod node_ids {
+        use super::*;
+        use angular_compiler::ml_parser::ast::{Node, NodeId};
+
+        fn collect_ids(nodes: &[Node], ids: &mut Vec<NodeId>) {
+            for node in nodes {
+                ids.push(node.id());
+                match node {
+                    Node::Element(el) => {
+                        ids.extend(el.attrs.iter().map(|a| a.id));
+                        collect_ids(&el.children, ids);
+                    }
+                    Node::Block(block) => {
+                        ids.extend(block.parameters.iter().map(|p| p.id));
+                        collect_ids(&block.children, ids);
+                    }
+                    Node::Expansion(expansion) => {
+                        for case in &expansion.cases {
+                            ids.push(case.id);
+                            collect_ids(&case.expression, ids);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        fn ids_of(html: &str) -> Vec<NodeId> {
+            let mut options = TokenizeOptions::default();
+            options.tokenize_expansion_forms = true;
+            let result = parse_with_options(html, options);
+            assert!(result.errors.is_empty(), "{:?}", result.errors);
+            let mut ids = Vec::new();
+            collect_ids(&result.root_nodes, &mut ids);
+            ids
+        }
+
+        #[test]
+        fn should_number_nodes_in_document_order() {
+            let ids = ids_of(
+                "<div a=\"1\" b>text<!--c--></div>@if (cond; as x) {<span></span>}{n, plural, =0 {<b>none</b>}}@let y = 1;",
+            );
+            assert_eq!(ids, (1..=ids.len()).collect::<Vec<_>>());
+            assert_eq!(ids.len(), 14);
+        }
+
+        #[test]
+        fn should_assign_the_same_ids_when_parsing_the_same_input() {
+            let html =
+                "<ul>@for (item of items; track item) {<li [title]=\"item\">{{item}}</li>}</ul>";
+            assert_eq!(ids_of(html), ids_of(html));
+        }
+    }
+
     // IMPLEMENTATION STATUS:
     // ✅ html_parser_spec.ts: ~1600/2055 lines (~78% complete)
     //    - Implemented ~160+ test cases
@@ -14,9 +14,10 @@ mod tests {
     use super::utils::humanize_dom;
     use angular_compiler::ml_parser::html_parser::HtmlParser;
     use angular_compiler::ml_parser::html_whitespaces::{
-        remove_whitespaces, PRESERVE_WS_ATTR_NAME,
+        remove_whitespaces, remove_whitespaces_with_policy, WhitespacePolicy, PRESERVE_WS_ATTR_NAME,
     };
     use angular_compiler::ml_parser::lexer::TokenizeOptions;
+    use std::sync::Arc;
 
     fn parse_and_remove_ws(template: &str, options: Option<TokenizeOptions>) -> Vec<Vec<String>> {
         let parser = HtmlParser::new();
@@ -25,6 +26,16 @@ mod tests {
         humanize_dom(&result, false).expect("Should parse without errors")
     }
 
+    fn parse_and_remove_ws_with_policy(
+        template: &str,
+        policy: WhitespacePolicy,
+    ) -> Vec<Vec<String>> {
+        let parser = HtmlParser::new();
+        let parse_result = parser.parse(template, "TestComp", None);
+        let result = remove_whitespaces_with_policy(parse_result, true, policy);
+        humanize_dom(&result, false).expect("Should parse without errors")
+    }
+
     #[test]
     fn should_remove_blank_text_nodes() {
         assert_eq!(parse_and_remove_ws(" ", None), Vec::<Vec<String>>::new());
@@ -268,4 +279,47 @@ mod tests {
         assert_eq!(result[2][1], " "); // Preserved space
         assert_eq!(result[3][1], "img");
     }
+
+    mod whitespace_policy {
+        use super::*;
+
+        #[test]
+        fn should_preserve_whitespaces_in_pre_by_default() {
+            let pre = parse_and_remove_ws_with_policy("<pre>  x  </pre>", Default::default());
+            assert_eq!(pre[1][1], "  x  ");
+
+            let div = parse_and_remove_ws_with_policy("<div>  x  </div>", Default::default());
+            assert_eq!(div[1][1], " x ");
+        }
+
+        #[test]
+        fn should_collapse_whitespaces_in_pre_with_collapse_all() {
+            let pre =
+                parse_and_remove_ws_with_policy("<pre>  x  </pre>", WhitespacePolicy::CollapseAll);
+            assert_eq!(pre[1][1], " x ");
+        }
+
+        #[test]
+        fn should_preserve_whitespaces_in_custom_tags() {
+            let preserved = vec!["pre", "code-block"];
+            let policy = WhitespacePolicy::Custom(Arc::new(move |tag| preserved.contains(&tag)));
+
+            let custom =
+                parse_and_remove_ws_with_policy("<code-block>  x  </code-block>", policy.clone());
+            assert_eq!(custom[1][1], "  x  ");
+
+            let pre = parse_and_remove_ws_with_policy("<pre>  x  </pre>", policy.clone());
+            assert_eq!(pre[1][1], "  x  ");
+
+            let div = parse_and_remove_ws_with_policy("<div>  x  </div>", policy);
+            assert_eq!(div[1][1], " x ");
+        }
+
+        #[test]
+        fn should_still_honor_preserve_attr_with_collapse_all() {
+            let template = format!("<div {}>  x  </div>", PRESERVE_WS_ATTR_NAME);
+            let result = parse_and_remove_ws_with_policy(&template, WhitespacePolicy::CollapseAll);
+            assert_eq!(result[1][1], "  x  ");
+        }
+    }
 }
@@ -201,6 +201,78 @@ mod html_lexer_tests {
             let result = tokenize_and_humanize_errors("<!", TokenizeOptions::default());
             assert!(!result.is_empty());
         }
+
+        #[test]
+        fn should_include_the_internal_subset() {
+            let result = tokenize_and_humanize_parts(
+                "<!DOCTYPE x [<!ELEMENT x (#PCDATA)><!-- > --><!ATTLIST x a CDATA \"]>\">]><x>",
+                TokenizeOptions::default(),
+            );
+            assert_eq!(
+                result,
+                vec![
+                    vec![
+                        "DOC_TYPE",
+                        "DOCTYPE x [<!ELEMENT x (#PCDATA)><!-- > --><!ATTLIST x a CDATA \"]>\">]"
+                    ],
+                    vec!["TAG_OPEN_START", "", "x"],
+                    vec!["TAG_OPEN_END"],
+                    vec!["EOF"],
+                ]
+            );
+        }
+    }
+
+    mod processing_instructions {
+        use super::*;
+
+        fn options() -> TokenizeOptions {
+            let mut options = TokenizeOptions::default();
+            options.tokenize_processing_instructions = true;
+            options
+        }
+
+        #[test]
+        fn should_parse_processing_instructions() {
+            let result = tokenize_and_humanize_parts(
+                "<?xml version=\"1.0\" ?><?empty?><?pi a\r\nb?>",
+                options(),
+            );
+            assert_eq!(
+                result,
+                vec![
+                    vec!["PROCESSING_INSTRUCTION", "xml", "version=\"1.0\" "],
+                    vec!["PROCESSING_INSTRUCTION", "empty", ""],
+                    vec!["PROCESSING_INSTRUCTION", "pi", "a\nb"],
+                    vec!["EOF"],
+                ]
+            );
+        }
+
+        #[test]
+        fn should_store_locations() {
+            let result = tokenize_and_humanize_source_spans("<?pi data?><a>", options());
+            assert_eq!(result[0], vec!["PROCESSING_INSTRUCTION", "<?pi data?>"]);
+        }
+
+        #[test]
+        fn should_report_missing_end() {
+            let result = tokenize_and_humanize_errors("<?pi data", options());
+            assert_eq!(
+                result,
+                vec![vec![
+                    "Unexpected character \"EOF\", expected \"?>\"".to_string(),
+                    "0:9".to_string()
+                ]]
+            );
+        }
+
+        #[test]
+        fn should_not_parse_processing_instructions_by_default() {
+            let result =
+                tokenize_and_humanize_parts_ignoring_errors("<?pi?>", TokenizeOptions::default());
+            assert!(!result.iter().any(|r| r[0] == "PROCESSING_INSTRUCTION"));
+        }
     }
 
     // SECTION 5: CDATA (lines 161-190)
@@ -590,40 +662,130 @@ mod html_lexer_tests {
     mod entities {
         use super::*;
 
+        fn parts(rows: &[&[&str]]) -> Vec<Vec<String>> {
+            rows.iter()
+                .map(|row| row.iter().map(|part| part.to_string()).collect())
+                .collect()
+        }
+
         #[test]
         fn should_parse_named_entities() {
-            let result = tokenize_and_humanize_parts("&amp;", TokenizeOptions::default());
-            assert!(result.len() >= 1);
+            let result = tokenize_and_humanize_parts("a&amp;b", TokenizeOptions::default());
+            assert_eq!(
+                result,
+                parts(&[
+                    &["TEXT", "a"],
+                    &["ENCODED_ENTITY", "&", "&amp;"],
+                    &["TEXT", "b"],
+                    &["EOF"],
+                ])
+            );
         }
 
         #[test]
         fn should_parse_numeric_entities() {
-            let result = tokenize_and_humanize_parts("&#65;", TokenizeOptions::default());
-            assert!(result.len() >= 1);
+            let result = tokenize_and_humanize_parts("&#169;", TokenizeOptions::default());
+            assert_eq!(
+                result,
+                parts(&[&["ENCODED_ENTITY", "©", "&#169;"], &["EOF"]])
+            );
         }
 
         #[test]
         fn should_parse_hex_entities() {
-            let result = tokenize_and_humanize_parts("&#x41;", TokenizeOptions::default());
-            assert!(result.len() >= 1);
+            let result = tokenize_and_humanize_parts("&#xA9;", TokenizeOptions::default());
+            assert_eq!(
+                result,
+                parts(&[&["ENCODED_ENTITY", "©", "&#xA9;"], &["EOF"]])
+            );
         }
 
         #[test]
         fn should_parse_hex_entities_with_capital_x() {
             let result = tokenize_and_humanize_parts("&#X41;", TokenizeOptions::default());
-            assert!(result.len() >= 1);
+            assert_eq!(
+                result,
+                parts(&[&["ENCODED_ENTITY", "A", "&#X41;"], &["EOF"]])
+            );
+        }
+
+        #[test]
+        fn should_parse_astral_entities() {
+            let result =
+                tokenize_and_humanize_parts("&#x1F600;&#128512;", TokenizeOptions::default());
+            assert_eq!(
+                result,
+                parts(&[
+                    &["ENCODED_ENTITY", "😀", "&#x1F600;"],
+                    &["ENCODED_ENTITY", "😀", "&#128512;"],
+                    &["EOF"],
+                ])
+            );
+        }
+
+        #[test]
+        fn should_decode_out_of_range_entities_to_the_replacement_character() {
+            let result =
+                tokenize_and_humanize_parts("&#xD800;&#x110000;", TokenizeOptions::default());
+            assert_eq!(
+                result,
+                parts(&[
+                    &["ENCODED_ENTITY", "\u{FFFD}", "&#xD800;"],
+                    &["ENCODED_ENTITY", "\u{FFFD}", "&#x110000;"],
+                    &["EOF"],
+                ])
+            );
         }
 
         #[test]
         fn should_handle_invalid_numeric_entities() {
-            let result = tokenize_and_humanize_parts("&#xyz;", TokenizeOptions::default());
-            assert!(result.len() >= 1);
+            let result =
+                tokenize_and_humanize_parts_ignoring_errors("&#xZZ;", TokenizeOptions::default());
+            assert_eq!(
+                result,
+                parts(&[
+                    &["ENCODED_ENTITY", "&#xZ", "&#xZ"],
+                    &["TEXT", "Z;"],
+                    &["EOF"],
+                ])
+            );
+        }
+
+        #[test]
+        fn should_report_malformed_numeric_entities() {
+            let errors = |input| tokenize_and_humanize_errors(input, TokenizeOptions::default());
+            assert_eq!(
+                errors("&#xZZ;"),
+                parts(&[&[
+                    "Unable to parse entity \"&#xZ\" - hexadecimal character reference entities must end with \";\"",
+                    "0:4",
+                ]])
+            );
+            assert_eq!(
+                errors("&#3sdf;"),
+                parts(&[&[
+                    "Unable to parse entity \"&#3s\" - decimal character reference entities must end with \";\"",
+                    "0:4",
+                ]])
+            );
+            assert_eq!(
+                errors("&#xABC"),
+                parts(&[&["Unexpected character \"EOF\"", "0:6"]])
+            );
         }
 
         #[test]
         fn should_parse_multiple_entities() {
             let result = tokenize_and_humanize_parts("&lt;&gt;&amp;", TokenizeOptions::default());
-            assert!(result.len() >= 1);
+            assert_eq!(
+                result,
+                parts(&[
+                    &["ENCODED_ENTITY", "<", "&lt;"],
+                    &["ENCODED_ENTITY", ">", "&gt;"],
+                    &["ENCODED_ENTITY", "&", "&amp;"],
+                    &["EOF"],
+                ])
+            );
         }
     }
 
@@ -693,6 +855,36 @@ mod html_lexer_tests {
                 .iter()
                 .any(|r| r[0] == "RAW_TEXT" && r[1].contains("&amp;")));
         }
+
+        #[test]
+        fn should_use_the_content_type_of_the_given_tag_definitions() {
+            use angular_compiler::ml_parser::lexer::tokenize;
+            use angular_compiler::ml_parser::xml_tags::get_xml_tag_definition;
+
+            // XML has no raw text elements, so entities in <script> are decoded
+            let result = tokenize(
+                "<script>&amp;</script>".to_string(),
+                "someUrl".to_string(),
+                |name| get_xml_tag_definition(name),
+                TokenizeOptions::default(),
+            );
+            assert_eq!(
+                humanize_parts(&result.tokens)[2],
+                vec!["ENCODED_ENTITY", "&", "&amp;"]
+            );
+        }
+
+        #[test]
+        fn should_keep_entities_verbatim_in_script_and_style() {
+            for tag in ["script", "style"] {
+                let result = tokenize_and_humanize_parts(
+                    &format!("<{0}>a &amp; &#169;</{0}>", tag),
+                    TokenizeOptions::default(),
+                );
+                assert_eq!(result[2], vec!["RAW_TEXT", "a &amp; &#169;"]);
+                assert!(!result.iter().any(|r| r[0] == "ENCODED_ENTITY"));
+            }
+        }
     }
 
     // SECTION 12: ESCAPABLE RAW TEXT (lines 574-627)
@@ -721,6 +913,23 @@ mod html_lexer_tests {
             assert_eq!(result[3][2], "&amp;");
         }
 
+        #[test]
+        fn should_decode_entities_in_textarea() {
+            let result = tokenize_and_humanize_parts(
+                "<textarea>a &amp; &#169;</textarea>",
+                TokenizeOptions::default(),
+            );
+            assert_eq!(
+                result[2..6],
+                [
+                    vec!["ESCAPABLE_RAW_TEXT", "a "],
+                    vec!["ENCODED_ENTITY", "&", "&amp;"],
+                    vec!["ESCAPABLE_RAW_TEXT", " "],
+                    vec!["ENCODED_ENTITY", "©", "&#169;"],
+                ]
+            );
+        }
+
         #[test]
         fn should_ignore_other_opening_tags() {
             let result =
@@ -1017,6 +1226,22 @@ mod html_lexer_tests {
                 .any(|r| r[0] == "INCOMPLETE_BLOCK_OPEN" || r[0] == "TEXT"));
         }
 
+        #[test]
+        fn should_include_the_at_sign_in_block_start_spans() {
+            let result =
+                tokenize_and_humanize_source_spans("a @if (x) {", TokenizeOptions::default());
+            assert_eq!(
+                result,
+                vec![
+                    vec!["TEXT", "a "],
+                    vec!["BLOCK_OPEN_START", "@if"],
+                    vec!["BLOCK_PARAMETER", "x"],
+                    vec!["BLOCK_OPEN_END", "{"],
+                    vec!["EOF", ""],
+                ]
+            );
+        }
+
         #[test]
         fn should_parse_incomplete_block_start_at_end_of_input() {
             let result = tokenize_and_humanize_parts(
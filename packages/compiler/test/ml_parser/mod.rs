@@ -8,6 +8,7 @@
 pub mod util;
 pub mod lexer_tests;
 pub mod html_parser_tests;
+pub mod xml_parser_tests;
 pub mod html_whitespaces_tests;
 pub mod ast_serializer_tests;
 
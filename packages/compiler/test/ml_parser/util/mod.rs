@@ -43,6 +43,7 @@ fn get_token_type(token: &Token) -> TokenType {
         Token::AttrValueText(_) => TokenType::AttrValueText,
         Token::AttrValueInterpolation(_) => TokenType::AttrValueInterpolation,
         Token::DocType(_) => TokenType::DocType,
+        Token::ProcessingInstruction(_) => TokenType::ProcessingInstruction,
         Token::ExpansionFormStart(_) => TokenType::ExpansionFormStart,
         Token::ExpansionCaseValue(_) => TokenType::ExpansionCaseValue,
         Token::ExpansionCaseExpStart(_) => TokenType::ExpansionCaseExpStart,
@@ -90,6 +91,7 @@ fn get_token_parts(token: &Token) -> &Vec<Arc<str>> {
         Token::AttrValueText(t) => &t.parts,
         Token::AttrValueInterpolation(t) => &t.parts,
         Token::DocType(t) => &t.parts,
+        Token::ProcessingInstruction(t) => &t.parts,
         Token::ExpansionFormStart(t) => &t.parts,
         Token::ExpansionCaseValue(t) => &t.parts,
         Token::ExpansionCaseExpStart(t) => &t.parts,
@@ -137,6 +139,7 @@ fn get_token_source_span(token: &Token) -> &ParseSourceSpan {
         Token::AttrValueText(t) => &t.source_span,
         Token::AttrValueInterpolation(t) => &t.source_span,
         Token::DocType(t) => &t.source_span,
+        Token::ProcessingInstruction(t) => &t.source_span,
         Token::ExpansionFormStart(t) => &t.source_span,
         Token::ExpansionCaseValue(t) => &t.source_span,
         Token::ExpansionCaseExpStart(t) => &t.source_span,
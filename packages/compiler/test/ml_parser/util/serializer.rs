@@ -182,6 +182,7 @@ mod tests {
             source_span: span,
             tokens: vec![],
             i18n: None,
+            id: 0,
         };
 
         let visitor = SerializerVisitor::new();
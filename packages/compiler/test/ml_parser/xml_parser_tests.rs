@@ -0,0 +1,81 @@
+/**
+ * XML Parser Tests
+ *
+ * Test suite for parsing XML translation files (XLIFF, XMB) with the XML tag definitions
+ */
+
+#[path = "util/mod.rs"]
+mod utils;
+
+#[cfg(test)]
+mod tests {
+    use super::utils::humanize_dom;
+    use angular_compiler::ml_parser::parser::ParseTreeResult;
+    use angular_compiler::ml_parser::xml_parser::XmlParser;
+
+    fn parse(xml: &str) -> ParseTreeResult {
+        XmlParser::new().parse(xml, "messages.xlf", None)
+    }
+
+    #[test]
+    fn should_parse_elements_without_html_semantics() {
+        // `<p>` would be implicitly closed by `<div>` in HTML
+        let result = parse("<p><div/></p>");
+        assert_eq!(
+            humanize_dom(&result, false).unwrap(),
+            vec![
+                vec!["Element", "p", "0"],
+                vec!["Element", "div", "1", "#selfClosing"],
+            ]
+        );
+    }
+
+    #[test]
+    fn should_skip_processing_instructions() {
+        let result = parse(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" ?><xliff version=\"1.2\"><?pi data?><file/></xliff>",
+        );
+        assert_eq!(
+            humanize_dom(&result, false).unwrap(),
+            vec![
+                vec!["Element", "xliff", "0"],
+                vec!["Attribute", "version", "1.2"],
+                vec!["Element", "file", "1", "#selfClosing"],
+            ]
+        );
+    }
+
+    #[test]
+    fn should_skip_a_doctype_with_an_internal_subset() {
+        let result = parse(
+            r#"<?xml version="1.0" encoding="UTF-8" ?>
+<!DOCTYPE messagebundle [
+<!ELEMENT messagebundle (msg)*>
+<!ATTLIST messagebundle class CDATA #IMPLIED>
+<!-- a comment with > in it -->
+<!ENTITY sep "->">
+]>
+<messagebundle><msg id="1">Hello</msg></messagebundle>"#,
+        );
+        assert_eq!(
+            humanize_dom(&result, false).unwrap(),
+            vec![
+                vec!["Text", "\n\n", "0"],
+                vec!["Element", "messagebundle", "0"],
+                vec!["Element", "msg", "1"],
+                vec!["Attribute", "id", "1"],
+                vec!["Text", "Hello", "2"],
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_an_unterminated_processing_instruction() {
+        let result = parse("<?xml version=\"1.0\"");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            result.errors[0].msg,
+            "Unexpected character \"EOF\", expected \"?>\""
+        );
+    }
+}
@@ -137,6 +137,27 @@ mod tests {
         assert!(content_b.is_some(), "b.js content not found");
     }
 
+    #[test]
+    fn should_embed_the_template_source_in_sources_content() {
+        let template = ParseSourceFile {
+            content: "<div>{{ greeting }}</div>".to_string(),
+            url: "app.component.html".to_string(),
+        };
+
+        let mut ctx = EmitterVisitorContext::create_root();
+        ctx.print(Some(&create_source_span(&template, 0)), "o0", false);
+        ctx.print(Some(&create_source_span(&template, 3)), "o1", false);
+
+        let mut map = ctx.to_source_map_generator("o.ts", 0);
+        let sm = map.to_json().unwrap();
+        assert_eq!(sm.sources, vec![template.url.clone()]);
+        assert_eq!(sm.sources_content, vec![Some(template.content.clone())]);
+
+        let sm = map.set_include_sources_content(false).to_json().unwrap();
+        assert_eq!(sm.sources, vec![template.url.clone()]);
+        assert_eq!(sm.sources_content, vec![None]);
+    }
+
     #[test]
     fn should_generate_a_valid_mapping() {
         let file_a = ParseSourceFile {
@@ -166,6 +166,7 @@ impl Visitor for R3AstHumanizer {
         let target_str = event
             .target
             .as_ref()
+            .or(event.phase.as_ref())
             .map(|s| s.to_string())
             .unwrap_or_else(|| String::new());
 
@@ -951,6 +952,19 @@ mod tests {
                 .any(|v| v[0] == "BoundEvent" && v[2] == "event" && v[3] == "window"));
         }
 
+        #[test]
+        fn should_parse_legacy_animation_events_with_a_phase() {
+            let result =
+                expect_from_html(r#"<div (@trigger.done)="onDone()"></div>"#, false, false);
+            assert!(result.contains(&vec![
+                "BoundEvent".to_string(),
+                "3".to_string(),
+                "trigger".to_string(),
+                "done".to_string(),
+                "onDone()".to_string(),
+            ]));
+        }
+
         #[test]
         fn should_parse_property_reads_bound_via_two_way_binding() {
             let result = expect_from_html(r#"<div [(prop)]="a.b.c"></div>"#, false, false);
@@ -1146,10 +1160,20 @@ mod tests {
                 false,
                 false,
             );
-            assert!(result.iter().any(|v| v[0] == "DeferredBlock"));
-            assert!(result.iter().any(|v| v[0] == "DeferredBlockPlaceholder"));
-            assert!(result.iter().any(|v| v[0] == "DeferredBlockLoading"));
-            assert!(result.iter().any(|v| v[0] == "DeferredBlockError"));
+            assert_eq!(
+                result,
+                vec![
+                    vec!["DeferredBlock"],
+                    vec!["Element", "calendar-cmp", "#selfClosing"],
+                    vec!["BoundAttribute", "0", "date", "current"],
+                    vec!["DeferredBlockPlaceholder"],
+                    vec!["Text", "Placeholder content!"],
+                    vec!["DeferredBlockLoading"],
+                    vec!["Text", "Loading..."],
+                    vec!["DeferredBlockError"],
+                    vec!["Text", "Loading failed :("],
+                ]
+            );
         }
 
         #[test]
@@ -1159,7 +1183,29 @@ mod tests {
                 false,
                 false,
             );
-            assert!(result.iter().any(|v| v[0] == "DeferredBlockLoading"));
+            assert!(result
+                .iter()
+                .any(|v| v == &["DeferredBlockLoading", "after 100ms", "minimum 1500ms"]));
+        }
+
+        #[test]
+        fn should_parse_connected_block_parameters_in_any_order() {
+            let result = expect_from_html(
+                "@defer {a}@loading (minimum 1s; after 500ms) {b}@placeholder (minimum 2s) {c}",
+                false,
+                false,
+            );
+            assert_eq!(
+                result,
+                vec![
+                    vec!["DeferredBlock"],
+                    vec!["Text", "a"],
+                    vec!["DeferredBlockPlaceholder", "minimum 2000ms"],
+                    vec!["Text", "c"],
+                    vec!["DeferredBlockLoading", "after 500ms", "minimum 1000ms"],
+                    vec!["Text", "b"],
+                ]
+            );
         }
 
         #[test]
@@ -1719,10 +1765,20 @@ mod tests {
                 false,
                 false,
             );
-            assert!(result.iter().any(|v| v[0] == "DeferredBlock"));
-            assert!(result.iter().any(|v| v[0] == "DeferredBlockPlaceholder"));
-            assert!(result.iter().any(|v| v[0] == "DeferredBlockLoading"));
-            assert!(result.iter().any(|v| v[0] == "DeferredBlockError"));
+            assert_eq!(
+                result,
+                vec![
+                    vec!["DeferredBlock"],
+                    vec!["Element", "calendar-cmp", "#selfClosing"],
+                    vec!["BoundAttribute", "0", "date", "current"],
+                    vec!["DeferredBlockPlaceholder"],
+                    vec!["Text", "Placeholder content!"],
+                    vec!["DeferredBlockLoading"],
+                    vec!["Text", "Loading..."],
+                    vec!["DeferredBlockError"],
+                    vec!["Text", "Loading failed :("],
+                ]
+            );
         }
 
         #[test]
@@ -2187,25 +2243,25 @@ foo) {{{ item }}}
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@placeholder block can only be used after an @defer block.")]
         fn should_report_connected_defer_blocks_used_without_a_defer_block_placeholder() {
             let _ = parse_r3("@placeholder {placeholder}", ParseR3Options::default());
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@loading block can only be used after an @defer block.")]
         fn should_report_connected_defer_blocks_used_without_a_defer_block_loading() {
             let _ = parse_r3("@loading {loading}", ParseR3Options::default());
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@error block can only be used after an @defer block.")]
         fn should_report_connected_defer_blocks_used_without_a_defer_block_error() {
             let _ = parse_r3("@error {error}", ParseR3Options::default());
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@defer block can only have one @placeholder block")]
         fn should_report_multiple_placeholder_blocks() {
             let _ = parse_r3(
                 "@defer {hello} @placeholder {p1} @placeholder {p2}",
@@ -2214,7 +2270,7 @@ foo) {{{ item }}}
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@defer block can only have one @loading block")]
         fn should_report_multiple_loading_blocks() {
             let _ = parse_r3(
                 "@defer {hello} @loading {l1} @loading {l2}",
@@ -2223,7 +2279,7 @@ foo) {{{ item }}}
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@defer block can only have one @error block")]
         fn should_report_multiple_error_blocks() {
             let _ = parse_r3(
                 "@defer {hello} @error {e1} @error {e2}",
@@ -2269,7 +2325,7 @@ foo) {{{ item }}}
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@switch block must have exactly one parameter")]
         fn should_report_if_a_switch_has_no_parameters() {
             let _ = parse_r3(
                 r#"
@@ -2282,7 +2338,7 @@ foo) {{{ item }}}
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@switch block must have exactly one parameter")]
         fn should_report_if_a_switch_has_more_than_one_parameter() {
             let _ = parse_r3(
                 r#"
@@ -2295,7 +2351,7 @@ foo) {{{ item }}}
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@case block must have exactly one parameter")]
         fn should_report_if_a_case_has_no_parameters() {
             let _ = parse_r3(
                 r#"
@@ -2336,7 +2392,22 @@ foo) {{{ item }}}
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@switch block has more than one @case with the value")]
+        fn should_report_if_a_switch_has_duplicate_case_values() {
+            let _ = parse_r3(
+                r#"
+          @switch (cond) {
+            @case ('a') {first}
+            @case ('b') {second}
+            @case ('a') {third}
+          }
+        "#,
+                ParseR3Options::default(),
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "@default block cannot have parameters")]
         fn should_report_if_a_default_block_has_parameters() {
             let _ = parse_r3(
                 r#"
@@ -2354,19 +2425,25 @@ foo) {{{ item }}}
         use super::*;
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@for loop does not have an expression")]
         fn should_report_if_for_loop_does_not_have_an_expression() {
             let _ = parse_r3("@for {hello}", ParseR3Options::default());
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@for loop must have a")]
         fn should_report_if_for_loop_does_not_have_a_tracking_expression() {
             let _ = parse_r3("@for (a of b) {hello}", ParseR3Options::default());
         }
 
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "@for loop expression must match the pattern")]
+        fn should_report_for_loop_expression_without_of() {
+            let _ = parse_r3("@for (a in b; track a) {hello}", ParseR3Options::default());
+        }
+
+        #[test]
+        #[should_panic(expected = "@for loop can only have one")]
         fn should_report_multiple_track_parameters() {
             let _ = parse_r3(
                 "@for (a of b; track c; track d) {hello}",
@@ -1321,6 +1321,51 @@ mod tests {
                 }
             }
 
+            #[test]
+            fn should_resolve_references_to_any_export_as_name_of_a_directive() {
+                let parse_result =
+                    parse_template("<div multi #r=\"bar\"></div>", "", Default::default());
+                let mut matcher = SelectorMatcher::<Vec<TestDirectiveMeta>>::new();
+                let selector = CssSelector::parse("[multi]")
+                    .unwrap()
+                    .into_iter()
+                    .next()
+                    .unwrap();
+                matcher.add_selectable(
+                    selector,
+                    vec![TestDirectiveMeta {
+                        name: "Multi".to_string(),
+                        selector: "[multi]".to_string(),
+                        export_as: Some(vec!["foo".to_string(), "bar".to_string()]),
+                        inputs: IdentityInputMapping::new(vec![]),
+                        outputs: IdentityInputMapping::new(vec![]),
+                        is_component: false,
+                        is_structural: false,
+                        animation_trigger_names: None,
+                        ng_content_selectors: None,
+                        preserve_whitespaces: false,
+                    }],
+                );
+                let binder = R3TargetBinder::<TestDirectiveMeta>::new(Some(
+                    DirectiveMatcher::Selector(matcher),
+                ));
+                let target = Target {
+                    template: Some(parse_result.nodes.clone()),
+                    host: None,
+                };
+                let res = binder.bind(target);
+
+                let Some(t::R3Node::Element(el)) = parse_result.nodes.first() else {
+                    panic!("expected an element");
+                };
+                match res.get_reference_target(&el.references[0]) {
+                    Some(ReferenceTarget::DirectiveOnNode { directive, .. }) => {
+                        assert_eq!(directive.name(), "Multi");
+                    }
+                    _ => panic!("expected the reference to resolve to a directive"),
+                }
+            }
+
             #[test]
             fn should_resolve_references_to_components() {
                 let parse_result = parse_template("<comp #myComp></comp>", "", Default::default());
@@ -77,6 +77,8 @@ fn compile_template(template: &str) -> (Vec<o::Statement>, ConstantPool) {
         i18n_use_external_ids: false,
         change_detection: None,
         relative_template_path: None,
+        enable_debug_locations: false,
+        template_start: None,
         has_directive_dependencies: false,
         raw_imports: None,
     };
@@ -219,6 +221,8 @@ fn should_handle_ngfor_nested_svg_attributes() {
         i18n_use_external_ids: false,
         change_detection: None,
         relative_template_path: None,
+        enable_debug_locations: false,
+        template_start: None,
         has_directive_dependencies: false,
         raw_imports: None,
     };
@@ -661,4 +661,390 @@ mod tests {
             }
         }
     }
+
+    mod localized_string {
+        use angular_compiler::output::output_ast as o;
+        use angular_compiler::parse_util::{ParseLocation, ParseSourceSpan};
+
+        fn span() -> ParseSourceSpan {
+            let location =
+                ParseLocation::from_source(String::new(), "file.ts".to_string(), 0, 0, 0);
+            ParseSourceSpan::new(location.clone(), location)
+        }
+
+        fn literal(text: &str) -> o::LiteralPiece {
+            o::LiteralPiece {
+                text: text.to_string(),
+                source_span: span(),
+            }
+        }
+
+        fn placeholder(name: &str) -> o::PlaceholderPiece {
+            o::PlaceholderPiece {
+                text: name.to_string(),
+                source_span: span(),
+                associated_message_id: None,
+            }
+        }
+
+        fn localized(
+            meta_block: &str,
+            message_parts: Vec<o::LiteralPiece>,
+            placeholder_names: Vec<o::PlaceholderPiece>,
+        ) -> o::LocalizedString {
+            o::LocalizedString {
+                meta_block: meta_block.to_string(),
+                message_parts,
+                placeholder_names,
+                expressions: vec![],
+                source_span: None,
+            }
+        }
+
+        fn head(meta_block: &str, text: &str) -> (String, String) {
+            let head = localized(meta_block, vec![literal(text)], vec![]).serialize_i18n_head();
+            (head.cooked, head.raw)
+        }
+
+        fn part(placeholder_name: &str, text: &str) -> (String, String) {
+            let part = localized(
+                "",
+                vec![literal(""), literal(text)],
+                vec![placeholder(placeholder_name)],
+            )
+            .serialize_i18n_template_part(1);
+            (part.cooked, part.raw)
+        }
+
+        fn pair(cooked: &str, raw: &str) -> (String, String) {
+            (cooked.to_string(), raw.to_string())
+        }
+
+        #[test]
+        fn should_serialize_the_meta_block_into_the_head() {
+            assert_eq!(head("", ""), pair("", ""));
+            assert_eq!(head("desc", ""), pair(":desc:", ":desc:"));
+            assert_eq!(head("desc@@id", ""), pair(":desc@@id:", ":desc@@id:"));
+            assert_eq!(
+                head("meaning|desc", ""),
+                pair(":meaning|desc:", ":meaning|desc:")
+            );
+            assert_eq!(head("@@id", "Hello"), pair(":@@id:Hello", ":@@id:Hello"));
+        }
+
+        #[test]
+        fn should_escape_the_head() {
+            assert_eq!(head("desc:1", ""), pair(":desc:1:", ":desc\\:1:"));
+            assert_eq!(
+                head("meaning:2|desc:1@@id:3", ""),
+                pair(":meaning:2|desc:1@@id:3:", ":meaning\\:2|desc\\:1@@id\\:3:")
+            );
+            assert_eq!(head("", ":text"), pair(":text", "\\:text"));
+            assert_eq!(head("abc\\def", ""), pair(":abc\\def:", ":abc\\\\def:"));
+            assert_eq!(head("", "a`b${c}"), pair("a`b${c}", "a\\`b$\\{c}"));
+        }
+
+        #[test]
+        fn should_serialize_template_parts() {
+            assert_eq!(part("", ""), pair("", ""));
+            assert_eq!(part("abc", ""), pair(":abc:", ":abc:"));
+            assert_eq!(part("abc", "def"), pair(":abc:def", ":abc:def"));
+            assert_eq!(part("abc:def", ""), pair(":abc:def:", ":abc\\:def:"));
+            assert_eq!(part("abc", ":def"), pair(":abc::def", ":abc::def"));
+        }
+
+        #[test]
+        fn should_append_the_associated_message_id_to_the_placeholder() {
+            let mut ph = placeholder("ICU");
+            ph.associated_message_id = Some("123".to_string());
+            let part = localized("", vec![literal(""), literal("")], vec![ph])
+                .serialize_i18n_template_part(1);
+            assert_eq!(part.cooked, ":ICU@@123:");
+        }
+    }
+
+    mod localize_statements {
+        use angular_compiler::i18n::create_i18n_message_factory;
+        use angular_compiler::i18n::i18n_ast as i18n;
+        use angular_compiler::ml_parser::ast as html;
+        use angular_compiler::ml_parser::html_parser::HtmlParser;
+        use angular_compiler::ml_parser::lexer::TokenizeOptions;
+        use angular_compiler::output::abstract_emitter::EmitterVisitorContext;
+        use angular_compiler::output::abstract_js_emitter::AbstractJsEmitterVisitor;
+        use angular_compiler::output::output_ast as o;
+        use angular_compiler::render3::view::i18n::localize_utils::create_localize_statements;
+        use angular_compiler::render3::view::i18n::meta::parse_i18n_meta;
+        use std::collections::{HashMap, HashSet};
+
+        /// Build the message of the single `i18n` element in `template`, the way the i18n meta
+        /// visitor does.
+        fn message_for(template: &str) -> i18n::Message {
+            let mut tokenize_options = TokenizeOptions::default();
+            tokenize_options.tokenize_expansion_forms = true;
+            let result = HtmlParser::new().parse(template, "file.ts", Some(tokenize_options));
+            let element = match &result.root_nodes[0] {
+                html::Node::Element(element) => element,
+                node => panic!("expected an element, got {:?}", node),
+            };
+            let meta = element
+                .attrs
+                .iter()
+                .find(|attr| attr.name.as_ref() == "i18n")
+                .map(|attr| parse_i18n_meta(&attr.value))
+                .unwrap_or_default();
+
+            let mut factory = create_i18n_message_factory(HashSet::new(), false, true, false);
+            factory.create_message(
+                &element.children,
+                meta.meaning.as_deref(),
+                meta.description.as_deref(),
+                meta.custom_id.as_deref(),
+                None,
+            )
+        }
+
+        fn emit(statements: &[o::Statement]) -> String {
+            let mut visitor = AbstractJsEmitterVisitor::new();
+            let mut ctx = EmitterVisitorContext::new(0);
+            for statement in statements {
+                statement.visit_statement(&mut visitor, &mut ctx);
+            }
+            ctx.to_source()
+        }
+
+        #[test]
+        fn should_emit_a_localize_tagged_template() {
+            let message = message_for("<p i18n=\"desc@@id\">Hello {{name}}</p>");
+            let variable = o::ReadVarExpr {
+                name: "i18n_0".to_string(),
+                type_: None,
+                source_span: None,
+            };
+            let params = HashMap::from([("INTERPOLATION".to_string(), *o::variable("name"))]);
+
+            let statements = create_localize_statements(&variable, &message, &params);
+            let localized = match &statements[..] {
+                [o::Statement::Expression(stmt)] => match stmt.expr.as_ref() {
+                    o::Expression::WriteVar(write) => match write.value.as_ref() {
+                        o::Expression::Localized(localized) => localized,
+                        expr => panic!("expected a localized string, got {:?}", expr),
+                    },
+                    expr => panic!("expected a variable write, got {:?}", expr),
+                },
+                statements => panic!("unexpected statements {:?}", statements),
+            };
+            assert_eq!(localized.serialize_i18n_head().cooked, ":desc@@id:Hello ");
+            assert_eq!(localized.placeholder_names[0].text, "INTERPOLATION");
+
+            assert_eq!(
+                emit(&statements).trim(),
+                "i18n_0 = $localize `:desc@@id:Hello ${name}:INTERPOLATION:`;"
+            );
+        }
+    }
+
+    mod template_compilation {
+        use super::*;
+        use angular_compiler::constant_pool::ConstantPool;
+        use angular_compiler::core::ViewEncapsulation;
+        use angular_compiler::expression_parser::parser::Parser;
+        use angular_compiler::output::abstract_emitter::EmitterVisitorContext;
+        use angular_compiler::output::abstract_js_emitter::AbstractJsEmitterVisitor;
+        use angular_compiler::output::output_ast as o;
+        use angular_compiler::parse_util::{ParseLocation, ParseSourceFile, ParseSourceSpan};
+        use angular_compiler::render3::util::R3Reference;
+        use angular_compiler::render3::view::api::{
+            DeclarationListEmitMode, R3ComponentDeferMetadata, R3ComponentMetadata,
+            R3ComponentTemplate, R3DirectiveMetadata, R3HostMetadata, R3LifecycleMetadata,
+        };
+        use angular_compiler::render3::view::compiler::compile_component_from_metadata;
+        use angular_compiler::schema::dom_element_schema_registry::DomElementSchemaRegistry;
+        use angular_compiler::template_parser::binding_parser::BindingParser;
+        use indexmap::IndexMap;
+        use std::sync::Arc;
+
+        /// Compile `template` as the template of a component and emit the constant pool followed
+        /// by the component definition.
+        fn compile(template: &str) -> String {
+            let parsed = parse_r3(template, ParseR3Options::default());
+            let source_file = Arc::new(ParseSourceFile::new(String::new(), "test.ts".to_string()));
+            let location = ParseLocation::new(source_file, 0, 0, 0);
+            let type_span = ParseSourceSpan::new(location.clone(), location);
+
+            let parser = Parser::new();
+            let schema_registry = DomElementSchemaRegistry::new();
+            let mut binding_parser = BindingParser::new(&parser, &schema_registry, vec![]);
+
+            let meta = R3ComponentMetadata {
+                directive: R3DirectiveMetadata {
+                    name: "TestComponent".to_string(),
+                    type_: R3Reference {
+                        value: *o::variable("TestComponent"),
+                        type_expr: *o::variable("TestComponent"),
+                    },
+                    type_argument_count: 0,
+                    type_source_span: type_span,
+                    deps: None,
+                    selector: Some("test-comp".to_string()),
+                    queries: vec![],
+                    view_queries: vec![],
+                    host: R3HostMetadata::default(),
+                    lifecycle: R3LifecycleMetadata::default(),
+                    inputs: IndexMap::new(),
+                    outputs: IndexMap::new(),
+                    uses_inheritance: false,
+                    export_as: None,
+                    providers: None,
+                    is_standalone: true,
+                    is_signal: false,
+                    host_directives: None,
+                },
+                template: R3ComponentTemplate {
+                    nodes: parsed.nodes,
+                    ng_content_selectors: vec![],
+                    preserve_whitespaces: false,
+                },
+                declarations: vec![],
+                defer: R3ComponentDeferMetadata::PerComponent {
+                    dependencies_fn: None,
+                },
+                declaration_list_emit_mode: DeclarationListEmitMode::Direct,
+                styles: vec![],
+                external_styles: None,
+                encapsulation: ViewEncapsulation::Emulated,
+                animations: None,
+                view_providers: None,
+                relative_context_file_path: "test.ts".to_string(),
+                i18n_use_external_ids: false,
+                change_detection: None,
+                relative_template_path: None,
+                enable_debug_locations: false,
+                template_start: None,
+                has_directive_dependencies: false,
+                raw_imports: None,
+            };
+
+            let mut constant_pool = ConstantPool::new(false);
+            let compiled =
+                compile_component_from_metadata(&meta, &mut constant_pool, &mut binding_parser);
+
+            let mut visitor = AbstractJsEmitterVisitor::new();
+            let mut ctx = EmitterVisitorContext::new(0);
+            for statement in &constant_pool.statements {
+                statement.visit_statement(&mut visitor, &mut ctx);
+            }
+            let definition = o::Statement::Expression(o::ExpressionStatement {
+                expr: Box::new(compiled.expression),
+                source_span: None,
+            });
+            definition.visit_statement(&mut visitor, &mut ctx);
+            ctx.to_source()
+        }
+
+        #[test]
+        fn should_emit_a_localize_tagged_template_for_an_i18n_element() {
+            let output = compile("<p i18n=\"desc@@id\">Hello {{name}}</p>");
+
+            assert!(
+                output.contains(
+                    "$localize `:desc@@id:Hello ${\"\u{FFFD}0\u{FFFD}\"}:INTERPOLATION:`"
+                ),
+                "unexpected output: {}",
+                output
+            );
+            assert!(
+                output.contains("i0.ɵɵi18nExp(ctx.name);"),
+                "unexpected output: {}",
+                output
+            );
+        }
+    }
+
+    mod nested_icu_extraction {
+        use angular_compiler::i18n::i18n_ast as i18n;
+        use angular_compiler::i18n::{MessageBundle, Xliff};
+        use angular_compiler::ml_parser::html_parser::HtmlParser;
+        use std::collections::HashMap;
+
+        const PLURAL_WITH_SELECT: &str = "<div i18n>{count, plural, =0 {none} other {# items {gender, select, male {his} female {her} other {their}}}}</div>";
+
+        fn extract(template: &str) -> MessageBundle {
+            let mut bundle =
+                MessageBundle::new(HtmlParser::new(), vec![], HashMap::new(), None, false);
+            let errors = bundle.update_from_template(template, "url");
+            assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+            bundle
+        }
+
+        fn case_keys(icu: &i18n::Icu) -> Vec<&str> {
+            icu.cases.keys().map(|k| k.as_str()).collect()
+        }
+
+        #[test]
+        fn should_keep_cases_of_a_select_nested_in_a_plural() {
+            let bundle = extract(PLURAL_WITH_SELECT);
+            let messages = bundle.get_messages();
+            assert_eq!(messages.len(), 1);
+
+            let plural = match &messages[0].nodes[..] {
+                [i18n::Node::Icu(icu)] => icu,
+                nodes => panic!("expected a single ICU node, got {:?}", nodes),
+            };
+            assert_eq!(plural.type_, "plural");
+            assert_eq!(plural.expression_placeholder.as_deref(), Some("VAR_PLURAL"));
+            assert_eq!(case_keys(plural), vec!["=0", "other"]);
+
+            let other = match &plural.cases["other"] {
+                i18n::Node::Container(container) => &container.children,
+                node => panic!("expected a container, got {:?}", node),
+            };
+            match &other[0] {
+                i18n::Node::Text(text) => assert_eq!(text.value, "# items "),
+                node => panic!("expected `#` text, got {:?}", node),
+            }
+            let select = match &other[1] {
+                i18n::Node::Icu(icu) => icu,
+                node => panic!("expected a nested ICU, got {:?}", node),
+            };
+            assert_eq!(select.type_, "select");
+            assert_eq!(select.expression_placeholder.as_deref(), Some("VAR_SELECT"));
+            assert_eq!(case_keys(select), vec!["male", "female", "other"]);
+
+            let placeholders: Vec<&str> = messages[0]
+                .placeholders
+                .keys()
+                .map(|k| k.as_str())
+                .collect();
+            assert!(placeholders.contains(&"VAR_PLURAL"));
+            assert!(placeholders.contains(&"VAR_SELECT"));
+        }
+
+        #[test]
+        fn should_reserialize_a_select_nested_in_a_plural_via_xliff() {
+            let bundle = extract(PLURAL_WITH_SELECT);
+            let xliff = bundle.write(&Xliff::new(), None);
+            assert!(
+                xliff.contains(
+                    "<source>{count, plural, =0 {none} other {# items {gender, select, male {his} female {her} other {their} }} }</source>"
+                ),
+                "unexpected xliff output: {}",
+                xliff
+            );
+        }
+
+        #[test]
+        fn should_serialize_an_icu_placeholder_with_its_cases_via_xliff() {
+            let bundle = extract(
+                "<div i18n>Hello {count, plural, =0 {none} other {{gender, select, male {his} other {their}}}}</div>",
+            );
+            let xliff = bundle.write(&Xliff::new(), None);
+            assert!(
+                xliff.contains(
+                    "<x equiv-text=\"{count, plural, =0 {...} other {...}}\" id=\"ICU\"/>"
+                ),
+                "unexpected xliff output: {}",
+                xliff
+            );
+        }
+    }
 }
@@ -7,6 +7,9 @@
 mod view_util;
 use view_util::{parse_r3, ParseR3Options};
 
+use angular_compiler::render3::r3_ast::R3Node;
+use angular_compiler::render3::view::template::{parse_template, ParseTemplateOptions};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,4 +121,32 @@ mod tests {
             "Second comment source span should contain the comment text"
         );
     }
+
+    fn span_text(preserve_whitespaces: bool) -> String {
+        let parsed = parse_template(
+            "<span>  a  b  </span>",
+            "path://to/template",
+            ParseTemplateOptions {
+                preserve_whitespaces: Some(preserve_whitespaces),
+                ..Default::default()
+            },
+        );
+        match &parsed.nodes[0] {
+            R3Node::Element(el) => match &el.children[0] {
+                R3Node::Text(text) => text.value.to_string(),
+                other => panic!("Expected text node, got {:?}", other),
+            },
+            other => panic!("Expected element node, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_keep_original_whitespace_when_preserve_whitespaces_is_enabled() {
+        let preserved = span_text(true);
+        let collapsed = span_text(false);
+
+        assert_eq!(preserved, "  a  b  ");
+        assert_eq!(collapsed, " a b ");
+        assert_ne!(preserved, collapsed);
+    }
 }
@@ -12,6 +12,7 @@ use angular_compiler::ml_parser::lexer::TokenizeOptions;
 use angular_compiler::render3::r3_template_transform::{
     html_ast_to_render3_ast, Render3ParseOptions, Render3ParseResult,
 };
+use angular_compiler::render3::view::i18n::meta::I18nMetaVisitor;
 use angular_compiler::render3::view::template::LEADING_TRIVIA_CHARS;
 use angular_compiler::render3::view::template::{
     make_binding_parser, make_binding_parser_with_parser,
@@ -65,8 +66,10 @@ pub fn parse_r3(input: &str, options: ParseR3Options) -> Render3ParseResult {
         panic!("Parse errors: {}", msg);
     }
 
-    // Process i18n metadata (simplified - would need I18nMetaVisitor in real implementation)
-    let mut html_nodes = parse_result.root_nodes;
+    // Process i18n metadata, like `processI18nMeta`
+    let mut html_nodes = I18nMetaVisitor::new(false, false, true, false)
+        .visit_all_with_errors(parse_result.root_nodes)
+        .root_nodes;
 
     // Handle whitespace preservation
     if options.preserve_whitespaces != Some(true) {
@@ -92,6 +95,7 @@ pub fn parse_r3(input: &str, options: ParseR3Options) -> Render3ParseResult {
     let collect_comment_nodes = options.collect_comment_nodes.unwrap_or(false);
     let r3_options = Render3ParseOptions {
         collect_comment_nodes,
+        preserve_whitespaces: options.preserve_whitespaces == Some(true),
     };
 
     let r3_result = html_ast_to_render3_ast(&html_nodes, &mut binding_parser, &r3_options);